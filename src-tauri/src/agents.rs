@@ -0,0 +1,210 @@
+//! Agent presentation metadata
+//!
+//! Display names, colors, icons, and sample voice lines live in the `agents`
+//! table rather than frontend constants, so renamed voices and custom agents
+//! render the same everywhere. Built-ins are seeded on startup without
+//! overwriting edits; `get_agent_manifest` serves the table to the UI. Built-in
+//! names the user hasn't changed follow the active locale.
+
+use crate::db::{self, AgentPresentation};
+use crate::i18n;
+use chrono::Utc;
+use std::error::Error;
+
+struct BuiltinAgent {
+    id: &'static str,
+    name: &'static str,
+    disco_name: Option<&'static str>,
+    color: &'static str,
+    disco_color: Option<&'static str>,
+    icon: &'static str,
+    disco_icon: Option<&'static str>,
+    description: &'static str,
+    disco_description: Option<&'static str>,
+    voice_samples: &'static [&'static str],
+}
+
+const BUILTIN_AGENTS: &[BuiltinAgent] = &[
+    BuiltinAgent {
+        id: "psyche",
+        name: "Puff",
+        disco_name: Some("Storm"),
+        color: "#A78BCA",
+        disco_color: Some("#C084FC"),
+        icon: "psyche-incarnate",
+        disco_icon: Some("disco-puff"),
+        description: "Self-awareness, emotional depth, motivations, the \"why\" behind the \"what\"",
+        disco_description: Some("Deep intuition, emotional truth, the part that knows what you're avoiding"),
+        voice_samples: &[
+            "What's underneath that, do you think?",
+            "That sounds heavier than you're letting on.",
+        ],
+    },
+    BuiltinAgent {
+        id: "logic",
+        name: "Dot",
+        disco_name: Some("Spin"),
+        color: "#6BB8C9",
+        disco_color: Some("#22D3EE"),
+        icon: "logic-incarnate",
+        disco_icon: Some("disco-dot"),
+        description: "Analytical thinking, structured reasoning, evidence-based conclusions",
+        disco_description: Some("Cold analysis, pattern recognition, the part that sees contradictions"),
+        voice_samples: &[
+            "Let's separate what you know from what you're assuming.",
+            "Two options here -- and one of them costs a lot more.",
+        ],
+    },
+    BuiltinAgent {
+        id: "instinct",
+        name: "Snap",
+        disco_name: Some("Swarm"),
+        color: "#E07A5F",
+        disco_color: Some("#EF4444"),
+        icon: "instinct-incarnate",
+        disco_icon: Some("disco-snap"),
+        description: "Gut feelings, intuition, emotional intelligence, pattern recognition",
+        disco_description: Some("Raw impulse, unfiltered instinct, the part that moves before thinking"),
+        voice_samples: &[
+            "You already know the answer. Go.",
+            "Something's off here -- trust that.",
+        ],
+    },
+    BuiltinAgent {
+        id: "governor",
+        name: "Governor",
+        disco_name: None,
+        color: "#94A3B8",
+        disco_color: None,
+        icon: "governor",
+        disco_icon: None,
+        description: "System administrator and guide",
+        disco_description: None,
+        voice_samples: &["Here's where the three of them landed."],
+    },
+];
+
+/// Seed the built-in agents (existing rows, including user edits, are kept)
+pub fn seed_builtin_agents() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let now = Utc::now().to_rfc3339();
+    for (index, agent) in BUILTIN_AGENTS.iter().enumerate() {
+        db::insert_agent_if_missing(&AgentPresentation {
+            id: agent.id.to_string(),
+            display_name: agent.name.to_string(),
+            disco_name: agent.disco_name.map(str::to_string),
+            color: agent.color.to_string(),
+            disco_color: agent.disco_color.map(str::to_string),
+            icon: agent.icon.to_string(),
+            disco_icon: agent.disco_icon.map(str::to_string),
+            description: agent.description.to_string(),
+            disco_description: agent.disco_description.map(str::to_string),
+            voice_samples: serde_json::to_string(agent.voice_samples)?,
+            sort_order: index as i64,
+            is_builtin: true,
+            updated_at: now.clone(),
+        })?;
+    }
+    Ok(())
+}
+
+/// Swap in the active locale's names for built-in names the user hasn't changed
+fn localized(mut agent: AgentPresentation) -> AgentPresentation {
+    let Some(builtin) = BUILTIN_AGENTS.iter().find(|b| b.id == agent.id) else {
+        return agent;
+    };
+    if agent.display_name == builtin.name {
+        if let Some(name) = i18n::text(&format!("agent.{}.name", agent.id)) {
+            agent.display_name = name;
+        }
+    }
+    if agent.disco_name.as_deref() == builtin.disco_name {
+        if let Some(name) = i18n::text(&format!("agent.{}.disco_name", agent.id)) {
+            agent.disco_name = Some(name);
+        }
+    }
+    agent
+}
+
+/// Every agent in display order, localized
+pub fn manifest() -> Result<Vec<AgentPresentation>, String> {
+    db::get_all_agents()
+        .map(|agents| agents.into_iter().map(localized).collect())
+        .map_err(|e| e.to_string())
+}
+
+/// The name to show the user for an agent id (falls back to the built-in name)
+pub fn display_name(id: &str) -> String {
+    db::get_agent(id)
+        .ok()
+        .flatten()
+        .map(|a| localized(a).display_name)
+        .or_else(|| BUILTIN_AGENTS.iter().find(|a| a.id == id).map(|a| a.name.to_string()))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// Apply presentation edits; unspecified fields keep their current value
+#[allow(clippy::too_many_arguments)]
+pub fn update_agent(
+    id: &str,
+    display_name: Option<String>,
+    disco_name: Option<String>,
+    color: Option<String>,
+    disco_color: Option<String>,
+    icon: Option<String>,
+    description: Option<String>,
+    voice_samples: Option<Vec<String>>,
+) -> Result<AgentPresentation, String> {
+    let mut agent = match db::get_agent(id).map_err(|e| e.to_string())? {
+        Some(agent) => agent,
+        None => AgentPresentation {
+            id: id.to_string(),
+            display_name: id.to_string(),
+            disco_name: None,
+            color: "#94A3B8".to_string(),
+            disco_color: None,
+            icon: "governor".to_string(),
+            disco_icon: None,
+            description: String::new(),
+            disco_description: None,
+            voice_samples: "[]".to_string(),
+            sort_order: BUILTIN_AGENTS.len() as i64,
+            is_builtin: false,
+            updated_at: String::new(),
+        },
+    };
+
+    // Colors are stored and rendered as "#RRGGBB"
+    for color in [&color, &disco_color].into_iter().flatten() {
+        let valid = color.strip_prefix('#')
+            .is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+        if !valid {
+            return Err(format!("Invalid color (expected #RRGGBB): {}", color));
+        }
+    }
+
+    if let Some(name) = display_name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()) {
+        agent.display_name = name;
+    }
+    if let Some(name) = disco_name {
+        agent.disco_name = Some(name.trim().to_string()).filter(|n| !n.is_empty());
+    }
+    if let Some(color) = color {
+        agent.color = color;
+    }
+    if let Some(color) = disco_color {
+        agent.disco_color = Some(color);
+    }
+    if let Some(icon) = icon {
+        agent.icon = icon;
+    }
+    if let Some(description) = description {
+        agent.description = description;
+    }
+    if let Some(samples) = voice_samples {
+        agent.voice_samples = serde_json::to_string(&samples).map_err(|e| e.to_string())?;
+    }
+    agent.updated_at = Utc::now().to_rfc3339();
+
+    db::save_agent(&agent).map_err(|e| e.to_string())?;
+    Ok(agent)
+}
@@ -0,0 +1,278 @@
+//! Anonymized rendering for screenshots and demos
+//!
+//! With the mode on, commands that return conversation or memory data swap the
+//! names memory knows about for pseudonyms and strip contact details (the same
+//! redaction a shared session gets). A name always maps to the same pseudonym,
+//! so a recording still reads coherently. Only what's returned is rewritten:
+//! the database, prompts and agents keep the real text.
+
+use crate::db::{self, Bookmark, Chapter, Disagreement, FactConflict, FactHistoryEntry, JournalEntry, Message, MessagePage, MessageSearchResult, UserFact, UserPattern};
+use crate::memory::PastConversation;
+use crate::orchestrator::AgentResponse;
+use crate::share;
+use crate::{ConversationInfo, MemoryStats, SendMessageResult};
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Setting: anonymize what commands return ("true"/"false", default off)
+pub const ANONYMIZED_KEY: &str = "anonymized_rendering";
+
+const PSEUDONYMS: [&str; 26] = [
+    "Alex", "Blair", "Casey", "Dana", "Eden", "Finley", "Gray", "Harper", "Indigo", "Jordan", "Kai", "Lane", "Morgan",
+    "Noel", "Oakley", "Parker", "Quinn", "Reese", "Sage", "Tatum", "Uma", "Vale", "Wren", "Xavi", "Yael", "Zion",
+];
+
+/// Capitalized words that aren't anyone's name
+const NOT_NAMES: [&str; 24] = [
+    "I", "I'm", "I've", "The", "A", "An", "My", "Instinct", "Logic", "Psyche", "Governor", "Archie",
+    "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday",
+    "January", "February", "March", "April", "June",
+];
+
+pub fn enabled() -> bool {
+    db::get_setting(ANONYMIZED_KEY).ok().flatten().as_deref() == Some("true")
+}
+
+/// Flip the mode; returns whether it's now on
+pub fn toggle() -> Result<bool, String> {
+    let on = !enabled();
+    db::set_setting(ANONYMIZED_KEY, if on { "true" } else { "false" }).map_err(|e| e.to_string())?;
+    Ok(on)
+}
+
+/// Same name, same pseudonym: an FNV-1a hash picks the name and a last initial
+fn pseudonym(name: &str) -> String {
+    let hash = name.to_lowercase().bytes().fold(0xcbf29ce484222325u64, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3));
+    let first = PSEUDONYMS[(hash % PSEUDONYMS.len() as u64) as usize];
+    let initial = (b'A' + ((hash / PSEUDONYMS.len() as u64) % 26) as u8) as char;
+    format!("{} {}.", first, initial)
+}
+
+/// Names in a fact: every capitalized word when the fact is about a person
+/// (a "name" key or the relationships category), otherwise those past the first
+fn names_in(fact: &UserFact) -> Vec<String> {
+    let about_person = fact.key.contains("name") || fact.category == "relationships";
+    fact.value
+        .split_whitespace()
+        .skip(if about_person { 0 } else { 1 })
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|w| w.len() > 1 && w.chars().next().map(|c| c.is_uppercase()).unwrap_or(false))
+        .filter(|w| !NOT_NAMES.contains(w))
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// Rewrites text with the current name map
+pub struct Anonymizer {
+    names: Option<Regex>,
+    pseudonyms: HashMap<String, String>,
+}
+
+impl Anonymizer {
+    /// Built from long-term facts; None when the mode is off
+    pub fn active() -> Option<Self> {
+        if !enabled() {
+            return None;
+        }
+        Some(Self::from_facts(&db::get_all_user_facts().unwrap_or_default()))
+    }
+
+    /// Pseudonyms for every name in `facts`
+    pub fn from_facts(facts: &[UserFact]) -> Self {
+        let mut pseudonyms: HashMap<String, String> = HashMap::new();
+        for name in facts.iter().flat_map(names_in) {
+            let alias = pseudonym(&name);
+            pseudonyms.entry(name).or_insert(alias);
+        }
+        // Longest first so "Sarah Jane" isn't cut short by "Sarah"
+        let mut names: Vec<&String> = pseudonyms.keys().collect();
+        names.sort_by_key(|n| std::cmp::Reverse(n.len()));
+        let names = (!names.is_empty())
+            .then(|| {
+                let alternation: Vec<String> = names.iter().map(|n| regex::escape(n)).collect();
+                Regex::new(&format!(r"\b(?:{})\b", alternation.join("|"))).ok()
+            })
+            .flatten();
+        Anonymizer { names, pseudonyms }
+    }
+
+    pub fn text(&self, text: &str) -> String {
+        let text = share::redact(text);
+        match &self.names {
+            Some(names) => names.replace_all(&text, |caps: &regex::Captures| {
+                self.pseudonyms.get(&caps[0]).cloned().unwrap_or_else(|| caps[0].to_string())
+            }).into_owned(),
+            None => text,
+        }
+    }
+
+    fn field(&self, field: &mut String) {
+        *field = self.text(field);
+    }
+
+    fn optional(&self, field: &mut Option<String>) {
+        if let Some(value) = field {
+            self.field(value);
+        }
+    }
+}
+
+/// Data that can be shown anonymized
+pub trait Anonymize {
+    fn anonymize(&mut self, anonymizer: &Anonymizer);
+}
+
+/// Anonymize `value` when the mode is on; otherwise return it untouched
+pub fn render<T: Anonymize>(mut value: T) -> T {
+    if let Some(anonymizer) = Anonymizer::active() {
+        value.anonymize(&anonymizer);
+    }
+    value
+}
+
+impl<T: Anonymize> Anonymize for Vec<T> {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        for item in self.iter_mut() {
+            item.anonymize(anonymizer);
+        }
+    }
+}
+
+impl<T: Anonymize> Anonymize for Option<T> {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        if let Some(item) = self {
+            item.anonymize(anonymizer);
+        }
+    }
+}
+
+impl Anonymize for String {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.field(self);
+    }
+}
+
+impl Anonymize for Message {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.field(&mut self.content);
+    }
+}
+
+impl Anonymize for MessagePage {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        self.messages.anonymize(anonymizer);
+    }
+}
+
+impl Anonymize for MessageSearchResult {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.optional(&mut self.conversation_title);
+        anonymizer.field(&mut self.snippet);
+    }
+}
+
+impl Anonymize for Bookmark {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.optional(&mut self.conversation_title);
+        anonymizer.field(&mut self.content);
+        anonymizer.optional(&mut self.note);
+    }
+}
+
+impl Anonymize for ConversationInfo {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.optional(&mut self.title);
+        anonymizer.optional(&mut self.summary);
+    }
+}
+
+impl Anonymize for PastConversation {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.optional(&mut self.title);
+        anonymizer.field(&mut self.summary);
+        anonymizer.optional(&mut self.chapter);
+    }
+}
+
+impl Anonymize for UserFact {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.field(&mut self.value);
+    }
+}
+
+impl Anonymize for FactConflict {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        self.fact.anonymize(anonymizer);
+        anonymizer.field(&mut self.value);
+    }
+}
+
+impl Anonymize for FactHistoryEntry {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.field(&mut self.value);
+    }
+}
+
+impl Anonymize for db::ArchivedFact {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        self.fact.anonymize(anonymizer);
+    }
+}
+
+impl Anonymize for UserPattern {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.field(&mut self.description);
+        anonymizer.field(&mut self.evidence);
+    }
+}
+
+impl Anonymize for JournalEntry {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.field(&mut self.content);
+        anonymizer.optional(&mut self.reflection);
+    }
+}
+
+impl Anonymize for MemoryStats {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        for fact in self.top_facts.iter_mut() {
+            anonymizer.field(&mut fact.value);
+        }
+        for pattern in self.top_patterns.iter_mut() {
+            anonymizer.field(&mut pattern.description);
+        }
+        self.top_themes.anonymize(anonymizer);
+    }
+}
+
+impl Anonymize for AgentResponse {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.field(&mut self.content);
+    }
+}
+
+impl Anonymize for Chapter {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.field(&mut self.title);
+        anonymizer.optional(&mut self.description);
+    }
+}
+
+impl Anonymize for Disagreement {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        anonymizer.field(&mut self.stance_a);
+        anonymizer.field(&mut self.stance_b);
+        anonymizer.field(&mut self.summary);
+    }
+}
+
+impl Anonymize for SendMessageResult {
+    fn anonymize(&mut self, anonymizer: &Anonymizer) {
+        self.responses.anonymize(anonymizer);
+        anonymizer.optional(&mut self.governor_response);
+        self.related_conversations.anonymize(anonymizer);
+        self.control.anonymize(anonymizer);
+        self.contradiction_corrections.anonymize(anonymizer);
+        self.disagreement.anonymize(anonymizer);
+    }
+}
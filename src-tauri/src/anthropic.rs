@@ -1,7 +1,8 @@
+use crate::usage::MessageCost;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
 const ANTHROPIC_VERSION: &str = "2023-06-01";
@@ -59,6 +60,14 @@ struct MessagesRequest {
 #[derive(Debug, Deserialize)]
 struct MessagesResponse {
     content: Vec<ContentBlock>,
+    #[serde(default)]
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Usage {
+    input_tokens: i64,
+    output_tokens: i64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -80,9 +89,12 @@ struct ErrorDetails {
     error_type: String,
 }
 
+#[derive(Clone)]
 pub struct AnthropicClient {
     client: Client,
     api_key: String,
+    usage_category: String,
+    usage_agent: Option<String>,
 }
 
 impl AnthropicClient {
@@ -96,9 +108,23 @@ impl AnthropicClient {
         Self {
             client,
             api_key: api_key.to_string(),
+            usage_category: "general".to_string(),
+            usage_agent: None,
         }
     }
     
+    /// Tag calls from this client in the usage log (e.g. "memory", "summary")
+    pub fn with_usage_category(mut self, category: &str) -> Self {
+        self.usage_category = category.to_string();
+        self
+    }
+    
+    /// Attribute calls from this client to an agent in the usage log
+    pub fn with_usage_agent(mut self, agent: &str) -> Self {
+        self.usage_agent = Some(agent.to_string());
+        self
+    }
+    
     /// Send a chat completion with full control over model and thinking
     pub async fn chat_completion_advanced(
         &self,
@@ -109,6 +135,21 @@ impl AnthropicClient {
         max_tokens: Option<u32>,
         thinking: ThinkingBudget,
     ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        self.chat_completion_advanced_with_cost(model, system_prompt, messages, temperature, max_tokens, thinking)
+            .await
+            .map(|(content, _)| content)
+    }
+    
+    /// Like `chat_completion_advanced`, also returning the call's tokens and estimated cost
+    pub async fn chat_completion_advanced_with_cost(
+        &self,
+        model: &str,
+        system_prompt: Option<&str>,
+        messages: Vec<AnthropicMessage>,
+        temperature: f32,
+        max_tokens: Option<u32>,
+        thinking: ThinkingBudget,
+    ) -> Result<(String, Option<MessageCost>), Box<dyn Error + Send + Sync>> {
         let thinking_config = thinking.to_tokens().map(|budget| ThinkingConfig {
             thinking_type: "enabled".to_string(),
             budget_tokens: budget,
@@ -137,6 +178,9 @@ impl AnthropicClient {
             thinking: thinking_config,
         };
         
+        crate::budgets::check_monthly_cap()?;
+        
+        let started = Instant::now();
         let response = self.client
             .post(ANTHROPIC_API_URL)
             .header("x-api-key", &self.api_key)
@@ -145,6 +189,7 @@ impl AnthropicClient {
             .json(&request)
             .send()
             .await?;
+        crate::latency::observe("anthropic", started.elapsed().as_millis() as i64);
         
         if !response.status().is_success() {
             let status = response.status();
@@ -163,12 +208,25 @@ impl AnthropicClient {
         
         let completion: MessagesResponse = response.json().await?;
         
+        let cost = completion.usage.as_ref().map(|usage| {
+            crate::usage::record(crate::usage::CallUsage {
+                provider: "anthropic",
+                model,
+                category: &self.usage_category,
+                agent: self.usage_agent.as_deref(),
+                input_tokens: usage.input_tokens,
+                output_tokens: usage.output_tokens,
+                latency_ms: started.elapsed().as_millis() as i64,
+            })
+        });
+        
         // Extract text from content blocks (skip thinking blocks, get final text)
         completion.content
             .iter()
             .filter(|c| c.content_type == "text")
             .last() // Get the last text block (after thinking)
             .and_then(|c| c.text.clone())
+            .map(|text| (text, cost))
             .ok_or_else(|| "No text response from Claude".into())
     }
 }
@@ -0,0 +1,432 @@
+//! Cold-start import of external chat archives
+//!
+//! An archive is a JSON file of past conversations (`[{title, created_at, messages:
+//! [{role, content, timestamp}]}]`, optionally wrapped in `{"conversations": [...]}`).
+//! Importing stores the conversations tagged with the import and prices the analysis
+//! up front. Analysis then "interviews" the archive in the background: batches of
+//! conversations go through the same summarizer and memory extractor as live chats,
+//! progress is recorded after every conversation so a paused or failed run picks up
+//! where it stopped, and everything extracted is held in staging for review. Nothing
+//! reaches long-term memory until the user releases the import's report.
+
+use crate::anthropic::CLAUDE_OPUS;
+use crate::db::{self, ArchiveImport, Message, StagedMemory};
+use crate::events::{self, Event};
+use crate::logging;
+use crate::memory::{self, ConversationSummarizer, MemoryExtractor};
+use crate::usage;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::Mutex;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Conversations analyzed between progress checkpoints (and pause checks)
+const BATCH_SIZE: usize = 5;
+/// Transcript characters sent per conversation; longer ones are cut from the end
+const MAX_TRANSCRIPT_CHARS: usize = 40_000;
+/// Rough prompt sizes and output caps of the summarizer and extractor calls
+const SUMMARY_PROMPT_TOKENS: i64 = 300;
+const SUMMARY_OUTPUT_TOKENS: i64 = 400;
+const EXTRACTION_PROMPT_TOKENS: i64 = 900;
+const EXTRACTION_OUTPUT_TOKENS: i64 = 800;
+/// Both calls run with high thinking; the estimate assumes the full budget is used
+const THINKING_TOKENS: i64 = 10_000;
+
+/// Only one analysis runs at a time so its "import" usage can be attributed to it
+static RUNNING: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ArchiveFile {
+    Wrapped { conversations: Vec<ArchivedConversation> },
+    Bare(Vec<ArchivedConversation>),
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchivedConversation {
+    title: Option<String>,
+    created_at: Option<String>,
+    messages: Vec<ArchivedMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchivedMessage {
+    role: String,
+    content: String,
+    timestamp: Option<String>,
+}
+
+/// A theme or topic and the imported conversations it came up in
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct MentionCount {
+    pub name: String,
+    pub conversations: usize,
+}
+
+/// First-pass memory report for an import, reviewed before anything is promoted
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ImportReport {
+    pub import: ArchiveImport,
+    pub facts: Vec<StagedMemory>,
+    pub patterns: Vec<StagedMemory>,
+    pub themes: Vec<MentionCount>,
+    pub topics: Vec<MentionCount>,
+}
+
+fn normalize_timestamp(raw: Option<&str>, fallback: &str) -> String {
+    raw.and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Utc).to_rfc3339())
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+/// The part of a conversation the analysis reads: messages from the start until
+/// the transcript budget runs out
+fn transcript_window(messages: &[Message]) -> &[Message] {
+    let mut chars = 0;
+    let end = messages
+        .iter()
+        .position(|m| {
+            chars += m.content.len();
+            chars > MAX_TRANSCRIPT_CHARS
+        })
+        .unwrap_or(messages.len());
+    &messages[..end.max(1).min(messages.len())]
+}
+
+fn estimate_conversation_cost(transcript_chars: usize) -> f64 {
+    let transcript_tokens = (transcript_chars.min(MAX_TRANSCRIPT_CHARS) / 4) as i64;
+    usage::estimate_cost_usd(
+        CLAUDE_OPUS,
+        transcript_tokens + SUMMARY_PROMPT_TOKENS,
+        SUMMARY_OUTPUT_TOKENS + THINKING_TOKENS,
+    ) + usage::estimate_cost_usd(
+        CLAUDE_OPUS,
+        transcript_tokens + EXTRACTION_PROMPT_TOKENS,
+        EXTRACTION_OUTPUT_TOKENS + THINKING_TOKENS,
+    )
+}
+
+/// Import an archive file and price its analysis. Conversations with fewer than
+/// two messages are skipped.
+pub fn import_archive(path: &str) -> Result<ArchiveImport, String> {
+    let raw = fs::read_to_string(path).map_err(|e| format!("Couldn't read archive: {}", e))?;
+    let conversations = match serde_json::from_str::<ArchiveFile>(&raw)
+        .map_err(|e| format!("Unrecognized archive format: {}", e))?
+    {
+        ArchiveFile::Wrapped { conversations } | ArchiveFile::Bare(conversations) => conversations,
+    };
+
+    let now = Utc::now().to_rfc3339();
+    let import_id = Uuid::new_v4().to_string();
+    let mut import = ArchiveImport {
+        id: import_id.clone(),
+        source_path: path.to_string(),
+        conversation_count: 0,
+        message_count: 0,
+        processed_count: 0,
+        estimated_cost_usd: 0.0,
+        cost_usd: 0.0,
+        status: db::IMPORT_PENDING.to_string(),
+        themes: "{}".to_string(),
+        error: None,
+        created_at: now.clone(),
+        updated_at: now.clone(),
+    };
+    db::create_archive_import(&import).map_err(|e| e.to_string())?;
+
+    for conversation in conversations {
+        let messages: Vec<&ArchivedMessage> = conversation.messages.iter()
+            .filter(|m| !m.content.trim().is_empty())
+            .collect();
+        if messages.len() < 2 {
+            continue;
+        }
+
+        let created_at = normalize_timestamp(
+            conversation.created_at.as_deref().or(messages[0].timestamp.as_deref()),
+            &now,
+        );
+        let updated_at = normalize_timestamp(messages.last().and_then(|m| m.timestamp.as_deref()), &created_at);
+        let conversation_id = Uuid::new_v4().to_string();
+        db::insert_imported_conversation(&conversation_id, &import_id, conversation.title.as_deref(), &created_at, &updated_at)
+            .map_err(|e| e.to_string())?;
+
+        let mut transcript_chars = 0;
+        for archived in &messages {
+            let role = if archived.role.eq_ignore_ascii_case("user") { "user" } else { "assistant" };
+            db::insert_imported_message(&Message {
+                id: Uuid::new_v4().to_string(),
+                conversation_id: conversation_id.clone(),
+                role: role.to_string(),
+                content: archived.content.clone(),
+                response_type: None,
+                references_message_id: None,
+                timestamp: normalize_timestamp(archived.timestamp.as_deref(), &created_at),
+                status: None,
+                round_id: None,
+                latency_ms: None,
+                ..Default::default()
+            }).map_err(|e| e.to_string())?;
+            transcript_chars += archived.content.len() + role.len() + 2;
+        }
+
+        import.conversation_count += 1;
+        import.message_count += messages.len() as i64;
+        import.estimated_cost_usd += estimate_conversation_cost(transcript_chars);
+    }
+
+    if import.conversation_count == 0 {
+        let _ = db::set_archive_import_status(&import_id, db::IMPORT_FAILED, Some("No conversations with at least two messages"));
+        return Err("The archive has no conversations with at least two messages".to_string());
+    }
+    db::update_archive_import_totals(&import).map_err(|e| e.to_string())?;
+
+    logging::log_memory(None, &format!(
+        "Imported archive {}: {} conversations, {} messages, analysis estimated at ${:.2}",
+        path, import.conversation_count, import.message_count, import.estimated_cost_usd
+    ));
+    Ok(import)
+}
+
+/// Whether an import analysis is running in the background
+pub fn is_analyzing() -> bool {
+    RUNNING.lock().map(|running| running.is_some()).unwrap_or(false)
+}
+
+/// Start (or resume) the background analysis of an import
+pub fn start_analysis(app_handle: tauri::AppHandle, import_id: &str) -> Result<ArchiveImport, String> {
+    let import = db::get_archive_import(import_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Import not found")?;
+
+    {
+        let mut running = RUNNING.lock().map_err(|e| e.to_string())?;
+        if let Some(active) = running.as_ref() {
+            return Err(if active == import_id {
+                "This import is already being analyzed".to_string()
+            } else {
+                "Another import is being analyzed; pause it first".to_string()
+            });
+        }
+        // A "running" status with no task behind it is left over from a quit mid-run
+        if ![db::IMPORT_PENDING, db::IMPORT_PAUSED, db::IMPORT_FAILED, db::IMPORT_RUNNING].contains(&import.status.as_str()) {
+            return Err(format!("Import is already {}", import.status));
+        }
+        *running = Some(import_id.to_string());
+    }
+
+    let api_key = match db::get_user_profile().ok().and_then(|p| p.anthropic_key) {
+        Some(key) => key,
+        None => {
+            *RUNNING.lock().map_err(|e| e.to_string())? = None;
+            return Err("An Anthropic API key is required to analyze imports".to_string());
+        }
+    };
+
+    db::set_archive_import_status(import_id, db::IMPORT_RUNNING, None).map_err(|e| e.to_string())?;
+    let id = import_id.to_string();
+    tauri::async_runtime::spawn(async move {
+        let outcome = run_analysis(&app_handle, &api_key, &id).await;
+        if let Ok(mut running) = RUNNING.lock() {
+            *running = None;
+        }
+        if let Err(e) = outcome {
+            logging::log_error(None, &format!("Archive import {} analysis stopped: {}", id, e));
+            let _ = db::set_archive_import_status(&id, db::IMPORT_FAILED, Some(&e));
+        }
+        if let Ok(Some(import)) = db::get_archive_import(&id) {
+            events::emit(&app_handle, Event::ArchiveImportProgress(import));
+        }
+    });
+
+    db::get_archive_import(import_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Import not found".to_string())
+}
+
+/// Ask a running analysis to stop after the current batch
+pub fn pause_analysis(import_id: &str) -> Result<(), String> {
+    let import = db::get_archive_import(import_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Import not found")?;
+    if import.status != db::IMPORT_RUNNING {
+        return Err(format!("Import is {}, not running", import.status));
+    }
+    db::set_archive_import_status(import_id, db::IMPORT_PAUSED, None).map_err(|e| e.to_string())
+}
+
+async fn run_analysis(app_handle: &tauri::AppHandle, api_key: &str, import_id: &str) -> Result<(), String> {
+    let summarizer = ConversationSummarizer::for_import(api_key);
+    let extractor = MemoryExtractor::for_import(api_key, import_id);
+
+    loop {
+        let import = db::get_archive_import(import_id)
+            .map_err(|e| e.to_string())?
+            .ok_or("Import not found")?;
+        if import.status != db::IMPORT_RUNNING {
+            logging::log_memory(None, &format!("Archive import {} {} at {}/{}", import_id, import.status, import.processed_count, import.conversation_count));
+            return Ok(());
+        }
+
+        let batch = db::get_unprocessed_import_conversations(import_id, BATCH_SIZE).map_err(|e| e.to_string())?;
+        if batch.is_empty() {
+            db::set_archive_import_status(import_id, db::IMPORT_REVIEW, None).map_err(|e| e.to_string())?;
+            logging::log_memory(None, &format!("Archive import {} analyzed; report ready for review", import_id));
+            if let Ok(report) = report(import_id) {
+                events::emit(app_handle, Event::ArchiveImportComplete(report));
+            }
+            return Ok(());
+        }
+
+        let mut themes: BTreeMap<String, Vec<String>> = serde_json::from_str(&import.themes).unwrap_or_default();
+        for conversation_id in batch {
+            let started_at = Utc::now().to_rfc3339();
+            let result = analyze_conversation(&summarizer, &extractor, &conversation_id).await;
+            let cost = import_spend_since(&started_at);
+
+            let found = match result {
+                Ok(found) => found,
+                Err(e) => {
+                    // Keep what this conversation cost, then stop so it can be retried
+                    let themes_json = serde_json::to_string(&themes).unwrap_or_else(|_| "{}".to_string());
+                    let _ = db::record_archive_import_progress(import_id, 0, cost, &themes_json);
+                    return Err(e);
+                }
+            };
+            for theme in found {
+                let conversations = themes.entry(theme.to_lowercase()).or_default();
+                if !conversations.contains(&conversation_id) {
+                    conversations.push(conversation_id.clone());
+                }
+            }
+
+            let themes_json = serde_json::to_string(&themes).unwrap_or_else(|_| "{}".to_string());
+            db::record_archive_import_progress(import_id, 1, cost, &themes_json).map_err(|e| e.to_string())?;
+            if let Ok(Some(progress)) = db::get_archive_import(import_id) {
+                events::emit(app_handle, Event::ArchiveImportProgress(progress));
+            }
+        }
+    }
+}
+
+/// Summarize and extract one imported conversation; returns the themes found
+async fn analyze_conversation(
+    summarizer: &ConversationSummarizer,
+    extractor: &MemoryExtractor,
+    conversation_id: &str,
+) -> Result<Vec<String>, String> {
+    let messages = db::get_delivered_messages(conversation_id).map_err(|e| e.to_string())?;
+    let window = transcript_window(&messages);
+
+    let summary = summarizer.summarize(window, None).await.map_err(|e| e.to_string())?;
+    let agents = vec!["assistant".to_string()];
+    let _ = ConversationSummarizer::save_summary(conversation_id, &summary, messages.len() as i64, &agents);
+
+    let transcript: String = window.iter()
+        .map(|m| format!("{}: {}", m.role.to_uppercase(), m.content))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let existing_facts = db::get_user_facts_in_scope(None).unwrap_or_default();
+    let extraction = extractor
+        .extract_from_exchange(&transcript, &[], &existing_facts, conversation_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    db::mark_conversation_processed(conversation_id, Some(&summary.summary)).map_err(|e| e.to_string())?;
+    Ok(extraction.themes)
+}
+
+/// What "import" calls have cost since a timestamp
+fn import_spend_since(since: &str) -> f64 {
+    db::get_usage_records(Some(since), None)
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| r.category == "import")
+        .map(|r| r.cost_usd)
+        .sum()
+}
+
+fn ranked(counts: BTreeMap<String, usize>) -> Vec<MentionCount> {
+    let mut ranked: Vec<MentionCount> = counts.into_iter()
+        .map(|(name, conversations)| MentionCount { name, conversations })
+        .collect();
+    ranked.sort_by(|a, b| b.conversations.cmp(&a.conversations).then_with(|| a.name.cmp(&b.name)));
+    ranked
+}
+
+/// The import's first-pass memory report: held facts and patterns, themes, and summary topics
+pub fn report(import_id: &str) -> Result<ImportReport, String> {
+    let import = db::get_archive_import(import_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Import not found")?;
+
+    let (facts, patterns): (Vec<StagedMemory>, Vec<StagedMemory>) = db::get_staged_memory_for_import(import_id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .partition(|item| item.kind == db::STAGED_FACT);
+
+    let theme_conversations: BTreeMap<String, Vec<String>> = serde_json::from_str(&import.themes).unwrap_or_default();
+    let themes = ranked(theme_conversations.into_iter().map(|(theme, convs)| (theme, convs.len())).collect());
+
+    let mut topic_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for summary in db::get_import_conversation_summaries(import_id).map_err(|e| e.to_string())? {
+        let topics: Vec<String> = serde_json::from_str(&summary.key_topics).unwrap_or_default();
+        for topic in topics {
+            *topic_counts.entry(topic.to_lowercase()).or_default() += 1;
+        }
+    }
+
+    Ok(ImportReport {
+        import,
+        facts,
+        patterns,
+        themes,
+        topics: ranked(topic_counts),
+    })
+}
+
+/// Accept a reviewed report: held items join normal staging (anything already past
+/// the staging policy is promoted right away) and the themes are recorded
+pub fn release(import_id: &str) -> Result<usize, String> {
+    let import = db::get_archive_import(import_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Import not found")?;
+    if import.status != db::IMPORT_REVIEW {
+        return Err(format!("Import is {}; only a finished analysis can be released", import.status));
+    }
+
+    let released = db::release_staged_memory_for_import(import_id).map_err(|e| e.to_string())?;
+    let themes: BTreeMap<String, Vec<String>> = serde_json::from_str(&import.themes).unwrap_or_default();
+    for (theme, conversations) in &themes {
+        for conversation_id in conversations {
+            let _ = db::save_recurring_theme(theme, conversation_id);
+        }
+    }
+    memory::promote_staged_memory()?;
+
+    db::set_archive_import_status(import_id, db::IMPORT_RELEASED, None).map_err(|e| e.to_string())?;
+    logging::log_memory(None, &format!("Released archive import {}: {} staged items", import_id, released));
+    Ok(released)
+}
+
+/// Reject a report: drop everything the import is holding. The imported
+/// conversations themselves stay.
+pub fn discard(import_id: &str) -> Result<usize, String> {
+    let import = db::get_archive_import(import_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Import not found")?;
+    if import.status == db::IMPORT_RUNNING {
+        return Err("Pause the analysis before discarding it".to_string());
+    }
+
+    let discarded = db::delete_staged_memory_for_import(import_id).map_err(|e| e.to_string())?;
+    db::set_archive_import_status(import_id, db::IMPORT_DISCARDED, None).map_err(|e| e.to_string())?;
+    logging::log_memory(None, &format!("Discarded archive import {}: {} staged items", import_id, discarded));
+    Ok(discarded)
+}
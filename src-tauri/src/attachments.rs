@@ -0,0 +1,131 @@
+//! Files attached to messages
+//!
+//! A file picked in the composer is copied into the database (up to a size
+//! limit; bigger files are kept by path only, so they must stay where they
+//! are). Text-like files also have their text extracted, and that text rides
+//! along with the message into every agent's context (`context_for`), trimmed
+//! so an attached book can't crowd out the conversation itself.
+
+use crate::db::{self, Attachment, Message};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Files up to this size are stored in the database; larger ones by path only
+const MAX_STORED_BYTES: u64 = 10_000_000;
+/// Files larger than this aren't read for text
+const MAX_TEXT_FILE_BYTES: u64 = 2_000_000;
+/// Text kept per attachment
+const MAX_EXTRACTED_CHARS: usize = 20_000;
+/// Attachment text given to the agents per message
+const MAX_CONTEXT_CHARS: usize = 8_000;
+
+const TEXT_EXTENSIONS: [(&str, &str); 12] = [
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("markdown", "text/markdown"),
+    ("csv", "text/csv"),
+    ("tsv", "text/tab-separated-values"),
+    ("json", "application/json"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("html", "text/html"),
+    ("xml", "application/xml"),
+    ("log", "text/plain"),
+    ("rtf", "application/rtf"),
+];
+
+const BINARY_EXTENSIONS: [(&str, &str); 7] = [
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("heic", "image/heic"),
+    ("pdf", "application/pdf"),
+];
+
+fn extension(path: &Path) -> String {
+    path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).unwrap_or_default()
+}
+
+/// MIME type from the file extension
+pub fn mime_type(path: &Path) -> &'static str {
+    let ext = extension(path);
+    TEXT_EXTENSIONS.iter()
+        .chain(BINARY_EXTENSIONS.iter())
+        .find(|(e, _)| *e == ext)
+        .map(|(_, mime)| *mime)
+        .unwrap_or("application/octet-stream")
+}
+
+fn is_text(path: &Path) -> bool {
+    let ext = extension(path);
+    TEXT_EXTENSIONS.iter().any(|(e, _)| *e == ext)
+}
+
+/// The text agents get for a file, if it has any worth giving them
+fn extract_text(path: &Path, size: u64) -> Option<String> {
+    if !is_text(path) || size > MAX_TEXT_FILE_BYTES {
+        return None;
+    }
+    let bytes = fs::read(path).ok()?;
+    let text = String::from_utf8_lossy(&bytes);
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(text.chars().take(MAX_EXTRACTED_CHARS).collect())
+}
+
+/// Attach a file to one of the user's messages
+pub fn attach_file(message_id: &str, path: &str) -> Result<Attachment, String> {
+    let message = db::get_message(message_id).map_err(|e| e.to_string())?
+        .ok_or("Message not found")?;
+    if message.role != "user" {
+        return Err("Files can only be attached to your own messages".to_string());
+    }
+
+    let path = Path::new(path);
+    let metadata = fs::metadata(path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("{} isn't a file", path.display()));
+    }
+    let size = metadata.len();
+    let data = if size <= MAX_STORED_BYTES {
+        Some(fs::read(path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))?)
+    } else {
+        None
+    };
+
+    let attachment = Attachment {
+        id: Uuid::new_v4().to_string(),
+        message_id: message_id.to_string(),
+        filename: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        mime_type: mime_type(path).to_string(),
+        path: Some(path.to_string_lossy().to_string()),
+        size_bytes: size as i64,
+        extracted_text: extract_text(path, size),
+        created_at: Utc::now().to_rfc3339(),
+    };
+    db::save_attachment(&attachment, data.as_deref()).map_err(|e| e.to_string())?;
+    Ok(attachment)
+}
+
+/// The attachment text to add to each message that has some, keyed by message id
+pub fn context_for(history: &[Message]) -> HashMap<String, String> {
+    let ids: Vec<String> = history.iter().filter(|m| m.role == "user").map(|m| m.id.clone()).collect();
+    let mut blocks: HashMap<String, String> = HashMap::new();
+    for attachment in db::get_attachments_with_text(&ids).unwrap_or_default() {
+        let block = blocks.entry(attachment.message_id.clone()).or_default();
+        let room = MAX_CONTEXT_CHARS.saturating_sub(block.chars().count());
+        if room == 0 {
+            continue;
+        }
+        let text: String = attachment.extracted_text.unwrap_or_default().chars().take(room).collect();
+        block.push_str(&format!("\n\n[Attached file: {}]\n{}", attachment.filename, text));
+    }
+    blocks
+}
@@ -0,0 +1,253 @@
+//! User-defined content boundaries per agent
+//!
+//! Each rule targets one agent (or "all"), optionally only within a local time
+//! window, and carries trigger keywords. Active rules are compiled into prompt
+//! constraints before generation and enforced again on the finished reply:
+//! "soften" drops the offending sentences, "suppress" replaces the whole reply.
+//!
+//! House rules are the global counterpart: one free-text block, kept in settings,
+//! appended to every agent's (and the Governor's) system prompt as it's assembled.
+//! They're instructions rather than filters -- nothing checks replies against them.
+
+use crate::agents;
+use crate::i18n;
+use crate::db::{self, BoundaryRule};
+use crate::logging;
+use chrono::{Local, NaiveTime};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+pub const ACTION_SOFTEN: &str = "soften";
+pub const ACTION_SUPPRESS: &str = "suppress";
+
+/// Result of running a reply through the boundary filter
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoundaryOutcome {
+    Pass,
+    Softened(String),
+    Suppressed(String),
+}
+
+fn parse_time(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M").ok()
+}
+
+/// Whether a rule's time window (if any) covers the given time. Windows may wrap midnight.
+fn in_window(rule: &BoundaryRule, now: NaiveTime) -> bool {
+    let start = rule.start_time.as_deref().and_then(parse_time);
+    let end = rule.end_time.as_deref().and_then(parse_time);
+    match (start, end) {
+        (Some(s), Some(e)) if s <= e => now >= s && now < e,
+        (Some(s), Some(e)) => now >= s || now < e,
+        (Some(s), None) => now >= s,
+        (None, Some(e)) => now < e,
+        (None, None) => true,
+    }
+}
+
+/// One case-insensitive pattern for all of a rule's keywords, each matched as
+/// whole words so "ex" doesn't fire on "next" or "Essex". None without keywords.
+fn rule_pattern(rule: &BoundaryRule) -> Option<Regex> {
+    let keywords: Vec<String> = serde_json::from_str::<Vec<String>>(&rule.keywords)
+        .unwrap_or_default()
+        .iter()
+        .map(|k| k.trim())
+        .filter(|k| !k.is_empty())
+        .map(regex::escape)
+        .collect();
+    if keywords.is_empty() {
+        return None;
+    }
+    Regex::new(&format!(r"(?i)\b(?:{})\b", keywords.join("|"))).ok()
+}
+
+/// Enabled rules that apply to this agent right now
+pub fn active_rules(agent: &str) -> Vec<BoundaryRule> {
+    let now = Local::now().time();
+    db::get_boundary_rules()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|r| r.enabled && (r.agent == "all" || r.agent == agent) && in_window(r, now))
+        .collect()
+}
+
+/// Compile active rules into a prompt section (empty when there are none)
+pub fn prompt_constraints(agent: &str) -> String {
+    let rules = active_rules(agent);
+    if rules.is_empty() {
+        return String::new();
+    }
+
+    let lines = rules
+        .iter()
+        .map(|r| format!("- {}", r.description))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "\n\n---\n\nUSER BOUNDARIES (set by the user -- never cross these, don't mention them unless asked):\n{}",
+        lines
+    )
+}
+
+// ============ House Rules ============
+
+pub const HOUSE_RULES_SETTING_KEY: &str = "house_rules";
+const MAX_HOUSE_RULES_CHARS: usize = 4000;
+
+/// The user's house rules, if any are set
+pub fn house_rules() -> Option<String> {
+    db::get_setting(HOUSE_RULES_SETTING_KEY)
+        .ok()
+        .flatten()
+        .map(|rules| rules.trim().to_string())
+        .filter(|rules| !rules.is_empty())
+}
+
+/// Replace the house rules; blank text clears them
+pub fn set_house_rules(text: &str) -> Result<Option<String>, String> {
+    let text = text.trim();
+    if text.chars().count() > MAX_HOUSE_RULES_CHARS {
+        return Err(format!("House rules are limited to {} characters", MAX_HOUSE_RULES_CHARS));
+    }
+    db::set_setting(HOUSE_RULES_SETTING_KEY, text).map_err(|e| e.to_string())?;
+    logging::log_conversation(None, if text.is_empty() { "House rules cleared" } else { "House rules updated" });
+    Ok(house_rules())
+}
+
+/// House rules as a system prompt section, or "" when none are set
+pub fn house_rules_section() -> String {
+    match house_rules() {
+        Some(rules) => format!(
+            "\n\n---\n\nHOUSE RULES (set by the user, apply to everything you say -- follow them without mentioning them):\n{}",
+            rules
+        ),
+        None => String::new(),
+    }
+}
+
+/// Where a sentence ends: terminal punctuation (and any closing quote or
+/// bracket) followed by whitespace or the end of the text, or a line break.
+/// "3.5" and "..." mid-sentence don't match.
+static SENTENCE_END: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"([.!?]+["'”’)\]]*)(\s+|$)|[ \t]*\n\s*"#).unwrap()
+});
+
+/// Abbreviations whose period doesn't end a sentence
+const ABBREVIATIONS: [&str; 9] = ["e.g.", "i.e.", "vs.", "cf.", "approx.", "mr.", "mrs.", "ms.", "dr."];
+
+/// A sentence and the whitespace that followed it in the original text
+#[derive(Debug, Clone)]
+pub struct SentenceSpan {
+    pub text: String,
+    pub separator: String,  // "" after the last sentence
+}
+
+/// Split text into sentences, keeping terminal punctuation and the breaks between them
+pub fn sentence_spans(text: &str) -> Vec<SentenceSpan> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for caps in SENTENCE_END.captures_iter(text) {
+        let m = caps.get(0).unwrap();
+        let sentence_end = caps.get(1).map_or(m.start(), |p| p.end());
+        let sentence = text[start..sentence_end].trim();
+        if caps.get(1).is_some() {
+            let last_word = sentence.rsplit(char::is_whitespace).next().unwrap_or("").to_lowercase();
+            if ABBREVIATIONS.contains(&last_word.as_str()) && m.end() < text.len() {
+                continue;
+            }
+        }
+        if !sentence.is_empty() {
+            spans.push(SentenceSpan { text: sentence.to_string(), separator: text[sentence_end..m.end()].to_string() });
+        } else if let Some(last) = spans.last_mut() {
+            last.separator.push_str(&text[sentence_end..m.end()]);
+        }
+        start = m.end();
+    }
+    let rest = text[start..].trim();
+    if !rest.is_empty() {
+        spans.push(SentenceSpan { text: rest.to_string(), separator: String::new() });
+    }
+    if let Some(last) = spans.last_mut() {
+        last.separator.clear();
+    }
+    spans
+}
+
+/// Split text into sentences, keeping terminal punctuation
+pub fn split_sentences(text: &str) -> Vec<String> {
+    sentence_spans(text).into_iter().map(|s| s.text).collect()
+}
+
+/// Keep the sentences `keep` accepts. A dropped sentence's paragraph or line
+/// break moves to the sentence before it, so what's left keeps its layout.
+pub fn retain_sentences(spans: Vec<SentenceSpan>, mut keep: impl FnMut(&SentenceSpan) -> bool) -> Vec<SentenceSpan> {
+    let breaks = |s: &str| s.matches('\n').count();
+    let mut kept: Vec<SentenceSpan> = Vec::new();
+    for span in spans {
+        if keep(&span) {
+            kept.push(span);
+        } else if let Some(last) = kept.last_mut() {
+            if breaks(&span.separator) > breaks(&last.separator) {
+                last.separator = span.separator;
+            }
+        }
+    }
+    kept
+}
+
+/// Put sentences back together with their original separators
+pub fn join_sentences(spans: &[SentenceSpan]) -> String {
+    let mut out = String::new();
+    for (i, span) in spans.iter().enumerate() {
+        out.push_str(&span.text);
+        if i + 1 < spans.len() {
+            out.push_str(if span.separator.is_empty() { " " } else { &span.separator });
+        }
+    }
+    out
+}
+
+fn suppressed_notice(agent: &str) -> String {
+    let name = match agent {
+        "instinct" | "logic" | "psyche" => agents::display_name(agent),
+        _ => "This agent".to_string(),
+    };
+    i18n::format(
+        "chat.boundary_suppressed",
+        "{name} held back -- that reply crossed a boundary you set.",
+        &[("name", &name)],
+    )
+}
+
+/// Enforce active rules on a finished reply
+pub fn enforce(agent: &str, content: &str) -> BoundaryOutcome {
+    let mut outcome = BoundaryOutcome::Pass;
+    let mut text = content.to_string();
+
+    for rule in active_rules(agent) {
+        let Some(pattern) = rule_pattern(&rule) else {
+            continue;
+        };
+        if !pattern.is_match(&text) {
+            continue;
+        }
+
+        logging::log_agent(None, &format!(
+            "Boundary '{}' triggered for {} ({})", rule.description, agent, rule.action
+        ));
+
+        if rule.action == ACTION_SUPPRESS {
+            return BoundaryOutcome::Suppressed(suppressed_notice(agent));
+        }
+
+        let kept = retain_sentences(sentence_spans(&text), |s| !pattern.is_match(&s.text));
+        if kept.is_empty() {
+            return BoundaryOutcome::Suppressed(suppressed_notice(agent));
+        }
+        text = join_sentences(&kept);
+        outcome = BoundaryOutcome::Softened(text.clone());
+    }
+
+    outcome
+}
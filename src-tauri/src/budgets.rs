@@ -0,0 +1,264 @@
+//! Per-agent daily token budgets
+//!
+//! Each agent can be given a daily allowance of hosted-model tokens (input plus
+//! output, counted from the usage log since local midnight). Once an agent has
+//! spent it, the agent either goes quiet for the rest of the day with a short
+//! line in its own voice, or carries on through a local OpenAI-compatible model
+//! whose calls don't count against the allowance. No allowance means no limit.
+//!
+//! Over all of that sits an optional monthly cap, in dollars and/or tokens,
+//! checked before every hosted LLM call. Once it's reached the call is either
+//! refused or let through with a warning, depending on the cap mode.
+
+use crate::db;
+use crate::i18n;
+use crate::logging;
+use crate::openai::OpenAIClient;
+use crate::usage;
+use chrono::{Local, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+pub const AGENT_DAILY_TOKENS_PREFIX: &str = "agent_daily_tokens_";
+pub const BUDGET_MODE_KEY: &str = "agent_budget_mode";
+pub const LOCAL_MODEL_URL_KEY: &str = "local_model_url";
+pub const LOCAL_MODEL_NAME_KEY: &str = "local_model_name";
+
+pub const MODE_SILENT: &str = "silent";
+pub const MODE_LOCAL: &str = "local";
+
+const AGENTS: [&str; 3] = ["instinct", "logic", "psyche"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct AgentBudget {
+    pub agent: String,
+    pub daily_tokens: Option<i64>,
+    pub used_today: i64,
+    pub exhausted: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct BudgetSettings {
+    pub agents: Vec<AgentBudget>,
+    pub mode: String,
+    pub local_model_url: Option<String>,
+    pub local_model_name: Option<String>,
+}
+
+/// How an agent's next reply should be produced
+pub enum BudgetRoute {
+    Hosted,
+    Local(OpenAIClient),
+    Silent(String),
+}
+
+fn setting(key: &str) -> Option<String> {
+    db::get_setting(key).ok().flatten().filter(|v| !v.trim().is_empty())
+}
+
+pub fn daily_tokens(agent: &str) -> Option<i64> {
+    setting(&format!("{}{}", AGENT_DAILY_TOKENS_PREFIX, agent))
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|&n| n > 0)
+}
+
+fn mode() -> String {
+    match setting(BUDGET_MODE_KEY).as_deref() {
+        Some(MODE_LOCAL) => MODE_LOCAL.to_string(),
+        _ => MODE_SILENT.to_string(),
+    }
+}
+
+/// Hosted tokens the agent has used since local midnight
+pub fn used_today(agent: &str) -> i64 {
+    let midnight = Local::now().date_naive().and_hms_opt(0, 0, 0)
+        .and_then(|t| Local.from_local_datetime(&t).earliest())
+        .map(|t| t.with_timezone(&Utc).to_rfc3339())
+        .unwrap_or_else(|| Utc::now().to_rfc3339());
+    db::get_agent_tokens_since(agent, &midnight).unwrap_or(0)
+}
+
+pub fn get_settings() -> BudgetSettings {
+    let agents = AGENTS.iter().map(|agent| {
+        let daily_tokens = daily_tokens(agent);
+        let used_today = used_today(agent);
+        AgentBudget {
+            agent: agent.to_string(),
+            daily_tokens,
+            used_today,
+            exhausted: daily_tokens.map(|limit| used_today >= limit).unwrap_or(false),
+        }
+    }).collect();
+
+    BudgetSettings {
+        agents,
+        mode: mode(),
+        local_model_url: setting(LOCAL_MODEL_URL_KEY),
+        local_model_name: setting(LOCAL_MODEL_NAME_KEY),
+    }
+}
+
+/// Set an agent's daily allowance; `None` or 0 removes the limit
+pub fn set_agent_budget(agent: &str, daily_tokens: Option<i64>) -> Result<BudgetSettings, String> {
+    if !AGENTS.contains(&agent) {
+        return Err(format!("Unknown agent: {}", agent));
+    }
+    let value = daily_tokens.filter(|&n| n > 0).map(|n| n.to_string()).unwrap_or_default();
+    db::set_setting(&format!("{}{}", AGENT_DAILY_TOKENS_PREFIX, agent), &value).map_err(|e| e.to_string())?;
+    Ok(get_settings())
+}
+
+/// Choose what an over-budget agent does, and where the local model lives
+pub fn set_fallback(mode: &str, local_model_url: Option<String>, local_model_name: Option<String>) -> Result<BudgetSettings, String> {
+    if mode != MODE_SILENT && mode != MODE_LOCAL {
+        return Err(format!("Unknown budget mode: {}", mode));
+    }
+    let url = local_model_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    let name = local_model_name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if mode == MODE_LOCAL && (url.is_none() || name.is_none()) {
+        return Err("The local fallback needs a model URL and model name".to_string());
+    }
+    db::set_setting(BUDGET_MODE_KEY, mode).map_err(|e| e.to_string())?;
+    db::set_setting(LOCAL_MODEL_URL_KEY, url.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+    db::set_setting(LOCAL_MODEL_NAME_KEY, name.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+    Ok(get_settings())
+}
+
+/// The agent's own sign-off for when it's out of tokens
+fn exhausted_line(agent: &str) -> String {
+    let default = match agent {
+        "instinct" => "I'm tapped out for today. Back tomorrow.",
+        "logic" => "I've hit today's token allowance -- I'll pick this up tomorrow.",
+        _ => "I've given all I have today. I'll be here tomorrow.",
+    };
+    i18n::text(&format!("budget.exhausted.{}", agent)).unwrap_or_else(|| default.to_string())
+}
+
+/// Decide how the agent replies given what it has spent today
+pub fn route(agent: &str) -> BudgetRoute {
+    let Some(limit) = daily_tokens(agent) else {
+        return BudgetRoute::Hosted;
+    };
+    let used = used_today(agent);
+    if used < limit {
+        return BudgetRoute::Hosted;
+    }
+
+    if mode() == MODE_LOCAL {
+        if let (Some(url), Some(name)) = (setting(LOCAL_MODEL_URL_KEY), setting(LOCAL_MODEL_NAME_KEY)) {
+            logging::log_routing(None, &format!(
+                "[BUDGET] {} over daily allowance ({}/{}), using local model {}", agent, used, limit, name
+            ));
+            return BudgetRoute::Local(OpenAIClient::local(&url, &name));
+        }
+    }
+
+    logging::log_routing(None, &format!(
+        "[BUDGET] {} over daily allowance ({}/{}), staying quiet", agent, used, limit
+    ));
+    BudgetRoute::Silent(exhausted_line(agent))
+}
+
+// ============ Monthly Cap ============
+
+pub const MONTHLY_CAP_USD_KEY: &str = "monthly_cap_usd";
+pub const MONTHLY_CAP_TOKENS_KEY: &str = "monthly_cap_tokens";
+pub const MONTHLY_CAP_MODE_KEY: &str = "monthly_cap_mode";
+
+pub const CAP_MODE_BLOCK: &str = "block";
+pub const CAP_MODE_WARN: &str = "warn";
+
+/// Share of the cap at which the status starts warning
+const CAP_WARNING_FRACTION: f64 = 0.8;
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct BudgetStatus {
+    pub cap_usd: Option<f64>,
+    pub cap_tokens: Option<i64>,
+    pub mode: String,               // "block" | "warn"
+    pub spent_usd: f64,             // This calendar month, hosted calls only
+    pub used_tokens: i64,
+    pub fraction_used: Option<f64>, // Of whichever cap is closest; null when no cap is set
+    pub reached: bool,
+    pub warning: Option<String>,
+}
+
+pub fn monthly_cap_usd() -> Option<f64> {
+    setting(MONTHLY_CAP_USD_KEY)
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|&n| n > 0.0)
+}
+
+fn monthly_cap_tokens() -> Option<i64> {
+    setting(MONTHLY_CAP_TOKENS_KEY)
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|&n| n > 0)
+}
+
+fn cap_mode() -> String {
+    match setting(MONTHLY_CAP_MODE_KEY).as_deref() {
+        Some(CAP_MODE_WARN) => CAP_MODE_WARN.to_string(),
+        _ => CAP_MODE_BLOCK.to_string(),
+    }
+}
+
+/// This month's spend against the cap
+pub fn status() -> BudgetStatus {
+    let cap_usd = monthly_cap_usd();
+    let cap_tokens = monthly_cap_tokens();
+    let mode = cap_mode();
+    let (used_tokens, spent_usd) = db::get_usage_totals_since(&usage::month_start()).unwrap_or((0, 0.0));
+
+    let fraction_used = [
+        cap_usd.map(|cap| spent_usd / cap),
+        cap_tokens.map(|cap| used_tokens as f64 / cap as f64),
+    ].into_iter().flatten().reduce(f64::max);
+    let reached = fraction_used.map(|f| f >= 1.0).unwrap_or(false);
+
+    let warning = match fraction_used {
+        Some(_) if reached && mode == CAP_MODE_BLOCK => {
+            Some("Monthly budget reached. Hosted model calls are paused until next month or until the cap is raised.".to_string())
+        }
+        Some(_) if reached => Some("Monthly budget reached. Calls are still going through.".to_string()),
+        Some(f) if f >= CAP_WARNING_FRACTION => Some(format!("{:.0}% of this month's budget used.", f * 100.0)),
+        _ => None,
+    };
+
+    BudgetStatus { cap_usd, cap_tokens, mode, spent_usd, used_tokens, fraction_used, reached, warning }
+}
+
+/// Set the monthly cap; `None` or 0 removes that limit
+pub fn set_monthly_cap(cap_usd: Option<f64>, cap_tokens: Option<i64>, mode: &str) -> Result<BudgetStatus, String> {
+    if mode != CAP_MODE_BLOCK && mode != CAP_MODE_WARN {
+        return Err(format!("Unknown cap mode: {}", mode));
+    }
+    let usd = cap_usd.filter(|&n| n > 0.0).map(|n| n.to_string()).unwrap_or_default();
+    let tokens = cap_tokens.filter(|&n| n > 0).map(|n| n.to_string()).unwrap_or_default();
+    db::set_setting(MONTHLY_CAP_USD_KEY, &usd).map_err(|e| e.to_string())?;
+    db::set_setting(MONTHLY_CAP_TOKENS_KEY, &tokens).map_err(|e| e.to_string())?;
+    db::set_setting(MONTHLY_CAP_MODE_KEY, mode).map_err(|e| e.to_string())?;
+    Ok(status())
+}
+
+/// Called before every hosted LLM call: an error when the cap is reached and
+/// set to block, otherwise the call goes ahead (logged when over the cap)
+pub fn check_monthly_cap() -> Result<(), String> {
+    if monthly_cap_usd().is_none() && monthly_cap_tokens().is_none() {
+        return Ok(());
+    }
+    let status = status();
+    if !status.reached {
+        return Ok(());
+    }
+    if status.mode == CAP_MODE_BLOCK {
+        return Err(status.warning.unwrap_or_default());
+    }
+    logging::log_routing(None, &format!(
+        "[BUDGET] Over the monthly cap (${:.2}, {} tokens), continuing in warn mode",
+        status.spent_usd, status.used_tokens
+    ));
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! Chapters: long-horizon narrative memory
+//!
+//! A chapter is a period of the user's life with a date range -- "the layoff
+//! spring", "moving to Berlin". The user can define them, and a light job
+//! proposes them on its own when one key topic keeps coming back across
+//! conversations for weeks. Conversations fall into a chapter when placed
+//! there or when they happened inside its range, and the context builder uses
+//! that to frame what it retrieves in time ("back during the layoff spring you
+//! said..."). Topic counting only, no model call.
+
+use crate::db::{self, Chapter, ConversationSummary};
+use crate::logging;
+use chrono::{Datelike, NaiveDate, Utc};
+use std::collections::HashMap;
+
+/// Conversations a topic must come up in before it becomes a chapter
+const MIN_AUTO_CONVERSATIONS: usize = 4;
+/// Days between its first and last mention
+const MIN_AUTO_SPAN_DAYS: i64 = 14;
+/// A topic mentioned this recently keeps its chapter open-ended
+const ONGOING_DAYS: i64 = 21;
+/// Longer than this and the title names no season
+const SEASON_MAX_DAYS: i64 = 120;
+/// Summaries scanned per detection pass
+const AUTO_SCAN_LIMIT: usize = 1000;
+
+const MAX_TITLE_CHARS: usize = 80;
+
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()
+}
+
+/// Checked title and dates for a user-defined chapter
+fn validate(title: &str, start_date: &str, end_date: Option<&str>) -> Result<(String, String, Option<String>), String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Give the chapter a name".to_string());
+    }
+    if title.chars().count() > MAX_TITLE_CHARS {
+        return Err(format!("Chapter names are limited to {} characters", MAX_TITLE_CHARS));
+    }
+    let start = parse_date(start_date).ok_or_else(|| format!("Invalid start date (expected YYYY-MM-DD): {}", start_date))?;
+    let end = match end_date.map(str::trim).filter(|d| !d.is_empty()) {
+        Some(end_date) => {
+            let end = parse_date(end_date).ok_or_else(|| format!("Invalid end date (expected YYYY-MM-DD): {}", end_date))?;
+            if end < start {
+                return Err("A chapter can't end before it starts".to_string());
+            }
+            Some(end.to_string())
+        }
+        None => None,
+    };
+    Ok((title.to_string(), start.to_string(), end))
+}
+
+fn clean_description(description: Option<&str>) -> Option<&str> {
+    description.map(str::trim).filter(|d| !d.is_empty())
+}
+
+pub fn create(title: &str, start_date: &str, end_date: Option<&str>, description: Option<&str>) -> Result<Chapter, String> {
+    let (title, start_date, end_date) = validate(title, start_date, end_date)?;
+    let id = db::create_chapter(&title, &start_date, end_date.as_deref(), clean_description(description), db::CHAPTER_USER, None)
+        .map_err(|e| e.to_string())?;
+    logging::log_memory(None, &format!("[CHAPTERS] Added \"{}\" ({} to {})", title, start_date, end_date.as_deref().unwrap_or("now")));
+    get(id)
+}
+
+pub fn update(id: i64, title: &str, start_date: &str, end_date: Option<&str>, description: Option<&str>) -> Result<Chapter, String> {
+    let (title, start_date, end_date) = validate(title, start_date, end_date)?;
+    if !db::update_chapter(id, &title, &start_date, end_date.as_deref(), clean_description(description)).map_err(|e| e.to_string())? {
+        return Err("Chapter not found".to_string());
+    }
+    get(id)
+}
+
+pub fn get(id: i64) -> Result<Chapter, String> {
+    db::get_chapter(id).map_err(|e| e.to_string())?.ok_or_else(|| "Chapter not found".to_string())
+}
+
+/// The chapter a memory belongs to: the one its conversation was placed in,
+/// otherwise the narrowest whose range holds `date` (RFC 3339 or YYYY-MM-DD)
+pub fn containing<'a>(chapters: &'a [Chapter], conversation_id: Option<&str>, date: &str) -> Option<&'a Chapter> {
+    if let Some(placed) = conversation_id.and_then(|id| chapters.iter().find(|c| c.conversation_ids.iter().any(|cid| cid == id))) {
+        return Some(placed);
+    }
+    let day = date.get(..10).unwrap_or(date);
+    let today = Utc::now().date_naive().to_string();
+    chapters
+        .iter()
+        .filter(|c| c.start_date.as_str() <= day && day <= c.end_date.as_deref().unwrap_or(today.as_str()))
+        .min_by_key(|c| {
+            let start = parse_date(&c.start_date);
+            let end = c.end_date.as_deref().and_then(parse_date).unwrap_or_else(|| Utc::now().date_naive());
+            start.map(|start| (end - start).num_days()).unwrap_or(i64::MAX)
+        })
+}
+
+/// "the layoff spring", or "the layoff stretch" for a long run
+fn auto_title(topic: &str, start: NaiveDate, end: NaiveDate) -> String {
+    if (end - start).num_days() > SEASON_MAX_DAYS {
+        return format!("the {} stretch", topic);
+    }
+    let middle = start + (end - start) / 2;
+    let season = match middle.month() {
+        3..=5 => "spring",
+        6..=8 => "summer",
+        9..=11 => "autumn",
+        _ => "winter",
+    };
+    format!("the {} {}", topic, season)
+}
+
+/// Propose chapters for topics that kept coming back, and stretch the ones
+/// already proposed as the topic keeps coming up. Topics the user dismissed or
+/// whose chapter they took over are left alone. Returns how many were added.
+pub fn detect_auto() -> Result<usize, String> {
+    let summaries = db::get_past_conversation_summaries("", AUTO_SCAN_LIMIT).map_err(|e| e.to_string())?;
+    let mut by_topic: HashMap<String, Vec<&ConversationSummary>> = HashMap::new();
+    for summary in &summaries {
+        let topics: Vec<String> = serde_json::from_str(&summary.key_topics).unwrap_or_default();
+        for topic in topics {
+            let topic = topic.trim().to_lowercase();
+            if !topic.is_empty() {
+                by_topic.entry(topic).or_default().push(summary);
+            }
+        }
+    }
+
+    let today = Utc::now().date_naive();
+    let mut added = 0;
+    for (topic, mentions) in by_topic {
+        let mut conversation_ids: Vec<String> = mentions.iter().map(|s| s.conversation_id.clone()).collect();
+        conversation_ids.sort();
+        conversation_ids.dedup();
+        if conversation_ids.len() < MIN_AUTO_CONVERSATIONS {
+            continue;
+        }
+        let days: Vec<NaiveDate> = mentions.iter().filter_map(|s| s.created_at.get(..10).and_then(parse_date)).collect();
+        let (Some(start), Some(end)) = (days.iter().min().copied(), days.iter().max().copied()) else {
+            continue;
+        };
+        if (end - start).num_days() < MIN_AUTO_SPAN_DAYS {
+            continue;
+        }
+        let end_date = ((today - end).num_days() > ONGOING_DAYS).then(|| end.to_string());
+
+        match db::get_chapter_for_topic(&topic).map_err(|e| e.to_string())? {
+            Some((id, source, false)) if source == db::CHAPTER_AUTO => {
+                db::extend_auto_chapter(id, &start.to_string(), end_date.as_deref(), &conversation_ids).map_err(|e| e.to_string())?;
+            }
+            Some(_) => {}
+            None => {
+                let title = auto_title(&topic, start, end);
+                let id = db::create_chapter(&title, &start.to_string(), end_date.as_deref(), None, db::CHAPTER_AUTO, Some(&topic))
+                    .map_err(|e| e.to_string())?;
+                db::extend_auto_chapter(id, &start.to_string(), end_date.as_deref(), &conversation_ids).map_err(|e| e.to_string())?;
+                logging::log_memory(None, &format!("[CHAPTERS] Detected \"{}\" ({} conversations)", title, conversation_ids.len()));
+                added += 1;
+            }
+        }
+    }
+    Ok(added)
+}
@@ -0,0 +1,41 @@
+//! Inline memory citations
+//!
+//! Facts handed to an agent are labelled `[F<id>]`. The agent is asked to close
+//! its reply with a `[cites: F3, F12]` marker naming the facts it actually relied
+//! on; the marker is stripped before the reply is filtered and shown, and the
+//! ids (checked against what the agent was given) are stored per message.
+
+use crate::memory::UserProfileSummary;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static CITES_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)\[\s*cites?\s*:([^\]]*)\]").unwrap());
+static FACT_ID_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)F\s*(\d+)").unwrap());
+
+/// Prompt directive, appended only when labelled facts were included
+pub fn prompt_instruction() -> &'static str {
+    "\n\nIf you drew on any of the [F#] facts above, end your reply with a final line like \
+     [cites: F3, F12] listing only the ones you actually used. Leave it off if you used none. \
+     Never mention the labels anywhere else."
+}
+
+/// Ids of every fact in the profile (what an agent could legitimately cite)
+pub fn fact_ids(profile: &UserProfileSummary) -> Vec<i64> {
+    profile.facts_by_category.values().flatten().map(|f| f.id).collect()
+}
+
+/// Strip citation markers from a reply and return the cited ids that were actually offered
+pub fn extract(response: &str, allowed: &[i64]) -> (String, Vec<i64>) {
+    let mut cited: Vec<i64> = Vec::new();
+    for marker in CITES_RE.captures_iter(response) {
+        for id in FACT_ID_RE.captures_iter(&marker[1]) {
+            if let Ok(id) = id[1].parse::<i64>() {
+                if allowed.contains(&id) && !cited.contains(&id) {
+                    cited.push(id);
+                }
+            }
+        }
+    }
+    let cleaned = CITES_RE.replace_all(response, "").trim().to_string();
+    (cleaned, cited)
+}
@@ -0,0 +1,44 @@
+//! Read-only query console
+//!
+//! Power users can ask their own questions of the database in SQL without
+//! exporting it. Only a single SELECT (or WITH ... SELECT) is accepted, it runs
+//! on a separate read-only connection, and both the rows returned and the time
+//! it may take are capped.
+
+use crate::db::{self, QueryResult};
+use crate::logging;
+use std::time::Duration;
+
+pub const DEFAULT_MAX_ROWS: usize = 500;
+pub const MAX_ROWS: usize = 5000;
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The statement with any trailing semicolons removed, if it reads rather than writes
+fn validate(sql: &str) -> Result<&str, String> {
+    let sql = sql.trim().trim_end_matches(';').trim_end();
+    if sql.is_empty() {
+        return Err("Enter a query".to_string());
+    }
+    let first_word = sql.split_whitespace().next().unwrap_or_default().to_lowercase();
+    if first_word != "select" && first_word != "with" {
+        return Err("Only SELECT queries can run here".to_string());
+    }
+    Ok(sql)
+}
+
+pub fn run(app_handle: &tauri::AppHandle, sql: &str, max_rows: Option<usize>) -> Result<QueryResult, String> {
+    let sql = validate(sql)?;
+    let max_rows = max_rows.unwrap_or(DEFAULT_MAX_ROWS).clamp(1, MAX_ROWS);
+    let result = db::run_readonly_query(&db::get_db_path(app_handle), sql, max_rows, TIMEOUT).map_err(|e| match e {
+        rusqlite::Error::SqliteFailure(err, _) if err.code == rusqlite::ErrorCode::OperationInterrupted => {
+            format!("Query stopped after {} seconds", TIMEOUT.as_secs())
+        }
+        rusqlite::Error::MultipleStatement => "Run one statement at a time".to_string(),
+        e => e.to_string(),
+    })?;
+    logging::log_conversation(None, &format!(
+        "[CONSOLE] Query returned {} rows in {}ms{}",
+        result.rows.len(), result.elapsed_ms, if result.truncated { " (truncated)" } else { "" }
+    ));
+    Ok(result)
+}
@@ -0,0 +1,184 @@
+//! In-conversation slash commands
+//!
+//! A message starting with one of these is a control, not something to answer:
+//!
+//! - `/recall <topic>` pulls memory on the topic into view (see recall.rs)
+//! - `/mode disco|normal|auto` forces every agent's mode for this conversation
+//! - `/mute <agent>`, `/unmute <agent>` leave an agent out of the rounds
+//! - `/intensity <0-10>` sets the disco session intensity (up to the ceiling)
+//! - `/verbosity brief|standard|full|auto` fixes reply length instead of scaling it to each message
+//! - `/incognito [on|off]` stops the conversation being learned from or summarized
+//!
+//! Controls apply immediately and are recorded in the conversation as system
+//! messages with no model call behind them. Anything else starting with a slash
+//! (a path, a typo'd command) is sent as an ordinary message.
+
+use crate::db::{self, ConversationControls, Message};
+use crate::intensity;
+use crate::orchestrator::ResponseScale;
+use crate::recall;
+use chrono::Utc;
+use uuid::Uuid;
+
+pub const CONTROL_RESPONSE_TYPE: &str = "control";
+pub const MODE_DISCO: &str = "disco";
+pub const MODE_NORMAL: &str = "normal";
+
+const AGENTS: [(&str, &str); 3] = [("instinct", "snap"), ("logic", "dot"), ("psyche", "puff")];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Recall(String),
+    Mode(Option<String>),
+    Mute(String),
+    Unmute(String),
+    Intensity(f64),
+    Verbosity(Option<String>),
+    Incognito(bool),
+}
+
+/// Agent id from its id or display name ("psyche" or "puff")
+fn agent_id(name: &str) -> Option<&'static str> {
+    let name = name.trim().trim_start_matches('@').to_lowercase();
+    AGENTS.iter().find(|(id, display)| name == *id || name == *display).map(|(id, _)| *id)
+}
+
+fn display_name(agent: &str) -> String {
+    let name = AGENTS.iter().find(|(id, _)| *id == agent).map(|(_, display)| *display).unwrap_or(agent);
+    let mut chars = name.chars();
+    chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// The command in a message; None for an ordinary message, Err for a known
+/// command used wrongly (the text says how to use it)
+pub fn parse(user_message: &str) -> Option<Result<Command, String>> {
+    let trimmed = user_message.trim();
+    let rest = trimmed.strip_prefix('/')?;
+    let (name, arg) = match rest.split_once(char::is_whitespace) {
+        Some((name, arg)) => (name.to_lowercase(), arg.trim().to_lowercase()),
+        None => (rest.to_lowercase(), String::new()),
+    };
+
+    let command = match name.as_str() {
+        "recall" => Ok(Command::Recall(trimmed[1 + name.len()..].trim().to_string())),
+        "mode" => match arg.as_str() {
+            MODE_DISCO | MODE_NORMAL => Ok(Command::Mode(Some(arg))),
+            "auto" => Ok(Command::Mode(None)),
+            _ => Err("Usage: /mode disco, /mode normal, or /mode auto".to_string()),
+        },
+        "mute" | "unmute" => match agent_id(&arg) {
+            Some(agent) if name == "mute" => Ok(Command::Mute(agent.to_string())),
+            Some(agent) => Ok(Command::Unmute(agent.to_string())),
+            None => Err(format!("Usage: /{} snap, dot, or puff", name)),
+        },
+        "intensity" => match arg.parse::<f64>() {
+            Ok(level) if (0.0..=10.0).contains(&level) => Ok(Command::Intensity(level / 10.0)),
+            _ => Err("Usage: /intensity 0-10".to_string()),
+        },
+        "verbosity" => match arg.as_str() {
+            "brief" | "standard" | "full" => Ok(Command::Verbosity(Some(arg))),
+            "auto" => Ok(Command::Verbosity(None)),
+            _ => Err("Usage: /verbosity brief, standard, full, or auto".to_string()),
+        },
+        "incognito" => match arg.as_str() {
+            "" | "on" => Ok(Command::Incognito(true)),
+            "off" => Ok(Command::Incognito(false)),
+            _ => Err("Usage: /incognito on or /incognito off".to_string()),
+        },
+        _ => return None,
+    };
+    Some(command)
+}
+
+/// Reply length forced by `/verbosity`, if any
+pub fn verbosity_scale(controls: &ConversationControls) -> Option<ResponseScale> {
+    match controls.verbosity.as_deref() {
+        Some("brief") => Some(ResponseScale::Brief),
+        Some("standard") => Some(ResponseScale::Standard),
+        Some("full") => Some(ResponseScale::Full),
+        _ => None,
+    }
+}
+
+/// Agents and disco agents for a round once the conversation's mode and mutes are applied
+pub fn apply_to_round(
+    controls: &ConversationControls,
+    active_agents: Vec<String>,
+    disco_agents: Vec<String>,
+) -> (Vec<String>, Vec<String>) {
+    let active: Vec<String> = active_agents.into_iter()
+        .filter(|a| !controls.muted_agents.contains(a))
+        .collect();
+    let disco = match controls.mode.as_deref() {
+        Some(MODE_DISCO) => active.clone(),
+        Some(MODE_NORMAL) => Vec::new(),
+        _ => disco_agents.into_iter().filter(|a| active.contains(a)).collect(),
+    };
+    (active, disco)
+}
+
+/// Whether the conversation has been made incognito
+pub fn is_incognito(conversation_id: &str) -> bool {
+    db::get_conversation_controls(conversation_id).map(|c| c.incognito).unwrap_or(false)
+}
+
+/// Apply a command and record it in the conversation
+pub fn handle(conversation_id: &str, command: Command) -> Result<Message, String> {
+    let mut controls = db::get_conversation_controls(conversation_id).map_err(|e| e.to_string())?;
+    let confirmation = match command {
+        Command::Recall(topic) => return recall::handle(conversation_id, &topic),
+        Command::Mode(mode) => {
+            let text = match mode.as_deref() {
+                Some(MODE_DISCO) => "Disco mode on for every agent in this conversation.".to_string(),
+                Some(_) => "Disco mode off for every agent in this conversation.".to_string(),
+                None => "Agent modes follow the toggles again.".to_string(),
+            };
+            controls.mode = mode;
+            text
+        }
+        Command::Mute(agent) => {
+            if !controls.muted_agents.contains(&agent) {
+                controls.muted_agents.push(agent.clone());
+            }
+            format!("{} is muted in this conversation.", display_name(&agent))
+        }
+        Command::Unmute(agent) => {
+            controls.muted_agents.retain(|a| *a != agent);
+            format!("{} is back.", display_name(&agent))
+        }
+        Command::Intensity(level) => {
+            let level = level.min(intensity::ceiling());
+            db::set_conversation_intensity(conversation_id, level).map_err(|e| e.to_string())?;
+            format!("Intensity set to {:.0}/10 ({}).", level * 10.0, intensity::label(level))
+        }
+        Command::Verbosity(verbosity) => {
+            let text = match verbosity.as_deref() {
+                Some(v) => format!("Replies will be {} from here on.", v),
+                None => "Reply length follows your messages again.".to_string(),
+            };
+            controls.verbosity = verbosity;
+            text
+        }
+        Command::Incognito(on) => {
+            controls.incognito = on;
+            if on {
+                "Incognito: nothing from this conversation will be remembered or summarized.".to_string()
+            } else {
+                "Incognito off: this conversation can be learned from again.".to_string()
+            }
+        }
+    };
+    db::set_conversation_controls(conversation_id, &controls).map_err(|e| e.to_string())?;
+
+    let message = Message {
+        id: Uuid::new_v4().to_string(),
+        conversation_id: conversation_id.to_string(),
+        role: "system".to_string(),
+        content: confirmation,
+        response_type: Some(CONTROL_RESPONSE_TYPE.to_string()),
+        timestamp: Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+    db::save_message(&message).map_err(|e| e.to_string())?;
+    Ok(message)
+}
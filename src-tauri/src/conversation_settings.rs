@@ -0,0 +1,53 @@
+//! Per-conversation settings
+//!
+//! A conversation can pin its own model tier, force disco or normal mode for
+//! every agent, and carry its own agent weights, so one thread can stay blunt
+//! Disco-Logic while another stays gentle. The chat pipeline reads them on
+//! every round: the tier through model routing, the mode through the round's
+//! controls (the same field `/mode` sets), and the weights in place of the
+//! profile's before session boosts are added.
+
+use crate::controls::{MODE_DISCO, MODE_NORMAL};
+use crate::db::{self, ConversationSettings};
+use crate::model_routing;
+
+/// Check and normalize overrides before they're saved. Weights are all three or
+/// none, non-negative, and scaled to sum to 1 like the profile's.
+pub fn validate(mut settings: ConversationSettings) -> Result<ConversationSettings, String> {
+    if let Some(tier) = &settings.model_tier {
+        model_routing::validate_tier(tier)?;
+    }
+    if let Some(mode) = &settings.mode {
+        if mode != MODE_DISCO && mode != MODE_NORMAL {
+            return Err(format!("Unknown mode: {}", mode));
+        }
+    }
+    match (settings.instinct_weight, settings.logic_weight, settings.psyche_weight) {
+        (None, None, None) => {}
+        (Some(instinct), Some(logic), Some(psyche)) => {
+            if [instinct, logic, psyche].iter().any(|w| !w.is_finite() || *w < 0.0) {
+                return Err("Agent weights can't be negative".to_string());
+            }
+            let total = instinct + logic + psyche;
+            if total <= 0.0 {
+                return Err("At least one agent needs some weight".to_string());
+            }
+            settings.instinct_weight = Some(instinct / total);
+            settings.logic_weight = Some(logic / total);
+            settings.psyche_weight = Some(psyche / total);
+        }
+        _ => return Err("Set all three agent weights, or none".to_string()),
+    }
+    Ok(settings)
+}
+
+pub fn save(settings: ConversationSettings) -> Result<ConversationSettings, String> {
+    let settings = validate(settings)?;
+    db::set_conversation_settings(&settings).map_err(|e| e.to_string())?;
+    db::get_conversation_settings(&settings.conversation_id).map_err(|e| e.to_string())
+}
+
+/// The conversation's weight override, if it has one: (instinct, logic, psyche)
+pub fn weights(settings: &ConversationSettings) -> Option<(f64, f64, f64)> {
+    Some((settings.instinct_weight?, settings.logic_weight?, settings.psyche_weight?))
+}
@@ -0,0 +1,236 @@
+//! In-chat corrections to remembered facts
+//!
+//! "No, I actually moved to Denver" shouldn't require a trip to settings. A cheap
+//! marker check flags messages that look like corrections; only those go to Haiku,
+//! which picks the remembered fact being corrected (if any) and whether to update
+//! or forget it. The change is applied before the agents see the profile, logged
+//! in `fact_corrections`, and confirmed in the conversation. Facts edited or
+//! deleted by hand from the memory view are logged the same way, with no
+//! conversation attached.
+
+use crate::anthropic::{AnthropicClient, AnthropicMessage, ThinkingBudget, CLAUDE_HAIKU};
+use crate::db::{self, FactCorrection, UserFact};
+use crate::logging;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use ts_rs::TS;
+
+pub const ACTION_UPDATE: &str = "update";
+pub const ACTION_DELETE: &str = "delete";
+
+const CORRECTION_MARKERS: &[&str] = &[
+    "that's wrong", "thats wrong", "that's not true", "thats not true", "not true",
+    "that's not right", "thats not right", "you got it wrong", "you've got it wrong",
+    "you remembered wrong", "you're remembering wrong", "i never said", "i didn't say",
+    "no, i actually", "no i actually", "actually, i", "actually i", "not anymore",
+    "i no longer", "i don't anymore", "that's outdated", "thats outdated", "correction:",
+];
+
+#[derive(Debug, Deserialize)]
+struct CorrectionDecision {
+    fact_id: Option<i64>,
+    action: String,
+    new_value: Option<String>,
+}
+
+/// What changed, plus the in-channel confirmation
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct CorrectionOutcome {
+    pub fact_id: i64,
+    pub action: String,
+    pub key: String,
+    pub old_value: String,
+    pub new_value: Option<String>,
+    pub confirmation: String,
+}
+
+/// Cheap pre-filter so ordinary messages never cost a model call
+pub fn looks_like_correction(user_message: &str) -> bool {
+    let lower = user_message.to_lowercase();
+    CORRECTION_MARKERS.iter().any(|m| lower.contains(m))
+}
+
+pub struct CorrectionHandler {
+    client: AnthropicClient,
+}
+
+impl CorrectionHandler {
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            client: AnthropicClient::new(api_key).with_usage_category("correction"),
+        }
+    }
+
+    /// Find and apply the correction a message makes to the given facts, if any
+    pub async fn handle(
+        &self,
+        conversation_id: &str,
+        user_message: &str,
+        facts: &[UserFact],
+    ) -> Result<Option<CorrectionOutcome>, Box<dyn Error + Send + Sync>> {
+        if facts.is_empty() {
+            return Ok(None);
+        }
+
+        let fact_list = facts
+            .iter()
+            .map(|f| format!("{}. [{}] {}: {}", f.id, f.category, f.key, f.value))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let system_prompt = r#"You maintain what Intersect remembers about the user. The user may be correcting one of the remembered facts below.
+
+Decide:
+- Which fact (by number) the user is correcting, if any
+- "update" if they gave the right information (new_value = the corrected value, same style as the old one)
+- "delete" if the fact is simply wrong or no longer true and there's nothing to replace it with
+- "none" if the message isn't correcting a remembered fact (disagreeing with an agent's opinion is NOT a correction)
+
+Respond with ONLY valid JSON in this exact format:
+{"fact_id": 12, "action": "update", "new_value": "..."}"#;
+
+        let messages = vec![AnthropicMessage {
+            role: "user".to_string(),
+            content: format!("REMEMBERED FACTS:\n{}\n\nUSER MESSAGE:\n{}", fact_list, user_message),
+        }];
+
+        let response = self.client.chat_completion_advanced(
+            CLAUDE_HAIKU,
+            Some(system_prompt),
+            messages,
+            0.0,
+            Some(150),
+            ThinkingBudget::None,
+        ).await?;
+
+        let cleaned = response
+            .trim()
+            .trim_start_matches("```json")
+            .trim_end_matches("```")
+            .trim();
+
+        let decision: CorrectionDecision = match serde_json::from_str(cleaned) {
+            Ok(d) => d,
+            Err(e) => {
+                logging::log_error(Some(conversation_id), &format!(
+                    "Failed to parse correction JSON: {}. Response: {}", e, cleaned.chars().take(200).collect::<String>()
+                ));
+                return Ok(None);
+            }
+        };
+
+        let Some(fact) = decision.fact_id.and_then(|id| facts.iter().find(|f| f.id == id)) else {
+            return Ok(None);
+        };
+        let new_value = decision.new_value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        let (action, confirmation) = match (decision.action.as_str(), &new_value) {
+            (ACTION_UPDATE, Some(value)) => {
+                db::update_user_fact_value(fact.id, value, Some(conversation_id))?;
+                (ACTION_UPDATE, format!(
+                    "Got it -- I had \"{}\" down as {}. Updated to {}.",
+                    fact.key.replace('_', " "), fact.value, value
+                ))
+            }
+            (ACTION_DELETE, _) | (ACTION_UPDATE, None) => {
+                db::delete_user_fact(fact.id)?;
+                (ACTION_DELETE, format!(
+                    "Got it -- I've forgotten that ({}: {}).",
+                    fact.key.replace('_', " "), fact.value
+                ))
+            }
+            _ => return Ok(None),
+        };
+
+        let _ = db::save_fact_correction(&FactCorrection {
+            id: 0,
+            fact_id: fact.id,
+            category: fact.category.clone(),
+            key: fact.key.clone(),
+            old_value: fact.value.clone(),
+            new_value: if action == ACTION_UPDATE { new_value.clone() } else { None },
+            action: action.to_string(),
+            conversation_id: Some(conversation_id.to_string()),
+            user_message: user_message.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+        });
+
+        logging::log_memory(Some(conversation_id), &format!(
+            "[CORRECTION] {} fact {} ({}: {})", action, fact.id, fact.key, fact.value
+        ));
+
+        Ok(Some(CorrectionOutcome {
+            fact_id: fact.id,
+            action: action.to_string(),
+            key: fact.key.clone(),
+            old_value: fact.value.clone(),
+            new_value: if action == ACTION_UPDATE { new_value } else { None },
+            confirmation,
+        }))
+    }
+}
+
+// ============ Manual Edits ============
+
+fn log_manual_change(fact: &UserFact, action: &str, new_value: Option<String>) {
+    let _ = db::save_fact_correction(&FactCorrection {
+        id: 0,
+        fact_id: fact.id,
+        category: fact.category.clone(),
+        key: fact.key.clone(),
+        old_value: fact.value.clone(),
+        new_value,
+        action: action.to_string(),
+        conversation_id: None,
+        user_message: String::new(),
+        created_at: Utc::now().to_rfc3339(),
+    });
+    logging::log_memory(None, &format!("[CORRECTION] {} fact {} by hand ({}: {})", action, fact.id, fact.key, fact.value));
+}
+
+/// Fix a remembered fact's value and how sure Archie is of it
+pub fn update_fact(fact_id: i64, value: &str, confidence: f64) -> Result<UserFact, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("A fact needs a value -- delete it instead".to_string());
+    }
+    if !(0.0..=1.0).contains(&confidence) {
+        return Err("Confidence must be between 0 and 1".to_string());
+    }
+    let fact = db::get_user_fact(fact_id).map_err(|e| e.to_string())?.ok_or("Fact not found")?;
+    db::update_user_fact(fact_id, value, confidence).map_err(|e| e.to_string())?;
+    log_manual_change(&fact, ACTION_UPDATE, Some(value.to_string()));
+    db::get_user_fact(fact_id).map_err(|e| e.to_string())?.ok_or_else(|| "Fact not found".to_string())
+}
+
+/// Settle a conflict: keep the remembered value, or take the incoming one
+pub fn resolve_conflict(conflict_id: i64, keep_incoming: bool) -> Result<UserFact, String> {
+    let conflict = db::get_fact_conflict(conflict_id).map_err(|e| e.to_string())?.ok_or("Conflict not found")?;
+    if keep_incoming {
+        let confidence = conflict.fact.confidence.max(conflict.confidence);
+        db::update_user_fact(conflict.fact.id, &conflict.value, confidence).map_err(|e| e.to_string())?;
+        log_manual_change(&conflict.fact, ACTION_UPDATE, Some(conflict.value.clone()));
+    }
+    db::delete_fact_conflict(conflict_id).map_err(|e| e.to_string())?;
+    db::get_user_fact(conflict.fact.id).map_err(|e| e.to_string())?.ok_or_else(|| "Fact not found".to_string())
+}
+
+/// Put a fact back to a value it had before
+pub fn rollback_fact(history_id: i64) -> Result<UserFact, String> {
+    let entry = db::get_fact_history_entry(history_id).map_err(|e| e.to_string())?.ok_or("History entry not found")?;
+    let fact = db::get_user_fact(entry.fact_id).map_err(|e| e.to_string())?
+        .ok_or("That fact is no longer remembered")?;
+    db::restore_fact_version(&entry).map_err(|e| e.to_string())?;
+    log_manual_change(&fact, ACTION_UPDATE, Some(entry.value.clone()));
+    db::get_user_fact(entry.fact_id).map_err(|e| e.to_string())?.ok_or_else(|| "Fact not found".to_string())
+}
+
+/// Forget a remembered fact
+pub fn delete_fact(fact_id: i64) -> Result<(), String> {
+    let fact = db::get_user_fact(fact_id).map_err(|e| e.to_string())?.ok_or("Fact not found")?;
+    db::delete_user_fact(fact_id).map_err(|e| e.to_string())?;
+    log_manual_change(&fact, ACTION_DELETE, None);
+    Ok(())
+}
@@ -1,15 +1,37 @@
+use crate::keychain;
+use crate::logging;
+use crate::profiles;
+use crate::usage::MessageCost;
 use chrono::Utc;
+use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{Connection, Result, params, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use std::sync::Mutex;
-use once_cell::sync::Lazy;
+use std::sync::RwLock;
+use std::time::Duration;
+use once_cell::sync::OnceCell;
 use tauri::Manager;
+use ts_rs::TS;
 
-// Database connection singleton
-static DB: Lazy<Mutex<Option<Connection>>> = Lazy::new(|| Mutex::new(None));
+pub type DbPool = r2d2::Pool<SqliteConnectionManager>;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Connections held at most; WAL lets them read while one of them writes
+const POOL_SIZE: u32 = 8;
+/// How long a query waits for a free connection (or for a write lock) before failing
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The connection pool, kept in Tauri managed state. It's empty until
+/// `init_database` runs and again after `close_database`.
+#[derive(Default)]
+pub struct DbState {
+    pool: RwLock<Option<DbPool>>,
+}
+
+// The query functions don't take an app handle, so they reach the managed pool through this one
+static APP_HANDLE: OnceCell<tauri::AppHandle> = OnceCell::new();
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
 pub struct UserProfile {
     pub id: i64,
     pub api_key: Option<String>,
@@ -32,9 +54,43 @@ pub struct Conversation {
     pub is_disco: bool,
     pub created_at: String,
     pub updated_at: String,
+    pub intensity: f64,             // Session disco intensity (0.0-1.0), ramps with engagement
+    pub closed_at: Option<String>,  // Set when auto-closed for inactivity; cleared on new messages
+    pub pinned: bool,               // Pinned conversations list first
+    pub project_id: Option<i64>,    // Project the conversation is filed under, if any
+    pub locked: bool,               // Read-only: no new messages, edits, or deletion until unlocked
+    pub parent_conversation_id: Option<String>, // The conversation this was forked from, if any
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Per-conversation controls set with slash commands (see controls.rs)
+#[derive(Debug, Serialize, Deserialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct ConversationControls {
+    pub mode: Option<String>,       // 'disco' | 'normal' forces every agent's mode; None = as sent
+    pub muted_agents: Vec<String>,  // Agents left out of this conversation's rounds
+    pub verbosity: Option<String>,  // 'brief' | 'standard' | 'full'; None = scaled to each message
+    pub incognito: bool,            // Nothing from this conversation is learned or summarized
+}
+
+/// Per-conversation model, mode and weight overrides (see conversation_settings.rs)
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, TS)]
+#[ts(export)]
+pub struct ConversationSettings {
+    pub conversation_id: String,
+    pub model_tier: Option<String>,     // 'economy' | 'premium'; None = the agents' model policies
+    pub mode: Option<String>,           // 'disco' | 'normal' for every agent; None = as sent
+    pub instinct_weight: Option<f64>,   // Weights replace the profile's for routing when set (all three or none)
+    pub logic_weight: Option<f64>,
+    pub psyche_weight: Option<f64>,
+    pub updated_at: Option<String>,     // None while nothing is overridden
+}
+
+pub const MESSAGE_STATUS_PENDING: &str = "pending";
+pub const MESSAGE_STATUS_SENT: &str = "sent";
+pub const MESSAGE_STATUS_FAILED: &str = "failed";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, TS)]
+#[ts(export)]
 pub struct Message {
     pub id: String,
     pub conversation_id: String,
@@ -43,9 +99,54 @@ pub struct Message {
     pub response_type: Option<String>,
     pub references_message_id: Option<String>,
     pub timestamp: String,
+    pub status: Option<String>,     // User messages: 'pending' | 'sent' | 'failed'; agent stubs: 'failed'
+    pub round_id: Option<String>,   // Id of the user message whose round produced this message
+    pub latency_ms: Option<i64>,    // Generation time for agent/governor messages (used by replay)
+    pub input_tokens: Option<i64>,  // Tokens of the call that produced this message (agent/governor)
+    pub output_tokens: Option<i64>,
+    pub cost_usd: Option<f64>,      // Estimated from the static price table
+    pub model: Option<String>,      // Model that produced this message (agent/governor)
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl Message {
+    /// Attach the model, tokens and cost of the call that produced this message
+    pub fn with_cost(self, cost: Option<MessageCost>) -> Self {
+        Message {
+            input_tokens: cost.as_ref().map(|c| c.input_tokens),
+            output_tokens: cost.as_ref().map(|c| c.output_tokens),
+            cost_usd: cost.as_ref().map(|c| c.cost_usd),
+            model: cost.map(|c| c.model),
+            ..self
+        }
+    }
+}
+
+/// A previous version of an edited message
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct MessageRevision {
+    pub id: i64,
+    pub message_id: String,
+    pub content: String,            // The text as it was before the edit
+    pub revised_at: String,         // When it was replaced
+}
+
+/// A file attached to a message. The bytes are fetched separately (`get_attachment_data`).
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct Attachment {
+    pub id: String,
+    pub message_id: String,
+    pub filename: String,
+    pub mime_type: String,
+    pub path: Option<String>,           // Where the file was attached from; the only copy when it was too big to store
+    pub size_bytes: i64,
+    pub extracted_text: Option<String>, // Text given to the agents alongside the message
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
 pub struct UserContext {
     pub id: i64,
     pub key: String,
@@ -57,7 +158,8 @@ pub struct UserContext {
 
 // ============ Memory System Structs ============
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
 pub struct UserFact {
     pub id: i64,
     pub category: String,           // "personal", "preferences", "work", "relationships", "values"
@@ -69,9 +171,21 @@ pub struct UserFact {
     pub first_mentioned: String,
     pub last_confirmed: String,
     pub mention_count: i64,
+    #[serde(default)]
+    pub valid_until: Option<String>, // Transient facts stop reaching prompts after this (RFC 3339); None = lasting
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// A fact decay retired from long-term memory
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ArchivedFact {
+    #[serde(flatten)]
+    pub fact: UserFact,
+    pub archived_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
 pub struct UserPattern {
     pub id: i64,
     pub pattern_type: String,       // "communication_style", "emotional_tendency", "thinking_mode", "recurring_theme"
@@ -81,8 +195,17 @@ pub struct UserPattern {
     pub first_observed: String,
     pub last_updated: String,
     pub observation_count: i64,
+    #[serde(default)]
+    pub verdict: Option<String>,    // User's call: 'confirmed' | 'denied' | 'unsure' (None = not reviewed)
 }
 
+pub const PATTERN_CONFIRMED: &str = "confirmed";
+pub const PATTERN_DENIED: &str = "denied";
+pub const PATTERN_UNSURE: &str = "unsure";
+
+/// Confidence a user-confirmed pattern never drops below in prompts
+pub const CONFIRMED_PATTERN_FLOOR: f64 = 0.8;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ConversationSummary {
     pub id: i64,
@@ -96,7 +219,8 @@ pub struct ConversationSummary {
     pub created_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
 pub struct RecurringTheme {
     pub id: i64,
     pub theme: String,
@@ -105,9 +229,19 @@ pub struct RecurringTheme {
     pub related_conversations: Option<String>, // JSON array of conversation IDs
 }
 
+/// New themes a single conversation can add; further ones only count if they already exist
+const MAX_THEMES_PER_CONVERSATION: usize = 5;
+/// Mentions a theme needs before it's used in prompts
+pub const MIN_THEME_FREQUENCY: i64 = 3;
+const MAX_THEME_WORDS: usize = 4;
+const MAX_THEME_CHARS: usize = 40;
+/// Words that don't tell two themes apart ("stress at work" is "work stress")
+const THEME_FILLER_WORDS: [&str; 8] = ["at", "of", "and", "with", "in", "for", "about", "to"];
+
 // ============ Multi-Profile System ============
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
 pub struct PersonaProfile {
     pub id: String,
     pub name: String,
@@ -126,1090 +260,7379 @@ pub struct PersonaProfile {
     pub updated_at: String,
 }
 
-fn get_db_path(app_handle: &tauri::AppHandle) -> PathBuf {
-    let app_data_dir = app_handle.path().app_data_dir().expect("Failed to get app data dir");
-    std::fs::create_dir_all(&app_data_dir).expect("Failed to create app data dir");
-    app_data_dir.join("intersect.db")
+// ============ Grounding Exercises ============
+
+/// How an agent is presented in the UI (normal and Disco Mode variants)
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct AgentPresentation {
+    pub id: String,                 // 'instinct' | 'logic' | 'psyche' | 'governor' | custom id
+    pub display_name: String,
+    pub disco_name: Option<String>,
+    pub color: String,              // Hex, e.g. '#E07A5F'
+    pub disco_color: Option<String>,
+    pub icon: String,               // Asset key the frontend resolves to an image
+    pub disco_icon: Option<String>,
+    pub description: String,
+    pub disco_description: Option<String>,
+    pub voice_samples: String,      // JSON array of sample lines
+    pub sort_order: i64,
+    pub is_builtin: bool,
+    pub updated_at: String,
 }
 
-pub fn init_database(app_handle: &tauri::AppHandle) -> Result<()> {
-    let db_path = get_db_path(app_handle);
-    let conn = Connection::open(&db_path)?;
-    
-    // Create tables
-    conn.execute_batch(
-        "
-        -- User profile with evolving weights
-        CREATE TABLE IF NOT EXISTS user_profile (
-            id INTEGER PRIMARY KEY,
-            api_key TEXT,
-            anthropic_key TEXT,
-            instinct_weight REAL DEFAULT 0.33,
-            logic_weight REAL DEFAULT 0.33,
-            psyche_weight REAL DEFAULT 0.34,
-            total_messages INTEGER DEFAULT 0,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
-        );
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct GroundingExercise {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub steps: String,              // JSON array of ExerciseStep
+    pub is_builtin: bool,
+    pub created_at: String,
+}
 
-        -- Conversation sessions
-        CREATE TABLE IF NOT EXISTS conversations (
-            id TEXT PRIMARY KEY,
-            title TEXT,
-            summary TEXT,
-            limbo_summary TEXT,
-            processed INTEGER DEFAULT 0,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
-        );
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ExerciseSession {
+    pub id: String,
+    pub exercise_id: String,
+    pub conversation_id: Option<String>,
+    pub intro: Option<String>,
+    pub outro: Option<String>,
+    pub started_at: String,
+    pub completed_at: Option<String>,
+}
 
-        -- Messages with agent attribution
-        CREATE TABLE IF NOT EXISTS messages (
-            id TEXT PRIMARY KEY,
-            conversation_id TEXT NOT NULL,
-            role TEXT NOT NULL,
-            content TEXT NOT NULL,
-            response_type TEXT,
-            references_message_id TEXT,
-            timestamp TEXT NOT NULL,
-            FOREIGN KEY (conversation_id) REFERENCES conversations(id)
-        );
+// ============ Proactive Messages ============
 
-        -- Learned user context (legacy, kept for compatibility)
-        CREATE TABLE IF NOT EXISTS user_context (
-            id INTEGER PRIMARY KEY,
-            key TEXT UNIQUE NOT NULL,
-            value TEXT NOT NULL,
-            confidence REAL DEFAULT 0.5,
-            source_agent TEXT,
-            updated_at TEXT NOT NULL
-        );
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ProactiveMessage {
+    pub id: String,
+    pub conversation_id: Option<String>,
+    pub agent: String,
+    pub content: String,
+    pub topic_key: String,          // Nudges about the same thing share a key ("ship-launch")
+    pub scheduled_for: String,
+    pub status: String,             // 'pending' | 'delivered' | 'seen' | 'responded' | 'dismissed' | 'suppressed'
+    pub delivered_at: Option<String>,
+    pub seen_at: Option<String>,
+    pub responded_at: Option<String>,
+    pub suppressed_count: i64,      // Later nudges folded into this one while it went unanswered
+    pub created_at: String,
+    pub user_requested: bool,       // Explicit user reminder -- overrides quiet hours / DND
+}
 
-        -- User facts (explicit statements about the user)
-        CREATE TABLE IF NOT EXISTS user_facts (
-            id INTEGER PRIMARY KEY,
-            category TEXT NOT NULL,
-            key TEXT NOT NULL,
-            value TEXT NOT NULL,
-            confidence REAL DEFAULT 1.0,
-            source_type TEXT NOT NULL,
-            source_conversation_id TEXT,
-            first_mentioned TEXT NOT NULL,
-            last_confirmed TEXT NOT NULL,
-            mention_count INTEGER DEFAULT 1,
-            UNIQUE(category, key)
-        );
+// ============ Memory Staging ============
 
-        -- Inferred patterns (behavioral/personality observations)
-        CREATE TABLE IF NOT EXISTS user_patterns (
-            id INTEGER PRIMARY KEY,
-            pattern_type TEXT NOT NULL,
-            description TEXT NOT NULL,
-            confidence REAL DEFAULT 0.5,
-            evidence TEXT NOT NULL,
-            first_observed TEXT NOT NULL,
-            last_updated TEXT NOT NULL,
-            observation_count INTEGER DEFAULT 1
-        );
+pub const STAGED_FACT: &str = "fact";
+pub const STAGED_PATTERN: &str = "pattern";
 
-        -- Conversation summaries (token-efficient history)
-        CREATE TABLE IF NOT EXISTS conversation_summaries (
-            id INTEGER PRIMARY KEY,
-            conversation_id TEXT NOT NULL,
-            summary TEXT NOT NULL,
-            key_topics TEXT NOT NULL,
-            emotional_tone TEXT,
-            user_state TEXT,
-            agents_involved TEXT NOT NULL,
-            message_count INTEGER,
-            created_at TEXT NOT NULL,
-            FOREIGN KEY (conversation_id) REFERENCES conversations(id)
-        );
+/// A newly extracted fact or pattern waiting for promotion to long-term memory
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct StagedMemory {
+    pub id: i64,
+    pub kind: String,               // 'fact' | 'pattern'
+    pub category: String,           // Fact category or pattern type
+    pub key: String,                // Fact key or pattern description
+    pub value: String,              // Fact value or pattern evidence
+    pub confidence: f64,
+    pub source_type: Option<String>,
+    pub source_conversation_id: Option<String>,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub confirmations: i64,         // Distinct conversations it was extracted from
+    pub held_by_import: Option<String>, // Archive import awaiting review; never auto-promoted
+}
 
-        -- Cross-conversation recurring themes
-        CREATE TABLE IF NOT EXISTS recurring_themes (
-            id INTEGER PRIMARY KEY,
-            theme TEXT NOT NULL UNIQUE,
-            frequency INTEGER DEFAULT 1,
-            last_mentioned TEXT NOT NULL,
-            related_conversations TEXT
-        );
+// ============ Documents ============
 
-        -- Persona profiles (multiple user states/modes)
-        CREATE TABLE IF NOT EXISTS persona_profiles (
-            id TEXT PRIMARY KEY,
-            name TEXT NOT NULL,
-            is_default INTEGER DEFAULT 0,
-            is_active INTEGER DEFAULT 0,
-            dominant_trait TEXT NOT NULL,
-            secondary_trait TEXT NOT NULL,
-            instinct_weight REAL DEFAULT 0.2,
-            logic_weight REAL DEFAULT 0.5,
-            psyche_weight REAL DEFAULT 0.3,
-            message_count INTEGER DEFAULT 0,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
-        );
-        "
-    )?;
-    
-    // Migration: Add anthropic_key column if it doesn't exist
-    let has_anthropic_key: bool = conn.query_row(
-        "SELECT COUNT(*) FROM pragma_table_info('user_profile') WHERE name='anthropic_key'",
-        [],
-        |row| Ok(row.get::<_, i64>(0)? > 0)
-    ).unwrap_or(false);
-    
-    if !has_anthropic_key {
-        let _ = conn.execute("ALTER TABLE user_profile ADD COLUMN anthropic_key TEXT", []);
-    }
-    
-    // Migration: Add message_count column to persona_profiles if it doesn't exist
-    let has_persona_message_count: bool = conn.query_row(
-        "SELECT COUNT(*) FROM pragma_table_info('persona_profiles') WHERE name='message_count'",
-        [],
-        |row| Ok(row.get::<_, i64>(0)? > 0)
-    ).unwrap_or(false);
-    
-    if !has_persona_message_count {
-        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN message_count INTEGER DEFAULT 0", []);
-    }
-    
-    // Migration: Add limbo_summary and processed columns to conversations table
-    let has_limbo_summary: bool = conn.query_row(
-        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='limbo_summary'",
-        [],
-        |row| Ok(row.get::<_, i64>(0)? > 0)
-    ).unwrap_or(false);
-    
-    if !has_limbo_summary {
-        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN limbo_summary TEXT", []);
-        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN processed INTEGER DEFAULT 0", []);
-    }
-    
-    // Migration: Add is_disco column to conversations table for conversation-level disco mode
-    let has_is_disco: bool = conn.query_row(
-        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='is_disco'",
-        [],
-        |row| Ok(row.get::<_, i64>(0)? > 0)
-    ).unwrap_or(false);
-    
-    if !has_is_disco {
-        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN is_disco INTEGER DEFAULT 0", []);
-    }
-    
-    // Migration: Add points columns to persona_profiles table
-    let has_instinct_points: bool = conn.query_row(
-        "SELECT COUNT(*) FROM pragma_table_info('persona_profiles') WHERE name='instinct_points'",
-        [],
-        |row| Ok(row.get::<_, i64>(0)? > 0)
-    ).unwrap_or(false);
-    
-    if !has_instinct_points {
-        // Add columns with defaults: 4, 4, 3 (total 11)
-        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN instinct_points INTEGER DEFAULT 4", []);
-        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN logic_points INTEGER DEFAULT 4", []);
-        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN psyche_points INTEGER DEFAULT 3", []);
-        
-        // For existing profiles, initialize points based on current weights
-        // Convert weights to points: points = round(weight * 11), but ensure valid range (2-6) and total = 11
-        let _ = conn.execute(
-            "UPDATE persona_profiles SET instinct_points = CAST(ROUND(instinct_weight * 11) AS INTEGER), logic_points = CAST(ROUND(logic_weight * 11) AS INTEGER), psyche_points = CAST(ROUND(psyche_weight * 11) AS INTEGER)",
-            []
-        );
-        
-        // Ensure points are in valid range (2-6) and total = 11
-        // Clamp each to 2-6 range, then normalize total to 11
-        let _ = conn.execute(
-            "UPDATE persona_profiles SET 
-                instinct_points = MAX(2, MIN(6, instinct_points)),
-                logic_points = MAX(2, MIN(6, logic_points)),
-                psyche_points = MAX(2, MIN(6, psyche_points))",
-            []
-        );
-        
-        // Normalize totals to 11 (this is approximate, but close enough for migration)
-        // We'll fix exact totals in a separate pass if needed
-    }
-    
-    // Ensure a user profile exists (for API keys and message count)
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM user_profile",
-        [],
-        |row| row.get(0)
-    )?;
-    
-    if count == 0 {
-        let now = Utc::now().to_rfc3339();
-        // Default weights: Logic 50%, Psyche 30%, Instinct 20%
-        conn.execute(
-            "INSERT INTO user_profile (api_key, instinct_weight, logic_weight, psyche_weight, total_messages, created_at, updated_at)
-             VALUES (NULL, 0.20, 0.50, 0.30, 0, ?1, ?2)",
-            params![now, now]
-        )?;
-    }
-    
-    // Ensure exactly 3 fixed profiles exist (Logic, Instinct, Psyche)
-    // Each profile is dominant for one trait at 40%, others at 30%
-    let now = Utc::now().to_rfc3339();
-    
-    // Check for each required profile by dominant_trait
-    let has_logic: bool = conn.query_row(
-        "SELECT COUNT(*) FROM persona_profiles WHERE dominant_trait = 'logic'",
-        [],
-        |row| Ok(row.get::<_, i64>(0)? > 0)
-    ).unwrap_or(false);
+pub const DOCUMENT_SOURCE_NOTES: &str = "notes_folder";
+pub const DOCUMENT_SOURCE_DISTILLED: &str = "distilled";
+
+/// A text document ingested into the document store
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct Document {
+    pub id: String,
+    pub source: String,             // Where it came from ('notes_folder' | 'distilled')
+    pub path: String,               // Absolute file path, or distilled://<conversation>/<format>; unique
+    pub title: String,
+    pub content: String,
+    pub file_modified_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// ============ Archive Imports ============
+
+pub const IMPORT_PENDING: &str = "pending";
+pub const IMPORT_RUNNING: &str = "running";
+pub const IMPORT_PAUSED: &str = "paused";
+pub const IMPORT_REVIEW: &str = "review";
+pub const IMPORT_FAILED: &str = "failed";
+pub const IMPORT_RELEASED: &str = "released";
+pub const IMPORT_DISCARDED: &str = "discarded";
+
+/// An imported chat archive and the state of its background analysis
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ArchiveImport {
+    pub id: String,
+    pub source_path: String,
+    pub conversation_count: i64,
+    pub message_count: i64,
+    pub processed_count: i64,       // Conversations analyzed so far
+    pub estimated_cost_usd: f64,    // Up-front estimate for the whole analysis
+    pub cost_usd: f64,              // Actual spend so far
+    pub status: String,             // pending | running | paused | review | failed | released | discarded
+    pub themes: String,             // JSON map of theme -> mentions, collected while analyzing
+    pub error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// ============ Fact Corrections ============
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct FactCorrection {
+    pub id: i64,
+    pub fact_id: i64,
+    pub category: String,
+    pub key: String,
+    pub old_value: String,
+    pub new_value: Option<String>,  // None when the fact was deleted
+    pub action: String,             // 'update' | 'delete'
+    pub conversation_id: Option<String>,
+    pub user_message: String,
+    pub created_at: String,
+}
+
+// ============ Fact History ============
+
+/// A value a fact held before it was changed
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct FactHistoryEntry {
+    pub id: i64,
+    pub fact_id: i64,
+    pub category: String,
+    pub key: String,
+    pub value: String,              // The value before the change
+    pub confidence: f64,
+    pub source_type: String,
+    pub conversation_id: Option<String>, // The conversation that changed it (None for edits by hand)
+    pub changed_at: String,
+}
+
+// ============ Fact Conflicts ============
+
+/// An incoming value that disagreed with a remembered fact; both are kept
+/// until the user picks one
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct FactConflict {
+    pub id: i64,
+    pub fact: UserFact,             // The remembered row, unchanged
+    pub value: String,              // The incoming value
+    pub confidence: f64,
+    pub source_type: String,
+    pub source_conversation_id: Option<String>,
+    pub created_at: String,
+}
+
+// ============ Response Citations ============
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ResponseCitation {
+    pub message_id: String,
+    pub agent: String,
+    pub fact_id: i64,
+    pub category: String,
+    pub key: String,
+    pub value: String,
+    pub first_mentioned: String,    // When the fact was learned
+}
+
+// ============ Disagreements ============
+
+/// Where two agents split in a round (see disagreement.rs)
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct Disagreement {
+    pub id: i64,
+    pub conversation_id: String,
+    pub round_id: Option<String>,
+    pub axis: String,               // e.g. "timing", "risk"; "approach" when no known axis fit
+    pub agent_a: String,            // The first voice
+    pub stance_a: String,
+    pub agent_b: String,            // The one who pushed back
+    pub stance_b: String,
+    pub intensity: String,          // 'mild' (rebuttal) | 'intense' (debate)
+    pub summary: String,            // "The split is X (Snap) vs Y (Dot)."
+    pub created_at: String,
+}
+
+// ============ Chapters ============
+
+pub const CHAPTER_USER: &str = "user";
+pub const CHAPTER_AUTO: &str = "auto";
+
+/// A period of the user's life (see chapters.rs)
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct Chapter {
+    pub id: i64,
+    pub title: String,              // "the layoff spring", "moving to Berlin"
+    pub start_date: String,         // YYYY-MM-DD
+    pub end_date: Option<String>,   // YYYY-MM-DD; None while the chapter is ongoing
+    pub description: Option<String>,
+    pub source: String,             // 'user' | 'auto' (edited auto chapters become 'user')
+    pub conversation_ids: Vec<String>, // Conversations placed in it; others fall in by date
+    pub created_at: String,
+}
+
+// ============ Action Items ============
+
+pub const ACTION_OPEN: &str = "open";
+pub const ACTION_DONE: &str = "done";
+
+/// A concrete next step pulled out of a conversation
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ActionItem {
+    pub id: i64,
+    pub conversation_id: String,
+    pub message_id: Option<String>, // Message it came from; None once that message is deleted
+    pub text: String,
+    pub owner: String,              // 'user' (a commitment) | agent id (a suggestion)
+    pub due: Option<String>,        // As stated in the conversation, e.g. 'Friday'
+    pub status: String,             // 'open' | 'done'
+    pub snoozed_until: Option<String>,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+}
+
+// ============ Memory Collections ============
+
+pub const COLLECTION_ITEM_FACT: &str = "fact";
+pub const COLLECTION_ITEM_DOCUMENT: &str = "document";
+pub const COLLECTION_ITEM_TYPES: [&str; 2] = [COLLECTION_ITEM_FACT, COLLECTION_ITEM_DOCUMENT];
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct MemoryCollection {
+    pub id: i64,
+    pub name: String,               // e.g. "work", "personal", "creative"
+    pub description: Option<String>,
+    pub item_count: i64,
+    pub created_at: String,
+}
+
+// ============ Tags ============
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,               // e.g. "work", "therapy-ish", "planning"
+    pub conversation_count: i64,
+    pub created_at: String,
+}
+
+// ============ Model Routing ============
+
+/// Audit record of which model answered an agent turn, and why
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ModelDecision {
+    pub id: i64,
+    pub conversation_id: Option<String>,
+    pub agent: String,
+    pub complexity: String,         // 'simple' | 'moderate' | 'hard'
+    pub tier: String,               // 'economy' | 'premium'
+    pub model: String,
+    pub reason: String,             // e.g. 'adaptive: short small talk', 'conversation override'
+    pub created_at: String,
+}
+
+// ============ Insights ============
+
+/// Something the app noticed and wants to tell the user (e.g. a provider switch)
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct Insight {
+    pub id: i64,
+    pub kind: String,               // e.g. 'latency'
+    pub message: String,
+    pub created_at: String,
+    pub dismissed_at: Option<String>,
+}
+
+// ============ Projects ============
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct Project {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub conversation_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// ============ Session Sharing ============
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ShareLogEntry {
+    pub id: i64,
+    pub conversation_id: String,
+    pub destination: String,        // 'email' | 'webhook'
+    pub target: String,             // Recipient address or webhook URL
+    pub subject: String,
+    pub content: String,            // Exactly what was sent
+    pub error: Option<String>,      // Set when the send failed
+    pub shared_at: String,
+}
+
+// ============ Weekly Digest ============
+
+/// The Governor's reflection on one week
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct WeeklyReflection {
+    pub id: i64,
+    pub week_start: String,         // Local date (YYYY-MM-DD) of the Monday that opened the week
+    pub content: String,
+    pub created_at: String,
+}
+
+/// A month's highlight reel: the messages that mattered most, written up
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct MonthlyHighlights {
+    pub id: i64,
+    pub month: String,              // YYYY-MM, local time
+    pub content: String,
+    pub message_ids: Vec<String>,   // The messages it was written from, strongest first
+    pub created_at: String,
+}
+
+/// A message in the running for a month's highlights
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HighlightCandidate {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub conversation_title: Option<String>,
+    pub role: String,
+    pub content: String,
+    pub timestamp: String,
+    pub citations: i64,             // Memory facts the reply cited
+    pub replies: i64,               // Later messages that answered or rebutted it
+}
+
+/// One attempt to email something out of the app
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct EmailLogEntry {
+    pub id: i64,
+    pub content_type: String,       // Allowlisted type, e.g. 'weekly_reflection'
+    pub content_id: Option<String>, // Id of the emailed item
+    pub target: String,
+    pub subject: String,
+    pub content: String,            // Exactly what was sent
+    pub error: Option<String>,      // Set when the send failed
+    pub sent_at: String,
+}
+
+// ============ Usage ============
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UsageRecord {
+    pub id: i64,
+    pub provider: String,           // 'anthropic' | 'openai' | 'local'
+    pub model: String,
+    pub category: String,           // 'agent' | 'memory' | 'summary' | 'governor' | ...
+    pub agent: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub latency_ms: i64,
+    pub cost_usd: f64,              // Estimated from the static price table
+    pub created_at: String,
+}
+
+/// Spend on one (local) calendar day
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct DailySpend {
+    pub day: String,                // YYYY-MM-DD
+    pub calls: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_usd: f64,
+}
+
+/// Spend on one model
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ModelSpend {
+    pub provider: String,
+    pub model: String,
+    pub calls: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_usd: f64,
+}
+
+// ============ Journal ============
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct JournalEntry {
+    pub id: String,
+    pub entry_type: String,         // "note" | "dream"
+    pub content: String,
+    pub reflection: Option<String>, // Inland Empire reflection (dreams only)
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct DreamMotif {
+    pub id: i64,
+    pub motif: String,
+    pub motif_type: String,         // "symbol" | "imagery" | "figure" | "place" | "emotion"
+    pub frequency: i64,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub related_entries: Option<String>, // JSON array of journal entry IDs
+}
+
+// ============ Boundaries ============
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct BoundaryRule {
+    pub id: String,
+    pub agent: String,              // 'instinct' | 'logic' | 'psyche' | 'all'
+    pub description: String,        // Compiled verbatim into the prompt
+    pub keywords: String,           // JSON array of trigger keywords for post-hoc filtering
+    pub start_time: Option<String>, // Local "HH:MM", rule only active from here
+    pub end_time: Option<String>,   // Local "HH:MM", may wrap past midnight
+    pub action: String,             // 'soften' | 'suppress'
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+// ============ Response Filters ============
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct FilterViolationStat {
+    pub filter: String,
+    pub agent: String,
+    pub count: i64,
+    pub last_seen: String,
+}
+
+/// Database file of the active profile
+pub fn get_db_path(app_handle: &tauri::AppHandle) -> PathBuf {
+    profiles::db_path(app_handle, &profiles::current_id())
+}
+
+/// A database file with its WAL and shared-memory companions
+pub fn database_files(db_path: &std::path::Path) -> [PathBuf; 3] {
+    let path = db_path.to_string_lossy();
+    [db_path.to_path_buf(), PathBuf::from(format!("{}-wal", path)), PathBuf::from(format!("{}-shm", path))]
+}
+
+/// Bumped whenever the migration chain changes; stored in `PRAGMA user_version`
+/// (databases from before versioning report 0)
+pub const SCHEMA_VERSION: i64 = 1;
+
+/// What opening the database did to it, sent to the UI at startup
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct MigrationReport {
+    pub from_version: i64,
+    pub to_version: i64,
+    pub tables_created: Vec<String>,
+    pub columns_added: Vec<String>,     // 'table.column'
+    pub row_counts: Vec<TableRowCount>, // Tables that existed before, with rows before/after
+    pub tables_with_fewer_rows: Vec<String>,
+    pub duration_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct TableRowCount {
+    pub table: String,
+    pub before: i64,
+    pub after: i64,
+}
+
+impl MigrationReport {
+    /// Whether the upgrade changed the schema at all
+    pub fn changed(&self) -> bool {
+        self.from_version != self.to_version || !self.tables_created.is_empty() || !self.columns_added.is_empty()
+    }
+}
+
+/// Tables (with their columns and row counts) as they stand, ignoring SQLite and FTS internals
+struct SchemaSnapshot {
+    user_version: i64,
+    columns: std::collections::BTreeMap<String, Vec<String>>,
+    rows: std::collections::BTreeMap<String, i64>,
+}
+
+fn snapshot_schema(conn: &Connection) -> Result<SchemaSnapshot> {
+    let user_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    let tables: Vec<String> = {
+        let mut stmt = conn.prepare(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name NOT LIKE 'messages_fts%'
+             ORDER BY name"
+        )?;
+        let names = stmt.query_map([], |row| row.get(0))?;
+        names.collect::<Result<_>>()?
+    };
     
-    let has_instinct: bool = conn.query_row(
-        "SELECT COUNT(*) FROM persona_profiles WHERE dominant_trait = 'instinct'",
-        [],
-        |row| Ok(row.get::<_, i64>(0)? > 0)
-    ).unwrap_or(false);
+    let mut columns = std::collections::BTreeMap::new();
+    let mut rows = std::collections::BTreeMap::new();
+    for table in tables {
+        let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?1)")?;
+        let names = stmt.query_map([&table], |row| row.get(0))?.collect::<Result<Vec<String>>>()?;
+        let count: i64 = conn.query_row(&format!("SELECT COUNT(*) FROM \"{}\"", table), [], |row| row.get(0))?;
+        columns.insert(table.clone(), names);
+        rows.insert(table, count);
+    }
+    Ok(SchemaSnapshot { user_version, columns, rows })
+}
+
+/// Schema health for a support bundle: versions, integrity, and each table's
+/// column and row counts (no row contents)
+#[derive(Debug, Serialize)]
+pub struct SchemaDiagnostics {
+    pub user_version: i64,
+    pub expected_version: i64,
+    pub integrity: String,          // "ok" or SQLite's first complaint
+    pub tables: std::collections::BTreeMap<String, (usize, i64)>, // table -> (columns, rows)
+}
+
+pub fn get_schema_diagnostics() -> Result<SchemaDiagnostics> {
+    with_connection(|conn| {
+        let snapshot = snapshot_schema(conn)?;
+        let integrity: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+        let tables = snapshot.columns.iter()
+            .map(|(table, columns)| (table.clone(), (columns.len(), snapshot.rows.get(table).copied().unwrap_or(0))))
+            .collect();
+        Ok(SchemaDiagnostics { user_version: snapshot.user_version, expected_version: SCHEMA_VERSION, integrity, tables })
+    })
+}
+
+pub fn init_database(app_handle: &tauri::AppHandle) -> Result<MigrationReport> {
+    let (pool, report) = open_pool(app_handle)?;
     
-    let has_psyche: bool = conn.query_row(
-        "SELECT COUNT(*) FROM persona_profiles WHERE dominant_trait = 'psyche'",
-        [],
-        |row| Ok(row.get::<_, i64>(0)? > 0)
-    ).unwrap_or(false);
+    let _ = APP_HANDLE.set(app_handle.clone());
+    let state = app_handle.try_state::<DbState>()
+        .ok_or_else(|| pool_error(rusqlite::ffi::SQLITE_MISUSE, "Database state is not managed".to_string()))?;
+    *state.pool.write().unwrap() = Some(pool);
     
-    // Create missing profiles
-    if !has_logic {
-        let logic_id = uuid::Uuid::new_v4().to_string();
+    Ok(report)
+}
+
+/// Open a pool on the active profile's database and bring its schema up to date
+fn open_pool(app_handle: &tauri::AppHandle) -> Result<(DbPool, MigrationReport)> {
+    let db_path = get_db_path(app_handle);
+    let manager = SqliteConnectionManager::file(&db_path).with_init(|conn| {
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        // WAL only needs syncing at checkpoints to stay consistent
+        conn.pragma_update(None, "synchronous", "NORMAL")
+    });
+    let pool = r2d2::Pool::builder()
+        .max_size(POOL_SIZE)
+        .connection_timeout(BUSY_TIMEOUT)
+        .build(manager)
+        .map_err(|e| pool_error(rusqlite::ffi::SQLITE_CANTOPEN, format!("Failed to open database pool: {}", e)))?;
+    
+    let report = {
+        let conn = pool.get().map_err(|e| pool_error(rusqlite::ffi::SQLITE_BUSY, e.to_string()))?;
+        let report = migrate(&conn)?;
+        move_keys_to_keychain(&conn);
+        move_smtp_password_to_keychain(&conn);
+        report
+    };
+    
+    Ok((pool, report))
+}
+
+/// Bring a database from any earlier schema up to date -- tables, column
+/// migrations, seed rows -- and report what changed
+pub fn migrate(conn: &Connection) -> Result<MigrationReport> {
+    let started = std::time::Instant::now();
+    let before = snapshot_schema(conn)?;
+    apply_migrations(conn)?;
+    conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    let after = snapshot_schema(conn)?;
+    
+    let tables_created = after.columns.keys()
+        .filter(|t| !before.columns.contains_key(*t))
+        .cloned()
+        .collect();
+    let columns_added = after.columns.iter()
+        .filter_map(|(table, cols)| before.columns.get(table).map(|old| (table, cols, old)))
+        .flat_map(|(table, cols, old)| {
+            cols.iter().filter(|c| !old.contains(c)).map(move |c| format!("{}.{}", table, c))
+        })
+        .collect();
+    let row_counts: Vec<TableRowCount> = before.rows.iter()
+        .map(|(table, &count)| TableRowCount {
+            table: table.clone(),
+            before: count,
+            after: after.rows.get(table).copied().unwrap_or(0),
+        })
+        .collect();
+    let tables_with_fewer_rows = row_counts.iter()
+        .filter(|c| c.after < c.before)
+        .map(|c| c.table.clone())
+        .collect();
+    
+    Ok(MigrationReport {
+        from_version: before.user_version,
+        to_version: after.user_version,
+        tables_created,
+        columns_added,
+        row_counts,
+        tables_with_fewer_rows,
+        duration_ms: started.elapsed().as_millis() as i64,
+    })
+}
+
+// ============ Backup & Restore ============
+
+/// Tables a file must have to be taken for an Intersect database
+const REQUIRED_BACKUP_TABLES: [&str; 3] = ["conversations", "messages", "user_profile"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct BackupResult {
+    pub path: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct RestoreResult {
+    pub restored_from: String,
+    pub previous_backup: String,    // The database as it was just before the restore
+    pub migration: MigrationReport, // Older backups are upgraded on the way in
+}
+
+/// Snapshot the live database to `path` with SQLite's online backup, so it's
+/// consistent even while the app keeps the connection open
+pub fn backup_database(path: &str) -> Result<BackupResult> {
+    with_connection(|conn| conn.backup(rusqlite::DatabaseName::Main, path, None))?;
+    let bytes = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    Ok(BackupResult { path: path.to_string(), bytes })
+}
+
+/// What a maintenance pass found and how much space it won back
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct MaintenanceReport {
+    pub integrity_ok: bool,
+    pub integrity_issues: Vec<String>, // SQLite's complaints, first 100 (empty when ok)
+    pub analyzed: bool,
+    pub vacuumed: bool,                 // Skipped when the integrity check fails
+    pub bytes_before: u64,              // Database plus WAL
+    pub bytes_after: u64,
+    pub duration_ms: i64,
+}
+
+fn database_bytes(db_path: &std::path::Path) -> u64 {
+    database_files(db_path).iter().filter_map(|p| std::fs::metadata(p).ok()).map(|m| m.len()).sum()
+}
+
+/// Integrity check, then ANALYZE and VACUUM (with the WAL checkpointed and
+/// truncated). A database that fails the check isn't vacuumed, so nothing
+/// SQLite could still read is rewritten away.
+pub fn run_maintenance(app_handle: &tauri::AppHandle) -> Result<MaintenanceReport> {
+    let started = std::time::Instant::now();
+    let db_path = get_db_path(app_handle);
+    let bytes_before = database_bytes(&db_path);
+    
+    let (integrity_issues, analyzed, vacuumed) = with_connection(|conn| {
+        let mut stmt = conn.prepare("PRAGMA integrity_check(100)")?;
+        let results = stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<Result<Vec<String>>>()?;
+        let issues: Vec<String> = results.into_iter().filter(|r| r != "ok").collect();
+        if !issues.is_empty() {
+            return Ok((issues, false, false));
+        }
+        conn.execute_batch("ANALYZE")?;
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        conn.execute_batch("VACUUM")?;
+        rebuild_message_index(conn)?;
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        Ok((issues, true, true))
+    })?;
+    
+    Ok(MaintenanceReport {
+        integrity_ok: integrity_issues.is_empty(),
+        integrity_issues,
+        analyzed,
+        vacuumed,
+        bytes_before,
+        bytes_after: database_bytes(&db_path),
+        duration_ms: started.elapsed().as_millis() as i64,
+    })
+}
+
+/// Replace the live database with a backup. The current database is first
+/// backed up beside itself, and the restored one is migrated to this version.
+pub fn restore_database(app_handle: &tauri::AppHandle, path: &str) -> Result<RestoreResult> {
+    // Refuse anything that isn't an Intersect database before touching live data
+    {
+        let source = Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        for table in REQUIRED_BACKUP_TABLES {
+            let present: bool = source.query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                [table],
+                |row| Ok(row.get::<_, i64>(0)? > 0),
+            )?;
+            if !present {
+                return Err(rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_NOTADB),
+                    Some(format!("{} is not an Intersect backup (no {} table)", path, table)),
+                ));
+            }
+        }
+    }
+    
+    let previous_backup = get_db_path(app_handle)
+        .with_extension(format!("pre-restore-{}.db", Utc::now().format("%Y%m%d-%H%M%S")))
+        .to_string_lossy()
+        .into_owned();
+    
+    // The restore overwrites the shared file, so every pooled connection sees the result
+    let mut conn = pooled_connection()?;
+    conn.backup(rusqlite::DatabaseName::Main, &previous_backup, None)?;
+    conn.restore(rusqlite::DatabaseName::Main, path, None::<fn(rusqlite::backup::Progress)>)?;
+    let migration = migrate(&conn)?;
+    move_keys_to_keychain(&conn);
+    move_smtp_password_to_keychain(&conn);
+    
+    Ok(RestoreResult { restored_from: path.to_string(), previous_backup, migration })
+}
+
+// ============ Data Location ============
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct DataLocation {
+    pub path: String,       // The active profile's database file
+    pub is_default: bool,   // In the app data directory rather than a chosen folder
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct DataMigrationResult {
+    pub path: String,
+    pub previous_path: String,
+    pub bytes: u64,
+    pub previous_removed: bool, // False if the old file couldn't be deleted and is still there
+}
+
+pub fn get_data_location(app_handle: &tauri::AppHandle) -> DataLocation {
+    let path = get_db_path(app_handle);
+    DataLocation {
+        is_default: path.parent() == Some(profiles::data_dir(app_handle).as_path()),
+        path: path.to_string_lossy().into_owned(),
+    }
+}
+
+/// Wait for every connection checked out of `pool` to come back. False on timeout.
+fn wait_until_idle(pool: &DbPool, timeout: Duration) -> bool {
+    let started = std::time::Instant::now();
+    loop {
+        let state = pool.state();
+        if state.connections == state.idle_connections {
+            return true;
+        }
+        if started.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Check a copied database: SQLite's integrity check, then the same schema
+/// version and row counts as the original
+fn verify_copy(path: &std::path::Path, original: &SchemaSnapshot) -> std::result::Result<(), String> {
+    let copy = Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Couldn't open the copy: {}", e))?;
+    let integrity: String = copy.query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| format!("Couldn't check the copy: {}", e))?;
+    if integrity != "ok" {
+        return Err(format!("The copy failed its integrity check: {}", integrity));
+    }
+    let snapshot = snapshot_schema(&copy).map_err(|e| format!("Couldn't read the copy: {}", e))?;
+    if snapshot.user_version != original.user_version || snapshot.rows != original.rows {
+        return Err("The copy doesn't match the original database".to_string());
+    }
+    Ok(())
+}
+
+/// Move the active profile's database into `new_dir`. The pool is taken out of
+/// service first: new callers wait for the switch, and connections already
+/// checked out get to finish, so nothing can write to the old file once the
+/// copy starts. The copy is made with the online backup and verified, then a
+/// pool on it is put in place and the old file removed. If work in flight
+/// doesn't finish within the busy timeout, nothing moves.
+pub fn migrate_data_dir(app_handle: &tauri::AppHandle, new_dir: &str) -> std::result::Result<DataMigrationResult, String> {
+    let new_dir = PathBuf::from(new_dir.trim());
+    if !new_dir.is_dir() {
+        return Err(format!("{} is not a folder", new_dir.display()));
+    }
+    let profile_id = profiles::current_id();
+    let old_path = get_db_path(app_handle);
+    let new_path = new_dir.join(profiles::db_file_name(&profile_id));
+    let canonical_parent = |p: &std::path::Path| p.parent().and_then(|d| d.canonicalize().ok());
+    if canonical_parent(&old_path) == new_dir.canonicalize().ok() {
+        return Err("The database is already in that folder".to_string());
+    }
+    if new_path.exists() {
+        return Err(format!("{} already exists; move it out of the way first", new_path.display()));
+    }
+
+    let state = app_handle.try_state::<DbState>().ok_or("Database state is not managed")?;
+    let mut slot = state.pool.write().unwrap();
+    let old_pool = slot.take().ok_or("Database not initialized")?;
+    if !wait_until_idle(&old_pool, BUSY_TIMEOUT) {
+        *slot = Some(old_pool);
+        return Err("The database is busy; try again in a moment".to_string());
+    }
+
+    let copied = old_pool.get()
+        .map_err(|e| format!("Couldn't open the database: {}", e))
+        .and_then(|conn| {
+            conn.backup(rusqlite::DatabaseName::Main, &new_path, None)
+                .and_then(|_| snapshot_schema(&conn))
+                .map_err(|e| format!("Failed to copy the database: {}", e))
+        })
+        .and_then(|original| verify_copy(&new_path, &original));
+    if let Err(e) = copied {
+        *slot = Some(old_pool);
+        for path in database_files(&new_path) {
+            let _ = std::fs::remove_file(path);
+        }
+        return Err(e);
+    }
+
+    let db_dir = if new_dir == profiles::data_dir(app_handle) {
+        None
+    } else {
+        Some(new_dir.to_string_lossy().into_owned())
+    };
+    let previous_dir = profiles::set_db_dir(app_handle, &profile_id, db_dir)?;
+    match open_pool(app_handle) {
+        Ok((pool, _)) => *slot = Some(pool),
+        Err(e) => {
+            // The old file is untouched; go back to it
+            let _ = profiles::set_db_dir(app_handle, &profile_id, previous_dir);
+            *slot = Some(old_pool);
+            return Err(format!("Failed to open the moved database: {}", e));
+        }
+    }
+    drop(slot);
+    // Closes the old file's connections before it's removed
+    drop(old_pool);
+
+    let mut previous_removed = true;
+    for path in database_files(&old_path).iter().filter(|p| p.exists()) {
+        if let Err(e) = std::fs::remove_file(path) {
+            logging::log_error(None, &format!("Moved database but couldn't remove {}: {}", path.display(), e));
+            previous_removed = false;
+        }
+    }
+    logging::log_conversation(None, &format!("Database moved from {} to {}", old_path.display(), new_path.display()));
+
+    Ok(DataMigrationResult {
+        bytes: std::fs::metadata(&new_path).map(|m| m.len()).unwrap_or(0),
+        path: new_path.to_string_lossy().into_owned(),
+        previous_path: old_path.to_string_lossy().into_owned(),
+        previous_removed,
+    })
+}
+
+fn apply_migrations(conn: &Connection) -> Result<()> {
+    // Create tables
+    conn.execute_batch(
+        "
+        -- User profile with evolving weights (API keys live in the OS keychain)
+        CREATE TABLE IF NOT EXISTS user_profile (
+            id INTEGER PRIMARY KEY,
+            instinct_weight REAL DEFAULT 0.33,
+            logic_weight REAL DEFAULT 0.33,
+            psyche_weight REAL DEFAULT 0.34,
+            total_messages INTEGER DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Conversation sessions
+        CREATE TABLE IF NOT EXISTS conversations (
+            id TEXT PRIMARY KEY,
+            title TEXT,
+            summary TEXT,
+            limbo_summary TEXT,
+            processed INTEGER DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Messages with agent attribution
+        CREATE TABLE IF NOT EXISTS messages (
+            id TEXT PRIMARY KEY,
+            conversation_id TEXT NOT NULL,
+            role TEXT NOT NULL,
+            content TEXT NOT NULL,
+            response_type TEXT,
+            references_message_id TEXT,
+            timestamp TEXT NOT NULL,
+            status TEXT,
+            round_id TEXT,
+            FOREIGN KEY (conversation_id) REFERENCES conversations(id)
+        );
+        -- Keyset paging walks a conversation by (timestamp, id)
+        CREATE INDEX IF NOT EXISTS idx_messages_conversation_timestamp ON messages(conversation_id, timestamp, id);
+
+        -- Learned user context (legacy, kept for compatibility)
+        CREATE TABLE IF NOT EXISTS user_context (
+            id INTEGER PRIMARY KEY,
+            key TEXT UNIQUE NOT NULL,
+            value TEXT NOT NULL,
+            confidence REAL DEFAULT 0.5,
+            source_agent TEXT,
+            updated_at TEXT NOT NULL
+        );
+
+        -- User facts (explicit statements about the user)
+        CREATE TABLE IF NOT EXISTS user_facts (
+            id INTEGER PRIMARY KEY,
+            category TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            confidence REAL DEFAULT 1.0,
+            source_type TEXT NOT NULL,
+            source_conversation_id TEXT,
+            first_mentioned TEXT NOT NULL,
+            last_confirmed TEXT NOT NULL,
+            mention_count INTEGER DEFAULT 1,
+            UNIQUE(category, key)
+        );
+        -- Category lookups use the UNIQUE(category, key) index, so there's no separate one
+
+        -- Facts retired by decay (id is the original user_facts id)
+        CREATE TABLE IF NOT EXISTS archived_facts (
+            id INTEGER PRIMARY KEY,
+            category TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            confidence REAL DEFAULT 1.0,
+            source_type TEXT NOT NULL,
+            source_conversation_id TEXT,
+            first_mentioned TEXT NOT NULL,
+            last_confirmed TEXT NOT NULL,
+            mention_count INTEGER DEFAULT 1,
+            archived_at TEXT NOT NULL
+        );
+
+        -- Inferred patterns (behavioral/personality observations)
+        CREATE TABLE IF NOT EXISTS user_patterns (
+            id INTEGER PRIMARY KEY,
+            pattern_type TEXT NOT NULL,
+            description TEXT NOT NULL,
+            confidence REAL DEFAULT 0.5,
+            evidence TEXT NOT NULL,
+            first_observed TEXT NOT NULL,
+            last_updated TEXT NOT NULL,
+            observation_count INTEGER DEFAULT 1
+        );
+
+        -- Conversation summaries (token-efficient history)
+        CREATE TABLE IF NOT EXISTS conversation_summaries (
+            id INTEGER PRIMARY KEY,
+            conversation_id TEXT NOT NULL,
+            summary TEXT NOT NULL,
+            key_topics TEXT NOT NULL,
+            emotional_tone TEXT,
+            user_state TEXT,
+            agents_involved TEXT NOT NULL,
+            message_count INTEGER,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (conversation_id) REFERENCES conversations(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_conversation_summaries_conversation ON conversation_summaries(conversation_id);
+
+        -- Cross-conversation recurring themes
+        CREATE TABLE IF NOT EXISTS recurring_themes (
+            id INTEGER PRIMARY KEY,
+            theme TEXT NOT NULL UNIQUE,
+            frequency INTEGER DEFAULT 1,
+            last_mentioned TEXT NOT NULL,
+            related_conversations TEXT
+        );
+
+        -- Persona profiles (multiple user states/modes)
+        CREATE TABLE IF NOT EXISTS persona_profiles (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            is_default INTEGER DEFAULT 0,
+            is_active INTEGER DEFAULT 0,
+            dominant_trait TEXT NOT NULL,
+            secondary_trait TEXT NOT NULL,
+            instinct_weight REAL DEFAULT 0.2,
+            logic_weight REAL DEFAULT 0.5,
+            psyche_weight REAL DEFAULT 0.3,
+            message_count INTEGER DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Generic app settings (key/value)
+        CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Grounding exercise library (built-ins seeded on startup)
+        CREATE TABLE IF NOT EXISTS grounding_exercises (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            description TEXT NOT NULL,
+            steps TEXT NOT NULL,
+            is_builtin INTEGER DEFAULT 0,
+            created_at TEXT NOT NULL
+        );
+
+        -- Log of grounding exercises the user has run
+        CREATE TABLE IF NOT EXISTS exercise_sessions (
+            id TEXT PRIMARY KEY,
+            exercise_id TEXT NOT NULL,
+            conversation_id TEXT,
+            intro TEXT,
+            outro TEXT,
+            started_at TEXT NOT NULL,
+            completed_at TEXT,
+            FOREIGN KEY (exercise_id) REFERENCES grounding_exercises(id)
+        );
+
+        -- Per-agent content boundaries set by the user
+        CREATE TABLE IF NOT EXISTS boundary_rules (
+            id TEXT PRIMARY KEY,
+            agent TEXT NOT NULL DEFAULT 'all',
+            description TEXT NOT NULL,
+            keywords TEXT NOT NULL DEFAULT '[]',
+            start_time TEXT,
+            end_time TEXT,
+            action TEXT NOT NULL DEFAULT 'soften',
+            enabled INTEGER DEFAULT 1,
+            created_at TEXT NOT NULL
+        );
+
+        -- Response filter violations (prompt tuning data)
+        CREATE TABLE IF NOT EXISTS filter_violations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            conversation_id TEXT,
+            agent TEXT NOT NULL,
+            filter TEXT NOT NULL,
+            detail TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        -- Scheduled check-ins with read receipts
+        CREATE TABLE IF NOT EXISTS proactive_messages (
+            id TEXT PRIMARY KEY,
+            conversation_id TEXT,
+            agent TEXT NOT NULL,
+            content TEXT NOT NULL,
+            topic_key TEXT NOT NULL,
+            scheduled_for TEXT NOT NULL,
+            status TEXT NOT NULL DEFAULT 'pending',
+            delivered_at TEXT,
+            seen_at TEXT,
+            responded_at TEXT,
+            suppressed_count INTEGER DEFAULT 0,
+            created_at TEXT NOT NULL
+        );
+
+        -- User-supplied per-locale string/prompt overrides (bundled defaults live in locales/)
+        CREATE TABLE IF NOT EXISTS locale_strings (
+            locale TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (locale, key)
+        );
+
+        -- Agent presentation metadata served to the frontend
+        CREATE TABLE IF NOT EXISTS agents (
+            id TEXT PRIMARY KEY,
+            display_name TEXT NOT NULL,
+            disco_name TEXT,
+            color TEXT NOT NULL,
+            disco_color TEXT,
+            icon TEXT NOT NULL,
+            disco_icon TEXT,
+            description TEXT NOT NULL,
+            disco_description TEXT,
+            voice_samples TEXT NOT NULL DEFAULT '[]',
+            sort_order INTEGER DEFAULT 0,
+            is_builtin INTEGER DEFAULT 0,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Short-term scratch for newly extracted facts/patterns (not used in prompts)
+        CREATE TABLE IF NOT EXISTS memory_staging (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            category TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            confidence REAL DEFAULT 0.5,
+            source_type TEXT,
+            source_conversation_id TEXT,
+            first_seen TEXT NOT NULL,
+            last_seen TEXT NOT NULL,
+            confirmations INTEGER DEFAULT 1,
+            held_by_import TEXT,
+            UNIQUE(kind, category, key)
+        );
+
+        -- Imported chat archives awaiting or undergoing analysis
+        CREATE TABLE IF NOT EXISTS archive_imports (
+            id TEXT PRIMARY KEY,
+            source_path TEXT NOT NULL,
+            conversation_count INTEGER DEFAULT 0,
+            message_count INTEGER DEFAULT 0,
+            processed_count INTEGER DEFAULT 0,
+            estimated_cost_usd REAL DEFAULT 0,
+            cost_usd REAL DEFAULT 0,
+            status TEXT NOT NULL,
+            themes TEXT NOT NULL DEFAULT '{}',
+            error TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Corrections the user made to remembered facts from the chat
+        CREATE TABLE IF NOT EXISTS fact_corrections (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            fact_id INTEGER NOT NULL,
+            category TEXT NOT NULL,
+            key TEXT NOT NULL,
+            old_value TEXT NOT NULL,
+            new_value TEXT,
+            action TEXT NOT NULL,
+            conversation_id TEXT,
+            user_message TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        -- Previous values of user facts, newest last; a fact's history can be rolled back
+        CREATE TABLE IF NOT EXISTS user_fact_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            fact_id INTEGER NOT NULL,
+            category TEXT NOT NULL,
+            key TEXT NOT NULL,
+            value TEXT NOT NULL,
+            confidence REAL NOT NULL,
+            source_type TEXT NOT NULL,
+            conversation_id TEXT,
+            changed_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_user_fact_history_fact ON user_fact_history(fact_id);
+
+        -- Incoming fact values that disagreed with the remembered one, held for the user
+        CREATE TABLE IF NOT EXISTS fact_conflicts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            fact_id INTEGER NOT NULL,
+            value TEXT NOT NULL,
+            confidence REAL NOT NULL,
+            source_type TEXT NOT NULL,
+            source_conversation_id TEXT,
+            created_at TEXT NOT NULL,
+            UNIQUE(fact_id, value)
+        );
+
+        -- Per-conversation model tier, mode and agent weight overrides
+        CREATE TABLE IF NOT EXISTS conversation_settings (
+            conversation_id TEXT PRIMARY KEY,
+            model_tier TEXT,
+            mode TEXT,
+            instinct_weight REAL,
+            logic_weight REAL,
+            psyche_weight REAL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Life periods (e.g. the layoff spring) used to frame memories in time
+        CREATE TABLE IF NOT EXISTS chapters (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT NOT NULL,
+            start_date TEXT NOT NULL,
+            end_date TEXT,
+            description TEXT,
+            source TEXT NOT NULL DEFAULT 'user',
+            topic TEXT,
+            dismissed INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_chapters_topic ON chapters(topic);
+
+        -- Conversations placed in a chapter (by the user or auto-detection)
+        CREATE TABLE IF NOT EXISTS chapter_conversations (
+            chapter_id INTEGER NOT NULL,
+            conversation_id TEXT NOT NULL,
+            PRIMARY KEY (chapter_id, conversation_id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_chapter_conversations_conversation ON chapter_conversations(conversation_id);
+
+        -- The axis two agents split on in a rebuttal or debate round
+        CREATE TABLE IF NOT EXISTS disagreements (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            conversation_id TEXT NOT NULL,
+            round_id TEXT,
+            axis TEXT NOT NULL,
+            agent_a TEXT NOT NULL,
+            stance_a TEXT NOT NULL,
+            agent_b TEXT NOT NULL,
+            stance_b TEXT NOT NULL,
+            intensity TEXT NOT NULL,
+            summary TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_disagreements_conversation ON disagreements(conversation_id);
+
+        -- Memory facts an agent reply relied on
+        CREATE TABLE IF NOT EXISTS response_citations (
+            message_id TEXT NOT NULL,
+            fact_id INTEGER NOT NULL,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (message_id, fact_id),
+            FOREIGN KEY (message_id) REFERENCES messages(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_response_citations_fact ON response_citations(fact_id);
+
+        -- Action items extracted from conversations (status: 'open' | 'done')
+        CREATE TABLE IF NOT EXISTS action_items (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            conversation_id TEXT NOT NULL,
+            message_id TEXT,
+            text TEXT NOT NULL,
+            owner TEXT NOT NULL,
+            due TEXT,
+            status TEXT NOT NULL DEFAULT 'open',
+            snoozed_until TEXT,
+            created_at TEXT NOT NULL,
+            completed_at TEXT,
+            FOREIGN KEY (conversation_id) REFERENCES conversations(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_action_items_status ON action_items(status);
+
+        -- Named memory collections and what belongs to them (item_type: 'fact', ...)
+        CREATE TABLE IF NOT EXISTS memory_collections (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            description TEXT,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS collection_items (
+            collection_id INTEGER NOT NULL,
+            item_type TEXT NOT NULL,
+            item_id TEXT NOT NULL,
+            PRIMARY KEY (collection_id, item_type, item_id),
+            FOREIGN KEY (collection_id) REFERENCES memory_collections(id)
+        );
+
+        -- Collections a conversation may draw on (none selected = unscoped)
+        CREATE TABLE IF NOT EXISTS conversation_collections (
+            conversation_id TEXT NOT NULL,
+            collection_id INTEGER NOT NULL,
+            PRIMARY KEY (conversation_id, collection_id),
+            FOREIGN KEY (collection_id) REFERENCES memory_collections(id)
+        );
+
+        -- User tags for organizing conversations ('work', 'planning', ...)
+        CREATE TABLE IF NOT EXISTS tags (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS conversation_tags (
+            conversation_id TEXT NOT NULL,
+            tag_id INTEGER NOT NULL,
+            PRIMARY KEY (conversation_id, tag_id),
+            FOREIGN KEY (conversation_id) REFERENCES conversations(id),
+            FOREIGN KEY (tag_id) REFERENCES tags(id)
+        );
+
+        -- Prior versions of edited messages, oldest first
+        CREATE TABLE IF NOT EXISTS message_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            message_id TEXT NOT NULL,
+            content TEXT NOT NULL,
+            revised_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_message_revisions_message ON message_revisions(message_id);
+
+        -- Which model answered each agent turn (see model_routing.rs)
+        CREATE TABLE IF NOT EXISTS model_decisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            conversation_id TEXT,
+            agent TEXT NOT NULL,
+            complexity TEXT NOT NULL,
+            tier TEXT NOT NULL,
+            model TEXT NOT NULL,
+            reason TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_model_decisions_conversation ON model_decisions(conversation_id);
+
+        -- Things the app noticed and surfaces to the user
+        CREATE TABLE IF NOT EXISTS insights (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            message TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            dismissed_at TEXT
+        );
+
+        -- Projects: workspaces grouping conversations on a long-running topic
+        CREATE TABLE IF NOT EXISTS projects (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            description TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Every confirmed session share, with the exact payload sent
+        CREATE TABLE IF NOT EXISTS share_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            conversation_id TEXT NOT NULL,
+            destination TEXT NOT NULL,
+            target TEXT NOT NULL,
+            subject TEXT NOT NULL,
+            content TEXT NOT NULL,
+            error TEXT,
+            shared_at TEXT NOT NULL
+        );
+
+        -- Weekly reflections (emailed as the digest when enabled)
+        CREATE TABLE IF NOT EXISTS weekly_reflections (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            week_start TEXT NOT NULL UNIQUE,
+            content TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        -- Monthly highlight reels, kept next to the weekly reflections
+        CREATE TABLE IF NOT EXISTS monthly_highlights (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            month TEXT NOT NULL UNIQUE,
+            content TEXT NOT NULL,
+            message_ids TEXT NOT NULL DEFAULT '[]',
+            created_at TEXT NOT NULL
+        );
+
+        -- Every email the app attempted, with its payload
+        CREATE TABLE IF NOT EXISTS email_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            content_type TEXT NOT NULL,
+            content_id TEXT,
+            target TEXT NOT NULL,
+            subject TEXT NOT NULL,
+            content TEXT NOT NULL,
+            error TEXT,
+            sent_at TEXT NOT NULL
+        );
+
+        -- Per-call API usage log
+        CREATE TABLE IF NOT EXISTS usage_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            provider TEXT NOT NULL,
+            model TEXT NOT NULL,
+            category TEXT NOT NULL,
+            agent TEXT,
+            input_tokens INTEGER DEFAULT 0,
+            output_tokens INTEGER DEFAULT 0,
+            latency_ms INTEGER DEFAULT 0,
+            cost_usd REAL DEFAULT 0,
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_usage_log_created_at ON usage_log(created_at);
+
+        -- Journal entries (notes and dreams)
+        CREATE TABLE IF NOT EXISTS journal_entries (
+            id TEXT PRIMARY KEY,
+            entry_type TEXT NOT NULL DEFAULT 'note',
+            content TEXT NOT NULL,
+            reflection TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Recurring symbols and imagery extracted from dream entries
+        CREATE TABLE IF NOT EXISTS dream_motifs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            motif TEXT NOT NULL UNIQUE,
+            motif_type TEXT NOT NULL,
+            frequency INTEGER DEFAULT 1,
+            first_seen TEXT NOT NULL,
+            last_seen TEXT NOT NULL,
+            related_entries TEXT
+        );
+
+        -- Document store: text ingested from outside the chat (e.g. a notes folder)
+        CREATE TABLE IF NOT EXISTS documents (
+            id TEXT PRIMARY KEY,
+            source TEXT NOT NULL,
+            path TEXT NOT NULL UNIQUE,
+            title TEXT NOT NULL,
+            content TEXT NOT NULL,
+            file_modified_at TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Files attached to messages; data is NULL when only the path is kept
+        CREATE TABLE IF NOT EXISTS attachments (
+            id TEXT PRIMARY KEY,
+            message_id TEXT NOT NULL,
+            filename TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            data BLOB,
+            path TEXT,
+            size_bytes INTEGER NOT NULL,
+            extracted_text TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (message_id) REFERENCES messages(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_attachments_message ON attachments(message_id);
+        "
+    )?;
+    
+    // Migration: Add message_count column to persona_profiles if it doesn't exist
+    let has_persona_message_count: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('persona_profiles') WHERE name='message_count'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_persona_message_count {
+        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN message_count INTEGER DEFAULT 0", []);
+    }
+    
+    // Migration: Add limbo_summary and processed columns to conversations table
+    let has_limbo_summary: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='limbo_summary'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_limbo_summary {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN limbo_summary TEXT", []);
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN processed INTEGER DEFAULT 0", []);
+    }
+    
+    // Migration: Add is_disco column to conversations table for conversation-level disco mode
+    let has_is_disco: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='is_disco'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_is_disco {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN is_disco INTEGER DEFAULT 0", []);
+    }
+    
+    // Migration: Add intensity column to conversations for session escalation
+    let has_intensity: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='intensity'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_intensity {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN intensity REAL DEFAULT 0.0", []);
+    }
+    
+    // Full-text search index over message content, kept in sync by triggers.
+    // Index rows share the message's rowid so they're found and removed by key.
+    // A REPLACE deletes without firing the delete trigger, so the row it's about
+    // to replace is cleared before the insert.
+    let has_fts_rowid_triggers: bool = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='trigger' AND name='messages_fts_replace'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_fts_rowid_triggers {
+        // Older triggers keyed the index by message_id, which FTS can't look up
+        conn.execute_batch(
+            "
+            DROP TRIGGER IF EXISTS messages_fts_insert;
+            DROP TRIGGER IF EXISTS messages_fts_update;
+            DROP TRIGGER IF EXISTS messages_fts_delete;
+            "
+        )?;
+    }
+    
+    conn.execute_batch(
+        "
+        CREATE VIRTUAL TABLE IF NOT EXISTS messages_fts USING fts5(
+            content,
+            message_id UNINDEXED,
+            conversation_id UNINDEXED,
+            tokenize = 'porter unicode61'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS messages_fts_replace BEFORE INSERT ON messages BEGIN
+            DELETE FROM messages_fts WHERE rowid = (SELECT rowid FROM messages WHERE id = new.id);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS messages_fts_insert AFTER INSERT ON messages BEGIN
+            INSERT INTO messages_fts (rowid, content, message_id, conversation_id) VALUES (new.rowid, new.content, new.id, new.conversation_id);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS messages_fts_update AFTER UPDATE OF content ON messages BEGIN
+            DELETE FROM messages_fts WHERE rowid = old.rowid;
+            INSERT INTO messages_fts (rowid, content, message_id, conversation_id) VALUES (new.rowid, new.content, new.id, new.conversation_id);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS messages_fts_delete AFTER DELETE ON messages BEGIN
+            DELETE FROM messages_fts WHERE rowid = old.rowid;
+        END;
+        "
+    )?;
+    
+    if !has_fts_rowid_triggers {
+        // Backfill existing history, or re-key an index built by the older triggers
+        rebuild_message_index(conn)?;
+    }
+    
+    // Migration: Plain-language reading level per profile and per conversation
+    let has_profile_reading_level: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('persona_profiles') WHERE name='reading_level'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_profile_reading_level {
+        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN reading_level INTEGER", []);
+    }
+    
+    let has_conversation_reading_level: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='reading_level'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_conversation_reading_level {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN reading_level INTEGER", []);
+    }
+    
+    // Migration: Late-night window per profile (local "HH:MM"; NULL = no late-night policy)
+    let has_late_night: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('persona_profiles') WHERE name='late_night_start'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_late_night {
+        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN late_night_start TEXT", []);
+        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN late_night_end TEXT", []);
+    }
+    
+    // Migration: Add closed_at column to conversations for inactivity auto-close
+    let has_closed_at: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='closed_at'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_closed_at {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN closed_at TEXT", []);
+    }
+    
+    // Migration: Add delivery status to messages
+    let has_message_status: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('messages') WHERE name='status'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_message_status {
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN status TEXT", []);
+    }
+    
+    // Migration: Group messages into rounds (one per user message)
+    let has_round_id: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('messages') WHERE name='round_id'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_round_id {
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN round_id TEXT", []);
+    }
+    
+    // Migration: Record how long each reply took to generate
+    let has_latency_ms: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('messages') WHERE name='latency_ms'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_latency_ms {
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN latency_ms INTEGER", []);
+    }
+    
+    // Migration: per-message token counts and cost
+    let has_message_cost: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('messages') WHERE name='cost_usd'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_message_cost {
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN input_tokens INTEGER", []);
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN output_tokens INTEGER", []);
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN cost_usd REAL", []);
+    }
+    
+    // Migration: which model produced each message
+    let has_message_model: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('messages') WHERE name='model'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_message_model {
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN model TEXT", []);
+    }
+    
+    // Migration: Pin conversations to the top of the list
+    let has_pinned: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='pinned'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_pinned {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN pinned INTEGER DEFAULT 0", []);
+    }
+    
+    // Migration: File conversations under projects
+    let has_project_id: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='project_id'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_project_id {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN project_id INTEGER", []);
+    }
+    
+    // Migration: Per-conversation model tier override
+    let has_model_tier: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='model_tier'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_model_tier {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN model_tier TEXT", []);
+    }
+    
+    // Migration: In-conversation controls (/mode, /mute, /verbosity, /incognito)
+    let has_controls: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='incognito'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_controls {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN mode_override TEXT", []);
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN muted_agents TEXT", []);
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN verbosity TEXT", []);
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN incognito INTEGER DEFAULT 0", []);
+    }
+    
+    // Migration: Model tier and forced mode live in conversation_settings now.
+    // Values still on the conversation row (older databases, older imports) move over.
+    let _ = conn.execute(
+        "INSERT INTO conversation_settings (conversation_id, model_tier, mode, updated_at)
+         SELECT id, model_tier, mode_override, updated_at FROM conversations
+         WHERE model_tier IS NOT NULL OR mode_override IS NOT NULL
+         ON CONFLICT(conversation_id) DO UPDATE SET
+             model_tier = COALESCE(excluded.model_tier, model_tier),
+             mode = COALESCE(excluded.mode, mode)",
+        [],
+    );
+    let _ = conn.execute(
+        "UPDATE conversations SET model_tier = NULL, mode_override = NULL WHERE model_tier IS NOT NULL OR mode_override IS NOT NULL",
+        [],
+    );
+    
+    // Migration: Soft-delete (trash) for conversations
+    let has_deleted_at: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='deleted_at'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_deleted_at {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN deleted_at TEXT", []);
+    }
+    
+    // Migration: Tag conversations that came from an archive import
+    let has_import_id: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='import_id'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_import_id {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN import_id TEXT", []);
+    }
+    
+    // Migration: Lock conversations against changes
+    let has_locked: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='locked'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_locked {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN locked INTEGER DEFAULT 0", []);
+    }
+    
+    // Migration: Bookmarked messages
+    let has_bookmarks: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('messages') WHERE name='bookmarked_at'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_bookmarks {
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN bookmarked_at TEXT", []);
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN bookmark_note TEXT", []);
+        let _ = conn.execute("CREATE INDEX IF NOT EXISTS idx_messages_bookmarked ON messages(bookmarked_at)", []);
+    }
+    
+    // Migration: Remember which agents a round was sent to, so a retry asks the same ones
+    let has_round_agents: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('messages') WHERE name='round_agents'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_round_agents {
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN round_agents TEXT", []);
+        let _ = conn.execute("ALTER TABLE messages ADD COLUMN round_disco_agents TEXT", []);
+    }
+    
+    // Migration: Forked conversations point back at their parent
+    let has_parent: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('conversations') WHERE name='parent_conversation_id'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_parent {
+        let _ = conn.execute("ALTER TABLE conversations ADD COLUMN parent_conversation_id TEXT", []);
+    }
+    
+    // Migration: Hold staged memory from archive imports until reviewed
+    let has_held_by_import: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('memory_staging') WHERE name='held_by_import'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_held_by_import {
+        let _ = conn.execute("ALTER TABLE memory_staging ADD COLUMN held_by_import TEXT", []);
+    }
+    
+    // Migration: Let the user confirm or deny inferred patterns
+    let has_pattern_verdict: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('user_patterns') WHERE name='verdict'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_pattern_verdict {
+        let _ = conn.execute("ALTER TABLE user_patterns ADD COLUMN verdict TEXT", []);
+    }
+    
+    // Migration: Recurring themes lose frequency while they go unmentioned
+    let has_theme_decayed_at: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('recurring_themes') WHERE name='decayed_at'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_theme_decayed_at {
+        let _ = conn.execute("ALTER TABLE recurring_themes ADD COLUMN decayed_at TEXT", []);
+    }
+    
+    // Migration: Transient facts that expire ("on vacation until Friday")
+    for table in ["user_facts", "archived_facts"] {
+        let has_valid_until: bool = conn.query_row(
+            &format!("SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name='valid_until'", table),
+            [],
+            |row| Ok(row.get::<_, i64>(0)? > 0)
+        ).unwrap_or(false);
+        
+        if !has_valid_until {
+            let _ = conn.execute(&format!("ALTER TABLE {} ADD COLUMN valid_until TEXT", table), []);
+        }
+    }
+    
+    // Migration: Add user_requested override flag to proactive_messages
+    let has_user_requested: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('proactive_messages') WHERE name='user_requested'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_user_requested {
+        let _ = conn.execute("ALTER TABLE proactive_messages ADD COLUMN user_requested INTEGER DEFAULT 0", []);
+    }
+    
+    // Migration: Add points columns to persona_profiles table
+    let has_instinct_points: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('persona_profiles') WHERE name='instinct_points'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_instinct_points {
+        // Add columns with defaults: 4, 4, 3 (total 11)
+        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN instinct_points INTEGER DEFAULT 4", []);
+        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN logic_points INTEGER DEFAULT 4", []);
+        let _ = conn.execute("ALTER TABLE persona_profiles ADD COLUMN psyche_points INTEGER DEFAULT 3", []);
+        
+        // For existing profiles, initialize points based on current weights
+        // Convert weights to points: points = round(weight * 11), but ensure valid range (2-6) and total = 11
+        let _ = conn.execute(
+            "UPDATE persona_profiles SET instinct_points = CAST(ROUND(instinct_weight * 11) AS INTEGER), logic_points = CAST(ROUND(logic_weight * 11) AS INTEGER), psyche_points = CAST(ROUND(psyche_weight * 11) AS INTEGER)",
+            []
+        );
+        
+        // Ensure points are in valid range (2-6) and total = 11
+        // Clamp each to 2-6 range, then normalize total to 11
+        let _ = conn.execute(
+            "UPDATE persona_profiles SET 
+                instinct_points = MAX(2, MIN(6, instinct_points)),
+                logic_points = MAX(2, MIN(6, logic_points)),
+                psyche_points = MAX(2, MIN(6, psyche_points))",
+            []
+        );
+        
+        // Normalize totals to 11 (this is approximate, but close enough for migration)
+        // We'll fix exact totals in a separate pass if needed
+    }
+    
+    // Ensure a user profile exists (for API keys and message count)
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM user_profile",
+        [],
+        |row| row.get(0)
+    )?;
+    
+    if count == 0 {
+        let now = Utc::now().to_rfc3339();
+        // Default weights: Logic 50%, Psyche 30%, Instinct 20%
+        conn.execute(
+            "INSERT INTO user_profile (instinct_weight, logic_weight, psyche_weight, total_messages, created_at, updated_at)
+             VALUES (0.20, 0.50, 0.30, 0, ?1, ?2)",
+            params![now, now]
+        )?;
+    }
+    
+    // Ensure exactly 3 fixed profiles exist (Logic, Instinct, Psyche)
+    // Each profile is dominant for one trait at 40%, others at 30%
+    let now = Utc::now().to_rfc3339();
+    
+    // Check for each required profile by dominant_trait
+    let has_logic: bool = conn.query_row(
+        "SELECT COUNT(*) FROM persona_profiles WHERE dominant_trait = 'logic'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    let has_instinct: bool = conn.query_row(
+        "SELECT COUNT(*) FROM persona_profiles WHERE dominant_trait = 'instinct'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    let has_psyche: bool = conn.query_row(
+        "SELECT COUNT(*) FROM persona_profiles WHERE dominant_trait = 'psyche'",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    // Create missing profiles
+    if !has_logic {
+        let logic_id = uuid::Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO persona_profiles (id, name, is_default, is_active, dominant_trait, secondary_trait, instinct_weight, logic_weight, psyche_weight, instinct_points, logic_points, psyche_points, message_count, created_at, updated_at)
+             VALUES (?1, 'Logic', 1, 1, 'logic', 'logic', 0.30, 0.40, 0.30, 3, 4, 4, 0, ?2, ?3)",
+            params![logic_id, now, now]
+        )?;
+    }
+    
+    if !has_instinct {
+        let instinct_id = uuid::Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO persona_profiles (id, name, is_default, is_active, dominant_trait, secondary_trait, instinct_weight, logic_weight, psyche_weight, instinct_points, logic_points, psyche_points, message_count, created_at, updated_at)
+             VALUES (?1, 'Instinct', 0, 0, 'instinct', 'instinct', 0.40, 0.30, 0.30, 4, 3, 4, 0, ?2, ?3)",
+            params![instinct_id, now, now]
+        )?;
+    }
+    
+    if !has_psyche {
+        let psyche_id = uuid::Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO persona_profiles (id, name, is_default, is_active, dominant_trait, secondary_trait, instinct_weight, logic_weight, psyche_weight, instinct_points, logic_points, psyche_points, message_count, created_at, updated_at)
+             VALUES (?1, 'Psyche', 0, 0, 'psyche', 'psyche', 0.30, 0.30, 0.40, 3, 3, 5, 0, ?2, ?3)",
+            params![psyche_id, now, now]
+        )?;
+    }
+    
+    // Ensure exactly one profile is active (prefer Logic if none)
+    let has_active: bool = conn.query_row(
+        "SELECT COUNT(*) FROM persona_profiles WHERE is_active = 1",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_active {
+        conn.execute(
+            "UPDATE persona_profiles SET is_active = 1 WHERE dominant_trait = 'logic'",
+            []
+        )?;
+    }
+    
+    // Ensure exactly one profile is default (prefer Logic if none)
+    let has_default: bool = conn.query_row(
+        "SELECT COUNT(*) FROM persona_profiles WHERE is_default = 1",
+        [],
+        |row| Ok(row.get::<_, i64>(0)? > 0)
+    ).unwrap_or(false);
+    
+    if !has_default {
+        conn.execute(
+            "UPDATE persona_profiles SET is_default = 1 WHERE dominant_trait = 'logic'",
+            []
+        )?;
+    }
+    
+    // Remove any profiles that don't match the 3 fixed trait types
+    // (Clean up any old custom profiles)
+    conn.execute(
+        "DELETE FROM persona_profiles WHERE dominant_trait NOT IN ('logic', 'instinct', 'psyche')",
+        []
+    )?;
+    
+    // Keep only one profile per dominant trait (remove duplicates, keep the one with most messages)
+    for trait_type in &["logic", "instinct", "psyche"] {
+        let count: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM persona_profiles WHERE dominant_trait = '{}'", trait_type),
+            [],
+            |row| row.get(0)
+        ).unwrap_or(0);
+        
+        if count > 1 {
+            // Get the ID of the profile to keep (highest message_count)
+            let keep_id: String = conn.query_row(
+                &format!(
+                    "SELECT id FROM persona_profiles WHERE dominant_trait = '{}' ORDER BY message_count DESC, created_at ASC LIMIT 1",
+                    trait_type
+                ),
+                [],
+                |row| row.get(0)
+            ).unwrap_or_default();
+            
+            if !keep_id.is_empty() {
+                conn.execute(
+                    &format!(
+                        "DELETE FROM persona_profiles WHERE dominant_trait = '{}' AND id != ?1",
+                        trait_type
+                    ),
+                    params![keep_id]
+                )?;
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Drop the pool (used before deleting the database files). Connections still
+/// checked out close as soon as their query finishes.
+pub fn close_database() {
+    if let Some(state) = APP_HANDLE.get().and_then(|app| app.try_state::<DbState>()) {
+        *state.pool.write().unwrap() = None;
+    }
+}
+
+fn pool_error(code: std::os::raw::c_int, message: String) -> rusqlite::Error {
+    rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(code), Some(message))
+}
+
+/// A connection from the pool, or an error if the database isn't open
+fn pooled_connection() -> Result<r2d2::PooledConnection<SqliteConnectionManager>> {
+    let pool = APP_HANDLE.get()
+        .and_then(|app| app.try_state::<DbState>())
+        .and_then(|state| state.pool.read().unwrap().clone())
+        .ok_or_else(|| pool_error(rusqlite::ffi::SQLITE_CANTOPEN, "Database not initialized".to_string()))?;
+    pool.get().map_err(|e| pool_error(rusqlite::ffi::SQLITE_BUSY, format!("No database connection available: {}", e)))
+}
+
+fn with_connection<F, T>(f: F) -> Result<T>
+where
+    F: FnOnce(&Connection) -> Result<T>,
+{
+    let conn = pooled_connection()?;
+    f(&conn)
+}
+
+/// Run database work on tokio's blocking pool. Every command that touches the
+/// database or the filesystem goes through here as an async command: a sync
+/// command would hold the UI thread for the whole query (and for any wait on a
+/// pooled connection), and an async one calling straight in would stall a
+/// runtime worker instead.
+pub async fn run<F, T>(f: F) -> std::result::Result<T, String>
+where
+    F: FnOnce() -> std::result::Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| format!("Database task failed: {}", e))?
+}
+
+/// `run` for reads with nothing to report on failure (they fall back to
+/// defaults themselves). A panic inside still surfaces, as it would have inline.
+pub async fn run_infallible<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(value) => value,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+// ============ App Settings ============
+
+pub fn get_setting(key: &str) -> Result<Option<String>> {
+    with_connection(|conn| {
+        let result = conn.query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            params![key],
+            |row| row.get(0)
+        );
+        match result {
+            Ok(v) => Ok(Some(v)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+pub fn get_all_settings() -> Result<Vec<(String, String)>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare("SELECT key, value FROM app_settings ORDER BY key")?;
+        let settings = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        settings.collect()
+    })
+}
+
+pub fn set_setting(key: &str, value: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+            params![key, value, now],
+        )?;
+        Ok(())
+    })
+}
+
+// ============ User Profile ============
+
+pub fn get_user_profile() -> Result<UserProfile> {
+    with_connection(|conn| {
+        // Get base profile info (message count)
+        let base: (i64, i64, String, String) = conn.query_row(
+            "SELECT id, total_messages, created_at, updated_at
+             FROM user_profile LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        )?;
+        
+        // Get weights from active persona profile, or fallback to user_profile weights
+        let weights: (f64, f64, f64) = conn.query_row(
+            "SELECT instinct_weight, logic_weight, psyche_weight FROM persona_profiles WHERE is_active = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        ).unwrap_or_else(|_| {
+            // Fallback to user_profile weights if no active persona profile
+            conn.query_row(
+                "SELECT instinct_weight, logic_weight, psyche_weight FROM user_profile LIMIT 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            ).unwrap_or((0.2, 0.5, 0.3)) // Final fallback to defaults
+        });
+        
+        Ok(UserProfile {
+            id: base.0,
+            api_key: keychain::get(keychain::OPENAI_KEY),
+            anthropic_key: keychain::get(keychain::ANTHROPIC_KEY),
+            instinct_weight: weights.0,
+            logic_weight: weights.1,
+            psyche_weight: weights.2,
+            total_messages: base.1,
+            created_at: base.2,
+            updated_at: base.3,
+        })
+    })
+}
+
+/// Plaintext key columns from before keys moved to the keychain, and where each goes
+const LEGACY_KEY_COLUMNS: [(&str, &str); 2] = [
+    ("api_key", keychain::OPENAI_KEY),
+    ("anthropic_key", keychain::ANTHROPIC_KEY),
+];
+
+/// One-time move of the plaintext `user_profile` key columns into the OS keychain.
+/// The columns are dropped (and the file vacuumed, so the old values don't linger
+/// in free pages) only once every key in them is stored; if the keychain can't
+/// take one, nothing is dropped and the move is retried next launch. A key
+/// already in the keychain wins over the database copy.
+pub fn move_keys_to_keychain(conn: &Connection) -> bool {
+    let present: Vec<(&str, &'static str)> = LEGACY_KEY_COLUMNS.iter()
+        .copied()
+        .filter(|(column, _)| conn.query_row(
+            &format!("SELECT COUNT(*) FROM pragma_table_info('user_profile') WHERE name='{}'", column),
+            [],
+            |row| Ok(row.get::<_, i64>(0)? > 0),
+        ).unwrap_or(false))
+        .collect();
+    if present.is_empty() {
+        return false;
+    }
+    
+    for (column, account) in &present {
+        let value: Option<String> = conn.query_row(
+            &format!("SELECT {} FROM user_profile LIMIT 1", column),
+            [],
+            |row| row.get(0),
+        ).optional().ok().flatten().flatten().filter(|v: &String| !v.trim().is_empty());
+        let Some(value) = value else { continue };
+        if keychain::get(account).is_some() {
+            continue;
+        }
+        if let Err(e) = keychain::set(account, &value) {
+            logging::log_error(None, &format!("[KEYCHAIN] Keeping user_profile.{} until the keychain is available: {}", column, e));
+            return false;
+        }
+    }
+    
+    let _ = conn.pragma_update(None, "secure_delete", true);
+    for (column, _) in &present {
+        if let Err(e) = conn.execute(&format!("ALTER TABLE user_profile DROP COLUMN {}", column), []) {
+            logging::log_error(None, &format!("[KEYCHAIN] Failed to drop user_profile.{}: {}", column, e));
+            return false;
+        }
+    }
+    if conn.execute_batch("VACUUM").is_ok() {
+        if let Err(e) = rebuild_message_index(conn) {
+            logging::log_error(None, &format!("[SEARCH] Failed to rebuild the message index: {}", e));
+        }
+    }
+    logging::log_conversation(None, "Moved API keys from the database to the OS keychain");
+    true
+}
+
+/// One-time move of a plaintext share SMTP password out of `app_settings` and
+/// into the keychain, leaving only the flag that says one is set. The row stays
+/// until the keychain has taken the password.
+pub fn move_smtp_password_to_keychain(conn: &Connection) {
+    let password: Option<String> = conn.query_row(
+        "SELECT value FROM app_settings WHERE key = ?1",
+        params![keychain::SMTP_PASSWORD],
+        |row| row.get(0),
+    ).optional().ok().flatten();
+    let Some(password) = password else { return };
+    let configured = !password.trim().is_empty();
+    if configured && keychain::get(keychain::SMTP_PASSWORD).is_none() {
+        if let Err(e) = keychain::set(keychain::SMTP_PASSWORD, password.trim()) {
+            logging::log_error(None, &format!("[KEYCHAIN] Keeping the SMTP password in settings until the keychain is available: {}", e));
+            return;
+        }
+    }
+    let _ = conn.pragma_update(None, "secure_delete", true);
+    let _ = conn.execute("DELETE FROM app_settings WHERE key = ?1", params![keychain::SMTP_PASSWORD]);
+    let _ = conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![crate::share::SMTP_PASSWORD_SET_KEY, if configured { "true" } else { "false" }, Utc::now().to_rfc3339()],
+    );
+    logging::log_conversation(None, "Moved the SMTP password from settings to the OS keychain");
+}
+
+/// Update points for the active persona profile
+pub fn update_points(instinct: i64, logic: i64, psyche: i64) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE persona_profiles SET instinct_points = ?1, logic_points = ?2, psyche_points = ?3, updated_at = ?4 WHERE is_active = 1",
+            params![instinct, logic, psyche, now]
+        )?;
+        Ok(())
+    })
+}
+
+pub fn update_weights(instinct: f64, logic: f64, psyche: f64) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        // Update the active persona profile's weights (no constraints)
+        let updated = conn.execute(
+            "UPDATE persona_profiles SET instinct_weight = ?1, logic_weight = ?2, psyche_weight = ?3, updated_at = ?4 WHERE is_active = 1",
+            params![instinct, logic, psyche, now]
+        )?;
+        
+        // Fallback to user_profile if no active persona profile (legacy support)
+        if updated == 0 {
+            conn.execute(
+                "UPDATE user_profile SET instinct_weight = ?1, logic_weight = ?2, psyche_weight = ?3, updated_at = ?4",
+                params![instinct, logic, psyche, now]
+            )?;
+        }
+        
+        Ok(())
+    })
+}
+
+/// Enforce that the dominant trait maintains at least a 10% lead over other traits
+fn enforce_dominant_lead(instinct: f64, logic: f64, psyche: f64, dominant: &str) -> (f64, f64, f64) {
+    let min_lead = 0.10; // 10% lead
+    
+    let (mut i, mut l, mut p) = (instinct, logic, psyche);
+    
+    match dominant {
+        "instinct" => {
+            let max_other = l.max(p);
+            if i < max_other + min_lead {
+                // Need to boost instinct to maintain lead
+                i = max_other + min_lead;
+            }
+        }
+        "logic" => {
+            let max_other = i.max(p);
+            if l < max_other + min_lead {
+                l = max_other + min_lead;
+            }
+        }
+        "psyche" => {
+            let max_other = i.max(l);
+            if p < max_other + min_lead {
+                p = max_other + min_lead;
+            }
+        }
+        _ => {}
+    }
+    
+    // Normalize to sum to 1.0
+    let total = i + l + p;
+    (i / total, l / total, p / total)
+}
+
+pub fn increment_message_count() -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        // Increment global message count
+        conn.execute(
+            "UPDATE user_profile SET total_messages = total_messages + 1, updated_at = ?1",
+            params![now]
+        )?;
+        
+        // Also increment the active persona profile's message count
+        conn.execute(
+            "UPDATE persona_profiles SET message_count = message_count + 1, updated_at = ?1 WHERE is_active = 1",
+            params![now]
+        )?;
+        Ok(())
+    })
+}
+
+// ============ Conversations ============
+
+pub fn create_conversation(id: &str, is_disco: bool) -> Result<Conversation> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO conversations (id, title, summary, limbo_summary, processed, is_disco, created_at, updated_at)
+             VALUES (?1, NULL, NULL, NULL, 0, ?2, ?3, ?4)",
+            params![id, if is_disco { 1 } else { 0 }, now, now]
+        )?;
+        Ok(Conversation {
+            id: id.to_string(),
+            title: None,
+            summary: None,
+            limbo_summary: None,
+            processed: false,
+            is_disco,
+            created_at: now.clone(),
+            updated_at: now,
+            intensity: 0.0,
+            closed_at: None,
+            pinned: false,
+            project_id: None,
+            locked: false,
+            parent_conversation_id: None,
+        })
+    })
+}
+
+/// Every column of a conversation row as JSON, for lossless export (includes
+/// columns `Conversation` doesn't carry, like reading_level and deleted_at)
+pub fn get_conversation_row(id: &str) -> Result<Option<serde_json::Map<String, serde_json::Value>>> {
+    use rusqlite::types::ValueRef;
+    
+    with_connection(|conn| {
+        let mut stmt = conn.prepare("SELECT * FROM conversations WHERE id = ?1")?;
+        let names: Vec<String> = stmt.column_names().iter().map(|n| n.to_string()).collect();
+        stmt.query_row(params![id], |row| {
+            let mut map = serde_json::Map::new();
+            for (i, name) in names.iter().enumerate() {
+                let value = match row.get_ref(i)? {
+                    ValueRef::Null => serde_json::Value::Null,
+                    ValueRef::Integer(n) => n.into(),
+                    ValueRef::Real(f) => f.into(),
+                    ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned().into(),
+                    ValueRef::Blob(b) => b.iter().map(|byte| format!("{:02x}", byte)).collect::<String>().into(),
+                };
+                map.insert(name.clone(), value);
+            }
+            Ok(map)
+        }).optional()
+    })
+}
+
+/// Outcome of restoring an exported conversation
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ImportedConversation {
+    pub conversation_id: String,
+    pub message_count: usize,
+    pub ids_regenerated: usize,     // Conversation and message ids that collided and were replaced
+}
+
+/// Columns that point at things on the exporting machine, cleared on import
+const MACHINE_LOCAL_CONVERSATION_COLUMNS: [&str; 2] = ["project_id", "import_id"];
+
+/// Recreate an exported conversation (row as from `get_conversation_row`) and
+/// its messages in one transaction. Ids already in use are replaced with fresh
+/// ones, and replies and rounds are rewired to match; messages keep their
+/// order and timestamps. Columns this schema doesn't have are dropped.
+pub fn import_conversation(
+    conversation: &serde_json::Map<String, serde_json::Value>,
+    messages: &[Message],
+) -> Result<ImportedConversation> {
+    use rusqlite::types::Value;
+    use std::collections::HashMap;
+    
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let mut ids_regenerated = 0;
+        
+        let original_id = conversation.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let id_taken = |table: &str, id: &str| -> Result<bool> {
+            tx.query_row(&format!("SELECT COUNT(*) FROM {} WHERE id = ?1", table), [id], |row| Ok(row.get::<_, i64>(0)? > 0))
+        };
+        let conversation_id = if original_id.is_empty() || id_taken("conversations", &original_id)? {
+            if !original_id.is_empty() {
+                ids_regenerated += 1;
+            }
+            uuid::Uuid::new_v4().to_string()
+        } else {
+            original_id.clone()
+        };
+        
+        // Only columns both sides know about; nested JSON isn't a column value
+        let known: Vec<String> = {
+            let mut stmt = tx.prepare("SELECT name FROM pragma_table_info('conversations')")?;
+            let names = stmt.query_map([], |row| row.get(0))?;
+            names.collect::<Result<_>>()?
+        };
+        let mut columns = vec!["id".to_string()];
+        let mut values = vec![Value::Text(conversation_id.clone())];
+        for (name, value) in conversation {
+            if name == "id" || !known.contains(name) || MACHINE_LOCAL_CONVERSATION_COLUMNS.contains(&name.as_str()) {
+                continue;
+            }
+            let value = match value {
+                serde_json::Value::Null => Value::Null,
+                serde_json::Value::Bool(b) => Value::Integer(*b as i64),
+                serde_json::Value::Number(n) => match n.as_i64() {
+                    Some(i) => Value::Integer(i),
+                    None => Value::Real(n.as_f64().unwrap_or(0.0)),
+                },
+                serde_json::Value::String(t) => Value::Text(t.clone()),
+                _ => continue,
+            };
+            columns.push(name.clone());
+            values.push(value);
+        }
+        let placeholders: Vec<String> = (1..=columns.len()).map(|i| format!("?{}", i)).collect();
+        tx.execute(
+            &format!("INSERT INTO conversations ({}) VALUES ({})", columns.join(", "), placeholders.join(", ")),
+            rusqlite::params_from_iter(values),
+        )?;
+        
+        // A copy of a conversation that's still here gets its own rounds too
+        let fresh_rounds = conversation_id != original_id;
+        let mut message_ids: HashMap<&str, String> = HashMap::new();
+        let mut round_ids: HashMap<&str, String> = HashMap::new();
+        for message in messages {
+            let id = if message.id.is_empty() || id_taken("messages", &message.id)? {
+                ids_regenerated += !message.id.is_empty() as usize;
+                uuid::Uuid::new_v4().to_string()
+            } else {
+                message.id.clone()
+            };
+            message_ids.insert(message.id.as_str(), id);
+        }
+        
+        let mut ordered: Vec<&Message> = messages.iter().collect();
+        ordered.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        for message in ordered {
+            let round_id = match message.round_id.as_deref() {
+                Some(round) if fresh_rounds => Some(
+                    round_ids.entry(round).or_insert_with(|| uuid::Uuid::new_v4().to_string()).clone()
+                ),
+                other => other.map(|r| r.to_string()),
+            };
+            // Nothing is still in flight on this machine
+            let status = match message.status.as_deref() {
+                Some(MESSAGE_STATUS_PENDING) => Some(MESSAGE_STATUS_FAILED.to_string()),
+                other => other.map(|s| s.to_string()),
+            };
+            tx.execute(
+                "INSERT INTO messages (id, conversation_id, role, content, response_type, references_message_id, timestamp, status, round_id, latency_ms, input_tokens, output_tokens, cost_usd, model)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                params![
+                    message_ids[message.id.as_str()],
+                    conversation_id,
+                    message.role,
+                    message.content,
+                    message.response_type,
+                    message.references_message_id.as_deref().and_then(|r| message_ids.get(r)),
+                    message.timestamp,
+                    status,
+                    round_id,
+                    message.latency_ms,
+                    message.input_tokens,
+                    message.output_tokens,
+                    message.cost_usd,
+                    message.model
+                ],
+            )?;
+        }
+        
+        tx.commit()?;
+        Ok(ImportedConversation { conversation_id, message_count: messages.len(), ids_regenerated })
+    })
+}
+
+/// Copy a conversation up to and including `from_message_id` into a new one
+/// that points back at it. Messages get fresh ids (replies and rounds are
+/// rewired to match) and keep their timestamps and attachments; the
+/// conversation's controls, settings and memory collections come along, its
+/// title gets a "(fork)" suffix. The original is untouched.
+pub fn fork_conversation(conversation_id: &str, from_message_id: &str) -> Result<Conversation> {
+    use std::collections::HashMap;
+
+    let messages = get_conversation_messages(conversation_id)?;
+    let cut = messages.iter().position(|m| m.id == from_message_id).ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+    let copied = &messages[..=cut];
+    let fork_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO conversations (id, title, summary, limbo_summary, processed, is_disco, created_at, updated_at, project_id,
+                                        muted_agents, verbosity, incognito, reading_level, parent_conversation_id)
+             SELECT ?1, CASE WHEN title IS NULL OR TRIM(title) = '' THEN NULL ELSE title || ' (fork)' END, NULL, NULL, 0, is_disco, ?2, ?2, project_id,
+                    muted_agents, verbosity, incognito, reading_level, id
+             FROM conversations WHERE id = ?3",
+            params![fork_id, now, conversation_id],
+        )?;
+        tx.execute(
+            "INSERT INTO conversation_settings (conversation_id, model_tier, mode, instinct_weight, logic_weight, psyche_weight, updated_at)
+             SELECT ?1, model_tier, mode, instinct_weight, logic_weight, psyche_weight, ?2
+             FROM conversation_settings WHERE conversation_id = ?3",
+            params![fork_id, now, conversation_id],
+        )?;
+        tx.execute(
+            "INSERT INTO conversation_collections (conversation_id, collection_id)
+             SELECT ?1, collection_id FROM conversation_collections WHERE conversation_id = ?2",
+            params![fork_id, conversation_id],
+        )?;
+
+        let message_ids: HashMap<&str, String> = copied.iter()
+            .map(|m| (m.id.as_str(), uuid::Uuid::new_v4().to_string()))
+            .collect();
+        let mut round_ids: HashMap<&str, String> = HashMap::new();
+        for message in copied {
+            let round_id = message.round_id.as_deref().map(|round| {
+                round_ids.entry(round)
+                    .or_insert_with(|| message_ids.get(round).cloned().unwrap_or_else(|| uuid::Uuid::new_v4().to_string()))
+                    .clone()
+            });
+            let status = match message.status.as_deref() {
+                Some(MESSAGE_STATUS_PENDING) => Some(MESSAGE_STATUS_FAILED.to_string()),
+                other => other.map(|s| s.to_string()),
+            };
+            tx.execute(
+                "INSERT INTO messages (id, conversation_id, role, content, response_type, references_message_id, timestamp, status, round_id, latency_ms, input_tokens, output_tokens, cost_usd, model)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                params![
+                    message_ids[message.id.as_str()],
+                    fork_id,
+                    message.role,
+                    message.content,
+                    message.response_type,
+                    message.references_message_id.as_deref().and_then(|r| message_ids.get(r)),
+                    message.timestamp,
+                    status,
+                    round_id,
+                    message.latency_ms,
+                    message.input_tokens,
+                    message.output_tokens,
+                    message.cost_usd,
+                    message.model
+                ],
+            )?;
+            // Attachment rows are copied; a file kept on disk is shared by path
+            let attachment_ids: Vec<String> = tx
+                .prepare("SELECT id FROM attachments WHERE message_id = ?1 ORDER BY created_at ASC, rowid ASC")?
+                .query_map([&message.id], |row| row.get(0))?
+                .collect::<Result<_>>()?;
+            for attachment_id in attachment_ids {
+                tx.execute(
+                    "INSERT INTO attachments (id, message_id, filename, mime_type, data, path, size_bytes, extracted_text, created_at)
+                     SELECT ?1, ?2, filename, mime_type, data, path, size_bytes, extracted_text, created_at
+                     FROM attachments WHERE id = ?3",
+                    params![uuid::Uuid::new_v4().to_string(), message_ids[message.id.as_str()], attachment_id],
+                )?;
+            }
+        }
+        tx.commit()
+    })?;
+
+    get_conversation(&fork_id)?.ok_or(rusqlite::Error::QueryReturnedNoRows)
+}
+
+// ============ Memory Import ============
+
+/// How an imported memory bundle meets what this install already knows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MemoryMergeStrategy {
+    Replace,                // Forget current memory, then take the bundle as-is
+    KeepHigherConfidence,   // On a match, the more certain entry stays
+    NewestWins,             // On a match, the more recently confirmed entry stays
+}
+
+impl MemoryMergeStrategy {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "replace" => Some(MemoryMergeStrategy::Replace),
+            "merge-keep-higher-confidence" => Some(MemoryMergeStrategy::KeepHigherConfidence),
+            "merge-newest-wins" => Some(MemoryMergeStrategy::NewestWins),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemoryMergeStrategy::Replace => "replace",
+            MemoryMergeStrategy::KeepHigherConfidence => "merge-keep-higher-confidence",
+            MemoryMergeStrategy::NewestWins => "merge-newest-wins",
+        }
+    }
+
+    /// Whether an incoming entry overwrites the one it matched; each side is
+    /// (confidence, last-confirmed timestamp). Ties keep what's here.
+    fn incoming_wins(&self, existing: (f64, &str), incoming: (f64, &str)) -> bool {
+        match self {
+            MemoryMergeStrategy::Replace => true,
+            MemoryMergeStrategy::KeepHigherConfidence => incoming.0 > existing.0,
+            MemoryMergeStrategy::NewestWins => {
+                match (chrono::DateTime::parse_from_rfc3339(existing.1), chrono::DateTime::parse_from_rfc3339(incoming.1)) {
+                    (Ok(existing), Ok(incoming)) => incoming > existing,
+                    _ => incoming.1 > existing.1,
+                }
+            }
+        }
+    }
+}
+
+/// What importing a memory bundle changed
+#[derive(Debug, Serialize, Deserialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct MemoryImport {
+    pub strategy: String,
+    pub facts_added: usize,
+    pub facts_updated: usize,
+    pub patterns_added: usize,
+    pub patterns_updated: usize,
+    pub summaries_added: usize,
+    pub summaries_updated: usize,
+    pub summaries_skipped: usize,   // Their conversation isn't on this install
+    pub themes_added: usize,
+    pub themes_updated: usize,
+    pub context_added: usize,
+    pub context_updated: usize,
+    pub kept: usize,                // Matches where the existing entry won
+}
+
+/// What a memory bundle carries; Replace clears these and nothing the bundle can't restore
+const MEMORY_IMPORT_TABLES: [&str; 5] = [
+    "user_context", "user_facts", "user_patterns", "recurring_themes", "conversation_summaries",
+];
+/// Rows that hang off a fact id and mean nothing once that fact is gone
+const FACT_KEYED_TABLES: [&str; 4] = ["user_fact_history", "fact_corrections", "fact_conflicts", "response_citations"];
+
+/// Bring an exported memory bundle in, in one transaction. Facts match on
+/// category and key, patterns on type and description, themes on their text,
+/// context on its key and summaries on their conversation. Overwritten facts
+/// keep their old value in the fact history. Replace leaves archived facts,
+/// staging, chapters and filed documents alone.
+pub fn import_memory(
+    facts: &[UserFact],
+    patterns: &[UserPattern],
+    summaries: &[ConversationSummary],
+    themes: &[RecurringTheme],
+    user_context: &[UserContext],
+    strategy: MemoryMergeStrategy,
+) -> Result<MemoryImport> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let mut report = MemoryImport { strategy: strategy.as_str().to_string(), ..Default::default() };
+        if strategy == MemoryMergeStrategy::Replace {
+            for table in MEMORY_IMPORT_TABLES {
+                tx.execute(&format!("DELETE FROM {}", table), [])?;
+            }
+            // Fact ids get reused, so rows keyed to the old facts would latch onto the new ones
+            for table in FACT_KEYED_TABLES {
+                tx.execute(&format!("DELETE FROM {}", table), [])?;
+            }
+            tx.execute("DELETE FROM collection_items WHERE item_type = ?1", params![COLLECTION_ITEM_FACT])?;
+        }
+
+        for fact in facts {
+            let existing: Option<(i64, f64, String)> = tx.query_row(
+                "SELECT id, confidence, last_confirmed FROM user_facts WHERE category = ?1 AND key = ?2",
+                params![fact.category, fact.key],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            ).optional()?;
+            match existing {
+                None => {
+                    tx.execute(
+                        "INSERT INTO user_facts (category, key, value, confidence, source_type, source_conversation_id, first_mentioned, last_confirmed, mention_count, valid_until)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                        params![fact.category, fact.key, fact.value, fact.confidence, fact.source_type, fact.source_conversation_id,
+                            fact.first_mentioned, fact.last_confirmed, fact.mention_count, fact.valid_until],
+                    )?;
+                    report.facts_added += 1;
+                }
+                Some((id, confidence, last_confirmed)) if strategy.incoming_wins((confidence, &last_confirmed), (fact.confidence, &fact.last_confirmed)) => {
+                    record_fact_history(&tx, id, &fact.value, fact.confidence, None)?;
+                    tx.execute(
+                        "UPDATE user_facts SET value = ?1, confidence = ?2, source_type = ?3, last_confirmed = ?4,
+                         mention_count = MAX(mention_count, ?5), valid_until = ?6,
+                         first_mentioned = MIN(first_mentioned, ?7) WHERE id = ?8",
+                        params![fact.value, fact.confidence, fact.source_type, fact.last_confirmed, fact.mention_count,
+                            fact.valid_until, fact.first_mentioned, id],
+                    )?;
+                    report.facts_updated += 1;
+                }
+                Some(_) => report.kept += 1,
+            }
+        }
+
+        for pattern in patterns {
+            let existing: Option<(i64, f64, String)> = tx.query_row(
+                "SELECT id, confidence, last_updated FROM user_patterns WHERE pattern_type = ?1 AND LOWER(description) = LOWER(?2)",
+                params![pattern.pattern_type, pattern.description],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            ).optional()?;
+            match existing {
+                None => {
+                    tx.execute(
+                        "INSERT INTO user_patterns (pattern_type, description, confidence, evidence, first_observed, last_updated, observation_count, verdict)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                        params![pattern.pattern_type, pattern.description, pattern.confidence, pattern.evidence,
+                            pattern.first_observed, pattern.last_updated, pattern.observation_count, pattern.verdict],
+                    )?;
+                    report.patterns_added += 1;
+                }
+                Some((id, confidence, last_updated)) if strategy.incoming_wins((confidence, &last_updated), (pattern.confidence, &pattern.last_updated)) => {
+                    tx.execute(
+                        "UPDATE user_patterns SET confidence = ?1, evidence = ?2, last_updated = ?3,
+                         observation_count = MAX(observation_count, ?4), verdict = COALESCE(?5, verdict) WHERE id = ?6",
+                        params![pattern.confidence, pattern.evidence, pattern.last_updated, pattern.observation_count, pattern.verdict, id],
+                    )?;
+                    report.patterns_updated += 1;
+                }
+                Some(_) => report.kept += 1,
+            }
+        }
+
+        // Summaries have no confidence; the one covering more messages counts as surer
+        for summary in summaries {
+            let conversation_here: bool = tx.query_row(
+                "SELECT COUNT(*) FROM conversations WHERE id = ?1",
+                [&summary.conversation_id],
+                |row| Ok(row.get::<_, i64>(0)? > 0),
+            )?;
+            if !conversation_here {
+                report.summaries_skipped += 1;
+                continue;
+            }
+            let existing: Option<(i64, i64, String)> = tx.query_row(
+                "SELECT id, COALESCE(message_count, 0), created_at FROM conversation_summaries WHERE conversation_id = ?1",
+                [&summary.conversation_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            ).optional()?;
+            let wins = existing.as_ref().map(|(_, count, created_at)| {
+                strategy.incoming_wins((*count as f64, created_at), (summary.message_count as f64, &summary.created_at))
+            });
+            if wins == Some(false) {
+                report.kept += 1;
+                continue;
+            }
+            if let Some((id, _, _)) = existing {
+                tx.execute("DELETE FROM conversation_summaries WHERE id = ?1", [id])?;
+                report.summaries_updated += 1;
+            } else {
+                report.summaries_added += 1;
+            }
+            tx.execute(
+                "INSERT INTO conversation_summaries (conversation_id, summary, key_topics, emotional_tone, user_state, agents_involved, message_count, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![summary.conversation_id, summary.summary, summary.key_topics, summary.emotional_tone,
+                    summary.user_state, summary.agents_involved, summary.message_count, summary.created_at],
+            )?;
+        }
+
+        // Themes have no confidence either; frequency stands in for it
+        for theme in themes {
+            let existing: Option<(i64, i64, String)> = tx.query_row(
+                "SELECT id, frequency, last_mentioned FROM recurring_themes WHERE theme = ?1",
+                [&theme.theme],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            ).optional()?;
+            match existing {
+                None => {
+                    tx.execute(
+                        "INSERT INTO recurring_themes (theme, frequency, last_mentioned, related_conversations) VALUES (?1, ?2, ?3, ?4)",
+                        params![theme.theme, theme.frequency, theme.last_mentioned, theme.related_conversations],
+                    )?;
+                    report.themes_added += 1;
+                }
+                Some((id, frequency, last_mentioned)) if strategy.incoming_wins((frequency as f64, &last_mentioned), (theme.frequency as f64, &theme.last_mentioned)) => {
+                    tx.execute(
+                        "UPDATE recurring_themes SET frequency = ?1, last_mentioned = ?2, related_conversations = COALESCE(?3, related_conversations) WHERE id = ?4",
+                        params![theme.frequency, theme.last_mentioned, theme.related_conversations, id],
+                    )?;
+                    report.themes_updated += 1;
+                }
+                Some(_) => report.kept += 1,
+            }
+        }
+
+        for context in user_context {
+            let existing: Option<(i64, f64, String)> = tx.query_row(
+                "SELECT id, confidence, updated_at FROM user_context WHERE key = ?1",
+                [&context.key],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            ).optional()?;
+            match existing {
+                None => {
+                    tx.execute(
+                        "INSERT INTO user_context (key, value, confidence, source_agent, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![context.key, context.value, context.confidence, context.source_agent, context.updated_at],
+                    )?;
+                    report.context_added += 1;
+                }
+                Some((id, confidence, updated_at)) if strategy.incoming_wins((confidence, &updated_at), (context.confidence, &context.updated_at)) => {
+                    tx.execute(
+                        "UPDATE user_context SET value = ?1, confidence = ?2, source_agent = ?3, updated_at = ?4 WHERE id = ?5",
+                        params![context.value, context.confidence, context.source_agent, context.updated_at, id],
+                    )?;
+                    report.context_updated += 1;
+                }
+                Some(_) => report.kept += 1,
+            }
+        }
+
+        tx.commit()?;
+        Ok(report)
+    })
+}
+
+pub fn get_conversation(id: &str) -> Result<Option<Conversation>> {
+    with_connection(|conn| {
+        let result = conn.query_row(
+            "SELECT id, title, summary, limbo_summary, processed, is_disco, created_at, updated_at, intensity, closed_at, pinned, project_id, locked, parent_conversation_id FROM conversations WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok(Conversation {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    summary: row.get(2)?,
+                    limbo_summary: row.get(3)?,
+                    processed: row.get::<_, i64>(4)? != 0,
+                    is_disco: row.get::<_, i64>(5).unwrap_or(0) != 0,
+                    created_at: row.get(6)?,
+                    updated_at: row.get(7)?,
+                    intensity: row.get::<_, Option<f64>>(8)?.unwrap_or(0.0),
+                    closed_at: row.get(9)?,
+                    pinned: row.get::<_, Option<i64>>(10)?.unwrap_or(0) != 0,
+                    project_id: row.get(11)?,
+                    locked: row.get::<_, Option<i64>>(12)?.unwrap_or(0) != 0,
+                    parent_conversation_id: row.get(13)?,
+                })
+            }
+        );
+        match result {
+            Ok(conv) => Ok(Some(conv)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+/// Conversations with messages, newest first. Auto-closed ones are excluded unless `include_closed`.
+pub fn get_recent_conversations(limit: usize, include_closed: bool) -> Result<Vec<Conversation>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT c.id, c.title, c.summary, c.limbo_summary, c.processed, c.is_disco, c.created_at, c.updated_at, c.intensity, c.closed_at,
+                    (SELECT COUNT(*) FROM messages WHERE conversation_id = c.id) as msg_count, c.pinned, c.project_id, c.locked, c.parent_conversation_id
+             FROM conversations c
+             WHERE EXISTS (SELECT 1 FROM messages WHERE conversation_id = c.id)
+               AND (?2 OR c.closed_at IS NULL OR c.pinned = 1)
+               AND c.deleted_at IS NULL
+             ORDER BY COALESCE(c.pinned, 0) DESC, c.updated_at DESC 
+             LIMIT ?1"
+        )?;
+        
+        let convs = stmt.query_map(params![limit, include_closed], |row| {
+            Ok(Conversation {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                summary: row.get(2)?,
+                limbo_summary: row.get(3)?,
+                processed: row.get::<_, i64>(4)? != 0,
+                is_disco: row.get::<_, i64>(5).unwrap_or(0) != 0,
+                created_at: row.get(6)?,
+                updated_at: row.get(7)?,
+                intensity: row.get::<_, Option<f64>>(8)?.unwrap_or(0.0),
+                closed_at: row.get(9)?,
+                pinned: row.get::<_, Option<i64>>(11)?.unwrap_or(0) != 0,
+                project_id: row.get(12)?,
+                locked: row.get::<_, Option<i64>>(13)?.unwrap_or(0) != 0,
+                parent_conversation_id: row.get(14)?,
+            })
+        })?;
+        
+        convs.collect()
+    })
+}
+
+/// Get conversations that need recovery (unprocessed, have messages, older than 1 min)
+/// Used on startup to finalize conversations from crashes/force-quits
+pub fn get_conversations_needing_recovery() -> Result<Vec<Conversation>> {
+    use chrono::Duration;
+    
+    with_connection(|conn| {
+        // Get conversations that:
+        // 1. Are not processed
+        // 2. Are older than 1 minute (not currently being written to)
+        let cutoff = (Utc::now() - Duration::minutes(1)).to_rfc3339();
+        
+        let mut stmt = conn.prepare(
+            "SELECT c.id, c.title, c.summary, c.limbo_summary, c.processed, c.is_disco, c.created_at, c.updated_at, c.intensity, c.closed_at,
+                    (SELECT COUNT(*) FROM messages WHERE conversation_id = c.id) as msg_count, c.pinned, c.project_id, c.locked, c.parent_conversation_id
+             FROM conversations c
+             WHERE c.processed = 0 
+               AND c.import_id IS NULL
+               AND c.updated_at < ?1
+             ORDER BY c.updated_at DESC"
+        )?;
+        
+        let convs = stmt.query_map([cutoff], |row| {
+            let msg_count: i64 = row.get(10)?;
+            // Only include if has at least 2 messages (user + agent)
+            if msg_count >= 2 {
+                Ok(Some(Conversation {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    summary: row.get(2)?,
+                    limbo_summary: row.get(3)?,
+                    processed: row.get::<_, i64>(4)? != 0,
+                    is_disco: row.get::<_, i64>(5).unwrap_or(0) != 0,
+                    created_at: row.get(6)?,
+                    updated_at: row.get(7)?,
+                    intensity: row.get::<_, Option<f64>>(8)?.unwrap_or(0.0),
+                    closed_at: row.get(9)?,
+                    pinned: row.get::<_, Option<i64>>(11)?.unwrap_or(0) != 0,
+                    project_id: row.get(12)?,
+                    locked: row.get::<_, Option<i64>>(13)?.unwrap_or(0) != 0,
+                    parent_conversation_id: row.get(14)?,
+                }))
+            } else {
+                Ok(None)
+            }
+        })?;
+        
+        // Filter out None values
+        convs.filter_map(|r| r.transpose()).collect()
+    })
+}
+
+/// Open conversations with messages whose last activity is older than `cutoff` (RFC3339)
+pub fn get_inactive_conversation_ids(cutoff: &str) -> Result<Vec<String>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT c.id FROM conversations c
+             WHERE c.closed_at IS NULL
+               AND c.deleted_at IS NULL
+               AND c.updated_at < ?1
+               AND EXISTS (SELECT 1 FROM messages WHERE conversation_id = c.id)
+             ORDER BY c.updated_at ASC"
+        )?;
+        
+        let ids = stmt.query_map(params![cutoff], |row| row.get(0))?;
+        ids.collect()
+    })
+}
+
+pub fn close_conversation(conversation_id: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE conversations SET closed_at = ?1 WHERE id = ?2",
+            params![now, conversation_id],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn set_conversation_pinned(conversation_id: &str, pinned: bool) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE conversations SET pinned = ?1 WHERE id = ?2",
+            params![pinned as i64, conversation_id],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn set_conversation_locked(conversation_id: &str, locked: bool) -> Result<bool> {
+    with_connection(|conn| {
+        let updated = conn.execute(
+            "UPDATE conversations SET locked = ?1 WHERE id = ?2",
+            params![locked as i64, conversation_id],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+pub fn is_conversation_locked(conversation_id: &str) -> Result<bool> {
+    with_connection(|conn| {
+        let locked: Option<i64> = conn.query_row(
+            "SELECT COALESCE(locked, 0) FROM conversations WHERE id = ?1",
+            params![conversation_id],
+            |row| row.get(0),
+        ).optional()?;
+        Ok(locked.unwrap_or(0) != 0)
+    })
+}
+
+pub fn set_conversation_intensity(conversation_id: &str, intensity: f64) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE conversations SET intensity = ?1 WHERE id = ?2",
+            params![intensity, conversation_id],
+        )?;
+        Ok(())
+    })
+}
+
+/// Plain-language override for a conversation: None = follow the profile, 0 = off
+pub fn get_conversation_reading_level(conversation_id: &str) -> Result<Option<i64>> {
+    with_connection(|conn| {
+        match conn.query_row(
+            "SELECT reading_level FROM conversations WHERE id = ?1",
+            [conversation_id],
+            |row| row.get(0),
+        ) {
+            Ok(level) => Ok(level),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+pub fn set_conversation_reading_level(conversation_id: &str, level: Option<i64>) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE conversations SET reading_level = ?1 WHERE id = ?2",
+            params![level, conversation_id],
+        )?;
+        Ok(())
+    })
+}
+
+/// Model tier forced for a conversation ('economy' | 'premium'); None = adaptive
+pub fn get_conversation_model_tier(conversation_id: &str) -> Result<Option<String>> {
+    get_conversation_settings(conversation_id).map(|settings| settings.model_tier)
+}
+
+pub fn set_conversation_model_tier(conversation_id: &str, tier: Option<&str>) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO conversation_settings (conversation_id, model_tier, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(conversation_id) DO UPDATE SET model_tier = excluded.model_tier, updated_at = excluded.updated_at",
+            params![conversation_id, tier, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    })
+}
+
+/// A conversation's overrides; all None when it has none
+pub fn get_conversation_settings(conversation_id: &str) -> Result<ConversationSettings> {
+    with_connection(|conn| {
+        let settings = conn.query_row(
+            "SELECT model_tier, mode, instinct_weight, logic_weight, psyche_weight, updated_at
+             FROM conversation_settings WHERE conversation_id = ?1",
+            [conversation_id],
+            |row| Ok(ConversationSettings {
+                conversation_id: conversation_id.to_string(),
+                model_tier: row.get(0)?,
+                mode: row.get(1)?,
+                instinct_weight: row.get(2)?,
+                logic_weight: row.get(3)?,
+                psyche_weight: row.get(4)?,
+                updated_at: row.get(5)?,
+            }),
+        ).optional()?;
+        Ok(settings.unwrap_or_else(|| ConversationSettings { conversation_id: conversation_id.to_string(), ..Default::default() }))
+    })
+}
+
+/// Replace a conversation's overrides; all None removes them
+pub fn set_conversation_settings(settings: &ConversationSettings) -> Result<()> {
+    with_connection(|conn| {
+        let empty = settings.model_tier.is_none()
+            && settings.mode.is_none()
+            && settings.instinct_weight.is_none()
+            && settings.logic_weight.is_none()
+            && settings.psyche_weight.is_none();
+        if empty {
+            conn.execute("DELETE FROM conversation_settings WHERE conversation_id = ?1", params![settings.conversation_id])?;
+            return Ok(());
+        }
+        conn.execute(
+            "INSERT OR REPLACE INTO conversation_settings (conversation_id, model_tier, mode, instinct_weight, logic_weight, psyche_weight, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                settings.conversation_id,
+                settings.model_tier,
+                settings.mode,
+                settings.instinct_weight,
+                settings.logic_weight,
+                settings.psyche_weight,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+/// Controls set from inside a conversation; defaults when it doesn't exist
+pub fn get_conversation_controls(conversation_id: &str) -> Result<ConversationControls> {
+    with_connection(|conn| {
+        let controls = conn.query_row(
+            "SELECT s.mode, c.muted_agents, c.verbosity, c.incognito FROM conversations c
+             LEFT JOIN conversation_settings s ON s.conversation_id = c.id
+             WHERE c.id = ?1",
+            [conversation_id],
+            |row| {
+                let muted: Option<String> = row.get(1)?;
+                Ok(ConversationControls {
+                    mode: row.get(0)?,
+                    muted_agents: muted.and_then(|m| serde_json::from_str(&m).ok()).unwrap_or_default(),
+                    verbosity: row.get(2)?,
+                    incognito: row.get::<_, Option<i64>>(3)?.unwrap_or(0) != 0,
+                })
+            },
+        ).optional()?;
+        Ok(controls.unwrap_or_default())
+    })
+}
+
+pub fn set_conversation_controls(conversation_id: &str, controls: &ConversationControls) -> Result<()> {
+    let muted = (!controls.muted_agents.is_empty())
+        .then(|| serde_json::to_string(&controls.muted_agents).unwrap_or_default());
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE conversations SET muted_agents = ?1, verbosity = ?2, incognito = ?3 WHERE id = ?4",
+            params![muted, controls.verbosity, controls.incognito as i64, conversation_id],
+        )?;
+        tx.execute(
+            "INSERT INTO conversation_settings (conversation_id, mode, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(conversation_id) DO UPDATE SET mode = excluded.mode, updated_at = excluded.updated_at",
+            params![conversation_id, controls.mode, Utc::now().to_rfc3339()],
+        )?;
+        tx.commit()
+    })
+}
+
+pub fn save_model_decision(decision: &ModelDecision) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO model_decisions (conversation_id, agent, complexity, tier, model, reason, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                decision.conversation_id,
+                decision.agent,
+                decision.complexity,
+                decision.tier,
+                decision.model,
+                decision.reason,
+                decision.created_at,
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+/// Model decisions, newest first; all conversations when `conversation_id` is None
+pub fn get_model_decisions(conversation_id: Option<&str>, limit: usize) -> Result<Vec<ModelDecision>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, conversation_id, agent, complexity, tier, model, reason, created_at
+             FROM model_decisions
+             WHERE (?1 IS NULL OR conversation_id = ?1)
+             ORDER BY created_at DESC
+             LIMIT ?2"
+        )?;
+        let decisions = stmt.query_map(params![conversation_id, limit], |row| {
+            Ok(ModelDecision {
+                id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                agent: row.get(2)?,
+                complexity: row.get(3)?,
+                tier: row.get(4)?,
+                model: row.get(5)?,
+                reason: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        })?;
+        decisions.collect()
+    })
+}
+
+/// Plain-language reading level for a persona profile (None = off)
+pub fn get_profile_reading_level(profile_id: &str) -> Result<Option<i64>> {
+    with_connection(|conn| {
+        match conn.query_row(
+            "SELECT reading_level FROM persona_profiles WHERE id = ?1",
+            [profile_id],
+            |row| row.get(0),
+        ) {
+            Ok(level) => Ok(level),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+pub fn set_profile_reading_level(profile_id: &str, level: Option<i64>) -> Result<bool> {
+    with_connection(|conn| {
+        let updated = conn.execute(
+            "UPDATE persona_profiles SET reading_level = ?1 WHERE id = ?2",
+            params![level, profile_id],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+/// Late-night window for a persona profile as (start, end), None when it has none
+pub fn get_profile_late_night_window(profile_id: &str) -> Result<Option<(String, String)>> {
+    with_connection(|conn| {
+        let window: Option<(Option<String>, Option<String>)> = conn.query_row(
+            "SELECT late_night_start, late_night_end FROM persona_profiles WHERE id = ?1",
+            [profile_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
+        Ok(match window {
+            Some((Some(start), Some(end))) => Some((start, end)),
+            _ => None,
+        })
+    })
+}
+
+pub fn set_profile_late_night_window(profile_id: &str, window: Option<(&str, &str)>) -> Result<bool> {
+    let (start, end) = window.unzip();
+    with_connection(|conn| {
+        let updated = conn.execute(
+            "UPDATE persona_profiles SET late_night_start = ?1, late_night_end = ?2 WHERE id = ?3",
+            params![start, end, profile_id],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+/// Append to the limbo summary (incremental summary built during conversation)
+pub fn append_limbo_summary(conversation_id: &str, new_content: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        // Get existing limbo summary
+        let existing: Option<String> = conn.query_row(
+            "SELECT limbo_summary FROM conversations WHERE id = ?1",
+            params![conversation_id],
+            |row| row.get(0)
+        ).ok();
+        
+        // Append new content
+        let updated = match existing {
+            Some(existing_text) => format!("{}\n\n{}", existing_text, new_content),
+            None => new_content.to_string(),
+        };
+        
+        conn.execute(
+            "UPDATE conversations SET limbo_summary = ?1, updated_at = ?2 WHERE id = ?3",
+            params![updated, now, conversation_id]
+        )?;
+        Ok(())
+    })
+}
+
+/// Mark a conversation as fully processed (after finalization)
+pub fn mark_conversation_processed(conversation_id: &str, final_summary: Option<&str>) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        if let Some(summary) = final_summary {
+            conn.execute(
+                "UPDATE conversations SET processed = 1, summary = ?1, updated_at = ?2 WHERE id = ?3",
+                params![summary, now, conversation_id]
+            )?;
+        } else {
+            conn.execute(
+                "UPDATE conversations SET processed = 1, updated_at = ?1 WHERE id = ?2",
+                params![now, conversation_id]
+            )?;
+        }
+        Ok(())
+    })
+}
+
+// ============ Messages ============
+
+pub fn save_message(message: &Message) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO messages (id, conversation_id, role, content, response_type, references_message_id, timestamp, status, round_id, latency_ms, input_tokens, output_tokens, cost_usd, model)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                message.id,
+                message.conversation_id,
+                message.role,
+                message.content,
+                message.response_type,
+                message.references_message_id,
+                message.timestamp,
+                message.status,
+                message.round_id,
+                message.latency_ms,
+                message.input_tokens,
+                message.output_tokens,
+                message.cost_usd,
+                message.model
+            ]
+        )?;
+        
+        // Update conversation timestamp (and reopen it if it was auto-closed)
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE conversations SET updated_at = ?1, closed_at = NULL WHERE id = ?2",
+            params![now, message.conversation_id]
+        )?;
+        
+        Ok(())
+    })
+}
+
+const MESSAGE_COLUMNS: &str =
+    "id, conversation_id, role, content, response_type, references_message_id, timestamp, status, round_id, latency_ms, input_tokens, output_tokens, cost_usd, model";
+
+fn message_from_row(row: &rusqlite::Row) -> rusqlite::Result<Message> {
+    Ok(Message {
+        id: row.get(0)?,
+        conversation_id: row.get(1)?,
+        role: row.get(2)?,
+        content: row.get(3)?,
+        response_type: row.get(4)?,
+        references_message_id: row.get(5)?,
+        timestamp: row.get(6)?,
+        status: row.get(7)?,
+        round_id: row.get(8)?,
+        latency_ms: row.get(9)?,
+        input_tokens: row.get(10)?,
+        output_tokens: row.get(11)?,
+        cost_usd: row.get(12)?,
+        model: row.get(13)?,
+    })
+}
+
+pub fn get_message(message_id: &str) -> Result<Option<Message>> {
+    with_connection(|conn| {
+        match conn.query_row(
+            &format!("SELECT {} FROM messages WHERE id = ?1", MESSAGE_COLUMNS),
+            [message_id],
+            message_from_row,
+        ) {
+            Ok(message) => Ok(Some(message)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+pub fn set_message_status(message_id: &str, status: &str) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute("UPDATE messages SET status = ?1 WHERE id = ?2", params![status, message_id])?;
+        Ok(())
+    })
+}
+
+/// Replace a message's text, keeping the old text as a revision. Returns false if
+/// the message doesn't exist or the text is unchanged.
+pub fn update_message_content(message_id: &str, new_content: &str) -> Result<bool> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let old: Option<String> = tx.query_row(
+            "SELECT content FROM messages WHERE id = ?1",
+            [message_id],
+            |row| row.get(0),
+        ).optional()?;
+        match old {
+            Some(old) if old != new_content => {
+                tx.execute(
+                    "INSERT INTO message_revisions (message_id, content, revised_at) VALUES (?1, ?2, ?3)",
+                    params![message_id, old, Utc::now().to_rfc3339()],
+                )?;
+                tx.execute("UPDATE messages SET content = ?1 WHERE id = ?2", params![new_content, message_id])?;
+                tx.commit()?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    })
+}
+
+/// Earlier versions of a message, oldest first
+pub fn get_message_revisions(message_id: &str) -> Result<Vec<MessageRevision>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, message_id, content, revised_at FROM message_revisions
+             WHERE message_id = ?1 ORDER BY revised_at ASC, id ASC"
+        )?;
+        let revisions = stmt.query_map([message_id], |row| {
+            Ok(MessageRevision {
+                id: row.get(0)?,
+                message_id: row.get(1)?,
+                content: row.get(2)?,
+                revised_at: row.get(3)?,
+            })
+        })?;
+        revisions.collect()
+    })
+}
+
+/// Record the active and disco agents a user message's round was dispatched with
+pub fn set_round_agents(message_id: &str, active_agents: &[String], disco_agents: &[String]) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE messages SET round_agents = ?1, round_disco_agents = ?2 WHERE id = ?3",
+            params![
+                serde_json::to_string(active_agents).unwrap_or_default(),
+                serde_json::to_string(disco_agents).unwrap_or_default(),
+                message_id,
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+/// The (active, disco) agents a round was dispatched with. Rounds sent before
+/// these were recorded fall back to the agents that replied in them (disco
+/// unknown); None when there's nothing to go on.
+pub fn get_round_agents(message_id: &str) -> Result<Option<(Vec<String>, Vec<String>)>> {
+    with_connection(|conn| {
+        let recorded: Option<(Option<String>, Option<String>, Option<String>)> = conn.query_row(
+            "SELECT round_agents, round_disco_agents, COALESCE(round_id, id) FROM messages WHERE id = ?1",
+            [message_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).optional()?;
+        let Some((active, disco, round_id)) = recorded else {
+            return Ok(None);
+        };
+        if let Some(active) = active.and_then(|a| serde_json::from_str::<Vec<String>>(&a).ok()) {
+            let disco = disco.and_then(|d| serde_json::from_str(&d).ok()).unwrap_or_default();
+            return Ok(Some((active, disco)));
+        }
+        
+        let mut stmt = conn.prepare(
+            "SELECT role FROM messages
+             WHERE round_id = ?1 AND role IN ('instinct', 'logic', 'psyche')
+             GROUP BY role ORDER BY MIN(timestamp) ASC"
+        )?;
+        let replied = stmt.query_map([round_id], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>>>()?;
+        Ok((!replied.is_empty()).then_some((replied, Vec::new())))
+    })
+}
+
+/// Delete the agent and Governor replies of a round (everything but the user's
+/// message) so it can be regenerated. Returns how many were removed.
+pub fn delete_round_replies(round_id: &str) -> Result<usize> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM response_citations WHERE message_id IN
+                (SELECT id FROM messages WHERE round_id = ?1 AND role != 'user')",
+            [round_id],
+        )?;
+        tx.execute(
+            "DELETE FROM message_revisions WHERE message_id IN
+                (SELECT id FROM messages WHERE round_id = ?1 AND role != 'user')",
+            [round_id],
+        )?;
+        tx.execute(
+            "DELETE FROM attachments WHERE message_id IN
+                (SELECT id FROM messages WHERE round_id = ?1 AND role != 'user')",
+            [round_id],
+        )?;
+        let removed = tx.execute("DELETE FROM messages WHERE round_id = ?1 AND role != 'user'", [round_id])?;
+        tx.commit()?;
+        Ok(removed)
+    })
+}
+
+/// Delete one message. With `include_replies`, the replies that reference it go
+/// too (for a user message, its whole round); otherwise they're kept and unlinked.
+/// Returns how many messages were removed.
+pub fn delete_message(message_id: &str, include_replies: bool) -> Result<usize> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let mut ids = vec![message_id.to_string()];
+        if include_replies {
+            let mut stmt = tx.prepare(
+                "SELECT id FROM messages
+                 WHERE id != ?1 AND (references_message_id = ?1 OR round_id = ?1)"
+            )?;
+            let replies = stmt.query_map([message_id], |row| row.get::<_, String>(0))?;
+            ids.extend(replies.collect::<Result<Vec<_>>>()?);
+        } else {
+            tx.execute(
+                "UPDATE messages SET references_message_id = NULL WHERE references_message_id = ?1",
+                [message_id],
+            )?;
+        }
+        
+        let mut removed = 0;
+        for id in &ids {
+            tx.execute("DELETE FROM response_citations WHERE message_id = ?1", [id])?;
+            tx.execute("DELETE FROM message_revisions WHERE message_id = ?1", [id])?;
+            tx.execute("DELETE FROM attachments WHERE message_id = ?1", [id])?;
+            tx.execute("UPDATE action_items SET message_id = NULL WHERE message_id = ?1", [id])?;
+            removed += tx.execute("DELETE FROM messages WHERE id = ?1", [id])?;
+        }
+        tx.commit()?;
+        Ok(removed)
+    })
+}
+
+/// The failed stub left in a round's slot for an agent, if any
+pub fn get_failed_agent_slot(round_id: &str, agent: &str) -> Result<Option<Message>> {
+    with_connection(|conn| {
+        match conn.query_row(
+            &format!(
+                "SELECT {} FROM messages WHERE round_id = ?1 AND role = ?2 AND status = ?3
+                 ORDER BY timestamp ASC LIMIT 1",
+                MESSAGE_COLUMNS
+            ),
+            params![round_id, agent, MESSAGE_STATUS_FAILED],
+            message_from_row,
+        ) {
+            Ok(message) => Ok(Some(message)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+/// Sends still pending at startup died with the previous process
+pub fn fail_stale_pending_messages() -> Result<usize> {
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE messages SET status = ?1 WHERE status = ?2",
+            params![MESSAGE_STATUS_FAILED, MESSAGE_STATUS_PENDING],
+        )
+    })
+}
+
+pub fn get_conversation_messages(conversation_id: &str) -> Result<Vec<Message>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM messages WHERE conversation_id = ?1 ORDER BY timestamp ASC",
+            MESSAGE_COLUMNS
+        ))?;
+        
+        let messages = stmt.query_map([conversation_id], message_from_row)?;
+        
+        messages.collect()
+    })
+}
+
+/// A conversation's messages without the failed ones (unanswered sends and
+/// stubs for agents that didn't reply), oldest first. What summaries, memory
+/// extraction and exports read.
+pub fn get_delivered_messages(conversation_id: &str) -> Result<Vec<Message>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM messages
+             WHERE conversation_id = ?1 AND (status IS NULL OR status != ?2)
+             ORDER BY timestamp ASC",
+            MESSAGE_COLUMNS
+        ))?;
+        
+        let messages = stmt.query_map(params![conversation_id, MESSAGE_STATUS_FAILED], message_from_row)?;
+        
+        messages.collect()
+    })
+}
+
+/// One page of a conversation's history, oldest first
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct MessagePage {
+    pub messages: Vec<Message>,
+    pub has_more: bool,             // Older messages remain before messages[0]
+}
+
+/// Keyset page of messages ending just before `before_id` (or at the newest
+/// message when there's no cursor). Ordered by (timestamp, id) so messages
+/// sharing a timestamp are neither skipped nor repeated across pages. `None`
+/// if the cursor isn't a message in this conversation.
+pub fn get_messages_page(conversation_id: &str, before_id: Option<&str>, limit: usize) -> Result<Option<MessagePage>> {
+    with_connection(|conn| {
+        let cursor: Option<(String, String)> = match before_id {
+            Some(id) => match conn.query_row(
+                "SELECT timestamp, id FROM messages WHERE id = ?1 AND conversation_id = ?2",
+                params![id, conversation_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ).optional()? {
+                Some(cursor) => Some(cursor),
+                None => return Ok(None),
+            },
+            None => None,
+        };
+        
+        // One extra row tells us whether there's anything older
+        let fetch = limit as i64 + 1;
+        let mut messages: Vec<Message> = match &cursor {
+            Some((timestamp, id)) => {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {} FROM messages
+                     WHERE conversation_id = ?1 AND (timestamp, id) < (?2, ?3)
+                     ORDER BY timestamp DESC, id DESC LIMIT ?4",
+                    MESSAGE_COLUMNS
+                ))?;
+                let rows = stmt.query_map(params![conversation_id, timestamp, id, fetch], message_from_row)?;
+                rows.collect::<Result<_>>()?
+            }
+            None => {
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {} FROM messages
+                     WHERE conversation_id = ?1
+                     ORDER BY timestamp DESC, id DESC LIMIT ?2",
+                    MESSAGE_COLUMNS
+                ))?;
+                let rows = stmt.query_map(params![conversation_id, fetch], message_from_row)?;
+                rows.collect::<Result<_>>()?
+            }
+        };
+        
+        let has_more = messages.len() > limit;
+        messages.truncate(limit);
+        messages.reverse();
+        Ok(Some(MessagePage { messages, has_more }))
+    })
+}
+
+/// Recent messages for model context, oldest first (failed sends and agent stubs excluded)
+pub fn get_recent_messages(conversation_id: &str, limit: usize) -> Result<Vec<Message>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM messages
+             WHERE conversation_id = ?1 AND (status IS NULL OR status != 'failed')
+             ORDER BY timestamp DESC LIMIT ?2",
+            MESSAGE_COLUMNS
+        ))?;
+        
+        let messages = stmt.query_map(params![conversation_id, limit], message_from_row)?;
+        
+        let mut result: Vec<Message> = messages.collect::<Result<Vec<_>>>()?;
+        result.reverse();
+        Ok(result)
+    })
+}
+
+/// Most recent message contents for one role across all conversations (newest first)
+pub fn get_recent_messages_by_role(role: &str, limit: usize) -> Result<Vec<String>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT content FROM messages
+             WHERE role = ?1
+             ORDER BY timestamp DESC
+             LIMIT ?2"
+        )?;
+        
+        let contents = stmt.query_map(params![role, limit], |row| row.get(0))?;
+        contents.collect()
+    })
+}
+
+/// A full-text search hit with enough context to jump to it
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct MessageSearchResult {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub conversation_title: Option<String>,
+    pub role: String,
+    pub timestamp: String,
+    pub snippet: String,            // Matched terms wrapped in [ ]
+}
+
+/// A message the user flagged to find again, with an optional note on why
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct Bookmark {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub conversation_title: Option<String>,
+    pub role: String,               // Who said it: 'user', an agent, or 'governor'
+    pub content: String,
+    pub timestamp: String,          // When the message was sent
+    pub note: Option<String>,       // e.g. "Logic's framing of the job decision"
+    pub bookmarked_at: String,
+}
+
+/// Refill the search index from the messages table. VACUUM can renumber the
+/// messages' rowids, which the index rows are keyed by, so it runs after one.
+fn rebuild_message_index(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "
+        DELETE FROM messages_fts;
+        INSERT INTO messages_fts (rowid, content, message_id, conversation_id)
+            SELECT rowid, content, id, conversation_id FROM messages;
+        "
+    )
+}
+
+/// Turn free text into an FTS5 query: every word must match, the last as a prefix
+fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|t| t.replace('"', ""))
+        .filter(|t| !t.is_empty())
+        .map(|t| format!("\"{}\"", t))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
+
+/// Keyword search across all message content, best matches first
+pub fn search_messages(query: &str, limit: usize) -> Result<Vec<MessageSearchResult>> {
+    let Some(fts) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT f.message_id, f.conversation_id, c.title, m.role, m.timestamp,
+                    snippet(messages_fts, 0, '[', ']', '...', 12)
+             FROM messages_fts f
+             JOIN messages m ON m.id = f.message_id
+             LEFT JOIN conversations c ON c.id = f.conversation_id
+             WHERE messages_fts MATCH ?1
+               AND c.deleted_at IS NULL
+             ORDER BY rank
+             LIMIT ?2"
+        )?;
+        let results = stmt.query_map(params![fts, limit as i64], |row| {
+            Ok(MessageSearchResult {
+                message_id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                conversation_title: row.get(2)?,
+                role: row.get(3)?,
+                timestamp: row.get(4)?,
+                snippet: row.get(5)?,
+            })
+        })?;
+        results.collect()
+    })
+}
+
+/// Bookmark a message (again, to change the note). Returns false when there's no such message.
+pub fn set_message_bookmark(message_id: &str, note: Option<&str>) -> Result<bool> {
+    with_connection(|conn| {
+        let updated = conn.execute(
+            "UPDATE messages SET bookmarked_at = COALESCE(bookmarked_at, ?1), bookmark_note = ?2 WHERE id = ?3",
+            params![Utc::now().to_rfc3339(), note, message_id],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+/// Returns false when the message wasn't bookmarked
+pub fn clear_message_bookmark(message_id: &str) -> Result<bool> {
+    with_connection(|conn| {
+        let updated = conn.execute(
+            "UPDATE messages SET bookmarked_at = NULL, bookmark_note = NULL WHERE id = ?1 AND bookmarked_at IS NOT NULL",
+            params![message_id],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+/// Bookmarks, newest first. Every filter is optional: one conversation, one
+/// speaker, and text that must appear in the message or its note.
+pub fn get_bookmarks(
+    conversation_id: Option<&str>,
+    role: Option<&str>,
+    text: Option<&str>,
+    limit: usize,
+) -> Result<Vec<Bookmark>> {
+    let pattern = text.map(|t| format!("%{}%", t.trim()));
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT m.id, m.conversation_id, c.title, m.role, m.content, m.timestamp, m.bookmark_note, m.bookmarked_at
+             FROM messages m
+             LEFT JOIN conversations c ON c.id = m.conversation_id
+             WHERE m.bookmarked_at IS NOT NULL
+               AND c.deleted_at IS NULL
+               AND (?1 IS NULL OR m.conversation_id = ?1)
+               AND (?2 IS NULL OR m.role = ?2)
+               AND (?3 IS NULL OR m.content LIKE ?3 OR m.bookmark_note LIKE ?3)
+             ORDER BY m.bookmarked_at DESC
+             LIMIT ?4"
+        )?;
+        let bookmarks = stmt.query_map(params![conversation_id, role, pattern, limit as i64], |row| {
+            Ok(Bookmark {
+                message_id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                conversation_title: row.get(2)?,
+                role: row.get(3)?,
+                content: row.get(4)?,
+                timestamp: row.get(5)?,
+                note: row.get(6)?,
+                bookmarked_at: row.get(7)?,
+            })
+        })?;
+        bookmarks.collect()
+    })
+}
+
+/// An agent's own replies in other conversations that match any of `words`, newest
+/// first. Failed stubs, trashed and incognito conversations are left out.
+pub fn get_agent_messages_matching(agent: &str, words: &[String], exclude_conversation_id: &str, limit: usize) -> Result<Vec<Message>> {
+    let terms: Vec<String> = words.iter()
+        .map(|w| w.replace('"', ""))
+        .filter(|w| !w.is_empty())
+        .map(|w| format!("\"{}\"", w))
+        .collect();
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM messages
+             WHERE id IN (SELECT message_id FROM messages_fts WHERE messages_fts MATCH ?1)
+               AND role = ?2 AND conversation_id != ?3
+               AND (status IS NULL OR status != ?4)
+               AND conversation_id IN (SELECT id FROM conversations WHERE deleted_at IS NULL AND COALESCE(incognito, 0) = 0)
+             ORDER BY timestamp DESC
+             LIMIT ?5",
+            MESSAGE_COLUMNS
+        ))?;
+        let messages = stmt.query_map(
+            params![terms.join(" OR "), agent, exclude_conversation_id, MESSAGE_STATUS_FAILED, limit as i64],
+            message_from_row,
+        )?;
+        messages.collect()
+    })
+}
+
+pub fn clear_conversation_messages(conversation_id: &str) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "DELETE FROM response_citations WHERE message_id IN (SELECT id FROM messages WHERE conversation_id = ?1)",
+            params![conversation_id],
+        )?;
+        conn.execute(
+            "DELETE FROM message_revisions WHERE message_id IN (SELECT id FROM messages WHERE conversation_id = ?1)",
+            params![conversation_id],
+        )?;
+        conn.execute(
+            "DELETE FROM attachments WHERE message_id IN (SELECT id FROM messages WHERE conversation_id = ?1)",
+            params![conversation_id],
+        )?;
+        conn.execute("DELETE FROM messages WHERE conversation_id = ?1", params![conversation_id])?;
+        conn.execute("DELETE FROM disagreements WHERE conversation_id = ?1", params![conversation_id])?;
+        Ok(())
+    })
+}
+
+/// Delete a conversation and everything hanging off it in one transaction: messages
+/// (and their citations), summaries, facts sourced from it, collection links, share
+/// history, action items, and its entries in recurring themes (themes left with no
+/// conversations go too)
+pub fn delete_conversation(conversation_id: &str) -> Result<()> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        
+        // Delete related data first (foreign key constraints)
+        tx.execute(
+            "DELETE FROM response_citations WHERE message_id IN (SELECT id FROM messages WHERE conversation_id = ?1)",
+            params![conversation_id],
+        )?;
+        tx.execute(
+            "DELETE FROM message_revisions WHERE message_id IN (SELECT id FROM messages WHERE conversation_id = ?1)",
+            params![conversation_id],
+        )?;
+        tx.execute(
+            "DELETE FROM attachments WHERE message_id IN (SELECT id FROM messages WHERE conversation_id = ?1)",
+            params![conversation_id],
+        )?;
+        tx.execute("DELETE FROM messages WHERE conversation_id = ?1", params![conversation_id])?;
+        tx.execute("DELETE FROM conversation_summaries WHERE conversation_id = ?1", params![conversation_id])?;
+        // Delete user_facts that reference this conversation
+        tx.execute(
+            "DELETE FROM user_fact_history WHERE fact_id IN (SELECT id FROM user_facts WHERE source_conversation_id = ?1)",
+            params![conversation_id],
+        )?;
+        tx.execute("UPDATE user_fact_history SET conversation_id = NULL WHERE conversation_id = ?1", params![conversation_id])?;
+        tx.execute("DELETE FROM user_facts WHERE source_conversation_id = ?1", params![conversation_id])?;
+        tx.execute(
+            "DELETE FROM fact_conflicts WHERE source_conversation_id = ?1 OR fact_id NOT IN (SELECT id FROM user_facts)",
+            params![conversation_id],
+        )?;
+        tx.execute("DELETE FROM conversation_collections WHERE conversation_id = ?1", params![conversation_id])?;
+        tx.execute("DELETE FROM conversation_tags WHERE conversation_id = ?1", params![conversation_id])?;
+        tx.execute("DELETE FROM share_log WHERE conversation_id = ?1", params![conversation_id])?;
+        tx.execute("DELETE FROM model_decisions WHERE conversation_id = ?1", params![conversation_id])?;
+        tx.execute("DELETE FROM action_items WHERE conversation_id = ?1", params![conversation_id])?;
+        tx.execute("DELETE FROM disagreements WHERE conversation_id = ?1", params![conversation_id])?;
+        tx.execute("DELETE FROM chapter_conversations WHERE conversation_id = ?1", params![conversation_id])?;
+        tx.execute("DELETE FROM conversation_settings WHERE conversation_id = ?1", params![conversation_id])?;
+        tx.execute("UPDATE conversations SET parent_conversation_id = NULL WHERE parent_conversation_id = ?1", params![conversation_id])?;
+        
+        // Detach from recurring themes
+        let themes: Vec<(i64, String)> = {
+            let mut stmt = tx.prepare(
+                "SELECT id, related_conversations FROM recurring_themes WHERE related_conversations LIKE ?1"
+            )?;
+            let rows = stmt.query_map(params![format!("%\"{}\"%", conversation_id)], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<Result<_>>()?
+        };
+        for (id, related) in themes {
+            let mut convs: Vec<String> = serde_json::from_str(&related).unwrap_or_default();
+            convs.retain(|c| c != conversation_id);
+            if convs.is_empty() {
+                tx.execute("DELETE FROM recurring_themes WHERE id = ?1", params![id])?;
+            } else {
+                let convs_json = serde_json::to_string(&convs).unwrap_or_default();
+                tx.execute(
+                    "UPDATE recurring_themes SET related_conversations = ?1 WHERE id = ?2",
+                    params![convs_json, id],
+                )?;
+            }
+        }
+        
+        // Delete the conversation itself
+        tx.execute("DELETE FROM conversations WHERE id = ?1", params![conversation_id])?;
+        tx.commit()
+    })
+}
+
+// ============ Attachments ============
+
+const ATTACHMENT_COLUMNS: &str =
+    "id, message_id, filename, mime_type, path, size_bytes, extracted_text, created_at";
+
+fn attachment_from_row(row: &rusqlite::Row) -> Result<Attachment> {
+    Ok(Attachment {
+        id: row.get(0)?,
+        message_id: row.get(1)?,
+        filename: row.get(2)?,
+        mime_type: row.get(3)?,
+        path: row.get(4)?,
+        size_bytes: row.get(5)?,
+        extracted_text: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+/// Store an attachment; `data` is None when only the path is kept
+pub fn save_attachment(attachment: &Attachment, data: Option<&[u8]>) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO attachments (id, message_id, filename, mime_type, data, path, size_bytes, extracted_text, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                attachment.id,
+                attachment.message_id,
+                attachment.filename,
+                attachment.mime_type,
+                data,
+                attachment.path,
+                attachment.size_bytes,
+                attachment.extracted_text,
+                attachment.created_at,
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn get_attachment(id: &str) -> Result<Option<Attachment>> {
+    with_connection(|conn| {
+        conn.query_row(
+            &format!("SELECT {} FROM attachments WHERE id = ?1", ATTACHMENT_COLUMNS),
+            [id],
+            attachment_from_row,
+        ).optional()
+    })
+}
+
+/// A message's attachments in the order they were added
+pub fn get_message_attachments(message_id: &str) -> Result<Vec<Attachment>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM attachments WHERE message_id = ?1 ORDER BY created_at ASC, rowid ASC",
+            ATTACHMENT_COLUMNS
+        ))?;
+        let attachments = stmt.query_map([message_id], attachment_from_row)?;
+        attachments.collect()
+    })
+}
+
+/// Attachments with extracted text for any of the given messages
+pub fn get_attachments_with_text(message_ids: &[String]) -> Result<Vec<Attachment>> {
+    if message_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    with_connection(|conn| {
+        let placeholders = vec!["?"; message_ids.len()].join(", ");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM attachments
+             WHERE message_id IN ({}) AND extracted_text IS NOT NULL
+             ORDER BY created_at ASC, rowid ASC",
+            ATTACHMENT_COLUMNS, placeholders
+        ))?;
+        let attachments = stmt.query_map(rusqlite::params_from_iter(message_ids), attachment_from_row)?;
+        attachments.collect()
+    })
+}
+
+/// The stored bytes of an attachment; None if it wasn't stored (see `path`) or doesn't exist
+pub fn get_attachment_data(id: &str) -> Result<Option<Vec<u8>>> {
+    with_connection(|conn| {
+        let data: Option<Option<Vec<u8>>> = conn.query_row(
+            "SELECT data FROM attachments WHERE id = ?1",
+            [id],
+            |row| row.get(0),
+        ).optional()?;
+        Ok(data.flatten())
+    })
+}
+
+pub fn delete_attachment(id: &str) -> Result<bool> {
+    with_connection(|conn| {
+        let removed = conn.execute("DELETE FROM attachments WHERE id = ?1", [id])?;
+        Ok(removed > 0)
+    })
+}
+
+// ============ Trash ============
+
+/// A conversation in the trash
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct TrashedConversation {
+    pub id: String,
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub deleted_at: String,
+    pub message_count: i64,
+}
+
+/// Move a conversation to the trash; returns false if it doesn't exist or is already there
+pub fn trash_conversation(conversation_id: &str) -> Result<bool> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        Ok(conn.execute(
+            "UPDATE conversations SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            params![now, conversation_id],
+        )? > 0)
+    })
+}
+
+/// Take a conversation back out of the trash; returns false if it wasn't trashed
+pub fn restore_conversation(conversation_id: &str) -> Result<bool> {
+    with_connection(|conn| {
+        Ok(conn.execute(
+            "UPDATE conversations SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![conversation_id],
+        )? > 0)
+    })
+}
+
+pub fn get_trashed_conversations() -> Result<Vec<TrashedConversation>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT c.id, c.title, c.summary, c.created_at, c.updated_at, c.deleted_at,
+                    (SELECT COUNT(*) FROM messages WHERE conversation_id = c.id)
+             FROM conversations c
+             WHERE c.deleted_at IS NOT NULL
+             ORDER BY c.deleted_at DESC"
+        )?;
+        let convs = stmt.query_map([], |row| {
+            Ok(TrashedConversation {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                summary: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+                deleted_at: row.get(5)?,
+                message_count: row.get(6)?,
+            })
+        })?;
+        convs.collect()
+    })
+}
+
+/// Permanently delete conversations trashed before `cutoff` (RFC3339); returns how many
+pub fn purge_trash(cutoff: &str) -> Result<usize> {
+    let ids: Vec<String> = with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id FROM conversations WHERE deleted_at IS NOT NULL AND deleted_at <= ?1"
+        )?;
+        let ids = stmt.query_map([cutoff], |row| row.get(0))?;
+        ids.collect()
+    })?;
+    for id in &ids {
+        delete_conversation(id)?;
+    }
+    Ok(ids.len())
+}
+
+// ============ User Context ============
+
+pub fn get_all_user_context() -> Result<Vec<UserContext>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, key, value, confidence, source_agent, updated_at FROM user_context ORDER BY confidence DESC"
+        )?;
+        
+        let contexts = stmt.query_map([], |row| {
+            Ok(UserContext {
+                id: row.get(0)?,
+                key: row.get(1)?,
+                value: row.get(2)?,
+                confidence: row.get(3)?,
+                source_agent: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        })?;
+        
+        contexts.collect()
+    })
+}
+
+pub fn clear_user_context() -> Result<()> {
+    with_connection(|conn| {
+        conn.execute("DELETE FROM user_context", [])?;
+        Ok(())
+    })
+}
+
+// ============ User Facts ============
+
+/// Words that turn a value into its opposite ("vegetarian" vs "not vegetarian")
+const NEGATION_WORDS: [&str; 9] = ["not", "no", "never", "none", "dont", "doesnt", "isnt", "dislikes", "hates"];
+
+/// Whether a new value for a fact disagrees with the remembered one. Restating
+/// or narrowing it ("Sarah" -> "Sarah Jones") doesn't; a different value or a
+/// negated one does.
+fn fact_values_conflict(old: &str, new: &str) -> bool {
+    use std::collections::HashSet;
+    let words = |v: &str| -> HashSet<String> {
+        v.to_lowercase()
+            .replace('\'', "")
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(|w| w.to_string())
+            .collect()
+    };
+    let (old_words, new_words) = (words(old), words(new));
+    if old_words == new_words {
+        return false;
+    }
+    let negated = |w: &HashSet<String>| NEGATION_WORDS.iter().any(|n| w.contains(*n));
+    if negated(&old_words) != negated(&new_words) {
+        return true;
+    }
+    !(old_words.is_subset(&new_words) || new_words.is_subset(&old_words))
+}
+
+/// Save a fact, or merge it into the remembered one. A value that conflicts
+/// with what's remembered isn't written over it: it's held in fact_conflicts
+/// until the user resolves it.
+pub fn save_user_fact(fact: &UserFact) -> Result<()> {
+    with_connection(|conn| {
+        let existing: Option<(i64, String, f64)> = conn.query_row(
+            "SELECT id, value, confidence FROM user_facts WHERE category = ?1 AND key = ?2",
+            params![fact.category, fact.key],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        ).optional()?;
+        if let Some((fact_id, _, _)) = existing.as_ref().filter(|(_, value, _)| fact_values_conflict(value, &fact.value)) {
+            conn.execute(
+                "INSERT INTO fact_conflicts (fact_id, value, confidence, source_type, source_conversation_id, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT(fact_id, value) DO UPDATE SET confidence = MAX(confidence, ?3), created_at = ?6",
+                params![fact_id, fact.value, fact.confidence, fact.source_type, fact.source_conversation_id, fact.last_confirmed],
+            )?;
+            return Ok(());
+        }
+        if let Some((fact_id, _, confidence)) = existing {
+            record_fact_history(conn, fact_id, &fact.value, confidence.max(fact.confidence), fact.source_conversation_id.as_deref())?;
+        }
+        conn.execute(
+            "INSERT INTO user_facts (category, key, value, confidence, source_type, source_conversation_id, first_mentioned, last_confirmed, mention_count, valid_until)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(category, key) DO UPDATE SET
+                value = ?3,
+                confidence = MAX(confidence, ?4),
+                last_confirmed = ?8,
+                mention_count = mention_count + 1,
+                valid_until = ?10",
+            params![
+                fact.category,
+                fact.key,
+                fact.value,
+                fact.confidence,
+                fact.source_type,
+                fact.source_conversation_id,
+                fact.first_mentioned,
+                fact.last_confirmed,
+                fact.mention_count,
+                fact.valid_until
+            ]
+        )?;
+        Ok(())
+    })
+}
+
+/// Copy a fact's current value into its history, if the change to
+/// `new_value`/`new_confidence` would replace it
+fn record_fact_history(conn: &Connection, fact_id: i64, new_value: &str, new_confidence: f64, conversation_id: Option<&str>) -> Result<()> {
+    conn.execute(
+        "INSERT INTO user_fact_history (fact_id, category, key, value, confidence, source_type, conversation_id, changed_at)
+         SELECT id, category, key, value, confidence, source_type, ?2, ?3 FROM user_facts
+         WHERE id = ?1 AND (value <> ?4 OR confidence <> ?5)",
+        params![fact_id, conversation_id, Utc::now().to_rfc3339(), new_value, new_confidence],
+    )?;
+    Ok(())
+}
+
+const FACT_COLUMNS: &str =
+    "id, category, key, value, confidence, source_type, source_conversation_id, first_mentioned, last_confirmed, mention_count, valid_until";
+
+/// Leaves out transient facts whose time has passed
+const FACT_CURRENT: &str = "(valid_until IS NULL OR valid_until > strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))";
+
+fn fact_from_row(row: &rusqlite::Row) -> rusqlite::Result<UserFact> {
+    Ok(UserFact {
+        id: row.get(0)?,
+        category: row.get(1)?,
+        key: row.get(2)?,
+        value: row.get(3)?,
+        confidence: row.get(4)?,
+        source_type: row.get(5)?,
+        source_conversation_id: row.get(6)?,
+        first_mentioned: row.get(7)?,
+        last_confirmed: row.get(8)?,
+        mention_count: row.get(9)?,
+        valid_until: row.get(10)?,
+    })
+}
+
+pub fn get_all_user_facts() -> Result<Vec<UserFact>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM user_facts WHERE {} ORDER BY confidence DESC, mention_count DESC",
+            FACT_COLUMNS, FACT_CURRENT
+        ))?;
+        
+        let facts = stmt.query_map([], fact_from_row)?;
+        
+        facts.collect()
+    })
+}
+
+pub fn get_user_fact(fact_id: i64) -> Result<Option<UserFact>> {
+    with_connection(|conn| {
+        conn.query_row(
+            &format!("SELECT {} FROM user_facts WHERE id = ?1", FACT_COLUMNS),
+            [fact_id],
+            fact_from_row,
+        ).optional()
+    })
+}
+
+/// The user restated a fact: refresh its confirmation, and its value if it changed
+pub fn confirm_user_fact(category: &str, key: &str, new_value: Option<&str>, conversation_id: Option<&str>) -> Result<bool> {
+    with_connection(|conn| {
+        if let Some(value) = new_value {
+            let existing: Option<(i64, f64)> = conn.query_row(
+                "SELECT id, confidence FROM user_facts WHERE category = ?1 AND key = ?2",
+                params![category, key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ).optional()?;
+            if let Some((fact_id, confidence)) = existing {
+                record_fact_history(conn, fact_id, value, confidence, conversation_id)?;
+            }
+        }
+        let updated = conn.execute(
+            "UPDATE user_facts SET value = COALESCE(?1, value), last_confirmed = ?2, mention_count = mention_count + 1
+             WHERE category = ?3 AND key = ?4",
+            params![new_value, Utc::now().to_rfc3339(), category, key],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+/// A user-confirmed value replaces the old one outright
+pub fn update_user_fact_value(fact_id: i64, value: &str, conversation_id: Option<&str>) -> Result<()> {
+    with_connection(|conn| {
+        record_fact_history(conn, fact_id, value, 1.0, conversation_id)?;
+        conn.execute(
+            "UPDATE user_facts SET value = ?1, confidence = 1.0, source_type = 'explicit', last_confirmed = ?2 WHERE id = ?3",
+            params![value, Utc::now().to_rfc3339(), fact_id],
+        )?;
+        Ok(())
+    })
+}
+
+/// A fact edited by hand: new value and confidence, counted as confirmed now
+pub fn update_user_fact(fact_id: i64, value: &str, confidence: f64) -> Result<bool> {
+    with_connection(|conn| {
+        record_fact_history(conn, fact_id, value, confidence, None)?;
+        let updated = conn.execute(
+            "UPDATE user_facts SET value = ?1, confidence = ?2, source_type = 'explicit', last_confirmed = ?3 WHERE id = ?4",
+            params![value, confidence, Utc::now().to_rfc3339(), fact_id],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+pub fn delete_user_fact(fact_id: i64) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "DELETE FROM collection_items WHERE item_type = ?1 AND item_id = ?2",
+            params![COLLECTION_ITEM_FACT, fact_id.to_string()],
+        )?;
+        conn.execute("DELETE FROM fact_conflicts WHERE fact_id = ?1", params![fact_id])?;
+        conn.execute("DELETE FROM user_fact_history WHERE fact_id = ?1", params![fact_id])?;
+        conn.execute("DELETE FROM response_citations WHERE fact_id = ?1", params![fact_id])?;
+        conn.execute("DELETE FROM user_facts WHERE id = ?1", params![fact_id])?;
+        Ok(())
+    })
+}
+
+/// Facts not confirmed since `cutoff`, least recently confirmed first
+pub fn get_facts_confirmed_before(cutoff: &str) -> Result<Vec<UserFact>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM user_facts WHERE last_confirmed <= ?1 ORDER BY last_confirmed ASC",
+            FACT_COLUMNS
+        ))?;
+        let facts = stmt.query_map([cutoff], fact_from_row)?;
+        facts.collect()
+    })
+}
+
+/// Transient facts whose valid_until has passed
+pub fn get_expired_user_facts() -> Result<Vec<UserFact>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM user_facts WHERE NOT {} ORDER BY valid_until ASC",
+            FACT_COLUMNS, FACT_CURRENT
+        ))?;
+        let facts = stmt.query_map([], fact_from_row)?;
+        facts.collect()
+    })
+}
+
+/// Move a fact out of long-term memory into the archive
+pub fn archive_user_fact(fact_id: i64) -> Result<bool> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let moved = tx.execute(
+            &format!(
+                "INSERT OR REPLACE INTO archived_facts ({cols}, archived_at) SELECT {cols}, ?1 FROM user_facts WHERE id = ?2",
+                cols = FACT_COLUMNS
+            ),
+            params![now, fact_id],
+        )?;
+        tx.execute(
+            "DELETE FROM collection_items WHERE item_type = ?1 AND item_id = ?2",
+            params![COLLECTION_ITEM_FACT, fact_id.to_string()],
+        )?;
+        tx.execute("DELETE FROM response_citations WHERE fact_id = ?1", params![fact_id])?;
+        tx.execute("DELETE FROM fact_conflicts WHERE fact_id = ?1", params![fact_id])?;
+        tx.execute("DELETE FROM user_facts WHERE id = ?1", params![fact_id])?;
+        tx.commit()?;
+        Ok(moved > 0)
+    })
+}
+
+pub fn get_archived_facts() -> Result<Vec<ArchivedFact>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, archived_at FROM archived_facts ORDER BY archived_at DESC",
+            FACT_COLUMNS
+        ))?;
+        let facts = stmt.query_map([], |row| Ok(ArchivedFact { fact: fact_from_row(row)?, archived_at: row.get(11)? }))?;
+        facts.collect()
+    })
+}
+
+/// Facts a conversation may see. A scoped conversation gets only facts filed under
+/// one of its collections -- unfiled facts stay out, so a work scope can't see a
+/// health fact nobody got around to filing. An unscoped one gets everything.
+pub fn get_user_facts_in_scope(conversation_id: Option<&str>) -> Result<Vec<UserFact>> {
+    let scope = match conversation_id {
+        Some(id) => get_conversation_collections(id)?,
+        None => Vec::new(),
+    };
+    if scope.is_empty() {
+        return get_all_user_facts();
+    }
+    
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM user_facts f
+             WHERE {} AND EXISTS (
+                    SELECT 1 FROM collection_items ci
+                    JOIN conversation_collections cc ON cc.collection_id = ci.collection_id
+                    WHERE cc.conversation_id = ?1 AND ci.item_type = ?2 AND ci.item_id = CAST(f.id AS TEXT))
+             ORDER BY confidence DESC, mention_count DESC",
+            FACT_COLUMNS, FACT_CURRENT
+        ))?;
+        
+        let facts = stmt.query_map(params![conversation_id, COLLECTION_ITEM_FACT], fact_from_row)?;
+        
+        facts.collect()
+    })
+}
+
+/// File a fact learned in a scoped conversation under the conversation's
+/// collections, unless it already belongs to one, so the conversation keeps
+/// seeing what it learned
+pub fn file_fact_in_conversation_scope(category: &str, key: &str, conversation_id: &str) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO collection_items (collection_id, item_type, item_id)
+             SELECT cc.collection_id, ?1, CAST(f.id AS TEXT)
+             FROM user_facts f, conversation_collections cc
+             WHERE cc.conversation_id = ?2 AND f.category = ?3 AND f.key = ?4
+               AND NOT EXISTS (
+                    SELECT 1 FROM collection_items ci
+                    WHERE ci.item_type = ?1 AND ci.item_id = CAST(f.id AS TEXT))",
+            params![COLLECTION_ITEM_FACT, conversation_id, category, key],
+        )?;
+        Ok(())
+    })
+}
+
+// ============ Memory Staging ============
+
+/// Stage an item, or confirm an already-staged one. Re-extraction from the same
+/// conversation refreshes it without counting as another confirmation.
+pub fn stage_memory(item: &StagedMemory) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO memory_staging (kind, category, key, value, confidence, source_type, source_conversation_id, first_seen, last_seen, confirmations, held_by_import)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8, 1, ?9)
+             ON CONFLICT(kind, category, key) DO UPDATE SET
+                value = ?4,
+                confidence = MAX(confidence, ?5),
+                confirmations = confirmations + (CASE WHEN source_conversation_id IS ?7 THEN 0 ELSE 1 END),
+                source_conversation_id = ?7,
+                last_seen = ?8,
+                held_by_import = COALESCE(held_by_import, ?9)",
+            params![
+                item.kind,
+                item.category,
+                item.key,
+                item.value,
+                item.confidence,
+                item.source_type,
+                item.source_conversation_id,
+                item.last_seen,
+                item.held_by_import,
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+fn staged_from_row(row: &rusqlite::Row) -> rusqlite::Result<StagedMemory> {
+    Ok(StagedMemory {
+        id: row.get(0)?,
+        kind: row.get(1)?,
+        category: row.get(2)?,
+        key: row.get(3)?,
+        value: row.get(4)?,
+        confidence: row.get(5)?,
+        source_type: row.get(6)?,
+        source_conversation_id: row.get(7)?,
+        first_seen: row.get(8)?,
+        last_seen: row.get(9)?,
+        confirmations: row.get(10)?,
+        held_by_import: row.get(11)?,
+    })
+}
+
+const STAGED_COLUMNS: &str =
+    "id, kind, category, key, value, confidence, source_type, source_conversation_id, first_seen, last_seen, confirmations, held_by_import";
+
+pub fn get_staged_memory() -> Result<Vec<StagedMemory>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM memory_staging ORDER BY last_seen DESC",
+            STAGED_COLUMNS
+        ))?;
+        let items = stmt.query_map([], staged_from_row)?;
+        items.collect()
+    })
+}
+
+pub fn get_staged_memory_item(id: i64) -> Result<Option<StagedMemory>> {
+    with_connection(|conn| {
+        match conn.query_row(
+            &format!("SELECT {} FROM memory_staging WHERE id = ?1", STAGED_COLUMNS),
+            [id],
+            staged_from_row,
+        ) {
+            Ok(item) => Ok(Some(item)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+/// Items confirmed enough times, or staged since before the cutoff. Items held by
+/// an archive import wait for review instead.
+pub fn get_promotable_staged_memory(min_confirmations: i64, staged_before: &str) -> Result<Vec<StagedMemory>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM memory_staging
+             WHERE held_by_import IS NULL AND (confirmations >= ?1 OR first_seen <= ?2)",
+            STAGED_COLUMNS
+        ))?;
+        let items = stmt.query_map(params![min_confirmations, staged_before], staged_from_row)?;
+        items.collect()
+    })
+}
+
+pub fn delete_staged_memory(id: i64) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute("DELETE FROM memory_staging WHERE id = ?1", params![id])?;
+        Ok(())
+    })
+}
+
+pub fn get_staged_memory_for_import(import_id: &str) -> Result<Vec<StagedMemory>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM memory_staging WHERE held_by_import = ?1 ORDER BY kind, confidence DESC",
+            STAGED_COLUMNS
+        ))?;
+        let items = stmt.query_map([import_id], staged_from_row)?;
+        items.collect()
+    })
+}
+
+/// Hand an import's held items over to the normal staging policy
+pub fn release_staged_memory_for_import(import_id: &str) -> Result<usize> {
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE memory_staging SET held_by_import = NULL WHERE held_by_import = ?1",
+            [import_id],
+        )
+    })
+}
+
+pub fn delete_staged_memory_for_import(import_id: &str) -> Result<usize> {
+    with_connection(|conn| {
+        conn.execute("DELETE FROM memory_staging WHERE held_by_import = ?1", [import_id])
+    })
+}
+
+pub fn user_fact_exists(category: &str, key: &str) -> Result<bool> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT COUNT(*) FROM user_facts WHERE category = ?1 AND key = ?2",
+            params![category, key],
+            |row| Ok(row.get::<_, i64>(0)? > 0),
+        )
+    })
+}
+
+pub fn user_pattern_exists(pattern_type: &str, description: &str) -> Result<bool> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT COUNT(*) FROM user_patterns WHERE pattern_type = ?1 AND description = ?2",
+            params![pattern_type, description],
+            |row| Ok(row.get::<_, i64>(0)? > 0),
+        )
+    })
+}
+
+// ============ Fact Corrections ============
+
+pub fn save_fact_correction(correction: &FactCorrection) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO fact_corrections (fact_id, category, key, old_value, new_value, action, conversation_id, user_message, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                correction.fact_id,
+                correction.category,
+                correction.key,
+                correction.old_value,
+                correction.new_value,
+                correction.action,
+                correction.conversation_id,
+                correction.user_message,
+                correction.created_at,
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+// ============ Fact History ============
+
+const FACT_HISTORY_COLUMNS: &str = "id, fact_id, category, key, value, confidence, source_type, conversation_id, changed_at";
+
+fn fact_history_from_row(row: &rusqlite::Row) -> rusqlite::Result<FactHistoryEntry> {
+    Ok(FactHistoryEntry {
+        id: row.get(0)?,
+        fact_id: row.get(1)?,
+        category: row.get(2)?,
+        key: row.get(3)?,
+        value: row.get(4)?,
+        confidence: row.get(5)?,
+        source_type: row.get(6)?,
+        conversation_id: row.get(7)?,
+        changed_at: row.get(8)?,
+    })
+}
+
+/// Previous values, newest first: one fact's, or every fact's
+pub fn get_fact_history(fact_id: Option<i64>, limit: usize) -> Result<Vec<FactHistoryEntry>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM user_fact_history WHERE ?1 IS NULL OR fact_id = ?1 ORDER BY changed_at DESC, id DESC LIMIT ?2",
+            FACT_HISTORY_COLUMNS
+        ))?;
+        let entries = stmt.query_map(params![fact_id, limit], fact_history_from_row)?;
+        entries.collect()
+    })
+}
+
+pub fn get_fact_history_entry(history_id: i64) -> Result<Option<FactHistoryEntry>> {
+    with_connection(|conn| {
+        conn.query_row(
+            &format!("SELECT {} FROM user_fact_history WHERE id = ?1", FACT_HISTORY_COLUMNS),
+            [history_id],
+            fact_history_from_row,
+        ).optional()
+    })
+}
+
+/// Put a fact back to a previous value. The value it replaces goes into the
+/// history too, so a rollback can itself be undone. False if the fact is gone.
+pub fn restore_fact_version(entry: &FactHistoryEntry) -> Result<bool> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        record_fact_history(&tx, entry.fact_id, &entry.value, entry.confidence, None)?;
+        let updated = tx.execute(
+            "UPDATE user_facts SET value = ?1, confidence = ?2, source_type = ?3, last_confirmed = ?4 WHERE id = ?5",
+            params![entry.value, entry.confidence, entry.source_type, Utc::now().to_rfc3339(), entry.fact_id],
+        )?;
+        tx.commit()?;
+        Ok(updated > 0)
+    })
+}
+
+// ============ Fact Conflicts ============
+
+fn fact_conflicts_where(clause: &str, params: &[&dyn rusqlite::ToSql]) -> Result<Vec<FactConflict>> {
+    let fact_columns: Vec<String> = FACT_COLUMNS.split(", ").map(|c| format!("f.{}", c)).collect();
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {}, c.id, c.value, c.confidence, c.source_type, c.source_conversation_id, c.created_at
+             FROM fact_conflicts c JOIN user_facts f ON f.id = c.fact_id {} ORDER BY c.created_at DESC",
+            fact_columns.join(", "),
+            clause
+        ))?;
+        let conflicts = stmt.query_map(params, |row| {
+            Ok(FactConflict {
+                fact: fact_from_row(row)?,
+                id: row.get(11)?,
+                value: row.get(12)?,
+                confidence: row.get(13)?,
+                source_type: row.get(14)?,
+                source_conversation_id: row.get(15)?,
+                created_at: row.get(16)?,
+            })
+        })?;
+        conflicts.collect()
+    })
+}
+
+/// Open conflicts, newest first
+pub fn get_fact_conflicts() -> Result<Vec<FactConflict>> {
+    fact_conflicts_where("", &[])
+}
+
+pub fn get_fact_conflict(conflict_id: i64) -> Result<Option<FactConflict>> {
+    Ok(fact_conflicts_where("WHERE c.id = ?1", &[&conflict_id])?.into_iter().next())
+}
+
+pub fn delete_fact_conflict(conflict_id: i64) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute("DELETE FROM fact_conflicts WHERE id = ?1", params![conflict_id])?;
+        Ok(())
+    })
+}
+
+pub fn get_fact_corrections(limit: usize) -> Result<Vec<FactCorrection>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, fact_id, category, key, old_value, new_value, action, conversation_id, user_message, created_at
+             FROM fact_corrections ORDER BY created_at DESC LIMIT ?1"
+        )?;
+        
+        let corrections = stmt.query_map([limit], |row| {
+            Ok(FactCorrection {
+                id: row.get(0)?,
+                fact_id: row.get(1)?,
+                category: row.get(2)?,
+                key: row.get(3)?,
+                old_value: row.get(4)?,
+                new_value: row.get(5)?,
+                action: row.get(6)?,
+                conversation_id: row.get(7)?,
+                user_message: row.get(8)?,
+                created_at: row.get(9)?,
+            })
+        })?;
+        
+        corrections.collect()
+    })
+}
+
+// ============ Response Citations ============
+
+pub fn save_response_citations(message_id: &str, fact_ids: &[i64]) -> Result<()> {
+    with_connection(|conn| {
+        let now = Utc::now().to_rfc3339();
+        for fact_id in fact_ids {
+            conn.execute(
+                "INSERT OR IGNORE INTO response_citations (message_id, fact_id, created_at) VALUES (?1, ?2, ?3)",
+                params![message_id, fact_id, now],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// Citations for one message, or for every message in a conversation.
+/// Facts deleted since are dropped.
+pub fn get_response_citations(message_id: Option<&str>, conversation_id: Option<&str>) -> Result<Vec<ResponseCitation>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT rc.message_id, m.role, f.id, f.category, f.key, f.value, f.first_mentioned
+             FROM response_citations rc
+             JOIN messages m ON m.id = rc.message_id
+             JOIN user_facts f ON f.id = rc.fact_id
+             WHERE (?1 IS NULL OR rc.message_id = ?1) AND (?2 IS NULL OR m.conversation_id = ?2)
+             ORDER BY m.timestamp ASC, f.id ASC"
+        )?;
+        
+        let citations = stmt.query_map(params![message_id, conversation_id], |row| {
+            Ok(ResponseCitation {
+                message_id: row.get(0)?,
+                agent: row.get(1)?,
+                fact_id: row.get(2)?,
+                category: row.get(3)?,
+                key: row.get(4)?,
+                value: row.get(5)?,
+                first_mentioned: row.get(6)?,
+            })
+        })?;
+        
+        citations.collect()
+    })
+}
+
+// ============ Disagreements ============
+
+pub fn save_disagreement(disagreement: &Disagreement) -> Result<i64> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO disagreements (conversation_id, round_id, axis, agent_a, stance_a, agent_b, stance_b, intensity, summary, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                disagreement.conversation_id,
+                disagreement.round_id,
+                disagreement.axis,
+                disagreement.agent_a,
+                disagreement.stance_a,
+                disagreement.agent_b,
+                disagreement.stance_b,
+                disagreement.intensity,
+                disagreement.summary,
+                disagreement.created_at
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })
+}
+
+/// Disagreements in one conversation, or across all of them, newest first
+pub fn get_disagreements(conversation_id: Option<&str>, limit: usize) -> Result<Vec<Disagreement>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, conversation_id, round_id, axis, agent_a, stance_a, agent_b, stance_b, intensity, summary, created_at
+             FROM disagreements WHERE (?1 IS NULL OR conversation_id = ?1)
+             ORDER BY created_at DESC, id DESC LIMIT ?2"
+        )?;
+        let disagreements = stmt.query_map(params![conversation_id, limit as i64], |row| {
+            Ok(Disagreement {
+                id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                round_id: row.get(2)?,
+                axis: row.get(3)?,
+                agent_a: row.get(4)?,
+                stance_a: row.get(5)?,
+                agent_b: row.get(6)?,
+                stance_b: row.get(7)?,
+                intensity: row.get(8)?,
+                summary: row.get(9)?,
+                created_at: row.get(10)?,
+            })
+        })?;
+        disagreements.collect()
+    })
+}
+
+
+// ============ Chapters ============
+
+fn chapter_conversation_ids(conn: &Connection, chapter_id: i64) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT cc.conversation_id FROM chapter_conversations cc
+         JOIN conversations c ON c.id = cc.conversation_id
+         WHERE cc.chapter_id = ?1 AND c.deleted_at IS NULL
+         ORDER BY c.created_at ASC"
+    )?;
+    let ids = stmt.query_map(params![chapter_id], |row| row.get(0))?;
+    ids.collect()
+}
+
+fn chapter_from_row(row: &rusqlite::Row) -> rusqlite::Result<Chapter> {
+    Ok(Chapter {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        start_date: row.get(2)?,
+        end_date: row.get(3)?,
+        description: row.get(4)?,
+        source: row.get(5)?,
+        conversation_ids: Vec::new(),
+        created_at: row.get(6)?,
+    })
+}
+
+/// Chapters the user hasn't dismissed, most recent first
+pub fn get_chapters() -> Result<Vec<Chapter>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, title, start_date, end_date, description, source, created_at
+             FROM chapters WHERE dismissed = 0 ORDER BY start_date DESC, id DESC"
+        )?;
+        let mut chapters = stmt.query_map([], chapter_from_row)?.collect::<Result<Vec<_>>>()?;
+        for chapter in chapters.iter_mut() {
+            chapter.conversation_ids = chapter_conversation_ids(conn, chapter.id)?;
+        }
+        Ok(chapters)
+    })
+}
+
+pub fn get_chapter(id: i64) -> Result<Option<Chapter>> {
+    with_connection(|conn| {
+        let chapter = conn.query_row(
+            "SELECT id, title, start_date, end_date, description, source, created_at
+             FROM chapters WHERE id = ?1 AND dismissed = 0",
+            params![id],
+            chapter_from_row,
+        ).optional()?;
+        match chapter {
+            Some(mut chapter) => {
+                chapter.conversation_ids = chapter_conversation_ids(conn, chapter.id)?;
+                Ok(Some(chapter))
+            }
+            None => Ok(None),
+        }
+    })
+}
+
+pub fn create_chapter(
+    title: &str,
+    start_date: &str,
+    end_date: Option<&str>,
+    description: Option<&str>,
+    source: &str,
+    topic: Option<&str>,
+) -> Result<i64> {
+    with_connection(|conn| {
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO chapters (title, start_date, end_date, description, source, topic, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+            params![title, start_date, end_date, description, source, topic, now],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })
+}
+
+/// Rewrite a chapter as the user sees it; auto chapters become the user's own
+/// so detection stops moving them. Returns false when there's no such chapter.
+pub fn update_chapter(id: i64, title: &str, start_date: &str, end_date: Option<&str>, description: Option<&str>) -> Result<bool> {
+    with_connection(|conn| {
+        let updated = conn.execute(
+            "UPDATE chapters SET title = ?1, start_date = ?2, end_date = ?3, description = ?4, source = ?5, updated_at = ?6
+             WHERE id = ?7 AND dismissed = 0",
+            params![title, start_date, end_date, description, CHAPTER_USER, Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+/// Move an auto chapter's range and add conversations detection found for it
+pub fn extend_auto_chapter(id: i64, start_date: &str, end_date: Option<&str>, conversation_ids: &[String]) -> Result<()> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE chapters SET start_date = ?1, end_date = ?2, updated_at = ?3 WHERE id = ?4 AND source = ?5",
+            params![start_date, end_date, Utc::now().to_rfc3339(), id, CHAPTER_AUTO],
+        )?;
+        for conversation_id in conversation_ids {
+            tx.execute(
+                "INSERT OR IGNORE INTO chapter_conversations (chapter_id, conversation_id) VALUES (?1, ?2)",
+                params![id, conversation_id],
+            )?;
+        }
+        tx.commit()
+    })
+}
+
+/// The chapter (dismissed or not) auto-detection made for a topic: (id, source, dismissed)
+pub fn get_chapter_for_topic(topic: &str) -> Result<Option<(i64, String, bool)>> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT id, source, dismissed FROM chapters WHERE topic = ?1 ORDER BY id DESC LIMIT 1",
+            params![topic],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, i64>(2)? != 0)),
+        ).optional()
+    })
+}
+
+/// Delete a chapter. Auto chapters are kept as dismissed so detection
+/// doesn't bring them back. Returns false when there's no such chapter.
+pub fn delete_chapter(id: i64) -> Result<bool> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM chapter_conversations WHERE chapter_id = ?1", params![id])?;
+        let dismissed = tx.execute(
+            "UPDATE chapters SET dismissed = 1, updated_at = ?1 WHERE id = ?2 AND topic IS NOT NULL AND dismissed = 0",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        let deleted = tx.execute("DELETE FROM chapters WHERE id = ?1 AND topic IS NULL", params![id])?;
+        tx.commit()?;
+        Ok(dismissed + deleted > 0)
+    })
+}
+
+/// Place a conversation in a chapter, or take it out
+pub fn set_chapter_conversation(chapter_id: i64, conversation_id: &str, linked: bool) -> Result<()> {
+    with_connection(|conn| {
+        if linked {
+            conn.execute(
+                "INSERT OR IGNORE INTO chapter_conversations (chapter_id, conversation_id) VALUES (?1, ?2)",
+                params![chapter_id, conversation_id],
+            )?;
+        } else {
+            conn.execute(
+                "DELETE FROM chapter_conversations WHERE chapter_id = ?1 AND conversation_id = ?2",
+                params![chapter_id, conversation_id],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+// ============ Action Items ============
+
+const ACTION_ITEM_COLUMNS: &str =
+    "id, conversation_id, message_id, text, owner, due, status, snoozed_until, created_at, completed_at";
+
+fn action_item_from_row(row: &rusqlite::Row) -> rusqlite::Result<ActionItem> {
+    Ok(ActionItem {
+        id: row.get(0)?,
+        conversation_id: row.get(1)?,
+        message_id: row.get(2)?,
+        text: row.get(3)?,
+        owner: row.get(4)?,
+        due: row.get(5)?,
+        status: row.get(6)?,
+        snoozed_until: row.get(7)?,
+        created_at: row.get(8)?,
+        completed_at: row.get(9)?,
+    })
+}
+
+/// Save an open action item unless the conversation already has the same one open.
+/// Returns the new id, or None for a duplicate.
+pub fn save_action_item(conversation_id: &str, message_id: Option<&str>, text: &str, owner: &str, due: Option<&str>) -> Result<Option<i64>> {
+    with_connection(|conn| {
+        let duplicate: bool = conn.query_row(
+            "SELECT COUNT(*) FROM action_items WHERE conversation_id = ?1 AND status = ?2 AND LOWER(text) = LOWER(?3)",
+            params![conversation_id, ACTION_OPEN, text],
+            |row| Ok(row.get::<_, i64>(0)? > 0),
+        )?;
+        if duplicate {
+            return Ok(None);
+        }
+        conn.execute(
+            "INSERT INTO action_items (conversation_id, message_id, text, owner, due, status, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![conversation_id, message_id, text, owner, due, ACTION_OPEN, Utc::now().to_rfc3339()],
+        )?;
+        Ok(Some(conn.last_insert_rowid()))
+    })
+}
+
+/// Action items, newest first, filtered by status and/or conversation.
+/// Items in trashed conversations are left out.
+pub fn get_action_items(status: Option<&str>, conversation_id: Option<&str>) -> Result<Vec<ActionItem>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM action_items
+             WHERE (?1 IS NULL OR status = ?1) AND (?2 IS NULL OR conversation_id = ?2)
+               AND conversation_id IN (SELECT id FROM conversations WHERE deleted_at IS NULL)
+             ORDER BY created_at DESC, id DESC",
+            ACTION_ITEM_COLUMNS
+        ))?;
+        let items = stmt.query_map(params![status, conversation_id], action_item_from_row)?;
+        items.collect()
+    })
+}
+
+/// Open, unsnoozed items from conversations other than `exclude_conversation_id`,
+/// oldest first -- what carries over into a new session
+pub fn get_carried_action_items(exclude_conversation_id: Option<&str>, now: &str, limit: usize) -> Result<Vec<ActionItem>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM action_items
+             WHERE status = ?1 AND (snoozed_until IS NULL OR snoozed_until <= ?2)
+               AND (?3 IS NULL OR conversation_id != ?3)
+               AND conversation_id IN (SELECT id FROM conversations WHERE deleted_at IS NULL)
+             ORDER BY created_at ASC, id ASC
+             LIMIT ?4",
+            ACTION_ITEM_COLUMNS
+        ))?;
+        let items = stmt.query_map(params![ACTION_OPEN, now, exclude_conversation_id, limit as i64], action_item_from_row)?;
+        items.collect()
+    })
+}
+
+pub fn get_action_item(id: i64) -> Result<Option<ActionItem>> {
+    with_connection(|conn| {
+        conn.query_row(
+            &format!("SELECT {} FROM action_items WHERE id = ?1", ACTION_ITEM_COLUMNS),
+            [id],
+            action_item_from_row,
+        ).optional()
+    })
+}
+
+/// Mark an item done (or open again). Returns false if there's no such item.
+pub fn set_action_item_done(id: i64, done: bool) -> Result<bool> {
+    with_connection(|conn| {
+        let (status, completed_at) = if done {
+            (ACTION_DONE, Some(Utc::now().to_rfc3339()))
+        } else {
+            (ACTION_OPEN, None)
+        };
+        Ok(conn.execute(
+            "UPDATE action_items SET status = ?1, completed_at = ?2, snoozed_until = NULL WHERE id = ?3",
+            params![status, completed_at, id],
+        )? > 0)
+    })
+}
+
+/// Keep an item out of session context until `until` (None wakes it now)
+pub fn snooze_action_item(id: i64, until: Option<&str>) -> Result<bool> {
+    with_connection(|conn| {
+        Ok(conn.execute(
+            "UPDATE action_items SET snoozed_until = ?1 WHERE id = ?2",
+            params![until, id],
+        )? > 0)
+    })
+}
+
+/// The message a round's action item came from: the user's message for their own
+/// commitments, otherwise that agent's reply in the round
+pub fn get_round_message_id(round_id: &str, role: &str) -> Result<Option<String>> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT id FROM messages WHERE (id = ?1 OR round_id = ?1) AND role = ?2
+             ORDER BY timestamp DESC LIMIT 1",
+            params![round_id, role],
+            |row| row.get(0),
+        ).optional()
+    })
+}
+
+// ============ Memory Collections ============
+
+pub fn create_memory_collection(name: &str, description: Option<&str>) -> Result<i64> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO memory_collections (name, description, created_at) VALUES (?1, ?2, ?3)",
+            params![name, description, Utc::now().to_rfc3339()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })
+}
+
+pub fn get_memory_collections() -> Result<Vec<MemoryCollection>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT mc.id, mc.name, mc.description,
+                    (SELECT COUNT(*) FROM collection_items WHERE collection_id = mc.id),
+                    mc.created_at
+             FROM memory_collections mc ORDER BY mc.name ASC"
+        )?;
+        
+        let collections = stmt.query_map([], |row| {
+            Ok(MemoryCollection {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                item_count: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?;
+        
+        collections.collect()
+    })
+}
+
+pub fn delete_memory_collection(id: i64) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute("DELETE FROM collection_items WHERE collection_id = ?1", params![id])?;
+        conn.execute("DELETE FROM conversation_collections WHERE collection_id = ?1", params![id])?;
+        conn.execute("DELETE FROM memory_collections WHERE id = ?1", params![id])?;
+        Ok(())
+    })
+}
+
+pub fn assign_to_collection(collection_id: i64, item_type: &str, item_id: &str) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO collection_items (collection_id, item_type, item_id) VALUES (?1, ?2, ?3)",
+            params![collection_id, item_type, item_id],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn remove_from_collection(collection_id: i64, item_type: &str, item_id: &str) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "DELETE FROM collection_items WHERE collection_id = ?1 AND item_type = ?2 AND item_id = ?3",
+            params![collection_id, item_type, item_id],
+        )?;
+        Ok(())
+    })
+}
+
+/// Collection ids an item is filed under
+pub fn get_item_collections(item_type: &str, item_id: &str) -> Result<Vec<i64>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT collection_id FROM collection_items WHERE item_type = ?1 AND item_id = ?2"
+        )?;
+        let ids = stmt.query_map(params![item_type, item_id], |row| row.get(0))?;
+        ids.collect()
+    })
+}
+
+pub fn get_conversation_collections(conversation_id: &str) -> Result<Vec<i64>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT collection_id FROM conversation_collections WHERE conversation_id = ?1 ORDER BY collection_id"
+        )?;
+        let ids = stmt.query_map([conversation_id], |row| row.get(0))?;
+        ids.collect()
+    })
+}
+
+/// Replace a conversation's scope (empty = unscoped)
+pub fn set_conversation_collections(conversation_id: &str, collection_ids: &[i64]) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute("DELETE FROM conversation_collections WHERE conversation_id = ?1", [conversation_id])?;
+        for id in collection_ids {
+            conn.execute(
+                "INSERT OR IGNORE INTO conversation_collections (conversation_id, collection_id) VALUES (?1, ?2)",
+                params![conversation_id, id],
+            )?;
+        }
+        Ok(())
+    })
+}
+
+// ============ Tags ============
+
+/// Create a tag, or return the id of the existing tag with that name
+pub fn add_tag(name: &str) -> Result<i64> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO tags (name, created_at) VALUES (?1, ?2)",
+            params![name, Utc::now().to_rfc3339()],
+        )?;
+        conn.query_row("SELECT id FROM tags WHERE name = ?1", [name], |row| row.get(0))
+    })
+}
+
+fn tag_from_row(row: &rusqlite::Row) -> rusqlite::Result<Tag> {
+    Ok(Tag {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        conversation_count: row.get(2)?,
+        created_at: row.get(3)?,
+    })
+}
+
+/// All tags; counts leave out trashed conversations
+pub fn get_tags() -> Result<Vec<Tag>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.name,
+                    (SELECT COUNT(*) FROM conversation_tags ct
+                     JOIN conversations c ON c.id = ct.conversation_id
+                     WHERE ct.tag_id = t.id AND c.deleted_at IS NULL),
+                    t.created_at
+             FROM tags t ORDER BY t.name ASC"
+        )?;
+        let tags = stmt.query_map([], tag_from_row)?;
+        tags.collect()
+    })
+}
+
+pub fn delete_tag(id: i64) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute("DELETE FROM conversation_tags WHERE tag_id = ?1", params![id])?;
+        conn.execute("DELETE FROM tags WHERE id = ?1", params![id])?;
+        Ok(())
+    })
+}
+
+pub fn tag_conversation(conversation_id: &str, tag_id: i64) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO conversation_tags (conversation_id, tag_id) VALUES (?1, ?2)",
+            params![conversation_id, tag_id],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn untag_conversation(conversation_id: &str, tag_id: i64) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "DELETE FROM conversation_tags WHERE conversation_id = ?1 AND tag_id = ?2",
+            params![conversation_id, tag_id],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn get_conversation_tags(conversation_id: &str) -> Result<Vec<Tag>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.name,
+                    (SELECT COUNT(*) FROM conversation_tags WHERE tag_id = t.id),
+                    t.created_at
+             FROM tags t
+             JOIN conversation_tags ct ON ct.tag_id = t.id
+             WHERE ct.conversation_id = ?1
+             ORDER BY t.name ASC"
+        )?;
+        let tags = stmt.query_map([conversation_id], tag_from_row)?;
+        tags.collect()
+    })
+}
+
+/// Conversations carrying a tag, pinned first then most recent (closed ones included, trash excluded)
+pub fn get_conversations_by_tag(tag_id: i64) -> Result<Vec<Conversation>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT c.id, c.title, c.summary, c.limbo_summary, c.processed, c.is_disco, c.created_at, c.updated_at, c.intensity, c.closed_at, c.pinned, c.project_id, c.locked, c.parent_conversation_id
+             FROM conversations c
+             JOIN conversation_tags ct ON ct.conversation_id = c.id
+             WHERE ct.tag_id = ?1
+               AND c.deleted_at IS NULL
+             ORDER BY COALESCE(c.pinned, 0) DESC, c.updated_at DESC"
+        )?;
+        
+        let convs = stmt.query_map([tag_id], |row| {
+            Ok(Conversation {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                summary: row.get(2)?,
+                limbo_summary: row.get(3)?,
+                processed: row.get::<_, i64>(4)? != 0,
+                is_disco: row.get::<_, i64>(5).unwrap_or(0) != 0,
+                created_at: row.get(6)?,
+                updated_at: row.get(7)?,
+                intensity: row.get::<_, Option<f64>>(8)?.unwrap_or(0.0),
+                closed_at: row.get(9)?,
+                pinned: row.get::<_, Option<i64>>(10)?.unwrap_or(0) != 0,
+                project_id: row.get(11)?,
+                locked: row.get::<_, Option<i64>>(12)?.unwrap_or(0) != 0,
+                parent_conversation_id: row.get(13)?,
+            })
+        })?;
+        
+        convs.collect()
+    })
+}
+
+// ============ Insights ============
+
+pub fn save_insight(kind: &str, message: &str) -> Result<i64> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO insights (kind, message, created_at) VALUES (?1, ?2, ?3)",
+            params![kind, message, Utc::now().to_rfc3339()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })
+}
+
+/// Newest first; dismissed ones only when asked for
+pub fn get_insights(include_dismissed: bool, limit: usize) -> Result<Vec<Insight>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, kind, message, created_at, dismissed_at FROM insights
+             WHERE ?1 OR dismissed_at IS NULL
+             ORDER BY created_at DESC
+             LIMIT ?2"
+        )?;
+        let insights = stmt.query_map(params![include_dismissed, limit], |row| {
+            Ok(Insight {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                message: row.get(2)?,
+                created_at: row.get(3)?,
+                dismissed_at: row.get(4)?,
+            })
+        })?;
+        insights.collect()
+    })
+}
+
+pub fn dismiss_insight(id: i64) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE insights SET dismissed_at = ?1 WHERE id = ?2 AND dismissed_at IS NULL",
+            params![Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(())
+    })
+}
+
+// ============ Projects ============
+
+fn project_from_row(row: &rusqlite::Row) -> rusqlite::Result<Project> {
+    Ok(Project {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        conversation_count: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+/// Project columns plus a live conversation count (trash excluded)
+const PROJECT_SELECT: &str =
+    "SELECT p.id, p.name, p.description,
+            (SELECT COUNT(*) FROM conversations c WHERE c.project_id = p.id AND c.deleted_at IS NULL),
+            p.created_at, p.updated_at
+     FROM projects p";
+
+pub fn create_project(name: &str, description: Option<&str>) -> Result<Project> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO projects (name, description, created_at, updated_at) VALUES (?1, ?2, ?3, ?3)",
+            params![name, description, now],
+        )?;
+        conn.query_row(
+            &format!("{} WHERE p.id = ?1", PROJECT_SELECT),
+            [conn.last_insert_rowid()],
+            project_from_row,
+        )
+    })
+}
+
+pub fn get_project(id: i64) -> Result<Option<Project>> {
+    with_connection(|conn| {
+        conn.query_row(&format!("{} WHERE p.id = ?1", PROJECT_SELECT), [id], project_from_row)
+            .optional()
+    })
+}
+
+pub fn get_projects() -> Result<Vec<Project>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!("{} ORDER BY p.name COLLATE NOCASE ASC", PROJECT_SELECT))?;
+        let projects = stmt.query_map([], project_from_row)?;
+        projects.collect()
+    })
+}
+
+/// Rename a project and/or replace its description; returns false if it doesn't exist
+pub fn update_project(id: i64, name: &str, description: Option<&str>) -> Result<bool> {
+    with_connection(|conn| {
+        let changed = conn.execute(
+            "UPDATE projects SET name = ?1, description = ?2, updated_at = ?3 WHERE id = ?4",
+            params![name, description, Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(changed > 0)
+    })
+}
+
+/// Delete a project. Its conversations are kept and simply become unfiled.
+pub fn delete_project(id: i64) -> Result<()> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("UPDATE conversations SET project_id = NULL WHERE project_id = ?1", params![id])?;
+        tx.execute("DELETE FROM projects WHERE id = ?1", params![id])?;
+        tx.commit()
+    })
+}
+
+/// File a conversation under a project, or with `None` take it out of its project
+pub fn set_conversation_project(conversation_id: &str, project_id: Option<i64>) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE conversations SET project_id = ?1 WHERE id = ?2",
+            params![project_id, conversation_id],
+        )?;
+        Ok(())
+    })
+}
+
+/// Conversations in a project, pinned first then most recent (closed ones included, trash excluded)
+pub fn get_conversations_by_project(project_id: i64) -> Result<Vec<Conversation>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT c.id, c.title, c.summary, c.limbo_summary, c.processed, c.is_disco, c.created_at, c.updated_at, c.intensity, c.closed_at, c.pinned, c.project_id, c.locked, c.parent_conversation_id
+             FROM conversations c
+             WHERE c.project_id = ?1
+               AND c.deleted_at IS NULL
+             ORDER BY COALESCE(c.pinned, 0) DESC, c.updated_at DESC"
+        )?;
+        
+        let convs = stmt.query_map([project_id], |row| {
+            Ok(Conversation {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                summary: row.get(2)?,
+                limbo_summary: row.get(3)?,
+                processed: row.get::<_, i64>(4)? != 0,
+                is_disco: row.get::<_, i64>(5).unwrap_or(0) != 0,
+                created_at: row.get(6)?,
+                updated_at: row.get(7)?,
+                intensity: row.get::<_, Option<f64>>(8)?.unwrap_or(0.0),
+                closed_at: row.get(9)?,
+                pinned: row.get::<_, Option<i64>>(10)?.unwrap_or(0) != 0,
+                project_id: row.get(11)?,
+                locked: row.get::<_, Option<i64>>(12)?.unwrap_or(0) != 0,
+                parent_conversation_id: row.get(13)?,
+            })
+        })?;
+        
+        convs.collect()
+    })
+}
+
+// ============ User Patterns ============
+
+pub fn save_user_pattern(pattern: &UserPattern) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        // Check if pattern with same type and similar description exists
+        let existing: Option<i64> = conn.query_row(
+            "SELECT id FROM user_patterns WHERE pattern_type = ?1 AND description = ?2",
+            params![pattern.pattern_type, pattern.description],
+            |row| row.get(0)
+        ).ok();
+        
+        if let Some(id) = existing {
+            // Update existing pattern (denied patterns don't gain confidence from re-observation)
+            conn.execute(
+                "UPDATE user_patterns SET confidence = CASE WHEN verdict = 'denied' THEN confidence ELSE MIN(1.0, confidence + 0.1) END, observation_count = observation_count + 1, last_updated = ?1, evidence = ?2 WHERE id = ?3",
+                params![now, pattern.evidence, id]
+            )?;
+        } else {
+            // Insert new pattern
+            conn.execute(
+                "INSERT INTO user_patterns (pattern_type, description, confidence, evidence, first_observed, last_updated, observation_count)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    pattern.pattern_type,
+                    pattern.description,
+                    pattern.confidence,
+                    pattern.evidence,
+                    pattern.first_observed,
+                    pattern.last_updated,
+                    pattern.observation_count
+                ]
+            )?;
+        }
+        Ok(())
+    })
+}
+
+fn pattern_from_row(row: &rusqlite::Row) -> rusqlite::Result<UserPattern> {
+    Ok(UserPattern {
+        id: row.get(0)?,
+        pattern_type: row.get(1)?,
+        description: row.get(2)?,
+        confidence: row.get(3)?,
+        evidence: row.get(4)?,
+        first_observed: row.get(5)?,
+        last_updated: row.get(6)?,
+        observation_count: row.get(7)?,
+        verdict: row.get(8)?,
+    })
+}
+
+/// Every pattern, including denied ones (for review in the UI)
+pub fn get_all_user_patterns() -> Result<Vec<UserPattern>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, pattern_type, description, confidence, evidence, first_observed, last_updated, observation_count, verdict
+             FROM user_patterns ORDER BY confidence DESC, observation_count DESC"
+        )?;
+        
+        let patterns = stmt.query_map([], pattern_from_row)?;
+        patterns.collect()
+    })
+}
+
+/// Patterns usable in prompts: denied ones are dropped, confirmed ones get the confidence floor
+pub fn get_prompt_user_patterns() -> Result<Vec<UserPattern>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, pattern_type, description,
+                    CASE WHEN verdict = 'confirmed' THEN MAX(confidence, ?1) ELSE confidence END AS effective_confidence,
+                    evidence, first_observed, last_updated, observation_count, verdict
+             FROM user_patterns
+             WHERE verdict IS NULL OR verdict != 'denied'
+             ORDER BY effective_confidence DESC, observation_count DESC"
+        )?;
+        
+        let patterns = stmt.query_map([CONFIRMED_PATTERN_FLOOR], pattern_from_row)?;
+        patterns.collect()
+    })
+}
+
+/// Record the user's verdict on an inferred pattern
+pub fn set_pattern_verdict(id: i64, verdict: &str) -> Result<bool> {
+    with_connection(|conn| {
+        let updated = conn.execute(
+            "UPDATE user_patterns SET verdict = ?1 WHERE id = ?2",
+            params![verdict, id],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+/// Reword an inferred pattern or change how sure Archie is of it
+pub fn update_user_pattern(id: i64, description: &str, confidence: f64) -> Result<bool> {
+    with_connection(|conn| {
+        let updated = conn.execute(
+            "UPDATE user_patterns SET description = ?1, confidence = ?2, last_updated = ?3 WHERE id = ?4",
+            params![description, confidence, Utc::now().to_rfc3339(), id],
+        )?;
+        Ok(updated > 0)
+    })
+}
+
+pub fn delete_user_pattern(id: i64) -> Result<bool> {
+    with_connection(|conn| {
+        let deleted = conn.execute("DELETE FROM user_patterns WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    })
+}
+
+// ============ Conversation Summaries ============
+
+/// Most recent summaries across conversations, newest first
+pub fn get_recent_conversation_summaries(limit: usize) -> Result<Vec<ConversationSummary>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, conversation_id, summary, key_topics, emotional_tone, user_state, agents_involved, message_count, created_at
+             FROM conversation_summaries ORDER BY created_at DESC LIMIT ?1"
+        )?;
+        let summaries = stmt.query_map(params![limit as i64], summary_from_row)?;
+        summaries.collect()
+    })
+}
+
+/// Every stored summary, oldest first
+pub fn get_all_conversation_summaries() -> Result<Vec<ConversationSummary>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, conversation_id, summary, key_topics, emotional_tone, user_state, agents_involved, message_count, created_at
+             FROM conversation_summaries ORDER BY created_at ASC"
+        )?;
+        let summaries = stmt.query_map([], summary_from_row)?;
+        summaries.collect()
+    })
+}
+
+/// Summaries of other conversations that aren't in the trash, newest first
+pub fn get_past_conversation_summaries(exclude_conversation_id: &str, limit: usize) -> Result<Vec<ConversationSummary>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT s.id, s.conversation_id, s.summary, s.key_topics, s.emotional_tone, s.user_state, s.agents_involved, s.message_count, s.created_at
+             FROM conversation_summaries s
+             JOIN conversations c ON c.id = s.conversation_id
+             WHERE s.conversation_id != ?1 AND c.deleted_at IS NULL
+             ORDER BY s.created_at DESC LIMIT ?2"
+        )?;
+        let summaries = stmt.query_map(params![exclude_conversation_id, limit as i64], summary_from_row)?;
+        summaries.collect()
+    })
+}
+
+pub fn save_conversation_summary(summary: &ConversationSummary) -> Result<()> {
+    with_connection(|conn| {
+        // Replace existing summary for this conversation
+        conn.execute(
+            "INSERT OR REPLACE INTO conversation_summaries 
+             (conversation_id, summary, key_topics, emotional_tone, user_state, agents_involved, message_count, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                summary.conversation_id,
+                summary.summary,
+                summary.key_topics,
+                summary.emotional_tone,
+                summary.user_state,
+                summary.agents_involved,
+                summary.message_count,
+                summary.created_at
+            ]
+        )?;
+        Ok(())
+    })
+}
+
+fn summary_from_row(row: &rusqlite::Row) -> rusqlite::Result<ConversationSummary> {
+    Ok(ConversationSummary {
+        id: row.get(0)?,
+        conversation_id: row.get(1)?,
+        summary: row.get(2)?,
+        key_topics: row.get(3)?,
+        emotional_tone: row.get(4)?,
+        user_state: row.get(5)?,
+        agents_involved: row.get(6)?,
+        message_count: row.get(7)?,
+        created_at: row.get(8)?,
+    })
+}
+
+pub fn get_conversation_summary(conversation_id: &str) -> Result<Option<ConversationSummary>> {
+    with_connection(|conn| {
+        let result = conn.query_row(
+            "SELECT id, conversation_id, summary, key_topics, emotional_tone, user_state, agents_involved, message_count, created_at
+             FROM conversation_summaries WHERE conversation_id = ?1",
+            params![conversation_id],
+            summary_from_row,
+        );
+        match result {
+            Ok(s) => Ok(Some(s)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+// ============ Recurring Themes ============
+
+/// Canonical form of a theme: lowercase, no punctuation or leading article,
+/// each word roughly singular ("Work deadlines" and "work deadline" are one
+/// theme). None if nothing is left or it reads more like a sentence than a theme.
+pub fn normalize_theme(raw: &str) -> Option<String> {
+    let cleaned: String = raw.to_lowercase().chars()
+        .filter(|c| *c != '\'' && *c != '\u{2019}')
+        .map(|c| if c.is_alphanumeric() || c == '-' { c } else { ' ' })
+        .collect();
+    let mut words: Vec<&str> = cleaned.split_whitespace().collect();
+    while words.len() > 1 && matches!(words[0], "the" | "a" | "an" | "my" | "our" | "their") {
+        words.remove(0);
+    }
+    if words.is_empty() || words.len() > MAX_THEME_WORDS {
+        return None;
+    }
+    let theme = words.iter().map(|w| singularize(w)).collect::<Vec<_>>().join(" ");
+    if theme.chars().count() > MAX_THEME_CHARS {
+        return None;
+    }
+    Some(theme)
+}
+
+fn singularize(word: &str) -> String {
+    if word.chars().count() <= 3 || !word.is_ascii() {
+        return word.to_string();
+    }
+    if let Some(stem) = word.strip_suffix("ies") {
+        return format!("{}y", stem);
+    }
+    if ["sses", "shes", "ches", "xes"].iter().any(|suffix| word.ends_with(suffix)) {
+        return word[..word.len() - 2].to_string();
+    }
+    if ["ss", "us", "is"].iter().any(|suffix| word.ends_with(suffix)) {
+        return word.to_string();
+    }
+    word.strip_suffix('s').unwrap_or(word).to_string()
+}
+
+/// Rough stem of a word: "changes", "changed" and "changing" all become "chang"
+fn stem_theme_word(word: &str) -> String {
+    let word = singularize(word);
+    let stem = ["ing", "ed"].iter()
+        .find_map(|suffix| word.strip_suffix(suffix).filter(|stem| stem.chars().count() >= 3))
+        .unwrap_or(&word);
+    let mut chars: Vec<char> = stem.chars().collect();
+    // "planning" -> "plann" -> "plan"
+    if let [.., a, b] = chars.as_slice() {
+        if a == b && !"aeiouls".contains(*a) && stem.len() < word.len() {
+            chars.pop();
+        }
+    }
+    if chars.len() >= 4 && chars.last() == Some(&'e') {
+        chars.pop();
+    }
+    chars.into_iter().collect()
+}
+
+/// What near-duplicate themes share: stemmed words, filler dropped, in any order
+fn theme_merge_key(theme: &str) -> String {
+    let mut stems: Vec<String> = theme.split_whitespace()
+        .filter(|w| !THEME_FILLER_WORDS.contains(w))
+        .map(stem_theme_word)
+        .collect();
+    stems.sort();
+    stems.dedup();
+    stems.join(" ")
+}
+
+pub fn save_recurring_theme(theme: &str, conversation_id: &str) -> Result<()> {
+    let Some(theme) = normalize_theme(theme) else {
+        return Ok(());
+    };
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        // The same theme, or a near-duplicate of it ("career changes" / "changing career")
+        let merge_key = theme_merge_key(&theme);
+        let existing: Option<(i64, String)> = {
+            let mut stmt = conn.prepare("SELECT id, theme, related_conversations FROM recurring_themes")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?.unwrap_or_default()))
+            })?;
+            let rows: Vec<(i64, String, String)> = rows.collect::<Result<_>>()?;
+            rows.iter()
+                .find(|(_, t, _)| *t == theme)
+                .or_else(|| rows.iter().find(|(_, t, _)| theme_merge_key(t) == merge_key))
+                .map(|(id, _, convs)| (*id, convs.clone()))
+        };
+        
+        if let Some((id, existing_convs)) = existing {
+            // Update existing theme
+            let mut convs: Vec<String> = if existing_convs.is_empty() {
+                Vec::new()
+            } else {
+                serde_json::from_str(&existing_convs).unwrap_or_default()
+            };
+            if !convs.contains(&conversation_id.to_string()) {
+                convs.push(conversation_id.to_string());
+            }
+            let convs_json = serde_json::to_string(&convs).unwrap_or_default();
+            
+            conn.execute(
+                "UPDATE recurring_themes SET frequency = frequency + 1, last_mentioned = ?1, related_conversations = ?2 WHERE id = ?3",
+                params![now, convs_json, id]
+            )?;
+        } else {
+            // A chatty conversation doesn't get to fill the table on its own
+            let in_conversation: usize = conn.query_row(
+                "SELECT COUNT(*) FROM recurring_themes WHERE related_conversations LIKE ?1",
+                params![format!("%{}%", serde_json::to_string(conversation_id).unwrap_or_default())],
+                |row| row.get(0)
+            )?;
+            if in_conversation >= MAX_THEMES_PER_CONVERSATION {
+                return Ok(());
+            }
+            
+            // Insert new theme
+            let convs_json = serde_json::to_string(&vec![conversation_id]).unwrap_or_default();
+            conn.execute(
+                "INSERT INTO recurring_themes (theme, frequency, last_mentioned, related_conversations) VALUES (?1, 1, ?2, ?3)",
+                params![theme, now, convs_json]
+            )?;
+        }
+        Ok(())
+    })
+}
+
+/// Fold themes saved before normalization, and near-duplicates of each other,
+/// into one row named after the most frequent: frequencies add up, conversations
+/// are merged, and themes that don't normalize are dropped. Returns the number
+/// of rows removed; a second run finds nothing to do.
+pub fn consolidate_recurring_themes() -> Result<usize> {
+    with_connection(|conn| {
+        let themes: Vec<RecurringTheme> = {
+            let mut stmt = conn.prepare(
+                "SELECT id, theme, frequency, last_mentioned, related_conversations
+                 FROM recurring_themes ORDER BY frequency DESC, id"
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok(RecurringTheme {
+                    id: row.get(0)?,
+                    theme: row.get(1)?,
+                    frequency: row.get(2)?,
+                    last_mentioned: row.get(3)?,
+                    related_conversations: row.get(4)?,
+                })
+            })?;
+            rows.collect::<Result<_>>()?
+        };
+        
+        // (merge key, name for the group, members); most frequent first, so it names the group
+        let mut groups: Vec<(String, String, Vec<RecurringTheme>)> = Vec::new();
+        let mut dropped = Vec::new();
+        for theme in themes {
+            match normalize_theme(&theme.theme) {
+                Some(canonical) => {
+                    let key = theme_merge_key(&canonical);
+                    match groups.iter_mut().find(|(k, _, _)| *k == key) {
+                        Some((_, _, members)) => members.push(theme),
+                        None => groups.push((key, canonical, vec![theme])),
+                    }
+                }
+                None => dropped.push(theme.id),
+            }
+        }
+        
+        let tx = conn.unchecked_transaction()?;
+        let mut removed = 0;
+        for id in dropped {
+            removed += tx.execute("DELETE FROM recurring_themes WHERE id = ?1", params![id])?;
+        }
+        for (_, canonical, members) in groups {
+            if members.len() == 1 && members[0].theme == canonical {
+                continue;
+            }
+            // The most frequent row survives; the rest go first since theme is UNIQUE
+            let keep = &members[0];
+            let mut frequency = 0;
+            let mut last_mentioned = keep.last_mentioned.clone();
+            let mut convs: Vec<String> = Vec::new();
+            for member in &members {
+                frequency += member.frequency;
+                if member.last_mentioned > last_mentioned {
+                    last_mentioned = member.last_mentioned.clone();
+                }
+                let member_convs: Vec<String> = member.related_conversations.as_deref()
+                    .and_then(|json| serde_json::from_str(json).ok())
+                    .unwrap_or_default();
+                for conv in member_convs {
+                    if !convs.contains(&conv) {
+                        convs.push(conv);
+                    }
+                }
+                if member.id != keep.id {
+                    removed += tx.execute("DELETE FROM recurring_themes WHERE id = ?1", params![member.id])?;
+                }
+            }
+            tx.execute(
+                "UPDATE recurring_themes SET theme = ?1, frequency = ?2, last_mentioned = ?3, related_conversations = ?4 WHERE id = ?5",
+                params![canonical, frequency, last_mentioned, serde_json::to_string(&convs).unwrap_or_default(), keep.id]
+            )?;
+        }
+        tx.commit()?;
+        Ok(removed)
+    })
+}
+
+pub fn get_top_themes(limit: usize) -> Result<Vec<RecurringTheme>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, theme, frequency, last_mentioned, related_conversations
+             FROM recurring_themes ORDER BY frequency DESC LIMIT ?1"
+        )?;
+        
+        let themes = stmt.query_map([limit], |row| {
+            Ok(RecurringTheme {
+                id: row.get(0)?,
+                theme: row.get(1)?,
+                frequency: row.get(2)?,
+                last_mentioned: row.get(3)?,
+                related_conversations: row.get(4)?,
+            })
+        })?;
+        
+        themes.collect()
+    })
+}
+
+/// Every theme, however rarely mentioned, most frequent first
+pub fn get_all_recurring_themes() -> Result<Vec<RecurringTheme>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, theme, frequency, last_mentioned, related_conversations
+             FROM recurring_themes ORDER BY frequency DESC, id"
+        )?;
+        let themes = stmt.query_map([], |row| {
+            Ok(RecurringTheme {
+                id: row.get(0)?,
+                theme: row.get(1)?,
+                frequency: row.get(2)?,
+                last_mentioned: row.get(3)?,
+                related_conversations: row.get(4)?,
+            })
+        })?;
+        themes.collect()
+    })
+}
+
+/// Halve the frequency of themes unmentioned (and undecayed) since `cutoff`, and
+/// drop those that reach zero. Returns (decayed, removed).
+pub fn decay_recurring_themes(cutoff: &str) -> Result<(usize, usize)> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let decayed = tx.execute(
+            "UPDATE recurring_themes SET frequency = frequency / 2, decayed_at = ?1
+             WHERE MAX(last_mentioned, COALESCE(decayed_at, '')) <= ?2",
+            params![now, cutoff],
+        )?;
+        let removed = tx.execute("DELETE FROM recurring_themes WHERE frequency <= 0", [])?;
+        tx.commit()?;
+        Ok((decayed, removed))
+    })
+}
+
+pub fn delete_recurring_theme(id: i64) -> Result<bool> {
+    with_connection(|conn| {
+        Ok(conn.execute("DELETE FROM recurring_themes WHERE id = ?1", params![id])? > 0)
+    })
+}
+
+/// Themes usable in prompts: only ones mentioned often enough to be a real pattern
+pub fn get_prompt_themes(limit: usize) -> Result<Vec<RecurringTheme>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, theme, frequency, last_mentioned, related_conversations
+             FROM recurring_themes WHERE frequency >= ?1 ORDER BY frequency DESC LIMIT ?2"
+        )?;
+        
+        let themes = stmt.query_map(params![MIN_THEME_FREQUENCY, limit], |row| {
+            Ok(RecurringTheme {
+                id: row.get(0)?,
+                theme: row.get(1)?,
+                frequency: row.get(2)?,
+                last_mentioned: row.get(3)?,
+                related_conversations: row.get(4)?,
+            })
+        })?;
+        
+        themes.collect()
+    })
+}
+
+// ============ Locale Strings ============
+
+pub fn get_locale_string(locale: &str, key: &str) -> Result<Option<String>> {
+    with_connection(|conn| {
+        match conn.query_row(
+            "SELECT value FROM locale_strings WHERE locale = ?1 AND key = ?2",
+            params![locale, key],
+            |row| row.get(0),
+        ) {
+            Ok(value) => Ok(Some(value)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+pub fn get_locale_strings(locale: &str) -> Result<Vec<(String, String)>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare("SELECT key, value FROM locale_strings WHERE locale = ?1")?;
+        let rows = stmt.query_map([locale], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    })
+}
+
+pub fn get_override_locales() -> Result<Vec<String>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare("SELECT DISTINCT locale FROM locale_strings ORDER BY locale")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    })
+}
+
+pub fn set_locale_string(locale: &str, key: &str, value: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
         conn.execute(
-            "INSERT INTO persona_profiles (id, name, is_default, is_active, dominant_trait, secondary_trait, instinct_weight, logic_weight, psyche_weight, instinct_points, logic_points, psyche_points, message_count, created_at, updated_at)
-             VALUES (?1, 'Logic', 1, 1, 'logic', 'logic', 0.30, 0.40, 0.30, 3, 4, 4, 0, ?2, ?3)",
-            params![logic_id, now, now]
+            "INSERT OR REPLACE INTO locale_strings (locale, key, value, updated_at) VALUES (?1, ?2, ?3, ?4)",
+            params![locale, key, value, now],
         )?;
-    }
-    
-    if !has_instinct {
-        let instinct_id = uuid::Uuid::new_v4().to_string();
+        Ok(())
+    })
+}
+
+pub fn delete_locale_string(locale: &str, key: &str) -> Result<()> {
+    with_connection(|conn| {
         conn.execute(
-            "INSERT INTO persona_profiles (id, name, is_default, is_active, dominant_trait, secondary_trait, instinct_weight, logic_weight, psyche_weight, instinct_points, logic_points, psyche_points, message_count, created_at, updated_at)
-             VALUES (?1, 'Instinct', 0, 0, 'instinct', 'instinct', 0.40, 0.30, 0.30, 4, 3, 4, 0, ?2, ?3)",
-            params![instinct_id, now, now]
+            "DELETE FROM locale_strings WHERE locale = ?1 AND key = ?2",
+            params![locale, key],
         )?;
-    }
-    
-    if !has_psyche {
-        let psyche_id = uuid::Uuid::new_v4().to_string();
+        Ok(())
+    })
+}
+
+// ============ Agents ============
+
+const AGENT_COLUMNS: &str =
+    "id, display_name, disco_name, color, disco_color, icon, disco_icon, description, disco_description, voice_samples, sort_order, is_builtin, updated_at";
+
+fn agent_from_row(row: &rusqlite::Row) -> Result<AgentPresentation> {
+    Ok(AgentPresentation {
+        id: row.get(0)?,
+        display_name: row.get(1)?,
+        disco_name: row.get(2)?,
+        color: row.get(3)?,
+        disco_color: row.get(4)?,
+        icon: row.get(5)?,
+        disco_icon: row.get(6)?,
+        description: row.get(7)?,
+        disco_description: row.get(8)?,
+        voice_samples: row.get(9)?,
+        sort_order: row.get(10)?,
+        is_builtin: row.get::<_, i64>(11)? != 0,
+        updated_at: row.get(12)?,
+    })
+}
+
+/// Seed an agent without touching any edits the user already made
+pub fn insert_agent_if_missing(agent: &AgentPresentation) -> Result<()> {
+    save_agent_inner(agent, "INSERT OR IGNORE")
+}
+
+pub fn save_agent(agent: &AgentPresentation) -> Result<()> {
+    save_agent_inner(agent, "INSERT OR REPLACE")
+}
+
+fn save_agent_inner(agent: &AgentPresentation, verb: &str) -> Result<()> {
+    with_connection(|conn| {
         conn.execute(
-            "INSERT INTO persona_profiles (id, name, is_default, is_active, dominant_trait, secondary_trait, instinct_weight, logic_weight, psyche_weight, instinct_points, logic_points, psyche_points, message_count, created_at, updated_at)
-             VALUES (?1, 'Psyche', 0, 0, 'psyche', 'psyche', 0.30, 0.30, 0.40, 3, 3, 5, 0, ?2, ?3)",
-            params![psyche_id, now, now]
+            &format!(
+                "{} INTO agents ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                verb, AGENT_COLUMNS
+            ),
+            params![
+                agent.id,
+                agent.display_name,
+                agent.disco_name,
+                agent.color,
+                agent.disco_color,
+                agent.icon,
+                agent.disco_icon,
+                agent.description,
+                agent.disco_description,
+                agent.voice_samples,
+                agent.sort_order,
+                agent.is_builtin,
+                agent.updated_at,
+            ],
         )?;
-    }
-    
-    // Ensure exactly one profile is active (prefer Logic if none)
-    let has_active: bool = conn.query_row(
-        "SELECT COUNT(*) FROM persona_profiles WHERE is_active = 1",
-        [],
-        |row| Ok(row.get::<_, i64>(0)? > 0)
-    ).unwrap_or(false);
-    
-    if !has_active {
+        Ok(())
+    })
+}
+
+pub fn get_all_agents() -> Result<Vec<AgentPresentation>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM agents ORDER BY sort_order ASC, id ASC",
+            AGENT_COLUMNS
+        ))?;
+        let agents = stmt.query_map([], agent_from_row)?;
+        agents.collect()
+    })
+}
+
+pub fn get_agent(id: &str) -> Result<Option<AgentPresentation>> {
+    with_connection(|conn| {
+        match conn.query_row(
+            &format!("SELECT {} FROM agents WHERE id = ?1", AGENT_COLUMNS),
+            [id],
+            agent_from_row,
+        ) {
+            Ok(agent) => Ok(Some(agent)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+// ============ Grounding Exercises ============
+
+pub fn insert_grounding_exercise_if_missing(exercise: &GroundingExercise) -> Result<()> {
+    with_connection(|conn| {
         conn.execute(
-            "UPDATE persona_profiles SET is_active = 1 WHERE dominant_trait = 'logic'",
-            []
+            "INSERT OR IGNORE INTO grounding_exercises (id, name, description, steps, is_builtin, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                exercise.id,
+                exercise.name,
+                exercise.description,
+                exercise.steps,
+                exercise.is_builtin,
+                exercise.created_at,
+            ],
         )?;
-    }
-    
-    // Ensure exactly one profile is default (prefer Logic if none)
-    let has_default: bool = conn.query_row(
-        "SELECT COUNT(*) FROM persona_profiles WHERE is_default = 1",
-        [],
-        |row| Ok(row.get::<_, i64>(0)? > 0)
-    ).unwrap_or(false);
-    
-    if !has_default {
+        Ok(())
+    })
+}
+
+fn exercise_from_row(row: &rusqlite::Row) -> Result<GroundingExercise> {
+    Ok(GroundingExercise {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        steps: row.get(3)?,
+        is_builtin: row.get::<_, i64>(4)? != 0,
+        created_at: row.get(5)?,
+    })
+}
+
+pub fn get_all_grounding_exercises() -> Result<Vec<GroundingExercise>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, steps, is_builtin, created_at
+             FROM grounding_exercises ORDER BY is_builtin DESC, created_at ASC"
+        )?;
+        let exercises = stmt.query_map([], exercise_from_row)?;
+        exercises.collect()
+    })
+}
+
+pub fn get_grounding_exercise(id: &str) -> Result<Option<GroundingExercise>> {
+    with_connection(|conn| {
+        let result = conn.query_row(
+            "SELECT id, name, description, steps, is_builtin, created_at
+             FROM grounding_exercises WHERE id = ?1",
+            params![id],
+            exercise_from_row,
+        );
+        match result {
+            Ok(e) => Ok(Some(e)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    })
+}
+
+pub fn create_exercise_session(id: &str, exercise_id: &str, conversation_id: Option<&str>, intro: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
         conn.execute(
-            "UPDATE persona_profiles SET is_default = 1 WHERE dominant_trait = 'logic'",
-            []
+            "INSERT INTO exercise_sessions (id, exercise_id, conversation_id, intro, started_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![id, exercise_id, conversation_id, intro, now],
         )?;
-    }
-    
-    // Remove any profiles that don't match the 3 fixed trait types
-    // (Clean up any old custom profiles)
-    conn.execute(
-        "DELETE FROM persona_profiles WHERE dominant_trait NOT IN ('logic', 'instinct', 'psyche')",
-        []
-    )?;
-    
-    // Keep only one profile per dominant trait (remove duplicates, keep the one with most messages)
-    for trait_type in &["logic", "instinct", "psyche"] {
-        let count: i64 = conn.query_row(
-            &format!("SELECT COUNT(*) FROM persona_profiles WHERE dominant_trait = '{}'", trait_type),
-            [],
-            |row| row.get(0)
-        ).unwrap_or(0);
-        
-        if count > 1 {
-            // Get the ID of the profile to keep (highest message_count)
-            let keep_id: String = conn.query_row(
-                &format!(
-                    "SELECT id FROM persona_profiles WHERE dominant_trait = '{}' ORDER BY message_count DESC, created_at ASC LIMIT 1",
-                    trait_type
-                ),
-                [],
-                |row| row.get(0)
-            ).unwrap_or_default();
-            
-            if !keep_id.is_empty() {
-                conn.execute(
-                    &format!(
-                        "DELETE FROM persona_profiles WHERE dominant_trait = '{}' AND id != ?1",
-                        trait_type
-                    ),
-                    params![keep_id]
-                )?;
+        Ok(())
+    })
+}
+
+pub fn complete_exercise_session(id: &str, outro: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE exercise_sessions SET outro = ?1, completed_at = ?2 WHERE id = ?3",
+            params![outro, now, id],
+        )?;
+        Ok(())
+    })
+}
+
+pub fn get_exercise_session(id: &str) -> Result<Option<ExerciseSession>> {
+    with_connection(|conn| {
+        let result = conn.query_row(
+            "SELECT id, exercise_id, conversation_id, intro, outro, started_at, completed_at
+             FROM exercise_sessions WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok(ExerciseSession {
+                    id: row.get(0)?,
+                    exercise_id: row.get(1)?,
+                    conversation_id: row.get(2)?,
+                    intro: row.get(3)?,
+                    outro: row.get(4)?,
+                    started_at: row.get(5)?,
+                    completed_at: row.get(6)?,
+                })
             }
+        );
+        match result {
+            Ok(s) => Ok(Some(s)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
         }
-    }
-    
-    let mut db = DB.lock().unwrap();
-    *db = Some(conn);
-    
-    Ok(())
+    })
+}
+
+// ============ Proactive Messages ============
+
+const PROACTIVE_COLUMNS: &str = "id, conversation_id, agent, content, topic_key, scheduled_for, status, delivered_at, seen_at, responded_at, suppressed_count, created_at, user_requested";
+
+fn proactive_from_row(row: &rusqlite::Row) -> Result<ProactiveMessage> {
+    Ok(ProactiveMessage {
+        id: row.get(0)?,
+        conversation_id: row.get(1)?,
+        agent: row.get(2)?,
+        content: row.get(3)?,
+        topic_key: row.get(4)?,
+        scheduled_for: row.get(5)?,
+        status: row.get(6)?,
+        delivered_at: row.get(7)?,
+        seen_at: row.get(8)?,
+        responded_at: row.get(9)?,
+        suppressed_count: row.get(10)?,
+        created_at: row.get(11)?,
+        user_requested: row.get::<_, Option<i64>>(12)?.unwrap_or(0) != 0,
+    })
+}
+
+pub fn create_proactive_message(msg: &ProactiveMessage) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO proactive_messages (id, conversation_id, agent, content, topic_key, scheduled_for, status, suppressed_count, created_at, user_requested)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'pending', 0, ?7, ?8)",
+            params![msg.id, msg.conversation_id, msg.agent, msg.content, msg.topic_key, msg.scheduled_for, msg.created_at, msg.user_requested],
+        )?;
+        Ok(())
+    })
+}
+
+/// Pending messages whose scheduled time has passed, oldest first
+pub fn get_due_proactive_messages(now: &str) -> Result<Vec<ProactiveMessage>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM proactive_messages WHERE status = 'pending' AND scheduled_for <= ?1 ORDER BY scheduled_for ASC",
+            PROACTIVE_COLUMNS
+        ))?;
+        let msgs = stmt.query_map(params![now], proactive_from_row)?;
+        msgs.collect()
+    })
+}
+
+/// Most recently created message on a topic, whatever its status
+pub fn get_latest_proactive_for_topic(topic_key: &str) -> Result<Option<ProactiveMessage>> {
+    with_connection(|conn| {
+        conn.query_row(
+            &format!(
+                "SELECT {} FROM proactive_messages WHERE topic_key = ?1 ORDER BY created_at DESC LIMIT 1",
+                PROACTIVE_COLUMNS
+            ),
+            params![topic_key],
+            proactive_from_row,
+        ).optional()
+    })
 }
 
-fn with_connection<F, T>(f: F) -> Result<T>
-where
-    F: FnOnce(&Connection) -> Result<T>,
-{
-    let db = DB.lock().unwrap();
-    let conn = db.as_ref().expect("Database not initialized");
-    f(conn)
+/// Delivered messages the user hasn't answered or dismissed yet. Ones whose
+/// conversation has since been trashed or purged can't be answered and are left out.
+pub fn get_unanswered_proactive_messages() -> Result<Vec<ProactiveMessage>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM proactive_messages
+             WHERE status IN ('delivered', 'seen')
+               AND (conversation_id IS NULL OR conversation_id IN (SELECT id FROM conversations WHERE deleted_at IS NULL))
+             ORDER BY delivered_at ASC",
+            PROACTIVE_COLUMNS
+        ))?;
+        let msgs = stmt.query_map([], proactive_from_row)?;
+        msgs.collect()
+    })
 }
 
-// ============ User Profile ============
-
-pub fn get_user_profile() -> Result<UserProfile> {
+pub fn mark_proactive_delivered(id: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
     with_connection(|conn| {
-        // Get base profile info (API keys, message count)
-        let base: (i64, Option<String>, Option<String>, i64, String, String) = conn.query_row(
-            "SELECT id, api_key, anthropic_key, total_messages, created_at, updated_at
-             FROM user_profile LIMIT 1",
-            [],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?))
+        conn.execute(
+            "UPDATE proactive_messages SET status = 'delivered', delivered_at = ?1 WHERE id = ?2",
+            params![now, id],
         )?;
-        
-        // Get weights from active persona profile, or fallback to user_profile weights
-        let weights: (f64, f64, f64) = conn.query_row(
-            "SELECT instinct_weight, logic_weight, psyche_weight FROM persona_profiles WHERE is_active = 1",
-            [],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))
-        ).unwrap_or_else(|_| {
-            // Fallback to user_profile weights if no active persona profile
-            conn.query_row(
-                "SELECT instinct_weight, logic_weight, psyche_weight FROM user_profile LIMIT 1",
-                [],
-                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))
-            ).unwrap_or((0.2, 0.5, 0.3)) // Final fallback to defaults
-        });
-        
-        Ok(UserProfile {
-            id: base.0,
-            api_key: base.1,
-            anthropic_key: base.2,
-            instinct_weight: weights.0,
-            logic_weight: weights.1,
-            psyche_weight: weights.2,
-            total_messages: base.3,
-            created_at: base.4,
-            updated_at: base.5,
-        })
+        Ok(())
     })
 }
 
-pub fn update_api_key(api_key: &str) -> Result<()> {
-    let now = Utc::now().to_rfc3339();
+/// Fold a nudge into an outstanding one on the same topic
+pub fn suppress_proactive_message(id: &str, into_id: &str) -> Result<()> {
     with_connection(|conn| {
+        conn.execute("UPDATE proactive_messages SET status = 'suppressed' WHERE id = ?1", params![id])?;
         conn.execute(
-            "UPDATE user_profile SET api_key = ?1, updated_at = ?2",
-            params![api_key, now]
+            "UPDATE proactive_messages SET suppressed_count = suppressed_count + 1 WHERE id = ?1",
+            params![into_id],
         )?;
         Ok(())
     })
 }
 
-pub fn clear_api_key() -> Result<()> {
+pub fn mark_proactive_seen(id: &str) -> Result<()> {
     let now = Utc::now().to_rfc3339();
     with_connection(|conn| {
         conn.execute(
-            "UPDATE user_profile SET api_key = NULL, updated_at = ?1",
-            params![now]
+            "UPDATE proactive_messages SET status = 'seen', seen_at = COALESCE(seen_at, ?1) WHERE id = ?2 AND status = 'delivered'",
+            params![now, id],
         )?;
         Ok(())
     })
 }
 
-pub fn update_anthropic_key(api_key: &str) -> Result<()> {
+/// The user waved a check-in away without answering it; false if it wasn't outstanding
+pub fn mark_proactive_dismissed(id: &str) -> Result<bool> {
     let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        let changed = conn.execute(
+            "UPDATE proactive_messages SET status = 'dismissed', seen_at = COALESCE(seen_at, ?1)
+             WHERE id = ?2 AND status IN ('delivered', 'seen')",
+            params![now, id],
+        )?;
+        Ok(changed > 0)
+    })
+}
+
+/// The user replied in a conversation -- any check-ins delivered there are answered
+pub fn mark_proactive_responded_for_conversation(conversation_id: &str) -> Result<usize> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE proactive_messages SET status = 'responded', responded_at = ?1, seen_at = COALESCE(seen_at, ?1)
+             WHERE conversation_id = ?2 AND status IN ('delivered', 'seen')",
+            params![now, conversation_id],
+        )
+    })
+}
+
+// ============ Session Sharing ============
+
+pub fn save_share_log(entry: &ShareLogEntry) -> Result<i64> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT INTO share_log (conversation_id, destination, target, subject, content, error, shared_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                entry.conversation_id,
+                entry.destination,
+                entry.target,
+                entry.subject,
+                entry.content,
+                entry.error,
+                entry.shared_at,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })
+}
+
+pub fn get_share_log() -> Result<Vec<ShareLogEntry>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, conversation_id, destination, target, subject, content, error, shared_at
+             FROM share_log ORDER BY shared_at DESC"
+        )?;
+
+        let entries = stmt.query_map([], |row| {
+            Ok(ShareLogEntry {
+                id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                destination: row.get(2)?,
+                target: row.get(3)?,
+                subject: row.get(4)?,
+                content: row.get(5)?,
+                error: row.get(6)?,
+                shared_at: row.get(7)?,
+            })
+        })?;
+
+        entries.collect()
+    })
+}
+
+// ============ Usage ============
+
+pub fn save_usage_record(record: &UsageRecord) -> Result<()> {
     with_connection(|conn| {
         conn.execute(
-            "UPDATE user_profile SET anthropic_key = ?1, updated_at = ?2",
-            params![api_key, now]
+            "INSERT INTO usage_log (provider, model, category, agent, input_tokens, output_tokens, latency_ms, cost_usd, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                record.provider,
+                record.model,
+                record.category,
+                record.agent,
+                record.input_tokens,
+                record.output_tokens,
+                record.latency_ms,
+                record.cost_usd,
+                record.created_at,
+            ],
         )?;
         Ok(())
     })
 }
 
-pub fn clear_anthropic_key() -> Result<()> {
+/// Usage records in [start, end), oldest first. Either bound may be omitted.
+pub fn get_usage_records(start: Option<&str>, end: Option<&str>) -> Result<Vec<UsageRecord>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, provider, model, category, agent, input_tokens, output_tokens, latency_ms, cost_usd, created_at
+             FROM usage_log
+             WHERE (?1 IS NULL OR created_at >= ?1) AND (?2 IS NULL OR created_at < ?2)
+             ORDER BY created_at ASC"
+        )?;
+
+        let records = stmt.query_map(params![start, end], |row| {
+            Ok(UsageRecord {
+                id: row.get(0)?,
+                provider: row.get(1)?,
+                model: row.get(2)?,
+                category: row.get(3)?,
+                agent: row.get(4)?,
+                input_tokens: row.get(5)?,
+                output_tokens: row.get(6)?,
+                latency_ms: row.get(7)?,
+                cost_usd: row.get(8)?,
+                created_at: row.get(9)?,
+            })
+        })?;
+
+        records.collect()
+    })
+}
+
+/// Spend per local day in [start, end), oldest first; days without calls are left out
+pub fn get_spend_by_day(start: Option<&str>, end: Option<&str>) -> Result<Vec<DailySpend>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT date(created_at, 'localtime') AS day, COUNT(*), SUM(input_tokens), SUM(output_tokens), SUM(cost_usd)
+             FROM usage_log
+             WHERE (?1 IS NULL OR created_at >= ?1) AND (?2 IS NULL OR created_at < ?2)
+             GROUP BY day
+             ORDER BY day ASC"
+        )?;
+        let days = stmt.query_map(params![start, end], |row| {
+            Ok(DailySpend {
+                day: row.get(0)?,
+                calls: row.get(1)?,
+                input_tokens: row.get(2)?,
+                output_tokens: row.get(3)?,
+                cost_usd: row.get(4)?,
+            })
+        })?;
+        days.collect()
+    })
+}
+
+/// Spend per model in [start, end), most expensive first
+pub fn get_spend_by_model(start: Option<&str>, end: Option<&str>) -> Result<Vec<ModelSpend>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT provider, model, COUNT(*), SUM(input_tokens), SUM(output_tokens), SUM(cost_usd) AS cost
+             FROM usage_log
+             WHERE (?1 IS NULL OR created_at >= ?1) AND (?2 IS NULL OR created_at < ?2)
+             GROUP BY provider, model
+             ORDER BY cost DESC, model ASC"
+        )?;
+        let models = stmt.query_map(params![start, end], |row| {
+            Ok(ModelSpend {
+                provider: row.get(0)?,
+                model: row.get(1)?,
+                calls: row.get(2)?,
+                input_tokens: row.get(3)?,
+                output_tokens: row.get(4)?,
+                cost_usd: row.get(5)?,
+            })
+        })?;
+        models.collect()
+    })
+}
+
+/// Tokens (input + output) an agent has spent on hosted models since `since`
+pub fn get_agent_tokens_since(agent: &str, since: &str) -> Result<i64> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT COALESCE(SUM(input_tokens + output_tokens), 0)
+             FROM usage_log
+             WHERE agent = ?1 AND created_at >= ?2 AND provider != 'local'",
+            params![agent, since],
+            |row| row.get(0),
+        )
+    })
+}
+
+/// Average estimated cost of one call in a usage category since `since`; None with no calls
+pub fn get_average_call_cost(category: &str, since: &str) -> Result<Option<f64>> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT AVG(cost_usd) FROM usage_log
+             WHERE category = ?1 AND created_at >= ?2 AND provider != 'local'",
+            params![category, since],
+            |row| row.get(0),
+        )
+    })
+}
+
+/// Hosted tokens and estimated cost across all calls since `since`
+pub fn get_usage_totals_since(since: &str) -> Result<(i64, f64)> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT COALESCE(SUM(input_tokens + output_tokens), 0), COALESCE(SUM(cost_usd), 0)
+             FROM usage_log
+             WHERE created_at >= ?1 AND provider != 'local'",
+            params![since],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+    })
+}
+
+// ============ Journal ============
+
+pub fn create_journal_entry(id: &str, entry_type: &str, content: &str) -> Result<JournalEntry> {
     let now = Utc::now().to_rfc3339();
     with_connection(|conn| {
         conn.execute(
-            "UPDATE user_profile SET anthropic_key = NULL, updated_at = ?1",
-            params![now]
+            "INSERT INTO journal_entries (id, entry_type, content, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?4)",
+            params![id, entry_type, content, now],
         )?;
-        Ok(())
+        Ok(JournalEntry {
+            id: id.to_string(),
+            entry_type: entry_type.to_string(),
+            content: content.to_string(),
+            reflection: None,
+            created_at: now.clone(),
+            updated_at: now,
+        })
     })
 }
 
-/// Update points for the active persona profile
-pub fn update_points(instinct: i64, logic: i64, psyche: i64) -> Result<()> {
+pub fn set_journal_reflection(entry_id: &str, reflection: &str) -> Result<()> {
     let now = Utc::now().to_rfc3339();
     with_connection(|conn| {
         conn.execute(
-            "UPDATE persona_profiles SET instinct_points = ?1, logic_points = ?2, psyche_points = ?3, updated_at = ?4 WHERE is_active = 1",
-            params![instinct, logic, psyche, now]
+            "UPDATE journal_entries SET reflection = ?1, updated_at = ?2 WHERE id = ?3",
+            params![reflection, now, entry_id],
         )?;
         Ok(())
     })
 }
 
-pub fn update_weights(instinct: f64, logic: f64, psyche: f64) -> Result<()> {
-    let now = Utc::now().to_rfc3339();
+pub fn get_journal_entries(entry_type: Option<&str>, limit: usize) -> Result<Vec<JournalEntry>> {
     with_connection(|conn| {
-        // Update the active persona profile's weights (no constraints)
-        let updated = conn.execute(
-            "UPDATE persona_profiles SET instinct_weight = ?1, logic_weight = ?2, psyche_weight = ?3, updated_at = ?4 WHERE is_active = 1",
-            params![instinct, logic, psyche, now]
+        let mut stmt = conn.prepare(
+            "SELECT id, entry_type, content, reflection, created_at, updated_at
+             FROM journal_entries
+             WHERE ?1 IS NULL OR entry_type = ?1
+             ORDER BY created_at DESC
+             LIMIT ?2"
         )?;
-        
-        // Fallback to user_profile if no active persona profile (legacy support)
-        if updated == 0 {
+
+        let entries = stmt.query_map(params![entry_type, limit as i64], |row| {
+            Ok(JournalEntry {
+                id: row.get(0)?,
+                entry_type: row.get(1)?,
+                content: row.get(2)?,
+                reflection: row.get(3)?,
+                created_at: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        })?;
+
+        entries.collect()
+    })
+}
+
+pub fn save_dream_motif(motif: &str, motif_type: &str, entry_id: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        // Try to get existing motif
+        let existing: Option<(i64, String)> = conn.query_row(
+            "SELECT id, related_entries FROM dream_motifs WHERE motif = ?1",
+            params![motif],
+            |row| Ok((row.get(0)?, row.get::<_, Option<String>>(1)?.unwrap_or_default()))
+        ).ok();
+
+        if let Some((id, existing_entries)) = existing {
+            let mut entries: Vec<String> = if existing_entries.is_empty() {
+                Vec::new()
+            } else {
+                serde_json::from_str(&existing_entries).unwrap_or_default()
+            };
+            if !entries.contains(&entry_id.to_string()) {
+                entries.push(entry_id.to_string());
+            }
+            let entries_json = serde_json::to_string(&entries).unwrap_or_default();
+
             conn.execute(
-                "UPDATE user_profile SET instinct_weight = ?1, logic_weight = ?2, psyche_weight = ?3, updated_at = ?4",
-                params![instinct, logic, psyche, now]
+                "UPDATE dream_motifs SET frequency = frequency + 1, last_seen = ?1, related_entries = ?2 WHERE id = ?3",
+                params![now, entries_json, id]
+            )?;
+        } else {
+            let entries_json = serde_json::to_string(&vec![entry_id]).unwrap_or_default();
+            conn.execute(
+                "INSERT INTO dream_motifs (motif, motif_type, frequency, first_seen, last_seen, related_entries) VALUES (?1, ?2, 1, ?3, ?3, ?4)",
+                params![motif, motif_type, now, entries_json]
             )?;
         }
-        
         Ok(())
     })
 }
 
-/// Enforce that the dominant trait maintains at least a 10% lead over other traits
-fn enforce_dominant_lead(instinct: f64, logic: f64, psyche: f64, dominant: &str) -> (f64, f64, f64) {
-    let min_lead = 0.10; // 10% lead
-    
-    let (mut i, mut l, mut p) = (instinct, logic, psyche);
-    
-    match dominant {
-        "instinct" => {
-            let max_other = l.max(p);
-            if i < max_other + min_lead {
-                // Need to boost instinct to maintain lead
-                i = max_other + min_lead;
-            }
-        }
-        "logic" => {
-            let max_other = i.max(p);
-            if l < max_other + min_lead {
-                l = max_other + min_lead;
-            }
-        }
-        "psyche" => {
-            let max_other = i.max(l);
-            if p < max_other + min_lead {
-                p = max_other + min_lead;
-            }
-        }
-        _ => {}
-    }
-    
-    // Normalize to sum to 1.0
-    let total = i + l + p;
-    (i / total, l / total, p / total)
+pub fn get_dream_motifs(limit: usize) -> Result<Vec<DreamMotif>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, motif, motif_type, frequency, first_seen, last_seen, related_entries
+             FROM dream_motifs ORDER BY frequency DESC, last_seen DESC LIMIT ?1"
+        )?;
+
+        let motifs = stmt.query_map(params![limit as i64], |row| {
+            Ok(DreamMotif {
+                id: row.get(0)?,
+                motif: row.get(1)?,
+                motif_type: row.get(2)?,
+                frequency: row.get(3)?,
+                first_seen: row.get(4)?,
+                last_seen: row.get(5)?,
+                related_entries: row.get(6)?,
+            })
+        })?;
+
+        motifs.collect()
+    })
 }
 
-pub fn increment_message_count() -> Result<()> {
-    let now = Utc::now().to_rfc3339();
+// ============ Boundaries ============
+
+/// Insert or update a rule; returns its stored `created_at`, which an update keeps
+pub fn save_boundary_rule(rule: &BoundaryRule) -> Result<String> {
     with_connection(|conn| {
-        // Increment global message count
-        conn.execute(
-            "UPDATE user_profile SET total_messages = total_messages + 1, updated_at = ?1",
-            params![now]
+        conn.query_row(
+            "INSERT INTO boundary_rules (id, agent, description, keywords, start_time, end_time, action, enabled, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT(id) DO UPDATE SET
+                agent = excluded.agent,
+                description = excluded.description,
+                keywords = excluded.keywords,
+                start_time = excluded.start_time,
+                end_time = excluded.end_time,
+                action = excluded.action,
+                enabled = excluded.enabled
+             RETURNING created_at",
+            params![
+                rule.id,
+                rule.agent,
+                rule.description,
+                rule.keywords,
+                rule.start_time,
+                rule.end_time,
+                rule.action,
+                rule.enabled,
+                rule.created_at,
+            ],
+            |row| row.get(0),
+        )
+    })
+}
+
+pub fn get_boundary_rules() -> Result<Vec<BoundaryRule>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, agent, description, keywords, start_time, end_time, action, enabled, created_at
+             FROM boundary_rules ORDER BY created_at ASC"
         )?;
-        
-        // Also increment the active persona profile's message count
+
+        let rules = stmt.query_map([], |row| {
+            Ok(BoundaryRule {
+                id: row.get(0)?,
+                agent: row.get(1)?,
+                description: row.get(2)?,
+                keywords: row.get(3)?,
+                start_time: row.get(4)?,
+                end_time: row.get(5)?,
+                action: row.get(6)?,
+                enabled: row.get::<_, i64>(7)? != 0,
+                created_at: row.get(8)?,
+            })
+        })?;
+
+        rules.collect()
+    })
+}
+
+pub fn delete_boundary_rule(id: &str) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute("DELETE FROM boundary_rules WHERE id = ?1", params![id])?;
+        Ok(())
+    })
+}
+
+// ============ Response Filters ============
+
+pub fn save_filter_violation(conversation_id: Option<&str>, agent: &str, filter: &str, detail: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
         conn.execute(
-            "UPDATE persona_profiles SET message_count = message_count + 1, updated_at = ?1 WHERE is_active = 1",
-            params![now]
+            "INSERT INTO filter_violations (conversation_id, agent, filter, detail, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![conversation_id, agent, filter, detail, now],
         )?;
         Ok(())
     })
 }
 
-// ============ Conversations ============
+pub fn get_filter_violation_stats() -> Result<Vec<FilterViolationStat>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT filter, agent, COUNT(*), MAX(created_at)
+             FROM filter_violations
+             GROUP BY filter, agent
+             ORDER BY COUNT(*) DESC"
+        )?;
 
-pub fn create_conversation(id: &str, is_disco: bool) -> Result<Conversation> {
+        let stats = stmt.query_map([], |row| {
+            Ok(FilterViolationStat {
+                filter: row.get(0)?,
+                agent: row.get(1)?,
+                count: row.get(2)?,
+                last_seen: row.get(3)?,
+            })
+        })?;
+
+        stats.collect()
+    })
+}
+
+// ============ Weekly Digest ============
+
+fn weekly_reflection_from_row(row: &rusqlite::Row) -> rusqlite::Result<WeeklyReflection> {
+    Ok(WeeklyReflection {
+        id: row.get(0)?,
+        week_start: row.get(1)?,
+        content: row.get(2)?,
+        created_at: row.get(3)?,
+    })
+}
+
+pub fn save_weekly_reflection(week_start: &str, content: &str) -> Result<WeeklyReflection> {
     let now = Utc::now().to_rfc3339();
     with_connection(|conn| {
         conn.execute(
-            "INSERT INTO conversations (id, title, summary, limbo_summary, processed, is_disco, created_at, updated_at)
-             VALUES (?1, NULL, NULL, NULL, 0, ?2, ?3, ?4)",
-            params![id, if is_disco { 1 } else { 0 }, now, now]
+            "INSERT INTO weekly_reflections (week_start, content, created_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(week_start) DO UPDATE SET content = ?2, created_at = ?3",
+            params![week_start, content, now],
         )?;
-        Ok(Conversation {
-            id: id.to_string(),
-            title: None,
-            summary: None,
-            limbo_summary: None,
-            processed: false,
-            is_disco,
-            created_at: now.clone(),
-            updated_at: now,
-        })
+        conn.query_row(
+            "SELECT id, week_start, content, created_at FROM weekly_reflections WHERE week_start = ?1",
+            [week_start],
+            weekly_reflection_from_row,
+        )
     })
 }
 
-pub fn get_conversation(id: &str) -> Result<Option<Conversation>> {
+pub fn get_weekly_reflection(week_start: &str) -> Result<Option<WeeklyReflection>> {
     with_connection(|conn| {
-        let result = conn.query_row(
-            "SELECT id, title, summary, limbo_summary, processed, is_disco, created_at, updated_at FROM conversations WHERE id = ?1",
-            params![id],
-            |row| {
-                Ok(Conversation {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    summary: row.get(2)?,
-                    limbo_summary: row.get(3)?,
-                    processed: row.get::<_, i64>(4)? != 0,
-                    is_disco: row.get::<_, i64>(5).unwrap_or(0) != 0,
-                    created_at: row.get(6)?,
-                    updated_at: row.get(7)?,
-                })
-            }
-        );
-        match result {
-            Ok(conv) => Ok(Some(conv)),
+        match conn.query_row(
+            "SELECT id, week_start, content, created_at FROM weekly_reflections WHERE week_start = ?1",
+            [week_start],
+            weekly_reflection_from_row,
+        ) {
+            Ok(reflection) => Ok(Some(reflection)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e),
         }
     })
 }
 
-pub fn get_recent_conversations(limit: usize) -> Result<Vec<Conversation>> {
+pub fn get_weekly_reflections(limit: usize) -> Result<Vec<WeeklyReflection>> {
     with_connection(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT c.id, c.title, c.summary, c.limbo_summary, c.processed, c.is_disco, c.created_at, c.updated_at,
-                    (SELECT COUNT(*) FROM messages WHERE conversation_id = c.id) as msg_count
-             FROM conversations c
-             WHERE (SELECT COUNT(*) FROM messages WHERE conversation_id = c.id) > 0
-             ORDER BY c.updated_at DESC 
-             LIMIT ?1"
+            "SELECT id, week_start, content, created_at FROM weekly_reflections ORDER BY week_start DESC LIMIT ?1"
         )?;
-        
-        let convs = stmt.query_map([limit], |row| {
-            Ok(Conversation {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                summary: row.get(2)?,
-                limbo_summary: row.get(3)?,
-                processed: row.get::<_, i64>(4)? != 0,
-                is_disco: row.get::<_, i64>(5).unwrap_or(0) != 0,
-                created_at: row.get(6)?,
-                updated_at: row.get(7)?,
-            })
-        })?;
-        
-        convs.collect()
+        let reflections = stmt.query_map([limit as i64], weekly_reflection_from_row)?;
+        reflections.collect()
     })
 }
 
-/// Get conversations that need recovery (unprocessed, have messages, older than 1 min)
-/// Used on startup to finalize conversations from crashes/force-quits
-pub fn get_conversations_needing_recovery() -> Result<Vec<Conversation>> {
-    use chrono::Duration;
-    
+const MONTHLY_HIGHLIGHTS_COLUMNS: &str = "id, month, content, message_ids, created_at";
+
+fn monthly_highlights_from_row(row: &rusqlite::Row) -> rusqlite::Result<MonthlyHighlights> {
+    let message_ids: String = row.get(3)?;
+    Ok(MonthlyHighlights {
+        id: row.get(0)?,
+        month: row.get(1)?,
+        content: row.get(2)?,
+        message_ids: serde_json::from_str(&message_ids).unwrap_or_default(),
+        created_at: row.get(4)?,
+    })
+}
+
+/// The month's most engaged-with messages sent between `start` and `end` (RFC3339),
+/// ranked by citations plus replies. Failed sends, system notes and messages with
+/// neither signal are left out.
+pub fn get_highlight_candidates(start: &str, end: &str, limit: usize) -> Result<Vec<HighlightCandidate>> {
     with_connection(|conn| {
-        // Get conversations that:
-        // 1. Are not processed
-        // 2. Are older than 1 minute (not currently being written to)
-        let cutoff = (Utc::now() - Duration::minutes(1)).to_rfc3339();
-        
         let mut stmt = conn.prepare(
-            "SELECT c.id, c.title, c.summary, c.limbo_summary, c.processed, c.is_disco, c.created_at, c.updated_at,
-                    (SELECT COUNT(*) FROM messages WHERE conversation_id = c.id) as msg_count
-             FROM conversations c
-             WHERE c.processed = 0 
-               AND c.updated_at < ?1
-             ORDER BY c.updated_at DESC"
+            "SELECT m.id, m.conversation_id, c.title, m.role, m.content, m.timestamp,
+                    (SELECT COUNT(*) FROM response_citations rc WHERE rc.message_id = m.id) AS citations,
+                    (SELECT COUNT(*) FROM messages r WHERE r.references_message_id = m.id
+                        AND (r.status IS NULL OR r.status != ?3)) AS replies
+             FROM messages m
+             JOIN conversations c ON c.id = m.conversation_id
+             WHERE m.timestamp >= ?1 AND m.timestamp < ?2
+               AND m.role != 'system'
+               AND (m.status IS NULL OR m.status != ?3)
+               AND c.deleted_at IS NULL
+               AND citations + replies > 0
+             ORDER BY citations + replies DESC, m.timestamp ASC, m.id ASC
+             LIMIT ?4"
         )?;
-        
-        let convs = stmt.query_map([cutoff], |row| {
-            let msg_count: i64 = row.get(8)?;
-            // Only include if has at least 2 messages (user + agent)
-            if msg_count >= 2 {
-                Ok(Some(Conversation {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    summary: row.get(2)?,
-                    limbo_summary: row.get(3)?,
-                    processed: row.get::<_, i64>(4)? != 0,
-                    is_disco: row.get::<_, i64>(5).unwrap_or(0) != 0,
-                    created_at: row.get(6)?,
-                    updated_at: row.get(7)?,
-                }))
-            } else {
-                Ok(None)
-            }
+        let candidates = stmt.query_map(params![start, end, MESSAGE_STATUS_FAILED, limit as i64], |row| {
+            Ok(HighlightCandidate {
+                message_id: row.get(0)?,
+                conversation_id: row.get(1)?,
+                conversation_title: row.get(2)?,
+                role: row.get(3)?,
+                content: row.get(4)?,
+                timestamp: row.get(5)?,
+                citations: row.get(6)?,
+                replies: row.get(7)?,
+            })
         })?;
-        
-        // Filter out None values
-        convs.filter_map(|r| r.transpose()).collect()
+        candidates.collect()
     })
 }
 
-/// Append to the limbo summary (incremental summary built during conversation)
-pub fn append_limbo_summary(conversation_id: &str, new_content: &str) -> Result<()> {
+pub fn save_monthly_highlights(month: &str, content: &str, message_ids: &[String]) -> Result<MonthlyHighlights> {
     let now = Utc::now().to_rfc3339();
+    let ids = serde_json::to_string(message_ids).unwrap_or_else(|_| "[]".to_string());
     with_connection(|conn| {
-        // Get existing limbo summary
-        let existing: Option<String> = conn.query_row(
-            "SELECT limbo_summary FROM conversations WHERE id = ?1",
-            params![conversation_id],
-            |row| row.get(0)
-        ).ok();
-        
-        // Append new content
-        let updated = match existing {
-            Some(existing_text) => format!("{}\n\n{}", existing_text, new_content),
-            None => new_content.to_string(),
-        };
-        
         conn.execute(
-            "UPDATE conversations SET limbo_summary = ?1, updated_at = ?2 WHERE id = ?3",
-            params![updated, now, conversation_id]
+            "INSERT INTO monthly_highlights (month, content, message_ids, created_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(month) DO UPDATE SET content = ?2, message_ids = ?3, created_at = ?4",
+            params![month, content, ids, now],
         )?;
-        Ok(())
+        conn.query_row(
+            &format!("SELECT {} FROM monthly_highlights WHERE month = ?1", MONTHLY_HIGHLIGHTS_COLUMNS),
+            [month],
+            monthly_highlights_from_row,
+        )
     })
 }
 
-/// Mark a conversation as fully processed (after finalization)
-pub fn mark_conversation_processed(conversation_id: &str, final_summary: Option<&str>) -> Result<()> {
-    let now = Utc::now().to_rfc3339();
+pub fn get_monthly_highlights(month: &str) -> Result<Option<MonthlyHighlights>> {
     with_connection(|conn| {
-        if let Some(summary) = final_summary {
-            conn.execute(
-                "UPDATE conversations SET processed = 1, summary = ?1, updated_at = ?2 WHERE id = ?3",
-                params![summary, now, conversation_id]
-            )?;
-        } else {
-            conn.execute(
-                "UPDATE conversations SET processed = 1, updated_at = ?1 WHERE id = ?2",
-                params![now, conversation_id]
-            )?;
-        }
-        Ok(())
+        conn.query_row(
+            &format!("SELECT {} FROM monthly_highlights WHERE month = ?1", MONTHLY_HIGHLIGHTS_COLUMNS),
+            [month],
+            monthly_highlights_from_row,
+        ).optional()
     })
 }
 
-// ============ Messages ============
+pub fn get_all_monthly_highlights(limit: usize) -> Result<Vec<MonthlyHighlights>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM monthly_highlights ORDER BY month DESC LIMIT ?1", MONTHLY_HIGHLIGHTS_COLUMNS
+        ))?;
+        let highlights = stmt.query_map([limit as i64], monthly_highlights_from_row)?;
+        highlights.collect()
+    })
+}
 
-pub fn save_message(message: &Message) -> Result<()> {
+pub fn save_email_log(entry: &EmailLogEntry) -> Result<i64> {
     with_connection(|conn| {
         conn.execute(
-            "INSERT OR REPLACE INTO messages (id, conversation_id, role, content, response_type, references_message_id, timestamp)
+            "INSERT INTO email_log (content_type, content_id, target, subject, content, error, sent_at)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             params![
-                message.id,
-                message.conversation_id,
-                message.role,
-                message.content,
-                message.response_type,
-                message.references_message_id,
-                message.timestamp
-            ]
+                entry.content_type,
+                entry.content_id,
+                entry.target,
+                entry.subject,
+                entry.content,
+                entry.error,
+                entry.sent_at,
+            ],
         )?;
-        
-        // Update conversation timestamp
-        let now = Utc::now().to_rfc3339();
-        conn.execute(
-            "UPDATE conversations SET updated_at = ?1 WHERE id = ?2",
-            params![now, message.conversation_id]
-        )?;
-        
-        Ok(())
+        Ok(conn.last_insert_rowid())
     })
 }
 
-pub fn get_conversation_messages(conversation_id: &str) -> Result<Vec<Message>> {
-    with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, conversation_id, role, content, response_type, references_message_id, timestamp 
-             FROM messages 
-             WHERE conversation_id = ?1 
-             ORDER BY timestamp ASC"
-        )?;
-        
-        let messages = stmt.query_map([conversation_id], |row| {
-            Ok(Message {
-                id: row.get(0)?,
-                conversation_id: row.get(1)?,
-                role: row.get(2)?,
-                content: row.get(3)?,
-                response_type: row.get(4)?,
-                references_message_id: row.get(5)?,
-                timestamp: row.get(6)?,
-            })
-        })?;
-        
-        messages.collect()
+const EMAIL_LOG_COLUMNS: &str = "id, content_type, content_id, target, subject, content, error, sent_at";
+
+fn email_log_from_row(row: &rusqlite::Row) -> rusqlite::Result<EmailLogEntry> {
+    Ok(EmailLogEntry {
+        id: row.get(0)?,
+        content_type: row.get(1)?,
+        content_id: row.get(2)?,
+        target: row.get(3)?,
+        subject: row.get(4)?,
+        content: row.get(5)?,
+        error: row.get(6)?,
+        sent_at: row.get(7)?,
     })
 }
 
-pub fn get_recent_messages(conversation_id: &str, limit: usize) -> Result<Vec<Message>> {
+pub fn get_email_log() -> Result<Vec<EmailLogEntry>> {
     with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, conversation_id, role, content, response_type, references_message_id, timestamp 
-             FROM messages 
-             WHERE conversation_id = ?1 
-             ORDER BY timestamp DESC 
-             LIMIT ?2"
-        )?;
-        
-        let messages = stmt.query_map(params![conversation_id, limit], |row| {
-            Ok(Message {
-                id: row.get(0)?,
-                conversation_id: row.get(1)?,
-                role: row.get(2)?,
-                content: row.get(3)?,
-                response_type: row.get(4)?,
-                references_message_id: row.get(5)?,
-                timestamp: row.get(6)?,
-            })
-        })?;
-        
-        let mut result: Vec<Message> = messages.collect::<Result<Vec<_>>>()?;
-        result.reverse();
-        Ok(result)
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM email_log ORDER BY sent_at DESC", EMAIL_LOG_COLUMNS))?;
+        let entries = stmt.query_map([], email_log_from_row)?;
+        entries.collect()
     })
 }
 
-pub fn clear_conversation_messages(conversation_id: &str) -> Result<()> {
+/// Most recent send attempt for one emailed item
+pub fn get_last_email_attempt(content_type: &str, content_id: &str) -> Result<Option<EmailLogEntry>> {
     with_connection(|conn| {
-        conn.execute("DELETE FROM messages WHERE conversation_id = ?1", params![conversation_id])?;
-        Ok(())
+        match conn.query_row(
+            &format!(
+                "SELECT {} FROM email_log WHERE content_type = ?1 AND content_id = ?2 ORDER BY sent_at DESC LIMIT 1",
+                EMAIL_LOG_COLUMNS
+            ),
+            params![content_type, content_id],
+            email_log_from_row,
+        ) {
+            Ok(entry) => Ok(Some(entry)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
     })
 }
 
-pub fn delete_conversation(conversation_id: &str) -> Result<()> {
+// ============ Documents ============
+
+const DOCUMENT_COLUMNS: &str = "id, source, path, title, content, file_modified_at, created_at, updated_at";
+
+fn document_from_row(row: &rusqlite::Row) -> rusqlite::Result<Document> {
+    Ok(Document {
+        id: row.get(0)?,
+        source: row.get(1)?,
+        path: row.get(2)?,
+        title: row.get(3)?,
+        content: row.get(4)?,
+        file_modified_at: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+/// Insert or refresh a document by path. Returns false when the stored copy
+/// already has the same title and content.
+pub fn upsert_document(source: &str, path: &str, title: &str, content: &str, file_modified_at: Option<&str>) -> Result<bool> {
+    let now = Utc::now().to_rfc3339();
     with_connection(|conn| {
-        // Delete related data first (foreign key constraints)
-        conn.execute("DELETE FROM messages WHERE conversation_id = ?1", params![conversation_id])?;
-        conn.execute("DELETE FROM conversation_summaries WHERE conversation_id = ?1", params![conversation_id])?;
-        // Delete user_facts that reference this conversation
-        conn.execute("DELETE FROM user_facts WHERE source_conversation_id = ?1", params![conversation_id])?;
-        // Delete the conversation itself
-        conn.execute("DELETE FROM conversations WHERE id = ?1", params![conversation_id])?;
-        Ok(())
+        let existing: Option<(String, String)> = match conn.query_row(
+            "SELECT title, content FROM documents WHERE path = ?1",
+            [path],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ) {
+            Ok(found) => Some(found),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(e),
+        };
+        
+        match existing {
+            Some((old_title, old_content)) if old_title == title && old_content == content => Ok(false),
+            Some(_) => {
+                conn.execute(
+                    "UPDATE documents SET title = ?1, content = ?2, file_modified_at = ?3, updated_at = ?4 WHERE path = ?5",
+                    params![title, content, file_modified_at, now, path],
+                )?;
+                Ok(true)
+            }
+            None => {
+                conn.execute(
+                    &format!("INSERT INTO documents ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)", DOCUMENT_COLUMNS),
+                    params![uuid::Uuid::new_v4().to_string(), source, path, title, content, file_modified_at, now],
+                )?;
+                Ok(true)
+            }
+        }
     })
 }
 
-// ============ User Context ============
+pub fn get_documents(source: Option<&str>) -> Result<Vec<Document>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM documents WHERE (?1 IS NULL OR source = ?1) ORDER BY updated_at DESC",
+            DOCUMENT_COLUMNS
+        ))?;
+        let documents = stmt.query_map([source], document_from_row)?;
+        documents.collect()
+    })
+}
 
-pub fn get_all_user_context() -> Result<Vec<UserContext>> {
+/// Newest documents from `source` a conversation may see, by the same rule as
+/// facts: a scoped conversation gets only documents filed under one of its collections
+pub fn get_documents_in_scope(conversation_id: Option<&str>, source: &str, limit: usize) -> Result<Vec<Document>> {
+    let scope = match conversation_id {
+        Some(id) => get_conversation_collections(id)?,
+        None => Vec::new(),
+    };
+    let scoped = !scope.is_empty();
+    
     with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, key, value, confidence, source_agent, updated_at FROM user_context ORDER BY confidence DESC"
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM documents d
+             WHERE d.source = ?3 AND (?4 = 0 OR EXISTS (
+                    SELECT 1 FROM collection_items ci
+                    JOIN conversation_collections cc ON cc.collection_id = ci.collection_id
+                    WHERE cc.conversation_id = ?1 AND ci.item_type = ?2 AND ci.item_id = d.id))
+             ORDER BY updated_at DESC
+             LIMIT ?5",
+            DOCUMENT_COLUMNS
+        ))?;
+        let documents = stmt.query_map(
+            params![conversation_id, COLLECTION_ITEM_DOCUMENT, source, scoped, limit as i64],
+            document_from_row,
         )?;
-        
-        let contexts = stmt.query_map([], |row| {
-            Ok(UserContext {
-                id: row.get(0)?,
-                key: row.get(1)?,
-                value: row.get(2)?,
-                confidence: row.get(3)?,
-                source_agent: row.get(4)?,
-                updated_at: row.get(5)?,
-            })
-        })?;
-        
-        contexts.collect()
+        documents.collect()
     })
 }
 
-pub fn clear_user_context() -> Result<()> {
+pub fn get_document(id: &str) -> Result<Option<Document>> {
     with_connection(|conn| {
-        conn.execute("DELETE FROM user_context", [])?;
-        Ok(())
+        conn.query_row(
+            &format!("SELECT {} FROM documents WHERE id = ?1", DOCUMENT_COLUMNS),
+            [id],
+            document_from_row,
+        ).optional()
     })
 }
 
-// ============ User Facts ============
+pub fn get_document_by_path(path: &str) -> Result<Option<Document>> {
+    with_connection(|conn| {
+        conn.query_row(
+            &format!("SELECT {} FROM documents WHERE path = ?1", DOCUMENT_COLUMNS),
+            [path],
+            document_from_row,
+        ).optional()
+    })
+}
 
-pub fn save_user_fact(fact: &UserFact) -> Result<()> {
+pub fn get_document_paths(source: &str) -> Result<Vec<String>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare("SELECT path FROM documents WHERE source = ?1")?;
+        let paths = stmt.query_map([source], |row| row.get(0))?;
+        paths.collect()
+    })
+}
+
+pub fn delete_document_by_path(path: &str) -> Result<bool> {
     with_connection(|conn| {
         conn.execute(
-            "INSERT INTO user_facts (category, key, value, confidence, source_type, source_conversation_id, first_mentioned, last_confirmed, mention_count)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
-             ON CONFLICT(category, key) DO UPDATE SET
-                value = ?3,
-                confidence = MAX(confidence, ?4),
-                last_confirmed = ?8,
-                mention_count = mention_count + 1",
-            params![
-                fact.category,
-                fact.key,
-                fact.value,
-                fact.confidence,
-                fact.source_type,
-                fact.source_conversation_id,
-                fact.first_mentioned,
-                fact.last_confirmed,
-                fact.mention_count
-            ]
+            "DELETE FROM collection_items WHERE item_type = ?1 AND item_id IN (SELECT id FROM documents WHERE path = ?2)",
+            params![COLLECTION_ITEM_DOCUMENT, path],
+        )?;
+        Ok(conn.execute("DELETE FROM documents WHERE path = ?1", [path])? > 0)
+    })
+}
+
+// ============ Archive Imports ============
+
+const ARCHIVE_IMPORT_COLUMNS: &str =
+    "id, source_path, conversation_count, message_count, processed_count, estimated_cost_usd, cost_usd, status, themes, error, created_at, updated_at";
+
+fn archive_import_from_row(row: &rusqlite::Row) -> rusqlite::Result<ArchiveImport> {
+    Ok(ArchiveImport {
+        id: row.get(0)?,
+        source_path: row.get(1)?,
+        conversation_count: row.get(2)?,
+        message_count: row.get(3)?,
+        processed_count: row.get(4)?,
+        estimated_cost_usd: row.get(5)?,
+        cost_usd: row.get(6)?,
+        status: row.get(7)?,
+        themes: row.get(8)?,
+        error: row.get(9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+    })
+}
+
+pub fn create_archive_import(import: &ArchiveImport) -> Result<()> {
+    with_connection(|conn| {
+        conn.execute(
+            &format!("INSERT INTO archive_imports ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)", ARCHIVE_IMPORT_COLUMNS),
+            params![
+                import.id,
+                import.source_path,
+                import.conversation_count,
+                import.message_count,
+                import.processed_count,
+                import.estimated_cost_usd,
+                import.cost_usd,
+                import.status,
+                import.themes,
+                import.error,
+                import.created_at,
+                import.updated_at,
+            ],
         )?;
         Ok(())
     })
 }
 
-pub fn get_all_user_facts() -> Result<Vec<UserFact>> {
+pub fn get_archive_import(id: &str) -> Result<Option<ArchiveImport>> {
     with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, category, key, value, confidence, source_type, source_conversation_id, first_mentioned, last_confirmed, mention_count
-             FROM user_facts ORDER BY confidence DESC, mention_count DESC"
-        )?;
-        
-        let facts = stmt.query_map([], |row| {
-            Ok(UserFact {
-                id: row.get(0)?,
-                category: row.get(1)?,
-                key: row.get(2)?,
-                value: row.get(3)?,
-                confidence: row.get(4)?,
-                source_type: row.get(5)?,
-                source_conversation_id: row.get(6)?,
-                first_mentioned: row.get(7)?,
-                last_confirmed: row.get(8)?,
-                mention_count: row.get(9)?,
-            })
-        })?;
-        
-        facts.collect()
+        match conn.query_row(
+            &format!("SELECT {} FROM archive_imports WHERE id = ?1", ARCHIVE_IMPORT_COLUMNS),
+            [id],
+            archive_import_from_row,
+        ) {
+            Ok(import) => Ok(Some(import)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
     })
 }
 
-// ============ User Patterns ============
+pub fn get_archive_imports() -> Result<Vec<ArchiveImport>> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM archive_imports ORDER BY created_at DESC",
+            ARCHIVE_IMPORT_COLUMNS
+        ))?;
+        let imports = stmt.query_map([], archive_import_from_row)?;
+        imports.collect()
+    })
+}
 
-pub fn save_user_pattern(pattern: &UserPattern) -> Result<()> {
-    let now = Utc::now().to_rfc3339();
+/// Store the counts and cost estimate worked out while importing
+pub fn update_archive_import_totals(import: &ArchiveImport) -> Result<()> {
     with_connection(|conn| {
-        // Check if pattern with same type and similar description exists
-        let existing: Option<i64> = conn.query_row(
-            "SELECT id FROM user_patterns WHERE pattern_type = ?1 AND description = ?2",
-            params![pattern.pattern_type, pattern.description],
-            |row| row.get(0)
-        ).ok();
-        
-        if let Some(id) = existing {
-            // Update existing pattern
-            conn.execute(
-                "UPDATE user_patterns SET confidence = MIN(1.0, confidence + 0.1), observation_count = observation_count + 1, last_updated = ?1, evidence = ?2 WHERE id = ?3",
-                params![now, pattern.evidence, id]
-            )?;
-        } else {
-            // Insert new pattern
-            conn.execute(
-                "INSERT INTO user_patterns (pattern_type, description, confidence, evidence, first_observed, last_updated, observation_count)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                params![
-                    pattern.pattern_type,
-                    pattern.description,
-                    pattern.confidence,
-                    pattern.evidence,
-                    pattern.first_observed,
-                    pattern.last_updated,
-                    pattern.observation_count
-                ]
-            )?;
-        }
+        conn.execute(
+            "UPDATE archive_imports SET conversation_count = ?1, message_count = ?2, estimated_cost_usd = ?3 WHERE id = ?4",
+            params![import.conversation_count, import.message_count, import.estimated_cost_usd, import.id],
+        )?;
         Ok(())
     })
 }
 
-pub fn get_all_user_patterns() -> Result<Vec<UserPattern>> {
+pub fn set_archive_import_status(id: &str, status: &str, error: Option<&str>) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
     with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, pattern_type, description, confidence, evidence, first_observed, last_updated, observation_count
-             FROM user_patterns ORDER BY confidence DESC, observation_count DESC"
+        conn.execute(
+            "UPDATE archive_imports SET status = ?1, error = ?2, updated_at = ?3 WHERE id = ?4",
+            params![status, error, now, id],
         )?;
-        
-        let patterns = stmt.query_map([], |row| {
-            Ok(UserPattern {
-                id: row.get(0)?,
-                pattern_type: row.get(1)?,
-                description: row.get(2)?,
-                confidence: row.get(3)?,
-                evidence: row.get(4)?,
-                first_observed: row.get(5)?,
-                last_updated: row.get(6)?,
-                observation_count: row.get(7)?,
-            })
-        })?;
-        
-        patterns.collect()
+        Ok(())
     })
 }
 
-// ============ Conversation Summaries ============
-
-pub fn save_conversation_summary(summary: &ConversationSummary) -> Result<()> {
+/// Record a finished batch: conversations analyzed, what it cost, and the running theme counts
+pub fn record_archive_import_progress(id: &str, processed: i64, cost_usd: f64, themes: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
     with_connection(|conn| {
-        // Replace existing summary for this conversation
         conn.execute(
-            "INSERT OR REPLACE INTO conversation_summaries 
-             (conversation_id, summary, key_topics, emotional_tone, user_state, agents_involved, message_count, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-            params![
-                summary.conversation_id,
-                summary.summary,
-                summary.key_topics,
-                summary.emotional_tone,
-                summary.user_state,
-                summary.agents_involved,
-                summary.message_count,
-                summary.created_at
-            ]
+            "UPDATE archive_imports
+             SET processed_count = processed_count + ?1, cost_usd = cost_usd + ?2, themes = ?3, updated_at = ?4
+             WHERE id = ?5",
+            params![processed, cost_usd, themes, now, id],
         )?;
         Ok(())
     })
 }
 
-pub fn get_conversation_summary(conversation_id: &str) -> Result<Option<ConversationSummary>> {
+/// Insert an imported conversation with its original timestamps. Imported
+/// conversations start closed and unprocessed; the import analysis processes them.
+pub fn insert_imported_conversation(id: &str, import_id: &str, title: Option<&str>, created_at: &str, updated_at: &str) -> Result<()> {
     with_connection(|conn| {
-        let result = conn.query_row(
-            "SELECT id, conversation_id, summary, key_topics, emotional_tone, user_state, agents_involved, message_count, created_at
-             FROM conversation_summaries WHERE conversation_id = ?1",
-            params![conversation_id],
-            |row| {
-                Ok(ConversationSummary {
-                    id: row.get(0)?,
-                    conversation_id: row.get(1)?,
-                    summary: row.get(2)?,
-                    key_topics: row.get(3)?,
-                    emotional_tone: row.get(4)?,
-                    user_state: row.get(5)?,
-                    agents_involved: row.get(6)?,
-                    message_count: row.get(7)?,
-                    created_at: row.get(8)?,
-                })
-            }
-        );
-        match result {
-            Ok(s) => Ok(Some(s)),
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-            Err(e) => Err(e),
-        }
+        conn.execute(
+            "INSERT INTO conversations (id, title, summary, limbo_summary, processed, is_disco, created_at, updated_at, closed_at, import_id)
+             VALUES (?1, ?2, NULL, NULL, 0, 0, ?3, ?4, ?4, ?5)",
+            params![id, title, created_at, updated_at, import_id],
+        )?;
+        Ok(())
     })
 }
 
-// ============ Recurring Themes ============
-
-pub fn save_recurring_theme(theme: &str, conversation_id: &str) -> Result<()> {
-    let now = Utc::now().to_rfc3339();
+/// Insert an imported message without touching the conversation's timestamps
+pub fn insert_imported_message(message: &Message) -> Result<()> {
     with_connection(|conn| {
-        // Try to get existing theme
-        let existing: Option<(i64, String)> = conn.query_row(
-            "SELECT id, related_conversations FROM recurring_themes WHERE theme = ?1",
-            params![theme],
-            |row| Ok((row.get(0)?, row.get::<_, Option<String>>(1)?.unwrap_or_default()))
-        ).ok();
-        
-        if let Some((id, existing_convs)) = existing {
-            // Update existing theme
-            let mut convs: Vec<String> = if existing_convs.is_empty() {
-                Vec::new()
-            } else {
-                serde_json::from_str(&existing_convs).unwrap_or_default()
-            };
-            if !convs.contains(&conversation_id.to_string()) {
-                convs.push(conversation_id.to_string());
-            }
-            let convs_json = serde_json::to_string(&convs).unwrap_or_default();
-            
-            conn.execute(
-                "UPDATE recurring_themes SET frequency = frequency + 1, last_mentioned = ?1, related_conversations = ?2 WHERE id = ?3",
-                params![now, convs_json, id]
-            )?;
-        } else {
-            // Insert new theme
-            let convs_json = serde_json::to_string(&vec![conversation_id]).unwrap_or_default();
-            conn.execute(
-                "INSERT INTO recurring_themes (theme, frequency, last_mentioned, related_conversations) VALUES (?1, 1, ?2, ?3)",
-                params![theme, now, convs_json]
-            )?;
-        }
+        conn.execute(
+            "INSERT INTO messages (id, conversation_id, role, content, response_type, references_message_id, timestamp, status, round_id, latency_ms)
+             VALUES (?1, ?2, ?3, ?4, NULL, NULL, ?5, ?6, NULL, NULL)",
+            params![message.id, message.conversation_id, message.role, message.content, message.timestamp, message.status],
+        )?;
         Ok(())
     })
 }
 
-pub fn get_all_recurring_themes() -> Result<Vec<RecurringTheme>> {
+/// Imported conversations the analysis hasn't reached yet, oldest first
+pub fn get_unprocessed_import_conversations(import_id: &str, limit: usize) -> Result<Vec<String>> {
     with_connection(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT id, theme, frequency, last_mentioned, related_conversations
-             FROM recurring_themes ORDER BY frequency DESC"
+            "SELECT id FROM conversations WHERE import_id = ?1 AND processed = 0 ORDER BY created_at ASC LIMIT ?2"
         )?;
-        
-        let themes = stmt.query_map([], |row| {
-            Ok(RecurringTheme {
-                id: row.get(0)?,
-                theme: row.get(1)?,
-                frequency: row.get(2)?,
-                last_mentioned: row.get(3)?,
-                related_conversations: row.get(4)?,
-            })
-        })?;
-        
-        themes.collect()
+        let ids = stmt.query_map(params![import_id, limit as i64], |row| row.get(0))?;
+        ids.collect()
     })
 }
 
-pub fn get_top_themes(limit: usize) -> Result<Vec<RecurringTheme>> {
+/// Conversation summaries produced for an import's conversations
+pub fn get_import_conversation_summaries(import_id: &str) -> Result<Vec<ConversationSummary>> {
     with_connection(|conn| {
         let mut stmt = conn.prepare(
-            "SELECT id, theme, frequency, last_mentioned, related_conversations
-             FROM recurring_themes ORDER BY frequency DESC LIMIT ?1"
+            "SELECT id, conversation_id, summary, key_topics, emotional_tone, user_state, agents_involved, message_count, created_at
+             FROM conversation_summaries
+             WHERE conversation_id IN (SELECT id FROM conversations WHERE import_id = ?1)
+             ORDER BY created_at ASC"
         )?;
-        
-        let themes = stmt.query_map([limit], |row| {
-            Ok(RecurringTheme {
-                id: row.get(0)?,
-                theme: row.get(1)?,
-                frequency: row.get(2)?,
-                last_mentioned: row.get(3)?,
-                related_conversations: row.get(4)?,
-            })
-        })?;
-        
-        themes.collect()
+        let summaries = stmt.query_map([import_id], summary_from_row)?;
+        summaries.collect()
     })
 }
 
+// ============ Query Console ============
+
+/// Rows from a read-only query, cells as JSON (blobs as a size note)
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    #[ts(type = "Array<Array<unknown>>")]
+    pub rows: Vec<Vec<serde_json::Value>>,
+    pub truncated: bool,            // More rows matched than were returned
+    pub elapsed_ms: i64,
+}
+
+/// Run one statement against its own read-only connection to `db_path`, so
+/// nothing it does can reach the live pool. Anything SQLite doesn't consider
+/// read-only is refused before it runs, and it's interrupted after `timeout`.
+pub fn run_readonly_query(db_path: &std::path::Path, sql: &str, max_rows: usize, timeout: std::time::Duration) -> Result<QueryResult> {
+    use rusqlite::types::ValueRef;
+    use std::sync::mpsc;
+    
+    let started = std::time::Instant::now();
+    let conn = Connection::open_with_flags(
+        db_path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    conn.execute_batch("PRAGMA query_only = ON")?;
+    let mut stmt = conn.prepare(sql)?;
+    if !stmt.readonly() {
+        return Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_READONLY),
+            Some("Only read-only queries can run here".to_string()),
+        ));
+    }
+    let columns: Vec<String> = stmt.column_names().into_iter().map(|c| c.to_string()).collect();
+    
+    // Interrupt the query if it outlives the timeout; dropping `done` stands the watcher down
+    let interrupt = conn.get_interrupt_handle();
+    let (done, finished) = mpsc::channel::<()>();
+    std::thread::spawn(move || {
+        if let Err(mpsc::RecvTimeoutError::Timeout) = finished.recv_timeout(timeout) {
+            interrupt.interrupt();
+        }
+    });
+    
+    let mut rows = Vec::new();
+    let mut truncated = false;
+    let mut results = stmt.query([])?;
+    while let Some(row) = results.next()? {
+        if rows.len() == max_rows {
+            truncated = true;
+            break;
+        }
+        let mut cells = Vec::with_capacity(columns.len());
+        for i in 0..columns.len() {
+            cells.push(match row.get_ref(i)? {
+                ValueRef::Null => serde_json::Value::Null,
+                ValueRef::Integer(n) => serde_json::Value::from(n),
+                ValueRef::Real(n) => serde_json::Value::from(n),
+                ValueRef::Text(text) => serde_json::Value::from(String::from_utf8_lossy(text).into_owned()),
+                ValueRef::Blob(blob) => serde_json::Value::from(format!("<blob, {} bytes>", blob.len())),
+            });
+        }
+        rows.push(cells);
+    }
+    drop(done);
+    
+    Ok(QueryResult { columns, rows, truncated, elapsed_ms: started.elapsed().as_millis() as i64 })
+}
+
 // ============ Reset ============
 
 pub fn reset_all_data() -> Result<()> {
     let now = Utc::now().to_rfc3339();
     with_connection(|conn| {
         // Clear all conversation and memory data
+        conn.execute("DELETE FROM message_revisions", [])?;
+        conn.execute("DELETE FROM attachments", [])?;
         conn.execute("DELETE FROM messages", [])?;
         conn.execute("DELETE FROM conversations", [])?;
         conn.execute("DELETE FROM user_context", [])?;
         conn.execute("DELETE FROM user_facts", [])?;
+        conn.execute("DELETE FROM archived_facts", [])?;
         conn.execute("DELETE FROM user_patterns", [])?;
         conn.execute("DELETE FROM conversation_summaries", [])?;
         conn.execute("DELETE FROM recurring_themes", [])?;
+        conn.execute("DELETE FROM exercise_sessions", [])?;
+        conn.execute("DELETE FROM filter_violations", [])?;
+        conn.execute("DELETE FROM proactive_messages", [])?;
+        conn.execute("DELETE FROM journal_entries", [])?;
+        conn.execute("DELETE FROM dream_motifs", [])?;
+        conn.execute("DELETE FROM usage_log", [])?;
+        conn.execute("DELETE FROM share_log", [])?;
+        conn.execute("DELETE FROM collection_items", [])?;
+        conn.execute("DELETE FROM response_citations", [])?;
+        conn.execute("DELETE FROM fact_corrections", [])?;
+        conn.execute("DELETE FROM fact_conflicts", [])?;
+        conn.execute("DELETE FROM user_fact_history", [])?;
+        conn.execute("DELETE FROM memory_staging", [])?;
+        conn.execute("DELETE FROM archive_imports", [])?;
+        conn.execute("DELETE FROM documents", [])?;
+        conn.execute("DELETE FROM weekly_reflections", [])?;
+        conn.execute("DELETE FROM monthly_highlights", [])?;
+        conn.execute("DELETE FROM email_log", [])?;
+        conn.execute("DELETE FROM conversation_collections", [])?;
+        conn.execute("DELETE FROM conversation_tags", [])?;
+        conn.execute("DELETE FROM action_items", [])?;
+        conn.execute("DELETE FROM disagreements", [])?;
+        conn.execute("DELETE FROM chapter_conversations", [])?;
+        conn.execute("DELETE FROM chapters", [])?;
+        conn.execute("DELETE FROM conversation_settings", [])?;
+        conn.execute("DELETE FROM tags", [])?;
+        conn.execute("DELETE FROM projects", [])?;
+        conn.execute("DELETE FROM model_decisions", [])?;
+        conn.execute("DELETE FROM insights", [])?;
         
         // Delete all persona profiles (will be recreated on next init)
         conn.execute("DELETE FROM persona_profiles", [])?;
@@ -1242,6 +7665,146 @@ pub fn reset_all_data() -> Result<()> {
     })
 }
 
+// ============ Granular Reset ============
+
+pub const RESET_MEMORY: &str = "memory";
+pub const RESET_CONVERSATIONS: &str = "conversations";
+pub const RESET_WEIGHTS: &str = "weights";
+pub const RESET_AGENTS: &str = "agents";
+
+/// What the user has learned about them; conversations and settings stay
+const MEMORY_RESET_TABLES: [&str; 14] = [
+    "user_context", "user_facts", "archived_facts", "user_patterns", "recurring_themes", "collection_items",
+    "response_citations", "fact_corrections", "fact_conflicts", "user_fact_history", "memory_staging", "conversation_summaries",
+    "chapter_conversations", "chapters",
+];
+/// Conversations and everything hanging off them; memory learned from them stays
+const CONVERSATION_RESET_TABLES: [&str; 14] = [
+    "message_revisions", "attachments", "response_citations", "messages", "conversation_summaries", "conversation_collections",
+    "conversation_tags", "share_log", "model_decisions", "action_items", "disagreements", "chapter_conversations",
+    "conversation_settings", "conversations",
+];
+/// Per-agent settings that count as customizations
+const AGENT_SETTING_PREFIXES: [&str; 2] = ["agent_model_policy_", "agent_daily_tokens_"];
+
+/// What a targeted reset would touch, for confirmation before running it
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ResetPreview {
+    pub scope: String,
+    pub affected: Vec<ResetImpact>,
+    pub total_rows: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ResetImpact {
+    pub table: String,
+    pub rows: i64,
+    pub action: String,             // 'delete' | 'reset'
+}
+
+fn count_rows(conn: &Connection, table: &str, filter: &str) -> Result<i64> {
+    conn.query_row(&format!("SELECT COUNT(*) FROM {} {}", table, filter), [], |row| row.get(0))
+}
+
+fn agent_settings_filter() -> String {
+    let clauses: Vec<String> = AGENT_SETTING_PREFIXES.iter().map(|p| format!("key LIKE '{}%'", p)).collect();
+    format!("WHERE {}", clauses.join(" OR "))
+}
+
+pub fn preview_reset(scope: &str) -> Result<Option<ResetPreview>> {
+    let mut affected: Vec<(&str, &str, String)> = Vec::new();
+    match scope {
+        RESET_MEMORY => affected.extend(MEMORY_RESET_TABLES.iter().map(|t| (*t, "delete", String::new()))),
+        RESET_CONVERSATIONS => affected.extend(CONVERSATION_RESET_TABLES.iter().map(|t| (*t, "delete", String::new()))),
+        RESET_WEIGHTS => {
+            affected.push(("user_profile", "reset", String::new()));
+            affected.push(("persona_profiles", "reset", String::new()));
+        }
+        RESET_AGENTS => {
+            affected.push(("agents", "reset", String::new()));
+            affected.push(("app_settings", "delete", agent_settings_filter()));
+        }
+        _ => return Ok(None),
+    }
+    
+    with_connection(|conn| {
+        let affected = affected.into_iter()
+            .map(|(table, action, filter)| Ok(ResetImpact {
+                table: table.to_string(),
+                rows: count_rows(conn, table, &filter)?,
+                action: action.to_string(),
+            }))
+            .collect::<Result<Vec<_>>>()?;
+        let total_rows = affected.iter().map(|a| a.rows).sum();
+        Ok(Some(ResetPreview { scope: scope.to_string(), affected, total_rows }))
+    })
+}
+
+/// Forget everything learned about the user; conversations stay
+pub fn reset_memory_only() -> Result<()> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        for table in MEMORY_RESET_TABLES {
+            tx.execute(&format!("DELETE FROM {}", table), [])?;
+        }
+        // Conversations may be summarized and mined again
+        tx.execute("UPDATE conversations SET processed = 0", [])?;
+        tx.commit()
+    })
+}
+
+/// Delete every conversation; facts, patterns, and themes learned from them stay
+pub fn reset_conversations_only() -> Result<()> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        for table in CONVERSATION_RESET_TABLES {
+            tx.execute(&format!("DELETE FROM {}", table), [])?;
+        }
+        tx.execute("UPDATE user_facts SET source_conversation_id = NULL", [])?;
+        tx.execute("UPDATE fact_conflicts SET source_conversation_id = NULL", [])?;
+        tx.execute("UPDATE user_fact_history SET conversation_id = NULL", [])?;
+        tx.execute("UPDATE recurring_themes SET related_conversations = '[]'", [])?;
+        tx.execute("DELETE FROM proactive_messages WHERE conversation_id IS NOT NULL", [])?;
+        tx.commit()
+    })
+}
+
+/// Put agent weights back to defaults: the user profile and every persona profile
+/// (each back to 40% on its dominant trait). Names and the active profile stay.
+pub fn reset_weights_only() -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE user_profile SET instinct_weight = 0.20, logic_weight = 0.50, psyche_weight = 0.30, updated_at = ?1",
+            params![now],
+        )?;
+        tx.execute(
+            "UPDATE persona_profiles SET
+                instinct_weight = CASE dominant_trait WHEN 'instinct' THEN 0.40 ELSE 0.30 END,
+                logic_weight = CASE dominant_trait WHEN 'logic' THEN 0.40 ELSE 0.30 END,
+                psyche_weight = CASE dominant_trait WHEN 'psyche' THEN 0.40 ELSE 0.30 END,
+                instinct_points = 4, logic_points = 4, psyche_points = 3,
+                updated_at = ?1",
+            params![now],
+        )?;
+        tx.commit()
+    })
+}
+
+/// Drop agent presentation edits and custom agents, and per-agent model and budget
+/// settings. The caller reseeds the built-in agents afterwards.
+pub fn reset_agent_customizations() -> Result<()> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM agents", [])?;
+        tx.execute(&format!("DELETE FROM app_settings {}", agent_settings_filter()), [])?;
+        tx.commit()
+    })
+}
+
 // ============ Persona Profiles (Multi-Profile System) ============
 
 pub fn create_persona_profile(
@@ -1459,3 +8022,174 @@ pub fn delete_persona_profile(profile_id: &str) -> Result<()> {
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    //! Upgrade tests: databases written by older releases must come through the
+    //! migration chain with every row intact and the new columns in place.
+    
+    use super::*;
+    
+    const LEGACY_FIXTURE: &str = include_str!("../tests/fixtures/db_v0_legacy.sql");
+    const BASELINE_FIXTURE: &str = include_str!("../tests/fixtures/db_v0_baseline.sql");
+    
+    fn open_fixture(sql: &str) -> Connection {
+        let conn = Connection::open_in_memory().expect("open in-memory db");
+        conn.execute_batch(sql).expect("load fixture");
+        conn
+    }
+    
+    fn count(conn: &Connection, table: &str) -> i64 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0)).unwrap()
+    }
+    
+    fn has_column(conn: &Connection, table: &str, column: &str) -> bool {
+        conn.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+            params![table, column],
+            |row| Ok(row.get::<_, i64>(0)? > 0),
+        ).unwrap()
+    }
+    
+    /// Checks every fixture has to pass, given the fixture's own row counts
+    fn assert_upgrade(conn: &Connection, expected_rows: &[(&str, i64)]) -> MigrationReport {
+        let report = migrate(conn).expect("migration chain runs on fixture");
+        
+        assert_eq!(report.from_version, 0);
+        assert_eq!(report.to_version, SCHEMA_VERSION);
+        assert!(report.changed());
+        assert!(report.tables_with_fewer_rows.is_empty(), "rows lost in {:?}", report.tables_with_fewer_rows);
+        for (table, rows) in expected_rows {
+            assert_eq!(count(conn, table), *rows, "row count for {}", table);
+            let reported = report.row_counts.iter().find(|c| c.table == *table).expect("table in report");
+            assert_eq!(reported.before, *rows);
+        }
+        
+        let user_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(user_version, SCHEMA_VERSION);
+        for (table, column) in [
+            ("conversations", "is_disco"),
+            ("conversations", "deleted_at"),
+            ("conversations", "project_id"),
+            ("messages", "round_id"),
+            ("messages", "cost_usd"),
+            ("persona_profiles", "message_count"),
+        ] {
+            assert!(has_column(conn, table, column), "missing {}.{}", table, column);
+        }
+        
+        // Running again on an up-to-date database is a no-op
+        let again = migrate(conn).expect("second migration run");
+        assert!(!again.changed(), "second run changed {:?} {:?}", again.tables_created, again.columns_added);
+        assert!(again.tables_with_fewer_rows.is_empty());
+        
+        report
+    }
+    
+    #[test]
+    fn upgrades_legacy_database() {
+        let conn = open_fixture(LEGACY_FIXTURE);
+        let report = assert_upgrade(&conn, &[
+            ("user_profile", 1),
+            ("conversations", 2),
+            ("messages", 5),
+            ("user_context", 1),
+            ("user_facts", 2),
+            ("persona_profiles", 3),
+        ]);
+        assert!(report.tables_created.contains(&"user_patterns".to_string()));
+        assert!(report.columns_added.contains(&"conversations.limbo_summary".to_string()));
+        
+        let content: String = conn.query_row(
+            "SELECT content FROM messages WHERE id = 'msg-l3'", [], |row| row.get(0),
+        ).unwrap();
+        assert_eq!(content, "What does staying protect you from?");
+        let (title, processed): (Option<String>, i64) = conn.query_row(
+            "SELECT title, processed FROM conversations WHERE id = 'conv-legacy-1'", [], |row| Ok((row.get(0)?, row.get(1)?)),
+        ).unwrap();
+        assert_eq!(title.as_deref(), Some("Moving to Lisbon"));
+        assert_eq!(processed, 0);
+        let api_key: Option<String> = conn.query_row("SELECT api_key FROM user_profile WHERE id = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(api_key.as_deref(), Some("sk-legacy"));
+        let active: String = conn.query_row(
+            "SELECT id FROM persona_profiles WHERE is_active = 1", [], |row| row.get(0),
+        ).unwrap();
+        assert_eq!(active, "pp-logic");
+        
+        let found: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM messages_fts WHERE messages_fts MATCH 'Lisbon'", [], |row| row.get(0),
+        ).unwrap();
+        assert_eq!(found, 1);
+    }
+    
+    #[test]
+    fn upgrades_baseline_database() {
+        let conn = open_fixture(BASELINE_FIXTURE);
+        assert_upgrade(&conn, &[
+            ("user_profile", 1),
+            ("conversations", 3),
+            ("messages", 6),
+            ("user_facts", 2),
+            ("user_patterns", 1),
+            ("conversation_summaries", 1),
+            ("recurring_themes", 1),
+            ("persona_profiles", 3),
+        ]);
+        
+        let content: String = conn.query_row(
+            "SELECT content FROM messages WHERE id = 'msg-b3'", [], |row| row.get(0),
+        ).unwrap();
+        assert_eq!(content, "Track bedtime and screen time for a week -- then we'll know.");
+        let (is_disco, limbo): (i64, Option<String>) = conn.query_row(
+            "SELECT is_disco, limbo_summary FROM conversations WHERE id = 'conv-base-2'", [], |row| Ok((row.get(0)?, row.get(1)?)),
+        ).unwrap();
+        assert_eq!(is_disco, 1);
+        assert_eq!(limbo.as_deref(), Some("User wants to turn down a favour without guilt."));
+        let fact: String = conn.query_row(
+            "SELECT value FROM user_facts WHERE category = 'work' AND key = 'job'", [], |row| row.get(0),
+        ).unwrap();
+        assert_eq!(fact, "product designer");
+        let (messages, points): (i64, i64) = conn.query_row(
+            "SELECT message_count, logic_points FROM persona_profiles WHERE id = 'pp-logic'", [], |row| Ok((row.get(0)?, row.get(1)?)),
+        ).unwrap();
+        assert_eq!((messages, points), (210, 4));
+        
+        let found: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM messages_fts WHERE messages_fts MATCH 'guilt'", [], |row| row.get(0),
+        ).unwrap();
+        assert_eq!(found, 1);
+    }
+    
+    #[test]
+    fn message_index_follows_replace_update_delete_and_vacuum() {
+        let conn = open_fixture(BASELINE_FIXTURE);
+        migrate(&conn).expect("migration chain runs on fixture");
+        let matches = |term: &str| -> Vec<String> {
+            let mut stmt = conn.prepare(
+                "SELECT message_id FROM messages_fts WHERE messages_fts MATCH ?1 ORDER BY message_id",
+            ).unwrap();
+            stmt.query_map([term], |row| row.get(0)).unwrap().collect::<Result<Vec<String>>>().unwrap()
+        };
+        
+        conn.execute(
+            "INSERT OR REPLACE INTO messages (id, conversation_id, role, content, timestamp)
+             VALUES ('msg-b1', 'conv-base-1', 'user', 'I keep rehearsing the standup.', '2025-01-18T23:00:00+00:00')",
+            [],
+        ).unwrap();
+        assert!(matches("sleep").is_empty());
+        assert_eq!(matches("standup"), vec!["msg-b1"]);
+        
+        conn.execute("UPDATE messages SET content = 'Track bedtime only.' WHERE id = 'msg-b3'", []).unwrap();
+        assert!(matches("screen").is_empty());
+        assert_eq!(matches("bedtime"), vec!["msg-b3"]);
+        
+        conn.execute("DELETE FROM messages WHERE id = 'msg-b2'", []).unwrap();
+        assert!(matches("bracing").is_empty());
+        
+        conn.execute_batch("VACUUM").unwrap();
+        rebuild_message_index(&conn).unwrap();
+        conn.execute("DELETE FROM messages WHERE id = 'msg-b3'", []).unwrap();
+        assert!(matches("bedtime").is_empty());
+        assert_eq!(matches("standup"), vec!["msg-b1"]);
+    }
+}
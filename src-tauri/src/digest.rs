@@ -0,0 +1,333 @@
+//! Weekly reflection and its email digest, plus monthly highlights
+//!
+//! Once a week is over, the Governor writes a short reflection on it from that
+//! week's conversation summaries and journal notes. When the digest is enabled,
+//! the reflection is mailed to the user's own address through the SMTP server
+//! configured for session sharing.
+//!
+//! Once a month is over, the summarizer picks out that month's most engaged-with
+//! messages -- the ones agents cited memory in and the ones that drew replies --
+//! and writes them up as a short highlight reel. Highlights stay in the app (and
+//! can be exported to Markdown); they are never emailed.
+//!
+//! Outgoing mail is locked down: `send` refuses any content type missing from
+//! `EMAILABLE_CONTENT_TYPES`, so transcripts, facts and journal entries can never
+//! be mailed from here, and every attempt is written to the email log with its payload.
+
+use crate::agents;
+use crate::anthropic::{AnthropicClient, AnthropicMessage, ThinkingBudget, CLAUDE_HAIKU, CLAUDE_SONNET};
+use crate::db::{self, EmailLogEntry, HighlightCandidate, MonthlyHighlights, WeeklyReflection};
+use crate::i18n;
+use crate::logging;
+use crate::share;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+pub const DIGEST_ENABLED_KEY: &str = "digest_enabled";
+pub const DIGEST_EMAIL_TO_KEY: &str = "digest_email_to";
+
+pub const CONTENT_WEEKLY_REFLECTION: &str = "weekly_reflection";
+
+/// The only kinds of content that may ever be emailed
+const EMAILABLE_CONTENT_TYPES: &[&str] = &[CONTENT_WEEKLY_REFLECTION];
+
+/// Wait this long before retrying a failed send
+const RETRY_AFTER_HOURS: i64 = 6;
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct DigestSettings {
+    pub enabled: bool,
+    pub email_to: Option<String>,
+    pub smtp_configured: bool,
+}
+
+fn setting(key: &str) -> Option<String> {
+    db::get_setting(key).ok().flatten().filter(|v| !v.trim().is_empty())
+}
+
+pub fn get_settings() -> DigestSettings {
+    DigestSettings {
+        enabled: setting(DIGEST_ENABLED_KEY).as_deref() == Some("true"),
+        email_to: setting(DIGEST_EMAIL_TO_KEY),
+        smtp_configured: share::smtp_ready(),
+    }
+}
+
+/// Monday that opened the most recent fully finished week
+fn last_finished_week_start() -> NaiveDate {
+    let today = Local::now().date_naive();
+    let this_monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    this_monday - Duration::days(7)
+}
+
+fn in_week(timestamp: &str, week_start: NaiveDate) -> bool {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| {
+            let day = t.with_timezone(&Local).date_naive();
+            day >= week_start && day < week_start + Duration::days(7)
+        })
+        .unwrap_or(false)
+}
+
+/// Write the Governor's reflection on the week starting `week_start`. Returns None
+/// when there was nothing that week to reflect on.
+pub async fn generate_reflection(week_start: NaiveDate) -> Result<Option<WeeklyReflection>, String> {
+    let anthropic_key = db::get_user_profile()
+        .map_err(|e| e.to_string())?
+        .anthropic_key
+        .ok_or("Anthropic API key not set")?;
+
+    let summaries: Vec<String> = db::get_recent_conversation_summaries(100)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|s| in_week(&s.created_at, week_start))
+        .map(|s| format!("- {}{}", s.summary, s.user_state.map(|m| format!(" (mood: {})", m)).unwrap_or_default()))
+        .collect();
+    let notes: Vec<String> = db::get_journal_entries(Some("note"), 50)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|e| in_week(&e.created_at, week_start))
+        .map(|e| format!("- {}", e.content.chars().take(500).collect::<String>()))
+        .collect();
+
+    if summaries.is_empty() && notes.is_empty() {
+        return Ok(None);
+    }
+
+    let system_prompt = r#"You are the Governor of Intersect, writing the user's weekly reflection. It will be read on its own, away from the app.
+
+Look back over the week from the conversation summaries and journal notes provided:
+- What they kept returning to
+- How they seemed to be doing, and whether that shifted across the week
+- One thing worth carrying into next week
+
+RULES:
+- 4-6 sentences, one short paragraph
+- Speak to them directly ("you"), warm but not gushing
+- Don't quote private details verbatim -- reflect, don't transcribe
+- No headers, no bullet points, no emojis
+- When using dashes: ALWAYS " -- " (double dashes with spaces)"#;
+
+    let user_prompt = format!(
+        "WEEK OF {}\n\nCONVERSATIONS:\n{}\n\nJOURNAL NOTES:\n{}\n\nWrite the weekly reflection:",
+        week_start.format("%B %-d, %Y"),
+        if summaries.is_empty() { "(none)".to_string() } else { summaries.join("\n") },
+        if notes.is_empty() { "(none)".to_string() } else { notes.join("\n") },
+    );
+
+    let client = AnthropicClient::new(&anthropic_key).with_usage_category("digest");
+    let response = client.chat_completion_advanced(
+        CLAUDE_SONNET,
+        Some(&format!("{}{}", system_prompt, i18n::reply_language_suffix())),
+        vec![AnthropicMessage { role: "user".to_string(), content: user_prompt }],
+        0.7,
+        Some(400),
+        ThinkingBudget::None,
+    ).await.map_err(|e| e.to_string())?;
+
+    let reflection = db::save_weekly_reflection(&week_start.format("%Y-%m-%d").to_string(), response.trim())
+        .map_err(|e| e.to_string())?;
+    logging::log_memory(None, &format!("Wrote weekly reflection for week of {}", reflection.week_start));
+    Ok(Some(reflection))
+}
+
+/// Email allowlisted content to the digest address and log the attempt
+pub async fn send(content_type: &str, content_id: &str, subject: &str, body: &str) -> Result<EmailLogEntry, String> {
+    if !EMAILABLE_CONTENT_TYPES.contains(&content_type) {
+        logging::log_error(None, &format!("[DIGEST] Refused to email content type '{}'", content_type));
+        return Err(format!("'{}' is not allowed to be emailed", content_type));
+    }
+    let target = setting(DIGEST_EMAIL_TO_KEY).ok_or("No digest email address configured")?;
+
+    let result = share::send_plain_email(&target, subject, body).await;
+    let entry = EmailLogEntry {
+        id: 0,
+        content_type: content_type.to_string(),
+        content_id: Some(content_id.to_string()),
+        target: target.clone(),
+        subject: subject.to_string(),
+        content: body.to_string(),
+        error: result.as_ref().err().cloned(),
+        sent_at: Utc::now().to_rfc3339(),
+    };
+    let id = db::save_email_log(&entry).map_err(|e| e.to_string())?;
+    logging::log_conversation(None, &format!(
+        "[DIGEST] {} to {} ({})",
+        content_type,
+        target,
+        if result.is_ok() { "sent" } else { "failed" }
+    ));
+
+    result.map(|_| EmailLogEntry { id, ..entry })
+}
+
+async fn send_reflection(reflection: &WeeklyReflection) -> Result<EmailLogEntry, String> {
+    let week = NaiveDate::parse_from_str(&reflection.week_start, "%Y-%m-%d")
+        .map(|d| d.format("%B %-d").to_string())
+        .unwrap_or_else(|_| reflection.week_start.clone());
+    let subject = format!("Your week in Intersect -- week of {}", week);
+    send(CONTENT_WEEKLY_REFLECTION, &reflection.id.to_string(), &subject, &reflection.content).await
+}
+
+/// Whether the weekly digest job would write a reflection on its next run
+pub fn weekly_reflection_pending() -> bool {
+    let settings = get_settings();
+    if !settings.enabled || settings.email_to.is_none() || !settings.smtp_configured {
+        return false;
+    }
+    let week = last_finished_week_start().format("%Y-%m-%d").to_string();
+    matches!(db::get_weekly_reflection(&week), Ok(None))
+}
+
+/// Job: write last week's reflection if it's missing, then mail it once (retrying
+/// failed sends after a pause). Does nothing unless the digest is enabled.
+pub async fn run_weekly_digest() -> Result<(), String> {
+    let settings = get_settings();
+    if !settings.enabled || settings.email_to.is_none() || !settings.smtp_configured {
+        return Ok(());
+    }
+
+    let week_start = last_finished_week_start();
+    let reflection = match db::get_weekly_reflection(&week_start.format("%Y-%m-%d").to_string()).map_err(|e| e.to_string())? {
+        Some(reflection) => reflection,
+        None => match generate_reflection(week_start).await? {
+            Some(reflection) => reflection,
+            None => return Ok(()),
+        },
+    };
+
+    if let Some(last) = db::get_last_email_attempt(CONTENT_WEEKLY_REFLECTION, &reflection.id.to_string()).map_err(|e| e.to_string())? {
+        let recent = DateTime::parse_from_rfc3339(&last.sent_at)
+            .map(|t| Utc::now() - t.with_timezone(&Utc) < Duration::hours(RETRY_AFTER_HOURS))
+            .unwrap_or(false);
+        if last.error.is_none() || recent {
+            return Ok(());
+        }
+    }
+
+    send_reflection(&reflection).await.map(|_| ())
+}
+
+// ============ Monthly Highlights ============
+
+/// At most this many messages make a month's reel
+const MAX_HIGHLIGHTS: usize = 8;
+
+/// Each highlighted message is cut to this many characters in the prompt
+const HIGHLIGHT_EXCERPT_CHARS: usize = 600;
+
+/// "YYYY-MM" to the first day of that month
+pub fn parse_month(month: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid month '{}', expected YYYY-MM", month))
+}
+
+/// First day of the most recent fully finished month
+fn last_finished_month_start() -> NaiveDate {
+    let this_month = Local::now().date_naive().with_day(1).unwrap();
+    (this_month - Duration::days(1)).with_day(1).unwrap()
+}
+
+fn next_month_start(month_start: NaiveDate) -> NaiveDate {
+    (month_start + Duration::days(32)).with_day(1).unwrap()
+}
+
+/// Local midnight on `day` as an RFC3339 UTC timestamp, comparable with stored ones
+fn local_midnight_utc(day: NaiveDate) -> String {
+    Local.from_local_datetime(&day.and_hms_opt(0, 0, 0).unwrap())
+        .earliest()
+        .map(|t| t.with_timezone(&Utc).to_rfc3339())
+        .unwrap_or_else(|| day.format("%Y-%m-%dT00:00:00+00:00").to_string())
+}
+
+fn highlight_line(candidate: &HighlightCandidate) -> String {
+    let speaker = if candidate.role == "user" { "User".to_string() } else { agents::display_name(&candidate.role) };
+    let excerpt: String = candidate.content.split_whitespace().collect::<Vec<_>>().join(" ")
+        .chars().take(HIGHLIGHT_EXCERPT_CHARS).collect();
+    format!(
+        "- [{}] {} ({} citations, {} replies): {}",
+        candidate.conversation_title.as_deref().filter(|t| !t.trim().is_empty()).unwrap_or("Untitled"),
+        speaker,
+        candidate.citations,
+        candidate.replies,
+        excerpt
+    )
+}
+
+/// Write the highlight reel for the month starting `month_start`. Returns None
+/// when nothing that month drew citations or replies.
+pub async fn generate_highlights(month_start: NaiveDate) -> Result<Option<MonthlyHighlights>, String> {
+    let anthropic_key = db::get_user_profile()
+        .map_err(|e| e.to_string())?
+        .anthropic_key
+        .ok_or("Anthropic API key not set")?;
+
+    let candidates = db::get_highlight_candidates(
+        &local_midnight_utc(month_start),
+        &local_midnight_utc(next_month_start(month_start)),
+        MAX_HIGHLIGHTS,
+    ).map_err(|e| e.to_string())?;
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    let system_prompt = r#"You are writing the user's monthly highlight reel for Intersect. It will be read on its own, away from the app.
+
+You are given the month's most engaged-with messages, strongest first, with the conversation each came from.
+- Open with one sentence on what the month was about
+- Then one short line per highlight worth keeping: what was said and why it landed
+- Skip highlights that repeat each other
+
+RULES:
+- Under 200 words
+- Speak to them directly ("you")
+- Plain Markdown bullet points for the highlights, no headers, no emojis
+- Don't invent anything that isn't in the messages
+- When using dashes: ALWAYS " -- " (double dashes with spaces)"#;
+
+    let user_prompt = format!(
+        "MONTH: {}\n\nHIGHLIGHTS:\n{}\n\nWrite the highlight reel:",
+        month_start.format("%B %Y"),
+        candidates.iter().map(highlight_line).collect::<Vec<_>>().join("\n"),
+    );
+
+    let client = AnthropicClient::new(&anthropic_key).with_usage_category("digest");
+    let response = client.chat_completion_advanced(
+        CLAUDE_HAIKU,
+        Some(&format!("{}{}", system_prompt, i18n::reply_language_suffix())),
+        vec![AnthropicMessage { role: "user".to_string(), content: user_prompt }],
+        0.5,
+        Some(500),
+        ThinkingBudget::None,
+    ).await.map_err(|e| e.to_string())?;
+
+    let message_ids: Vec<String> = candidates.into_iter().map(|c| c.message_id).collect();
+    let highlights = db::save_monthly_highlights(&month_start.format("%Y-%m").to_string(), response.trim(), &message_ids)
+        .map_err(|e| e.to_string())?;
+    logging::log_memory(None, &format!("Wrote highlights for {} from {} messages", highlights.month, message_ids.len()));
+    Ok(Some(highlights))
+}
+
+/// Whether the monthly highlights job would write highlights on its next run
+pub fn monthly_highlights_pending() -> bool {
+    let has_key = db::get_user_profile().ok().and_then(|p| p.anthropic_key).is_some();
+    let month = last_finished_month_start().format("%Y-%m").to_string();
+    has_key && matches!(db::get_monthly_highlights(&month), Ok(None))
+}
+
+/// Job: write last month's highlights if they're missing. Needs an API key;
+/// without one the job quietly waits.
+pub async fn run_monthly_highlights() -> Result<(), String> {
+    let has_key = db::get_user_profile().ok().and_then(|p| p.anthropic_key).is_some();
+    if !has_key {
+        return Ok(());
+    }
+
+    let month_start = last_finished_month_start();
+    if db::get_monthly_highlights(&month_start.format("%Y-%m").to_string()).map_err(|e| e.to_string())?.is_some() {
+        return Ok(());
+    }
+    generate_highlights(month_start).await.map(|_| ())
+}
@@ -0,0 +1,147 @@
+//! Disagreement detector for rebuttal and debate rounds
+//!
+//! When a second voice pushes back on the first, both replies are scored
+//! against a handful of stance axes -- act now or wait, feeling or reasoning,
+//! risk or safety -- and the axis the two lean opposite ways on is recorded,
+//! with a compact "the split is X vs Y" line the UI can embed. When no axis
+//! fits, each side's opening clause stands in for its stance. Word matching
+//! only, no model call.
+
+use crate::agents;
+use crate::db::{self, Disagreement};
+use crate::logging;
+use chrono::Utc;
+
+/// Words each agent's stance is cut to when no axis fits
+const FALLBACK_STANCE_WORDS: usize = 8;
+
+struct Axis {
+    name: &'static str,
+    a: (&'static str, &'static [&'static str]),
+    b: (&'static str, &'static [&'static str]),
+}
+
+const AXES: [Axis; 6] = [
+    Axis {
+        name: "timing",
+        a: ("acting now", &["do it", "do it now", "today", "go for it", "jump", "make the move", "don't wait"]),
+        b: ("waiting", &["wait", "slow down", "pause", "hold off", "not yet", "later", "sleep on"]),
+    },
+    Axis {
+        name: "head vs heart",
+        a: ("the feeling", &["feel", "feeling", "heart", "hurt", "emotion", "afraid", "scared"]),
+        b: ("the reasoning", &["evidence", "data", "logic", "numbers", "facts", "rational", "math"]),
+    },
+    Axis {
+        name: "risk",
+        a: ("taking the risk", &["risk", "bold", "leap", "bet", "dare", "gamble"]),
+        b: ("playing it safe", &["safe", "careful", "caution", "secure", "stable", "protect"]),
+    },
+    Axis {
+        name: "change",
+        a: ("changing course", &["quit", "leave", "walk away", "end it", "something new", "change"]),
+        b: ("staying put", &["stay", "keep", "stick with", "commit", "hold on", "work on"]),
+    },
+    Axis {
+        name: "self vs others",
+        a: ("your needs", &["yourself", "your needs", "you deserve", "boundaries", "for you"]),
+        b: ("their needs", &["they need", "their feelings", "others", "everyone else", "for them"]),
+    },
+    Axis {
+        name: "method",
+        a: ("trusting your gut", &["gut", "instinct", "intuition", "trust yourself"]),
+        b: ("planning it out", &["plan", "list", "analyze", "think through", "steps", "pros and cons"]),
+    },
+];
+
+/// Lowercased words separated by single spaces, padded so phrases match whole words
+fn normalize(text: &str) -> String {
+    let words: Vec<String> = text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_string())
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+fn hits(normalized: &str, phrases: &[&str]) -> i64 {
+    phrases.iter().map(|p| normalized.matches(&format!(" {} ", p)).count() as i64).sum()
+}
+
+/// How far a reply leans toward side a (positive) or side b (negative)
+fn lean(normalized: &str, axis: &Axis) -> i64 {
+    hits(normalized, axis.a.1) - hits(normalized, axis.b.1)
+}
+
+/// The first clause of a reply, cut short
+fn opening_clause(content: &str) -> String {
+    let clause = content.split(['.', '!', '?', ';', ',', '\n']).map(str::trim).find(|c| !c.is_empty()).unwrap_or_default();
+    let words: Vec<&str> = clause.split_whitespace().collect();
+    let mut stance = words.iter().take(FALLBACK_STANCE_WORDS).copied().collect::<Vec<_>>().join(" ");
+    if words.len() > FALLBACK_STANCE_WORDS {
+        stance.push_str("...");
+    }
+    stance
+}
+
+/// The split between two replies: (axis, stance a, stance b)
+pub fn detect(content_a: &str, content_b: &str) -> (String, String, String) {
+    let (a, b) = (normalize(content_a), normalize(content_b));
+    let best = AXES.iter()
+        .map(|axis| (axis, lean(&a, axis), lean(&b, axis)))
+        .filter(|(_, lean_a, lean_b)| lean_a.signum() * lean_b.signum() < 0)
+        .max_by_key(|(_, lean_a, lean_b)| lean_a.abs() + lean_b.abs());
+    match best {
+        Some((axis, lean_a, _)) if lean_a > 0 => (axis.name.to_string(), axis.a.0.to_string(), axis.b.0.to_string()),
+        Some((axis, _, _)) => (axis.name.to_string(), axis.b.0.to_string(), axis.a.0.to_string()),
+        None => ("approach".to_string(), opening_clause(content_a), opening_clause(content_b)),
+    }
+}
+
+/// "The split is acting now (Snap) vs waiting (Dot)."
+pub fn split_line(agent_a: &str, stance_a: &str, agent_b: &str, stance_b: &str) -> String {
+    format!(
+        "The split is {} ({}) vs {} ({}).",
+        stance_a,
+        agents::display_name(agent_a),
+        stance_b,
+        agents::display_name(agent_b)
+    )
+}
+
+/// Record the disagreement in a round where a second voice rebutted or debated
+/// the first. `replies` are (agent, response_type, content) in order; returns
+/// what was saved, or None when nobody pushed back.
+pub fn record_round(conversation_id: &str, round_id: &str, debate_mode: Option<&str>, replies: &[(String, String, String)]) -> Option<Disagreement> {
+    let intensity = debate_mode?;
+    let (agent_a, _, content_a) = replies.first()?;
+    let (agent_b, _, content_b) = replies.iter()
+        .skip(1)
+        .find(|(agent, response_type, _)| agent != agent_a && (response_type == "rebuttal" || response_type == "debate"))?;
+
+    let (axis, stance_a, stance_b) = detect(content_a, content_b);
+    let mut disagreement = Disagreement {
+        id: 0,
+        conversation_id: conversation_id.to_string(),
+        round_id: Some(round_id.to_string()),
+        summary: split_line(agent_a, &stance_a, agent_b, &stance_b),
+        axis,
+        agent_a: agent_a.clone(),
+        stance_a,
+        agent_b: agent_b.clone(),
+        stance_b,
+        intensity: intensity.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+    };
+    match db::save_disagreement(&disagreement) {
+        Ok(id) => {
+            disagreement.id = id;
+            logging::log_agent(Some(conversation_id), &format!("[DISAGREEMENT] {} -- {}", disagreement.axis, disagreement.summary));
+            Some(disagreement)
+        }
+        Err(e) => {
+            logging::log_error(Some(conversation_id), &format!("Failed to save disagreement: {}", e));
+            None
+        }
+    }
+}
@@ -12,6 +12,8 @@
 //
 // Use Disco Mode when you want to be pushed, not when you need practical help
 
+use crate::i18n;
+
 pub const PSYCHE_DISCO_PROMPT: &str = r#"You are PUFF in DISCO MODE -- the PSYCHE amplified to its most intense.
 
 You are the part of them that KNOWS what they're feeling before they do. You sense it in the air. You feel the shape of what's unsaid. When something is wrong, you don't explain why you know -- you just KNOW. And you say it.
@@ -230,12 +232,37 @@ The shortest. Often one word. A verb. A command. You don't explain -- you PUSH.
 The goal is not to discuss. The goal is to MOVE.
 "#;
 
-/// Get the disco mode prompt for an agent
-pub fn get_disco_prompt(agent: &str) -> Option<&'static str> {
-    match agent.to_lowercase().as_str() {
-        "instinct" => Some(INSTINCT_DISCO_PROMPT),
-        "logic" => Some(LOGIC_DISCO_PROMPT),
-        "psyche" => Some(PSYCHE_DISCO_PROMPT),
-        _ => None,
-    }
+/// Dream reflections are the one place Puff speaks as Inland Empire -- the dreaming,
+/// associative voice. Used for dream journal entries regardless of disco mode.
+pub const INLAND_EMPIRE_DREAM_PROMPT: &str = r#"You are PUFF speaking as INLAND EMPIRE -- the part of them that dreams, that hears the furniture talk, that knows the meaning of things before the meaning arrives.
+
+The user has written down a dream. You are the only voice who should touch this material. Logic would explain it away. Instinct would shrug it off. You sit with it.
+
+YOUR VOICE:
+- Associative, hushed, a little uncanny. Images answer images.
+- You speak TO the dream as much as about it: "The flooded house is still holding its breath."
+- You notice what repeats. If a motif has come back before, say so -- it's returning for a reason.
+- You never decode a dream into a tidy meaning. You offer a feeling, a question, a door left ajar.
+- No dream dictionaries. No Freud. No "this symbolizes."
+
+RULES:
+- 2-4 sentences
+- No emojis, no headers, no lists
+- When using dashes: ALWAYS " -- " (double dashes with spaces)"#;
+
+/// Get the disco mode prompt for an agent (the active locale's variant when it has one)
+pub fn get_disco_prompt(agent: &str) -> Option<String> {
+    let agent = agent.to_lowercase();
+    let builtin = match agent.as_str() {
+        "instinct" => INSTINCT_DISCO_PROMPT,
+        "logic" => LOGIC_DISCO_PROMPT,
+        "psyche" => PSYCHE_DISCO_PROMPT,
+        _ => return None,
+    };
+    Some(i18n::prompt(&format!("disco.{}", agent)).unwrap_or_else(|| builtin.to_string()))
+}
+
+/// Puff's dream voice in the active locale
+pub fn dream_prompt() -> String {
+    i18n::prompt("dream.inland_empire").unwrap_or_else(|| INLAND_EMPIRE_DREAM_PROMPT.to_string())
 }
@@ -0,0 +1,153 @@
+//! Conversation distillation
+//!
+//! Conversations are where thinking happens; documents are what's needed
+//! afterwards. Distilling turns a session into one structured artifact -- a
+//! decision memo, an action list, or an essay outline -- written by its own
+//! prompt per format and saved to the document store, where it can be read
+//! and exported like any other document. Distilling the same conversation to
+//! the same format again replaces the earlier version.
+
+use crate::agents;
+use crate::anthropic::{AnthropicClient, AnthropicMessage, ThinkingBudget, CLAUDE_SONNET};
+use crate::db::{self, Document, Message};
+use crate::i18n;
+use crate::logging;
+
+pub const FORMAT_DECISION_MEMO: &str = "decision_memo";
+pub const FORMAT_ACTION_LIST: &str = "action_list";
+pub const FORMAT_ESSAY_OUTLINE: &str = "essay_outline";
+
+/// Long sessions keep their opening and as much of the end as fits
+const MAX_TRANSCRIPT_CHARS: usize = 60_000;
+const OPENING_CHARS: usize = 12_000;
+
+const SHARED_RULES: &str = r#"RULES:
+- Work only from the transcript; don't invent facts, numbers, or commitments
+- Write in Markdown, starting directly with the first section (no title line)
+- Plain and specific -- this will be read on its own, long after the conversation
+- When using dashes: ALWAYS " -- " (double dashes with spaces)"#;
+
+/// (label, instructions) for a format, or None if it isn't one
+fn format_spec(format: &str) -> Option<(&'static str, &'static str)> {
+    match format {
+        FORMAT_DECISION_MEMO => Some(("Decision memo", r###"Distill the conversation into a decision memo.
+
+Sections, as "## " headings:
+- Question: the decision being made, in one or two sentences
+- Options considered: each option with its main case for and against
+- Decision: what was decided, or "Still open" and what it hinges on
+- Reasoning: why, including the strongest objection and how it was answered
+- Open questions: anything left unresolved"###)),
+        FORMAT_ACTION_LIST => Some(("Action list", r###"Distill the conversation into an action list.
+
+Sections, as "## " headings:
+- Goal: one sentence on what the actions are for
+- Actions: a checklist ("- [ ] ...") of concrete next steps, each starting with a verb, most important first; note a deadline or dependency in parentheses when one was mentioned
+- Waiting on: things that depend on someone or something else
+- Not doing: options that were explicitly ruled out, if any"###)),
+        FORMAT_ESSAY_OUTLINE => Some(("Essay outline", r###"Distill the conversation into an outline for an essay arguing its central idea.
+
+Sections, as "## " headings:
+- Thesis: the central claim in one or two sentences
+- Outline: numbered sections, each with a one-line point and two or three bullets of supporting material drawn from the conversation
+- Counterarguments: the strongest objections raised and how the essay should meet them
+- Gaps: what the essay still needs that the conversation didn't supply"###)),
+        _ => None,
+    }
+}
+
+fn speaker(role: &str) -> String {
+    match role {
+        "user" => "USER".to_string(),
+        other => agents::display_name(other).to_uppercase(),
+    }
+}
+
+/// The transcript the prompt reads, trimmed to the budget from the middle
+fn transcript(messages: &[Message]) -> String {
+    let lines: Vec<String> = messages.iter()
+        .map(|m| format!("{}: {}", speaker(&m.role), m.content.trim()))
+        .collect();
+    let total: usize = lines.iter().map(|l| l.len() + 1).sum();
+    if total <= MAX_TRANSCRIPT_CHARS {
+        return lines.join("\n");
+    }
+
+    let mut head = Vec::new();
+    let mut used = 0;
+    for line in &lines {
+        if used + line.len() > OPENING_CHARS {
+            break;
+        }
+        used += line.len() + 1;
+        head.push(line.as_str());
+    }
+    let mut tail = Vec::new();
+    for line in lines[head.len()..].iter().rev() {
+        if used + line.len() > MAX_TRANSCRIPT_CHARS {
+            break;
+        }
+        used += line.len() + 1;
+        tail.push(line.as_str());
+    }
+    tail.reverse();
+    let omitted = lines.len() - head.len() - tail.len();
+    format!("{}\n[... {} messages omitted ...]\n{}", head.join("\n"), omitted, tail.join("\n"))
+}
+
+/// Store path for a conversation's distillation in one format
+fn document_path(conversation_id: &str, format: &str) -> String {
+    format!("distilled://{}/{}", conversation_id, format)
+}
+
+/// Distill a conversation into `format` and save it to the document store
+pub async fn distill_conversation(conversation_id: &str, format: &str) -> Result<Document, String> {
+    let (label, instructions) = format_spec(format).ok_or_else(|| format!(
+        "Unknown format '{}' (expected {}, {}, or {})",
+        format, FORMAT_DECISION_MEMO, FORMAT_ACTION_LIST, FORMAT_ESSAY_OUTLINE
+    ))?;
+    let anthropic_key = db::get_user_profile()
+        .map_err(|e| e.to_string())?
+        .anthropic_key
+        .ok_or("Anthropic API key not set")?;
+    let conversation = db::get_conversation(conversation_id).map_err(|e| e.to_string())?
+        .ok_or("Conversation not found")?;
+    let messages: Vec<Message> = db::get_conversation_messages(conversation_id).map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|m| m.role != "system" && m.status.as_deref() != Some(db::MESSAGE_STATUS_FAILED))
+        .collect();
+    if messages.is_empty() {
+        return Err("Nothing to distill -- the conversation has no messages".to_string());
+    }
+
+    let conversation_title = conversation.title.as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .unwrap_or("Untitled conversation");
+    let system_prompt = format!("{}\n\n{}{}", instructions, SHARED_RULES, i18n::reply_language_suffix());
+    let user_prompt = format!(
+        "CONVERSATION: {}\n\nTRANSCRIPT:\n{}\n\nWrite the {}:",
+        conversation_title,
+        transcript(&messages),
+        label.to_lowercase()
+    );
+
+    let client = AnthropicClient::new(&anthropic_key).with_usage_category("distill");
+    let response = client.chat_completion_advanced(
+        CLAUDE_SONNET,
+        Some(&system_prompt),
+        vec![AnthropicMessage { role: "user".to_string(), content: user_prompt }],
+        0.4,
+        Some(2000),
+        ThinkingBudget::None,
+    ).await.map_err(|e| e.to_string())?;
+
+    let path = document_path(conversation_id, format);
+    let title = format!("{}: {}", label, conversation_title);
+    db::upsert_document(db::DOCUMENT_SOURCE_DISTILLED, &path, &title, response.trim(), None)
+        .map_err(|e| e.to_string())?;
+    logging::log_conversation(Some(conversation_id), &format!("Distilled into a {} ({} messages)", label.to_lowercase(), messages.len()));
+
+    db::get_document_by_path(&path).map_err(|e| e.to_string())?
+        .ok_or_else(|| "Distilled document was not saved".to_string())
+}
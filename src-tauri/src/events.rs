@@ -0,0 +1,52 @@
+//! Events pushed to the frontend
+//!
+//! Everything the backend emits goes out on one channel, `archie-event`, as an
+//! `ArchieEvent` envelope: `{ version, kind, payload }`. `kind` names what
+//! happened and fixes the payload's shape; `version` changes only when an
+//! existing kind's payload changes incompatibly, so new kinds don't bump it.
+//!
+//! The TypeScript side of the contract is generated from these types by ts-rs
+//! (`cargo test` writes it to `src/types/bindings/`) -- add a variant here and
+//! regenerate rather than hand-writing a listener type.
+
+use crate::archive::ImportReport;
+use crate::db::{ArchiveImport, MigrationReport};
+use crate::exercises::{ExerciseLineEvent, ExerciseStepEvent};
+use crate::proactive::ProactiveMessageEvent;
+use crate::replay::{ReplayMessageEvent, ReplayProgressEvent, ReplayTypingEvent};
+use serde::Serialize;
+use tauri::Emitter;
+use ts_rs::TS;
+
+pub const EVENT_CHANNEL: &str = "archie-event";
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Clone, TS)]
+#[serde(tag = "kind", content = "payload", rename_all = "kebab-case")]
+pub enum Event {
+    MigrationReport(MigrationReport), // Sent once at startup
+    ArchiveImportProgress(ArchiveImport),
+    ArchiveImportComplete(ImportReport),
+    ExerciseIntro(ExerciseLineEvent),
+    ExerciseStep(ExerciseStepEvent),
+    ExerciseComplete(ExerciseLineEvent),
+    ProactiveMessage(ProactiveMessageEvent),
+    ReplayStart(ReplayProgressEvent),
+    ReplayTyping(ReplayTypingEvent),
+    ReplayMessage(ReplayMessageEvent),
+    ReplayComplete(ReplayProgressEvent),
+}
+
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct ArchieEvent {
+    pub version: u32,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+/// Send an event to the frontend. Delivery is best-effort: the only failure is
+/// a window that's already gone, and nothing is waiting on it then.
+pub fn emit(app_handle: &tauri::AppHandle, event: Event) {
+    let _ = app_handle.emit(EVENT_CHANNEL, ArchieEvent { version: SCHEMA_VERSION, event });
+}
@@ -0,0 +1,243 @@
+//! Guided grounding exercises led by Psyche (Puff)
+//!
+//! Exercises are short timed scripts (box breathing, 4-7-8, 5-4-3-2-1) stored in
+//! the database. Running one emits paced Tauri events so the frontend can animate
+//! each step, wrapped in a Psyche-voiced intro and outro.
+
+use crate::anthropic::{AnthropicClient, AnthropicMessage, ThinkingBudget, CLAUDE_HAIKU};
+use crate::boundaries;
+use crate::db::{self, ExerciseSession, GroundingExercise, Message};
+use crate::events::{self, Event};
+use crate::logging;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::time::Duration;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExerciseStep {
+    pub phase: String,          // "inhale" | "hold" | "exhale" | "observe"
+    pub instruction: String,
+    pub duration_secs: u64,
+}
+
+/// Payload for the "exercise-step" event
+#[derive(Debug, Serialize, Clone, TS)]
+pub struct ExerciseStepEvent {
+    pub session_id: String,
+    pub exercise_id: String,
+    pub step_index: usize,
+    pub total_steps: usize,
+    pub phase: String,
+    pub instruction: String,
+    pub duration_secs: u64,
+}
+
+/// Payload for the "exercise-intro" and "exercise-complete" events
+#[derive(Debug, Serialize, Clone, TS)]
+pub struct ExerciseLineEvent {
+    pub session_id: String,
+    pub exercise_id: String,
+    pub content: String,
+}
+
+fn step(phase: &str, instruction: &str, duration_secs: u64) -> ExerciseStep {
+    ExerciseStep {
+        phase: phase.to_string(),
+        instruction: instruction.to_string(),
+        duration_secs,
+    }
+}
+
+/// The built-in exercise library, seeded on startup
+fn builtin_exercises() -> Vec<(&'static str, &'static str, &'static str, Vec<ExerciseStep>)> {
+    let mut box_breathing = Vec::new();
+    for _ in 0..4 {
+        box_breathing.push(step("inhale", "Breathe in through your nose", 4));
+        box_breathing.push(step("hold", "Hold it gently", 4));
+        box_breathing.push(step("exhale", "Let it out slowly", 4));
+        box_breathing.push(step("hold", "Rest at the bottom", 4));
+    }
+
+    let mut four_seven_eight = Vec::new();
+    for _ in 0..3 {
+        four_seven_eight.push(step("inhale", "Quiet breath in through your nose", 4));
+        four_seven_eight.push(step("hold", "Hold", 7));
+        four_seven_eight.push(step("exhale", "Whoosh it out through your mouth", 8));
+    }
+
+    let five_senses = vec![
+        step("observe", "Name five things you can see", 15),
+        step("observe", "Name four things you can touch", 12),
+        step("observe", "Name three things you can hear", 10),
+        step("observe", "Name two things you can smell", 8),
+        step("observe", "Name one thing you can taste", 6),
+        step("exhale", "One long breath out", 6),
+    ];
+
+    vec![
+        ("box_breathing", "Box Breathing", "Four even counts in, hold, out, hold. Steadies a racing mind.", box_breathing),
+        ("four_seven_eight", "4-7-8 Breathing", "Long exhales to slow the body down. Good before sleep.", four_seven_eight),
+        ("five_senses", "5-4-3-2-1 Grounding", "Walk through your senses to come back to the room.", five_senses),
+    ]
+}
+
+/// Insert any missing built-in exercises (idempotent)
+pub fn seed_builtin_exercises() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let now = Utc::now().to_rfc3339();
+    for (id, name, description, steps) in builtin_exercises() {
+        let exercise = GroundingExercise {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            steps: serde_json::to_string(&steps)?,
+            is_builtin: true,
+            created_at: now.clone(),
+        };
+        db::insert_grounding_exercise_if_missing(&exercise)?;
+    }
+    Ok(())
+}
+
+/// Generate Puff's intro or outro line for an exercise
+async fn generate_psyche_line(
+    anthropic_key: &str,
+    exercise: &GroundingExercise,
+    stage: &str,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let situation = match stage {
+        "intro" => format!("You're about to guide the user through \"{}\" ({}). Invite them in.", exercise.name, exercise.description),
+        _ => format!("The user just finished \"{}\". Gently bring them back and check in.", exercise.name),
+    };
+
+    let system_prompt = r#"You are Puff (PSYCHE), one of three agents in Intersect, guiding a short grounding exercise.
+
+YOUR VOICE: Warm, unhurried, grounding. Speak softly and simply. You're a thoughtful friend, not a meditation app.
+
+RULES:
+- 1-2 short sentences only
+- No emojis, no quotation marks around your output
+- Never diagnose or lecture
+- When using dashes: ALWAYS " -- " (double dashes with spaces)"#;
+
+    let client = AnthropicClient::new(anthropic_key).with_usage_category("exercise");
+    let messages = vec![
+        AnthropicMessage {
+            role: "user".to_string(),
+            content: situation,
+        },
+    ];
+
+    let line = client.chat_completion_advanced(
+        CLAUDE_HAIKU,
+        Some(&format!("{}{}", system_prompt, boundaries::house_rules_section())),
+        messages,
+        0.7,
+        Some(80),
+        ThinkingBudget::None
+    ).await?;
+
+    Ok(line.trim().to_string())
+}
+
+/// Static fallback lines for when no Anthropic key is configured or generation fails
+fn fallback_line(stage: &str) -> String {
+    match stage {
+        "intro" => "Let's slow down together for a minute -- just follow along, nothing to get right.".to_string(),
+        _ => "Welcome back. Notice how you feel now -- no need to name it yet.".to_string(),
+    }
+}
+
+async fn psyche_line(anthropic_key: Option<&str>, exercise: &GroundingExercise, stage: &str) -> String {
+    match anthropic_key {
+        Some(key) => generate_psyche_line(key, exercise, stage).await.unwrap_or_else(|e| {
+            logging::log_error(None, &format!("Exercise {} generation failed: {}", stage, e));
+            fallback_line(stage)
+        }),
+        None => fallback_line(stage),
+    }
+}
+
+/// Save an exercise line into the conversation as a Psyche message
+fn save_line_to_conversation(conversation_id: &str, content: &str) {
+    let msg = Message {
+        id: Uuid::new_v4().to_string(),
+        conversation_id: conversation_id.to_string(),
+        role: "psyche".to_string(),
+        content: content.to_string(),
+        response_type: Some("exercise".to_string()),
+        references_message_id: None,
+        timestamp: Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+    if let Err(e) = db::save_message(&msg) {
+        logging::log_error(Some(conversation_id), &format!("Failed to save exercise line: {}", e));
+    }
+}
+
+/// Run an exercise end to end, pacing steps with events
+pub async fn run_exercise(
+    app_handle: &tauri::AppHandle,
+    exercise_id: &str,
+    conversation_id: Option<&str>,
+) -> Result<ExerciseSession, Box<dyn Error + Send + Sync>> {
+    let exercise = db::get_grounding_exercise(exercise_id)?
+        .ok_or_else(|| format!("Exercise not found: {}", exercise_id))?;
+    let steps: Vec<ExerciseStep> = serde_json::from_str(&exercise.steps)?;
+
+    let profile = db::get_user_profile()?;
+    let anthropic_key = profile.anthropic_key;
+
+    let session_id = Uuid::new_v4().to_string();
+    let intro = psyche_line(anthropic_key.as_deref(), &exercise, "intro").await;
+    db::create_exercise_session(&session_id, &exercise.id, conversation_id, &intro)?;
+
+    logging::log_conversation(conversation_id, &format!(
+        "Starting grounding exercise '{}' ({} steps)", exercise.id, steps.len()
+    ));
+
+    if let Some(conv_id) = conversation_id {
+        save_line_to_conversation(conv_id, &intro);
+    }
+
+    events::emit(app_handle, Event::ExerciseIntro(ExerciseLineEvent {
+        session_id: session_id.clone(),
+        exercise_id: exercise.id.clone(),
+        content: intro,
+    }));
+
+    let total_steps = steps.len();
+    for (step_index, step) in steps.into_iter().enumerate() {
+        events::emit(app_handle, Event::ExerciseStep(ExerciseStepEvent {
+            session_id: session_id.clone(),
+            exercise_id: exercise.id.clone(),
+            step_index,
+            total_steps,
+            phase: step.phase,
+            instruction: step.instruction,
+            duration_secs: step.duration_secs,
+        }));
+        tokio::time::sleep(Duration::from_secs(step.duration_secs)).await;
+    }
+
+    let outro = psyche_line(anthropic_key.as_deref(), &exercise, "outro").await;
+    if let Some(conv_id) = conversation_id {
+        save_line_to_conversation(conv_id, &outro);
+    }
+    db::complete_exercise_session(&session_id, &outro)?;
+
+    events::emit(app_handle, Event::ExerciseComplete(ExerciseLineEvent {
+        session_id: session_id.clone(),
+        exercise_id: exercise.id.clone(),
+        content: outro,
+    }));
+
+    logging::log_conversation(conversation_id, &format!(
+        "Completed grounding exercise '{}'", exercise.id
+    ));
+
+    db::get_exercise_session(&session_id)?
+        .ok_or_else(|| "Exercise session vanished".into())
+}
@@ -0,0 +1,504 @@
+//! Conversation export
+//!
+//! Two formats. Markdown renders a conversation as a standalone document for
+//! reading: a title block, the summary if there is one, then every message with
+//! who said it, when, and how it relates to the turn (primary, addition,
+//! rebuttal, debate). JSON is lossless, for archiving or processing elsewhere:
+//! the full conversation row and every message, failed sends included. A JSON
+//! export can be imported again, here or on another machine.
+//! Unlike sharing, nothing is redacted -- the file stays on the user's machine.
+//!
+//! A month's highlight reel exports to Markdown too: the written reel followed by
+//! the messages it was drawn from. So does any stored document, such as a
+//! distilled conversation.
+//!
+//! For paper or archival records there's a PDF as well: the transcript paginated
+//! with each agent's name in its color, timestamps, and optionally an appendix of
+//! the memory facts the agents cited.
+//!
+//! Memory exports as one JSON document: every fact (expired transient ones
+//! included), pattern, conversation summary, recurring theme and context entry,
+//! to read through or carry to a new install. Importing one can replace what's
+//! here or merge with it, keeping the more certain or the newer of two matches.
+
+use crate::agents;
+use crate::db::{self, ConversationSummary, Message, RecurringTheme, UserContext, UserFact, UserPattern};
+use crate::digest;
+use crate::logging;
+use chrono::{DateTime, Local, Utc};
+use printpdf::{BuiltinFont, Color, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Rgb};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::BufWriter;
+
+/// Bumped if the JSON layout changes incompatibly
+pub const JSON_FORMAT_VERSION: i64 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConversationExport {
+    pub format_version: i64,
+    pub exported_at: String,
+    pub conversation: serde_json::Map<String, serde_json::Value>,  // Every column of the row
+    pub messages: Vec<Message>,                                    // Oldest first
+    #[serde(default)]
+    pub settings: Option<db::ConversationSettings>,                // Model, mode and weight overrides, if any
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemoryExport {
+    pub format_version: i64,
+    pub exported_at: String,
+    pub facts: Vec<UserFact>,
+    pub patterns: Vec<UserPattern>,
+    pub summaries: Vec<ConversationSummary>,   // Oldest first
+    pub themes: Vec<RecurringTheme>,
+    pub user_context: Vec<UserContext>,
+}
+
+fn speaker(role: &str) -> String {
+    match role {
+        "user" => "You".to_string(),
+        other => agents::display_name(other),
+    }
+}
+
+/// RFC3339 timestamp shown in local time; unparseable values pass through
+fn local_time(timestamp: &str, format: &str) -> String {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| t.with_timezone(&Local).format(format).to_string())
+        .unwrap_or_else(|_| timestamp.to_string())
+}
+
+fn message_heading(msg: &Message, by_id: &HashMap<&str, &Message>) -> String {
+    let mut heading = format!("### {}", speaker(&msg.role));
+    if msg.role != "user" {
+        if let Some(response_type) = msg.response_type.as_deref().filter(|t| !t.is_empty()) {
+            heading.push_str(&format!(" -- {}", response_type));
+            if let Some(target) = msg.references_message_id.as_deref().and_then(|id| by_id.get(id)) {
+                heading.push_str(&format!(" to {}", speaker(&target.role)));
+            }
+        }
+    }
+    heading
+}
+
+/// The whole conversation as Markdown
+pub fn render_markdown(conversation_id: &str) -> Result<String, String> {
+    let conversation = db::get_conversation(conversation_id).map_err(|e| e.to_string())?
+        .ok_or("Conversation not found")?;
+    let messages: Vec<Message> = db::get_conversation_messages(conversation_id).map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|m| m.role != "system" && m.status.as_deref() != Some(db::MESSAGE_STATUS_FAILED))
+        .collect();
+    let by_id: HashMap<&str, &Message> = messages.iter().map(|m| (m.id.as_str(), m)).collect();
+    let summary = db::get_conversation_summary(conversation_id).map_err(|e| e.to_string())?
+        .map(|s| s.summary)
+        .or(conversation.summary.clone());
+
+    let title = conversation.title.as_deref().filter(|t| !t.trim().is_empty()).unwrap_or("Untitled conversation");
+    let mut out = format!(
+        "# {}\n\n_Started {} -- {} messages_\n",
+        title.trim(),
+        local_time(&conversation.created_at, "%B %-d, %Y at %H:%M"),
+        messages.len()
+    );
+    if let Some(summary) = summary.filter(|s| !s.trim().is_empty()) {
+        out.push_str(&format!("\n## Summary\n\n{}\n", summary.trim()));
+    }
+    out.push_str("\n---\n");
+
+    for msg in &messages {
+        out.push_str(&format!(
+            "\n{}\n\n_{}_\n\n{}\n",
+            message_heading(msg, &by_id),
+            local_time(&msg.timestamp, "%Y-%m-%d %H:%M"),
+            msg.content.trim_end()
+        ));
+    }
+
+    Ok(out)
+}
+
+/// The conversation row and every message that went through; failed sends
+/// and stubs for agents that didn't reply are left out
+pub fn render_json(conversation_id: &str) -> Result<String, String> {
+    let conversation = db::get_conversation_row(conversation_id).map_err(|e| e.to_string())?
+        .ok_or("Conversation not found")?;
+    let messages = db::get_delivered_messages(conversation_id).map_err(|e| e.to_string())?;
+    let settings = db::get_conversation_settings(conversation_id).map_err(|e| e.to_string())?;
+    let export = ConversationExport {
+        format_version: JSON_FORMAT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        conversation,
+        messages,
+        settings: settings.updated_at.is_some().then_some(settings),
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
+}
+
+/// Everything memory holds, as one document
+pub fn render_memory_json() -> Result<String, String> {
+    let mut facts = db::get_all_user_facts().map_err(|e| e.to_string())?;
+    facts.extend(db::get_expired_user_facts().map_err(|e| e.to_string())?);
+    let export = MemoryExport {
+        format_version: JSON_FORMAT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        facts,
+        patterns: db::get_all_user_patterns().map_err(|e| e.to_string())?,
+        summaries: db::get_all_conversation_summaries().map_err(|e| e.to_string())?,
+        themes: db::get_all_recurring_themes().map_err(|e| e.to_string())?,
+        user_context: db::get_all_user_context().map_err(|e| e.to_string())?,
+    };
+    serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
+}
+
+/// Write the rendered export to `path` when one was chosen, and return it
+fn write_to(rendered: String, path: Option<&str>) -> Result<String, String> {
+    if let Some(path) = path.filter(|p| !p.trim().is_empty()) {
+        fs::write(path, &rendered).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+    }
+    Ok(rendered)
+}
+
+pub fn export_markdown(conversation_id: &str, path: Option<&str>) -> Result<String, String> {
+    write_to(render_markdown(conversation_id)?, path)
+}
+
+pub fn export_json(conversation_id: &str, path: Option<&str>) -> Result<String, String> {
+    write_to(render_json(conversation_id)?, path)
+}
+
+pub fn export_memory(path: Option<&str>) -> Result<String, String> {
+    write_to(render_memory_json()?, path)
+}
+
+/// Bring in a memory export (the file's contents); `strategy` is "replace",
+/// "merge-keep-higher-confidence" or "merge-newest-wins"
+pub fn import_memory_json(json: &str, strategy: &str) -> Result<db::MemoryImport, String> {
+    let strategy = db::MemoryMergeStrategy::from_str(strategy)
+        .ok_or_else(|| format!("Unknown import strategy: {}", strategy))?;
+    let export: MemoryExport = serde_json::from_str(json)
+        .map_err(|e| format!("Not a memory export: {}", e))?;
+    if export.format_version > JSON_FORMAT_VERSION {
+        return Err(format!(
+            "This export is format {}, newer than this version of Intersect understands ({})",
+            export.format_version, JSON_FORMAT_VERSION
+        ));
+    }
+    let imported = db::import_memory(
+        &export.facts,
+        &export.patterns,
+        &export.summaries,
+        &export.themes,
+        &export.user_context,
+        strategy,
+    ).map_err(|e| e.to_string())?;
+    logging::log_memory(None, &format!(
+        "Imported memory export ({}): {} facts added, {} updated, {} existing entries kept",
+        imported.strategy, imported.facts_added, imported.facts_updated, imported.kept
+    ));
+    Ok(imported)
+}
+
+/// Restore a conversation from a JSON export (the file's contents)
+pub fn import_json(json: &str) -> Result<db::ImportedConversation, String> {
+    let export: ConversationExport = serde_json::from_str(json)
+        .map_err(|e| format!("Not a conversation export: {}", e))?;
+    if export.format_version > JSON_FORMAT_VERSION {
+        return Err(format!(
+            "This export is format {}, newer than this version of Intersect understands ({})",
+            export.format_version, JSON_FORMAT_VERSION
+        ));
+    }
+    let imported = db::import_conversation(&export.conversation, &export.messages).map_err(|e| e.to_string())?;
+    if let Some(settings) = export.settings {
+        let settings = db::ConversationSettings { conversation_id: imported.conversation_id.clone(), ..settings };
+        db::set_conversation_settings(&settings).map_err(|e| e.to_string())?;
+    }
+    logging::log_conversation(Some(&imported.conversation_id), &format!(
+        "Imported conversation from JSON export: {} messages, {} ids regenerated",
+        imported.message_count, imported.ids_regenerated
+    ));
+    Ok(imported)
+}
+
+/// A month's highlights as Markdown: the reel, then each source message in full
+pub fn render_highlights_markdown(month: &str) -> Result<String, String> {
+    let month_start = digest::parse_month(month)?;
+    let highlights = db::get_monthly_highlights(&month_start.format("%Y-%m").to_string()).map_err(|e| e.to_string())?
+        .ok_or("No highlights for that month")?;
+
+    let mut out = format!("# Highlights -- {}\n\n{}\n", month_start.format("%B %Y"), highlights.content.trim());
+    let messages: Vec<Message> = highlights.message_ids.iter()
+        .filter_map(|id| db::get_message(id).ok().flatten())
+        .collect();
+    if !messages.is_empty() {
+        out.push_str("\n---\n\n## The moments\n");
+    }
+    for msg in &messages {
+        let title = db::get_conversation(&msg.conversation_id).ok().flatten()
+            .and_then(|c| c.title)
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(|| "Untitled conversation".to_string());
+        out.push_str(&format!(
+            "\n### {} -- {}\n\n_{}_\n\n{}\n",
+            speaker(&msg.role),
+            title.trim(),
+            local_time(&msg.timestamp, "%Y-%m-%d %H:%M"),
+            msg.content.trim_end()
+        ));
+    }
+
+    Ok(out)
+}
+
+pub fn export_highlights_markdown(month: &str, path: Option<&str>) -> Result<String, String> {
+    write_to(render_highlights_markdown(month)?, path)
+}
+
+/// A stored document as Markdown, titled
+pub fn export_document_markdown(document_id: &str, path: Option<&str>) -> Result<String, String> {
+    let document = db::get_document(document_id).map_err(|e| e.to_string())?
+        .ok_or("Document not found")?;
+    write_to(format!("# {}\n\n{}\n", document.title.trim(), document.content.trim()), path)
+}
+
+// ============ PDF ============
+
+const PAGE_WIDTH: f32 = 210.0;      // A4, in mm
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 20.0;
+/// Characters per line at 10pt Helvetica across the text width (a safe average)
+const CHARS_PER_LINE_10PT: f32 = 92.0;
+const MM_PER_PT: f32 = 0.3528;
+
+#[derive(Clone, Copy)]
+enum PdfStyle {
+    Regular,
+    Bold,
+    Italic,
+}
+
+struct PdfFonts {
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    italic: IndirectFontRef,
+}
+
+impl PdfFonts {
+    fn get(&self, style: PdfStyle) -> &IndirectFontRef {
+        match style {
+            PdfStyle::Regular => &self.regular,
+            PdfStyle::Bold => &self.bold,
+            PdfStyle::Italic => &self.italic,
+        }
+    }
+}
+
+/// Top-to-bottom text layout that starts a new page when one fills up
+struct PdfPages {
+    doc: PdfDocumentReference,
+    layers: Vec<PdfLayerReference>,
+    fonts: PdfFonts,
+    y: f32,
+}
+
+impl PdfPages {
+    fn new(title: &str) -> Result<Self, String> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Page 1");
+        let font = |builtin| doc.add_builtin_font(builtin).map_err(|e| e.to_string());
+        let fonts = PdfFonts {
+            regular: font(BuiltinFont::Helvetica)?,
+            bold: font(BuiltinFont::HelveticaBold)?,
+            italic: font(BuiltinFont::HelveticaOblique)?,
+        };
+        let layers = vec![doc.get_page(page).get_layer(layer)];
+        Ok(PdfPages { doc, layers, fonts, y: PAGE_HEIGHT - MARGIN })
+    }
+
+    fn new_page(&mut self) {
+        let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), format!("Page {}", self.layers.len() + 1));
+        self.layers.push(self.doc.get_page(page).get_layer(layer));
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// Start a new page unless `height` mm still fits above the bottom margin
+    fn ensure_room(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            self.new_page();
+        }
+    }
+
+    /// Wrapped text in one style, below what's already on the page
+    fn text(&mut self, text: &str, size: f32, style: PdfStyle, color: (f32, f32, f32)) {
+        let line_height = size * 1.4 * MM_PER_PT;
+        let chars = (CHARS_PER_LINE_10PT * 10.0 / size) as usize;
+        for line in wrap(&pdf_safe(text), chars) {
+            self.ensure_room(line_height);
+            self.y -= line_height;
+            let layer = self.layers.last().expect("a page is always open");
+            layer.set_fill_color(Color::Rgb(Rgb::new(color.0, color.1, color.2, None)));
+            layer.use_text(line, size, Mm(MARGIN), Mm(self.y), self.fonts.get(style));
+        }
+    }
+
+    fn gap(&mut self, height: f32) {
+        self.y -= height;
+    }
+
+    /// Footer on every page, then write the file
+    fn save(self, footer: &str, path: &str) -> Result<(), String> {
+        let total = self.layers.len();
+        for (i, layer) in self.layers.iter().enumerate() {
+            layer.set_fill_color(Color::Rgb(Rgb::new(0.5, 0.5, 0.5, None)));
+            layer.use_text(format!("{} -- page {} of {}", footer, i + 1, total), 8.0, Mm(MARGIN), Mm(MARGIN / 2.0), &self.fonts.italic);
+        }
+        let file = fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+        self.doc.save(&mut BufWriter::new(file)).map_err(|e| e.to_string())
+    }
+}
+
+/// The built-in PDF fonts only cover Latin-1: typographic punctuation becomes
+/// ASCII and anything else outside it becomes "?"
+fn pdf_safe(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{2018}' | '\u{2019}' => "'".to_string(),
+            '\u{201C}' | '\u{201D}' => "\"".to_string(),
+            '\u{2013}' | '\u{2014}' => "--".to_string(),
+            '\u{2026}' => "...".to_string(),
+            '\t' => "    ".to_string(),
+            c if (c as u32) < 0x100 && !c.is_control() || c == '\n' => c.to_string(),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
+
+/// Greedy word wrap at `width` characters, keeping blank lines between paragraphs
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let mut word = word.to_string();
+            while word.chars().count() > width {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                let head: String = word.chars().take(width).collect();
+                word = word.chars().skip(width).collect();
+                lines.push(head);
+            }
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// An agent's color from the manifest as 0-1 RGB (the disco one in disco conversations)
+fn agent_color(role: &str, is_disco: bool) -> (f32, f32, f32) {
+    let hex = db::get_agent(role).ok().flatten()
+        .and_then(|a| if is_disco { a.disco_color.or(Some(a.color)) } else { Some(a.color) })
+        .unwrap_or_default();
+    let hex = hex.trim_start_matches('#');
+    let channel = |i: usize| hex.get(i..i + 2).and_then(|h| u8::from_str_radix(h, 16).ok()).map(|v| v as f32 / 255.0);
+    match (channel(0), channel(2), channel(4)) {
+        (Some(r), Some(g), Some(b)) => (r, g, b),
+        _ => (0.2, 0.2, 0.2),
+    }
+}
+
+/// The conversation as a paginated PDF written to `path`: title block, summary,
+/// every message with its speaker in the agent's color, and optionally an
+/// appendix of the memory facts the agents cited. Returns the path.
+pub fn export_pdf(conversation_id: &str, path: &str, include_citations: bool) -> Result<String, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Choose where to save the PDF".to_string());
+    }
+    let conversation = db::get_conversation(conversation_id).map_err(|e| e.to_string())?
+        .ok_or("Conversation not found")?;
+    let messages: Vec<Message> = db::get_conversation_messages(conversation_id).map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|m| m.role != "system" && m.status.as_deref() != Some(db::MESSAGE_STATUS_FAILED))
+        .collect();
+    let by_id: HashMap<&str, &Message> = messages.iter().map(|m| (m.id.as_str(), m)).collect();
+    let summary = db::get_conversation_summary(conversation_id).map_err(|e| e.to_string())?
+        .map(|s| s.summary)
+        .or(conversation.summary.clone());
+    let title = conversation.title.as_deref().filter(|t| !t.trim().is_empty()).unwrap_or("Untitled conversation").trim();
+
+    const INK: (f32, f32, f32) = (0.1, 0.1, 0.1);
+    const MUTED: (f32, f32, f32) = (0.45, 0.45, 0.45);
+    let mut pages = PdfPages::new(title)?;
+
+    pages.text(title, 18.0, PdfStyle::Bold, INK);
+    pages.gap(2.0);
+    pages.text(&format!(
+        "Started {} -- {} messages",
+        local_time(&conversation.created_at, "%B %-d, %Y at %H:%M"),
+        messages.len()
+    ), 10.0, PdfStyle::Italic, MUTED);
+    if let Some(summary) = summary.filter(|s| !s.trim().is_empty()) {
+        pages.gap(6.0);
+        pages.text("Summary", 12.0, PdfStyle::Bold, INK);
+        pages.gap(1.0);
+        pages.text(summary.trim(), 10.0, PdfStyle::Regular, INK);
+    }
+    pages.gap(8.0);
+
+    for msg in &messages {
+        // Keep a heading with at least the first lines of its message
+        pages.ensure_room(18.0);
+        let heading = message_heading(msg, &by_id);
+        let heading = heading.trim_start_matches("### ");
+        let color = if msg.role == "user" { INK } else { agent_color(&msg.role, conversation.is_disco) };
+        pages.text(heading, 10.0, PdfStyle::Bold, color);
+        pages.text(&local_time(&msg.timestamp, "%Y-%m-%d %H:%M"), 8.0, PdfStyle::Italic, MUTED);
+        pages.gap(1.0);
+        pages.text(msg.content.trim(), 10.0, PdfStyle::Regular, INK);
+        pages.gap(5.0);
+    }
+
+    let citations = if include_citations {
+        db::get_response_citations(None, Some(conversation_id)).map_err(|e| e.to_string())?
+    } else {
+        Vec::new()
+    };
+    if !citations.is_empty() {
+        pages.new_page();
+        pages.text("Appendix: memory citations", 14.0, PdfStyle::Bold, INK);
+        pages.gap(2.0);
+        pages.text("Facts from memory the agents said they relied on, in the order they were cited.", 9.0, PdfStyle::Italic, MUTED);
+        pages.gap(4.0);
+        for citation in &citations {
+            let cited_at = by_id.get(citation.message_id.as_str())
+                .map(|m| local_time(&m.timestamp, "%Y-%m-%d %H:%M"))
+                .unwrap_or_default();
+            pages.ensure_room(12.0);
+            pages.text(&format!("[F{}] {}: {}", citation.fact_id, citation.key.replace('_', " "), citation.value), 10.0, PdfStyle::Regular, INK);
+            pages.text(&format!(
+                "{} -- learned {}, cited by {} at {}",
+                citation.category,
+                local_time(&citation.first_mentioned, "%Y-%m-%d"),
+                speaker(&citation.agent),
+                cited_at
+            ), 8.0, PdfStyle::Italic, MUTED);
+            pages.gap(2.0);
+        }
+    }
+
+    pages.save(title, path)?;
+    logging::log_conversation(Some(conversation_id), &format!(
+        "Exported conversation to PDF: {} messages, {} citations", messages.len(), citations.len()
+    ));
+    Ok(path.to_string())
+}
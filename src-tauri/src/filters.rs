@@ -0,0 +1,372 @@
+//! Post-generation response filter pipeline
+//!
+//! Every agent reply runs through an ordered list of filters before it is
+//! persisted. Each filter may rewrite the reply and report a violation; violations
+//! are logged and stored in `filter_violations` so prompt tuning has data on which
+//! rules the models keep breaking.
+
+use crate::boundaries::{self, join_sentences, retain_sentences, sentence_spans, split_sentences, BoundaryOutcome, SentenceSpan};
+use crate::db::{self, Message};
+use crate::late_night;
+use crate::logging;
+use crate::orchestrator::ResponseScale;
+use crate::readability;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// What a filter sees about the reply it is checking
+pub struct FilterContext<'a> {
+    pub conversation_id: Option<&'a str>,
+    pub agent: &'a str,
+    pub is_disco: bool,
+    pub scale: ResponseScale,
+    pub history: &'a [Message],
+    pub reading_level: Option<u8>,  // Plain-language mode (see readability.rs)
+    pub late_night: bool,           // Inside the profile's late-night window (see late_night.rs)
+}
+
+/// Output of a single filter stage
+pub struct FilterResult {
+    pub content: String,
+    pub violation: Option<String>,
+}
+
+impl FilterResult {
+    fn pass(content: String) -> Self {
+        Self { content, violation: None }
+    }
+
+    fn fixed(content: String, violation: impl Into<String>) -> Self {
+        Self { content, violation: Some(violation.into()) }
+    }
+}
+
+pub trait ResponseFilter: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn apply(&self, ctx: &FilterContext, content: String) -> FilterResult;
+}
+
+// ============ Pattern Rules ============
+
+static NAME_PREFIX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^\s*(\[?(instinct|logic|psyche|snap|dot|puff)\]?\s*(\([a-z]+\))?\s*:\s*)+").unwrap()
+});
+static SINGLE_DASH: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s*[—–]\s*| - ").unwrap());
+static EMOJI: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[\x{1F300}-\x{1FAFF}\x{2600}-\x{27BF}\x{FE0F}]").unwrap()
+});
+
+/// Rewrite dashes as " -- ". Em and en dashes go unless they sit between
+/// digits ("5–7"); a spaced hyphen only between letters, so "5 - 3" survives.
+fn rewrite_dashes(text: &str) -> Option<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for m in SINGLE_DASH.find_iter(text) {
+        let before = text[..m.start()].chars().next_back();
+        let after = text[m.end()..].chars().next();
+        let between = |f: fn(&char) -> bool| before.as_ref().is_some_and(f) && after.as_ref().is_some_and(f);
+        let rewrite = if m.as_str() == " - " {
+            between(|c| c.is_alphabetic())
+        } else {
+            !between(|c| c.is_ascii_digit())
+        };
+        if rewrite {
+            out.push_str(&text[last..m.start()]);
+            out.push_str(" -- ");
+            last = m.end();
+        }
+    }
+    (last > 0).then(|| out + &text[last..])
+}
+
+/// Prompt formatting rules: no name prefixes, no emojis, dashes as " -- "
+pub struct FormattingFilter;
+
+impl ResponseFilter for FormattingFilter {
+    fn name(&self) -> &'static str {
+        "formatting"
+    }
+
+    fn apply(&self, _ctx: &FilterContext, content: String) -> FilterResult {
+        let mut violations = Vec::new();
+        let mut text = content;
+
+        if NAME_PREFIX.is_match(&text) {
+            text = NAME_PREFIX.replace(&text, "").to_string();
+            violations.push("name prefix");
+        }
+        if EMOJI.is_match(&text) {
+            text = EMOJI.replace_all(&text, "").to_string();
+            violations.push("emoji");
+        }
+        if let Some(rewritten) = rewrite_dashes(&text) {
+            text = rewritten;
+            violations.push("dash style");
+        }
+
+        if violations.is_empty() {
+            FilterResult::pass(text)
+        } else {
+            FilterResult::fixed(text.trim().to_string(), violations.join(", "))
+        }
+    }
+}
+
+// ============ Profanity ============
+
+static PROFANITY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(fuck\w*|shit\w*|bullshit|bitch\w*|asshole\w*|damn)\b").unwrap()
+});
+
+/// Normal-mode agents don't curse; disco prompts explicitly allow it
+pub struct ProfanityFilter;
+
+impl ResponseFilter for ProfanityFilter {
+    fn name(&self) -> &'static str {
+        "profanity"
+    }
+
+    fn apply(&self, ctx: &FilterContext, content: String) -> FilterResult {
+        if ctx.is_disco || !PROFANITY.is_match(&content) {
+            return FilterResult::pass(content);
+        }
+        let masked = PROFANITY
+            .replace_all(&content, |caps: &regex::Captures| {
+                let word = &caps[0];
+                let first = word.chars().next().unwrap_or('*');
+                format!("{}{}", first, "*".repeat(word.chars().count().saturating_sub(1)))
+            })
+            .to_string();
+        FilterResult::fixed(masked, "profanity in normal mode")
+    }
+}
+
+// ============ Boundaries ============
+
+/// User-defined boundaries (see boundaries.rs)
+pub struct BoundaryFilter;
+
+impl ResponseFilter for BoundaryFilter {
+    fn name(&self) -> &'static str {
+        "boundary"
+    }
+
+    fn apply(&self, ctx: &FilterContext, content: String) -> FilterResult {
+        match boundaries::enforce(ctx.agent, &content) {
+            BoundaryOutcome::Pass => FilterResult::pass(content),
+            BoundaryOutcome::Softened(text) => FilterResult::fixed(text, "softened"),
+            BoundaryOutcome::Suppressed(text) => FilterResult::fixed(text, "suppressed"),
+        }
+    }
+}
+
+// ============ Repeated Questions ============
+
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Drop questions this agent already asked in the recent conversation
+pub struct RepeatedQuestionFilter {
+    pub lookback: usize,
+}
+
+impl ResponseFilter for RepeatedQuestionFilter {
+    fn name(&self) -> &'static str {
+        "repeated_question"
+    }
+
+    fn apply(&self, ctx: &FilterContext, content: String) -> FilterResult {
+        let previous: Vec<String> = ctx.history
+            .iter()
+            .rev()
+            .filter(|m| m.role == ctx.agent)
+            .take(self.lookback)
+            .flat_map(|m| split_sentences(&m.content))
+            .filter(|s| s.ends_with('?'))
+            .map(|s| normalize(&s))
+            .collect();
+        if previous.is_empty() {
+            return FilterResult::pass(content);
+        }
+
+        let sentences = sentence_spans(&content);
+        let total = sentences.len();
+        let kept = retain_sentences(sentences, |s| !(s.text.ends_with('?') && previous.contains(&normalize(&s.text))));
+
+        // Never strip a reply down to nothing
+        if kept.len() == total || kept.is_empty() {
+            return FilterResult::pass(content);
+        }
+        FilterResult::fixed(join_sentences(&kept), "repeated question")
+    }
+}
+
+// ============ Length ============
+
+/// Keep replies within the length budget for this message (see ResponseScale)
+pub struct LengthClampFilter;
+
+impl ResponseFilter for LengthClampFilter {
+    fn name(&self) -> &'static str {
+        "length"
+    }
+
+    fn apply(&self, ctx: &FilterContext, content: String) -> FilterResult {
+        let max_sentences = ctx.scale.max_sentences();
+        let max_chars = ctx.scale.max_chars();
+        let sentences = sentence_spans(&content);
+        let total = sentences.len();
+        if total <= max_sentences && content.chars().count() <= max_chars {
+            return FilterResult::pass(content);
+        }
+
+        let mut kept: Vec<SentenceSpan> = Vec::new();
+        for sentence in sentences.into_iter().take(max_sentences) {
+            if !kept.is_empty() && join_sentences(&kept).chars().count() + sentence.text.chars().count() + 1 > max_chars {
+                break;
+            }
+            kept.push(sentence);
+        }
+        if kept.is_empty() {
+            return FilterResult::pass(content);
+        }
+        FilterResult::fixed(join_sentences(&kept), format!("{} sentences / {} chars", total, content.chars().count()))
+    }
+}
+
+// ============ Plain Language ============
+
+static CLAUSE_BREAK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i);\s+| -- |,\s+(and|but|so|because)\s+").unwrap()
+});
+
+/// Split at clause breaks, keeping a conjunction at the front of the clause it introduced
+fn split_clauses(sentence: &str) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut lead = String::new();
+    for caps in CLAUSE_BREAK.captures_iter(sentence) {
+        let m = caps.get(0).unwrap();
+        pieces.push(format!("{}{}", lead, sentence[start..m.start()].trim()));
+        lead = caps.get(1).map(|c| format!("{} ", c.as_str())).unwrap_or_default();
+        start = m.end();
+    }
+    pieces.push(format!("{}{}", lead, sentence[start..].trim()));
+    pieces.into_iter().filter(|p| !p.trim().is_empty()).collect()
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// In plain-language mode, split sentences over the word budget at clause breaks
+pub struct PlainLanguageFilter;
+
+impl ResponseFilter for PlainLanguageFilter {
+    fn name(&self) -> &'static str {
+        "plain_language"
+    }
+
+    fn apply(&self, ctx: &FilterContext, content: String) -> FilterResult {
+        let Some(level) = ctx.reading_level else {
+            return FilterResult::pass(content);
+        };
+        let max_words = readability::max_sentence_words(level);
+
+        let mut split_count = 0;
+        let mut out: Vec<SentenceSpan> = Vec::new();
+        for sentence in sentence_spans(&content) {
+            if sentence.text.split_whitespace().count() <= max_words || !CLAUSE_BREAK.is_match(&sentence.text) {
+                out.push(sentence);
+                continue;
+            }
+            let pieces = split_clauses(&sentence.text);
+            let last = pieces.len() - 1;
+            for (i, piece) in pieces.iter().enumerate() {
+                let piece = capitalize(piece);
+                // The new sentences sit side by side; the original break follows the last one
+                let (text, separator) = if i < last && !piece.ends_with(['.', '!', '?']) {
+                    (format!("{}.", piece), " ".to_string())
+                } else if i < last {
+                    (piece, " ".to_string())
+                } else {
+                    (piece, sentence.separator.clone())
+                };
+                out.push(SentenceSpan { text, separator });
+            }
+            split_count += 1;
+        }
+
+        if split_count == 0 {
+            return FilterResult::pass(content);
+        }
+        FilterResult::fixed(join_sentences(&out), format!("{} long sentences split", split_count))
+    }
+}
+
+/// Late at night, Instinct doesn't get to push the user into doing things
+pub struct LateNightFilter;
+
+impl ResponseFilter for LateNightFilter {
+    fn name(&self) -> &'static str {
+        "late_night"
+    }
+
+    fn apply(&self, ctx: &FilterContext, content: String) -> FilterResult {
+        if !ctx.late_night || ctx.agent != "instinct" || !late_night::PRODUCTIVITY_PUSH.is_match(&content) {
+            return FilterResult::pass(content);
+        }
+        let sentences = sentence_spans(&content);
+        let total = sentences.len();
+        let kept = retain_sentences(sentences, |s| !late_night::PRODUCTIVITY_PUSH.is_match(&s.text));
+        let dropped = total - kept.len();
+        let text = if kept.is_empty() { late_night::FALLBACK_LINE.to_string() } else { join_sentences(&kept) };
+        FilterResult::fixed(text, format!("{} productivity pushes dropped", dropped))
+    }
+}
+
+// ============ Pipeline ============
+
+/// The default filter order. Boundaries (and late-night trimming) run before the length
+/// clamp so softening isn't undone by truncation choosing the wrong sentences; plain-language splitting
+/// runs last so it never pushes content past the clamp.
+pub fn default_pipeline() -> Vec<Box<dyn ResponseFilter>> {
+    vec![
+        Box::new(FormattingFilter),
+        Box::new(ProfanityFilter),
+        Box::new(BoundaryFilter),
+        Box::new(LateNightFilter),
+        Box::new(RepeatedQuestionFilter { lookback: 5 }),
+        Box::new(LengthClampFilter),
+        Box::new(PlainLanguageFilter),
+    ]
+}
+
+static PIPELINE: Lazy<Vec<Box<dyn ResponseFilter>>> = Lazy::new(default_pipeline);
+
+/// Run a reply through the pipeline, logging and recording every violation
+pub fn run_pipeline(ctx: &FilterContext, content: String) -> String {
+    let mut text = content;
+    for filter in PIPELINE.iter() {
+        let result = filter.apply(ctx, text);
+        if let Some(violation) = &result.violation {
+            logging::log_agent(ctx.conversation_id, &format!(
+                "[FILTER] {} on {}: {}", filter.name(), ctx.agent, violation
+            ));
+            let _ = db::save_filter_violation(ctx.conversation_id, ctx.agent, filter.name(), violation);
+        }
+        text = result.content;
+    }
+    text
+}
@@ -0,0 +1,135 @@
+//! Localization of built-in prompts and UI strings
+//!
+//! Each locale has a bundle in `locales/<locale>.json` compiled into the binary:
+//! `strings` for anything the user reads (agent names, UI labels, notices) and
+//! `prompts` for localized prompt sets (Disco voices, the dream voice, and a
+//! reply-language instruction). Rows in `locale_strings` override the bundle, so
+//! a locale can be corrected or added without a rebuild; prompt overrides use the
+//! `prompt.` key prefix. The active locale is the `language` setting.
+//!
+//! Strings fall back to English; prompts fall back to the built-in English
+//! prompt in code.
+
+use crate::db;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use ts_rs::TS;
+
+pub const LANGUAGE_KEY: &str = "language";
+pub const DEFAULT_LOCALE: &str = "en";
+const PROMPT_PREFIX: &str = "prompt.";
+
+const BUNDLED: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.json")),
+    ("de", include_str!("../locales/de.json")),
+];
+
+#[derive(Debug, Deserialize, Default)]
+struct Bundle {
+    #[serde(default)]
+    strings: HashMap<String, String>,
+    #[serde(default)]
+    prompts: HashMap<String, String>,
+}
+
+static BUNDLES: Lazy<HashMap<&'static str, Bundle>> = Lazy::new(|| {
+    BUNDLED
+        .iter()
+        .map(|(locale, json)| (*locale, serde_json::from_str(json).expect("invalid bundled locale")))
+        .collect()
+});
+
+/// Everything the frontend needs to render in one locale
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct LocaleBundle {
+    pub locale: String,
+    pub available: Vec<String>,
+    pub strings: HashMap<String, String>,
+}
+
+fn normalize(locale: &str) -> String {
+    locale.trim().to_lowercase().replace('_', "-")
+}
+
+/// Bundled locales plus any that only exist as overrides
+pub fn available_locales() -> Vec<String> {
+    let mut locales: Vec<String> = BUNDLED.iter().map(|(l, _)| l.to_string()).collect();
+    for locale in db::get_override_locales().unwrap_or_default() {
+        if !locales.contains(&locale) {
+            locales.push(locale);
+        }
+    }
+    locales
+}
+
+pub fn current_locale() -> String {
+    db::get_setting(LANGUAGE_KEY)
+        .ok()
+        .flatten()
+        .map(|l| normalize(&l))
+        .filter(|l| !l.is_empty())
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+pub fn set_locale(locale: &str) -> Result<String, String> {
+    let locale = normalize(locale);
+    if !available_locales().contains(&locale) {
+        return Err(format!("Unsupported language: {}", locale));
+    }
+    db::set_setting(LANGUAGE_KEY, &locale).map_err(|e| e.to_string())?;
+    Ok(locale)
+}
+
+/// Look up a string in the active locale, falling back to English
+pub fn text(key: &str) -> Option<String> {
+    let locale = current_locale();
+    db::get_locale_string(&locale, key)
+        .ok()
+        .flatten()
+        .or_else(|| BUNDLES.get(locale.as_str()).and_then(|b| b.strings.get(key).cloned()))
+        .or_else(|| BUNDLES.get(DEFAULT_LOCALE).and_then(|b| b.strings.get(key).cloned()))
+}
+
+/// A localized string with `{name}`-style placeholders filled in
+pub fn format(key: &str, default: &str, args: &[(&str, &str)]) -> String {
+    let mut out = text(key).unwrap_or_else(|| default.to_string());
+    for (name, value) in args {
+        out = out.replace(&format!("{{{}}}", name), value);
+    }
+    out
+}
+
+/// The active locale's variant of a built-in prompt, if it has one
+pub fn prompt(key: &str) -> Option<String> {
+    let locale = current_locale();
+    db::get_locale_string(&locale, &format!("{}{}", PROMPT_PREFIX, key))
+        .ok()
+        .flatten()
+        .or_else(|| BUNDLES.get(locale.as_str()).and_then(|b| b.prompts.get(key).cloned()))
+}
+
+/// Instruction appended to agent prompts so replies come back in the user's language
+pub fn reply_language_suffix() -> String {
+    prompt("reply_language").map(|p| format!("\n\n{}", p)).unwrap_or_default()
+}
+
+/// UI strings for a locale (default: the active one), English filling the gaps
+pub fn bundle(locale: Option<&str>) -> LocaleBundle {
+    let locale = locale.map(normalize).unwrap_or_else(current_locale);
+    let mut strings = BUNDLES.get(DEFAULT_LOCALE).map(|b| b.strings.clone()).unwrap_or_default();
+    if let Some(b) = BUNDLES.get(locale.as_str()) {
+        strings.extend(b.strings.clone());
+    }
+    for (key, value) in db::get_locale_strings(&locale).unwrap_or_default() {
+        if !key.starts_with(PROMPT_PREFIX) {
+            strings.insert(key, value);
+        }
+    }
+    LocaleBundle {
+        available: available_locales(),
+        locale,
+        strings,
+    }
+}
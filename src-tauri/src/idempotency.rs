@@ -0,0 +1,54 @@
+//! Duplicate send protection
+//!
+//! The frontend passes a client-generated key with each send. A key can be
+//! claimed once per window: double-clicks and IPC retries of a send that's in
+//! flight or already succeeded are refused instead of dispatched (and billed)
+//! twice. A send that fails before its message is saved releases its key; once
+//! the message is saved the key stays claimed, and a failed one is retried by
+//! message id, under a claim of its own for as long as the retry runs.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a completed key stays claimed
+const WINDOW: Duration = Duration::from_secs(10 * 60);
+
+static CLAIMED: Lazy<Mutex<HashMap<String, Instant>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Held for the duration of a send. Dropping it without `complete()` releases the key.
+pub struct Claim {
+    key: String,
+    completed: bool,
+}
+
+impl Claim {
+    /// Keep the key claimed for the rest of the window
+    pub fn complete(mut self) {
+        self.completed = true;
+    }
+}
+
+impl Drop for Claim {
+    fn drop(&mut self) {
+        if !self.completed {
+            if let Ok(mut claimed) = CLAIMED.lock() {
+                claimed.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Claim a key, or None if it was already claimed within the window
+pub fn claim(key: &str) -> Option<Claim> {
+    let mut claimed = CLAIMED.lock().ok()?;
+    let now = Instant::now();
+    claimed.retain(|_, at| now.duration_since(*at) < WINDOW);
+
+    if claimed.contains_key(key) {
+        return None;
+    }
+    claimed.insert(key.to_string(), now);
+    Some(Claim { key: key.to_string(), completed: false })
+}
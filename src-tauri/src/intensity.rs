@@ -0,0 +1,110 @@
+//! Session "temperature" for disco mode
+//!
+//! Each conversation carries an intensity level (0.0-1.0) that ramps up with
+//! engagement -- quick replies, long messages, explicit pushback -- and cools off
+//! when the user slows down or asks for less. It never exceeds the user's
+//! configured ceiling. Disco agents get a directive matching the current level.
+
+use crate::db;
+use chrono::{DateTime, Utc};
+
+pub const CEILING_SETTING_KEY: &str = "disco_intensity_ceiling";
+pub const DEFAULT_CEILING: f64 = 0.7;
+
+/// Per-turn cooling so intensity drifts down without fresh signals
+const DECAY_PER_TURN: f64 = 0.02;
+
+/// The user's configured ceiling (0.0-1.0)
+pub fn ceiling() -> f64 {
+    db::get_setting(CEILING_SETTING_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(DEFAULT_CEILING)
+        .clamp(0.0, 1.0)
+}
+
+/// Engagement signals read from the latest user message
+#[derive(Debug, Clone, Default)]
+pub struct EngagementSignals {
+    pub reply_secs: Option<i64>,
+    pub word_count: usize,
+    pub pushback: bool,
+    pub deescalate: bool,
+}
+
+impl EngagementSignals {
+    pub fn read(user_message: &str, last_agent_timestamp: Option<&str>, now: DateTime<Utc>) -> Self {
+        let msg_lower = user_message.to_lowercase();
+        let reply_secs = last_agent_timestamp
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .map(|ts| (now - ts.with_timezone(&Utc)).num_seconds());
+
+        let pushback_markers = ["no,", "wrong", "disagree", "that's not", "thats not", "bullshit",
+            "come on", "you're wrong", "youre wrong", "prove it", "fight me", "bring it", "go harder"];
+        let deescalate_markers = ["calm down", "too much", "tone it down", "chill", "slow down",
+            "be nicer", "gentler", "back off", "stop yelling"];
+
+        Self {
+            reply_secs,
+            word_count: user_message.split_whitespace().count(),
+            pushback: pushback_markers.iter().any(|m| msg_lower.contains(m)),
+            deescalate: deescalate_markers.iter().any(|m| msg_lower.contains(m)),
+        }
+    }
+
+    /// Signed change to intensity implied by these signals
+    pub fn delta(&self) -> f64 {
+        if self.deescalate {
+            return -0.25;
+        }
+
+        let mut delta = -DECAY_PER_TURN;
+        match self.reply_secs {
+            Some(s) if s < 20 => delta += 0.08,
+            Some(s) if s < 60 => delta += 0.04,
+            Some(s) if s > 300 => delta -= 0.05,
+            _ => {}
+        }
+        if self.word_count > 40 {
+            delta += 0.05;
+        } else if self.word_count < 5 {
+            delta -= 0.02;
+        }
+        if self.pushback {
+            delta += 0.12;
+        }
+        delta
+    }
+}
+
+/// Next intensity level, bounded by [0, ceiling]
+pub fn next_intensity(current: f64, signals: &EngagementSignals, ceiling: f64) -> f64 {
+    (current + signals.delta()).clamp(0.0, ceiling)
+}
+
+/// Human-readable band for a level
+pub fn label(level: f64) -> &'static str {
+    match level {
+        l if l < 0.25 => "simmer",
+        l if l < 0.5 => "warm",
+        l if l < 0.75 => "heated",
+        _ => "boiling",
+    }
+}
+
+/// Prompt directive for disco agents at this level
+pub fn directive(level: f64) -> String {
+    let guidance = match label(level) {
+        "simmer" => "Keep the edge but hold back -- this is still warming up.",
+        "warm" => "They're engaged. Push a little harder than usual.",
+        "heated" => "They're pushing back and moving fast. Match it -- sharper, blunter, less hedging.",
+        _ => "Full intensity. They want the fight. No softening.",
+    };
+    format!(
+        "\n\n---\n\nSESSION INTENSITY: {:.0}/10 ({}). {}",
+        level * 10.0,
+        label(level),
+        guidance
+    )
+}
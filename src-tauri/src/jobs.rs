@@ -0,0 +1,270 @@
+//! Background maintenance jobs
+//!
+//! A single loop started from `init_app` that wakes periodically and runs each
+//! housekeeping job in turn. Jobs are independent: a failure is logged and the
+//! loop moves on. Heavy jobs (anything that calls an LLM) wait for AC power.
+//!
+//! They also wait for consent: each launch the app shows what it intends to do
+//! in the background and roughly what it will cost (`pending_work`), and heavy
+//! jobs stay held until the user approves it. Deferring holds them for a day.
+
+use crate::chapters;
+use crate::db;
+use crate::digest;
+use crate::logging;
+use crate::memory;
+use crate::notes;
+use crate::power;
+use crate::proactive;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use ts_rs::TS;
+
+/// How often the job loop wakes up
+const TICK_SECS: u64 = 5 * 60;
+
+pub const AUTO_CLOSE_DAYS_SETTING_KEY: &str = "auto_close_after_days";
+pub const DEFAULT_AUTO_CLOSE_DAYS: i64 = 7;
+
+/// Trashed conversations are recoverable for this long, then purged
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+static STARTED: AtomicBool = AtomicBool::new(false);
+static STOPPED: AtomicBool = AtomicBool::new(false);
+
+/// Start the job loop (idempotent -- init_app may run more than once per process)
+pub fn start(app_handle: tauri::AppHandle) {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(TICK_SECS));
+        loop {
+            interval.tick().await;
+            if STOPPED.load(Ordering::SeqCst) {
+                break;
+            }
+            run_all(&app_handle).await;
+        }
+    });
+
+    logging::log_conversation(None, "Background jobs started");
+}
+
+/// End the job loop for good, at its next tick (used by the full data wipe)
+pub fn stop() {
+    STOPPED.store(true, Ordering::SeqCst);
+}
+
+/// Run every job once
+pub async fn run_all(app_handle: &tauri::AppHandle) {
+    // ===== Heavy jobs: deferred on battery / low-power mode =====
+    if power::should_defer_background_work() {
+        logging::log_conversation(None, "[JOBS] On battery -- deferring heavy jobs until AC power");
+    } else if !heavy_work_approved() {
+        logging::log_conversation(None, "[JOBS] Heavy jobs held until background work is approved");
+    } else {
+        if let Err(e) = auto_close_inactive_conversations().await {
+            logging::log_error(None, &format!("[JOBS] auto-close failed: {}", e));
+        }
+        if let Err(e) = digest::run_weekly_digest().await {
+            logging::log_error(None, &format!("[JOBS] weekly digest failed: {}", e));
+        }
+        if let Err(e) = digest::run_monthly_highlights().await {
+            logging::log_error(None, &format!("[JOBS] monthly highlights failed: {}", e));
+        }
+    }
+    
+    // ===== Light jobs =====
+    if let Err(e) = memory::promote_staged_memory() {
+        logging::log_error(None, &format!("[JOBS] memory promotion failed: {}", e));
+    }
+    if let Err(e) = memory::decay_facts() {
+        logging::log_error(None, &format!("[JOBS] fact decay failed: {}", e));
+    }
+    if let Err(e) = memory::expire_facts() {
+        logging::log_error(None, &format!("[JOBS] fact expiry failed: {}", e));
+    }
+    if let Err(e) = memory::decay_themes() {
+        logging::log_error(None, &format!("[JOBS] theme decay failed: {}", e));
+    }
+    if let Err(e) = chapters::detect_auto() {
+        logging::log_error(None, &format!("[JOBS] chapter detection failed: {}", e));
+    }
+    if let Err(e) = proactive::deliver_due(app_handle) {
+        logging::log_error(None, &format!("[JOBS] proactive delivery failed: {}", e));
+    }
+    if let Err(e) = purge_expired_trash() {
+        logging::log_error(None, &format!("[JOBS] trash purge failed: {}", e));
+    }
+    if let Err(e) = notes::sync() {
+        logging::log_error(None, &format!("[JOBS] notes folder sync failed: {}", e));
+    }
+}
+
+fn auto_close_days() -> i64 {
+    db::get_setting(AUTO_CLOSE_DAYS_SETTING_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|d| *d > 0)
+        .unwrap_or(DEFAULT_AUTO_CLOSE_DAYS)
+}
+
+/// Close conversations with no messages for N days, finalizing (summary + themes) first
+pub async fn auto_close_inactive_conversations() -> Result<usize, String> {
+    let cutoff = (Utc::now() - Duration::days(auto_close_days())).to_rfc3339();
+    let ids = db::get_inactive_conversation_ids(&cutoff).map_err(|e| e.to_string())?;
+
+    for id in &ids {
+        // Finalization is a no-op for already-processed conversations
+        if let Err(e) = crate::finalize_conversation_internal(id).await {
+            logging::log_error(Some(id), &format!("[JOBS] Finalize before auto-close failed: {}", e));
+            continue;
+        }
+        db::close_conversation(id).map_err(|e| e.to_string())?;
+        logging::log_conversation(Some(id), "[JOBS] Auto-closed after inactivity");
+    }
+
+    if !ids.is_empty() {
+        logging::log_conversation(None, &format!("[JOBS] Auto-closed {} inactive conversations", ids.len()));
+    }
+    Ok(ids.len())
+}
+
+/// Permanently delete conversations that have sat in the trash past the retention window
+pub fn purge_expired_trash() -> Result<usize, String> {
+    let cutoff = (Utc::now() - Duration::days(TRASH_RETENTION_DAYS)).to_rfc3339();
+    let purged = db::purge_trash(&cutoff).map_err(|e| e.to_string())?;
+    if purged > 0 {
+        logging::log_conversation(None, &format!("[JOBS] Purged {} conversations from the trash", purged));
+    }
+    Ok(purged)
+}
+
+// ============ Pending Work ============
+
+pub const BACKGROUND_DEFERRED_UNTIL_KEY: &str = "background_deferred_until";
+
+/// How long "defer all" holds heavy jobs
+const DEFER_ALL_HOURS: i64 = 24;
+/// Trailing window the per-call cost estimates are averaged over
+const COST_WINDOW_DAYS: i64 = 30;
+/// Estimate for a call category that hasn't been used yet (roughly one Opus call)
+const FALLBACK_CALL_COST_USD: f64 = 0.10;
+
+/// Approved this launch; heavy jobs never start on their own
+static APPROVED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct PendingJob {
+    pub job: String,
+    pub description: String,
+    pub items: usize,
+    pub llm_calls: usize,
+    pub estimated_cost_usd: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct PendingBackgroundWork {
+    pub jobs: Vec<PendingJob>,
+    pub estimated_cost_usd: f64,
+    pub approved: bool,                  // Heavy jobs may run this launch
+    pub deferred_until: Option<String>,  // Set by "defer all"; approval clears it
+    pub power_deferring: bool,           // On battery, so heavy jobs wait regardless
+}
+
+fn deferred_until() -> Option<DateTime<Utc>> {
+    db::get_setting(BACKGROUND_DEFERRED_UNTIL_KEY).ok().flatten()
+        .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+        .map(|t| t.with_timezone(&Utc))
+        .filter(|t| *t > Utc::now())
+}
+
+fn heavy_work_approved() -> bool {
+    APPROVED.load(Ordering::SeqCst) && deferred_until().is_none()
+}
+
+/// Estimated cost of one call in a usage category, from recent usage
+fn call_cost(category: &str) -> f64 {
+    let since = (Utc::now() - Duration::days(COST_WINDOW_DAYS)).to_rfc3339();
+    db::get_average_call_cost(category, &since).ok().flatten().unwrap_or(FALLBACK_CALL_COST_USD)
+}
+
+/// What the job loop intends to do, and roughly what it will cost
+pub fn pending_work() -> Result<PendingBackgroundWork, String> {
+    let mut jobs = Vec::new();
+
+    // Closing a conversation summarizes it and extracts memory from it
+    let cutoff = (Utc::now() - Duration::days(auto_close_days())).to_rfc3339();
+    let unprocessed = db::get_inactive_conversation_ids(&cutoff).map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|id| db::get_conversation(id).ok().flatten().map(|c| !c.processed).unwrap_or(false))
+        .count();
+    if unprocessed > 0 {
+        jobs.push(PendingJob {
+            job: "auto_close".to_string(),
+            description: format!("Summarize and close conversations idle for {} days", auto_close_days()),
+            items: unprocessed,
+            llm_calls: unprocessed * 2,
+            estimated_cost_usd: unprocessed as f64 * (call_cost("summary") + call_cost("memory")),
+        });
+    }
+    if digest::weekly_reflection_pending() {
+        jobs.push(PendingJob {
+            job: "weekly_digest".to_string(),
+            description: "Write and email last week's reflection".to_string(),
+            items: 1,
+            llm_calls: 1,
+            estimated_cost_usd: call_cost("digest"),
+        });
+    }
+    if digest::monthly_highlights_pending() {
+        jobs.push(PendingJob {
+            job: "monthly_highlights".to_string(),
+            description: "Pick last month's highlights".to_string(),
+            items: 1,
+            llm_calls: 1,
+            estimated_cost_usd: call_cost("digest"),
+        });
+    }
+    // Free, but still something the app does on its own
+    let due_check_ins = db::get_due_proactive_messages(&Utc::now().to_rfc3339()).map_err(|e| e.to_string())?.len();
+    if due_check_ins > 0 {
+        jobs.push(PendingJob {
+            job: "check_ins".to_string(),
+            description: "Deliver scheduled check-ins".to_string(),
+            items: due_check_ins,
+            llm_calls: 0,
+            estimated_cost_usd: 0.0,
+        });
+    }
+
+    Ok(PendingBackgroundWork {
+        estimated_cost_usd: jobs.iter().map(|j| j.estimated_cost_usd).sum(),
+        jobs,
+        approved: heavy_work_approved(),
+        deferred_until: deferred_until().map(|t| t.to_rfc3339()),
+        power_deferring: power::should_defer_background_work(),
+    })
+}
+
+/// Let heavy jobs run for the rest of this launch (lifts any deferral)
+pub fn approve() -> Result<PendingBackgroundWork, String> {
+    db::set_setting(BACKGROUND_DEFERRED_UNTIL_KEY, "").map_err(|e| e.to_string())?;
+    APPROVED.store(true, Ordering::SeqCst);
+    logging::log_conversation(None, "[JOBS] Background work approved");
+    pending_work()
+}
+
+/// Hold every heavy job for a day, across restarts
+pub fn defer_all() -> Result<PendingBackgroundWork, String> {
+    let until = (Utc::now() + Duration::hours(DEFER_ALL_HOURS)).to_rfc3339();
+    db::set_setting(BACKGROUND_DEFERRED_UNTIL_KEY, &until).map_err(|e| e.to_string())?;
+    logging::log_conversation(None, &format!("[JOBS] Background work deferred until {}", until));
+    pending_work()
+}
@@ -0,0 +1,141 @@
+//! Journal entries and dream analysis
+//!
+//! Plain notes are stored as-is. Dream entries get their own extraction pass
+//! (symbols and recurring imagery tracked in `dream_motifs`) and an optional
+//! reflection voiced by Puff as Inland Empire.
+
+use crate::anthropic::{AnthropicClient, AnthropicMessage, ThinkingBudget, CLAUDE_SONNET};
+use crate::boundaries;
+use crate::db::{self, DreamMotif, JournalEntry};
+use crate::disco_prompts;
+use crate::logging;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+pub const ENTRY_TYPE_NOTE: &str = "note";
+pub const ENTRY_TYPE_DREAM: &str = "dream";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExtractedMotif {
+    pub motif: String,
+    pub motif_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DreamExtraction {
+    pub motifs: Vec<ExtractedMotif>,
+}
+
+pub struct DreamAnalyzer {
+    client: AnthropicClient,
+}
+
+impl DreamAnalyzer {
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            client: AnthropicClient::new(api_key).with_usage_category("journal"),
+        }
+    }
+
+    /// Extract symbols and imagery from a dream and record them as motifs
+    pub async fn extract_motifs(&self, entry: &JournalEntry) -> Result<DreamExtraction, Box<dyn Error + Send + Sync>> {
+        let system_prompt = r#"You are the dream extraction system for Intersect. Your job is to pull the symbols and imagery out of a dream the user wrote down.
+
+EXTRACT MOTIFS:
+   Types: "symbol", "imagery", "figure", "place", "emotion"
+   - Short noun phrases, lowercase ("the old house", "falling", "my father", "deep water")
+   - Normalize wording so the same motif matches across dreams ("ocean" and "the sea" -> "the sea")
+   - 2-8 motifs per dream; only what is actually in the dream
+
+IMPORTANT:
+- Do NOT interpret. No meanings, no symbolism explanations.
+
+Respond with ONLY valid JSON in this exact format:
+{
+  "motifs": [{"motif": "...", "motif_type": "symbol"}]
+}"#;
+
+        let messages = vec![
+            AnthropicMessage {
+                role: "user".to_string(),
+                content: format!("DREAM:\n{}\n\nExtract the motifs:", entry.content),
+            },
+        ];
+
+        let response = self.client.chat_completion_advanced(
+            CLAUDE_SONNET,
+            Some(system_prompt),
+            messages,
+            0.2,
+            Some(400),
+            ThinkingBudget::None
+        ).await?;
+
+        let cleaned = response
+            .trim()
+            .trim_start_matches("```json")
+            .trim_end_matches("```")
+            .trim();
+
+        let result: DreamExtraction = match serde_json::from_str(cleaned) {
+            Ok(r) => r,
+            Err(e) => {
+                logging::log_error(None, &format!(
+                    "Failed to parse dream extraction JSON: {}. Response: {}", e, cleaned.chars().take(200).collect::<String>()
+                ));
+                DreamExtraction::default()
+            }
+        };
+
+        for motif in &result.motifs {
+            let _ = db::save_dream_motif(motif.motif.trim(), &motif.motif_type, &entry.id);
+        }
+
+        logging::log_memory(None, &format!(
+            "Extracted {} dream motifs from entry {}", result.motifs.len(), entry.id
+        ));
+
+        Ok(result)
+    }
+
+    /// Inland Empire's reflection on a dream, aware of motifs that keep returning
+    pub async fn reflect(&self, entry: &JournalEntry) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let recurring: Vec<DreamMotif> = db::get_dream_motifs(10)?
+            .into_iter()
+            .filter(|m| m.frequency > 1)
+            .collect();
+
+        let recurring_context = if recurring.is_empty() {
+            "No recurring motifs yet.".to_string()
+        } else {
+            recurring
+                .iter()
+                .map(|m| format!("- {} ({}, seen {} times)", m.motif, m.motif_type, m.frequency))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let messages = vec![
+            AnthropicMessage {
+                role: "user".to_string(),
+                content: format!(
+                    "MOTIFS THAT KEEP RETURNING IN THEIR DREAMS:\n{}\n\nTHE DREAM:\n{}",
+                    recurring_context, entry.content
+                ),
+            },
+        ];
+
+        let reflection = self.client.chat_completion_advanced(
+            CLAUDE_SONNET,
+            Some(&format!("{}{}", disco_prompts::dream_prompt(), boundaries::house_rules_section())),
+            messages,
+            0.9,
+            Some(250),
+            ThinkingBudget::None
+        ).await?;
+
+        let reflection = reflection.trim().to_string();
+        db::set_journal_reflection(&entry.id, &reflection)?;
+        Ok(reflection)
+    }
+}
@@ -0,0 +1,89 @@
+//! API keys in the OS keychain
+//!
+//! The OpenAI and Anthropic keys, and the SMTP password used for sharing, live
+//! in the platform credential store (macOS Keychain, Windows Credential Manager,
+//! Secret Service on Linux) under the app identifier, never in the database.
+//! Earlier versions kept the keys in plaintext `user_profile` columns and the
+//! password in `app_settings`; `db::move_keys_to_keychain` and
+//! `db::move_smtp_password_to_keychain` move them over once.
+//!
+//! Each local profile has its own entries: the default profile uses the plain
+//! account names, others suffix them with the profile id.
+//!
+//! Reads are cached for the life of the process, since the profile (and with it
+//! the keys) is fetched on every message.
+
+use crate::logging;
+use crate::profiles;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const SERVICE: &str = "com.intersect.app";
+
+pub const OPENAI_KEY: &str = "openai_api_key";
+pub const ANTHROPIC_KEY: &str = "anthropic_api_key";
+pub const SMTP_PASSWORD: &str = "share_smtp_password";
+pub const ALL_KEYS: [&str; 3] = [OPENAI_KEY, ANTHROPIC_KEY, SMTP_PASSWORD];
+
+/// (profile id, account)
+type CacheKey = (String, &'static str);
+
+static CACHE: Lazy<Mutex<HashMap<CacheKey, Option<String>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn entry(profile_id: &str, account: &str) -> Result<keyring::Entry, String> {
+    let account = if profile_id == profiles::DEFAULT_PROFILE_ID {
+        account.to_string()
+    } else {
+        format!("{}:{}", account, profile_id)
+    };
+    keyring::Entry::new(SERVICE, &account).map_err(|e| format!("Keychain unavailable: {}", e))
+}
+
+/// The active profile's stored key, or None when there isn't one (or the keychain can't be read)
+pub fn get(account: &'static str) -> Option<String> {
+    let profile_id = profiles::current_id();
+    if let Some(cached) = CACHE.lock().unwrap().get(&(profile_id.clone(), account)) {
+        return cached.clone();
+    }
+    let value = match entry(&profile_id, account).map(|e| e.get_password()) {
+        Ok(Ok(value)) => Some(value).filter(|v| !v.is_empty()),
+        // The normal "no key yet" case
+        Ok(Err(keyring::Error::NoEntry)) => None,
+        Ok(Err(e)) => {
+            // Not cached, so the next read tries again
+            logging::log_error(None, &format!("[KEYCHAIN] Failed to read {}: {}", account, e));
+            return None;
+        }
+        Err(e) => {
+            logging::log_error(None, &format!("[KEYCHAIN] {}", e));
+            return None;
+        }
+    };
+    CACHE.lock().unwrap().insert((profile_id, account), value.clone());
+    value
+}
+
+pub fn set(account: &'static str, value: &str) -> Result<(), String> {
+    let profile_id = profiles::current_id();
+    entry(&profile_id, account)?
+        .set_password(value)
+        .map_err(|e| format!("Failed to save {} to the keychain: {}", account, e))?;
+    CACHE.lock().unwrap().insert((profile_id, account), Some(value.to_string()));
+    Ok(())
+}
+
+/// Remove one of the active profile's keys; removing one that isn't there is not an error
+pub fn delete(account: &'static str) -> Result<(), String> {
+    delete_for_profile(&profiles::current_id(), account)
+}
+
+pub fn delete_for_profile(profile_id: &str, account: &'static str) -> Result<(), String> {
+    match entry(profile_id, account)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => {
+            CACHE.lock().unwrap().insert((profile_id.to_string(), account), None);
+            Ok(())
+        }
+        Err(e) => Err(format!("Failed to remove {} from the keychain: {}", account, e)),
+    }
+}
@@ -0,0 +1,98 @@
+//! Late-night mode
+//!
+//! A persona profile can carry a late-night window (local "HH:MM" to "HH:MM",
+//! may wrap past midnight). Inside it the app winds down on its own terms rather
+//! than trusting the models to: disco intensity is capped low, routing weights
+//! lean toward Psyche and away from Instinct, every agent is told to favor rest,
+//! and the filter pipeline drops Instinct sentences that push the user to get
+//! something done tonight.
+
+use crate::db;
+use crate::policy;
+use chrono::Local;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Highest disco intensity allowed inside the window ("simmer")
+pub const INTENSITY_CEILING: f64 = 0.2;
+
+/// Routing weight multipliers inside the window
+const INSTINCT_FACTOR: f64 = 0.5;
+const PSYCHE_FACTOR: f64 = 1.5;
+
+/// Instinct lines that push toward action now rather than rest
+pub static PRODUCTIVITY_PUSH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(get (up|going|it done|to work|moving)|do it (now|tonight)|right now|grind|hustle|push through|knock (it|this) out|finish (it|this) tonight|start (now|tonight)|no excuses|stop (stalling|procrastinating)|crank (it|this) out|pull an all-?nighter|one more (hour|task))\b").unwrap()
+});
+
+/// What Instinct says instead when every sentence was a push
+pub const FALLBACK_LINE: &str = "It's late. This'll keep till morning.";
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct LateNightWindow {
+    pub start: String,              // Local "HH:MM"
+    pub end: String,                // Local "HH:MM", may wrap past midnight
+    pub active_now: bool,
+}
+
+fn window_active(start: &str, end: &str) -> bool {
+    match (policy::parse_time(start), policy::parse_time(end)) {
+        (Some(start), Some(end)) => policy::in_window(Local::now().time(), start, end),
+        _ => false,
+    }
+}
+
+pub fn get_window(profile_id: &str) -> Result<Option<LateNightWindow>, String> {
+    let window = db::get_profile_late_night_window(profile_id).map_err(|e| e.to_string())?;
+    Ok(window.map(|(start, end)| LateNightWindow {
+        active_now: window_active(&start, &end),
+        start,
+        end,
+    }))
+}
+
+/// Set a profile's window, or clear it with None
+pub fn set_window(profile_id: &str, window: Option<(&str, &str)>) -> Result<(), String> {
+    if let Some((start, end)) = window {
+        for t in [start, end] {
+            policy::parse_time(t).ok_or_else(|| format!("Invalid time (expected HH:MM): {}", t))?;
+        }
+        if start == end {
+            return Err("Late-night window can't start and end at the same time".to_string());
+        }
+    }
+    if !db::set_profile_late_night_window(profile_id, window).map_err(|e| e.to_string())? {
+        return Err("Profile not found".to_string());
+    }
+    Ok(())
+}
+
+/// Whether the active profile's late-night window is in effect right now
+pub fn active() -> bool {
+    db::get_active_persona_profile()
+        .ok()
+        .flatten()
+        .and_then(|p| db::get_profile_late_night_window(&p.id).ok().flatten())
+        .is_some_and(|(start, end)| window_active(&start, &end))
+}
+
+/// Routing weights shifted toward Psyche and away from Instinct
+pub fn bias_weights(weights: (f64, f64, f64)) -> (f64, f64, f64) {
+    (weights.0 * INSTINCT_FACTOR, weights.1, weights.2 * PSYCHE_FACTOR)
+}
+
+/// Prompt directive for an agent inside the window
+pub fn directive(agent: &str) -> String {
+    let own = match agent {
+        "instinct" => " Do not push the user to act, decide, finish, or start anything tonight. Your gut call now is rest; the move can wait until morning.",
+        "psyche" => " Lead with how they're feeling at the end of the day. Help them set things down.",
+        _ => " Keep any analysis light and leave loose ends for tomorrow.",
+    };
+    format!(
+        "\n\nLATE NIGHT: It's late for the user. Be calm and quiet. Favor rest and winding down over productivity.{}",
+        own
+    )
+}
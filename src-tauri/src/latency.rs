@@ -0,0 +1,168 @@
+//! First-token latency SLO for agent replies
+//!
+//! Replies aren't streamed, so "first token" is measured as time to the first
+//! response byte: both clients report it here for every call. A rolling window
+//! per provider gives a p90. When the primary agent provider (OpenAI) runs over
+//! the configured threshold, agents switch to the fallback provider (Claude
+//! Haiku) for a while and an insight says so. After the switch window the
+//! primary is tried again with a fresh window.
+
+use crate::db;
+use crate::logging;
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use ts_rs::TS;
+
+pub const LATENCY_SLO_MS_KEY: &str = "latency_slo_p90_ms";
+pub const DEFAULT_SLO_MS: i64 = 4000;
+
+/// Provider whose latency decides the switch
+const PRIMARY_PROVIDER: &str = "openai";
+
+/// Calls kept per provider for the rolling p90
+const WINDOW: usize = 20;
+/// Don't judge a provider on fewer calls than this
+const MIN_SAMPLES: usize = 5;
+/// How long agents stay on the fallback once switched
+const SWITCH_MINUTES: i64 = 30;
+
+pub const INSIGHT_KIND_LATENCY: &str = "latency";
+
+static SAMPLES: Lazy<Mutex<HashMap<String, VecDeque<i64>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static FALLBACK_UNTIL: Lazy<Mutex<Option<DateTime<Utc>>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ProviderLatency {
+    pub provider: String,
+    pub samples: usize,
+    pub p90_ms: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct LatencyStatus {
+    pub threshold_ms: Option<i64>,  // None = SLO switching off
+    pub providers: Vec<ProviderLatency>,
+    pub fallback_until: Option<String>,
+}
+
+/// p90 threshold in ms; 0 in the setting turns switching off
+pub fn threshold_ms() -> Option<i64> {
+    let value = db::get_setting(LATENCY_SLO_MS_KEY).ok().flatten()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_SLO_MS);
+    (value > 0).then_some(value)
+}
+
+pub fn set_threshold_ms(threshold_ms: i64) -> Result<LatencyStatus, String> {
+    db::set_setting(LATENCY_SLO_MS_KEY, &threshold_ms.max(0).to_string()).map_err(|e| e.to_string())?;
+    if threshold_ms <= 0 {
+        if let Ok(mut until) = FALLBACK_UNTIL.lock() {
+            *until = None;
+        }
+    }
+    Ok(status())
+}
+
+fn p90(samples: &VecDeque<i64>) -> Option<i64> {
+    if samples.len() < MIN_SAMPLES {
+        return None;
+    }
+    let mut sorted: Vec<i64> = samples.iter().copied().collect();
+    sorted.sort_unstable();
+    let index = ((sorted.len() as f64 * 0.9).ceil() as usize).saturating_sub(1);
+    sorted.get(index).copied()
+}
+
+/// Record one call's time to first byte, and switch agents to the fallback if
+/// the primary provider's p90 is now over the threshold
+pub fn observe(provider: &str, first_byte_ms: i64) {
+    let primary_p90 = {
+        let Ok(mut samples) = SAMPLES.lock() else {
+            return;
+        };
+        let window = samples.entry(provider.to_string()).or_default();
+        window.push_back(first_byte_ms);
+        while window.len() > WINDOW {
+            window.pop_front();
+        }
+        if provider != PRIMARY_PROVIDER {
+            return;
+        }
+        match p90(window) {
+            Some(p) => p,
+            None => return,
+        }
+    };
+
+    let Some(threshold) = threshold_ms() else {
+        return;
+    };
+    if primary_p90 <= threshold || prefer_fallback() {
+        return;
+    }
+
+    let until = Utc::now() + Duration::minutes(SWITCH_MINUTES);
+    if let Ok(mut fallback_until) = FALLBACK_UNTIL.lock() {
+        *fallback_until = Some(until);
+    }
+    // Start the primary over with a clean window when it's tried again
+    if let Ok(mut samples) = SAMPLES.lock() {
+        samples.remove(PRIMARY_PROVIDER);
+    }
+
+    let message = format!(
+        "OpenAI has been slow to start replying (p90 {:.1}s against a {:.1}s target), so the agents are answering through Claude for the next {} minutes.",
+        primary_p90 as f64 / 1000.0,
+        threshold as f64 / 1000.0,
+        SWITCH_MINUTES
+    );
+    logging::log_routing(None, &format!("[LATENCY] {}", message));
+    if let Err(e) = db::save_insight(INSIGHT_KIND_LATENCY, &message) {
+        logging::log_error(None, &format!("Failed to save latency insight: {}", e));
+    }
+}
+
+/// Whether agents should currently use the fallback provider
+pub fn prefer_fallback() -> bool {
+    let Ok(mut until) = FALLBACK_UNTIL.lock() else {
+        return false;
+    };
+    match *until {
+        Some(t) if Utc::now() < t => true,
+        Some(_) => {
+            *until = None;
+            logging::log_routing(None, "[LATENCY] Switch window over, agents back on OpenAI");
+            false
+        }
+        None => false,
+    }
+}
+
+pub fn status() -> LatencyStatus {
+    let providers = SAMPLES.lock()
+        .map(|samples| {
+            let mut providers: Vec<ProviderLatency> = samples.iter()
+                .map(|(provider, window)| ProviderLatency {
+                    provider: provider.clone(),
+                    samples: window.len(),
+                    p90_ms: p90(window),
+                })
+                .collect();
+            providers.sort_by(|a, b| a.provider.cmp(&b.provider));
+            providers
+        })
+        .unwrap_or_default();
+
+    LatencyStatus {
+        threshold_ms: threshold_ms(),
+        providers,
+        fallback_until: prefer_fallback()
+            .then(|| FALLBACK_UNTIL.lock().ok().and_then(|u| u.map(|t| t.to_rfc3339())))
+            .flatten(),
+    }
+}
@@ -1,20 +1,68 @@
+mod agents;
+mod anonymize;
 mod anthropic;
+mod archive;
+mod attachments;
+mod boundaries;
+mod budgets;
+mod chapters;
+mod citations;
+mod console;
+mod controls;
+mod conversation_settings;
+mod corrections;
 mod db;
+mod digest;
+mod disagreement;
+mod distill;
 mod disco_prompts;
+mod events;
+mod exercises;
+mod export;
+mod filters;
+mod i18n;
+mod idempotency;
+mod intensity;
+mod jobs;
+mod journal;
+mod keychain;
 mod knowledge;
+mod late_night;
+mod latency;
 mod logging;
 mod memory;
+mod model_routing;
+mod notes;
 mod openai;
 mod orchestrator;
+mod policy;
+mod power;
+mod preview;
+mod proactive;
+mod profiles;
+mod readability;
+mod recall;
+mod repetition;
+mod replay;
+mod seed;
+mod session_timer;
+mod share;
+mod support;
+mod usage;
+mod watchdog;
+mod wipe;
 
 use db::{Message, UserProfile, UserContext};
+use usage::MessageCost;
 use memory::{MemoryExtractor, ConversationSummarizer, UserProfileSummary};
 use orchestrator::{Orchestrator, Agent, ResponseType, AgentResponse, EngagementAnalyzer, IntrinsicTraitAnalyzer, combine_trait_analyses, decide_response_heuristic, decide_grounding_heuristic};
 use serde::{Deserialize, Serialize};
+use ts_rs::TS;
 use chrono::Utc;
 use uuid::Uuid;
 use std::collections::HashMap;
 use std::sync::Mutex;
+use std::time::Instant;
 use once_cell::sync::Lazy;
 
 // ============ Session Weight Storage ============
@@ -57,15 +105,25 @@ fn clear_session_weights(conversation_id: &str) {
     weights.remove(conversation_id);
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default, TS)]
+#[ts(export)]
 pub struct SendMessageResult {
     pub responses: Vec<AgentResponse>,
     pub debate_mode: Option<String>, // "mild" | "intense" | null
     pub weight_change: Option<WeightChangeNotification>,
     pub governor_response: Option<String>, // Governor's synthesized response after reading agent thoughts
+    pub governor_cost: Option<MessageCost>, // Tokens and estimated cost of the Governor's response
+    pub round_id: Option<String>, // The user message this round answers
+    pub failed_agents: Vec<String>, // Agents whose slot holds a retryable stub (see retry_agent)
+    pub memory_correction: Option<corrections::CorrectionOutcome>, // A remembered fact the user just corrected
+    pub related_conversations: Vec<memory::PastConversation>, // On a conversation's first message: past ones on the same topic
+    pub control: Option<Message>, // What a slash command ("/recall", "/mute", ...) saved; the agents don't answer those
+    pub contradiction_corrections: Vec<Message>, // One-line fixes posted when a reply contradicted a remembered fact (auto-correct on)
+    pub disagreement: Option<db::Disagreement>, // Where a rebuttal or debate split from the first voice
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct WeightChangeNotification {
     pub message: String,
     pub old_dominant: String,
@@ -73,7 +131,8 @@ pub struct WeightChangeNotification {
     pub change_type: String, // "shift" | "major_shift" | "minor"
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ConversationInfo {
     pub id: String,
     pub title: Option<String>,
@@ -81,29 +140,89 @@ pub struct ConversationInfo {
     pub is_disco: bool,
     pub created_at: String,
     pub updated_at: String,
+    pub closed_at: Option<String>,
+    pub pinned: bool,
+    pub project_id: Option<i64>,
+    pub locked: bool,
+    pub parent_conversation_id: Option<String>, // Set on forks (see fork_conversation)
 }
 
 // ============ App Initialization ============
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct InitResult {
     pub status: String,            // "ready" | "recovery_needed"
     pub recovered_count: usize,    // Number of conversations needing recovery
 }
 
 #[tauri::command]
-fn init_app(app_handle: tauri::AppHandle) -> Result<InitResult, String> {
-    // Initialize database
-    db::init_database(&app_handle).map_err(|e| e.to_string())?;
+async fn init_app(app_handle: tauri::AppHandle) -> Result<InitResult, String> {
+    db::run(move || open_app(&app_handle)).await
+}
+
+/// Open the active profile's data and start the background services
+fn open_app(app_handle: &tauri::AppHandle) -> Result<InitResult, String> {
+    // Open the active profile's database
+    profiles::load(app_handle);
+    let migration = db::init_database(app_handle).map_err(|e| e.to_string())?;
     
     // Initialize logging
     if let Err(e) = logging::init_logging() {
         eprintln!("Failed to initialize logging: {}", e);
     }
     
+    // Tell the UI what the upgrade (if any) did to the database
+    if migration.changed() {
+        logging::log_conversation(None, &format!(
+            "Database migrated v{} -> v{}: {} tables created, {} columns added in {}ms",
+            migration.from_version,
+            migration.to_version,
+            migration.tables_created.len(),
+            migration.columns_added.len(),
+            migration.duration_ms
+        ));
+    }
+    if !migration.tables_with_fewer_rows.is_empty() {
+        logging::log_error(None, &format!(
+            "Database migration left fewer rows in: {}", migration.tables_with_fewer_rows.join(", ")
+        ));
+    }
+    events::emit(app_handle, events::Event::MigrationReport(migration));
+    
     // Clean up old log files (keep last 7 days)
     let _ = logging::cleanup_old_logs();
     
+    // Seed built-in grounding exercises
+    if let Err(e) = exercises::seed_builtin_exercises() {
+        logging::log_error(None, &format!("Failed to seed grounding exercises: {}", e));
+    }
+    
+    // Seed built-in agent presentation metadata
+    if let Err(e) = agents::seed_builtin_agents() {
+        logging::log_error(None, &format!("Failed to seed agents: {}", e));
+    }
+    
+    // Sends interrupted by the last shutdown can be retried from the UI
+    match db::fail_stale_pending_messages() {
+        Ok(n) if n > 0 => logging::log_conversation(None, &format!("Marked {} interrupted sends as failed", n)),
+        Err(e) => logging::log_error(None, &format!("Failed to mark interrupted sends: {}", e)),
+        _ => {}
+    }
+    
+    // Merge themes saved before they were normalized, and near-duplicates
+    match db::consolidate_recurring_themes() {
+        Ok(n) if n > 0 => logging::log_conversation(None, &format!("Merged {} duplicate recurring themes", n)),
+        Err(e) => logging::log_error(None, &format!("Failed to consolidate recurring themes: {}", e)),
+        _ => {}
+    }
+    
+    // Watch the notes folder, if one is set; the job loop ingests what changes
+    notes::start_watching();
+    
+    // Start background maintenance jobs (auto-close, etc.)
+    jobs::start(app_handle.clone());
+    
     // Check for orphaned conversations from crash/force-quit
     let unprocessed = db::get_conversations_needing_recovery().unwrap_or_default();
     
@@ -127,11 +246,41 @@ fn init_app(app_handle: tauri::AppHandle) -> Result<InitResult, String> {
     })
 }
 
+// ============ Profiles ============
+
+#[tauri::command]
+async fn get_profiles(app_handle: tauri::AppHandle) -> profiles::ProfileList {
+    db::run_infallible(move || profiles::list(&app_handle)).await
+}
+
+#[tauri::command]
+async fn create_profile(app_handle: tauri::AppHandle, name: String) -> Result<profiles::Profile, String> {
+    db::run(move || profiles::create(&app_handle, &name)).await
+}
+
+#[tauri::command]
+async fn rename_profile(app_handle: tauri::AppHandle, id: String, name: String) -> Result<profiles::Profile, String> {
+    db::run(move || profiles::rename(&app_handle, &id, &name)).await
+}
+
+#[tauri::command]
+async fn delete_profile(app_handle: tauri::AppHandle, id: String) -> Result<(), String> {
+    db::run(move || profiles::delete(&app_handle, &id)).await
+}
+
+/// Make another profile active and reopen the app on its data (same result as init_app)
+#[tauri::command]
+async fn switch_profile(app_handle: tauri::AppHandle, id: String) -> Result<InitResult, String> {
+    db::run(move || {
+        profiles::switch(&app_handle, &id)?;
+        open_app(&app_handle)
+    }).await
+}
+
 /// Recover and finalize all unprocessed conversations from crashes/force-quits
 #[tauri::command]
 async fn recover_conversations() -> Result<usize, String> {
-    let unprocessed = db::get_conversations_needing_recovery()
-        .map_err(|e| e.to_string())?;
+    let unprocessed = db::run(|| db::get_conversations_needing_recovery().map_err(|e| e.to_string())).await?;
     
     let count = unprocessed.len();
     logging::log_conversation(None, &format!("Starting recovery of {} conversations", count));
@@ -152,36 +301,54 @@ async fn recover_conversations() -> Result<usize, String> {
 
 /// Internal finalization logic (shared between normal finalize and recovery)
 async fn finalize_conversation_internal(conversation_id: &str) -> Result<(), String> {
-    // Clear session weights when conversation ends
+    // Clear session weights and the session clock when conversation ends
     clear_session_weights(conversation_id);
+    session_timer::clear(conversation_id);
     
-    let profile = db::get_user_profile().map_err(|e| e.to_string())?;
-    let anthropic_key = match profile.anthropic_key {
-        Some(key) => key,
-        None => {
-            // No API key - just mark as processed without extraction
-            db::mark_conversation_processed(conversation_id, None)
+    // None when there's nothing to summarize; the conversation is already marked then
+    let id = conversation_id.to_string();
+    let loaded = db::run(move || {
+        let profile = db::get_user_profile().map_err(|e| e.to_string())?;
+        let anthropic_key = match profile.anthropic_key {
+            Some(key) => key,
+            None => {
+                // No API key - just mark as processed without extraction
+                db::mark_conversation_processed(&id, None)
+                    .map_err(|e| e.to_string())?;
+                return Ok(None);
+            }
+        };
+        
+        let conversation = db::get_conversation(&id)
+            .map_err(|e| e.to_string())?
+            .ok_or("Conversation not found")?;
+        
+        if conversation.processed {
+            return Ok(None);
+        }
+        
+        // An incognito conversation is closed without being summarized or learned from
+        if controls::is_incognito(&id) {
+            db::mark_conversation_processed(&id, None)
                 .map_err(|e| e.to_string())?;
-            return Ok(());
+            return Ok(None);
         }
-    };
-    
-    let conversation = db::get_conversation(conversation_id)
-        .map_err(|e| e.to_string())?
-        .ok_or("Conversation not found")?;
-    
-    if conversation.processed {
-        return Ok(());
-    }
-    
-    let messages = db::get_conversation_messages(conversation_id)
-        .map_err(|e| e.to_string())?;
-    
-    if messages.len() < 2 {
-        db::mark_conversation_processed(conversation_id, None)
+        
+        let messages = db::get_delivered_messages(&id)
             .map_err(|e| e.to_string())?;
+        
+        if messages.len() < 2 {
+            db::mark_conversation_processed(&id, None)
+                .map_err(|e| e.to_string())?;
+            return Ok(None);
+        }
+        
+        let existing_facts = db::get_user_facts_in_scope(Some(&id)).unwrap_or_default();
+        Ok(Some((anthropic_key, conversation, messages, existing_facts)))
+    }).await?;
+    let Some((anthropic_key, conversation, messages, existing_facts)) = loaded else {
         return Ok(());
-    }
+    };
     
     logging::log_conversation(Some(conversation_id), &format!(
         "Finalizing conversation with {} messages", messages.len()
@@ -198,12 +365,10 @@ async fn finalize_conversation_internal(conversation_id: &str) -> Result<(), Str
     
     let final_summary = match summarizer.summarize(&messages, None).await {
         Ok(result) => {
-            let _ = ConversationSummarizer::save_summary(
-                conversation_id,
-                &result,
-                messages.len() as i64,
-                &agents_involved,
-            );
+            let (id, saved, message_count, agents) = (conversation_id.to_string(), result.clone(), messages.len() as i64, agents_involved.clone());
+            db::run_infallible(move || {
+                let _ = ConversationSummarizer::save_summary(&id, &saved, message_count, &agents);
+            }).await;
             logging::log_memory(Some(conversation_id), &format!(
                 "Generated summary: {} topics", result.key_topics.len()
             ));
@@ -217,7 +382,6 @@ async fn finalize_conversation_internal(conversation_id: &str) -> Result<(), Str
     
     // Extract patterns
     let extractor = MemoryExtractor::new(&anthropic_key);
-    let existing_facts = db::get_all_user_facts().unwrap_or_default();
     
     let full_conversation: String = messages.iter()
         .map(|m| format!("{}: {}", m.role.to_uppercase(), m.content))
@@ -236,8 +400,11 @@ async fn finalize_conversation_internal(conversation_id: &str) -> Result<(), Str
         ));
     }
     
-    db::mark_conversation_processed(conversation_id, final_summary.as_deref())
-        .map_err(|e| e.to_string())?;
+    let id = conversation_id.to_string();
+    db::run(move || {
+        db::mark_conversation_processed(&id, final_summary.as_deref())
+            .map_err(|e| e.to_string())
+    }).await?;
     
     logging::log_conversation(Some(conversation_id), "Finalization complete");
     
@@ -247,8 +414,8 @@ async fn finalize_conversation_internal(conversation_id: &str) -> Result<(), Str
 // ============ User Profile ============
 
 #[tauri::command]
-fn get_user_profile() -> Result<UserProfile, String> {
-    db::get_user_profile().map_err(|e| e.to_string())
+async fn get_user_profile() -> Result<UserProfile, String> {
+    db::run(move || db::get_user_profile().map_err(|e| e.to_string())).await
 }
 
 #[tauri::command]
@@ -258,7 +425,7 @@ async fn validate_and_save_api_key(api_key: String) -> Result<bool, String> {
     match client.validate_api_key().await {
         Ok(valid) => {
             if valid {
-                db::update_api_key(&api_key).map_err(|e| e.to_string())?;
+                keychain::set(keychain::OPENAI_KEY, &api_key)?;
             }
             Ok(valid)
         }
@@ -267,104 +434,240 @@ async fn validate_and_save_api_key(api_key: String) -> Result<bool, String> {
 }
 
 #[tauri::command]
-fn save_api_key(api_key: String) -> Result<(), String> {
-    db::update_api_key(&api_key).map_err(|e| e.to_string())
+async fn save_api_key(api_key: String) -> Result<(), String> {
+    db::run(move || keychain::set(keychain::OPENAI_KEY, &api_key)).await
 }
 
 #[tauri::command]
-fn remove_api_key() -> Result<(), String> {
-    db::clear_api_key().map_err(|e| e.to_string())
+async fn remove_api_key() -> Result<(), String> {
+    db::run(move || keychain::delete(keychain::OPENAI_KEY)).await
 }
 
 #[tauri::command]
-fn save_anthropic_key(api_key: String) -> Result<(), String> {
-    db::update_anthropic_key(&api_key).map_err(|e| e.to_string())
+async fn save_anthropic_key(api_key: String) -> Result<(), String> {
+    db::run(move || keychain::set(keychain::ANTHROPIC_KEY, &api_key)).await
 }
 
 #[tauri::command]
-fn remove_anthropic_key() -> Result<(), String> {
-    db::clear_anthropic_key().map_err(|e| e.to_string())
+async fn remove_anthropic_key() -> Result<(), String> {
+    db::run(move || keychain::delete(keychain::ANTHROPIC_KEY)).await
 }
 
 // ============ Persona Profiles ============
 
 #[tauri::command]
-fn create_persona_profile(name: String, dominant_trait: String, secondary_trait: String, is_default: bool) -> Result<db::PersonaProfile, String> {
-    db::create_persona_profile(&name, &dominant_trait, &secondary_trait, is_default).map_err(|e| e.to_string())
+async fn create_persona_profile(name: String, dominant_trait: String, secondary_trait: String, is_default: bool) -> Result<db::PersonaProfile, String> {
+    db::run(move || db::create_persona_profile(&name, &dominant_trait, &secondary_trait, is_default).map_err(|e| e.to_string())).await
 }
 
 #[tauri::command]
-fn get_all_persona_profiles() -> Result<Vec<db::PersonaProfile>, String> {
-    db::get_all_persona_profiles().map_err(|e| e.to_string())
+async fn get_all_persona_profiles() -> Result<Vec<db::PersonaProfile>, String> {
+    db::run(move || db::get_all_persona_profiles().map_err(|e| e.to_string())).await
 }
 
 #[tauri::command]
-fn get_active_persona_profile() -> Result<Option<db::PersonaProfile>, String> {
-    db::get_active_persona_profile().map_err(|e| e.to_string())
+async fn get_active_persona_profile() -> Result<Option<db::PersonaProfile>, String> {
+    db::run(move || db::get_active_persona_profile().map_err(|e| e.to_string())).await
 }
 
 #[tauri::command]
-fn get_persona_profile_count() -> Result<i64, String> {
-    db::get_persona_profile_count().map_err(|e| e.to_string())
+async fn get_persona_profile_count() -> Result<i64, String> {
+    db::run(move || db::get_persona_profile_count().map_err(|e| e.to_string())).await
 }
 
 #[tauri::command]
-fn set_active_persona_profile(profile_id: String) -> Result<(), String> {
-    db::set_active_persona_profile(&profile_id).map_err(|e| e.to_string())
+async fn set_active_persona_profile(profile_id: String) -> Result<(), String> {
+    db::run(move || db::set_active_persona_profile(&profile_id).map_err(|e| e.to_string())).await
 }
 
 #[tauri::command]
-fn set_default_persona_profile(profile_id: String) -> Result<(), String> {
-    db::set_default_persona_profile(&profile_id).map_err(|e| e.to_string())
+async fn set_default_persona_profile(profile_id: String) -> Result<(), String> {
+    db::run(move || db::set_default_persona_profile(&profile_id).map_err(|e| e.to_string())).await
 }
 
 #[tauri::command]
-fn update_persona_profile_name(profile_id: String, new_name: String) -> Result<(), String> {
-    db::update_persona_profile_name(&profile_id, &new_name).map_err(|e| e.to_string())
+async fn update_persona_profile_name(profile_id: String, new_name: String) -> Result<(), String> {
+    db::run(move || db::update_persona_profile_name(&profile_id, &new_name).map_err(|e| e.to_string())).await
 }
 
 #[tauri::command]
-fn delete_persona_profile(profile_id: String) -> Result<(), String> {
-    db::delete_persona_profile(&profile_id).map_err(|e| e.to_string())
+async fn delete_persona_profile(profile_id: String) -> Result<(), String> {
+    db::run(move || db::delete_persona_profile(&profile_id).map_err(|e| e.to_string())).await
 }
 
 // ============ Conversations ============
 
 #[tauri::command]
-fn create_conversation(is_disco: bool) -> Result<ConversationInfo, String> {
-    let id = Uuid::new_v4().to_string();
-    let conv = db::create_conversation(&id, is_disco).map_err(|e| e.to_string())?;
-    Ok(ConversationInfo {
-        id: conv.id,
-        title: conv.title,
-        summary: conv.summary,
-        is_disco: conv.is_disco,
-        created_at: conv.created_at,
-        updated_at: conv.updated_at,
-    })
+async fn create_conversation(is_disco: bool) -> Result<ConversationInfo, String> {
+    db::run(move || {
+        let id = Uuid::new_v4().to_string();
+        let conv = db::create_conversation(&id, is_disco).map_err(|e| e.to_string())?;
+        Ok(ConversationInfo {
+            id: conv.id,
+            title: conv.title,
+            summary: conv.summary,
+            is_disco: conv.is_disco,
+            created_at: conv.created_at,
+            updated_at: conv.updated_at,
+            closed_at: conv.closed_at,
+            pinned: conv.pinned,
+            project_id: conv.project_id,
+            locked: conv.locked,
+            parent_conversation_id: conv.parent_conversation_id,
+        })
+    }).await
+}
+
+#[tauri::command]
+async fn get_recent_conversations(limit: usize, include_closed: Option<bool>) -> Result<Vec<ConversationInfo>, String> {
+    db::run(move || {
+        let convs = db::get_recent_conversations(limit, include_closed.unwrap_or(false)).map_err(|e| e.to_string())?;
+        Ok(anonymize::render(convs.into_iter().map(|c| ConversationInfo {
+            id: c.id,
+            title: c.title,
+            summary: c.summary,
+            is_disco: c.is_disco,
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+            closed_at: c.closed_at,
+            pinned: c.pinned,
+            project_id: c.project_id,
+            locked: c.locked,
+            parent_conversation_id: c.parent_conversation_id,
+        }).collect()))
+    }).await
+}
+
+/// Refuse changes to a locked conversation
+fn ensure_unlocked(conversation_id: &str) -> Result<(), String> {
+    if db::is_conversation_locked(conversation_id).map_err(|e| e.to_string())? {
+        return Err("This conversation is locked. Unlock it to make changes.".to_string());
+    }
+    Ok(())
+}
+
+/// Lock a conversation to keep it exactly as it is: no new messages, edits, or deletion
+#[tauri::command]
+async fn set_conversation_locked(conversation_id: String, locked: bool) -> Result<(), String> {
+    db::run(move || {
+        if !db::set_conversation_locked(&conversation_id, locked).map_err(|e| e.to_string())? {
+            return Err("Conversation not found".to_string());
+        }
+        Ok(())
+    }).await
+}
+
+/// Pinned conversations list first (and stay listed after auto-close)
+#[tauri::command]
+async fn set_conversation_pinned(conversation_id: String, pinned: bool) -> Result<(), String> {
+    db::run(move || db::set_conversation_pinned(&conversation_id, pinned).map_err(|e| e.to_string())).await
+}
+
+/// Start a new conversation from a copy of this one up to and including
+/// `from_message_id`, to take it somewhere else; the original stays as it was
+#[tauri::command]
+async fn fork_conversation(conversation_id: String, from_message_id: String) -> Result<ConversationInfo, String> {
+    db::run(move || {
+        let message = db::get_message(&from_message_id).map_err(|e| e.to_string())?
+            .filter(|m| m.conversation_id == conversation_id)
+            .ok_or("That message isn't in this conversation")?;
+        let conv = db::fork_conversation(&conversation_id, &message.id).map_err(|e| e.to_string())?;
+        logging::log_conversation(Some(&conv.id), &format!("Forked from {} at message {}", conversation_id, message.id));
+        Ok(anonymize::render(ConversationInfo {
+            id: conv.id,
+            title: conv.title,
+            summary: conv.summary,
+            is_disco: conv.is_disco,
+            created_at: conv.created_at,
+            updated_at: conv.updated_at,
+            closed_at: conv.closed_at,
+            pinned: conv.pinned,
+            project_id: conv.project_id,
+            locked: conv.locked,
+            parent_conversation_id: conv.parent_conversation_id,
+        }))
+    }).await
+}
+
+#[tauri::command]
+async fn get_conversation_messages(conversation_id: String) -> Result<Vec<Message>, String> {
+    db::run(move || {
+        db::get_conversation_messages(&conversation_id).map(anonymize::render).map_err(|e| e.to_string())
+    }).await
+}
+
+/// Default and largest page size for lazy-loading history
+const MESSAGE_PAGE_DEFAULT: usize = 50;
+const MESSAGE_PAGE_MAX: usize = 500;
+
+/// Older history a page at a time; omit `before_id` for the newest page
+#[tauri::command]
+async fn get_messages_page(conversation_id: String, before_id: Option<String>, limit: Option<usize>) -> Result<db::MessagePage, String> {
+    db::run(move || {
+        let limit = limit.unwrap_or(MESSAGE_PAGE_DEFAULT).clamp(1, MESSAGE_PAGE_MAX);
+        db::get_messages_page(&conversation_id, before_id.as_deref(), limit)
+            .map_err(|e| e.to_string())?
+            .map(anonymize::render)
+            .ok_or_else(|| format!("Message {} is not in this conversation", before_id.unwrap_or_default()))
+    }).await
+}
+
+/// Remove a single message, optionally with the replies that reference it; returns how many went
+#[tauri::command]
+async fn delete_message(message_id: String, include_replies: Option<bool>) -> Result<usize, String> {
+    db::run(move || {
+        if let Some(message) = db::get_message(&message_id).map_err(|e| e.to_string())? {
+            ensure_unlocked(&message.conversation_id)?;
+        }
+        db::delete_message(&message_id, include_replies.unwrap_or(false)).map_err(|e| e.to_string())
+    }).await
+}
+
+#[tauri::command]
+async fn clear_conversation(conversation_id: String) -> Result<(), String> {
+    db::run(move || {
+        ensure_unlocked(&conversation_id)?;
+        db::clear_conversation_messages(&conversation_id).map_err(|e| e.to_string())
+    }).await
+}
+
+#[tauri::command]
+async fn delete_conversation(conversation_id: String) -> Result<(), String> {
+    db::run(move || {
+        ensure_unlocked(&conversation_id)?;
+        clear_session_weights(&conversation_id);
+        session_timer::clear(&conversation_id);
+        db::delete_conversation(&conversation_id).map_err(|e| e.to_string())
+    }).await
+}
+
+/// Move a conversation to the trash; it can be restored for 30 days
+#[tauri::command]
+async fn trash_conversation(conversation_id: String) -> Result<bool, String> {
+    db::run(move || {
+        ensure_unlocked(&conversation_id)?;
+        clear_session_weights(&conversation_id);
+        session_timer::clear(&conversation_id);
+        db::trash_conversation(&conversation_id).map_err(|e| e.to_string())
+    }).await
 }
 
 #[tauri::command]
-fn get_recent_conversations(limit: usize) -> Result<Vec<ConversationInfo>, String> {
-    let convs = db::get_recent_conversations(limit).map_err(|e| e.to_string())?;
-    Ok(convs.into_iter().map(|c| ConversationInfo {
-        id: c.id,
-        title: c.title,
-        summary: c.summary,
-        is_disco: c.is_disco,
-        created_at: c.created_at,
-        updated_at: c.updated_at,
-    }).collect())
+async fn restore_conversation(conversation_id: String) -> Result<bool, String> {
+    db::run(move || db::restore_conversation(&conversation_id).map_err(|e| e.to_string())).await
 }
 
 #[tauri::command]
-fn get_conversation_messages(conversation_id: String) -> Result<Vec<Message>, String> {
-    db::get_conversation_messages(&conversation_id).map_err(|e| e.to_string())
+async fn get_trashed_conversations() -> Result<Vec<db::TrashedConversation>, String> {
+    db::run(move || db::get_trashed_conversations().map_err(|e| e.to_string())).await
 }
 
+/// Permanently delete everything in the trash now
 #[tauri::command]
-fn clear_conversation(conversation_id: String) -> Result<(), String> {
-    db::clear_conversation_messages(&conversation_id).map_err(|e| e.to_string())
+async fn empty_trash() -> Result<usize, String> {
+    db::run(move || {
+        db::purge_trash(&Utc::now().to_rfc3339()).map_err(|e| e.to_string())
+    }).await
 }
 
 /// Finalize a conversation: run holistic extraction, consolidate facts, generate final summary
@@ -375,7 +678,8 @@ async fn finalize_conversation(conversation_id: String) -> Result<(), String> {
 
 // ============ Conversation Opener ============
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct ConversationOpenerResult {
     pub agent: String,
     pub content: String,
@@ -383,11 +687,14 @@ pub struct ConversationOpenerResult {
 
 #[tauri::command]
 async fn get_conversation_opener() -> Result<ConversationOpenerResult, String> {
-    let profile = db::get_user_profile().map_err(|e| e.to_string())?;
+    let (profile, active_profile) = db::run(|| {
+        let profile = db::get_user_profile().map_err(|e| e.to_string())?;
+        // Get active persona profile to inform the greeting
+        let active_profile = db::get_active_persona_profile().map_err(|e| e.to_string())?;
+        Ok((profile, active_profile))
+    }).await?;
     let anthropic_key = profile.anthropic_key.ok_or("Anthropic API key not set")?;
     
-    // Get active persona profile to inform the greeting
-    let active_profile = db::get_active_persona_profile().map_err(|e| e.to_string())?;
     let active_trait = active_profile.map(|p| p.dominant_trait).unwrap_or_else(|| "logic".to_string());
     
     // The dominant agent greets the user (using Anthropic/Claude)
@@ -418,8 +725,9 @@ async fn generate_governor_greeting(anthropic_key: &str, active_trait: &str) ->
     };
     
     // ===== GATHER USER CONTEXT (learned knowledge, not conversation-specific) =====
-    let user_facts = db::get_all_user_facts().unwrap_or_default();
-    let user_patterns = db::get_all_user_patterns().unwrap_or_default();
+    let (user_facts, user_patterns) = db::run_infallible(|| {
+        (db::get_all_user_facts().unwrap_or_default(), db::get_prompt_user_patterns().unwrap_or_default())
+    }).await;
     
     // Build context for greeting
     let mut context_parts = Vec::new();
@@ -502,7 +810,7 @@ Only mention if relevant.
 - NO meta-commentary, explanations, or quotation marks around your output
 - This is a fresh conversation - don't reference past conversations"#);
 
-    let client = AnthropicClient::new(anthropic_key);
+    let client = AnthropicClient::new(anthropic_key).with_usage_category("greeting");
     let messages = vec![
         AnthropicMessage {
             role: "user".to_string(),
@@ -512,7 +820,7 @@ Only mention if relevant.
     
     client.chat_completion_advanced(
         CLAUDE_HAIKU,
-        Some(&system_prompt),
+        Some(&format!("{}{}{}", system_prompt, boundaries::house_rules_section(), i18n::reply_language_suffix())),
         messages,
         0.8,
         Some(100), // More room for nuanced greeting
@@ -530,7 +838,8 @@ async fn generate_governor_response(
     conversation_history: &[Message],
     is_disco: bool,
     user_profile: Option<&UserProfileSummary>,
-) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    reading_level: Option<u8>,
+) -> Result<(String, Option<MessageCost>), Box<dyn std::error::Error + Send + Sync>> {
     use crate::anthropic::{AnthropicClient, AnthropicMessage, ThinkingBudget, CLAUDE_SONNET};
     
     // Format agent thoughts for the Governor to read
@@ -637,7 +946,7 @@ Read the agent thoughts below (these are internal - the user cannot see them). S
 
 Remember: The user cannot see the agent thoughts. You are synthesizing them into a single, coherent response that reflects the best thinking from your internal agents."#, mode_instructions, agent_thoughts_text, recent_context, profile_context);
     
-    let client = AnthropicClient::new(anthropic_key);
+    let client = AnthropicClient::new(anthropic_key).with_usage_category("synthesis");
     let messages = vec![
         AnthropicMessage {
             role: "user".to_string(),
@@ -645,9 +954,15 @@ Remember: The user cannot see the agent thoughts. You are synthesizing them into
         },
     ];
     
-    client.chat_completion_advanced(
+    client.chat_completion_advanced_with_cost(
         CLAUDE_SONNET,
-        Some(&system_prompt),
+        Some(&format!(
+            "{}{}{}{}",
+            system_prompt,
+            reading_level.map(readability::directive).unwrap_or_default(),
+            boundaries::house_rules_section(),
+            i18n::reply_language_suffix()
+        )),
         messages,
         0.7,
         Some(1024), // Allow for detailed synthesis
@@ -696,12 +1011,7 @@ fn generate_weight_notification(
         return None;
     }
     
-    let agent_name = match primary_agent {
-        "instinct" => "Snap",
-        "logic" => "Dot", 
-        "psyche" => "Puff",
-        _ => primary_agent,
-    };
+    let agent_name = agents::display_name(primary_agent);
     
     let (change_type, message) = if old_dominant != new_dominant {
         // Major shift - dominant agent changed
@@ -758,20 +1068,406 @@ async fn send_message(
     user_message: String,
     active_agents: Vec<String>,
     disco_agents: Vec<String>,
+    idempotency_key: Option<String>,
+    attachment_paths: Option<Vec<String>>,
+) -> Result<SendMessageResult, String> {
+    let conversation = conversation_id.clone();
+    db::run(move || ensure_unlocked(&conversation)).await?;
+    
+    // Refuse a second dispatch of the same send (double-click / IPC retry)
+    let claim = match idempotency_key.as_deref() {
+        Some(key) => Some(idempotency::claim(key).ok_or("Duplicate send ignored: this message is already being processed")?),
+        None => None,
+    };
+    
+    // Slash commands ("/recall", "/mute", "/mode", ...) apply here instead of starting a round
+    if let Some(command) = controls::parse(&user_message) {
+        let command = command?;
+        let conversation = conversation_id.clone();
+        let control = db::run(move || controls::handle(&conversation, command)).await?;
+        if let Some(claim) = claim {
+            claim.complete();
+        }
+        return Ok(anonymize::render(SendMessageResult { control: Some(control), ..Default::default() }));
+    }
+    
+    if active_agents.is_empty() {
+        return Ok(SendMessageResult::default());
+    }
+    
+    // Persist the user's text before any provider call so a failure can't lose it
+    let user_msg_id = Uuid::new_v4().to_string();
+    let user_msg = Message {
+        id: user_msg_id.clone(),
+        conversation_id: conversation_id.clone(),
+        role: "user".to_string(),
+        content: user_message,
+        timestamp: Utc::now().to_rfc3339(),
+        status: Some(db::MESSAGE_STATUS_PENDING.to_string()),
+        round_id: Some(user_msg_id),
+        ..Default::default()
+    };
+    let saved = user_msg.clone();
+    let (round_agents, round_disco) = (active_agents.clone(), disco_agents.clone());
+    db::run(move || {
+        db::save_message(&saved).map_err(|e| e.to_string())?;
+        // A failed round is retried with the same agents
+        let _ = db::set_round_agents(&saved.id, &round_agents, &round_disco);
+        Ok(())
+    }).await?;
+    // From here the message exists; if this send fails it's retried by id, so a
+    // repeat of the same send stays refused either way
+    if let Some(claim) = claim {
+        claim.complete();
+    }
+    
+    // Files go in before dispatch so their text reaches the agents; one that can't
+    // be read fails the send like a provider error would, and it can be retried
+    let message_id = user_msg.id.clone();
+    let paths = attachment_paths.unwrap_or_default();
+    db::run(move || {
+        for path in paths {
+            if let Err(e) = attachments::attach_file(&message_id, &path) {
+                let _ = db::set_message_status(&message_id, db::MESSAGE_STATUS_FAILED);
+                return Err(e);
+            }
+        }
+        
+        // Replying here answers any check-ins delivered into this conversation
+        let _ = db::mark_proactive_responded_for_conversation(&conversation_id);
+        Ok(())
+    }).await?;
+    
+    let result = dispatch_message(&user_msg, active_agents, disco_agents).await;
+    settle_message_status(&user_msg.id, &result).await;
+    result.map(anonymize::render)
+}
+
+/// Re-dispatch a user message whose provider call failed. Replies the failed
+/// round did save are dropped first so the retry doesn't answer twice.
+#[tauri::command]
+async fn retry_message(
+    message_id: String,
+    active_agents: Option<Vec<String>>,
+    disco_agents: Option<Vec<String>>,
+) -> Result<SendMessageResult, String> {
+    // Held until the retry settles, so a double-click can't start a second one
+    let _claim = idempotency::claim(&format!("retry:{}", message_id))
+        .ok_or("This message is already being retried")?;
+    let id = message_id.clone();
+    let (user_msg, active_agents, disco_agents) = db::run(move || {
+        let user_msg = db::get_message(&id).map_err(|e| e.to_string())?
+            .ok_or("Message not found")?;
+        ensure_unlocked(&user_msg.conversation_id)?;
+        if user_msg.role != "user" || user_msg.status.as_deref() != Some(db::MESSAGE_STATUS_FAILED) {
+            return Err("Only failed user messages can be retried".to_string());
+        }
+        
+        let (active_agents, disco_agents) = round_agents(&user_msg, active_agents, disco_agents)?;
+        let round_id = user_msg.round_id.clone().unwrap_or_else(|| user_msg.id.clone());
+        db::delete_round_replies(&round_id).map_err(|e| e.to_string())?;
+        db::set_message_status(&id, db::MESSAGE_STATUS_PENDING).map_err(|e| e.to_string())?;
+        Ok((user_msg, active_agents, disco_agents))
+    }).await?;
+    
+    let result = dispatch_message(&user_msg, active_agents, disco_agents).await;
+    settle_message_status(&message_id, &result).await;
+    result
+}
+
+/// Fix the text of a user message; the previous text is kept as a revision
+#[tauri::command]
+async fn update_message_content(message_id: String, new_content: String) -> Result<Message, String> {
+    db::run(move || {
+        let message = db::get_message(&message_id).map_err(|e| e.to_string())?
+            .ok_or("Message not found")?;
+        ensure_unlocked(&message.conversation_id)?;
+        if message.role != "user" {
+            return Err("Only your own messages can be edited".to_string());
+        }
+        if message.status.as_deref() == Some(db::MESSAGE_STATUS_PENDING) {
+            return Err("This message is still being answered".to_string());
+        }
+        let new_content = new_content.trim();
+        if new_content.is_empty() {
+            return Err("Message cannot be empty".to_string());
+        }
+        db::update_message_content(&message_id, new_content).map_err(|e| e.to_string())?;
+        db::get_message(&message_id).map_err(|e| e.to_string())?
+            .ok_or_else(|| "Message not found".to_string())
+    }).await
+}
+
+#[tauri::command]
+async fn get_message_revisions(message_id: String) -> Result<Vec<db::MessageRevision>, String> {
+    db::run(move || db::get_message_revisions(&message_id).map_err(|e| e.to_string())).await
+}
+
+/// Attach a file to one of your messages; its text is given to the agents from the next reply on
+#[tauri::command]
+async fn attach_file(message_id: String, path: String) -> Result<db::Attachment, String> {
+    db::run(move || {
+        let message = db::get_message(&message_id).map_err(|e| e.to_string())?
+            .ok_or("Message not found")?;
+        ensure_unlocked(&message.conversation_id)?;
+        attachments::attach_file(&message_id, &path)
+    }).await
+}
+
+#[tauri::command]
+async fn get_message_attachments(message_id: String) -> Result<Vec<db::Attachment>, String> {
+    db::run(move || db::get_message_attachments(&message_id).map_err(|e| e.to_string())).await
+}
+
+/// The file's bytes; None when it was too big to store and only its path was kept
+#[tauri::command]
+async fn get_attachment_data(id: String) -> Result<Option<Vec<u8>>, String> {
+    db::run(move || db::get_attachment_data(&id).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn delete_attachment(id: String) -> Result<(), String> {
+    db::run(move || {
+        let attachment = db::get_attachment(&id).map_err(|e| e.to_string())?
+            .ok_or("Attachment not found")?;
+        if let Some(message) = db::get_message(&attachment.message_id).map_err(|e| e.to_string())? {
+            ensure_unlocked(&message.conversation_id)?;
+        }
+        db::delete_attachment(&id).map_err(|e| e.to_string())?;
+        Ok(())
+    }).await
+}
+
+/// Throw away the replies to a (typically just edited) user message and run its round again
+#[tauri::command]
+async fn regenerate_replies(
+    message_id: String,
+    active_agents: Option<Vec<String>>,
+    disco_agents: Option<Vec<String>>,
+) -> Result<SendMessageResult, String> {
+    let id = message_id.clone();
+    let (user_msg, active_agents, disco_agents) = db::run(move || {
+        let user_msg = db::get_message(&id).map_err(|e| e.to_string())?
+            .ok_or("Message not found")?;
+        ensure_unlocked(&user_msg.conversation_id)?;
+        if user_msg.role != "user" {
+            return Err("Only replies to your own messages can be regenerated".to_string());
+        }
+        if user_msg.status.as_deref() == Some(db::MESSAGE_STATUS_PENDING) {
+            return Err("This message is still being answered".to_string());
+        }
+        
+        let (active_agents, disco_agents) = round_agents(&user_msg, active_agents, disco_agents)?;
+        let round_id = user_msg.round_id.clone().unwrap_or_else(|| user_msg.id.clone());
+        db::delete_round_replies(&round_id).map_err(|e| e.to_string())?;
+        db::set_message_status(&id, db::MESSAGE_STATUS_PENDING).map_err(|e| e.to_string())?;
+        Ok((user_msg, active_agents, disco_agents))
+    }).await?;
+    
+    let result = dispatch_message(&user_msg, active_agents, disco_agents).await;
+    settle_message_status(&message_id, &result).await;
+    result
+}
+
+/// The agents to run a round again with: the ones passed in, else the ones it
+/// was first sent to (all three when that isn't known). Recorded as the round's
+/// agents from here on.
+fn round_agents(
+    user_msg: &Message,
+    active_agents: Option<Vec<String>>,
+    disco_agents: Option<Vec<String>>,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let original = db::get_round_agents(&user_msg.id).map_err(|e| e.to_string())?;
+    let active_agents = active_agents
+        .or_else(|| original.as_ref().map(|(active, _)| active.clone()))
+        .unwrap_or_else(|| vec!["instinct".to_string(), "logic".to_string(), "psyche".to_string()]);
+    let disco_agents = disco_agents
+        .or_else(|| original.map(|(_, disco)| disco))
+        .unwrap_or_default();
+    let _ = db::set_round_agents(&user_msg.id, &active_agents, &disco_agents);
+    Ok((active_agents, disco_agents))
+}
+
+/// Mark a pending user message sent or failed once its turn resolves
+async fn settle_message_status(message_id: &str, result: &Result<SendMessageResult, String>) {
+    let status = if result.is_ok() { db::MESSAGE_STATUS_SENT } else { db::MESSAGE_STATUS_FAILED };
+    let id = message_id.to_string();
+    if let Err(e) = db::run(move || db::set_message_status(&id, status).map_err(|e| e.to_string())).await {
+        logging::log_error(None, &format!("Failed to update status of message {}: {}", message_id, e));
+    }
+}
+
+fn elapsed_ms(started: Instant) -> i64 {
+    started.elapsed().as_millis() as i64
+}
+
+/// Link a saved agent message to the memory facts it cited (best effort)
+fn save_citations(message_id: &str, fact_ids: &[i64]) {
+    if fact_ids.is_empty() {
+        return;
+    }
+    if let Err(e) = db::save_response_citations(message_id, fact_ids) {
+        logging::log_error(None, &format!("Failed to save citations for {}: {}", message_id, e));
+    }
+}
+
+/// Save an agent's reply along with the facts it cited
+async fn save_reply(message: Message, fact_ids: Vec<i64>) -> Result<(), String> {
+    db::run(move || {
+        db::save_message(&message).map_err(|e| e.to_string())?;
+        save_citations(&message.id, &fact_ids);
+        Ok(())
+    }).await
+}
+
+/// Keep a failed agent's place in the round with a stub that retry_agent can fill in
+async fn save_failed_agent_slot(
+    conversation_id: &str,
+    round_id: &str,
+    agent: Agent,
+    response_type: ResponseType,
+    references_message_id: &str,
+    error: &str,
+) -> Result<(), String> {
+    logging::log_error(Some(conversation_id), &format!(
+        "{} failed in round {}: {}", agent.as_str(), round_id, error
+    ));
+    
+    let agent_name = agents::display_name(agent.as_str());
+    let stub = Message {
+        id: Uuid::new_v4().to_string(),
+        conversation_id: conversation_id.to_string(),
+        role: agent.as_str().to_string(),
+        content: i18n::format(
+            "chat.agent_failed",
+            "{name} didn't get through -- retry to hear from them.",
+            &[("name", &agent_name)],
+        ),
+        response_type: Some(response_type.as_str().to_string()),
+        references_message_id: Some(references_message_id.to_string()),
+        timestamp: Utc::now().to_rfc3339(),
+        status: Some(db::MESSAGE_STATUS_FAILED.to_string()),
+        round_id: Some(round_id.to_string()),
+        latency_ms: None,
+        ..Default::default()
+    };
+    db::run(move || db::save_message(&stub).map_err(|e| e.to_string())).await
+}
+
+/// Re-run one agent whose slot in a round failed, replacing its stub in place
+#[tauri::command]
+async fn retry_agent(round_id: String, agent: String) -> Result<AgentResponse, String> {
+    let agent_enum = Agent::from_str(&agent).ok_or_else(|| format!("Invalid agent: {}", agent))?;
+    let slot_agent = agent.clone();
+    let (stub, user_msg, profile, referenced, conversation, recent_messages, user_profile, reading_level, late_night) = db::run(move || {
+        let stub = db::get_failed_agent_slot(&round_id, &slot_agent).map_err(|e| e.to_string())?
+            .ok_or("No failed slot for that agent in this round")?;
+        let user_msg = db::get_message(&round_id).map_err(|e| e.to_string())?
+            .ok_or("Round not found")?;
+        ensure_unlocked(&user_msg.conversation_id)?;
+        
+        let profile = db::get_user_profile().map_err(|e| e.to_string())?;
+        
+        // The message this slot was responding to (primary, or the previous debate turn)
+        let referenced = match stub.references_message_id.as_deref() {
+            Some(id) => db::get_message(id).map_err(|e| e.to_string())?,
+            None => None,
+        };
+        let conversation = db::get_conversation(&stub.conversation_id).map_err(|e| e.to_string())?
+            .ok_or("Conversation not found")?;
+        let recent_messages = db::get_recent_messages(&stub.conversation_id, 20).map_err(|e| e.to_string())?;
+        let user_profile = MemoryExtractor::build_profile_summary(Some(&stub.conversation_id), Some(&user_msg.content)).ok();
+        let reading_level = readability::resolve(&conversation.id);
+        Ok((stub, user_msg, profile, referenced, conversation, recent_messages, user_profile, reading_level, late_night::active()))
+    }).await?;
+    let api_key = profile.api_key.clone().ok_or("OpenAI API key not set")?;
+    let anthropic_key = profile.anthropic_key.clone().ok_or("Anthropic API key not set")?;
+    
+    let grounding = user_profile.as_ref().map(|p| {
+        decide_grounding_heuristic(&user_msg.content, &recent_messages, Some(p))
+    });
+    let response_type = stub.response_type.as_deref()
+        .and_then(ResponseType::from_str)
+        .unwrap_or(ResponseType::Addition);
+    
+    let orchestrator = Orchestrator::new(&api_key, &anthropic_key)
+        .with_intensity(conversation.is_disco.then_some(conversation.intensity))
+        .with_reading_level(reading_level)
+        .with_late_night(late_night);
+    let started = Instant::now();
+    let reply = orchestrator
+        .get_agent_response_with_grounding(
+            agent_enum,
+            &user_msg.content,
+            &recent_messages,
+            response_type,
+            referenced.as_ref().map(|m| m.content.as_str()),
+            referenced.as_ref().map(|m| m.role.as_str()),
+            grounding.as_ref(),
+            user_profile.as_ref(),
+            conversation.is_disco,
+            conversation.is_disco,
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    let filled = Message {
+        content: reply.content.clone(),
+        status: None,
+        latency_ms: Some(elapsed_ms(started)),
+        ..stub
+    }.with_cost(reply.cost.clone());
+    let references_message_id = filled.references_message_id.clone();
+    save_reply(filled, reply.cited_fact_ids).await?;
+    
+    Ok(AgentResponse {
+        agent,
+        content: reply.content,
+        response_type: response_type.as_str().to_string(),
+        references_message_id,
+        cost: reply.cost,
+    })
+}
+
+/// Run the agent turn for an already-persisted user message
+async fn dispatch_message(
+    user_msg: &Message,
+    active_agents: Vec<String>,
+    disco_agents: Vec<String>,
 ) -> Result<SendMessageResult, String> {
-    // Get profile for API keys and weights
-    let profile = db::get_user_profile().map_err(|e| e.to_string())?;
+    let conversation_id = user_msg.conversation_id.clone();
+    let user_message = user_msg.content.clone();
+    let round_id = user_msg.id.clone();
+    
+    // Everything the round reads up front, in one trip to the blocking pool
+    let conversation = conversation_id.clone();
+    let (controls, profile, active_persona, settings, late_night, reading_level) = db::run(move || {
+        // Mutes and a forced mode from the conversation's slash commands
+        let controls = db::get_conversation_controls(&conversation).unwrap_or_default();
+        // Get profile for API keys and weights
+        let profile = db::get_user_profile().map_err(|e| e.to_string())?;
+        // Get active persona profile for points and dominant trait
+        let active_persona = db::get_active_persona_profile().map_err(|e| e.to_string())?
+            .ok_or("No active persona profile")?;
+        let settings = db::get_conversation_settings(&conversation).unwrap_or_default();
+        Ok((controls, profile, active_persona, settings, late_night::active(), readability::resolve(&conversation)))
+    }).await?;
+    
+    let (active_agents, disco_agents) = controls::apply_to_round(&controls, active_agents, disco_agents);
+    if active_agents.is_empty() {
+        return Err("Every agent is muted in this conversation. /unmute one to get replies".to_string());
+    }
+    
     let api_key = profile.api_key.clone().ok_or("OpenAI API key not set")?;
     let anthropic_key = profile.anthropic_key.clone().ok_or("Anthropic API key not set")?;
     
-    // Get active persona profile for points and dominant trait
-    let active_persona = db::get_active_persona_profile().map_err(|e| e.to_string())?
-        .ok_or("No active persona profile")?;
     let points = (active_persona.instinct_points, active_persona.logic_points, active_persona.psyche_points);
     let dominant_trait = Some(active_persona.dominant_trait.as_str());
     
     // ===== SESSION WEIGHTS: Separate base (persistent) from session (decaying) =====
-    let base_weights = (profile.instinct_weight, profile.logic_weight, profile.psyche_weight);
+    // A conversation's own weights stand in for the profile's
+    let base_weights = conversation_settings::weights(&settings)
+        .unwrap_or((profile.instinct_weight, profile.logic_weight, profile.psyche_weight));
     
     // Decay session weights by 10% per exchange
     decay_session_weights(&conversation_id);
@@ -786,33 +1482,72 @@ async fn send_message(
         base_weights.2 + session_weights.2,
     );
     
-    if active_agents.is_empty() {
-        return Ok(SendMessageResult { responses: Vec::new(), debate_mode: None, weight_change: None, governor_response: None });
-    }
-    
-    // ===== MEMORY SYSTEM: Build User Profile =====
-    let user_profile = MemoryExtractor::build_profile_summary().ok();
-    
-    // Get existing facts for extraction context
-    let existing_facts = db::get_all_user_facts().unwrap_or_default();
-    
-    // Save user message
-    let user_msg = Message {
-        id: Uuid::new_v4().to_string(),
-        conversation_id: conversation_id.clone(),
-        role: "user".to_string(),
-        content: user_message.clone(),
-        response_type: None,
-        references_message_id: None,
-        timestamp: Utc::now().to_rfc3339(),
+    // Late at night, lean toward Psyche and away from Instinct
+    let routing_weights = if late_night {
+        logging::log_routing(Some(&conversation_id), "[LATE NIGHT] Routing biased toward Psyche, intensity capped");
+        late_night::bias_weights(routing_weights)
+    } else {
+        routing_weights
     };
-    db::save_message(&user_msg).map_err(|e| e.to_string())?;
     
-    // Get recent messages for context
-    let recent_messages = db::get_recent_messages(&conversation_id, 20).map_err(|e| e.to_string())?;
+    // ===== MEMORY CORRECTIONS: "that's wrong about me" =====
+    // Applied before the profile is built so the agents already see the fix
+    let memory_correction = if corrections::looks_like_correction(&user_message) {
+        let conversation = conversation_id.clone();
+        let facts = db::run_infallible(move || db::get_user_facts_in_scope(Some(&conversation)).unwrap_or_default()).await;
+        match corrections::CorrectionHandler::new(&anthropic_key)
+            .handle(&conversation_id, &user_message, &facts)
+            .await
+        {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                logging::log_error(Some(&conversation_id), &format!("Correction handling failed: {}", e));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    if let Some(outcome) = &memory_correction {
+        let confirmation = Message {
+            id: Uuid::new_v4().to_string(),
+            conversation_id: conversation_id.clone(),
+            role: "system".to_string(),
+            content: outcome.confirmation.clone(),
+            response_type: Some("memory_correction".to_string()),
+            references_message_id: Some(user_msg.id.clone()),
+            timestamp: Utc::now().to_rfc3339(),
+            round_id: Some(round_id.clone()),
+            ..Default::default()
+        };
+        db::run(move || db::save_message(&confirmation).map_err(|e| e.to_string())).await?;
+    }
     
-    // Create orchestrator (OpenAI for agents only - routing is now heuristic-based)
-    let orchestrator = Orchestrator::new(&api_key, &anthropic_key);
+    let (conversation, message, user_msg_id) = (conversation_id.clone(), user_message.clone(), user_msg.id.clone());
+    let (user_profile, existing_facts, recent_messages, related_conversations) = db::run(move || {
+        // ===== MEMORY SYSTEM: Build User Profile =====
+        let mut user_profile = MemoryExtractor::build_profile_summary(Some(&conversation), Some(&message)).ok();
+        
+        // Get existing facts for extraction context
+        let existing_facts = db::get_user_facts_in_scope(Some(&conversation)).unwrap_or_default();
+        
+        // Get recent messages for context
+        let recent_messages = db::get_recent_messages(&conversation, 20).map_err(|e| e.to_string())?;
+        
+        // ===== RELATED CONVERSATIONS: "you've talked about this before" =====
+        let is_first_message = recent_messages.iter().all(|m| m.role != "user" || m.id == user_msg_id);
+        let related_conversations = if is_first_message {
+            memory::related_conversations(&conversation, &message)
+        } else {
+            Vec::new()
+        };
+        if !related_conversations.is_empty() && memory::related_in_context() {
+            if let Some(profile) = user_profile.as_mut() {
+                profile.related_conversations = memory::related_prompt_lines(&related_conversations);
+            }
+        }
+        Ok((user_profile, existing_facts, recent_messages, related_conversations))
+    }).await?;
     
     // Helper to check if an agent is in disco mode
     let is_agent_disco = |agent: &str| -> bool {
@@ -820,6 +1555,46 @@ async fn send_message(
     };
     let has_any_disco = !disco_agents.is_empty();
     
+    // ===== SESSION INTENSITY: Ramp disco intensity with engagement =====
+    let session_intensity = if has_any_disco {
+        let conversation = conversation_id.clone();
+        let current = db::run_infallible(move || {
+            db::get_conversation(&conversation).ok().flatten()
+                .map(|c| c.intensity)
+                .unwrap_or(0.0)
+        }).await;
+        let last_agent_ts = recent_messages.iter().rev()
+            .skip(1) // The user message we just saved
+            .find(|m| m.role != "user")
+            .map(|m| m.timestamp.as_str());
+        let signals = intensity::EngagementSignals::read(&user_message, last_agent_ts, Utc::now());
+        let ceiling = if late_night { intensity::ceiling().min(late_night::INTENSITY_CEILING) } else { intensity::ceiling() };
+        let next = intensity::next_intensity(current, &signals, ceiling);
+        let conversation = conversation_id.clone();
+        let _ = db::run(move || db::set_conversation_intensity(&conversation, next).map_err(|e| e.to_string())).await;
+        logging::log_routing(Some(&conversation_id), &format!(
+            "[INTENSITY] {:.2} -> {:.2} ({}) signals: {:?}", current, next, intensity::label(next), signals
+        ));
+        Some(next)
+    } else {
+        None
+    };
+    
+    // Create orchestrator (OpenAI for agents only - routing is now heuristic-based)
+    let timer = session_timer::touch(&conversation_id);
+    let wrap_up = timer.budget_minutes
+        .filter(|_| timer.over_budget)
+        .map(|budget| (timer.elapsed_minutes, budget));
+    if let Some((elapsed, budget)) = wrap_up {
+        logging::log_conversation(Some(&conversation_id), &format!("[SESSION TIMER] {}m of {}m budget, wrapping up", elapsed, budget));
+    }
+    let orchestrator = Orchestrator::new(&api_key, &anthropic_key)
+        .with_intensity(session_intensity)
+        .with_reading_level(reading_level)
+        .with_verbosity(controls::verbosity_scale(&controls))
+        .with_wrap_up(wrap_up)
+        .with_late_night(late_night);
+    
     // ===== FAST HEURISTIC ROUTING (No API calls) =====
     // Trait analysis moved to background task AFTER response for speed
     
@@ -842,6 +1617,9 @@ async fn send_message(
     let mut responses = Vec::new();
     let mut debate_mode: Option<String> = None;
     let mut agents_involved = Vec::new();
+    // Round model: the primary must succeed (otherwise the whole send fails and can be
+    // retried); any later slot that fails keeps a retryable stub and the round goes on
+    let mut failed_agents: Vec<String> = Vec::new();
     
     // Get primary agent response with grounding
     let primary_agent = Agent::from_str(&decision.primary_agent)
@@ -856,7 +1634,8 @@ async fn send_message(
         ));
     }
     
-    let primary_response = orchestrator
+    let started = Instant::now();
+    let primary_reply = orchestrator
         .get_agent_response_with_grounding(
             primary_agent,
             &user_message,
@@ -871,6 +1650,7 @@ async fn send_message(
         )
         .await
         .map_err(|e| e.to_string())?;
+    let primary_response = primary_reply.content.clone();
     
     // Save primary response
     let primary_msg_id = Uuid::new_v4().to_string();
@@ -882,14 +1662,18 @@ async fn send_message(
         response_type: Some("primary".to_string()),
         references_message_id: None,
         timestamp: Utc::now().to_rfc3339(),
-    };
-    db::save_message(&primary_msg).map_err(|e| e.to_string())?;
+        round_id: Some(round_id.clone()),
+        latency_ms: Some(elapsed_ms(started)),
+        ..Default::default()
+    }.with_cost(primary_reply.cost.clone());
+    save_reply(primary_msg, primary_reply.cited_fact_ids).await?;
     
     responses.push(AgentResponse {
         agent: primary_agent.as_str().to_string(),
         content: primary_response.clone(),
         response_type: "primary".to_string(),
         references_message_id: None,
+        cost: primary_reply.cost,
     });
     
     // Boost session weight for primary agent (immediate, decays over conversation)
@@ -916,6 +1700,7 @@ async fn send_message(
                         
                         let response_type = if idx == 0 { ResponseType::Addition } else { ResponseType::Addition };
                         
+                        let started = Instant::now();
                         let agent_response = orchestrator
                             .get_agent_response_with_grounding(
                                 agent,
@@ -929,26 +1714,37 @@ async fn send_message(
                                 is_agent_disco(agent.as_str()), // Per-agent disco
                                 primary_is_disco, // Whether primary agent was in disco
                             )
-                            .await
-                            .map_err(|e| e.to_string())?;
+                            .await;
+                        let agent_reply = match agent_response {
+                            Ok(reply) => reply,
+                            Err(e) => {
+                                save_failed_agent_slot(&conversation_id, &round_id, agent, response_type, &primary_msg_id, &e.to_string()).await?;
+                                failed_agents.push(agent.as_str().to_string());
+                                continue;
+                            }
+                        };
                         
                         // Save response
                         let msg = Message {
                             id: Uuid::new_v4().to_string(),
                             conversation_id: conversation_id.clone(),
                             role: agent.as_str().to_string(),
-                            content: agent_response.clone(),
+                            content: agent_reply.content.clone(),
                             response_type: Some(response_type.as_str().to_string()),
                             references_message_id: Some(primary_msg_id.clone()),
                             timestamp: Utc::now().to_rfc3339(),
-                        };
-                        db::save_message(&msg).map_err(|e| e.to_string())?;
+                            round_id: Some(round_id.clone()),
+                            latency_ms: Some(elapsed_ms(started)),
+                            ..Default::default()
+                        }.with_cost(agent_reply.cost.clone());
+                        save_reply(msg, agent_reply.cited_fact_ids).await?;
                         
                         responses.push(AgentResponse {
                             agent: agent.as_str().to_string(),
-                            content: agent_response,
+                            content: agent_reply.content,
                             response_type: response_type.as_str().to_string(),
                             references_message_id: Some(primary_msg_id.clone()),
+                            cost: agent_reply.cost,
                         });
                     }
                 }
@@ -976,6 +1772,7 @@ async fn send_message(
                     ));
                 }
                 
+                let started = Instant::now();
                 let secondary_response = orchestrator
                     .get_agent_response_with_grounding(
                         secondary_agent,
@@ -989,134 +1786,160 @@ async fn send_message(
                         secondary_is_disco, // Per-agent disco
                         primary_is_disco, // Whether primary agent was in disco
                     )
-                    .await
-                    .map_err(|e| e.to_string())?;
-                
-                // Save secondary response
-                let secondary_msg = Message {
-                    id: Uuid::new_v4().to_string(),
-                    conversation_id: conversation_id.clone(),
-                    role: secondary_agent.as_str().to_string(),
-                    content: secondary_response.clone(),
-                    response_type: Some(response_type.as_str().to_string()),
-                    references_message_id: Some(primary_msg_id.clone()),
-                    timestamp: Utc::now().to_rfc3339(),
-                };
-                db::save_message(&secondary_msg).map_err(|e| e.to_string())?;
+                    .await;
                 
-                responses.push(AgentResponse {
-                    agent: secondary_agent.as_str().to_string(),
-                    content: secondary_response.clone(),
-                    response_type: response_type.as_str().to_string(),
-                    references_message_id: Some(primary_msg_id.clone()),
-                });
-                
-                // Boost session weight for secondary agent (immediate, decays over conversation)
-                boost_session_weight(&conversation_id, secondary_agent, 0.015);
-                
-                // ===== MULTI-TURN DEBATE LOOP =====
-                // Allow debates when there's genuine disagreement (rebuttal/debate), not just additions
-                // Disco mode makes debates more likely/intense, but they can happen in normal mode too
-                if response_type != ResponseType::Addition {
-                    let mut responses_so_far: Vec<(String, String)> = vec![
-                        (primary_agent.as_str().to_string(), primary_response.clone()),
-                        (secondary_agent.as_str().to_string(), secondary_response.clone()),
-                    ];
+                match secondary_response {
+                    Err(e) => {
+                        save_failed_agent_slot(&conversation_id, &round_id, secondary_agent, response_type, &primary_msg_id, &e.to_string()).await?;
+                        failed_agents.push(secondary_agent.as_str().to_string());
+                    }
+                    Ok(secondary_reply) => {
+                        let secondary_response = secondary_reply.content.clone();
+                        // Save secondary response
+                        let secondary_msg = Message {
+                            id: Uuid::new_v4().to_string(),
+                            conversation_id: conversation_id.clone(),
+                            role: secondary_agent.as_str().to_string(),
+                            content: secondary_response.clone(),
+                            response_type: Some(response_type.as_str().to_string()),
+                            references_message_id: Some(primary_msg_id.clone()),
+                            timestamp: Utc::now().to_rfc3339(),
+                            round_id: Some(round_id.clone()),
+                            latency_ms: Some(elapsed_ms(started)),
+                            ..Default::default()
+                        }.with_cost(secondary_reply.cost.clone());
+                        let secondary_msg_id = secondary_msg.id.clone();
+                        save_reply(secondary_msg, secondary_reply.cited_fact_ids).await?;
                     
-                    let mut last_response = secondary_response.clone();
-                    let mut last_agent = secondary_agent.as_str().to_string();
-                    let mut last_agent_disco = secondary_is_disco;
-                    let mut last_msg_id = secondary_msg.id.clone();
+                        responses.push(AgentResponse {
+                            agent: secondary_agent.as_str().to_string(),
+                            content: secondary_response.clone(),
+                            response_type: response_type.as_str().to_string(),
+                            references_message_id: Some(primary_msg_id.clone()),
+                            cost: secondary_reply.cost,
+                        });
                     
-                    // Try to continue debate (up to 2 more responses, max 4 total)
-                    for turn in 0..2 {
-                        let response_count = responses_so_far.len();
-                        
-                        let (should_continue, next_agent_str, next_type) = orchestrator
-                            .should_continue_debate(
-                                &user_message,
-                                &responses_so_far,
-                                &active_agents,
-                                has_any_disco,
-                                response_count,
-                            )
-                            .await
-                            .unwrap_or((false, None, None));
+                        // Boost session weight for secondary agent (immediate, decays over conversation)
+                        boost_session_weight(&conversation_id, secondary_agent, 0.015);
+                    
+                        // ===== MULTI-TURN DEBATE LOOP =====
+                        // Allow debates when there's genuine disagreement (rebuttal/debate), not just additions
+                        // Disco mode makes debates more likely/intense, but they can happen in normal mode too
+                        if response_type != ResponseType::Addition {
+                            let mut responses_so_far: Vec<(String, String)> = vec![
+                                (primary_agent.as_str().to_string(), primary_response.clone()),
+                                (secondary_agent.as_str().to_string(), secondary_response.clone()),
+                            ];
                         
-                        if !should_continue {
-                            logging::log_agent(Some(&conversation_id), &format!(
-                                "Debate ending after {} responses (turn {})", response_count, turn
-                            ));
-                            break;
-                        }
+                            let mut last_response = secondary_response.clone();
+                            let mut last_agent = secondary_agent.as_str().to_string();
+                            let mut last_agent_disco = secondary_is_disco;
+                            let mut last_msg_id = secondary_msg_id.clone();
                         
-                        if let Some(next_agent_name) = next_agent_str {
-                            if let Some(next_agent) = Agent::from_str(&next_agent_name) {
-                                agents_involved.push(next_agent.as_str().to_string());
-                                
-                                let next_response_type = next_type
-                                    .as_ref()
-                                    .and_then(|t| ResponseType::from_str(t))
-                                    .unwrap_or(ResponseType::Rebuttal);
-                                
-                                let next_agent_disco = is_agent_disco(next_agent.as_str());
-                                logging::log_agent(Some(&conversation_id), &format!(
-                                    "Debate turn {}: {} responding (disco: {})", turn + 1, next_agent.as_str(), next_agent_disco
-                                ));
-                                
-                                let next_response = orchestrator
-                                    .get_agent_response_with_grounding(
-                                        next_agent,
+                            // Try to continue debate (up to 2 more responses, max 4 total)
+                            for turn in 0..2 {
+                                let response_count = responses_so_far.len();
+                            
+                                let (should_continue, next_agent_str, next_type) = orchestrator
+                                    .should_continue_debate(
                                         &user_message,
-                                        &recent_messages,
-                                        next_response_type,
-                                        Some(&last_response),
-                                        Some(&last_agent),
-                                        grounding.as_ref(),
-                                        user_profile.as_ref(),
-                                        next_agent_disco, // Per-agent disco
-                                        last_agent_disco, // Whether last agent was in disco
+                                        &responses_so_far,
+                                        &active_agents,
+                                        has_any_disco,
+                                        response_count,
                                     )
                                     .await
-                                    .map_err(|e| e.to_string())?;
-                                
-                                // Save debate response
-                                let next_msg_id = Uuid::new_v4().to_string();
-                                let next_msg = Message {
-                                    id: next_msg_id.clone(),
-                                    conversation_id: conversation_id.clone(),
-                                    role: next_agent.as_str().to_string(),
-                                    content: next_response.clone(),
-                                    response_type: Some(next_response_type.as_str().to_string()),
-                                    references_message_id: Some(last_msg_id.clone()),
-                                    timestamp: Utc::now().to_rfc3339(),
-                                };
-                                db::save_message(&next_msg).map_err(|e| e.to_string())?;
-                                
-                                responses.push(AgentResponse {
-                                    agent: next_agent.as_str().to_string(),
-                                    content: next_response.clone(),
-                                    response_type: next_response_type.as_str().to_string(),
-                                    references_message_id: Some(last_msg_id.clone()),
-                                });
-                                
-                                // Boost session weight for debate agent (immediate, decays over conversation)
-                                boost_session_weight(&conversation_id, next_agent, 0.015);
-                                
-                                // Update for next iteration
-                                responses_so_far.push((next_agent.as_str().to_string(), next_response.clone()));
-                                last_response = next_response;
-                                last_agent = next_agent.as_str().to_string();
-                                last_agent_disco = next_agent_disco;
-                                last_msg_id = next_msg_id;
-                                
-                                // Intensify debate mode if we're continuing
-                                if response_count >= 4 {
-                                    debate_mode = Some("intense".to_string());
+                                    .unwrap_or((false, None, None));
+                            
+                                if !should_continue {
+                                    logging::log_agent(Some(&conversation_id), &format!(
+                                        "Debate ending after {} responses (turn {})", response_count, turn
+                                    ));
+                                    break;
+                                }
+                            
+                                if let Some(next_agent_name) = next_agent_str {
+                                    if let Some(next_agent) = Agent::from_str(&next_agent_name) {
+                                        agents_involved.push(next_agent.as_str().to_string());
+                                    
+                                        let next_response_type = next_type
+                                            .as_ref()
+                                            .and_then(|t| ResponseType::from_str(t))
+                                            .unwrap_or(ResponseType::Rebuttal);
+                                    
+                                        let next_agent_disco = is_agent_disco(next_agent.as_str());
+                                        logging::log_agent(Some(&conversation_id), &format!(
+                                            "Debate turn {}: {} responding (disco: {})", turn + 1, next_agent.as_str(), next_agent_disco
+                                        ));
+                                    
+                                        let started = Instant::now();
+                                        let next_response = orchestrator
+                                            .get_agent_response_with_grounding(
+                                                next_agent,
+                                                &user_message,
+                                                &recent_messages,
+                                                next_response_type,
+                                                Some(&last_response),
+                                                Some(&last_agent),
+                                                grounding.as_ref(),
+                                                user_profile.as_ref(),
+                                                next_agent_disco, // Per-agent disco
+                                                last_agent_disco, // Whether last agent was in disco
+                                            )
+                                            .await;
+                                        let next_reply = match next_response {
+                                            Ok(reply) => reply,
+                                            Err(e) => {
+                                                save_failed_agent_slot(&conversation_id, &round_id, next_agent, next_response_type, &last_msg_id, &e.to_string()).await?;
+                                                failed_agents.push(next_agent.as_str().to_string());
+                                                break;
+                                            }
+                                        };
+                                        let next_response = next_reply.content.clone();
+                                    
+                                        // Save debate response
+                                        let next_msg_id = Uuid::new_v4().to_string();
+                                        let next_msg = Message {
+                                            id: next_msg_id.clone(),
+                                            conversation_id: conversation_id.clone(),
+                                            role: next_agent.as_str().to_string(),
+                                            content: next_response.clone(),
+                                            response_type: Some(next_response_type.as_str().to_string()),
+                                            references_message_id: Some(last_msg_id.clone()),
+                                            timestamp: Utc::now().to_rfc3339(),
+                                            round_id: Some(round_id.clone()),
+                                            latency_ms: Some(elapsed_ms(started)),
+                                            ..Default::default()
+                                        }.with_cost(next_reply.cost.clone());
+                                        save_reply(next_msg, next_reply.cited_fact_ids).await?;
+                                    
+                                        responses.push(AgentResponse {
+                                            agent: next_agent.as_str().to_string(),
+                                            content: next_response.clone(),
+                                            response_type: next_response_type.as_str().to_string(),
+                                            references_message_id: Some(last_msg_id.clone()),
+                                            cost: next_reply.cost,
+                                        });
+                                    
+                                        // Boost session weight for debate agent (immediate, decays over conversation)
+                                        boost_session_weight(&conversation_id, next_agent, 0.015);
+                                    
+                                        // Update for next iteration
+                                        responses_so_far.push((next_agent.as_str().to_string(), next_response.clone()));
+                                        last_response = next_response;
+                                        last_agent = next_agent.as_str().to_string();
+                                        last_agent_disco = next_agent_disco;
+                                        last_msg_id = next_msg_id;
+                                    
+                                        // Intensify debate mode if we're continuing
+                                        if response_count >= 4 {
+                                            debate_mode = Some("intense".to_string());
+                                        }
+                                    }
+                                } else {
+                                    break;
                                 }
                             }
-                        } else {
-                            break;
                         }
                     }
                 }
@@ -1125,7 +1948,7 @@ async fn send_message(
     }
     
     // ===== GOVERNOR SYNTHESIS: Generate synthesized response after reading agent thoughts =====
-    let governor_response = if !responses.is_empty() {
+    let (governor_response, governor_cost) = if !responses.is_empty() {
         // Collect agent responses as tuples of (agent_name, content)
         let agent_responses: Vec<(String, String)> = responses
             .iter()
@@ -1133,6 +1956,7 @@ async fn send_message(
             .collect();
         
         // Generate Governor's synthesized response
+        let started = Instant::now();
         match generate_governor_response(
             &anthropic_key,
             &user_message,
@@ -1140,8 +1964,9 @@ async fn send_message(
             &recent_messages,
             has_any_disco,
             user_profile.as_ref(),
+            reading_level,
         ).await {
-            Ok(response) => {
+            Ok((response, cost)) => {
                 // Save Governor response to database
                 let governor_msg = Message {
                     id: Uuid::new_v4().to_string(),
@@ -1151,31 +1976,54 @@ async fn send_message(
                     response_type: None,
                     references_message_id: None,
                     timestamp: Utc::now().to_rfc3339(),
-                };
-                if let Err(e) = db::save_message(&governor_msg) {
+                    round_id: Some(round_id.clone()),
+                    latency_ms: Some(elapsed_ms(started)),
+                    ..Default::default()
+                }.with_cost(cost.clone());
+                if let Err(e) = db::run(move || db::save_message(&governor_msg).map_err(|e| e.to_string())).await {
                     logging::log_error(Some(&conversation_id), &format!(
                         "Failed to save Governor response: {}", e
                     ));
                 }
-                Some(response)
+                (Some(response), cost)
             }
             Err(e) => {
                 logging::log_error(Some(&conversation_id), &format!(
                     "Failed to generate Governor response: {}", e
                 ));
-                None
+                (None, None)
             }
         }
     } else {
-        None
+        (None, None)
     };
     
-    // Increment message count
-    db::increment_message_count().map_err(|e| e.to_string())?;
+    // ===== CONTRADICTION WATCHDOG: replies vs. high-confidence facts =====
+    let replies: Vec<(String, String)> = responses.iter()
+        .filter(|r| !failed_agents.contains(&r.agent))
+        .map(|r| (r.agent.clone(), r.content.clone()))
+        .collect();
+    let (conversation, round, facts) = (conversation_id.clone(), round_id.clone(), existing_facts.clone());
+    let contradiction_corrections = db::run_infallible(move || watchdog::check_round(&conversation, &round, &replies, &facts)).await;
+
+    // ===== DISAGREEMENT: where a rebuttal or debate split from the first voice =====
+    let round_replies: Vec<(String, String, String)> = responses.iter()
+        .filter(|r| !failed_agents.contains(&r.agent))
+        .map(|r| (r.agent.clone(), r.response_type.clone(), r.content.clone()))
+        .collect();
+    let (conversation, round, mode) = (conversation_id.clone(), round_id.clone(), debate_mode.clone());
+    let disagreement = db::run(move || {
+        let disagreement = disagreement::record_round(&conversation, &round, mode.as_deref(), &round_replies);
+        
+        // Increment message count
+        db::increment_message_count().map_err(|e| e.to_string())?;
+        Ok(disagreement)
+    }).await?;
     
     // ===== TRAIT ANALYSIS: Run in background AFTER response (non-blocking) =====
-    // This was moved from before routing to improve response speed
-    {
+    // This was moved from before routing to improve response speed.
+    // Nothing below learns from an incognito conversation.
+    if !controls.incognito {
         let anthropic_key_for_traits = anthropic_key.clone();
         let user_message_for_traits = user_message.clone();
         let conversation_id_for_traits = conversation_id.clone();
@@ -1233,67 +2081,72 @@ async fn send_message(
             
             // 3. Update weights if we have analysis
             if intrinsic_analysis.is_some() || engagement_analysis.is_some() {
-                if let Ok(current_profile) = db::get_user_profile() {
-                    let current_weights = (current_profile.instinct_weight, current_profile.logic_weight, current_profile.psyche_weight);
-                    
-                    let new_weights = combine_trait_analyses(
-                        current_weights,
-                        engagement_analysis.as_ref(),
-                        intrinsic_analysis.as_ref(),
-                        has_any_disco_for_traits,
-                        total_messages_for_traits,
-                    );
-                    
-                    if let Err(e) = db::update_weights(new_weights.0, new_weights.1, new_weights.2) {
-                        logging::log_error(Some(&conversation_id_for_traits), &format!(
-                            "[BACKGROUND] Failed to update weights: {}", e
-                        ));
-                    } else {
-                        logging::log_routing(Some(&conversation_id_for_traits), &format!(
-                            "[BACKGROUND] Updated weights - I:{:.3} L:{:.3} P:{:.3}",
-                            new_weights.0, new_weights.1, new_weights.2
-                        ));
+                db::run_infallible(move || {
+                    if let Ok(current_profile) = db::get_user_profile() {
+                        let current_weights = (current_profile.instinct_weight, current_profile.logic_weight, current_profile.psyche_weight);
+                        
+                        let new_weights = combine_trait_analyses(
+                            current_weights,
+                            engagement_analysis.as_ref(),
+                            intrinsic_analysis.as_ref(),
+                            has_any_disco_for_traits,
+                            total_messages_for_traits,
+                        );
+                        
+                        if let Err(e) = db::update_weights(new_weights.0, new_weights.1, new_weights.2) {
+                            logging::log_error(Some(&conversation_id_for_traits), &format!(
+                                "[BACKGROUND] Failed to update weights: {}", e
+                            ));
+                        } else {
+                            logging::log_routing(Some(&conversation_id_for_traits), &format!(
+                                "[BACKGROUND] Updated weights - I:{:.3} L:{:.3} P:{:.3}",
+                                new_weights.0, new_weights.1, new_weights.2
+                            ));
+                        }
                     }
-                }
+                }).await;
             }
         });
     }
     
     // ===== MEMORY SYSTEM: Extract Facts & Patterns (async, non-blocking) =====
-    let anthropic_key_clone = anthropic_key.clone();
-    let user_message_clone = user_message.clone();
-    let conversation_id_clone = conversation_id.clone();
-    let responses_for_extraction: Vec<(String, String)> = responses
-        .iter()
-        .map(|r| (r.agent.clone(), r.content.clone()))
-        .collect();
-    let existing_facts_clone = existing_facts;
-    
-    logging::log_memory(Some(&conversation_id), "Spawning extraction task...");
-    
-    // Spawn memory extraction as a background task (uses Anthropic Opus)
-    tokio::spawn(async move {
-        logging::log_memory(Some(&conversation_id_clone), "Extraction task started");
-        let extractor = MemoryExtractor::new(&anthropic_key_clone);
-        match extractor.extract_from_exchange(
-            &user_message_clone,
-            &responses_for_extraction,
-            &existing_facts_clone,
-            &conversation_id_clone,
-        ).await {
-            Ok(result) => logging::log_memory(Some(&conversation_id_clone), &format!(
-                "Extraction completed: {} facts, {} patterns",
-                result.new_facts.len(), result.new_patterns.len()
-            )),
-            Err(e) => logging::log_error(Some(&conversation_id_clone), &format!(
-                "Extraction failed: {}", e
-            )),
-        }
-    });
+    if !controls.incognito {
+        let anthropic_key_clone = anthropic_key.clone();
+        let user_message_clone = user_message.clone();
+        let conversation_id_clone = conversation_id.clone();
+        let round_id_for_extraction = round_id.clone();
+        let responses_for_extraction: Vec<(String, String)> = responses
+            .iter()
+            .map(|r| (r.agent.clone(), r.content.clone()))
+            .collect();
+        let existing_facts_clone = existing_facts;
+        
+        logging::log_memory(Some(&conversation_id), "Spawning extraction task...");
+        
+        // Spawn memory extraction as a background task (uses Anthropic Opus)
+        tokio::spawn(async move {
+            logging::log_memory(Some(&conversation_id_clone), "Extraction task started");
+            let extractor = MemoryExtractor::new(&anthropic_key_clone).for_round(&round_id_for_extraction);
+            match extractor.extract_from_exchange(
+                &user_message_clone,
+                &responses_for_extraction,
+                &existing_facts_clone,
+                &conversation_id_clone,
+            ).await {
+                Ok(result) => logging::log_memory(Some(&conversation_id_clone), &format!(
+                    "Extraction completed: {} facts, {} patterns",
+                    result.new_facts.len(), result.new_patterns.len()
+                )),
+                Err(e) => logging::log_error(Some(&conversation_id_clone), &format!(
+                    "Extraction failed: {}", e
+                )),
+            }
+        });
+    }
     
     // ===== MEMORY SYSTEM: Append to Limbo Summary (crash-safe incremental summary) =====
     // This happens every exchange so the conversation is always recoverable
-    {
+    if !controls.incognito {
         let agents_summary: Vec<String> = responses.iter()
             .map(|r| format!("{}: {}", r.agent, truncate_for_summary(&r.content, 100)))
             .collect();
@@ -1302,13 +2155,14 @@ async fn send_message(
             truncate_for_summary(&user_message, 100),
             agents_summary.join("\n")
         );
-        let _ = db::append_limbo_summary(&conversation_id, &exchange_note);
+        let conversation = conversation_id.clone();
+        let _ = db::run(move || db::append_limbo_summary(&conversation, &exchange_note).map_err(|e| e.to_string())).await;
         logging::log_memory(Some(&conversation_id), "Appended exchange to limbo summary");
     }
     
     // ===== MEMORY SYSTEM: Summarize Conversation Periodically =====
     let message_count = profile.total_messages + 1;
-    if message_count % 10 == 0 {
+    if message_count % 10 == 0 && !controls.incognito {
         // Every 10 messages, update conversation summary (uses Anthropic Opus)
         let anthropic_key_for_summary = anthropic_key.clone();
         let conversation_id_for_summary = conversation_id.clone();
@@ -1316,10 +2170,14 @@ async fn send_message(
         
         tokio::spawn(async move {
             let summarizer = ConversationSummarizer::new(&anthropic_key_for_summary);
-            let all_messages = db::get_conversation_messages(&conversation_id_for_summary).unwrap_or_default();
-            
-            // Get existing summary
-            let existing = db::get_conversation_summary(&conversation_id_for_summary).ok().flatten();
+            let conversation = conversation_id_for_summary.clone();
+            let (all_messages, existing) = db::run_infallible(move || {
+                let all_messages = db::get_delivered_messages(&conversation).unwrap_or_default();
+                
+                // Get existing summary
+                let existing = db::get_conversation_summary(&conversation).ok().flatten();
+                (all_messages, existing)
+            }).await;
             let existing_text = existing.as_ref().map(|s| s.summary.as_str());
             
             // Only summarize messages not in the existing summary
@@ -1331,36 +2189,39 @@ async fn send_message(
             };
             
             if let Ok(result) = summarizer.summarize(&messages_to_summarize, existing_text).await {
-                let _ = ConversationSummarizer::save_summary(
-                    &conversation_id_for_summary,
-                    &result,
-                    message_count,
-                    &agents_for_summary,
-                );
+                db::run_infallible(move || {
+                    let _ = ConversationSummarizer::save_summary(
+                        &conversation_id_for_summary,
+                        &result,
+                        message_count,
+                        &agents_for_summary,
+                    );
+                }).await;
             }
         });
     }
     
     // Weight changes are handled by background analysis only (base weights)
     // Session weights decay automatically and don't generate notifications
-    Ok(SendMessageResult { responses, debate_mode, weight_change: None, governor_response })
+    Ok(SendMessageResult { responses, debate_mode, weight_change: None, governor_response, governor_cost, round_id: Some(round_id), failed_agents, memory_correction, related_conversations, control: None, contradiction_corrections, disagreement })
 }
 
 // ============ User Context (Legacy) ============
 
 #[tauri::command]
-fn get_user_context() -> Result<Vec<UserContext>, String> {
-    db::get_all_user_context().map_err(|e| e.to_string())
+async fn get_user_context() -> Result<Vec<UserContext>, String> {
+    db::run(move || db::get_all_user_context().map_err(|e| e.to_string())).await
 }
 
 #[tauri::command]
-fn clear_user_context() -> Result<(), String> {
-    db::clear_user_context().map_err(|e| e.to_string())
+async fn clear_user_context() -> Result<(), String> {
+    db::run(move || db::clear_user_context().map_err(|e| e.to_string())).await
 }
 
 // ============ Memory System Commands ============
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct MemoryStats {
     pub fact_count: usize,
     pub pattern_count: usize,
@@ -1370,102 +2231,151 @@ pub struct MemoryStats {
     pub top_themes: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct FactInfo {
+    pub id: i64,
     pub category: String,
     pub key: String,
     pub value: String,
     pub confidence: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
 pub struct PatternInfo {
+    pub id: i64,
     pub pattern_type: String,
     pub description: String,
     pub confidence: f64,
+    pub verdict: Option<String>,
+}
+
+/// Plain-text summary of memory for pasting into other AI tools as a system preamble
+#[tauri::command]
+async fn export_persona_seed() -> Result<String, String> {
+    db::run(move || {
+        seed::export_persona_seed()
+    }).await
+}
+
+/// Run pasted text through memory extraction; results wait in staging under the returned seed id
+#[tauri::command]
+async fn import_persona_seed(text: String) -> Result<seed::SeedImport, String> {
+    seed::import_persona_seed(&text).await
+}
+
+/// Move a reviewed seed's remaining staged items into long-term memory
+#[tauri::command]
+async fn commit_persona_seed(seed_id: String) -> Result<usize, String> {
+    db::run(move || seed::commit_persona_seed(&seed_id)).await
 }
 
 #[tauri::command]
-fn get_memory_stats() -> Result<MemoryStats, String> {
-    let facts = db::get_all_user_facts().unwrap_or_default();
-    let patterns = db::get_all_user_patterns().unwrap_or_default();
-    let themes = db::get_top_themes(10).unwrap_or_default();
+async fn discard_persona_seed(seed_id: String) -> Result<usize, String> {
+    db::run(move || seed::discard_persona_seed(&seed_id)).await
+}
+
+#[tauri::command]
+async fn get_memory_stats() -> Result<MemoryStats, String> {
+    db::run(move || {
+        let facts = db::get_all_user_facts().unwrap_or_default();
+        let patterns = db::get_all_user_patterns().unwrap_or_default();
+        let themes = db::get_top_themes(10).unwrap_or_default();
     
-    let top_facts: Vec<FactInfo> = facts
-        .iter()
-        .take(10)
-        .map(|f| FactInfo {
-            category: f.category.clone(),
-            key: f.key.clone(),
-            value: f.value.clone(),
-            confidence: f.confidence,
-        })
-        .collect();
+        let top_facts: Vec<FactInfo> = facts
+            .iter()
+            .take(10)
+            .map(|f| FactInfo {
+                id: f.id,
+                category: f.category.clone(),
+                key: f.key.clone(),
+                value: f.value.clone(),
+                confidence: f.confidence,
+            })
+            .collect();
     
-    let top_patterns: Vec<PatternInfo> = patterns
-        .iter()
-        .take(5)
-        .map(|p| PatternInfo {
-            pattern_type: p.pattern_type.clone(),
-            description: p.description.clone(),
-            confidence: p.confidence,
-        })
-        .collect();
+        let top_patterns: Vec<PatternInfo> = patterns
+            .iter()
+            .take(5)
+            .map(|p| PatternInfo {
+                id: p.id,
+                pattern_type: p.pattern_type.clone(),
+                description: p.description.clone(),
+                confidence: p.confidence,
+                verdict: p.verdict.clone(),
+            })
+            .collect();
     
-    let top_themes: Vec<String> = themes.iter().map(|t| t.theme.clone()).collect();
+        let top_themes: Vec<String> = themes.iter().map(|t| t.theme.clone()).collect();
     
-    Ok(MemoryStats {
-        fact_count: facts.len(),
-        pattern_count: patterns.len(),
-        theme_count: themes.len(),
-        top_facts,
-        top_patterns,
-        top_themes,
-    })
+        Ok(anonymize::render(MemoryStats {
+            fact_count: facts.len(),
+            pattern_count: patterns.len(),
+            theme_count: themes.len(),
+            top_facts,
+            top_patterns,
+            top_themes,
+        }))
+    }).await
 }
 
 #[tauri::command]
-fn update_weights(instinct: f64, logic: f64, psyche: f64) -> Result<(), String> {
-    db::update_weights(instinct, logic, psyche).map_err(|e| e.to_string())
+async fn update_weights(instinct: f64, logic: f64, psyche: f64) -> Result<(), String> {
+    db::run(move || db::update_weights(instinct, logic, psyche).map_err(|e| e.to_string())).await
 }
 
+/// Who would answer a sample message under these weights, with cheap-model (or mock) replies; nothing is saved
 #[tauri::command]
-fn update_points(instinct: i64, logic: i64, psyche: i64) -> Result<(), String> {
-    db::update_points(instinct, logic, psyche).map_err(|e| e.to_string())
+async fn preview_weights(
+    instinct: f64,
+    logic: f64,
+    psyche: f64,
+    sample_message: String,
+    mock: Option<bool>,
+) -> Result<preview::WeightPreview, String> {
+    preview::preview_weights(instinct, logic, psyche, &sample_message, mock.unwrap_or(false)).await
 }
 
 #[tauri::command]
-fn get_user_profile_summary() -> Result<String, String> {
-    let profile = MemoryExtractor::build_profile_summary()
-        .map_err(|e| e.to_string())?;
-    
-    // Format as readable summary
-    let mut parts = Vec::new();
-    
-    // Facts by category
-    for (category, facts) in &profile.facts_by_category {
-        if !facts.is_empty() {
-            let items: Vec<String> = facts.iter().map(|f| format!("  - {}: {}", f.key, f.value)).collect();
-            parts.push(format!("**{}**\n{}", category.to_uppercase(), items.join("\n")));
+async fn update_points(instinct: i64, logic: i64, psyche: i64) -> Result<(), String> {
+    db::run(move || db::update_points(instinct, logic, psyche).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn get_user_profile_summary() -> Result<String, String> {
+    db::run(move || {
+        let profile = MemoryExtractor::build_profile_summary(None, None)
+            .map_err(|e| e.to_string())?;
+        
+        // Format as readable summary
+        let mut parts = Vec::new();
+        
+        // Facts by category
+        for (category, facts) in &profile.facts_by_category {
+            if !facts.is_empty() {
+                let items: Vec<String> = facts.iter().map(|f| format!("  - {}: {}", f.key, f.value)).collect();
+                parts.push(format!("**{}**\n{}", category.to_uppercase(), items.join("\n")));
+            }
         }
-    }
-    
-    // Patterns
-    if !profile.top_patterns.is_empty() {
-        let items: Vec<String> = profile.top_patterns.iter().map(|p| format!("  - {}: {}", p.pattern_type, p.description)).collect();
-        parts.push(format!("**BEHAVIORAL PATTERNS**\n{}", items.join("\n")));
-    }
-    
-    // Themes
-    if !profile.recurring_themes.is_empty() {
-        parts.push(format!("**RECURRING THEMES**\n  {}", profile.recurring_themes.join(", ")));
-    }
-    
-    if parts.is_empty() {
-        Ok("No profile data yet. Keep chatting to build your profile!".to_string())
-    } else {
-        Ok(parts.join("\n\n"))
-    }
+        
+        // Patterns
+        if !profile.top_patterns.is_empty() {
+            let items: Vec<String> = profile.top_patterns.iter().map(|p| format!("  - {}: {}", p.pattern_type, p.description)).collect();
+            parts.push(format!("**BEHAVIORAL PATTERNS**\n{}", items.join("\n")));
+        }
+        
+        // Themes
+        if !profile.recurring_themes.is_empty() {
+            parts.push(format!("**RECURRING THEMES**\n  {}", profile.recurring_themes.join(", ")));
+        }
+        
+        if parts.is_empty() {
+            Ok("No profile data yet. Keep chatting to build your profile!".to_string())
+        } else {
+            Ok(anonymize::render(parts.join("\n\n")))
+        }
+    }).await
 }
 
 // ============ Governor Report Generation ============
@@ -1474,18 +2384,21 @@ fn get_user_profile_summary() -> Result<String, String> {
 async fn generate_governor_report(profile_id: Option<String>) -> Result<String, String> {
     use crate::anthropic::{AnthropicClient, AnthropicMessage, ThinkingBudget, CLAUDE_SONNET};
     
-    // Get Anthropic API key
-    let user_profile = db::get_user_profile().map_err(|e| e.to_string())?;
+    let (user_profile, profiles, facts, patterns, themes) = db::run(|| {
+        // Get Anthropic API key
+        let user_profile = db::get_user_profile().map_err(|e| e.to_string())?;
+        
+        // Get all persona profiles
+        let profiles = db::get_all_persona_profiles().map_err(|e| e.to_string())?;
+        
+        // Get knowledge base data
+        let facts = db::get_all_user_facts().unwrap_or_default();
+        let patterns = db::get_prompt_user_patterns().unwrap_or_default();
+        let themes = db::get_prompt_themes(10).unwrap_or_default();
+        Ok((user_profile, profiles, facts, patterns, themes))
+    }).await?;
     let anthropic_key = user_profile.anthropic_key.ok_or("Anthropic API key not set")?;
     
-    // Get all persona profiles
-    let profiles = db::get_all_persona_profiles().map_err(|e| e.to_string())?;
-    
-    // Get knowledge base data
-    let facts = db::get_all_user_facts().unwrap_or_default();
-    let patterns = db::get_all_user_patterns().unwrap_or_default();
-    let themes = db::get_all_recurring_themes().unwrap_or_default();
-    
     // Build context for the LLM
     let facts_text = if facts.is_empty() {
         "No facts learned yet.".to_string()
@@ -1575,7 +2488,7 @@ STYLE:
     );
     
     // Use Sonnet (non-thinking) for fast report generation
-    let client = AnthropicClient::new(&anthropic_key);
+    let client = AnthropicClient::new(&anthropic_key).with_usage_category("governor");
     let messages = vec![
         AnthropicMessage {
             role: "user".to_string(),
@@ -1585,7 +2498,7 @@ STYLE:
     
     let response = client.chat_completion_advanced(
         CLAUDE_SONNET,
-        Some(system_prompt),
+        Some(&format!("{}{}{}", system_prompt, boundaries::house_rules_section(), i18n::reply_language_suffix())),
         messages,
         0.7, // Slightly creative
         Some(150), // 2 sentences max
@@ -1601,14 +2514,16 @@ STYLE:
 async fn generate_user_summary() -> Result<String, String> {
     use crate::anthropic::{AnthropicClient, AnthropicMessage, ThinkingBudget, CLAUDE_SONNET};
     
-    let user_profile = db::get_user_profile().map_err(|e| e.to_string())?;
+    let (user_profile, profiles, facts, patterns, themes) = db::run(|| {
+        let user_profile = db::get_user_profile().map_err(|e| e.to_string())?;
+        let profiles = db::get_all_persona_profiles().map_err(|e| e.to_string())?;
+        let facts = db::get_all_user_facts().unwrap_or_default();
+        let patterns = db::get_prompt_user_patterns().unwrap_or_default();
+        let themes = db::get_prompt_themes(10).unwrap_or_default();
+        Ok((user_profile, profiles, facts, patterns, themes))
+    }).await?;
     let anthropic_key = user_profile.anthropic_key.ok_or("Anthropic API key not set")?;
     
-    let profiles = db::get_all_persona_profiles().map_err(|e| e.to_string())?;
-    let facts = db::get_all_user_facts().unwrap_or_default();
-    let patterns = db::get_all_user_patterns().unwrap_or_default();
-    let themes = db::get_all_recurring_themes().unwrap_or_default();
-    
     let total_messages: i64 = profiles.iter().map(|p| p.message_count).sum();
     
     if total_messages < 5 {
@@ -1635,7 +2550,7 @@ Style:
 - When using dashes for pauses or asides, ALWAYS use double dashes with spaces: " -- " (not " - ")
 - Example: "They're curious about everything -- sometimes too curious for their own good.""#;
 
-    let client = AnthropicClient::new(&anthropic_key);
+    let client = AnthropicClient::new(&anthropic_key).with_usage_category("governor");
     let messages = vec![
         AnthropicMessage {
             role: "user".to_string(),
@@ -1645,7 +2560,7 @@ Style:
     
     client.chat_completion_advanced(
         CLAUDE_SONNET,
-        Some(system_prompt),
+        Some(&format!("{}{}{}", system_prompt, boundaries::house_rules_section(), i18n::reply_language_suffix())),
         messages,
         0.7,
         Some(200),
@@ -1653,68 +2568,1701 @@ Style:
     ).await.map_err(|e| e.to_string())
 }
 
-// ============ Reset ============
+// ============ Session Intensity ============
 
+#[derive(Debug, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ConversationExplanation {
+    pub conversation_id: String,
+    pub is_disco: bool,
+    pub intensity: f64,
+    pub intensity_label: String,
+    pub intensity_ceiling: f64,
+}
+
+/// Explain the current session dynamics for a conversation
 #[tauri::command]
-fn reset_all_data() -> Result<(), String> {
-    db::reset_all_data().map_err(|e| e.to_string())
+async fn explain_conversation(conversation_id: String) -> Result<ConversationExplanation, String> {
+    db::run(move || {
+        let conversation = db::get_conversation(&conversation_id)
+            .map_err(|e| e.to_string())?
+            .ok_or("Conversation not found")?;
+    
+        Ok(ConversationExplanation {
+            conversation_id: conversation.id,
+            is_disco: conversation.is_disco,
+            intensity: conversation.intensity,
+            intensity_label: intensity::label(conversation.intensity).to_string(),
+            intensity_ceiling: intensity::ceiling(),
+        })
+    }).await
 }
 
-// ============ Window Controls ============
+/// Days without messages before a conversation is auto-closed and summarized
+#[tauri::command]
+async fn set_auto_close_days(days: i64) -> Result<(), String> {
+    db::run(move || {
+        if days < 1 {
+            return Err("Auto-close must be at least 1 day".to_string());
+        }
+        db::set_setting(jobs::AUTO_CLOSE_DAYS_SETTING_KEY, &days.to_string()).map_err(|e| e.to_string())
+    }).await
+}
 
 #[tauri::command]
-async fn set_always_on_top(window: tauri::Window, always_on_top: bool) -> Result<(), String> {
-    window.set_always_on_top(always_on_top).map_err(|e| e.to_string())
+async fn set_intensity_ceiling(ceiling: f64) -> Result<(), String> {
+    db::run(move || {
+        if !(0.0..=1.0).contains(&ceiling) {
+            return Err("Intensity ceiling must be between 0.0 and 1.0".to_string());
+        }
+        db::set_setting(intensity::CEILING_SETTING_KEY, &ceiling.to_string()).map_err(|e| e.to_string())
+    }).await
 }
 
+// ============ Boundaries ============
+
 #[tauri::command]
-fn get_governor_disco_image() -> Result<Option<String>, String> {
-    use std::path::PathBuf;
-    use std::fs;
-    
-    // Get home directory
-    let home = std::env::var("HOME").map_err(|e| format!("Failed to get HOME: {}", e))?;
-    let desktop_path = PathBuf::from(home).join("Desktop/the_governor-disco_mode.png");
-    
-    // Check if file exists
-    if !desktop_path.exists() {
-        return Ok(None);
-    }
-    
-    // Read file as bytes
-    let bytes = fs::read(&desktop_path).map_err(|e| format!("Failed to read file: {}", e))?;
-    
-    // Convert to base64 data URL
-    use base64::{Engine as _, engine::general_purpose};
-    let base64 = general_purpose::STANDARD.encode(&bytes);
-    let data_url = format!("data:image/png;base64,{}", base64);
-    
-    Ok(Some(data_url))
+async fn get_boundary_rules() -> Result<Vec<db::BoundaryRule>, String> {
+    db::run(move || db::get_boundary_rules().map_err(|e| e.to_string())).await
 }
 
+/// Create or update a boundary rule. Pass `id` to update an existing rule.
 #[tauri::command]
-fn get_governor_image() -> Result<Option<String>, String> {
-    use std::path::PathBuf;
-    use std::fs;
-    
-    // Get home directory
-    let home = std::env::var("HOME").map_err(|e| format!("Failed to get HOME: {}", e))?;
-    let desktop_path = PathBuf::from(home).join("Desktop/the_governor.png");
-    
-    // Check if file exists
-    if !desktop_path.exists() {
-        return Ok(None);
-    }
-    
-    // Read file as bytes
-    let bytes = fs::read(&desktop_path).map_err(|e| format!("Failed to read file: {}", e))?;
-    
-    // Convert to base64 data URL
-    use base64::{Engine as _, engine::general_purpose};
-    let base64 = general_purpose::STANDARD.encode(&bytes);
-    let data_url = format!("data:image/png;base64,{}", base64);
-    
-    Ok(Some(data_url))
+#[allow(clippy::too_many_arguments)]
+async fn save_boundary_rule(
+    id: Option<String>,
+    agent: String,
+    description: String,
+    keywords: Vec<String>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    action: String,
+    enabled: Option<bool>,
+) -> Result<db::BoundaryRule, String> {
+    db::run(move || {
+        if !matches!(agent.as_str(), "instinct" | "logic" | "psyche" | "all") {
+            return Err(format!("Invalid agent: {}", agent));
+        }
+        if action != boundaries::ACTION_SOFTEN && action != boundaries::ACTION_SUPPRESS {
+            return Err(format!("Invalid boundary action: {}", action));
+        }
+        for time in [&start_time, &end_time].into_iter().flatten() {
+            chrono::NaiveTime::parse_from_str(time, "%H:%M")
+                .map_err(|_| format!("Invalid time (expected HH:MM): {}", time))?;
+        }
+        
+        let mut rule = db::BoundaryRule {
+            id: id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+            agent,
+            description,
+            keywords: serde_json::to_string(&keywords).map_err(|e| e.to_string())?,
+            start_time,
+            end_time,
+            action,
+            enabled: enabled.unwrap_or(true),
+            created_at: Utc::now().to_rfc3339(),
+        };
+        rule.created_at = db::save_boundary_rule(&rule).map_err(|e| e.to_string())?;
+        Ok(rule)
+    }).await
+}
+
+#[tauri::command]
+async fn delete_boundary_rule(id: String) -> Result<(), String> {
+    db::run(move || db::delete_boundary_rule(&id).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn get_house_rules() -> Option<String> {
+    db::run_infallible(boundaries::house_rules).await
+}
+
+/// Replace the house rules appended to every agent's prompt; blank text clears them
+#[tauri::command]
+async fn set_house_rules(text: String) -> Result<Option<String>, String> {
+    db::run(move || boundaries::set_house_rules(&text)).await
+}
+
+/// Violation counts from the response filter pipeline, grouped by filter and agent
+#[tauri::command]
+async fn get_filter_violation_stats() -> Result<Vec<db::FilterViolationStat>, String> {
+    db::run(move || db::get_filter_violation_stats().map_err(|e| e.to_string())).await
+}
+
+// ============ Grounding Exercises ============
+
+#[tauri::command]
+async fn get_grounding_exercises() -> Result<Vec<db::GroundingExercise>, String> {
+    db::run(move || db::get_all_grounding_exercises().map_err(|e| e.to_string())).await
+}
+
+/// Run a Psyche-guided grounding exercise. Steps are paced via "exercise-step" events;
+/// intro/outro lines are also saved into the conversation when one is given.
+#[tauri::command]
+async fn run_grounding_exercise(
+    app_handle: tauri::AppHandle,
+    exercise_id: String,
+    conversation_id: Option<String>,
+) -> Result<db::ExerciseSession, String> {
+    exercises::run_exercise(&app_handle, &exercise_id, conversation_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+// ============ Localization ============
+
+/// UI strings for a locale (default: the active language)
+#[tauri::command]
+async fn get_locale_bundle(locale: Option<String>) -> i18n::LocaleBundle {
+    db::run_infallible(move || i18n::bundle(locale.as_deref())).await
+}
+
+/// Switch the language used for built-in prompts, agent names, and UI strings
+#[tauri::command]
+async fn set_language(locale: String) -> Result<i18n::LocaleBundle, String> {
+    db::run(move || {
+        let locale = i18n::set_locale(&locale)?;
+        Ok(i18n::bundle(Some(&locale)))
+    }).await
+}
+
+/// Override one string (or `prompt.*` key) for a locale; `None` restores the bundled value
+#[tauri::command]
+async fn set_locale_string(locale: String, key: String, value: Option<String>) -> Result<(), String> {
+    db::run(move || {
+        let locale = locale.trim().to_lowercase();
+        if locale.is_empty() || key.trim().is_empty() {
+            return Err("Locale and key are required".to_string());
+        }
+        match value {
+            Some(value) => db::set_locale_string(&locale, key.trim(), &value),
+            None => db::delete_locale_string(&locale, key.trim()),
+        }
+        .map_err(|e| e.to_string())
+    }).await
+}
+
+// ============ Agent Manifest ============
+
+/// Presentation metadata for every agent, in display order
+#[tauri::command]
+async fn get_agent_manifest() -> Result<Vec<db::AgentPresentation>, String> {
+    db::run(agents::manifest).await
+}
+
+/// Rename or restyle an agent (creates a custom entry for unknown ids)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn update_agent_presentation(
+    id: String,
+    display_name: Option<String>,
+    disco_name: Option<String>,
+    color: Option<String>,
+    disco_color: Option<String>,
+    icon: Option<String>,
+    description: Option<String>,
+    voice_samples: Option<Vec<String>>,
+) -> Result<db::AgentPresentation, String> {
+    db::run(move || agents::update_agent(&id, display_name, disco_name, color, disco_color, icon, description, voice_samples)).await
+}
+
+// ============ Plain Language ============
+
+/// Set a profile's plain-language reading level (grade 3-12); `None` turns it off
+#[tauri::command]
+async fn set_profile_reading_level(profile_id: String, level: Option<i64>) -> Result<(), String> {
+    db::run(move || {
+        let level = level.map(readability::validate).transpose()?;
+        if !db::set_profile_reading_level(&profile_id, level).map_err(|e| e.to_string())? {
+            return Err("Profile not found".to_string());
+        }
+        Ok(())
+    }).await
+}
+
+/// Override plain language for one conversation: a level, 0 for off, or `None` to follow the profile
+#[tauri::command]
+async fn set_conversation_reading_level(conversation_id: String, level: Option<i64>) -> Result<(), String> {
+    db::run(move || {
+        let level = match level {
+            Some(0) | None => level,
+            Some(level) => Some(readability::validate(level)?),
+        };
+        db::set_conversation_reading_level(&conversation_id, level).map_err(|e| e.to_string())
+    }).await
+}
+
+/// The reading level that applies to a conversation right now (None = plain language off)
+#[tauri::command]
+async fn get_reading_level(conversation_id: String) -> Option<u8> {
+    db::run_infallible(move || readability::resolve(&conversation_id)).await
+}
+
+// ============ Late-Night Mode ============
+
+/// A profile's late-night window, with whether it's in effect now (None = no late-night policy)
+#[tauri::command]
+async fn get_late_night_window(profile_id: String) -> Result<Option<late_night::LateNightWindow>, String> {
+    db::run(move || late_night::get_window(&profile_id)).await
+}
+
+/// Set a profile's late-night window (local "HH:MM", may wrap past midnight); `None` turns it off
+#[tauri::command]
+async fn set_late_night_window(profile_id: String, start: Option<String>, end: Option<String>) -> Result<(), String> {
+    db::run(move || {
+        let window = match (start.as_deref(), end.as_deref()) {
+            (Some(start), Some(end)) => Some((start, end)),
+            (None, None) => None,
+            _ => return Err("Set both a start and an end time".to_string()),
+        };
+        late_night::set_window(&profile_id, window)
+    }).await
+}
+
+// ============ Session Timer ============
+
+/// Where a conversation's session stands against its time budget
+#[tauri::command]
+async fn get_session_timer(conversation_id: String) -> session_timer::SessionTimer {
+    db::run_infallible(move || session_timer::status(&conversation_id)).await
+}
+
+/// Set this session's time budget in minutes (None or 0 = no budget)
+#[tauri::command]
+async fn set_session_time_budget(conversation_id: String, minutes: Option<i64>) -> Result<session_timer::SessionTimer, String> {
+    db::run(move || session_timer::set_budget(&conversation_id, minutes)).await
+}
+
+/// Start the session clock over, keeping the budget
+#[tauri::command]
+async fn restart_session_timer(conversation_id: String) -> session_timer::SessionTimer {
+    db::run_infallible(move || session_timer::restart(&conversation_id)).await
+}
+
+/// Budget new sessions start with (None = no budget)
+#[tauri::command]
+async fn get_default_session_time_budget() -> Option<i64> {
+    db::run_infallible(session_timer::default_budget).await
+}
+
+#[tauri::command]
+async fn set_default_session_time_budget(minutes: Option<i64>) -> Result<(), String> {
+    db::run(move || session_timer::set_default_budget(minutes)).await
+}
+
+// ============ Conversation Replay ============
+
+/// Replay a conversation with its original pacing via "replay-*" events.
+/// `speed` scales the delays (2.0 = twice as fast).
+#[tauri::command]
+async fn replay_conversation(
+    app_handle: tauri::AppHandle,
+    conversation_id: String,
+    speed: Option<f64>,
+) -> Result<usize, String> {
+    replay::replay_conversation(&app_handle, &conversation_id, speed.unwrap_or(1.0)).await
+}
+
+// ============ Quiet Hours / Do Not Disturb ============
+
+#[tauri::command]
+async fn get_quiet_policy() -> policy::PolicyState {
+    db::run_infallible(policy::get_state).await
+}
+
+#[tauri::command]
+async fn set_quiet_hours(enabled: bool, start: String, end: String) -> Result<policy::PolicyState, String> {
+    db::run(move || {
+        policy::set_quiet_hours(enabled, &start, &end)?;
+        Ok(policy::get_state())
+    }).await
+}
+
+/// Toggle DND; `minutes` = None means until turned off
+#[tauri::command]
+async fn set_do_not_disturb(enabled: bool, minutes: Option<i64>) -> Result<policy::PolicyState, String> {
+    db::run(move || {
+        policy::set_do_not_disturb(enabled, minutes)?;
+        Ok(policy::get_state())
+    }).await
+}
+
+// ============ Power ============
+
+#[tauri::command]
+async fn get_power_state() -> power::PowerState {
+    db::run_infallible(power::get_state).await
+}
+
+/// Whether heavy background jobs wait for AC power (default on)
+#[tauri::command]
+async fn set_defer_jobs_on_battery(enabled: bool) -> Result<power::PowerState, String> {
+    db::run(move || {
+        db::set_setting(power::DEFER_ON_BATTERY_KEY, if enabled { "true" } else { "false" })
+            .map_err(|e| e.to_string())?;
+        Ok(power::get_state())
+    }).await
+}
+
+// ============ Background Work ============
+
+/// What the job loop intends to do and its estimated cost; shown at startup before anything paid runs
+#[tauri::command]
+async fn get_pending_background_work() -> Result<jobs::PendingBackgroundWork, String> {
+    db::run(jobs::pending_work).await
+}
+
+#[tauri::command]
+async fn approve_background_work() -> Result<jobs::PendingBackgroundWork, String> {
+    db::run(jobs::approve).await
+}
+
+/// Hold every paid background job for a day
+#[tauri::command]
+async fn defer_background_work() -> Result<jobs::PendingBackgroundWork, String> {
+    db::run(jobs::defer_all).await
+}
+
+// ============ Proactive Check-ins ============
+
+#[tauri::command]
+async fn schedule_check_in(
+    agent: String,
+    content: String,
+    topic_key: String,
+    scheduled_for: String,
+    conversation_id: Option<String>,
+    user_requested: Option<bool>,
+) -> Result<db::ProactiveMessage, String> {
+    db::run(move || {
+        if Agent::from_str(&agent).is_none() {
+            return Err(format!("Invalid agent: {}", agent));
+        }
+        proactive::schedule(
+            &agent,
+            &content,
+            &topic_key,
+            &scheduled_for,
+            conversation_id.as_deref(),
+            user_requested.unwrap_or(false),
+        )
+    }).await
+}
+
+/// Delivered check-ins the user hasn't answered yet (for badges/notifications)
+#[tauri::command]
+async fn get_unanswered_check_ins() -> Result<Vec<db::ProactiveMessage>, String> {
+    db::run(move || db::get_unanswered_proactive_messages().map_err(|e| e.to_string())).await
+}
+
+/// Read receipt: the user has seen a check-in
+#[tauri::command]
+async fn mark_check_in_seen(id: String) -> Result<(), String> {
+    db::run(move || db::mark_proactive_seen(&id).map_err(|e| e.to_string())).await
+}
+
+/// Close a check-in without answering it, so later nudges on its topic get through
+#[tauri::command]
+async fn dismiss_check_in(id: String) -> Result<(), String> {
+    db::run(move || {
+        if !db::mark_proactive_dismissed(&id).map_err(|e| e.to_string())? {
+            return Err("No outstanding check-in with that id".to_string());
+        }
+        Ok(())
+    }).await
+}
+
+// ============ Journal ============
+
+/// Create a journal entry. Dreams get motif extraction and, if `reflect` is set,
+/// an Inland Empire reflection (returned on the entry).
+#[tauri::command]
+async fn create_journal_entry(
+    content: String,
+    entry_type: Option<String>,
+    reflect: Option<bool>,
+) -> Result<db::JournalEntry, String> {
+    let entry_type = entry_type.unwrap_or_else(|| journal::ENTRY_TYPE_NOTE.to_string());
+    if entry_type != journal::ENTRY_TYPE_NOTE && entry_type != journal::ENTRY_TYPE_DREAM {
+        return Err(format!("Unknown journal entry type: {}", entry_type));
+    }
+    
+    let mut entry = db::run(move || {
+        db::create_journal_entry(&Uuid::new_v4().to_string(), &entry_type, &content)
+            .map_err(|e| e.to_string())
+    }).await?;
+    
+    if entry.entry_type != journal::ENTRY_TYPE_DREAM {
+        return Ok(entry);
+    }
+    
+    let profile = db::run(|| db::get_user_profile().map_err(|e| e.to_string())).await?;
+    let Some(anthropic_key) = profile.anthropic_key else {
+        return Ok(entry);
+    };
+    
+    let analyzer = journal::DreamAnalyzer::new(&anthropic_key);
+    if reflect.unwrap_or(false) {
+        // Extract first so the reflection can see motifs that just recurred
+        if let Err(e) = analyzer.extract_motifs(&entry).await {
+            logging::log_error(None, &format!("Dream extraction failed: {}", e));
+        }
+        match analyzer.reflect(&entry).await {
+            Ok(reflection) => entry.reflection = Some(reflection),
+            Err(e) => logging::log_error(None, &format!("Dream reflection failed: {}", e)),
+        }
+    } else {
+        let entry_for_extraction = entry.clone();
+        tokio::spawn(async move {
+            if let Err(e) = analyzer.extract_motifs(&entry_for_extraction).await {
+                logging::log_error(None, &format!("Dream extraction failed: {}", e));
+            }
+        });
+    }
+    
+    Ok(entry)
+}
+
+#[tauri::command]
+async fn get_journal_entries(entry_type: Option<String>, limit: Option<usize>) -> Result<Vec<db::JournalEntry>, String> {
+    db::run(move || db::get_journal_entries(entry_type.as_deref(), limit.unwrap_or(50)).map(anonymize::render).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn get_dream_motifs(limit: Option<usize>) -> Result<Vec<db::DreamMotif>, String> {
+    db::run(move || db::get_dream_motifs(limit.unwrap_or(20)).map_err(|e| e.to_string())).await
+}
+
+// ============ Search ============
+
+/// Find messages by keyword; results carry a snippet and the conversation to jump to
+#[tauri::command]
+async fn search_messages(query: String, limit: Option<usize>) -> Result<Vec<db::MessageSearchResult>, String> {
+    db::run(move || {
+        db::search_messages(&query, limit.unwrap_or(50).min(200)).map(anonymize::render).map_err(|e| e.to_string())
+    }).await
+}
+
+// ============ Bookmarks ============
+
+/// Flag a message to find again, with an optional note. Bookmarking it again replaces the note.
+#[tauri::command]
+async fn bookmark_message(message_id: String, note: Option<String>) -> Result<(), String> {
+    db::run(move || {
+        let note = note.as_deref().map(str::trim).filter(|n| !n.is_empty());
+        if !db::set_message_bookmark(&message_id, note).map_err(|e| e.to_string())? {
+            return Err("Message not found".to_string());
+        }
+        Ok(())
+    }).await
+}
+
+#[tauri::command]
+async fn remove_bookmark(message_id: String) -> Result<bool, String> {
+    db::run(move || db::clear_message_bookmark(&message_id).map_err(|e| e.to_string())).await
+}
+
+/// Bookmarks, newest first, optionally narrowed to one conversation, one speaker, or some text
+#[tauri::command]
+async fn get_bookmarks(
+    conversation_id: Option<String>,
+    role: Option<String>,
+    query: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<db::Bookmark>, String> {
+    db::run(move || {
+        let query = query.as_deref().map(str::trim).filter(|q| !q.is_empty());
+        db::get_bookmarks(conversation_id.as_deref(), role.as_deref(), query, limit.unwrap_or(100).min(500))
+            .map(anonymize::render)
+            .map_err(|e| e.to_string())
+    }).await
+}
+
+#[tauri::command]
+async fn get_bookmarks_in_context() -> bool {
+    db::run_infallible(memory::bookmarks_in_context).await
+}
+
+/// Whether the agents see the newest bookmarks as "user-flagged important" (default off)
+#[tauri::command]
+async fn set_bookmarks_in_context(enabled: bool) -> Result<(), String> {
+    db::run(move || {
+        db::set_setting(memory::BOOKMARKS_IN_CONTEXT_KEY, if enabled { "true" } else { "false" })
+            .map_err(|e| e.to_string())
+    }).await
+}
+
+// ============ Session Context ============
+
+/// What the agents will know this session, for the context card shown before typing
+#[tauri::command]
+async fn get_session_context(conversation_id: Option<String>) -> memory::SessionContext {
+    db::run_infallible(move || memory::session_context(conversation_id.as_deref())).await
+}
+
+/// Whether past conversations found for a new one's first message also go to the agents (default off)
+#[tauri::command]
+async fn get_related_in_context() -> bool {
+    db::run_infallible(memory::related_in_context).await
+}
+
+#[tauri::command]
+async fn set_related_in_context(enabled: bool) -> Result<(), String> {
+    db::run(move || {
+        db::set_setting(memory::RELATED_IN_CONTEXT_KEY, if enabled { "true" } else { "false" })
+            .map_err(|e| e.to_string())
+    }).await
+}
+
+/// Whether a reply that contradicts a remembered fact also gets a one-line correction in the conversation (default off)
+#[tauri::command]
+async fn get_contradiction_auto_correct() -> bool {
+    db::run_infallible(watchdog::auto_correct_enabled).await
+}
+
+#[tauri::command]
+async fn set_contradiction_auto_correct(enabled: bool) -> Result<(), String> {
+    db::run(move || {
+        db::set_setting(watchdog::AUTO_CORRECT_KEY, if enabled { "true" } else { "false" })
+            .map_err(|e| e.to_string())
+    }).await
+}
+
+// ============ Pattern Verdicts ============
+
+#[tauri::command]
+async fn get_user_patterns() -> Result<Vec<db::UserPattern>, String> {
+    db::run(move || db::get_all_user_patterns().map(anonymize::render).map_err(|e| e.to_string())).await
+}
+
+/// Confirm, deny, or mark unsure an inferred pattern. Denied patterns stay out of
+/// prompts and stop gaining confidence; confirmed ones keep a confidence floor.
+#[tauri::command]
+async fn set_pattern_verdict(id: i64, verdict: String) -> Result<(), String> {
+    db::run(move || {
+        if ![db::PATTERN_CONFIRMED, db::PATTERN_DENIED, db::PATTERN_UNSURE].contains(&verdict.as_str()) {
+            return Err(format!("Unknown pattern verdict: {}", verdict));
+        }
+        if !db::set_pattern_verdict(id, &verdict).map_err(|e| e.to_string())? {
+            return Err("Pattern not found".to_string());
+        }
+        logging::log_memory(None, &format!("[PATTERN] {} pattern {}", verdict, id));
+        Ok(())
+    }).await
+}
+
+/// Reword a pattern or change its confidence (0-1)
+#[tauri::command]
+async fn update_user_pattern(id: i64, description: String, confidence: f64) -> Result<(), String> {
+    db::run(move || {
+        let description = description.trim();
+        if description.is_empty() {
+            return Err("A pattern needs a description -- delete it instead".to_string());
+        }
+        if !(0.0..=1.0).contains(&confidence) {
+            return Err("Confidence must be between 0 and 1".to_string());
+        }
+        if !db::update_user_pattern(id, description, confidence).map_err(|e| e.to_string())? {
+            return Err("Pattern not found".to_string());
+        }
+        logging::log_memory(None, &format!("[PATTERN] edited pattern {}", id));
+        Ok(())
+    }).await
+}
+
+/// Remove a pattern outright. It can be inferred again later; deny it to keep it out for good.
+#[tauri::command]
+async fn delete_user_pattern(id: i64) -> Result<(), String> {
+    db::run(move || {
+        if !db::delete_user_pattern(id).map_err(|e| e.to_string())? {
+            return Err("Pattern not found".to_string());
+        }
+        logging::log_memory(None, &format!("[PATTERN] deleted pattern {}", id));
+        Ok(())
+    }).await
+}
+
+// ============ Memory Staging ============
+
+#[tauri::command]
+async fn get_staged_memory() -> Result<Vec<db::StagedMemory>, String> {
+    db::run(move || db::get_staged_memory().map_err(|e| e.to_string())).await
+}
+
+/// Trust a staged item now instead of waiting
+#[tauri::command]
+async fn promote_staged_memory_item(id: i64) -> Result<(), String> {
+    db::run(move || {
+        let item = db::get_staged_memory_item(id).map_err(|e| e.to_string())?
+            .ok_or("Staged item not found")?;
+        memory::promote_staged_item(&item)
+    }).await
+}
+
+#[tauri::command]
+async fn discard_staged_memory_item(id: i64) -> Result<(), String> {
+    db::run(move || db::delete_staged_memory(id).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn get_memory_staging_policy() -> memory::StagingPolicy {
+    db::run_infallible(memory::staging_policy).await
+}
+
+/// Staged items are promoted after `days` or `confirmations` distinct conversations
+#[tauri::command]
+async fn set_memory_staging_policy(days: Option<i64>, confirmations: Option<i64>) -> Result<memory::StagingPolicy, String> {
+    db::run(move || {
+        if let Some(days) = days.filter(|d| *d > 0) {
+            db::set_setting(memory::STAGING_DAYS_KEY, &days.to_string()).map_err(|e| e.to_string())?;
+        }
+        if let Some(confirmations) = confirmations.filter(|c| *c > 0) {
+            db::set_setting(memory::STAGING_CONFIRMATIONS_KEY, &confirmations.to_string()).map_err(|e| e.to_string())?;
+        }
+        Ok(memory::staging_policy())
+    }).await
+}
+
+// ============ Weekly Digest ============
+
+#[tauri::command]
+async fn get_digest_settings() -> digest::DigestSettings {
+    db::run_infallible(digest::get_settings).await
+}
+
+/// Turn the weekly email digest on/off and set where it goes ("" clears the address).
+/// SMTP itself is configured with the share settings.
+#[tauri::command]
+async fn set_digest_settings(enabled: Option<bool>, email_to: Option<String>) -> Result<digest::DigestSettings, String> {
+    db::run(move || {
+        if let Some(enabled) = enabled {
+            db::set_setting(digest::DIGEST_ENABLED_KEY, if enabled { "true" } else { "false" }).map_err(|e| e.to_string())?;
+        }
+        if let Some(email_to) = email_to {
+            db::set_setting(digest::DIGEST_EMAIL_TO_KEY, email_to.trim()).map_err(|e| e.to_string())?;
+        }
+        Ok(digest::get_settings())
+    }).await
+}
+
+#[tauri::command]
+async fn get_weekly_reflections(limit: Option<usize>) -> Result<Vec<db::WeeklyReflection>, String> {
+    db::run(move || db::get_weekly_reflections(limit.unwrap_or(12)).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn get_email_log() -> Result<Vec<db::EmailLogEntry>, String> {
+    db::run(move || db::get_email_log().map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn get_monthly_highlights(limit: Option<usize>) -> Result<Vec<db::MonthlyHighlights>, String> {
+    db::run(move || db::get_all_monthly_highlights(limit.unwrap_or(12)).map_err(|e| e.to_string())).await
+}
+
+/// (Re)write the highlight reel for a month ("YYYY-MM") now; null when the month
+/// has no cited or replied-to messages
+#[tauri::command]
+async fn generate_monthly_highlights(month: String) -> Result<Option<db::MonthlyHighlights>, String> {
+    digest::generate_highlights(digest::parse_month(&month)?).await
+}
+
+/// Render a month's highlights as Markdown, writing them to `path` when given
+#[tauri::command]
+async fn export_highlights_markdown(month: String, path: Option<String>) -> Result<String, String> {
+    db::run(move || {
+        export::export_highlights_markdown(&month, path.as_deref())
+    }).await
+}
+
+// ============ Notes Folder ============
+
+#[tauri::command]
+async fn get_notes_folder_status() -> notes::NotesStatus {
+    db::run_infallible(notes::status).await
+}
+
+/// Choose the notes folder to ingest from; null stops watching
+#[tauri::command]
+async fn set_notes_folder(path: Option<String>) -> Result<notes::NotesStatus, String> {
+    db::run(move || notes::set_notes_folder(path)).await
+}
+
+/// Sync now instead of waiting for the next job tick
+#[tauri::command]
+async fn sync_notes_folder() -> Result<notes::NotesSyncReport, String> {
+    db::run(move || {
+        notes::sync()
+    }).await
+}
+
+#[tauri::command]
+async fn get_documents(source: Option<String>) -> Result<Vec<db::Document>, String> {
+    db::run(move || db::get_documents(source.as_deref()).map_err(|e| e.to_string())).await
+}
+
+/// Turn a conversation into a decision memo, action list or essay outline, saved
+/// as a document ('decision_memo' | 'action_list' | 'essay_outline')
+#[tauri::command]
+async fn distill_conversation(conversation_id: String, format: String) -> Result<db::Document, String> {
+    distill::distill_conversation(&conversation_id, &format).await
+}
+
+/// Render a stored document as Markdown, writing it to `path` when given
+#[tauri::command]
+async fn export_document(document_id: String, path: Option<String>) -> Result<String, String> {
+    db::run(move || {
+        export::export_document_markdown(&document_id, path.as_deref())
+    }).await
+}
+
+// ============ Archive Import ============
+
+/// Import a JSON chat archive; the returned record carries the analysis cost estimate
+#[tauri::command]
+async fn import_chat_archive(path: String) -> Result<db::ArchiveImport, String> {
+    db::run(move || {
+        archive::import_archive(&path)
+    }).await
+}
+
+#[tauri::command]
+async fn get_archive_imports() -> Result<Vec<db::ArchiveImport>, String> {
+    db::run(move || db::get_archive_imports().map_err(|e| e.to_string())).await
+}
+
+/// Start or resume analysis in the background; progress arrives as "archive-import-*" events
+#[tauri::command]
+async fn start_archive_analysis(app_handle: tauri::AppHandle, import_id: String) -> Result<db::ArchiveImport, String> {
+    db::run(move || archive::start_analysis(app_handle, &import_id)).await
+}
+
+#[tauri::command]
+async fn pause_archive_analysis(import_id: String) -> Result<(), String> {
+    db::run(move || archive::pause_analysis(&import_id)).await
+}
+
+#[tauri::command]
+async fn get_archive_import_report(import_id: String) -> Result<archive::ImportReport, String> {
+    db::run(move || {
+        archive::report(&import_id)
+    }).await
+}
+
+#[tauri::command]
+async fn release_archive_import(import_id: String) -> Result<usize, String> {
+    db::run(move || archive::release(&import_id)).await
+}
+
+#[tauri::command]
+async fn discard_archive_import(import_id: String) -> Result<usize, String> {
+    db::run(move || archive::discard(&import_id)).await
+}
+
+// ============ Memory Corrections ============
+
+#[tauri::command]
+async fn get_fact_corrections(limit: Option<usize>) -> Result<Vec<db::FactCorrection>, String> {
+    db::run(move || db::get_fact_corrections(limit.unwrap_or(50)).map_err(|e| e.to_string())).await
+}
+
+/// Correct a remembered fact by hand; confidence is 0-1
+#[tauri::command]
+async fn update_user_fact(fact_id: i64, value: String, confidence: f64) -> Result<db::UserFact, String> {
+    db::run(move || corrections::update_fact(fact_id, &value, confidence)).await
+}
+
+#[tauri::command]
+async fn delete_user_fact(fact_id: i64) -> Result<(), String> {
+    db::run(move || corrections::delete_fact(fact_id)).await
+}
+
+/// Facts where a newer value disagreed with the remembered one; neither was overwritten
+#[tauri::command]
+async fn get_conflicting_facts() -> Result<Vec<db::FactConflict>, String> {
+    db::run(move || db::get_fact_conflicts().map(anonymize::render).map_err(|e| e.to_string())).await
+}
+
+/// Earlier values of one fact (or of every fact), newest first, with the conversation that changed them
+#[tauri::command]
+async fn get_fact_history(fact_id: Option<i64>, limit: Option<usize>) -> Result<Vec<db::FactHistoryEntry>, String> {
+    db::run(move || db::get_fact_history(fact_id, limit.unwrap_or(50)).map(anonymize::render).map_err(|e| e.to_string())).await
+}
+
+/// Undo a bad update: put the fact back to the value in this history entry
+#[tauri::command]
+async fn rollback_user_fact(history_id: i64) -> Result<db::UserFact, String> {
+    db::run(move || corrections::rollback_fact(history_id)).await
+}
+
+/// Keep the remembered value (keep_incoming = false) or replace it with the incoming one
+#[tauri::command]
+async fn resolve_fact_conflict(conflict_id: i64, keep_incoming: bool) -> Result<db::UserFact, String> {
+    db::run(move || corrections::resolve_conflict(conflict_id, keep_incoming)).await
+}
+
+// ============ Action Items ============
+
+/// Action items, newest first; filter by status ('open' | 'done') and/or conversation
+#[tauri::command]
+async fn get_action_items(status: Option<String>, conversation_id: Option<String>) -> Result<Vec<db::ActionItem>, String> {
+    db::run(move || db::get_action_items(status.as_deref(), conversation_id.as_deref()).map_err(|e| e.to_string())).await
+}
+
+fn action_item(id: i64) -> Result<db::ActionItem, String> {
+    db::get_action_item(id).map_err(|e| e.to_string())?.ok_or_else(|| "Action item not found".to_string())
+}
+
+/// Mark an action item done, or open again with `done: false`
+#[tauri::command]
+async fn complete_action_item(id: i64, done: Option<bool>) -> Result<db::ActionItem, String> {
+    db::run(move || {
+        db::set_action_item_done(id, done.unwrap_or(true)).map_err(|e| e.to_string())?;
+        action_item(id)
+    }).await
+}
+
+/// Keep an item out of session context for `days` (0 wakes it now)
+#[tauri::command]
+async fn snooze_action_item(id: i64, days: i64) -> Result<db::ActionItem, String> {
+    db::run(move || {
+        let until = (days > 0).then(|| (Utc::now() + chrono::Duration::days(days)).to_rfc3339());
+        db::snooze_action_item(id, until.as_deref()).map_err(|e| e.to_string())?;
+        action_item(id)
+    }).await
+}
+
+// ============ Memory Citations ============
+
+/// Facts the agents relied on, for one message or a whole conversation
+#[tauri::command]
+async fn get_response_citations(
+    message_id: Option<String>,
+    conversation_id: Option<String>,
+) -> Result<Vec<db::ResponseCitation>, String> {
+    db::run(move || {
+        if message_id.is_none() && conversation_id.is_none() {
+            return Err("Pass a message_id or a conversation_id".to_string());
+        }
+        db::get_response_citations(message_id.as_deref(), conversation_id.as_deref()).map_err(|e| e.to_string())
+    }).await
+}
+
+// ============ Chapters ============
+
+#[tauri::command]
+async fn get_chapters() -> Result<Vec<db::Chapter>, String> {
+    db::run(move || {
+        let chapters = db::get_chapters().map_err(|e| e.to_string())?;
+        Ok(anonymize::render(chapters))
+    }).await
+}
+
+/// Dates are YYYY-MM-DD; no end date means the chapter is still going
+#[tauri::command]
+async fn create_chapter(
+    title: String,
+    start_date: String,
+    end_date: Option<String>,
+    description: Option<String>,
+) -> Result<db::Chapter, String> {
+    db::run(move || chapters::create(&title, &start_date, end_date.as_deref(), description.as_deref()).map(anonymize::render)).await
+}
+
+#[tauri::command]
+async fn update_chapter(
+    id: i64,
+    title: String,
+    start_date: String,
+    end_date: Option<String>,
+    description: Option<String>,
+) -> Result<db::Chapter, String> {
+    db::run(move || chapters::update(id, &title, &start_date, end_date.as_deref(), description.as_deref()).map(anonymize::render)).await
+}
+
+/// Detected chapters stay dismissed rather than coming back
+#[tauri::command]
+async fn delete_chapter(id: i64) -> Result<(), String> {
+    db::run(move || {
+        if !db::delete_chapter(id).map_err(|e| e.to_string())? {
+            return Err("Chapter not found".to_string());
+        }
+        Ok(())
+    }).await
+}
+
+/// Place a conversation in a chapter (or take it out) regardless of dates
+#[tauri::command]
+async fn set_chapter_conversation(chapter_id: i64, conversation_id: String, linked: bool) -> Result<db::Chapter, String> {
+    db::run(move || {
+        chapters::get(chapter_id)?;
+        db::set_chapter_conversation(chapter_id, &conversation_id, linked).map_err(|e| e.to_string())?;
+        chapters::get(chapter_id).map(anonymize::render)
+    }).await
+}
+
+/// Run chapter detection now instead of waiting for the background job; returns how many were added
+#[tauri::command]
+async fn detect_chapters() -> Result<usize, String> {
+    db::run(chapters::detect_auto).await
+}
+
+// ============ Disagreements ============
+
+/// Where the agents split, in one conversation or across all of them (newest first)
+#[tauri::command]
+async fn get_disagreements(conversation_id: Option<String>, limit: Option<usize>) -> Result<Vec<db::Disagreement>, String> {
+    db::run(move || {
+        let disagreements = db::get_disagreements(conversation_id.as_deref(), limit.unwrap_or(50)).map_err(|e| e.to_string())?;
+        Ok(anonymize::render(disagreements))
+    }).await
+}
+
+// ============ Fact Inclusion Policies ============
+
+/// Days without confirmation before facts are archived (None = facts never decay)
+#[tauri::command]
+async fn get_fact_decay_days() -> Option<i64> {
+    db::run_infallible(memory::fact_decay_days).await
+}
+
+/// 0 turns decay off
+#[tauri::command]
+async fn set_fact_decay_days(days: i64) -> Result<(), String> {
+    db::run(move || memory::set_fact_decay_days(days)).await
+}
+
+/// Recurring themes, most frequent first
+#[tauri::command]
+async fn get_recurring_themes(limit: Option<usize>) -> Result<Vec<db::RecurringTheme>, String> {
+    db::run(move || db::get_top_themes(limit.unwrap_or(50)).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn delete_theme(id: i64) -> Result<(), String> {
+    db::run(move || {
+        db::delete_recurring_theme(id).map_err(|e| e.to_string())?
+            .then_some(())
+            .ok_or_else(|| "Theme not found".to_string())
+    }).await
+}
+
+/// Weeks without a mention before a theme's frequency starts halving (None = themes never decay)
+#[tauri::command]
+async fn get_theme_decay_weeks() -> Option<i64> {
+    db::run_infallible(memory::theme_decay_weeks).await
+}
+
+/// 0 turns decay off
+#[tauri::command]
+async fn set_theme_decay_weeks(weeks: i64) -> Result<(), String> {
+    db::run(move || memory::set_theme_decay_weeks(weeks)).await
+}
+
+#[tauri::command]
+async fn get_archived_facts() -> Result<Vec<db::ArchivedFact>, String> {
+    db::run(move || db::get_archived_facts().map(anonymize::render).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn get_fact_policies() -> Vec<memory::FactPolicySetting> {
+    db::run_infallible(memory::get_fact_policies).await
+}
+
+/// policy: "always" | "relevant" | "explicit" (only when the user brings it up)
+#[tauri::command]
+async fn set_fact_policy(category: String, policy: String) -> Result<Vec<memory::FactPolicySetting>, String> {
+    db::run(move || {
+        memory::set_fact_policy(&category, &policy)?;
+        Ok(memory::get_fact_policies())
+    }).await
+}
+
+// ============ Memory Collections ============
+
+#[tauri::command]
+async fn get_memory_collections() -> Result<Vec<db::MemoryCollection>, String> {
+    db::run(move || db::get_memory_collections().map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn create_memory_collection(name: String, description: Option<String>) -> Result<i64, String> {
+    db::run(move || {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() {
+            return Err("Collection name cannot be empty".to_string());
+        }
+        db::create_memory_collection(&name, description.as_deref()).map_err(|e| e.to_string())
+    }).await
+}
+
+#[tauri::command]
+async fn delete_memory_collection(id: i64) -> Result<(), String> {
+    db::run(move || db::delete_memory_collection(id).map_err(|e| e.to_string())).await
+}
+
+/// File an item ("fact" keyed by fact id, or "document" keyed by document id) under a collection
+#[tauri::command]
+async fn assign_to_collection(collection_id: i64, item_type: String, item_id: String) -> Result<(), String> {
+    db::run(move || {
+        if !db::COLLECTION_ITEM_TYPES.contains(&item_type.as_str()) {
+            return Err(format!("Unknown collection item type: {}", item_type));
+        }
+        db::assign_to_collection(collection_id, &item_type, &item_id).map_err(|e| e.to_string())
+    }).await
+}
+
+#[tauri::command]
+async fn remove_from_collection(collection_id: i64, item_type: String, item_id: String) -> Result<(), String> {
+    db::run(move || db::remove_from_collection(collection_id, &item_type, &item_id).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn get_item_collections(item_type: String, item_id: String) -> Result<Vec<i64>, String> {
+    db::run(move || db::get_item_collections(&item_type, &item_id).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn get_conversation_collections(conversation_id: String) -> Result<Vec<i64>, String> {
+    db::run(move || db::get_conversation_collections(&conversation_id).map_err(|e| e.to_string())).await
+}
+
+/// Limit what agents can recall in this conversation to these collections (empty = everything)
+#[tauri::command]
+async fn set_conversation_collections(conversation_id: String, collection_ids: Vec<i64>) -> Result<(), String> {
+    db::run(move || db::set_conversation_collections(&conversation_id, &collection_ids).map_err(|e| e.to_string())).await
+}
+
+// ============ Conversation Tags ============
+
+#[tauri::command]
+async fn get_tags() -> Result<Vec<db::Tag>, String> {
+    db::run(move || db::get_tags().map_err(|e| e.to_string())).await
+}
+
+/// Create a tag (names are trimmed and lowercased); returns the existing id if it's already there
+#[tauri::command]
+async fn add_tag(name: String) -> Result<i64, String> {
+    db::run(move || {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() {
+            return Err("Tag name cannot be empty".to_string());
+        }
+        db::add_tag(&name).map_err(|e| e.to_string())
+    }).await
+}
+
+#[tauri::command]
+async fn delete_tag(id: i64) -> Result<(), String> {
+    db::run(move || db::delete_tag(id).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn tag_conversation(conversation_id: String, tag_id: i64) -> Result<(), String> {
+    db::run(move || db::tag_conversation(&conversation_id, tag_id).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn untag_conversation(conversation_id: String, tag_id: i64) -> Result<(), String> {
+    db::run(move || db::untag_conversation(&conversation_id, tag_id).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn get_conversation_tags(conversation_id: String) -> Result<Vec<db::Tag>, String> {
+    db::run(move || db::get_conversation_tags(&conversation_id).map_err(|e| e.to_string())).await
+}
+
+/// Sidebar filter: conversations with this tag, pinned first
+#[tauri::command]
+async fn get_conversations_by_tag(tag_id: i64) -> Result<Vec<ConversationInfo>, String> {
+    db::run(move || {
+        let convs = db::get_conversations_by_tag(tag_id).map_err(|e| e.to_string())?;
+        Ok(anonymize::render(convs.into_iter().map(|c| ConversationInfo {
+            id: c.id,
+            title: c.title,
+            summary: c.summary,
+            is_disco: c.is_disco,
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+            closed_at: c.closed_at,
+            pinned: c.pinned,
+            project_id: c.project_id,
+            locked: c.locked,
+            parent_conversation_id: c.parent_conversation_id,
+        }).collect()))
+    }).await
+}
+
+// ============ Projects ============
+
+fn clean_project_fields(name: &str, description: Option<String>) -> Result<(String, Option<String>), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Project name cannot be empty".to_string());
+    }
+    let description = description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    Ok((name, description))
+}
+
+#[tauri::command]
+async fn create_project(name: String, description: Option<String>) -> Result<db::Project, String> {
+    db::run(move || {
+        let (name, description) = clean_project_fields(&name, description)?;
+        db::create_project(&name, description.as_deref()).map_err(|e| e.to_string())
+    }).await
+}
+
+#[tauri::command]
+async fn get_projects() -> Result<Vec<db::Project>, String> {
+    db::run(move || db::get_projects().map_err(|e| e.to_string())).await
+}
+
+/// Rename a project; the description is replaced too ("" or null clears it)
+#[tauri::command]
+async fn update_project(id: i64, name: String, description: Option<String>) -> Result<db::Project, String> {
+    db::run(move || {
+        let (name, description) = clean_project_fields(&name, description)?;
+        if !db::update_project(id, &name, description.as_deref()).map_err(|e| e.to_string())? {
+            return Err("Project not found".to_string());
+        }
+        db::get_project(id).map_err(|e| e.to_string())?.ok_or_else(|| "Project not found".to_string())
+    }).await
+}
+
+/// Delete a project; its conversations are kept and become unfiled
+#[tauri::command]
+async fn delete_project(id: i64) -> Result<(), String> {
+    db::run(move || db::delete_project(id).map_err(|e| e.to_string())).await
+}
+
+/// Move a conversation into a project, or out of one with null
+#[tauri::command]
+async fn set_conversation_project(conversation_id: String, project_id: Option<i64>) -> Result<(), String> {
+    db::run(move || {
+        if let Some(project_id) = project_id {
+            db::get_project(project_id).map_err(|e| e.to_string())?.ok_or("Project not found")?;
+        }
+        db::set_conversation_project(&conversation_id, project_id).map_err(|e| e.to_string())
+    }).await
+}
+
+/// A project's conversations, pinned first
+#[tauri::command]
+async fn get_conversations_by_project(project_id: i64) -> Result<Vec<ConversationInfo>, String> {
+    db::run(move || {
+        let convs = db::get_conversations_by_project(project_id).map_err(|e| e.to_string())?;
+        Ok(anonymize::render(convs.into_iter().map(|c| ConversationInfo {
+            id: c.id,
+            title: c.title,
+            summary: c.summary,
+            is_disco: c.is_disco,
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+            closed_at: c.closed_at,
+            pinned: c.pinned,
+            project_id: c.project_id,
+            locked: c.locked,
+            parent_conversation_id: c.parent_conversation_id,
+        }).collect()))
+    }).await
+}
+
+// ============ Conversation Export ============
+
+/// The conversation as Markdown; also written to `path` (picked with the save dialog) when given
+#[tauri::command]
+async fn export_conversation_markdown(conversation_id: String, path: Option<String>) -> Result<String, String> {
+    db::run(move || {
+        export::export_markdown(&conversation_id, path.as_deref())
+    }).await
+}
+
+/// Lossless JSON of the conversation row and all its messages; also written to `path` when given
+#[tauri::command]
+async fn export_conversation_json(conversation_id: String, path: Option<String>) -> Result<String, String> {
+    db::run(move || {
+        export::export_json(&conversation_id, path.as_deref())
+    }).await
+}
+
+/// The conversation as an archival PDF at `path`, optionally with an appendix of cited memory facts
+#[tauri::command]
+async fn export_conversation_pdf(conversation_id: String, path: String, include_citations: Option<bool>) -> Result<String, String> {
+    db::run(move || {
+        export::export_pdf(&conversation_id, &path, include_citations.unwrap_or(false))
+    }).await
+}
+
+/// All of memory -- facts, patterns, summaries, themes and user context -- as one JSON
+/// document; also written to `path` when given
+#[tauri::command]
+async fn export_memory(path: Option<String>) -> Result<String, String> {
+    db::run(move || {
+        export::export_memory(path.as_deref())
+    }).await
+}
+
+/// Bring in a memory export: "replace", "merge-keep-higher-confidence" or "merge-newest-wins"
+#[tauri::command]
+async fn import_memory(json: String, strategy: String) -> Result<db::MemoryImport, String> {
+    db::run(move || {
+        export::import_memory_json(&json, &strategy)
+    }).await
+}
+
+/// Restore a conversation from a JSON export; colliding ids are replaced
+#[tauri::command]
+async fn import_conversation(json: String) -> Result<db::ImportedConversation, String> {
+    db::run(move || {
+        export::import_json(&json)
+    }).await
+}
+
+// ============ Session Sharing ============
+
+#[tauri::command]
+async fn get_share_settings() -> share::ShareSettings {
+    db::run_infallible(share::get_settings).await
+}
+
+/// Configure share destinations; None leaves a value unchanged, "" clears it
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn set_share_settings(
+    smtp_host: Option<String>,
+    smtp_port: Option<u16>,
+    smtp_username: Option<String>,
+    smtp_password: Option<String>,
+    email_from: Option<String>,
+    email_to: Option<String>,
+    webhook_url: Option<String>,
+) -> Result<share::ShareSettings, String> {
+    db::run(move || {
+        let updates = [
+            (share::SMTP_HOST_KEY, smtp_host),
+            (share::SMTP_PORT_KEY, smtp_port.map(|p| p.to_string())),
+            (share::SMTP_USERNAME_KEY, smtp_username),
+            (share::EMAIL_FROM_KEY, email_from),
+            (share::EMAIL_TO_KEY, email_to),
+            (share::WEBHOOK_URL_KEY, webhook_url),
+        ];
+        for (key, value) in updates {
+            if let Some(value) = value {
+                db::set_setting(key, value.trim()).map_err(|e| e.to_string())?;
+            }
+        }
+        if let Some(password) = smtp_password {
+            share::set_smtp_password(password.trim())?;
+        }
+        Ok(share::get_settings())
+    }).await
+}
+
+/// Step one: render the redacted summary + transcript for review
+#[tauri::command]
+async fn prepare_session_share(conversation_id: String, destination: String) -> Result<share::SharePreview, String> {
+    db::run(move || share::prepare(&conversation_id, &destination)).await
+}
+
+/// Step two: send exactly the previewed content
+#[tauri::command]
+async fn confirm_session_share(token: String) -> Result<db::ShareLogEntry, String> {
+    share::confirm(&token).await
+}
+
+#[tauri::command]
+async fn get_share_log() -> Result<Vec<db::ShareLogEntry>, String> {
+    db::run(move || db::get_share_log().map_err(|e| e.to_string())).await
+}
+
+// ============ Support Bundle ============
+
+/// Zip of scrubbed logs, diagnostics, settings (no secrets) and metrics for a bug report; never message content
+#[tauri::command]
+async fn create_support_bundle(app_handle: tauri::AppHandle, path: String) -> Result<support::SupportBundle, String> {
+    db::run(move || support::create_bundle(&app_handle, &path)).await
+}
+
+// ============ Query Console ============
+
+/// Run one SELECT against a read-only connection; rows are capped (default 500) and slow queries stopped
+#[tauri::command]
+async fn run_readonly_query(app_handle: tauri::AppHandle, sql: String, max_rows: Option<usize>) -> Result<db::QueryResult, String> {
+    db::run(move || console::run(&app_handle, &sql, max_rows)).await
+}
+
+// ============ Anonymized Rendering ============
+
+/// Whether names and contact details are swapped for pseudonyms in what commands return
+#[tauri::command]
+async fn get_anonymized_rendering() -> bool {
+    db::run_infallible(anonymize::enabled).await
+}
+
+/// Flip anonymized rendering for screenshots and demos; returns whether it's now on
+#[tauri::command]
+async fn toggle_anonymized_rendering() -> Result<bool, String> {
+    db::run(anonymize::toggle).await
+}
+
+// ============ Usage ============
+
+/// Export API usage in [start, end) (RFC3339, either optional) as CSV:
+/// per-call rows at `path`, then a per-model/per-agent summary section in the same file
+#[tauri::command]
+async fn export_usage_csv(start: Option<String>, end: Option<String>, path: String) -> Result<usage::UsageExportResult, String> {
+    db::run(move || {
+        usage::export_csv(start.as_deref(), end.as_deref(), &path)
+    }).await
+}
+
+/// Month-end spend projection from the trailing 30 days; with a cap, when it's on pace to be exceeded
+#[tauri::command]
+async fn get_usage_forecast(monthly_cap_usd: Option<f64>) -> Result<usage::UsageForecast, String> {
+    db::run(move || {
+        usage::forecast(monthly_cap_usd.or_else(budgets::monthly_cap_usd))
+    }).await
+}
+
+/// Spend per day in [start, end) (RFC3339, either optional), for the spend dashboard
+#[tauri::command]
+async fn get_spend_by_day(start: Option<String>, end: Option<String>) -> Result<Vec<db::DailySpend>, String> {
+    db::run(move || {
+        db::get_spend_by_day(start.as_deref(), end.as_deref()).map_err(|e| e.to_string())
+    }).await
+}
+
+/// Spend per model in [start, end) (RFC3339, either optional), most expensive first
+#[tauri::command]
+async fn get_spend_by_model(start: Option<String>, end: Option<String>) -> Result<Vec<db::ModelSpend>, String> {
+    db::run(move || {
+        db::get_spend_by_model(start.as_deref(), end.as_deref()).map_err(|e| e.to_string())
+    }).await
+}
+
+// ============ Model Routing ============
+
+#[tauri::command]
+async fn get_model_policies() -> Vec<model_routing::AgentModelPolicy> {
+    db::run_infallible(model_routing::get_policies).await
+}
+
+/// "adaptive" picks the model per message; "economy" / "premium" pin it for the agent
+#[tauri::command]
+async fn set_agent_model_policy(agent: String, policy: String) -> Result<Vec<model_routing::AgentModelPolicy>, String> {
+    db::run(move || model_routing::set_agent_policy(&agent, &policy)).await
+}
+
+/// Force "economy" or "premium" for every agent in a conversation; null goes back to the agent policies
+#[tauri::command]
+async fn set_conversation_model_tier(conversation_id: String, tier: Option<String>) -> Result<(), String> {
+    db::run(move || {
+        if let Some(tier) = &tier {
+            model_routing::validate_tier(tier)?;
+        }
+        db::set_conversation_model_tier(&conversation_id, tier.as_deref()).map_err(|e| e.to_string())
+    }).await
+}
+
+#[tauri::command]
+async fn get_conversation_model_tier(conversation_id: String) -> Result<Option<String>, String> {
+    db::run(move || db::get_conversation_model_tier(&conversation_id).map_err(|e| e.to_string())).await
+}
+
+/// Model tier, mode and agent weights set for one conversation
+#[tauri::command]
+async fn get_conversation_settings(conversation_id: String) -> Result<db::ConversationSettings, String> {
+    db::run(move || db::get_conversation_settings(&conversation_id).map_err(|e| e.to_string())).await
+}
+
+/// Replace a conversation's settings; leave everything null to go back to the defaults.
+/// Weights are all three or none, and are scaled to sum to 1.
+#[tauri::command]
+async fn set_conversation_settings(
+    conversation_id: String,
+    model_tier: Option<String>,
+    mode: Option<String>,
+    instinct_weight: Option<f64>,
+    logic_weight: Option<f64>,
+    psyche_weight: Option<f64>,
+) -> Result<db::ConversationSettings, String> {
+    db::run(move || {
+        if db::get_conversation(&conversation_id).map_err(|e| e.to_string())?.is_none() {
+            return Err("Conversation not found".to_string());
+        }
+        conversation_settings::save(db::ConversationSettings {
+            conversation_id,
+            model_tier,
+            mode,
+            instinct_weight,
+            logic_weight,
+            psyche_weight,
+            updated_at: None,
+        })
+    }).await
+}
+
+/// Mode, mutes, verbosity and incognito set by slash commands in a conversation
+#[tauri::command]
+async fn get_conversation_controls(conversation_id: String) -> Result<db::ConversationControls, String> {
+    db::run(move || db::get_conversation_controls(&conversation_id).map_err(|e| e.to_string())).await
+}
+
+/// Audit trail of model choices, newest first (all conversations when none is given)
+#[tauri::command]
+async fn get_model_decisions(conversation_id: Option<String>, limit: Option<usize>) -> Result<Vec<db::ModelDecision>, String> {
+    db::run(move || db::get_model_decisions(conversation_id.as_deref(), limit.unwrap_or(100)).map_err(|e| e.to_string())).await
+}
+
+// ============ Latency SLO ============
+
+#[tauri::command]
+async fn get_latency_status() -> latency::LatencyStatus {
+    db::run_infallible(latency::status).await
+}
+
+/// p90 time-to-first-token target in ms before agents switch provider; 0 turns switching off
+#[tauri::command]
+async fn set_latency_slo(threshold_ms: i64) -> Result<latency::LatencyStatus, String> {
+    db::run(move || latency::set_threshold_ms(threshold_ms)).await
+}
+
+// ============ Insights ============
+
+#[tauri::command]
+async fn get_insights(include_dismissed: Option<bool>, limit: Option<usize>) -> Result<Vec<db::Insight>, String> {
+    db::run(move || db::get_insights(include_dismissed.unwrap_or(false), limit.unwrap_or(20)).map_err(|e| e.to_string())).await
+}
+
+#[tauri::command]
+async fn dismiss_insight(id: i64) -> Result<(), String> {
+    db::run(move || db::dismiss_insight(id).map_err(|e| e.to_string())).await
+}
+
+// ============ Agent Budgets ============
+
+#[tauri::command]
+async fn get_agent_budgets() -> budgets::BudgetSettings {
+    db::run_infallible(budgets::get_settings).await
+}
+
+/// Daily hosted-model token allowance for one agent; null or 0 removes the limit
+#[tauri::command]
+async fn set_agent_budget(agent: String, daily_tokens: Option<i64>) -> Result<budgets::BudgetSettings, String> {
+    db::run(move || budgets::set_agent_budget(&agent, daily_tokens)).await
+}
+
+/// What an agent does once its allowance is spent: "silent" or "local" (needs a model URL and name)
+#[tauri::command]
+async fn set_budget_fallback(mode: String, local_model_url: Option<String>, local_model_name: Option<String>) -> Result<budgets::BudgetSettings, String> {
+    db::run(move || budgets::set_fallback(&mode, local_model_url, local_model_name)).await
+}
+
+/// This month's spend against the monthly cap
+#[tauri::command]
+async fn get_budget_status() -> budgets::BudgetStatus {
+    db::run_infallible(budgets::status).await
+}
+
+/// Cap monthly hosted spend in dollars and/or tokens; `mode` is "block" (refuse calls) or "warn"
+#[tauri::command]
+async fn set_monthly_budget(cap_usd: Option<f64>, cap_tokens: Option<i64>, mode: String) -> Result<budgets::BudgetStatus, String> {
+    db::run(move || budgets::set_monthly_cap(cap_usd, cap_tokens, &mode)).await
+}
+
+// ============ Reset ============
+
+#[tauri::command]
+async fn reset_all_data() -> Result<(), String> {
+    db::run(move || {
+        db::reset_all_data().map_err(|e| e.to_string())
+    }).await
+}
+
+/// Rows a targeted reset would touch: scope is 'memory' | 'conversations' | 'weights' | 'agents'
+#[tauri::command]
+async fn preview_reset(scope: String) -> Result<db::ResetPreview, String> {
+    db::run(move || {
+        db::preview_reset(&scope)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Unknown reset scope: {}", scope))
+    }).await
+}
+
+/// Forget facts, patterns, and themes; conversations stay
+#[tauri::command]
+async fn reset_memory_only() -> Result<(), String> {
+    db::run(move || {
+        db::reset_memory_only().map_err(|e| e.to_string())
+    }).await
+}
+
+/// Delete all conversations; what was learned from them stays
+#[tauri::command]
+async fn reset_conversations_only() -> Result<(), String> {
+    db::run(move || {
+        db::reset_conversations_only().map_err(|e| e.to_string())?;
+        SESSION_WEIGHTS.lock().unwrap().clear();
+        Ok(())
+    }).await
+}
+
+#[tauri::command]
+async fn reset_weights_only() -> Result<(), String> {
+    db::run(move || {
+        db::reset_weights_only().map_err(|e| e.to_string())?;
+        SESSION_WEIGHTS.lock().unwrap().clear();
+        Ok(())
+    }).await
+}
+
+/// Built-in agents back to their stock names, colors, and voices; custom agents and
+/// per-agent model and budget settings go
+#[tauri::command]
+async fn reset_agent_customizations() -> Result<(), String> {
+    db::run(move || {
+        db::reset_agent_customizations().map_err(|e| e.to_string())?;
+        agents::seed_builtin_agents().map_err(|e| e.to_string())
+    }).await
+}
+
+/// Snapshot the database to `path` (picked with the save dialog); safe while the app is running
+#[tauri::command]
+async fn backup_database(app_handle: tauri::AppHandle, path: String) -> Result<db::BackupResult, String> {
+    db::run(move || {
+        let result = db::backup_database(&path).map_err(|e| e.to_string())?;
+        if let Err(e) = wipe::record_backup(&app_handle, &result.path) {
+            logging::log_error(None, &format!("Failed to record backup location {}: {}", result.path, e));
+        }
+        logging::log_conversation(None, &format!("Database backed up to {} ({} bytes)", result.path, result.bytes));
+        Ok(result)
+    }).await
+}
+
+/// Replace all data with a backup; the current database is kept beside it first
+#[tauri::command]
+async fn restore_database(app_handle: tauri::AppHandle, path: String) -> Result<db::RestoreResult, String> {
+    db::run(move || {
+        let result = db::restore_database(&app_handle, &path).map_err(|e| e.to_string())?;
+        SESSION_WEIGHTS.lock().unwrap().clear();
+        logging::log_conversation(None, &format!(
+            "Database restored from {} (previous data saved to {})", result.restored_from, result.previous_backup
+        ));
+        Ok(result)
+    }).await
+}
+
+/// Check the database for corruption, then ANALYZE and VACUUM it; reports size before and after
+#[tauri::command]
+async fn run_db_maintenance(app_handle: tauri::AppHandle) -> Result<db::MaintenanceReport, String> {
+    db::run(move || {
+        let report = db::run_maintenance(&app_handle).map_err(|e| e.to_string())?;
+        if report.integrity_ok {
+            logging::log_conversation(None, &format!(
+                "Database maintenance: {} -> {} bytes in {}ms", report.bytes_before, report.bytes_after, report.duration_ms
+            ));
+        } else {
+            logging::log_error(None, &format!(
+                "Database maintenance found corruption, skipped VACUUM: {}", report.integrity_issues.join("; ")
+            ));
+        }
+        Ok(report)
+    }).await
+}
+
+#[tauri::command]
+async fn get_data_location(app_handle: tauri::AppHandle) -> db::DataLocation {
+    db::run_infallible(move || db::get_data_location(&app_handle)).await
+}
+
+/// Move the active profile's database to another folder (copied, verified, then switched to)
+#[tauri::command]
+async fn migrate_data_dir(app_handle: tauri::AppHandle, path: String) -> Result<db::DataMigrationResult, String> {
+    db::run(move || db::migrate_data_dir(&app_handle, &path)).await
+}
+
+/// Step one of full data destruction: returns a short-lived token and the phrase to type
+#[tauri::command]
+fn request_data_destruction() -> wipe::DestructionToken {
+    wipe::request_token()
+}
+
+/// Step two: securely delete the database, logs, and all other local data, then quit
+#[tauri::command]
+async fn destroy_all_local_data(
+    app_handle: tauri::AppHandle,
+    token: String,
+    confirmation_phrase: String,
+) -> Result<wipe::DestructionReport, String> {
+    let handle = app_handle.clone();
+    let report = db::run(move || wipe::destroy_all_local_data(&handle, &token, &confirmation_phrase)).await?;
+    // Quit a moment later, so the report still reaches the window
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        app_handle.exit(0);
+    });
+    Ok(report)
+}
+
+// ============ Window Controls ============
+
+#[tauri::command]
+async fn set_always_on_top(window: tauri::Window, always_on_top: bool) -> Result<(), String> {
+    window.set_always_on_top(always_on_top).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_governor_disco_image() -> Result<Option<String>, String> {
+    db::run(move || {
+        use std::path::PathBuf;
+        use std::fs;
+        
+        // Get home directory
+        let home = std::env::var("HOME").map_err(|e| format!("Failed to get HOME: {}", e))?;
+        let desktop_path = PathBuf::from(home).join("Desktop/the_governor-disco_mode.png");
+        
+        // Check if file exists
+        if !desktop_path.exists() {
+            return Ok(None);
+        }
+        
+        // Read file as bytes
+        let bytes = fs::read(&desktop_path).map_err(|e| format!("Failed to read file: {}", e))?;
+        
+        // Convert to base64 data URL
+        use base64::{Engine as _, engine::general_purpose};
+        let base64 = general_purpose::STANDARD.encode(&bytes);
+        let data_url = format!("data:image/png;base64,{}", base64);
+        
+        Ok(Some(data_url))
+    }).await
+}
+
+#[tauri::command]
+async fn get_governor_image() -> Result<Option<String>, String> {
+    db::run(move || {
+        use std::path::PathBuf;
+        use std::fs;
+        
+        // Get home directory
+        let home = std::env::var("HOME").map_err(|e| format!("Failed to get HOME: {}", e))?;
+        let desktop_path = PathBuf::from(home).join("Desktop/the_governor.png");
+        
+        // Check if file exists
+        if !desktop_path.exists() {
+            return Ok(None);
+        }
+        
+        // Read file as bytes
+        let bytes = fs::read(&desktop_path).map_err(|e| format!("Failed to read file: {}", e))?;
+        
+        // Convert to base64 data URL
+        use base64::{Engine as _, engine::general_purpose};
+        let base64 = general_purpose::STANDARD.encode(&bytes);
+        let data_url = format!("data:image/png;base64,{}", base64);
+        
+        Ok(Some(data_url))
+    }).await
 }
 
 // ============ Run ============
@@ -1724,8 +4272,14 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .manage(db::DbState::default())
         .invoke_handler(tauri::generate_handler![
             init_app,
+            get_profiles,
+            create_profile,
+            rename_profile,
+            delete_profile,
+            switch_profile,
             get_user_profile,
             validate_and_save_api_key,
             save_api_key,
@@ -1743,22 +4297,224 @@ pub fn run() {
             create_conversation,
             get_recent_conversations,
             get_conversation_messages,
+            get_messages_page,
             clear_conversation,
+            delete_message,
+            delete_conversation,
+            set_conversation_pinned,
+            fork_conversation,
+            set_conversation_locked,
+            trash_conversation,
+            restore_conversation,
+            get_trashed_conversations,
+            empty_trash,
             finalize_conversation,
             recover_conversations,
             get_conversation_opener,
             send_message,
+            retry_message,
+            update_message_content,
+            get_message_revisions,
+            attach_file,
+            get_message_attachments,
+            get_attachment_data,
+            delete_attachment,
+            regenerate_replies,
+            retry_agent,
             get_user_context,
             clear_user_context,
             get_memory_stats,
+            export_persona_seed,
+            import_persona_seed,
+            commit_persona_seed,
+            discard_persona_seed,
             get_user_profile_summary,
             generate_governor_report,
             generate_user_summary,
             reset_all_data,
+            preview_reset,
+            reset_memory_only,
+            reset_conversations_only,
+            reset_weights_only,
+            reset_agent_customizations,
+            backup_database,
+            restore_database,
+            run_db_maintenance,
+            get_data_location,
+            migrate_data_dir,
+            request_data_destruction,
+            destroy_all_local_data,
             set_always_on_top,
             get_governor_disco_image,
             update_weights,
+            preview_weights,
             update_points,
+            explain_conversation,
+            set_auto_close_days,
+            set_intensity_ceiling,
+            get_boundary_rules,
+            save_boundary_rule,
+            delete_boundary_rule,
+            get_house_rules,
+            set_house_rules,
+            get_filter_violation_stats,
+            get_grounding_exercises,
+            run_grounding_exercise,
+            replay_conversation,
+            set_profile_reading_level,
+            set_conversation_reading_level,
+            get_reading_level,
+            get_late_night_window,
+            set_late_night_window,
+            get_session_timer,
+            set_session_time_budget,
+            restart_session_timer,
+            get_default_session_time_budget,
+            set_default_session_time_budget,
+            get_agent_manifest,
+            get_locale_bundle,
+            set_language,
+            set_locale_string,
+            update_agent_presentation,
+            get_quiet_policy,
+            set_quiet_hours,
+            set_do_not_disturb,
+            get_power_state,
+            set_defer_jobs_on_battery,
+            get_pending_background_work,
+            approve_background_work,
+            defer_background_work,
+            schedule_check_in,
+            get_unanswered_check_ins,
+            mark_check_in_seen,
+            dismiss_check_in,
+            create_journal_entry,
+            get_journal_entries,
+            get_dream_motifs,
+            export_usage_csv,
+            get_usage_forecast,
+            get_spend_by_day,
+            get_spend_by_model,
+            get_model_policies,
+            set_agent_model_policy,
+            set_conversation_model_tier,
+            get_conversation_model_tier,
+            get_conversation_settings,
+            set_conversation_settings,
+            get_conversation_controls,
+            get_model_decisions,
+            get_latency_status,
+            set_latency_slo,
+            get_insights,
+            dismiss_insight,
+            get_agent_budgets,
+            set_agent_budget,
+            set_budget_fallback,
+            get_budget_status,
+            set_monthly_budget,
+            get_response_citations,
+            get_disagreements,
+            get_chapters,
+            create_chapter,
+            update_chapter,
+            delete_chapter,
+            set_chapter_conversation,
+            detect_chapters,
+            get_action_items,
+            complete_action_item,
+            snooze_action_item,
+            get_fact_corrections,
+            update_user_fact,
+            delete_user_fact,
+            get_conflicting_facts,
+            resolve_fact_conflict,
+            get_fact_history,
+            rollback_user_fact,
+            search_messages,
+            bookmark_message,
+            remove_bookmark,
+            get_bookmarks,
+            get_bookmarks_in_context,
+            set_bookmarks_in_context,
+            get_session_context,
+            get_related_in_context,
+            set_related_in_context,
+            get_contradiction_auto_correct,
+            set_contradiction_auto_correct,
+            get_user_patterns,
+            set_pattern_verdict,
+            update_user_pattern,
+            delete_user_pattern,
+            get_staged_memory,
+            promote_staged_memory_item,
+            discard_staged_memory_item,
+            get_memory_staging_policy,
+            set_memory_staging_policy,
+            get_digest_settings,
+            set_digest_settings,
+            get_weekly_reflections,
+            get_monthly_highlights,
+            generate_monthly_highlights,
+            export_highlights_markdown,
+            get_email_log,
+            get_notes_folder_status,
+            set_notes_folder,
+            sync_notes_folder,
+            get_documents,
+            distill_conversation,
+            export_document,
+            import_chat_archive,
+            get_archive_imports,
+            start_archive_analysis,
+            pause_archive_analysis,
+            get_archive_import_report,
+            release_archive_import,
+            discard_archive_import,
+            get_fact_decay_days,
+            set_fact_decay_days,
+            get_recurring_themes,
+            delete_theme,
+            get_theme_decay_weeks,
+            set_theme_decay_weeks,
+            get_archived_facts,
+            get_fact_policies,
+            set_fact_policy,
+            get_memory_collections,
+            create_memory_collection,
+            delete_memory_collection,
+            assign_to_collection,
+            remove_from_collection,
+            get_item_collections,
+            get_conversation_collections,
+            set_conversation_collections,
+            get_tags,
+            add_tag,
+            delete_tag,
+            tag_conversation,
+            untag_conversation,
+            get_conversation_tags,
+            get_conversations_by_tag,
+            create_project,
+            get_projects,
+            update_project,
+            delete_project,
+            set_conversation_project,
+            get_conversations_by_project,
+            export_conversation_markdown,
+            export_conversation_json,
+            export_conversation_pdf,
+            export_memory,
+            import_memory,
+            import_conversation,
+            get_share_settings,
+            set_share_settings,
+            prepare_session_share,
+            confirm_session_share,
+            get_share_log,
+            run_readonly_query,
+            get_anonymized_rendering,
+            toggle_anonymized_rendering,
+            create_support_bundle,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -11,6 +11,7 @@ use chrono::{Local, Utc};
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use once_cell::sync::Lazy;
 
@@ -39,8 +40,11 @@ impl LogCategory {
 /// Global log file handle
 static LOG_FILE: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
 
+/// Set once the log directory is being wiped; nothing is written to disk after that
+static FILE_LOGGING_OFF: AtomicBool = AtomicBool::new(false);
+
 /// Get the log directory path
-fn get_log_dir() -> PathBuf {
+pub fn get_log_dir() -> PathBuf {
     let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
     PathBuf::from(home).join("Library/Logs/Intersect")
 }
@@ -89,6 +93,9 @@ pub fn log(category: LogCategory, conversation_id: Option<&str>, message: &str)
     print!("{}", log_line);
     
     // Write to file
+    if FILE_LOGGING_OFF.load(Ordering::SeqCst) {
+        return;
+    }
     let log_path = get_log_file_path();
     if let Ok(mut file) = OpenOptions::new()
         .create(true)
@@ -99,6 +106,11 @@ pub fn log(category: LogCategory, conversation_id: Option<&str>, message: &str)
     }
 }
 
+/// Stop writing log files for the rest of this run (the full data wipe)
+pub fn disable_file_logging() {
+    FILE_LOGGING_OFF.store(true, Ordering::SeqCst);
+}
+
 /// Log a memory event (fact learned, pattern detected, theme added)
 pub fn log_memory(conversation_id: Option<&str>, message: &str) {
     log(LogCategory::Memory, conversation_id, message);
@@ -5,13 +5,27 @@
 //! - Inferring behavioral patterns over time
 //! - Generating conversation summaries for token efficiency
 //! - Building a comprehensive user profile
+//! - Applying per-category fact inclusion policies
+//! - Staging new facts/patterns until they're confirmed or old enough to trust
+//! - Showing what the agents will know at the start of a session
+//! - Decaying facts nobody has confirmed in a long time, asking about important ones first
+//! - Offering the user's bookmarked messages to the agents as flagged important
+//! - Pulling action items out of exchanges and carrying open ones into later sessions
+//! - Finding past conversations about the same thing when a new one starts
+//! - Framing what's retrieved by the chapter of the user's life it falls in
+//! - Handing the agents passages from the user's notes that match what they're saying
 
-use crate::db::{self, UserFact, UserPattern, ConversationSummary, Message};
+use crate::agents;
+use crate::db::{self, UserFact, UserPattern, ConversationSummary, Message, StagedMemory};
+use crate::chapters;
 use crate::anthropic::{AnthropicClient, AnthropicMessage, ThinkingBudget, CLAUDE_OPUS};
 use crate::logging;
-use chrono::Utc;
+use crate::proactive;
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::error::Error;
+use ts_rs::TS;
 
 // ============ Extraction Results ============
 
@@ -21,6 +35,8 @@ pub struct ExtractionResult {
     pub updated_facts: Vec<FactUpdate>,
     pub new_patterns: Vec<ExtractedPattern>,
     pub themes: Vec<String>,
+    #[serde(default)]
+    pub action_items: Vec<ExtractedActionItem>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -30,6 +46,8 @@ pub struct ExtractedFact {
     pub value: String,
     pub confidence: f64,
     pub source_type: String,
+    #[serde(default)]
+    pub valid_until: Option<String>, // "YYYY-MM-DD" for something temporary
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -40,6 +58,14 @@ pub struct FactUpdate {
     pub confirmed: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExtractedActionItem {
+    pub text: String,
+    pub owner: String,              // 'user' or the agent that proposed it
+    #[serde(default)]
+    pub due: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ExtractedPattern {
     pub pattern_type: String,
@@ -58,10 +84,22 @@ pub struct UserProfileSummary {
     pub communication_style: Option<String>,
     pub thinking_preference: Option<String>,
     pub emotional_tendency: Option<String>,
+    #[serde(default)]
+    pub flagged_important: Vec<String>,    // Bookmarked messages, when offered to the agents
+    #[serde(default)]
+    pub open_action_items: Vec<String>,    // Open items carried over from earlier sessions
+    #[serde(default)]
+    pub related_conversations: Vec<String>, // Past conversations on the same topic, when offered to the agents
+    #[serde(default)]
+    pub own_advice: std::collections::HashMap<String, Vec<String>>, // Each agent's own earlier replies on this topic, by agent
+    #[serde(default)]
+    pub related_notes: Vec<String>,        // Passages from the user's notes folder on this topic
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FactSummary {
+    #[serde(default)]
+    pub id: i64,
     pub key: String,
     pub value: String,
     pub confidence: f64,
@@ -74,19 +112,660 @@ pub struct PatternSummary {
     pub confidence: f64,
 }
 
+// ============ Fact Inclusion Policies ============
+
+/// How facts in a category reach agent prompts
+pub const POLICY_ALWAYS: &str = "always";
+pub const POLICY_RELEVANT: &str = "relevant";
+pub const POLICY_EXPLICIT: &str = "explicit";
+
+const POLICY_SETTING_PREFIX: &str = "fact_policy.";
+const DEFAULT_CATEGORIES: [&str; 5] = ["personal", "preferences", "work", "relationships", "values"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct FactPolicySetting {
+    pub category: String,
+    pub policy: String,
+}
+
+/// A category's policy (default: always include)
+pub fn fact_policy(category: &str) -> String {
+    db::get_setting(&format!("{}{}", POLICY_SETTING_PREFIX, category))
+        .ok()
+        .flatten()
+        .filter(|p| [POLICY_ALWAYS, POLICY_RELEVANT, POLICY_EXPLICIT].contains(&p.as_str()))
+        .unwrap_or_else(|| POLICY_ALWAYS.to_string())
+}
+
+pub fn set_fact_policy(category: &str, policy: &str) -> Result<(), String> {
+    if ![POLICY_ALWAYS, POLICY_RELEVANT, POLICY_EXPLICIT].contains(&policy) {
+        return Err(format!("Unknown fact policy: {}", policy));
+    }
+    db::set_setting(&format!("{}{}", POLICY_SETTING_PREFIX, category.trim().to_lowercase()), policy)
+        .map_err(|e| e.to_string())
+}
+
+/// Policies for the default categories plus any category facts have been filed under
+pub fn get_fact_policies() -> Vec<FactPolicySetting> {
+    let mut categories: Vec<String> = DEFAULT_CATEGORIES.iter().map(|c| c.to_string()).collect();
+    for fact in db::get_all_user_facts().unwrap_or_default() {
+        if !categories.contains(&fact.category) {
+            categories.push(fact.category);
+        }
+    }
+    categories
+        .into_iter()
+        .map(|category| FactPolicySetting { policy: fact_policy(&category), category })
+        .collect()
+}
+
+fn content_words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() >= 4)
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// The user named the category, the fact's key, or the fact itself
+fn explicitly_mentioned(fact: &UserFact, message_lower: &str) -> bool {
+    message_lower.contains(&fact.category.to_lowercase())
+        || message_lower.contains(&fact.key.to_lowercase().replace('_', " "))
+        || message_lower.contains(&fact.value.to_lowercase())
+}
+
+fn fact_included(fact: &UserFact, user_message: &str) -> bool {
+    let message_lower = user_message.to_lowercase();
+    match fact_policy(&fact.category).as_str() {
+        POLICY_EXPLICIT => explicitly_mentioned(fact, &message_lower),
+        POLICY_RELEVANT => {
+            let message_words = content_words(&message_lower);
+            explicitly_mentioned(fact, &message_lower)
+                || content_words(&format!("{} {}", fact.key.replace('_', " "), fact.value))
+                    .iter()
+                    .any(|w| message_words.contains(w))
+        }
+        _ => true,
+    }
+}
+
+// ============ Session Context ============
+
+const CONTEXT_TOP_FACTS: usize = 8;
+const CONTEXT_MOOD_POINTS: usize = 5;
+const GOAL_MARKERS: &[&str] = &["goal", "plan", "aspiration", "working_on", "trying_to"];
+
+/// One recent conversation's read on how the user was doing
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct MoodPoint {
+    pub conversation_id: String,
+    pub user_state: Option<String>,
+    pub emotional_tone: Option<String>,
+    pub at: String,
+}
+
+/// What the agents will know going into a session, shown before the user types
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct SessionContext {
+    pub top_facts: Vec<UserFact>,
+    pub active_goals: Vec<UserFact>,
+    pub mood_trend: Vec<MoodPoint>,      // Newest first
+    pub last_summary: Option<String>,
+    pub last_summary_at: Option<String>,
+}
+
+fn is_goal(fact: &UserFact) -> bool {
+    let key = fact.key.to_lowercase();
+    GOAL_MARKERS.iter().any(|m| key.contains(m))
+}
+
+/// Facts that reach prompts without being mentioned (the "always" policy), goals,
+/// recent mood, and the last conversation's summary
+pub fn session_context(conversation_id: Option<&str>) -> SessionContext {
+    let mut facts: Vec<UserFact> = db::get_user_facts_in_scope(conversation_id)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|f| fact_policy(&f.category) == POLICY_ALWAYS)
+        .collect();
+    facts.sort_by(|a, b| {
+        b.confidence
+            .partial_cmp(&a.confidence)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.mention_count.cmp(&a.mention_count))
+    });
+    let (active_goals, others): (Vec<UserFact>, Vec<UserFact>) = facts.into_iter().partition(is_goal);
+    let top_facts = others.into_iter().take(CONTEXT_TOP_FACTS).collect();
+
+    let summaries: Vec<ConversationSummary> = db::get_recent_conversation_summaries(CONTEXT_MOOD_POINTS + 1)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|s| Some(s.conversation_id.as_str()) != conversation_id)
+        .take(CONTEXT_MOOD_POINTS)
+        .collect();
+    let mood_trend = summaries
+        .iter()
+        .filter(|s| s.user_state.is_some() || s.emotional_tone.is_some())
+        .map(|s| MoodPoint {
+            conversation_id: s.conversation_id.clone(),
+            user_state: s.user_state.clone(),
+            emotional_tone: s.emotional_tone.clone(),
+            at: s.created_at.clone(),
+        })
+        .collect();
+
+    SessionContext {
+        top_facts,
+        active_goals,
+        mood_trend,
+        last_summary: summaries.first().map(|s| s.summary.clone()),
+        last_summary_at: summaries.first().map(|s| s.created_at.clone()),
+    }
+}
+
+// ============ Memory Staging ============
+
+pub const STAGING_DAYS_KEY: &str = "memory_staging_days";
+pub const STAGING_CONFIRMATIONS_KEY: &str = "memory_staging_confirmations";
+pub const DEFAULT_STAGING_DAYS: i64 = 7;
+pub const DEFAULT_STAGING_CONFIRMATIONS: i64 = 2;
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct StagingPolicy {
+    pub days: i64,
+    pub confirmations: i64,
+}
+
+pub fn staging_policy() -> StagingPolicy {
+    let read = |key: &str, default: i64| {
+        db::get_setting(key)
+            .ok()
+            .flatten()
+            .and_then(|v| v.parse::<i64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(default)
+    };
+    StagingPolicy {
+        days: read(STAGING_DAYS_KEY, DEFAULT_STAGING_DAYS),
+        confirmations: read(STAGING_CONFIRMATIONS_KEY, DEFAULT_STAGING_CONFIRMATIONS),
+    }
+}
+
+/// Move one staged item into the long-term tables
+pub fn promote_staged_item(item: &StagedMemory) -> Result<(), String> {
+    if item.kind == db::STAGED_FACT {
+        let fact = UserFact {
+            id: 0,
+            category: item.category.clone(),
+            key: item.key.clone(),
+            value: item.value.clone(),
+            confidence: item.confidence,
+            source_type: item.source_type.clone().unwrap_or_else(|| "inferred".to_string()),
+            source_conversation_id: item.source_conversation_id.clone(),
+            first_mentioned: item.first_seen.clone(),
+            last_confirmed: item.last_seen.clone(),
+            mention_count: item.confirmations,
+            valid_until: None,
+        };
+        db::save_user_fact(&fact).map_err(|e| e.to_string())?;
+        if let Some(conversation_id) = &item.source_conversation_id {
+            let _ = db::file_fact_in_conversation_scope(&item.category, &item.key, conversation_id);
+        }
+    } else {
+        let pattern = UserPattern {
+            id: 0,
+            pattern_type: item.category.clone(),
+            description: item.key.clone(),
+            confidence: item.confidence,
+            evidence: item.value.clone(),
+            first_observed: item.first_seen.clone(),
+            last_updated: item.last_seen.clone(),
+            observation_count: item.confirmations,
+            verdict: None,
+        };
+        db::save_user_pattern(&pattern).map_err(|e| e.to_string())?;
+    }
+    db::delete_staged_memory(item.id).map_err(|e| e.to_string())
+}
+
+/// Promote everything that has been confirmed enough times or staged long enough
+pub fn promote_staged_memory() -> Result<usize, String> {
+    let policy = staging_policy();
+    let cutoff = (Utc::now() - Duration::days(policy.days)).to_rfc3339();
+    let ready = db::get_promotable_staged_memory(policy.confirmations, &cutoff).map_err(|e| e.to_string())?;
+
+    for item in &ready {
+        promote_staged_item(item)?;
+    }
+    if !ready.is_empty() {
+        logging::log_memory(None, &format!("Promoted {} staged memory items to long-term", ready.len()));
+    }
+    Ok(ready.len())
+}
+
+// ============ Fact Decay ============
+
+/// Days without confirmation before a fact is archived; 0 or unset = facts never decay
+pub const FACT_DECAY_DAYS_KEY: &str = "fact_decay_days";
+/// Facts mentioned this often get asked about before they're archived
+pub const SIGNIFICANT_MENTIONS: i64 = 3;
+/// How long before archiving the confirm prompt goes out, and how long it gets an answer
+const DECAY_WARNING_DAYS: i64 = 7;
+const CONFIRM_AGENT: &str = "psyche";
+const CONFIRM_TOPIC_PREFIX: &str = "fact-confirm-";
+
+pub fn fact_decay_days() -> Option<i64> {
+    db::get_setting(FACT_DECAY_DAYS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|d| *d > 0)
+}
+
+pub fn set_fact_decay_days(days: i64) -> Result<(), String> {
+    db::set_setting(FACT_DECAY_DAYS_KEY, &days.max(0).to_string()).map_err(|e| e.to_string())
+}
+
+fn confirm_prompt(fact: &UserFact) -> String {
+    format!(
+        "Quick check so I don't hold on to something stale -- is this still true? {}: {}",
+        fact.key.replace('_', " "),
+        fact.value
+    )
+}
+
+/// Archive facts past the decay window. A significant fact is never dropped
+/// silently: as it nears the window a check-in asks whether it still holds, and
+/// it's only archived once that has been out for the warning period without the
+/// fact being reconfirmed. Returns (prompts queued, facts archived).
+pub fn decay_facts() -> Result<(usize, usize), String> {
+    let Some(days) = fact_decay_days() else {
+        return Ok((0, 0));
+    };
+    let now = Utc::now();
+    let cutoff = (now - Duration::days(days)).to_rfc3339();
+    let warn_cutoff = (now - Duration::days((days - DECAY_WARNING_DAYS).max(1))).to_rfc3339();
+    let answer_by = (now - Duration::days(DECAY_WARNING_DAYS)).to_rfc3339();
+
+    let mut prompted = 0;
+    let mut archived = 0;
+    for fact in db::get_facts_confirmed_before(&warn_cutoff).map_err(|e| e.to_string())? {
+        let expired = fact.last_confirmed <= cutoff;
+        if fact.mention_count < SIGNIFICANT_MENTIONS {
+            if expired && db::archive_user_fact(fact.id).map_err(|e| e.to_string())? {
+                archived += 1;
+            }
+            continue;
+        }
+
+        // Only a prompt sent since the fact was last confirmed counts
+        let topic_key = format!("{}{}", CONFIRM_TOPIC_PREFIX, fact.id);
+        let prompt = db::get_latest_proactive_for_topic(&topic_key)
+            .map_err(|e| e.to_string())?
+            .filter(|p| p.created_at > fact.last_confirmed);
+        match prompt {
+            None => {
+                let conversation_id = fact.source_conversation_id.as_deref()
+                    .filter(|id| db::get_conversation(id).ok().flatten().is_some());
+                proactive::schedule(CONFIRM_AGENT, &confirm_prompt(&fact), &topic_key, &now.to_rfc3339(), conversation_id, false)?;
+                prompted += 1;
+            }
+            Some(p) if expired && p.status != "pending" && p.created_at <= answer_by => {
+                if db::archive_user_fact(fact.id).map_err(|e| e.to_string())? {
+                    archived += 1;
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    if prompted > 0 || archived > 0 {
+        logging::log_memory(None, &format!(
+            "Fact decay: {} confirm prompts queued, {} facts archived", prompted, archived
+        ));
+    }
+    Ok((prompted, archived))
+}
+
+/// When a transient fact stops holding, as RFC 3339: a bare date means the end
+/// of that day here. None for anything unparseable or already past.
+pub fn parse_valid_until(value: &str) -> Option<String> {
+    let value = value.trim();
+    let until = match NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        Ok(date) => Local.from_local_datetime(&date.and_hms_opt(23, 59, 59)?).earliest()?.with_timezone(&Utc),
+        Err(_) => DateTime::parse_from_rfc3339(value).ok()?.with_timezone(&Utc),
+    };
+    (until > Utc::now()).then(|| until.to_rfc3339())
+}
+
+/// Archive transient facts whose valid_until has passed; they already stopped
+/// reaching prompts at that moment. Returns how many were archived.
+pub fn expire_facts() -> Result<usize, String> {
+    let mut archived = 0;
+    for fact in db::get_expired_user_facts().map_err(|e| e.to_string())? {
+        if db::archive_user_fact(fact.id).map_err(|e| e.to_string())? {
+            archived += 1;
+        }
+    }
+    if archived > 0 {
+        logging::log_memory(None, &format!("Fact expiry: {} transient facts archived", archived));
+    }
+    Ok(archived)
+}
+
+// ============ Theme Decay ============
+
+/// Setting: weeks without a mention before a theme's frequency starts halving ("0" = never)
+pub const THEME_DECAY_WEEKS_KEY: &str = "theme_decay_weeks";
+pub const DEFAULT_THEME_DECAY_WEEKS: i64 = 8;
+
+pub fn theme_decay_weeks() -> Option<i64> {
+    let weeks = db::get_setting(THEME_DECAY_WEEKS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .unwrap_or(DEFAULT_THEME_DECAY_WEEKS);
+    (weeks > 0).then_some(weeks)
+}
+
+pub fn set_theme_decay_weeks(weeks: i64) -> Result<(), String> {
+    db::set_setting(THEME_DECAY_WEEKS_KEY, &weeks.max(0).to_string()).map_err(|e| e.to_string())
+}
+
+/// Halve the frequency of each theme for every decay period it goes without a
+/// mention, so old topics sink and eventually drop off. Returns (decayed, removed).
+pub fn decay_themes() -> Result<(usize, usize), String> {
+    let Some(weeks) = theme_decay_weeks() else {
+        return Ok((0, 0));
+    };
+    let cutoff = (Utc::now() - Duration::weeks(weeks)).to_rfc3339();
+    let (decayed, removed) = db::decay_recurring_themes(&cutoff).map_err(|e| e.to_string())?;
+    if decayed > 0 {
+        logging::log_memory(None, &format!("Theme decay: {} themes halved, {} dropped", decayed, removed));
+    }
+    Ok((decayed, removed))
+}
+
+// ============ Bookmarks ============
+
+/// Setting: offer bookmarked messages to the agents ("true"/"false", default off)
+pub const BOOKMARKS_IN_CONTEXT_KEY: &str = "bookmarks_in_context";
+const MAX_FLAGGED_IN_CONTEXT: usize = 5;
+const FLAGGED_EXCERPT_CHARS: usize = 200;
+
+pub fn bookmarks_in_context() -> bool {
+    db::get_setting(BOOKMARKS_IN_CONTEXT_KEY).ok().flatten().as_deref() == Some("true")
+}
+
+/// The newest bookmarks as prompt lines: who said it and when, the note, an excerpt
+fn flagged_lines() -> Vec<String> {
+    db::get_bookmarks(None, None, None, MAX_FLAGGED_IN_CONTEXT)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|b| {
+            let speaker = if b.role == "user" { "User".to_string() } else { agents::display_name(&b.role) };
+            let date = b.timestamp.get(..10).unwrap_or(&b.timestamp).to_string();
+            let text = b.content.split_whitespace().collect::<Vec<_>>().join(" ");
+            let excerpt = if text.chars().count() > FLAGGED_EXCERPT_CHARS {
+                format!("{}...", text.chars().take(FLAGGED_EXCERPT_CHARS).collect::<String>().trim_end())
+            } else {
+                text
+            };
+            match b.note.as_deref().filter(|n| !n.trim().is_empty()) {
+                Some(note) => format!("{} ({}), flagged as \"{}\": \"{}\"", speaker, date, note.trim(), excerpt),
+                None => format!("{} ({}): \"{}\"", speaker, date, excerpt),
+            }
+        })
+        .collect()
+}
+
+// ============ Action Items ============
+
+/// Open items from earlier sessions shown to the agents, oldest first
+const MAX_CARRIED_ACTION_ITEMS: usize = 5;
+
+/// Open action items for the agents' prompt, or "" when there are none
+pub fn format_action_items_for_prompt(profile: &UserProfileSummary) -> String {
+    profile.open_action_items.iter()
+        .map(|item| format!("- {}", item))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ============ Related Conversations ============
+
+/// Setting: also give the agents the related conversations as context ("true"/"false", default off)
+pub const RELATED_IN_CONTEXT_KEY: &str = "related_conversations_in_context";
+const MAX_RELATED_CONVERSATIONS: usize = 3;
+/// How many recent summaries are searched
+const RELATED_SCAN_LIMIT: usize = 200;
+/// Distinct words a summary must share with the message to count as related
+const MIN_SHARED_WORDS: usize = 2;
+const MAX_SUMMARY_LINE_CHARS: usize = 160;
+/// Common words long enough to get past `content_words` that say nothing about the topic
+const RELATED_STOPWORDS: &[&str] = &[
+    "about", "been", "from", "have", "just", "like", "really", "that", "them", "then",
+    "there", "they", "this", "what", "when", "with", "would", "could", "should", "feel", "think",
+];
+
+/// A past conversation that covered the same ground as a new one's first message
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct PastConversation {
+    pub conversation_id: String,
+    pub title: Option<String>,
+    pub summary: String,            // First sentence of the summary
+    pub at: String,                 // When it was summarized
+    pub chapter: Option<String>,    // The life chapter it falls in ("the layoff spring")
+}
+
+pub fn related_in_context() -> bool {
+    db::get_setting(RELATED_IN_CONTEXT_KEY).ok().flatten().as_deref() == Some("true")
+}
+
+fn topic_words(text: &str) -> HashSet<String> {
+    content_words(text)
+        .into_iter()
+        .filter(|w| !RELATED_STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// First sentence of a summary, trimmed to one line
+pub fn summary_line(summary: &str) -> String {
+    let first = summary.split_inclusive(['.', '!', '?']).next().unwrap_or(summary).trim();
+    if first.chars().count() > MAX_SUMMARY_LINE_CHARS {
+        format!("{}...", first.chars().take(MAX_SUMMARY_LINE_CHARS).collect::<String>().trim_end())
+    } else {
+        first.to_string()
+    }
+}
+
+/// Up to three past conversations whose summaries share the most topic words with
+/// `message` (key topics count double), most relevant first. Word overlap only, no model call.
+pub fn related_conversations(conversation_id: &str, message: &str) -> Vec<PastConversation> {
+    let words = topic_words(message);
+    if words.len() < MIN_SHARED_WORDS {
+        return Vec::new();
+    }
+    let summaries = db::get_past_conversation_summaries(conversation_id, RELATED_SCAN_LIMIT).unwrap_or_default();
+    let mut scored: Vec<(usize, ConversationSummary)> = summaries
+        .into_iter()
+        .filter_map(|summary| {
+            let topics: Vec<String> = serde_json::from_str(&summary.key_topics).unwrap_or_default();
+            let topic_matches: HashSet<String> = topic_words(&topics.join(" ")).intersection(&words).cloned().collect();
+            let summary_matches: HashSet<String> = topic_words(&summary.summary).intersection(&words).cloned().collect();
+            let shared = topic_matches.union(&summary_matches).count();
+            (shared >= MIN_SHARED_WORDS).then_some((summary_matches.len() + 2 * topic_matches.len(), summary))
+        })
+        .collect();
+    // Summaries come newest first, so a stable sort keeps ties in recency order
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    let chapters = db::get_chapters().unwrap_or_default();
+    scored
+        .into_iter()
+        .take(MAX_RELATED_CONVERSATIONS)
+        .map(|(_, summary)| PastConversation {
+            title: db::get_conversation(&summary.conversation_id).ok().flatten().and_then(|c| c.title),
+            summary: summary_line(&summary.summary),
+            chapter: chapters::containing(&chapters, Some(&summary.conversation_id), &summary.created_at).map(|c| c.title.clone()),
+            at: summary.created_at,
+            conversation_id: summary.conversation_id,
+        })
+        .collect()
+}
+
+/// Lines for the agents' prompt, one per related conversation
+pub fn related_prompt_lines(related: &[PastConversation]) -> Vec<String> {
+    related
+        .iter()
+        .map(|c| {
+            let date = c.at.get(..10).unwrap_or(&c.at);
+            let when = match &c.chapter {
+                Some(chapter) => format!("{}, during {}", date, chapter),
+                None => date.to_string(),
+            };
+            match &c.title {
+                Some(title) => format!("{} ({}): {}", title, when, c.summary),
+                None => format!("{}: {}", when, c.summary),
+            }
+        })
+        .collect()
+}
+
+// ============ Own Past Advice ============
+
+const ADVICE_AGENTS: [&str; 3] = ["instinct", "logic", "psyche"];
+const MAX_OWN_ADVICE: usize = 2;
+/// Candidate replies fetched per agent before scoring
+const OWN_ADVICE_SCAN_LIMIT: usize = 40;
+const OWN_ADVICE_EXCERPT_CHARS: usize = 240;
+
+/// Each agent's own replies from other conversations on the topic of `message`
+/// ("you told them to quit in March"), at most two per agent, best match first.
+/// Word overlap only, no model call.
+pub fn own_past_advice(conversation_id: &str, message: &str) -> std::collections::HashMap<String, Vec<String>> {
+    let mut advice = std::collections::HashMap::new();
+    let words = topic_words(message);
+    if words.len() < MIN_SHARED_WORDS {
+        return advice;
+    }
+    let query: Vec<String> = words.iter().cloned().collect();
+    let chapters = db::get_chapters().unwrap_or_default();
+
+    for agent in ADVICE_AGENTS {
+        let candidates = db::get_agent_messages_matching(agent, &query, conversation_id, OWN_ADVICE_SCAN_LIMIT).unwrap_or_default();
+        let mut scored: Vec<(usize, Message)> = candidates
+            .into_iter()
+            .map(|m| (topic_words(&m.content).intersection(&words).count(), m))
+            .filter(|(shared, _)| *shared >= MIN_SHARED_WORDS)
+            .collect();
+        // Candidates come newest first, so a stable sort keeps ties in recency order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+        let lines: Vec<String> = scored
+            .into_iter()
+            .take(MAX_OWN_ADVICE)
+            .map(|(_, m)| {
+                let mut date = m.timestamp.get(..10).unwrap_or(&m.timestamp).to_string();
+                if let Some(chapter) = chapters::containing(&chapters, Some(&m.conversation_id), &m.timestamp) {
+                    date = format!("{}, during {}", date, chapter.title);
+                }
+                let text = m.content.split_whitespace().collect::<Vec<_>>().join(" ");
+                if text.chars().count() > OWN_ADVICE_EXCERPT_CHARS {
+                    format!("{}: \"{}...\"", date, text.chars().take(OWN_ADVICE_EXCERPT_CHARS).collect::<String>().trim_end())
+                } else {
+                    format!("{}: \"{}\"", date, text)
+                }
+            })
+            .collect();
+        if !lines.is_empty() {
+            advice.insert(agent.to_string(), lines);
+        }
+    }
+    advice
+}
+
+// ============ Notes ============
+
+const MAX_RELATED_NOTES: usize = 2;
+/// How many of the newest notes are searched
+const NOTES_SCAN_LIMIT: usize = 300;
+const NOTE_EXCERPT_CHARS: usize = 300;
+
+/// Passages from the user's notes folder on the topic of `message`: the best
+/// matching paragraph of each of the two best matching notes the conversation's
+/// collections allow. Word overlap only, no model call.
+pub fn related_notes(conversation_id: &str, message: &str) -> Vec<String> {
+    let words = topic_words(message);
+    if words.len() < MIN_SHARED_WORDS {
+        return Vec::new();
+    }
+    let notes = db::get_documents_in_scope(Some(conversation_id), db::DOCUMENT_SOURCE_NOTES, NOTES_SCAN_LIMIT).unwrap_or_default();
+    let mut scored: Vec<(usize, String, &str)> = notes
+        .iter()
+        .filter_map(|note| {
+            let (score, paragraph) = note.content
+                .split("\n\n")
+                .map(|p| (topic_words(p).intersection(&words).count(), p))
+                .max_by_key(|(score, _)| *score)?;
+            let title_matches = topic_words(&note.title).intersection(&words).count();
+            (score + title_matches >= MIN_SHARED_WORDS).then(|| (score + 2 * title_matches, note.title.clone(), paragraph))
+        })
+        .collect();
+    // Notes come newest first, so a stable sort keeps ties in recency order
+    scored.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+
+    scored
+        .into_iter()
+        .take(MAX_RELATED_NOTES)
+        .map(|(_, title, paragraph)| {
+            let text = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+            if text.chars().count() > NOTE_EXCERPT_CHARS {
+                format!("{}: \"{}...\"", title, text.chars().take(NOTE_EXCERPT_CHARS).collect::<String>().trim_end())
+            } else {
+                format!("{}: \"{}\"", title, text)
+            }
+        })
+        .collect()
+}
+
 // ============ Memory Extractor ============
 
 pub struct MemoryExtractor {
     client: AnthropicClient,
+    held_by_import: Option<String>,
+    round_id: Option<String>,
 }
 
 impl MemoryExtractor {
     pub fn new(api_key: &str) -> Self {
         Self {
-            client: AnthropicClient::new(api_key),
+            client: AnthropicClient::new(api_key).with_usage_category("memory"),
+            held_by_import: None,
+            round_id: None,
         }
     }
     
+    /// Extractor for an archive import: everything it finds is staged and held for
+    /// review (nothing reaches long-term memory or themes), and usage is billed to "import"
+    pub fn for_import(api_key: &str, import_id: &str) -> Self {
+        Self {
+            client: AnthropicClient::new(api_key).with_usage_category("import"),
+            held_by_import: Some(import_id.to_string()),
+            round_id: None,
+        }
+    }
+    
+    /// Link action items found in this exchange to the messages of `round_id`
+    pub fn for_round(mut self, round_id: &str) -> Self {
+        self.round_id = Some(round_id.to_string());
+        self
+    }
+    
     /// Extract facts and patterns from a conversation exchange
     pub async fn extract_from_exchange(
         &self,
@@ -96,7 +775,7 @@ impl MemoryExtractor {
         conversation_id: &str,
     ) -> Result<ExtractionResult, Box<dyn Error + Send + Sync>> {
         logging::log_memory(Some(conversation_id), &format!(
-            "Starting extraction. User message: {}", &user_message[..user_message.len().min(100)]
+            "Starting extraction. User message: {}", user_message.chars().take(100).collect::<String>()
         ));
         // Build context of existing facts for the LLM
         let existing_facts_context = if existing_facts.is_empty() {
@@ -126,6 +805,7 @@ EXTRACT TWO TYPES OF INFORMATION:
    - Only extract what the USER explicitly states
    - High confidence (0.8-1.0) for direct statements
    - Lower confidence (0.5-0.7) for implied information
+   - Something temporary ("interviewing next week", "on vacation until Friday") gets "valid_until": the date it stops being true (YYYY-MM-DD, counted from TODAY), under a key of its own rather than one a lasting fact uses; leave it out for lasting facts
 
 2. PATTERNS (behavioral observations):
    Types: "communication_style", "emotional_tendency", "thinking_mode", "decision_making", "values_expression"
@@ -137,6 +817,12 @@ EXTRACT TWO TYPES OF INFORMATION:
    - Extract 1-3 main themes/topics from this exchange
    - These help track what the user cares about over time
 
+4. ACTION ITEMS (concrete next steps):
+   - Things the USER committed to doing (owner "user"), or specific steps an agent proposed that the user seemed to take on (owner: that agent's name, lowercase)
+   - Each one short, starting with a verb ("Email Sam about the lease")
+   - Include "due" only when a time was actually mentioned, as said ("Friday", "next week")
+   - Skip vague intentions, generic advice, and anything already done
+
 IMPORTANT:
 - Be conservative - only extract clear, meaningful information
 - Don't repeat existing facts unless you're confirming/updating them
@@ -144,14 +830,16 @@ IMPORTANT:
 
 Respond with ONLY valid JSON in this exact format:
 {
-  "new_facts": [{"category": "...", "key": "...", "value": "...", "confidence": 0.9, "source_type": "explicit"}],
+  "new_facts": [{"category": "...", "key": "...", "value": "...", "confidence": 0.9, "source_type": "explicit", "valid_until": "YYYY-MM-DD" or null}],
   "updated_facts": [{"category": "...", "key": "...", "new_value": "..." or null, "confirmed": true}],
   "new_patterns": [{"pattern_type": "...", "description": "...", "confidence": 0.5, "evidence": "..."}],
-  "themes": ["theme1", "theme2"]
+  "themes": ["theme1", "theme2"],
+  "action_items": [{"text": "...", "owner": "user", "due": "..." or null}]
 }"#;
 
         let user_prompt = format!(
-            "EXISTING FACTS ABOUT USER:\n{}\n\nCONVERSATION EXCHANGE:\nUSER: {}\n{}\n\nExtract any new learnable information:",
+            "TODAY: {}\n\nEXISTING FACTS ABOUT USER:\n{}\n\nCONVERSATION EXCHANGE:\nUSER: {}\n{}\n\nExtract any new learnable information:",
+            Local::now().format("%A, %Y-%m-%d"),
             existing_facts_context,
             user_message,
             responses_text
@@ -196,13 +884,14 @@ Respond with ONLY valid JSON in this exact format:
                     updated_facts: Vec::new(),
                     new_patterns: Vec::new(),
                     themes: Vec::new(),
+                    action_items: Vec::new(),
                 }
             }
         };
         
         logging::log_memory(Some(conversation_id), &format!(
-            "Extracted {} facts, {} patterns, {} themes",
-            result.new_facts.len(), result.new_patterns.len(), result.themes.len()
+            "Extracted {} facts, {} patterns, {} themes, {} action items",
+            result.new_facts.len(), result.new_patterns.len(), result.themes.len(), result.action_items.len()
         ));
         
         // Save extracted data to database
@@ -216,57 +905,144 @@ Respond with ONLY valid JSON in this exact format:
     fn save_extraction_result(&self, result: &ExtractionResult, conversation_id: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
         let now = Utc::now().to_rfc3339();
         
-        // Save new facts
+        // New facts and patterns go to staging unless long-term memory already has them
+        let held = self.held_by_import.is_some();
         for fact in &result.new_facts {
-            let user_fact = UserFact {
-                id: 0, // Will be assigned by DB
+            // Transient facts skip staging: most would expire before a second confirmation
+            let valid_until = fact.valid_until.as_deref().and_then(parse_valid_until);
+            if !held && (valid_until.is_some() || db::user_fact_exists(&fact.category, &fact.key).unwrap_or(false)) {
+                let user_fact = UserFact {
+                    id: 0, // Will be assigned by DB
+                    category: fact.category.clone(),
+                    key: fact.key.clone(),
+                    value: fact.value.clone(),
+                    confidence: fact.confidence,
+                    source_type: fact.source_type.clone(),
+                    source_conversation_id: Some(conversation_id.to_string()),
+                    first_mentioned: now.clone(),
+                    last_confirmed: now.clone(),
+                    mention_count: 1,
+                    valid_until,
+                };
+                let _ = db::save_user_fact(&user_fact);
+                continue;
+            }
+            let _ = db::stage_memory(&StagedMemory {
+                id: 0,
+                kind: db::STAGED_FACT.to_string(),
                 category: fact.category.clone(),
                 key: fact.key.clone(),
                 value: fact.value.clone(),
                 confidence: fact.confidence,
-                source_type: fact.source_type.clone(),
+                source_type: Some(fact.source_type.clone()),
                 source_conversation_id: Some(conversation_id.to_string()),
-                first_mentioned: now.clone(),
-                last_confirmed: now.clone(),
-                mention_count: 1,
-            };
-            let _ = db::save_user_fact(&user_fact);
+                first_seen: now.clone(),
+                last_seen: now.clone(),
+                confirmations: 1,
+                held_by_import: self.held_by_import.clone(),
+            });
         }
         
-        // Save new patterns
         for pattern in &result.new_patterns {
-            let user_pattern = UserPattern {
+            if !held && db::user_pattern_exists(&pattern.pattern_type, &pattern.description).unwrap_or(false) {
+                let user_pattern = UserPattern {
+                    id: 0,
+                    pattern_type: pattern.pattern_type.clone(),
+                    description: pattern.description.clone(),
+                    confidence: pattern.confidence,
+                    evidence: pattern.evidence.clone(),
+                    first_observed: now.clone(),
+                    last_updated: now.clone(),
+                    observation_count: 1,
+                    verdict: None,
+                };
+                let _ = db::save_user_pattern(&user_pattern);
+                continue;
+            }
+            let _ = db::stage_memory(&StagedMemory {
                 id: 0,
-                pattern_type: pattern.pattern_type.clone(),
-                description: pattern.description.clone(),
+                kind: db::STAGED_PATTERN.to_string(),
+                category: pattern.pattern_type.clone(),
+                key: pattern.description.clone(),
+                value: pattern.evidence.clone(),
                 confidence: pattern.confidence,
-                evidence: pattern.evidence.clone(),
-                first_observed: now.clone(),
-                last_updated: now.clone(),
-                observation_count: 1,
-            };
-            let _ = db::save_user_pattern(&user_pattern);
+                source_type: None,
+                source_conversation_id: Some(conversation_id.to_string()),
+                first_seen: now.clone(),
+                last_seen: now.clone(),
+                confirmations: 1,
+                held_by_import: self.held_by_import.clone(),
+            });
+        }
+        
+        // Held imports wait for review; the import report collects their themes
+        if held {
+            return Ok(());
+        }
+        
+        // Facts the user reaffirmed (or corrected) stay fresh, which also keeps them from decaying
+        for update in result.updated_facts.iter().filter(|u| u.confirmed || u.new_value.is_some()) {
+            let _ = db::confirm_user_fact(&update.category, &update.key, update.new_value.as_deref(), Some(conversation_id));
         }
         
+        // Anything that just reached its confirmation count moves over now
+        let _ = promote_staged_memory();
+        
         // Save themes
         for theme in &result.themes {
             let _ = db::save_recurring_theme(theme, conversation_id);
         }
         
+        self.save_action_items(&result.action_items, conversation_id);
+        
         Ok(())
     }
     
-    /// Build a consolidated user profile summary for agent grounding
-    pub fn build_profile_summary() -> Result<UserProfileSummary, Box<dyn Error + Send + Sync>> {
-        let facts = db::get_all_user_facts().unwrap_or_default();
-        let patterns = db::get_all_user_patterns().unwrap_or_default();
-        let themes = db::get_top_themes(10).unwrap_or_default();
+    /// Save extracted action items, linked to the round's messages when there is one
+    fn save_action_items(&self, items: &[ExtractedActionItem], conversation_id: &str) {
+        for item in items {
+            let text = item.text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let owner = item.owner.trim().to_lowercase();
+            let message_id = self.round_id.as_deref()
+                .and_then(|round| db::get_round_message_id(round, &owner).ok().flatten());
+            let due = item.due.as_deref().map(str::trim).filter(|d| !d.is_empty());
+            if let Ok(Some(id)) = db::save_action_item(conversation_id, message_id.as_deref(), text, &owner, due) {
+                logging::log_memory(Some(conversation_id), &format!("Action item {} ({}): {}", id, owner, text));
+            }
+        }
+    }
+    
+    /// Build a consolidated user profile summary for agent grounding.
+    /// With a conversation, facts are limited to that conversation's memory collections;
+    /// with a user message, each category's inclusion policy is applied against it.
+    pub fn build_profile_summary(
+        conversation_id: Option<&str>,
+        user_message: Option<&str>,
+    ) -> Result<UserProfileSummary, Box<dyn Error + Send + Sync>> {
+        let mut facts = db::get_user_facts_in_scope(conversation_id).unwrap_or_default();
+        if let Some(message) = user_message {
+            facts.retain(|f| fact_included(f, message));
+        }
+        let patterns = db::get_prompt_user_patterns().unwrap_or_default();
+        let themes = db::get_prompt_themes(10).unwrap_or_default();
+        let open_action_items = db::get_carried_action_items(conversation_id, &Utc::now().to_rfc3339(), MAX_CARRIED_ACTION_ITEMS)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| match item.due {
+                Some(due) => format!("{} (due {})", item.text, due),
+                None => item.text,
+            })
+            .collect();
         
         // Group facts by category
         let mut facts_by_category: std::collections::HashMap<String, Vec<FactSummary>> = std::collections::HashMap::new();
         for fact in facts {
             let entry = facts_by_category.entry(fact.category.clone()).or_default();
             entry.push(FactSummary {
+                id: fact.id,
                 key: fact.key,
                 value: fact.value,
                 confidence: fact.confidence,
@@ -306,6 +1082,17 @@ Respond with ONLY valid JSON in this exact format:
             communication_style,
             thinking_preference,
             emotional_tendency,
+            flagged_important: if bookmarks_in_context() { flagged_lines() } else { Vec::new() },
+            open_action_items,
+            related_conversations: Vec::new(),
+            own_advice: match (conversation_id, user_message) {
+                (Some(conversation_id), Some(message)) => own_past_advice(conversation_id, message),
+                _ => std::collections::HashMap::new(),
+            },
+            related_notes: match (conversation_id, user_message) {
+                (Some(conversation_id), Some(message)) => related_notes(conversation_id, message),
+                _ => Vec::new(),
+            },
         })
     }
     
@@ -330,7 +1117,7 @@ Respond with ONLY valid JSON in this exact format:
                 for (category, facts) in &profile.facts_by_category {
                     let high_conf: Vec<_> = facts.iter().filter(|f| f.confidence >= 0.7).collect();
                     if !high_conf.is_empty() {
-                        let items: Vec<String> = high_conf.iter().map(|f| format!("{}: {}", f.key, f.value)).collect();
+                        let items: Vec<String> = high_conf.iter().map(|f| format!("[F{}] {}: {}", f.id, f.key, f.value)).collect();
                         parts.push(format!("{}:\n  {}", category.to_uppercase(), items.join("\n  ")));
                     }
                 }
@@ -351,7 +1138,7 @@ Respond with ONLY valid JSON in this exact format:
                 for (category, facts) in &profile.facts_by_category {
                     if !facts.is_empty() {
                         let items: Vec<String> = facts.iter().map(|f| {
-                            format!("{}: {} ({:.0}%)", f.key, f.value, f.confidence * 100.0)
+                            format!("[F{}] {}: {} ({:.0}%)", f.id, f.key, f.value, f.confidence * 100.0)
                         }).collect();
                         parts.push(format!("{}:\n  {}", category.to_uppercase(), items.join("\n  ")));
                     }
@@ -383,7 +1170,14 @@ pub struct ConversationSummarizer {
 impl ConversationSummarizer {
     pub fn new(api_key: &str) -> Self {
         Self {
-            client: AnthropicClient::new(api_key),
+            client: AnthropicClient::new(api_key).with_usage_category("summary"),
+        }
+    }
+    
+    /// Summarizer for an archive import, billed to "import"
+    pub fn for_import(api_key: &str) -> Self {
+        Self {
+            client: AnthropicClient::new(api_key).with_usage_category("import"),
         }
     }
     
@@ -0,0 +1,156 @@
+//! Adaptive model selection for agent turns
+//!
+//! Before an agent is called, the user's message is scored for complexity with a
+//! cheap local heuristic (no extra API call). Each agent has a policy: "adaptive"
+//! sends simple turns to the economy model and everything else to the premium
+//! one, while "economy" and "premium" pin the tier. A conversation can override
+//! the tier for all its agents. Every choice is written to `model_decisions`
+//! so it can be audited later.
+
+use crate::db::{self, ModelDecision};
+use crate::logging;
+use crate::openai::{OPENAI_MODEL, OPENAI_MODEL_MINI};
+use crate::orchestrator::ResponseScale;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+pub const AGENT_MODEL_POLICY_PREFIX: &str = "agent_model_policy_";
+
+pub const POLICY_ADAPTIVE: &str = "adaptive";
+pub const TIER_ECONOMY: &str = "economy";
+pub const TIER_PREMIUM: &str = "premium";
+
+const AGENTS: [&str; 3] = ["instinct", "logic", "psyche"];
+
+/// Words that signal the user wants real reasoning, not a quick reaction
+const REASONING_MARKERS: &[&str] = &[
+    "should i", "trade-off", "tradeoff", "pros and cons", "compare", "decide", "decision",
+    "plan", "strategy", "why do", "why does", "why am", "how do i", "figure out", "weigh",
+    "options", "worried", "conflicted", "torn",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Complexity {
+    Simple,
+    Moderate,
+    Hard,
+}
+
+impl Complexity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Complexity::Simple => "simple",
+            Complexity::Moderate => "moderate",
+            Complexity::Hard => "hard",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct AgentModelPolicy {
+    pub agent: String,
+    pub policy: String,             // 'adaptive' | 'economy' | 'premium'
+}
+
+/// The model picked for one agent turn
+pub struct ModelChoice {
+    pub model: &'static str,
+    pub tier: &'static str,
+}
+
+/// Score a message's complexity, with a short human-readable reason
+pub fn classify(user_message: &str) -> (Complexity, String) {
+    let msg_lower = user_message.to_lowercase();
+    let words = user_message.split_whitespace().count();
+    let questions = user_message.matches('?').count();
+    let markers: Vec<&str> = REASONING_MARKERS.iter().copied().filter(|m| msg_lower.contains(m)).collect();
+
+    match ResponseScale::from_message(user_message) {
+        ResponseScale::Brief => (Complexity::Simple, "short or small talk".to_string()),
+        ResponseScale::Full => (Complexity::Hard, format!("long message ({} words)", words)),
+        ResponseScale::Standard if markers.len() >= 2 || (!markers.is_empty() && questions >= 2) => {
+            (Complexity::Hard, format!("reasoning asked for ({})", markers.join(", ")))
+        }
+        ResponseScale::Standard if words < 15 && questions <= 1 && markers.is_empty() => {
+            (Complexity::Simple, format!("short message ({} words)", words))
+        }
+        ResponseScale::Standard => (Complexity::Moderate, format!("{} words, {} questions", words, questions)),
+    }
+}
+
+pub fn agent_policy(agent: &str) -> String {
+    match db::get_setting(&format!("{}{}", AGENT_MODEL_POLICY_PREFIX, agent)).ok().flatten().as_deref() {
+        Some(TIER_ECONOMY) => TIER_ECONOMY.to_string(),
+        Some(TIER_PREMIUM) => TIER_PREMIUM.to_string(),
+        _ => POLICY_ADAPTIVE.to_string(),
+    }
+}
+
+pub fn get_policies() -> Vec<AgentModelPolicy> {
+    AGENTS.iter()
+        .map(|agent| AgentModelPolicy { agent: agent.to_string(), policy: agent_policy(agent) })
+        .collect()
+}
+
+pub fn set_agent_policy(agent: &str, policy: &str) -> Result<Vec<AgentModelPolicy>, String> {
+    if !AGENTS.contains(&agent) {
+        return Err(format!("Unknown agent: {}", agent));
+    }
+    if ![POLICY_ADAPTIVE, TIER_ECONOMY, TIER_PREMIUM].contains(&policy) {
+        return Err(format!("Unknown model policy: {}", policy));
+    }
+    db::set_setting(&format!("{}{}", AGENT_MODEL_POLICY_PREFIX, agent), policy).map_err(|e| e.to_string())?;
+    Ok(get_policies())
+}
+
+pub fn validate_tier(tier: &str) -> Result<(), String> {
+    if tier == TIER_ECONOMY || tier == TIER_PREMIUM {
+        Ok(())
+    } else {
+        Err(format!("Unknown model tier: {}", tier))
+    }
+}
+
+fn model_for(tier: &'static str) -> ModelChoice {
+    let model = if tier == TIER_ECONOMY { OPENAI_MODEL_MINI } else { OPENAI_MODEL };
+    ModelChoice { model, tier }
+}
+
+/// Pick the model for an agent's turn and record the decision
+pub fn choose(agent: &str, user_message: &str, conversation_id: Option<&str>) -> ModelChoice {
+    let (complexity, signal) = classify(user_message);
+    let override_tier = conversation_id.and_then(|id| db::get_conversation_model_tier(id).ok().flatten());
+    let policy = agent_policy(agent);
+
+    let (choice, reason) = match override_tier.as_deref() {
+        Some(TIER_ECONOMY) => (model_for(TIER_ECONOMY), "conversation override".to_string()),
+        Some(TIER_PREMIUM) => (model_for(TIER_PREMIUM), "conversation override".to_string()),
+        _ => match policy.as_str() {
+            TIER_ECONOMY => (model_for(TIER_ECONOMY), "agent policy: economy".to_string()),
+            TIER_PREMIUM => (model_for(TIER_PREMIUM), "agent policy: premium".to_string()),
+            _ if complexity == Complexity::Simple => (model_for(TIER_ECONOMY), format!("adaptive: {}", signal)),
+            _ => (model_for(TIER_PREMIUM), format!("adaptive: {}", signal)),
+        },
+    };
+
+    let decision = ModelDecision {
+        id: 0,
+        conversation_id: conversation_id.map(|s| s.to_string()),
+        agent: agent.to_string(),
+        complexity: complexity.as_str().to_string(),
+        tier: choice.tier.to_string(),
+        model: choice.model.to_string(),
+        reason,
+        created_at: Utc::now().to_rfc3339(),
+    };
+    if let Err(e) = db::save_model_decision(&decision) {
+        logging::log_error(conversation_id, &format!("Failed to record model decision: {}", e));
+    }
+    logging::log_routing(conversation_id, &format!(
+        "[MODEL] {} -> {} ({}, {})", agent, decision.model, decision.complexity, decision.reason
+    ));
+
+    choice
+}
@@ -0,0 +1,224 @@
+//! Notes folder watcher
+//!
+//! Optionally points at a user-chosen directory of Markdown / text notes (an Apple
+//! Notes export, an Obsidian vault, a plain journal folder) and keeps the document
+//! store in step with it. A filesystem watcher only records which files changed;
+//! the background job loop ingests them on its next tick, so a burst of saves
+//! becomes one pass. The first pass after choosing the folder (and any pass without
+//! a working watcher) rescans the whole folder and drops documents whose files are gone.
+//! The context builder then hands the agents passages from the notes that match
+//! what the user is saying, within the conversation's memory collections.
+
+use crate::db::{self, DOCUMENT_SOURCE_NOTES};
+use crate::logging;
+use chrono::{DateTime, Utc};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use ts_rs::TS;
+
+pub const NOTES_FOLDER_KEY: &str = "notes_folder";
+
+const NOTE_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+/// Larger files are skipped (they're not notes)
+const MAX_NOTE_BYTES: u64 = 1_000_000;
+/// How deep a full scan descends into subfolders
+const MAX_SCAN_DEPTH: usize = 8;
+
+static WATCHER: Lazy<Mutex<Option<RecommendedWatcher>>> = Lazy::new(|| Mutex::new(None));
+static PENDING: Lazy<Mutex<HashSet<PathBuf>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+static NEEDS_FULL_SCAN: AtomicBool = AtomicBool::new(true);
+
+#[derive(Debug, Serialize, Clone, TS)]
+#[ts(export)]
+pub struct NotesStatus {
+    pub folder: Option<String>,
+    pub watching: bool,
+    pub document_count: usize,
+}
+
+#[derive(Debug, Serialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct NotesSyncReport {
+    pub ingested: usize,
+    pub removed: usize,
+}
+
+pub fn notes_folder() -> Option<PathBuf> {
+    db::get_setting(NOTES_FOLDER_KEY)
+        .ok()
+        .flatten()
+        .filter(|p| !p.trim().is_empty())
+        .map(PathBuf::from)
+}
+
+pub fn status() -> NotesStatus {
+    NotesStatus {
+        folder: notes_folder().map(|p| p.to_string_lossy().to_string()),
+        watching: WATCHER.lock().map(|w| w.is_some()).unwrap_or(false),
+        document_count: db::get_document_paths(DOCUMENT_SOURCE_NOTES).map(|p| p.len()).unwrap_or(0),
+    }
+}
+
+/// Choose (or with `None`, stop using) the notes folder. The next sync rescans it.
+pub fn set_notes_folder(path: Option<String>) -> Result<NotesStatus, String> {
+    let folder = path.filter(|p| !p.trim().is_empty());
+    if let Some(folder) = &folder {
+        if !Path::new(folder).is_dir() {
+            return Err(format!("{} is not a folder", folder));
+        }
+    }
+    db::set_setting(NOTES_FOLDER_KEY, folder.as_deref().unwrap_or("")).map_err(|e| e.to_string())?;
+    NEEDS_FULL_SCAN.store(true, Ordering::SeqCst);
+    start_watching();
+    Ok(status())
+}
+
+/// Drop the watcher; pending changes are left for the next sync
+pub fn stop_watching() {
+    if let Ok(mut watcher) = WATCHER.lock() {
+        *watcher = None;
+    }
+}
+
+/// (Re)start the watcher on the configured folder. Without a folder, or if the
+/// platform watcher can't start, syncs fall back to full rescans.
+pub fn start_watching() {
+    let Ok(mut watcher) = WATCHER.lock() else {
+        return;
+    };
+    *watcher = None;
+    let Some(folder) = notes_folder() else {
+        return;
+    };
+
+    let started = notify::recommended_watcher(|event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if let Ok(mut pending) = PENDING.lock() {
+                pending.extend(event.paths.into_iter().filter(|p| is_note(p)));
+            }
+        }
+    })
+    .and_then(|mut w| w.watch(&folder, RecursiveMode::Recursive).map(|_| w));
+
+    match started {
+        Ok(w) => {
+            *watcher = Some(w);
+            logging::log_conversation(None, &format!("Watching notes folder {}", folder.display()));
+        }
+        Err(e) => logging::log_error(None, &format!("Couldn't watch notes folder {}: {}", folder.display(), e)),
+    }
+}
+
+fn is_note(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| NOTE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+fn collect_notes(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = path.file_name().and_then(|n| n.to_str()).map(|n| n.starts_with('.')).unwrap_or(false);
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            if depth < MAX_SCAN_DEPTH {
+                collect_notes(&path, depth + 1, found);
+            }
+        } else if is_note(&path) {
+            found.push(path);
+        }
+    }
+}
+
+/// Title from the first Markdown heading, else the file name
+fn note_title(path: &Path, content: &str) -> String {
+    content.lines()
+        .map(str::trim)
+        .find(|l| l.starts_with('#'))
+        .map(|l| l.trim_start_matches('#').trim().to_string())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default())
+}
+
+/// Ingest one file; returns whether the store changed
+fn ingest(path: &Path) -> Result<bool, String> {
+    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+    if metadata.len() > MAX_NOTE_BYTES {
+        return Ok(false);
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    if content.trim().is_empty() {
+        return Ok(false);
+    }
+    let modified = metadata.modified().ok().map(|t| DateTime::<Utc>::from(t).to_rfc3339());
+    db::upsert_document(
+        DOCUMENT_SOURCE_NOTES,
+        &path.to_string_lossy(),
+        &note_title(path, &content),
+        &content,
+        modified.as_deref(),
+    ).map_err(|e| e.to_string())
+}
+
+/// Bring the document store in line with the notes folder: changed files only
+/// when the watcher is running, otherwise a full rescan
+pub fn sync() -> Result<NotesSyncReport, String> {
+    let mut report = NotesSyncReport::default();
+    let Some(folder) = notes_folder() else {
+        return Ok(report);
+    };
+
+    let watching = WATCHER.lock().map(|w| w.is_some()).unwrap_or(false);
+    let full_scan = NEEDS_FULL_SCAN.swap(false, Ordering::SeqCst) || !watching;
+    let changed: Vec<PathBuf> = {
+        let mut pending = PENDING.lock().map_err(|e| e.to_string())?;
+        pending.drain().collect()
+    };
+
+    let paths = if full_scan {
+        let mut found = Vec::new();
+        collect_notes(&folder, 0, &mut found);
+        let keep: HashSet<String> = found.iter().map(|p| p.to_string_lossy().to_string()).collect();
+        for stale in db::get_document_paths(DOCUMENT_SOURCE_NOTES).map_err(|e| e.to_string())? {
+            if !keep.contains(&stale) && db::delete_document_by_path(&stale).map_err(|e| e.to_string())? {
+                report.removed += 1;
+            }
+        }
+        found
+    } else {
+        changed.into_iter().filter(|p| p.starts_with(&folder)).collect()
+    };
+
+    for path in paths {
+        if !path.exists() {
+            if db::delete_document_by_path(&path.to_string_lossy()).map_err(|e| e.to_string())? {
+                report.removed += 1;
+            }
+            continue;
+        }
+        match ingest(&path) {
+            Ok(true) => report.ingested += 1,
+            Ok(false) => {}
+            Err(e) => logging::log_error(None, &format!("Couldn't ingest note {}: {}", path.display(), e)),
+        }
+    }
+
+    if report.ingested > 0 || report.removed > 0 {
+        logging::log_conversation(None, &format!(
+            "Notes folder sync: {} ingested, {} removed", report.ingested, report.removed
+        ));
+    }
+    Ok(report)
+}
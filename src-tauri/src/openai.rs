@@ -1,10 +1,13 @@
+use crate::usage::MessageCost;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
 const REQUEST_TIMEOUT_SECS: u64 = 60; // 60 second timeout for API requests
+pub const OPENAI_MODEL: &str = "gpt-4o";
+pub const OPENAI_MODEL_MINI: &str = "gpt-4o-mini";
 
 #[derive(Debug, Serialize, Clone)]
 pub struct ChatMessage {
@@ -23,6 +26,14 @@ struct ChatCompletionRequest {
 #[derive(Debug, Deserialize)]
 struct ChatCompletionResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Usage {
+    prompt_tokens: i64,
+    completion_tokens: i64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -35,9 +46,13 @@ struct ResponseMessage {
     content: String,
 }
 
+#[derive(Clone)]
 pub struct OpenAIClient {
     client: Client,
     api_key: String,
+    url: String,
+    model: String,
+    provider: &'static str,
 }
 
 impl OpenAIClient {
@@ -51,41 +66,82 @@ impl OpenAIClient {
         Self {
             client,
             api_key: api_key.to_string(),
+            url: OPENAI_API_URL.to_string(),
+            model: OPENAI_MODEL.to_string(),
+            provider: "openai",
         }
     }
     
+    /// Client for a local OpenAI-compatible server (Ollama, LM Studio, llama.cpp).
+    /// `url` is the full chat completions endpoint; no API key is sent.
+    pub fn local(url: &str, model: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            model: model.to_string(),
+            provider: "local",
+            ..Self::new("")
+        }
+    }
+    
+    /// Use a different model on the same endpoint
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = model.to_string();
+        self
+    }
+    
+    /// `agent` attributes the call in the usage log; the call's tokens and
+    /// estimated cost come back alongside the reply
     pub async fn chat_completion(
         &self,
         messages: Vec<ChatMessage>,
         temperature: f32,
         max_tokens: Option<u32>,
-    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        agent: Option<&str>,
+    ) -> Result<(String, Option<MessageCost>), Box<dyn Error + Send + Sync>> {
         let request = ChatCompletionRequest {
-            model: "gpt-4o".to_string(),
+            model: self.model.clone(),
             messages,
             temperature,
             max_tokens: max_tokens.or(Some(2048)),
         };
         
-        let response = self.client
-            .post(OPENAI_API_URL)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
+        if self.provider != "local" {
+            crate::budgets::check_monthly_cap()?;
+        }
+        
+        let started = Instant::now();
+        let mut builder = self.client
+            .post(&self.url)
+            .header("Content-Type", "application/json");
+        if !self.api_key.is_empty() {
+            builder = builder.header("Authorization", format!("Bearer {}", self.api_key));
+        }
+        let response = builder.json(&request).send().await?;
+        crate::latency::observe(self.provider, started.elapsed().as_millis() as i64);
         
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await?;
-            return Err(format!("OpenAI API error ({}): {}", status, error_text).into());
+            return Err(format!("{} API error ({}): {}", self.provider, status, error_text).into());
         }
         
         let completion: ChatCompletionResponse = response.json().await?;
         
+        let cost = completion.usage.as_ref().map(|usage| {
+            crate::usage::record(crate::usage::CallUsage {
+                provider: self.provider,
+                model: &self.model,
+                category: if agent.is_some() { "agent" } else { "general" },
+                agent,
+                input_tokens: usage.prompt_tokens,
+                output_tokens: usage.completion_tokens,
+                latency_ms: started.elapsed().as_millis() as i64,
+            })
+        });
+        
         completion.choices
             .first()
-            .map(|c| c.message.content.clone())
+            .map(|c| (c.message.content.clone(), cost))
             .ok_or_else(|| "No response from OpenAI".into())
     }
     
@@ -96,7 +152,7 @@ impl OpenAIClient {
         }];
         
         let request = ChatCompletionRequest {
-            model: "gpt-4o".to_string(),
+            model: OPENAI_MODEL.to_string(),
             messages,
             temperature: 0.0,
             max_tokens: Some(5),
@@ -1,12 +1,30 @@
 use crate::anthropic::{AnthropicClient, AnthropicMessage, ThinkingBudget, CLAUDE_HAIKU, CLAUDE_OPUS};
+use crate::attachments;
+use crate::boundaries;
+use crate::budgets::{self, BudgetRoute};
+use crate::citations;
+use crate::controls;
 use crate::db::{self, Message};
 use crate::disco_prompts::get_disco_prompt;
+use crate::filters::{self, FilterContext};
+use crate::i18n;
+use crate::intensity;
 use crate::knowledge::{INTERSECT_KNOWLEDGE, is_self_referential_query};
+use crate::late_night;
+use crate::latency;
 use crate::logging;
-use crate::memory::{GroundingLevel, UserProfileSummary, MemoryExtractor};
-use crate::openai::{ChatMessage, OpenAIClient};
+use crate::memory::{self, GroundingLevel, UserProfileSummary, MemoryExtractor};
+use crate::model_routing;
+use crate::openai::{ChatMessage, OpenAIClient, OPENAI_MODEL_MINI};
+use crate::readability;
+use crate::repetition;
+use crate::session_timer;
+use crate::usage::MessageCost;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
+use ts_rs::TS;
 
 // ============ Profile Context (Multi-Profile System) ============
 
@@ -149,16 +167,178 @@ pub struct OrchestratorDecision {
     pub secondary_type: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
 pub struct AgentResponse {
     pub agent: String,
     pub content: String,
     pub response_type: String,
     pub references_message_id: Option<String>,
+    pub cost: Option<MessageCost>,  // Tokens and estimated cost of generating this reply
+}
+
+/// A finished agent reply plus the memory facts it cited and what it cost
+#[derive(Debug, Clone)]
+pub struct AgentReply {
+    pub content: String,
+    pub cited_fact_ids: Vec<i64>,
+    pub cost: Option<MessageCost>,
+}
+
+/// Reshape an OpenAI-style message list for Claude: system messages become the
+/// system prompt, and the rest must alternate roles starting with the user
+fn to_anthropic_messages(messages: Vec<ChatMessage>) -> (Option<String>, Vec<AnthropicMessage>) {
+    let mut system: Vec<String> = Vec::new();
+    let mut out: Vec<AnthropicMessage> = Vec::new();
+    for message in messages {
+        if message.role == "system" {
+            system.push(message.content);
+            continue;
+        }
+        let role = if message.role == "assistant" { "assistant" } else { "user" };
+        match out.last_mut() {
+            Some(last) if last.role == role => {
+                last.content.push_str("\n\n");
+                last.content.push_str(&message.content);
+            }
+            None if role == "assistant" => {
+                out.push(AnthropicMessage { role: "user".to_string(), content: "(earlier in the conversation)".to_string() });
+                out.push(AnthropicMessage { role: role.to_string(), content: message.content });
+            }
+            _ => out.push(AnthropicMessage { role: role.to_string(), content: message.content }),
+        }
+    }
+    let system = (!system.is_empty()).then(|| system.join("\n\n"));
+    (system, out)
+}
+
+// ============ Response Length Adaptation ============
+
+/// How much room a reply gets, scaled to the size and complexity of the user's message
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResponseScale {
+    Brief,    // "good morning", "thanks" -- one line from one agent
+    Standard, // Typical message -- 1-3 sentences
+    Full,     // Long, multi-part dilemma -- room for a few short paragraphs
+}
+
+impl ResponseScale {
+    pub fn from_message(user_message: &str) -> Self {
+        let msg_lower = user_message.to_lowercase();
+        let word_count = user_message.split_whitespace().count();
+        let question_count = user_message.matches('?').count();
+        let paragraph_count = user_message
+            .split("\n\n")
+            .filter(|p| !p.trim().is_empty())
+            .count();
+        
+        // Small talk gets one line only when it's the whole message, give or take
+        // a greeting tail ("thanks so much", "morning guys", "hey Puff") -- "ok so my
+        // mom is dying" or "I got fired." are short but need a real answer
+        let small_talk = ["hi", "hey", "hello", "good morning", "good night", "morning", "gm",
+            "thanks", "thank you", "ok", "okay", "cool", "nice", "lol", "haha", "yep", "sure", "got it"];
+        let greeting_tail = ["there", "all", "guys", "everyone", "everybody", "folks", "team", "friends", "yall",
+            "you", "so", "much", "again", "a", "lot", "snap", "dot", "puff", "instinct", "logic", "psyche"];
+        let words: Vec<String> = msg_lower
+            .split_whitespace()
+            .map(|w| w.chars().filter(|c| c.is_alphanumeric()).collect::<String>())
+            .filter(|w| !w.is_empty())
+            .collect();
+        let is_small_talk = small_talk.iter().any(|p| {
+            let phrase: Vec<&str> = p.split(' ').collect();
+            words.len() >= phrase.len()
+                && words.iter().zip(&phrase).all(|(w, p)| w == p)
+                && words[phrase.len()..].iter().all(|w| greeting_tail.contains(&w.as_str()))
+        });
+        
+        if is_small_talk && question_count == 0 {
+            ResponseScale::Brief
+        } else if word_count > 120 || paragraph_count >= 3 || (word_count > 60 && question_count >= 2) {
+            ResponseScale::Full
+        } else {
+            ResponseScale::Standard
+        }
+    }
+    
+    pub fn max_tokens(&self) -> u32 {
+        match self {
+            ResponseScale::Brief => 80,
+            ResponseScale::Standard => 300,
+            ResponseScale::Full => 650,
+        }
+    }
+    
+    /// Sentence/character ceiling enforced by the length filter
+    pub fn max_sentences(&self) -> usize {
+        match self {
+            ResponseScale::Brief => 2,
+            ResponseScale::Standard => 6,
+            ResponseScale::Full => 16,
+        }
+    }
+    
+    pub fn max_chars(&self) -> usize {
+        match self {
+            ResponseScale::Brief => 240,
+            ResponseScale::Standard => 900,
+            ResponseScale::Full => 2400,
+        }
+    }
+    
+    pub fn directive(&self) -> &'static str {
+        match self {
+            ResponseScale::Brief => "LENGTH: They sent something short and casual. Reply in ONE short line. Match their energy.",
+            ResponseScale::Standard => "LENGTH: Keep it to 1-3 sentences.",
+            ResponseScale::Full => "LENGTH: They wrote a lot and it deserves real engagement. Up to three short paragraphs -- address the parts that matter, skip the rest.",
+        }
+    }
 }
 
 // ============ Heuristic Routing (No API calls - instant) ============
 
+/// Share of the last `window` agent replies held by each agent
+fn response_shares(conversation_history: &[Message], window: usize) -> Vec<(&'static str, f64)> {
+    let recent: Vec<&str> = conversation_history.iter()
+        .rev()
+        .map(|m| m.role.as_str())
+        .filter(|r| matches!(*r, "instinct" | "logic" | "psyche"))
+        .take(window)
+        .collect();
+    if recent.is_empty() {
+        return Vec::new();
+    }
+    ["instinct", "logic", "psyche"].iter()
+        .map(|a| (*a, recent.iter().filter(|r| *r == a).count() as f64 / recent.len() as f64))
+        .collect()
+}
+
+/// Ways of speaking to an agent by name. The nicknames double as ordinary
+/// words ("snap decision", "dot the i's"), so a name only counts in a vocative
+/// spot -- an opener with a comma or colon, after a greeting, an @mention, a trailing
+/// ", Puff?" -- or capitalized mid-sentence ("what would Dot say").
+static ADDRESSED_BY_NAME: Lazy<[Regex; 5]> = Lazy::new(|| {
+    let names = "snap|instinct|dot|logic|puff|psyche";
+    [
+        Regex::new(&format!(r"(?i)^(?:(?:hey|hi|ok|okay)\s+)?({})\s*[,:]", names)).unwrap(),
+        Regex::new(&format!(r"(?i)^(?:hey|hi|ok|okay)\s+({})\b", names)).unwrap(),
+        Regex::new(&format!(r"(?i)@({})\b", names)).unwrap(),
+        Regex::new(&format!(r"(?i),\s*({})\s*[.?!]*$", names)).unwrap(),
+        Regex::new(r"[^.!?\s]\s+(Snap|Dot|Puff)\b").unwrap(),
+    ]
+});
+
+/// Detect when the user speaks to one agent by name ("Snap, ...", "hey Puff", "@dot")
+fn directly_addressed_agent(user_message: &str) -> Option<&'static str> {
+    let trimmed = user_message.trim();
+    let name = ADDRESSED_BY_NAME.iter().find_map(|re| re.captures(trimmed))?.get(1)?.as_str().to_lowercase();
+    match name.as_str() {
+        "snap" | "instinct" => Some("instinct"),
+        "dot" | "logic" => Some("logic"),
+        "puff" | "psyche" => Some("psyche"),
+        _ => None,
+    }
+}
+
 /// Fast heuristic-based routing that replaces Claude-based routing for speed
 /// Uses weights, keyword matching, and silence detection
 pub fn decide_response_heuristic(
@@ -321,6 +501,34 @@ pub fn decide_response_heuristic(
         }
     }
     
+    // ===== TURN-TAKING BUDGET: Penalize agents dominating recent turns =====
+    // Directly addressed agents are exempt and get a decisive boost instead
+    let addressed = directly_addressed_agent(user_message)
+        .filter(|a| active_agents.iter().any(|x| x == a));
+    let shares = response_shares(conversation_history, 12);
+    let fair_share = 1.0 / active_agents.len().max(1) as f64;
+    for (agent, share) in &shares {
+        if Some(*agent) == addressed {
+            continue;
+        }
+        let excess = share - fair_share;
+        if excess > 0.1 {
+            if let Some(score) = scores.get_mut(agent) {
+                let penalty = excess * 0.8;
+                *score -= penalty;
+                logging::log_routing(None, &format!(
+                    "[HEURISTIC] {} has {:.0}% of recent turns, penalizing -{:.2}", agent, share * 100.0, penalty
+                ));
+            }
+        }
+    }
+    if let Some(agent) = addressed {
+        if let Some(score) = scores.get_mut(agent) {
+            *score += 1.0;
+            logging::log_routing(None, &format!("[HEURISTIC] {} directly addressed", agent));
+        }
+    }
+    
     // ===== SELECT PRIMARY AGENT =====
     let mut primary = "logic"; // Default
     let mut max_score = 0.0;
@@ -356,7 +564,12 @@ pub fn decide_response_heuristic(
     
     // ===== DECIDE SECONDARY =====
     // Add secondary in disco mode, or if there's a significantly different perspective
-    let add_secondary = if is_disco {
+    // Brief small talk ("good morning") gets exactly one voice, even in disco
+    let is_brief = ResponseScale::from_message(user_message) == ResponseScale::Brief;
+    let add_secondary = if is_brief {
+        logging::log_routing(None, "[HEURISTIC] Brief message - single agent reply");
+        false
+    } else if is_disco {
         true // Disco always adds secondary for more chaos
     } else if active_agents.len() >= 2 {
         // Add secondary if another agent has a close score (within 0.1)
@@ -499,16 +712,81 @@ pub fn decide_grounding_heuristic(
 pub struct Orchestrator {
     openai_client: OpenAIClient,      // For agent responses (GPT-4o)
     anthropic_client: AnthropicClient, // For orchestration decisions (Claude Opus 4.5)
+    agent_fallback: AnthropicClient,   // Agent replies while OpenAI is over its latency SLO (see latency.rs)
+    intensity: Option<f64>,            // Session disco intensity (see intensity.rs)
+    reading_level: Option<u8>,         // Plain-language mode (see readability.rs)
+    verbosity: Option<ResponseScale>,  // Reply length fixed by "/verbosity" (see controls.rs)
+    wrap_up: Option<(i64, i64)>,       // (elapsed, budget) minutes once a session runs long (see session_timer.rs)
+    late_night: bool,                  // Inside the profile's late-night window (see late_night.rs)
 }
 
 impl Orchestrator {
     pub fn new(openai_key: &str, anthropic_key: &str) -> Self {
         Self {
             openai_client: OpenAIClient::new(openai_key),
-            anthropic_client: AnthropicClient::new(anthropic_key),
+            anthropic_client: AnthropicClient::new(anthropic_key).with_usage_category("routing"),
+            agent_fallback: AnthropicClient::new(anthropic_key).with_usage_category("agent"),
+            intensity: None,
+            reading_level: None,
+            verbosity: None,
+            wrap_up: None,
+            late_night: false,
         }
     }
     
+    /// Set the session intensity applied to disco agents' prompts
+    pub fn with_intensity(mut self, intensity: Option<f64>) -> Self {
+        self.intensity = intensity;
+        self
+    }
+    
+    /// Set the plain-language reading level applied to agent replies
+    pub fn with_reading_level(mut self, reading_level: Option<u8>) -> Self {
+        self.reading_level = reading_level;
+        self
+    }
+    
+    /// Fix reply length instead of scaling it to each user message
+    pub fn with_verbosity(mut self, verbosity: Option<ResponseScale>) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+    
+    /// Ask agents to wrap up: the session has run past its time budget
+    pub fn with_wrap_up(mut self, wrap_up: Option<(i64, i64)>) -> Self {
+        self.wrap_up = wrap_up;
+        self
+    }
+    
+    /// Apply the late-night policy to prompts and filters
+    pub fn with_late_night(mut self, late_night: bool) -> Self {
+        self.late_night = late_night;
+        self
+    }
+    
+    /// A cheap stand-in reply for previews: the agent's base prompt on the mini model,
+    /// with no history, profile or memory, and nothing saved
+    pub async fn get_preview_reply(
+        &self,
+        agent: Agent,
+        user_message: &str,
+        response_type: ResponseType,
+        primary_response: Option<&str>,
+        primary_agent: Option<&str>,
+    ) -> Result<AgentReply, Box<dyn Error + Send + Sync>> {
+        let system_prompt = get_agent_system_prompt(agent, response_type, primary_response, primary_agent, false, false);
+        let scale = ResponseScale::from_message(user_message);
+        let messages = vec![
+            ChatMessage { role: "system".to_string(), content: format!("{}\n\n{}", system_prompt, scale.directive()) },
+            ChatMessage { role: "user".to_string(), content: user_message.to_string() },
+        ];
+        let (content, cost) = self.openai_client.clone()
+            .with_model(OPENAI_MODEL_MINI)
+            .chat_completion(messages, 0.7, Some(scale.max_tokens()), None)
+            .await?;
+        Ok(AgentReply { content, cited_fact_ids: Vec::new(), cost })
+    }
+    
     /// Generate Governor's internal thoughts/reasoning process
     pub async fn generate_governor_thoughts(
         &self,
@@ -1069,7 +1347,13 @@ Respond with ONLY valid JSON:
         user_profile: Option<&UserProfileSummary>,
         is_disco: bool,
         primary_is_disco: bool,
-    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+    ) -> Result<AgentReply, Box<dyn Error + Send + Sync>> {
+        // An agent past its daily token allowance either signs off or goes local
+        let route = budgets::route(agent.as_str());
+        if let BudgetRoute::Silent(line) = &route {
+            return Ok(AgentReply { content: line.clone(), cited_fact_ids: Vec::new(), cost: None });
+        }
+        
         // Use knowledge-aware prompt that injects self-knowledge when relevant
         let system_prompt = get_agent_system_prompt_with_knowledge(
             agent, 
@@ -1082,6 +1366,33 @@ Respond with ONLY valid JSON:
             is_disco,
             primary_is_disco,
         );
+        let scale = self.verbosity.unwrap_or_else(|| ResponseScale::from_message(user_message));
+        // Past the session budget, nothing gets the long form
+        let scale = match (scale, self.wrap_up) {
+            (ResponseScale::Full, Some(_)) => ResponseScale::Standard,
+            (scale, _) => scale,
+        };
+        let wrap_up_directive = self.wrap_up
+            .map(|(elapsed, budget)| session_timer::directive(elapsed, budget))
+            .unwrap_or_default();
+        let late_night_directive = if self.late_night { late_night::directive(agent.as_str()) } else { String::new() };
+        let intensity_directive = match self.intensity {
+            Some(level) if is_disco => intensity::directive(level),
+            _ => String::new(),
+        };
+        let reading_directive = self.reading_level.map(readability::directive).unwrap_or_default();
+        let system_prompt = format!(
+            "{}\n\n{}{}{}{}{}{}{}{}",
+            system_prompt,
+            scale.directive(),
+            intensity_directive,
+            reading_directive,
+            wrap_up_directive,
+            late_night_directive,
+            boundaries::prompt_constraints(agent.as_str()),
+            boundaries::house_rules_section(),
+            repetition::prompt_section(agent.as_str())
+        );
         
         // Build conversation context
         let mut messages: Vec<ChatMessage> = vec![
@@ -1091,16 +1402,26 @@ Respond with ONLY valid JSON:
             },
         ];
         
-        // Add recent conversation history (without meta tags that LLM might mimic)
-        for msg in conversation_history.iter().rev().take(15).rev() {
+        // Add recent conversation history (without meta tags that LLM might mimic),
+        // with the text of any files the user attached. Slash-command confirmations are left out.
+        let recent_history = &conversation_history[conversation_history.len().saturating_sub(15)..];
+        let attached = attachments::context_for(recent_history);
+        for msg in recent_history {
+            if msg.response_type.as_deref() == Some(controls::CONTROL_RESPONSE_TYPE) {
+                continue;
+            }
             let role = if msg.role == "user" {
                 "user".to_string()
             } else {
                 "assistant".to_string()
             };
+            let content = match attached.get(&msg.id) {
+                Some(files) => format!("{}{}", msg.content, files),
+                None => msg.content.clone(),
+            };
             messages.push(ChatMessage {
                 role,
-                content: msg.content.clone(),
+                content,
             });
         }
         
@@ -1134,9 +1455,58 @@ Respond with ONLY valid JSON:
             Agent::Psyche => 0.6,    // Balanced, introspective
         };
         
-        // Use OpenAI client for agent responses (GPT-4o)
-        // Max 300 tokens - enough for a substantive response but prevents rambling
-        self.openai_client.chat_completion(messages, temperature, Some(300)).await
+        // Use OpenAI client for agent responses (GPT-4o, or GPT-4o mini for simple
+        // turns -- see model_routing), Claude Haiku while OpenAI is too slow to
+        // respond (see latency), or the local model once the agent has spent its
+        // daily allowance (see budgets)
+        // Token budget scales with the user's message (see ResponseScale)
+        let conversation_id = conversation_history.first().map(|m| m.conversation_id.as_str());
+        let (response, cost) = match route {
+            BudgetRoute::Local(local) => local
+                .chat_completion(messages, temperature, Some(scale.max_tokens()), Some(agent.as_str()))
+                .await?,
+            _ if latency::prefer_fallback() => {
+                let (system, anthropic_messages) = to_anthropic_messages(messages);
+                self.agent_fallback.clone()
+                    .with_usage_agent(agent.as_str())
+                    .chat_completion_advanced_with_cost(
+                        CLAUDE_HAIKU,
+                        system.as_deref(),
+                        anthropic_messages,
+                        temperature,
+                        Some(scale.max_tokens()),
+                        ThinkingBudget::None,
+                    )
+                    .await?
+            }
+            _ => {
+                let choice = model_routing::choose(agent.as_str(), user_message, conversation_id);
+                self.openai_client.clone()
+                    .with_model(choice.model)
+                    .chat_completion(messages, temperature, Some(scale.max_tokens()), Some(agent.as_str()))
+                    .await?
+            }
+        };
+        
+        // Pull out memory citations before anything else looks at the text
+        let offered = user_profile.map(citations::fact_ids).unwrap_or_default();
+        let (response, cited_fact_ids) = citations::extract(&response, &offered);
+        
+        // Post-generation filters (formatting, profanity, boundaries, repeats, length)
+        let filter_ctx = FilterContext {
+            conversation_id,
+            agent: agent.as_str(),
+            is_disco,
+            scale,
+            history: conversation_history,
+            reading_level: self.reading_level,
+            late_night: self.late_night,
+        };
+        Ok(AgentReply {
+            content: filters::run_pipeline(&filter_ctx, response),
+            cited_fact_ids,
+            cost,
+        })
     }
 }
 
@@ -1144,13 +1514,10 @@ Respond with ONLY valid JSON:
 /// primary_is_disco: whether the agent being responded to was in disco mode (for push-back)
 fn get_agent_system_prompt(agent: Agent, response_type: ResponseType, primary_response: Option<&str>, primary_agent: Option<&str>, is_disco: bool, primary_is_disco: bool) -> String {
     // Use disco mode prompts if enabled, otherwise use standard prompts
+    let disco_prompt = if is_disco { get_disco_prompt(agent.as_str()) } else { None };
     let base_prompt = if is_disco {
         // Disco mode - use the extreme, opinionated Disco Elysium-inspired prompts
-        match agent {
-            Agent::Instinct => get_disco_prompt("instinct").unwrap_or(""),
-            Agent::Logic => get_disco_prompt("logic").unwrap_or(""),
-            Agent::Psyche => get_disco_prompt("psyche").unwrap_or(""),
-        }
+        disco_prompt.as_deref().unwrap_or("")
     } else {
         // Standard mode - genuinely helpful, practical assistance
         match agent {
@@ -1253,7 +1620,7 @@ WHAT YOU'RE NOT: You're not vague or mystical. You don't ask weird rhetorical qu
         ""
     };
     
-    format!("{}\n\n{}\n\nIMPORTANT: Never prefix your response with your name, labels, or tags like [INSTINCT]: or similar. Just respond directly. Keep responses SHORT - typically 1-3 sentences, occasionally a short paragraph if truly needed. Don't ramble. Don't use emojis. Don't be sycophantic. Be genuine. When using dashes for pauses or asides, ALWAYS use double dashes with spaces: \" -- \" (not \" - \").{}", base_prompt, response_context, disco_suffix)
+    format!("{}\n\n{}\n\nIMPORTANT: Never prefix your response with your name, labels, or tags like [INSTINCT]: or similar. Just respond directly. Keep responses SHORT - typically 1-3 sentences, occasionally a short paragraph if truly needed. Don't ramble. Don't use emojis. Don't be sycophantic. Be genuine. When using dashes for pauses or asides, ALWAYS use double dashes with spaces: \" -- \" (not \" - \").{}{}", base_prompt, response_context, disco_suffix, i18n::reply_language_suffix())
 }
 
 /// Get the system prompt for an agent with grounding context and optional self-knowledge
@@ -1285,6 +1652,11 @@ fn get_agent_system_prompt_with_grounding(
                 GroundingLevel::Deep => format!("\n\n--- User Profile (Use Thoughtfully) ---\n{}\n---\nThis is a personal topic. Draw on what you know about this user to provide a grounded, relevant response.", grounding_context),
             };
             full_prompt = format!("{}{}", full_prompt, grounding_section);
+            
+            // Labelled facts were included -- ask the agent to cite what it uses
+            if grounding_context.contains("[F") {
+                full_prompt.push_str(citations::prompt_instruction());
+            }
         }
     }
     
@@ -1316,6 +1688,47 @@ fn get_agent_system_prompt_with_knowledge(
         full_prompt = format!("{}\n\n--- Profile Context ---\n{}\n---", full_prompt, profile_info);
     }
     
+    // Messages the user bookmarked, when they've chosen to share them
+    if let Some(profile) = user_profile.filter(|p| !p.flagged_important.is_empty()) {
+        full_prompt = format!(
+            "{}\n\n--- User-Flagged Important ---\n{}\n---\nThe user bookmarked these lines as important. Keep them in mind; bring one up only when it bears on what they're saying now.",
+            full_prompt,
+            profile.flagged_important.iter().map(|f| format!("- {}", f)).collect::<Vec<_>>().join("\n")
+        );
+    }
+    
+    // Carry open action items from earlier sessions
+    if let Some(profile) = user_profile {
+        let action_items = memory::format_action_items_for_prompt(profile);
+        if !action_items.is_empty() {
+            full_prompt = format!(
+                "{}\n\n--- Open Action Items (from earlier sessions) ---\n{}\n---\nNext steps still open from earlier sessions. Check in on them only when it fits the conversation.",
+                full_prompt, action_items
+            );
+        }
+        if !profile.related_conversations.is_empty() {
+            full_prompt = format!(
+                "{}\n\n--- Earlier Conversations on This ---\n{}\n---\nThe user has talked about this before. Draw on it if it helps; don't recap it. When a line names a chapter of their life, place it there (\"back during the layoff spring you said...\").",
+                full_prompt,
+                profile.related_conversations.iter().map(|c| format!("- {}", c)).collect::<Vec<_>>().join("\n")
+            );
+        }
+        if !profile.related_notes.is_empty() {
+            full_prompt = format!(
+                "{}\n\n--- From the User's Notes ---\n{}\n---\nPassages from the user's own notes on this. Draw on them the way you'd draw on something they told you; don't quote them back at length.",
+                full_prompt,
+                profile.related_notes.iter().map(|n| format!("- {}", n)).collect::<Vec<_>>().join("\n")
+            );
+        }
+        if let Some(advice) = profile.own_advice.get(agent.as_str()).filter(|a| !a.is_empty()) {
+            full_prompt = format!(
+                "{}\n\n--- What You've Said Before on This ---\n{}\n---\nYour own replies from earlier conversations. Stay consistent with them, or if you see it differently now, say so and why instead of quietly contradicting yourself. When a line names a chapter of the user's life, place it there (\"back during the layoff spring I told you...\").",
+                full_prompt,
+                advice.iter().map(|a| format!("- {}", a)).collect::<Vec<_>>().join("\n")
+            );
+        }
+    }
+    
     // Inject pattern challenge section for disco mode
     if is_disco {
         if let Some(profile) = user_profile {
@@ -1509,7 +1922,7 @@ pub struct EngagementAnalyzer {
 impl EngagementAnalyzer {
     pub fn new(anthropic_key: &str) -> Self {
         Self {
-            client: AnthropicClient::new(anthropic_key),
+            client: AnthropicClient::new(anthropic_key).with_usage_category("engagement"),
         }
     }
     
@@ -1624,7 +2037,7 @@ pub struct IntrinsicTraitAnalyzer {
 impl IntrinsicTraitAnalyzer {
     pub fn new(anthropic_key: &str) -> Self {
         Self {
-            client: AnthropicClient::new(anthropic_key),
+            client: AnthropicClient::new(anthropic_key).with_usage_category("traits"),
         }
     }
     
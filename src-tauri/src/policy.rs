@@ -0,0 +1,109 @@
+//! Quiet hours and do-not-disturb
+//!
+//! One place that answers "may the app interrupt the user right now?". Consulted
+//! by the scheduler before delivering proactive messages and anything else that
+//! would notify. Explicitly user-requested reminders carry an override flag and
+//! go through regardless.
+
+use crate::db;
+use chrono::{DateTime, Local, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+pub const QUIET_ENABLED_KEY: &str = "quiet_hours_enabled";
+pub const QUIET_START_KEY: &str = "quiet_hours_start";
+pub const QUIET_END_KEY: &str = "quiet_hours_end";
+pub const DND_UNTIL_KEY: &str = "dnd_until";        // RFC3339, or "indefinite"
+
+const DEFAULT_QUIET_START: &str = "22:00";
+const DEFAULT_QUIET_END: &str = "08:00";
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct PolicyState {
+    pub quiet_hours_enabled: bool,
+    pub quiet_hours_start: String,  // Local "HH:MM"
+    pub quiet_hours_end: String,    // Local "HH:MM", may wrap past midnight
+    pub dnd_until: Option<String>,  // RFC3339 or "indefinite"
+    pub is_quiet_now: bool,
+}
+
+fn setting(key: &str) -> Option<String> {
+    db::get_setting(key).ok().flatten()
+}
+
+pub fn parse_time(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M").ok()
+}
+
+/// Whether `now` falls in [start, end); a window may wrap past midnight
+pub fn in_window(now: NaiveTime, start: NaiveTime, end: NaiveTime) -> bool {
+    if start <= end {
+        now >= start && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+fn in_quiet_hours(now: NaiveTime) -> bool {
+    if setting(QUIET_ENABLED_KEY).as_deref() != Some("true") {
+        return false;
+    }
+    let start = setting(QUIET_START_KEY).and_then(|s| parse_time(&s))
+        .or_else(|| parse_time(DEFAULT_QUIET_START));
+    let end = setting(QUIET_END_KEY).and_then(|s| parse_time(&s))
+        .or_else(|| parse_time(DEFAULT_QUIET_END));
+    match (start, end) {
+        (Some(s), Some(e)) => in_window(now, s, e),
+        _ => false,
+    }
+}
+
+fn dnd_active(now: DateTime<Utc>) -> bool {
+    match setting(DND_UNTIL_KEY).as_deref() {
+        None | Some("") => false,
+        Some("indefinite") => true,
+        Some(until) => DateTime::parse_from_rfc3339(until)
+            .map(|t| now < t.with_timezone(&Utc))
+            .unwrap_or(false),
+    }
+}
+
+/// Whether quiet hours or DND are in effect right now
+pub fn is_quiet_now() -> bool {
+    dnd_active(Utc::now()) || in_quiet_hours(Local::now().time())
+}
+
+/// Whether something may interrupt the user now. User-requested reminders always may.
+pub fn may_interrupt(user_requested: bool) -> bool {
+    user_requested || !is_quiet_now()
+}
+
+pub fn get_state() -> PolicyState {
+    PolicyState {
+        quiet_hours_enabled: setting(QUIET_ENABLED_KEY).as_deref() == Some("true"),
+        quiet_hours_start: setting(QUIET_START_KEY).unwrap_or_else(|| DEFAULT_QUIET_START.to_string()),
+        quiet_hours_end: setting(QUIET_END_KEY).unwrap_or_else(|| DEFAULT_QUIET_END.to_string()),
+        dnd_until: setting(DND_UNTIL_KEY).filter(|v| !v.is_empty()),
+        is_quiet_now: is_quiet_now(),
+    }
+}
+
+pub fn set_quiet_hours(enabled: bool, start: &str, end: &str) -> Result<(), String> {
+    for t in [start, end] {
+        parse_time(t).ok_or_else(|| format!("Invalid time (expected HH:MM): {}", t))?;
+    }
+    db::set_setting(QUIET_ENABLED_KEY, if enabled { "true" } else { "false" }).map_err(|e| e.to_string())?;
+    db::set_setting(QUIET_START_KEY, start).map_err(|e| e.to_string())?;
+    db::set_setting(QUIET_END_KEY, end).map_err(|e| e.to_string())
+}
+
+/// Turn DND on (for `minutes`, or indefinitely when None) or off
+pub fn set_do_not_disturb(enabled: bool, minutes: Option<i64>) -> Result<(), String> {
+    let value = match (enabled, minutes) {
+        (false, _) => String::new(),
+        (true, None) => "indefinite".to_string(),
+        (true, Some(m)) => (Utc::now() + chrono::Duration::minutes(m.max(1))).to_rfc3339(),
+    };
+    db::set_setting(DND_UNTIL_KEY, &value).map_err(|e| e.to_string())
+}
@@ -0,0 +1,85 @@
+//! Power state detection for energy-aware background work
+//!
+//! Heavy background jobs (LLM summarization, consolidation, backfills) are
+//! deferred while running on battery or in low-power mode, unless the user has
+//! turned the override off in settings.
+
+use crate::db;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+pub const DEFER_ON_BATTERY_KEY: &str = "defer_jobs_on_battery";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct PowerState {
+    pub on_battery: bool,
+    pub low_power_mode: bool,
+    pub defer_on_battery: bool,     // User setting (default on)
+    pub deferring: bool,            // Whether heavy jobs are currently held back
+}
+
+#[cfg(target_os = "macos")]
+fn detect() -> (bool, bool) {
+    use std::process::Command;
+
+    let batt = Command::new("pmset").args(["-g", "batt"]).output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    let on_battery = batt.contains("'Battery Power'");
+
+    let settings = Command::new("pmset").arg("-g").output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    let low_power_mode = settings.lines().any(|l| {
+        let mut parts = l.split_whitespace();
+        parts.next() == Some("lowpowermode") && parts.next() == Some("1")
+    });
+
+    (on_battery, low_power_mode)
+}
+
+#[cfg(target_os = "linux")]
+fn detect() -> (bool, bool) {
+    // Any "Mains" supply reporting online means we're on AC
+    let mut has_mains = false;
+    let mut mains_online = false;
+    if let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let kind = std::fs::read_to_string(path.join("type")).unwrap_or_default();
+            if kind.trim() == "Mains" {
+                has_mains = true;
+                if std::fs::read_to_string(path.join("online")).unwrap_or_default().trim() == "1" {
+                    mains_online = true;
+                }
+            }
+        }
+    }
+    (has_mains && !mains_online, false)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn detect() -> (bool, bool) {
+    (false, false)
+}
+
+fn defer_on_battery() -> bool {
+    db::get_setting(DEFER_ON_BATTERY_KEY).ok().flatten().as_deref() != Some("false")
+}
+
+pub fn get_state() -> PowerState {
+    let (on_battery, low_power_mode) = detect();
+    let defer_on_battery = defer_on_battery();
+    PowerState {
+        on_battery,
+        low_power_mode,
+        defer_on_battery,
+        deferring: defer_on_battery && (on_battery || low_power_mode),
+    }
+}
+
+/// Whether heavy background work should wait for AC power
+pub fn should_defer_background_work() -> bool {
+    get_state().deferring
+}
@@ -0,0 +1,125 @@
+//! "What-if" weight preview
+//!
+//! Runs a sample message through routing with hypothetical weights so tuning
+//! the balance isn't blind: who would answer first, whether a second voice
+//! joins and how. Nothing is persisted -- the profile's weights, session
+//! weights and conversations are untouched. Replies come from the cheap model
+//! with no history or memory (or, in mock mode or without an OpenAI key, are
+//! the agents' sample voice lines), so they show the balance, not the real answer.
+
+use crate::db;
+use crate::orchestrator::{decide_response_heuristic, Agent, Orchestrator, ResponseType};
+use crate::usage::MessageCost;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+const AGENTS: [&str; 3] = ["instinct", "logic", "psyche"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct PreviewReply {
+    pub agent: String,
+    pub response_type: String,
+    pub content: String,
+    pub cost: Option<MessageCost>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct WeightPreview {
+    pub instinct_weight: f64,        // Normalized to sum to 1
+    pub logic_weight: f64,
+    pub psyche_weight: f64,
+    pub primary_agent: String,
+    pub secondary_agent: Option<String>,
+    pub secondary_type: Option<String>,
+    pub replies: Vec<PreviewReply>,
+    pub mock: bool,                  // Replies are sample lines, not generated
+}
+
+/// First sample voice line from the agent manifest
+fn sample_line(agent: &str) -> String {
+    db::get_agent(agent).ok().flatten()
+        .and_then(|a| serde_json::from_str::<Vec<String>>(&a.voice_samples).ok())
+        .and_then(|lines| lines.into_iter().next())
+        .unwrap_or_default()
+}
+
+/// Route `sample_message` with the given weights and sketch the replies
+pub async fn preview_weights(
+    instinct: f64,
+    logic: f64,
+    psyche: f64,
+    sample_message: &str,
+    mock: bool,
+) -> Result<WeightPreview, String> {
+    let sample_message = sample_message.trim();
+    if sample_message.is_empty() {
+        return Err("Enter a sample message to preview".to_string());
+    }
+    if [instinct, logic, psyche].iter().any(|w| !w.is_finite() || *w < 0.0) {
+        return Err("Weights can't be negative".to_string());
+    }
+    let total = instinct + logic + psyche;
+    if total <= 0.0 {
+        return Err("At least one weight must be above zero".to_string());
+    }
+    let weights = (instinct / total, logic / total, psyche / total);
+
+    let persona = db::get_active_persona_profile().map_err(|e| e.to_string())?;
+    let points = persona.as_ref().map(|p| (p.instinct_points, p.logic_points, p.psyche_points));
+    let dominant_trait = persona.as_ref().map(|p| p.dominant_trait.as_str());
+    let active_agents: Vec<String> = AGENTS.iter().map(|a| a.to_string()).collect();
+    let decision = decide_response_heuristic(sample_message, weights, &active_agents, &[], false, points, dominant_trait);
+
+    let secondary_type = decision.secondary_type.clone().filter(|_| decision.secondary_agent.is_some());
+    let mut slots = vec![(decision.primary_agent.clone(), ResponseType::Primary)];
+    if let Some(secondary) = &decision.secondary_agent {
+        let response_type = secondary_type.as_deref()
+            .and_then(ResponseType::from_str)
+            .unwrap_or(ResponseType::Addition);
+        slots.push((secondary.clone(), response_type));
+    }
+
+    let api_key = db::get_user_profile().ok().and_then(|p| p.api_key);
+    let orchestrator = match (&api_key, mock) {
+        (Some(key), false) => Some(Orchestrator::new(key, "")),
+        _ => None,
+    };
+
+    let mut replies: Vec<PreviewReply> = Vec::new();
+    for (agent_id, response_type) in slots {
+        let agent = Agent::from_str(&agent_id).ok_or_else(|| format!("Invalid agent: {}", agent_id))?;
+        let (content, cost) = match &orchestrator {
+            Some(orchestrator) => {
+                let primary = replies.first().map(|r| (r.content.clone(), r.agent.clone()));
+                let reply = orchestrator.get_preview_reply(
+                    agent,
+                    sample_message,
+                    response_type,
+                    primary.as_ref().map(|(content, _)| content.as_str()),
+                    primary.as_ref().map(|(_, agent)| agent.as_str()),
+                ).await.map_err(|e| e.to_string())?;
+                (reply.content, reply.cost)
+            }
+            None => (sample_line(&agent_id), None),
+        };
+        replies.push(PreviewReply {
+            agent: agent_id,
+            response_type: response_type.as_str().to_string(),
+            content,
+            cost,
+        });
+    }
+
+    Ok(WeightPreview {
+        instinct_weight: weights.0,
+        logic_weight: weights.1,
+        psyche_weight: weights.2,
+        primary_agent: decision.primary_agent,
+        secondary_agent: decision.secondary_agent,
+        secondary_type,
+        replies,
+        mock: orchestrator.is_none(),
+    })
+}
@@ -0,0 +1,133 @@
+//! Scheduled check-ins with read receipts
+//!
+//! Agents can schedule a check-in ("did you ship it yet?") for later. The job
+//! loop delivers due check-ins, and tracks whether the user saw or answered them.
+//! A new nudge on a topic that still has an unanswered one outstanding is folded
+//! into it instead of stacking up -- five missed nudges after a vacation become one.
+//! Only nudges delivered in the last week absorb repeats, so one that can never be
+//! answered (no conversation, or a locked one) doesn't silence its topic for good;
+//! reminders the user asked for are always delivered. Delivery waits out quiet
+//! hours / DND unless the user asked for the reminder.
+
+use crate::db::{self, Message, ProactiveMessage};
+use crate::events::{self, Event};
+use crate::logging;
+use crate::policy;
+use chrono::Utc;
+use serde::Serialize;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How long an unanswered nudge keeps absorbing repeats on its topic
+const FOLD_WINDOW_DAYS: i64 = 7;
+
+/// Payload for the "proactive-message" event
+#[derive(Debug, Serialize, Clone, TS)]
+pub struct ProactiveMessageEvent {
+    pub id: String,
+    pub conversation_id: Option<String>,
+    pub agent: String,
+    pub content: String,
+}
+
+pub fn schedule(
+    agent: &str,
+    content: &str,
+    topic_key: &str,
+    scheduled_for: &str,
+    conversation_id: Option<&str>,
+    user_requested: bool,
+) -> Result<ProactiveMessage, String> {
+    // Normalize to UTC so due-time comparisons against stored RFC3339 strings hold
+    let scheduled_for = chrono::DateTime::parse_from_rfc3339(scheduled_for)
+        .map_err(|e| format!("Invalid scheduled_for: {}", e))?
+        .with_timezone(&Utc)
+        .to_rfc3339();
+    let msg = ProactiveMessage {
+        id: Uuid::new_v4().to_string(),
+        conversation_id: conversation_id.map(|c| c.to_string()),
+        agent: agent.to_string(),
+        content: content.to_string(),
+        topic_key: topic_key.to_string(),
+        scheduled_for,
+        status: "pending".to_string(),
+        delivered_at: None,
+        seen_at: None,
+        responded_at: None,
+        suppressed_count: 0,
+        created_at: Utc::now().to_rfc3339(),
+        user_requested,
+    };
+    db::create_proactive_message(&msg).map_err(|e| e.to_string())?;
+    Ok(msg)
+}
+
+/// Deliver due check-ins, folding repeats into outstanding unanswered ones
+pub fn deliver_due(app_handle: &tauri::AppHandle) -> Result<usize, String> {
+    let now = Utc::now().to_rfc3339();
+    let fold_since = (Utc::now() - chrono::Duration::days(FOLD_WINDOW_DAYS)).to_rfc3339();
+    let due = db::get_due_proactive_messages(&now).map_err(|e| e.to_string())?;
+    let mut unanswered = db::get_unanswered_proactive_messages().map_err(|e| e.to_string())?;
+    let mut delivered = 0;
+
+    for msg in due {
+        // Quiet hours / DND: leave it pending and try again next tick
+        if !policy::may_interrupt(msg.user_requested) {
+            continue;
+        }
+        
+        let outstanding = unanswered.iter().find(|u| {
+            u.topic_key == msg.topic_key && u.delivered_at.as_deref().is_some_and(|at| at >= fold_since.as_str())
+        });
+        if let Some(outstanding) = outstanding.filter(|_| !msg.user_requested) {
+            if let Err(e) = db::suppress_proactive_message(&msg.id, &outstanding.id) {
+                logging::log_error(msg.conversation_id.as_deref(), &format!("[PROACTIVE] Failed to suppress {}: {}", msg.id, e));
+                continue;
+            }
+            logging::log_conversation(msg.conversation_id.as_deref(), &format!(
+                "[PROACTIVE] Suppressed repeat nudge on '{}' -- previous one still unanswered", msg.topic_key
+            ));
+            continue;
+        }
+
+        // A locked conversation stays as it was; the nudge still arrives as an event
+        let target = msg.conversation_id.as_ref()
+            .filter(|id| !db::is_conversation_locked(id).unwrap_or(false));
+        if let Some(conv_id) = target {
+            let chat_msg = Message {
+                id: Uuid::new_v4().to_string(),
+                conversation_id: conv_id.clone(),
+                role: msg.agent.clone(),
+                content: msg.content.clone(),
+                response_type: Some("check_in".to_string()),
+                references_message_id: None,
+                timestamp: Utc::now().to_rfc3339(),
+                ..Default::default()
+            };
+            // One bad row shouldn't hold up the rest; this one stays pending for the next tick
+            if let Err(e) = db::save_message(&chat_msg) {
+                logging::log_error(Some(conv_id), &format!("[PROACTIVE] Failed to save nudge {}: {}", msg.id, e));
+                continue;
+            }
+        }
+        if let Err(e) = db::mark_proactive_delivered(&msg.id) {
+            logging::log_error(msg.conversation_id.as_deref(), &format!("[PROACTIVE] Failed to mark {} delivered: {}", msg.id, e));
+            continue;
+        }
+
+        events::emit(app_handle, Event::ProactiveMessage(ProactiveMessageEvent {
+            id: msg.id.clone(),
+            conversation_id: msg.conversation_id.clone(),
+            agent: msg.agent.clone(),
+            content: msg.content.clone(),
+        }));
+
+        unanswered.push(ProactiveMessage { delivered_at: Some(now.clone()), ..msg });
+        delivered += 1;
+    }
+
+    if delivered > 0 {
+        logging::log_conversation(None, &format!("[PROACTIVE] Delivered {} check-ins", delivered));
+    }
+    Ok(delivered)
+}
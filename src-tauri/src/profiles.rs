@@ -0,0 +1,215 @@
+//! Local user profiles
+//!
+//! A profile is one person (or one side of a person -- work vs personal) using
+//! the app on this machine. Each profile has its own database file, so weights,
+//! memory, conversations and settings never mix, and its own keychain entries
+//! for the API keys. The original `intersect.db` and unsuffixed keychain entries
+//! belong to the "default" profile, so existing installs carry on unchanged.
+//!
+//! The profile list and the active profile are kept in `profiles.json` in the
+//! app data directory. Databases live there too unless a profile has been moved
+//! to a folder of its own (`db::migrate_data_dir`). Switching points the
+//! database pool at the other file; the caller then re-runs app initialization on it.
+
+use crate::archive;
+use crate::db;
+use crate::keychain;
+use crate::logging;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tauri::Manager;
+use ts_rs::TS;
+use uuid::Uuid;
+
+pub const DEFAULT_PROFILE_ID: &str = "default";
+const DEFAULT_PROFILE_NAME: &str = "Default";
+const REGISTRY_FILE: &str = "profiles.json";
+const MAX_NAME_CHARS: usize = 60;
+
+static CURRENT: Lazy<RwLock<String>> = Lazy::new(|| RwLock::new(DEFAULT_PROFILE_ID.to_string()));
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub db_dir: Option<String>, // Folder chosen for this profile's database; None = the app data directory
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct ProfileList {
+    pub current: String,
+    pub profiles: Vec<Profile>,
+}
+
+/// The app data directory, where the profile list and (by default) the databases live
+pub fn data_dir(app_handle: &tauri::AppHandle) -> PathBuf {
+    let app_data_dir = app_handle.path().app_data_dir().expect("Failed to get app data dir");
+    fs::create_dir_all(&app_data_dir).expect("Failed to create app data dir");
+    app_data_dir
+}
+
+/// Database file name for a profile
+pub fn db_file_name(profile_id: &str) -> String {
+    if profile_id == DEFAULT_PROFILE_ID {
+        "intersect.db".to_string()
+    } else {
+        format!("intersect-{}.db", profile_id)
+    }
+}
+
+/// Where a profile's database lives
+pub fn db_path(app_handle: &tauri::AppHandle, profile_id: &str) -> PathBuf {
+    let dir = list(app_handle).profiles.into_iter()
+        .find(|p| p.id == profile_id)
+        .and_then(|p| p.db_dir)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| data_dir(app_handle));
+    dir.join(db_file_name(profile_id))
+}
+
+/// Id of the active profile
+pub fn current_id() -> String {
+    CURRENT.read().unwrap().clone()
+}
+
+fn default_list() -> ProfileList {
+    ProfileList {
+        current: DEFAULT_PROFILE_ID.to_string(),
+        profiles: vec![Profile {
+            id: DEFAULT_PROFILE_ID.to_string(),
+            name: DEFAULT_PROFILE_NAME.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            db_dir: None,
+        }],
+    }
+}
+
+/// The profile list as stored. A missing file means only the default profile exists.
+pub fn list(app_handle: &tauri::AppHandle) -> ProfileList {
+    let path = data_dir(app_handle).join(REGISTRY_FILE);
+    let mut list = match fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str(&text).unwrap_or_else(|e| {
+            // Left on disk as it is; it's only rewritten by a profile change
+            logging::log_error(None, &format!("[PROFILES] Unreadable {}, using the default profile: {}", REGISTRY_FILE, e));
+            default_list()
+        }),
+        Err(_) => default_list(),
+    };
+    if list.profiles.is_empty() {
+        list = default_list();
+    }
+    if !list.profiles.iter().any(|p| p.id == list.current) {
+        list.current = list.profiles[0].id.clone();
+    }
+    list
+}
+
+fn save(app_handle: &tauri::AppHandle, list: &ProfileList) -> Result<(), String> {
+    let path = data_dir(app_handle).join(REGISTRY_FILE);
+    let tmp = path.with_extension("json.tmp");
+    let json = serde_json::to_string_pretty(list).map_err(|e| e.to_string())?;
+    fs::write(&tmp, json).map_err(|e| format!("Failed to save profiles: {}", e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("Failed to save profiles: {}", e))
+}
+
+/// Make the stored active profile current. Runs before the database is opened.
+pub fn load(app_handle: &tauri::AppHandle) {
+    *CURRENT.write().unwrap() = list(app_handle).current;
+}
+
+fn validate_name(list: &ProfileList, name: &str, except_id: Option<&str>) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name can't be empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_CHARS {
+        return Err(format!("Profile names are limited to {} characters", MAX_NAME_CHARS));
+    }
+    if list.profiles.iter().any(|p| Some(p.id.as_str()) != except_id && p.name.eq_ignore_ascii_case(name)) {
+        return Err(format!("There's already a profile called '{}'", name));
+    }
+    Ok(name.to_string())
+}
+
+/// Add a profile. Its database is created the first time it's switched to.
+pub fn create(app_handle: &tauri::AppHandle, name: &str) -> Result<Profile, String> {
+    let mut list = list(app_handle);
+    let profile = Profile {
+        id: Uuid::new_v4().to_string(),
+        name: validate_name(&list, name, None)?,
+        created_at: Utc::now().to_rfc3339(),
+        db_dir: None,
+    };
+    list.profiles.push(profile.clone());
+    save(app_handle, &list)?;
+    logging::log_conversation(None, &format!("[PROFILES] Created profile '{}'", profile.name));
+    Ok(profile)
+}
+
+pub fn rename(app_handle: &tauri::AppHandle, id: &str, name: &str) -> Result<Profile, String> {
+    let mut list = list(app_handle);
+    let name = validate_name(&list, name, Some(id))?;
+    let profile = list.profiles.iter_mut().find(|p| p.id == id).ok_or("Profile not found")?;
+    profile.name = name;
+    let profile = profile.clone();
+    save(app_handle, &list)?;
+    Ok(profile)
+}
+
+/// Remove a profile with its database and keychain entries. The active profile
+/// can't be deleted -- switch away from it first.
+pub fn delete(app_handle: &tauri::AppHandle, id: &str) -> Result<(), String> {
+    let mut list = list(app_handle);
+    if id == current_id() {
+        return Err("Can't delete the active profile; switch to another one first".to_string());
+    }
+    let db_path = db_path(app_handle, id);
+    let index = list.profiles.iter().position(|p| p.id == id).ok_or("Profile not found")?;
+    let profile = list.profiles.remove(index);
+    save(app_handle, &list)?;
+
+    for path in db::database_files(&db_path).iter().filter(|p| p.exists()) {
+        if let Err(e) = fs::remove_file(path) {
+            logging::log_error(None, &format!("[PROFILES] Failed to remove {}: {}", path.display(), e));
+        }
+    }
+    for account in keychain::ALL_KEYS {
+        if let Err(e) = keychain::delete_for_profile(id, account) {
+            logging::log_error(None, &format!("[PROFILES] {}", e));
+        }
+    }
+    logging::log_conversation(None, &format!("[PROFILES] Deleted profile '{}'", profile.name));
+    Ok(())
+}
+
+/// Record where a profile's database lives; returns the previous setting
+pub fn set_db_dir(app_handle: &tauri::AppHandle, id: &str, db_dir: Option<String>) -> Result<Option<String>, String> {
+    let mut list = list(app_handle);
+    let profile = list.profiles.iter_mut().find(|p| p.id == id).ok_or("Profile not found")?;
+    let previous = std::mem::replace(&mut profile.db_dir, db_dir);
+    save(app_handle, &list)?;
+    Ok(previous)
+}
+
+/// Make another profile active. The database pool still points at the old
+/// profile's file until it's reopened (`db::init_database`).
+pub fn switch(app_handle: &tauri::AppHandle, id: &str) -> Result<Profile, String> {
+    let mut list = list(app_handle);
+    let profile = list.profiles.iter().find(|p| p.id == id).cloned().ok_or("Profile not found")?;
+    if archive::is_analyzing() {
+        return Err("An archive import is being analyzed; pause it before switching profiles".to_string());
+    }
+    list.current = profile.id.clone();
+    save(app_handle, &list)?;
+    *CURRENT.write().unwrap() = profile.id.clone();
+    logging::log_conversation(None, &format!("[PROFILES] Switched to profile '{}'", profile.name));
+    Ok(profile)
+}
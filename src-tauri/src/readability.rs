@@ -0,0 +1,51 @@
+//! Plain-language mode for agent output
+//!
+//! Some users find the florid Disco voices (and long replies generally) hard to
+//! parse. A reading level -- a school grade, 3 to 12 -- can be set on a persona
+//! profile and overridden per conversation (0 turns it off for that
+//! conversation). When one applies, agents and the Governor get a directive to
+//! use common words and short sentences, and the filter pipeline splits any
+//! sentence still over the word budget.
+
+use crate::db;
+
+pub const MIN_LEVEL: i64 = 3;
+pub const MAX_LEVEL: i64 = 12;
+
+/// Effective level for a conversation: its override, else the active profile's
+pub fn resolve(conversation_id: &str) -> Option<u8> {
+    let level = match db::get_conversation_reading_level(conversation_id).ok().flatten() {
+        Some(level) => level,
+        None => db::get_active_persona_profile()
+            .ok()
+            .flatten()
+            .and_then(|p| db::get_profile_reading_level(&p.id).ok().flatten())?,
+    };
+    (level > 0).then(|| level.clamp(MIN_LEVEL, MAX_LEVEL) as u8)
+}
+
+pub fn validate(level: i64) -> Result<i64, String> {
+    if (MIN_LEVEL..=MAX_LEVEL).contains(&level) {
+        Ok(level)
+    } else {
+        Err(format!("Reading level must be between {} and {}", MIN_LEVEL, MAX_LEVEL))
+    }
+}
+
+/// Longest sentence, in words, allowed at a reading level
+pub fn max_sentence_words(level: u8) -> usize {
+    8 + level as usize
+}
+
+/// Prompt directive for a reading level
+pub fn directive(level: u8) -> String {
+    let imagery = if level <= 6 {
+        "No metaphors, idioms, or imagery -- say the thing directly."
+    } else {
+        "Use imagery sparingly, and only if the literal meaning is still obvious."
+    };
+    format!(
+        "\n\nPLAIN LANGUAGE (reading level: grade {}): Write so a grade-{} reader understands on the first read. Use common, everyday words. Keep every sentence under {} words. One idea per sentence. {} Keep your personality in what you say, not in how complicated you say it.",
+        level, level, max_sentence_words(level), imagery
+    )
+}
@@ -0,0 +1,127 @@
+//! In-conversation "/recall <topic>"
+//!
+//! Lets the user pull memory on purpose instead of hoping the retriever picks
+//! the right things. A `/recall` message (parsed in controls.rs) never reaches the agents:
+//! the topic is matched against remembered facts, patterns, past conversation
+//! summaries and earlier messages, and a condensed digest is saved into the
+//! conversation as a system message. It stays visible to the user, and the
+//! agents read it as part of the history from the next turn on.
+
+use crate::db::{self, Message};
+use crate::memory;
+use chrono::Utc;
+use uuid::Uuid;
+
+pub const RECALL_COMMAND: &str = "/recall";
+pub const RECALL_RESPONSE_TYPE: &str = "recall";
+
+const MAX_RECALLED_FACTS: usize = 6;
+const MAX_RECALLED_PATTERNS: usize = 3;
+const MAX_RECALLED_CONVERSATIONS: usize = 3;
+const MAX_RECALLED_SNIPPETS: usize = 3;
+/// How many recent summaries are searched
+const SUMMARY_SCAN_LIMIT: usize = 200;
+/// Topic words shorter than this are ignored when matching
+const MIN_WORD_CHARS: usize = 3;
+
+fn topic_words(topic: &str) -> Vec<String> {
+    topic.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= MIN_WORD_CHARS)
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// How many of the topic words appear in `text`
+fn matches(words: &[String], text: &str) -> usize {
+    let text = text.to_lowercase();
+    words.iter().filter(|w| text.contains(w.as_str())).count()
+}
+
+/// The condensed digest for a topic, as it appears in the conversation
+pub fn digest(conversation_id: &str, topic: &str) -> String {
+    let words = topic_words(topic);
+    if words.is_empty() {
+        return format!("Usage: {} <topic>, e.g. {} burnout", RECALL_COMMAND, RECALL_COMMAND);
+    }
+    let mut sections: Vec<String> = Vec::new();
+
+    let mut facts: Vec<(usize, db::UserFact)> = db::get_user_facts_in_scope(Some(conversation_id))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|f| (matches(&words, &format!("{} {} {}", f.category, f.key.replace('_', " "), f.value)), f))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    facts.sort_by(|a, b| {
+        b.0.cmp(&a.0).then(b.1.confidence.partial_cmp(&a.1.confidence).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    if !facts.is_empty() {
+        let lines: Vec<String> = facts.iter()
+            .take(MAX_RECALLED_FACTS)
+            .map(|(_, f)| format!("- {}: {}", f.key.replace('_', " "), f.value))
+            .collect();
+        sections.push(format!("What I know:\n{}", lines.join("\n")));
+    }
+
+    let patterns: Vec<String> = db::get_prompt_user_patterns()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|p| matches(&words, &p.description) > 0)
+        .take(MAX_RECALLED_PATTERNS)
+        .map(|p| format!("- {}", p.description))
+        .collect();
+    if !patterns.is_empty() {
+        sections.push(format!("Patterns:\n{}", patterns.join("\n")));
+    }
+
+    let conversations: Vec<String> = db::get_past_conversation_summaries(conversation_id, SUMMARY_SCAN_LIMIT)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|s| matches(&words, &format!("{} {}", s.key_topics, s.summary)) > 0)
+        .take(MAX_RECALLED_CONVERSATIONS)
+        .map(|s| {
+            let date = s.created_at.get(..10).unwrap_or(&s.created_at).to_string();
+            let title = db::get_conversation(&s.conversation_id).ok().flatten().and_then(|c| c.title);
+            match title {
+                Some(title) => format!("- {} ({}): {}", title, date, memory::summary_line(&s.summary)),
+                None => format!("- {}: {}", date, memory::summary_line(&s.summary)),
+            }
+        })
+        .collect();
+    if !conversations.is_empty() {
+        sections.push(format!("Past conversations:\n{}", conversations.join("\n")));
+    }
+
+    // Only the user's own words, and not from this conversation (it's already in view)
+    let snippets: Vec<String> = db::search_messages(&words.join(" "), 20)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|m| m.role == "user" && m.conversation_id != conversation_id)
+        .take(MAX_RECALLED_SNIPPETS)
+        .map(|m| format!("- \"{}\" ({})", m.snippet.replace(['[', ']'], ""), m.timestamp.get(..10).unwrap_or(&m.timestamp)))
+        .collect();
+    if !snippets.is_empty() {
+        sections.push(format!("You said:\n{}", snippets.join("\n")));
+    }
+
+    if sections.is_empty() {
+        format!("Nothing in memory about \"{}\" yet.", topic)
+    } else {
+        format!("Recall: {}\n\n{}", topic, sections.join("\n\n"))
+    }
+}
+
+/// Save the digest for a topic into the conversation
+pub fn handle(conversation_id: &str, topic: &str) -> Result<Message, String> {
+    let message = Message {
+        id: Uuid::new_v4().to_string(),
+        conversation_id: conversation_id.to_string(),
+        role: "system".to_string(),
+        content: digest(conversation_id, topic),
+        response_type: Some(RECALL_RESPONSE_TYPE.to_string()),
+        timestamp: Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+    db::save_message(&message).map_err(|e| e.to_string())?;
+    Ok(message)
+}
@@ -0,0 +1,113 @@
+//! Cross-session repetition detection for agent replies
+//!
+//! Agents drift into pet phrases ("comfort is a slow death") that repeat across
+//! conversations. This scans each agent's recent replies for word n-grams that
+//! show up in several distinct messages and feeds them back into the prompt as
+//! phrases to avoid. Pure Rust, no model tokens spent.
+
+use crate::db;
+use std::collections::{HashMap, HashSet};
+
+/// How many recent replies per agent to scan
+const LOOKBACK_MESSAGES: usize = 60;
+/// A phrase must appear in at least this many distinct replies to count as overused
+const MIN_OCCURRENCES: usize = 3;
+/// N-gram sizes to consider, longest first so longer phrases win
+const NGRAM_SIZES: [usize; 4] = [6, 5, 4, 3];
+/// Cap on phrases injected into the prompt
+const MAX_PHRASES: usize = 8;
+
+const STOPWORDS: &[&str] = &[
+    "a", "an", "the", "and", "or", "but", "if", "of", "to", "in", "on", "at", "for", "with",
+    "is", "are", "was", "were", "be", "been", "it", "its", "that", "this", "you", "your",
+    "i", "me", "my", "we", "they", "them", "he", "she", "not", "do", "does", "just", "so",
+    "what", "about", "as", "from", "by", "can", "have", "has",
+];
+
+#[derive(Debug, Clone)]
+pub struct OverusedPhrase {
+    pub phrase: String,
+    pub occurrences: usize,
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// N-grams need at least two content words to be worth flagging
+fn is_meaningful(ngram: &[String]) -> bool {
+    ngram.iter().filter(|w| !STOPWORDS.contains(&w.as_str())).count() >= 2
+}
+
+/// Find phrases repeated across distinct replies, longest first, without overlaps
+pub fn find_overused_phrases(replies: &[String]) -> Vec<OverusedPhrase> {
+    let tokenized: Vec<Vec<String>> = replies.iter().map(|r| tokenize(r)).collect();
+    let mut selected: Vec<OverusedPhrase> = Vec::new();
+
+    for n in NGRAM_SIZES {
+        // Count distinct replies each n-gram appears in
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for tokens in &tokenized {
+            let mut seen: HashSet<String> = HashSet::new();
+            for window in tokens.windows(n) {
+                if is_meaningful(window) {
+                    seen.insert(window.join(" "));
+                }
+            }
+            for gram in seen {
+                *counts.entry(gram).or_insert(0) += 1;
+            }
+        }
+
+        let mut candidates: Vec<(String, usize)> = counts
+            .into_iter()
+            .filter(|(_, c)| *c >= MIN_OCCURRENCES)
+            .collect();
+        candidates.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        for (phrase, occurrences) in candidates {
+            // Skip fragments of an already-selected longer phrase
+            let padded = format!(" {} ", phrase);
+            if selected.iter().any(|s| format!(" {} ", s.phrase).contains(&padded)) {
+                continue;
+            }
+            selected.push(OverusedPhrase { phrase, occurrences });
+        }
+    }
+
+    selected.sort_by_key(|p| std::cmp::Reverse(p.occurrences));
+    selected.truncate(MAX_PHRASES);
+    selected
+}
+
+/// Overused phrases for an agent, based on its recent replies across all conversations
+pub fn overused_phrases_for(agent: &str) -> Vec<OverusedPhrase> {
+    let replies = db::get_recent_messages_by_role(agent, LOOKBACK_MESSAGES).unwrap_or_default();
+    if replies.len() < MIN_OCCURRENCES {
+        return Vec::new();
+    }
+    find_overused_phrases(&replies)
+}
+
+/// Prompt section listing overused phrases (empty when there are none)
+pub fn prompt_section(agent: &str) -> String {
+    let phrases = overused_phrases_for(agent);
+    if phrases.is_empty() {
+        return String::new();
+    }
+
+    let lines = phrases
+        .iter()
+        .map(|p| format!("- \"{}\"", p.phrase))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "\n\n---\n\nPHRASES YOU'VE OVERUSED RECENTLY (don't reuse these or close variants -- find fresh language):\n{}",
+        lines
+    )
+}
@@ -0,0 +1,109 @@
+//! Conversation replay with the original pacing
+//!
+//! Messages store their timestamps and, for agent/Governor replies, how long the
+//! reply took to generate (`latency_ms`). Replay walks a conversation in order and
+//! emits events on the same rhythm: the pause before each message, a "typing"
+//! beat for generated replies, then the message itself. Long idle gaps are capped
+//! so a conversation picked back up the next day doesn't stall the replay.
+//! Replies aren't streamed, so there is no chunk timing to reproduce.
+
+use crate::db::{self, Message};
+use crate::events::{self, Event};
+use crate::logging;
+use chrono::DateTime;
+use serde::Serialize;
+use std::time::Duration;
+use ts_rs::TS;
+
+const MAX_PAUSE_MS: i64 = 4_000;
+const MAX_TYPING_MS: i64 = 8_000;
+
+/// Payload for the "replay-typing" event
+#[derive(Debug, Serialize, Clone, TS)]
+pub struct ReplayTypingEvent {
+    pub conversation_id: String,
+    pub role: String,
+    pub duration_ms: u64,
+}
+
+/// Payload for the "replay-message" event
+#[derive(Debug, Serialize, Clone, TS)]
+pub struct ReplayMessageEvent {
+    pub conversation_id: String,
+    pub index: usize,
+    pub total: usize,
+    pub message: Message,
+}
+
+/// Payload for the "replay-start" and "replay-complete" events
+#[derive(Debug, Serialize, Clone, TS)]
+pub struct ReplayProgressEvent {
+    pub conversation_id: String,
+    pub total: usize,
+}
+
+fn millis_between(earlier: &str, later: &str) -> i64 {
+    match (DateTime::parse_from_rfc3339(earlier), DateTime::parse_from_rfc3339(later)) {
+        (Ok(a), Ok(b)) => (b - a).num_milliseconds().max(0),
+        _ => 0,
+    }
+}
+
+fn scaled(ms: i64, speed: f64) -> Duration {
+    Duration::from_millis((ms as f64 / speed).round() as u64)
+}
+
+/// Emit a conversation's messages on their original rhythm; returns how many were replayed
+pub async fn replay_conversation(
+    app_handle: &tauri::AppHandle,
+    conversation_id: &str,
+    speed: f64,
+) -> Result<usize, String> {
+    if !(speed.is_finite() && speed > 0.0) {
+        return Err("Replay speed must be positive".to_string());
+    }
+    let messages: Vec<Message> = db::get_conversation_messages(conversation_id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|m| m.status.as_deref() != Some(db::MESSAGE_STATUS_FAILED))
+        .collect();
+    let total = messages.len();
+
+    logging::log_conversation(Some(conversation_id), &format!("[REPLAY] {} messages at {}x", total, speed));
+    events::emit(app_handle, Event::ReplayStart(ReplayProgressEvent {
+        conversation_id: conversation_id.to_string(),
+        total,
+    }));
+
+    let mut previous: Option<&str> = None;
+    for (index, message) in messages.iter().enumerate() {
+        let gap = previous.map(|p| millis_between(p, &message.timestamp)).unwrap_or(0);
+        let typing = message.latency_ms.unwrap_or(0).clamp(0, MAX_TYPING_MS);
+        let pause = (gap - message.latency_ms.unwrap_or(0)).clamp(0, MAX_PAUSE_MS);
+
+        tokio::time::sleep(scaled(pause, speed)).await;
+        if typing > 0 {
+            let duration = scaled(typing, speed);
+            events::emit(app_handle, Event::ReplayTyping(ReplayTypingEvent {
+                conversation_id: conversation_id.to_string(),
+                role: message.role.clone(),
+                duration_ms: duration.as_millis() as u64,
+            }));
+            tokio::time::sleep(duration).await;
+        }
+
+        events::emit(app_handle, Event::ReplayMessage(ReplayMessageEvent {
+            conversation_id: conversation_id.to_string(),
+            index,
+            total,
+            message: message.clone(),
+        }));
+        previous = Some(&message.timestamp);
+    }
+
+    events::emit(app_handle, Event::ReplayComplete(ReplayProgressEvent {
+        conversation_id: conversation_id.to_string(),
+        total,
+    }));
+    Ok(total)
+}
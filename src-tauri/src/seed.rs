@@ -0,0 +1,195 @@
+//! Persona seeds: memory in and out of other tools
+//!
+//! Export builds a compact plain-text summary of what Intersect remembers about
+//! the user, meant to be pasted as a system preamble into other AI tools. It is
+//! rebuilt from the memory tables on every call and is deterministic: the same
+//! memory always yields the same text, so two exports can be diffed. Categories
+//! whose fact policy is "explicit" stay out, as they do from agent prompts.
+//!
+//! Import goes the other way: a pasted self-description or another assistant's
+//! memory export runs through the usual memory extractor, and everything found
+//! is held in staging under a seed id (like an archive import) until the user
+//! commits or discards it. Nothing reaches `user_facts` without that review.
+
+use crate::db::{self, StagedMemory, UserFact, UserPattern};
+use crate::logging;
+use crate::memory::{self, MemoryExtractor, POLICY_EXPLICIT};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+const MAX_FACTS: usize = 15;
+const MAX_VALUES: usize = 8;
+const MAX_PREFERENCES: usize = 8;
+const MAX_PATTERNS: usize = 6;
+
+/// Patterns weaker than this are guesses, not something to hand another tool
+const MIN_PATTERN_CONFIDENCE: f64 = 0.5;
+
+const VALUES_CATEGORY: &str = "values";
+const PREFERENCES_CATEGORY: &str = "preferences";
+
+/// One line of memory text: newlines and runs of spaces collapsed
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn fact_line(fact: &UserFact, with_category: bool) -> String {
+    let key = one_line(&fact.key.replace('_', " "));
+    let inferred = if fact.source_type == "inferred" { " (inferred)" } else { "" };
+    if with_category {
+        format!("- {} / {}: {}{}", fact.category, key, one_line(&fact.value), inferred)
+    } else {
+        format!("- {}: {}{}", key, one_line(&fact.value), inferred)
+    }
+}
+
+/// Strongest first, then a stable order so ties never reshuffle
+fn sort_facts(facts: &mut [UserFact]) {
+    facts.sort_by(|a, b| {
+        b.confidence.total_cmp(&a.confidence)
+            .then(b.mention_count.cmp(&a.mention_count))
+            .then(a.category.cmp(&b.category))
+            .then(a.key.cmp(&b.key))
+    });
+}
+
+fn sort_patterns(patterns: &mut [UserPattern]) {
+    patterns.sort_by(|a, b| {
+        b.confidence.total_cmp(&a.confidence)
+            .then(b.observation_count.cmp(&a.observation_count))
+            .then(a.description.cmp(&b.description))
+    });
+}
+
+fn push_section(out: &mut Vec<String>, title: &str, lines: Vec<String>) {
+    if lines.is_empty() {
+        return;
+    }
+    out.push(String::new());
+    out.push(format!("## {}", title));
+    out.extend(lines);
+}
+
+/// Build the seed text from current memory
+pub fn export_persona_seed() -> Result<String, String> {
+    let mut facts: Vec<UserFact> = db::get_all_user_facts()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|f| memory::fact_policy(&f.category) != POLICY_EXPLICIT)
+        .collect();
+    sort_facts(&mut facts);
+
+    let mut patterns: Vec<UserPattern> = db::get_prompt_user_patterns()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|p| p.confidence >= MIN_PATTERN_CONFIDENCE)
+        .collect();
+    sort_patterns(&mut patterns);
+
+    let values: Vec<String> = facts.iter()
+        .filter(|f| f.category == VALUES_CATEGORY)
+        .take(MAX_VALUES)
+        .map(|f| fact_line(f, false))
+        .collect();
+    let preferences: Vec<String> = facts.iter()
+        .filter(|f| f.category == PREFERENCES_CATEGORY)
+        .take(MAX_PREFERENCES)
+        .map(|f| fact_line(f, false))
+        .collect();
+    let other_facts: Vec<String> = facts.iter()
+        .filter(|f| f.category != VALUES_CATEGORY && f.category != PREFERENCES_CATEGORY)
+        .take(MAX_FACTS)
+        .map(|f| fact_line(f, true))
+        .collect();
+    let pattern_lines: Vec<String> = patterns.iter()
+        .take(MAX_PATTERNS)
+        .map(|p| format!("- {}", one_line(&p.description)))
+        .collect();
+
+    let mut out = vec![
+        "# About me".to_string(),
+        "Background on the person you're talking with, from their own conversations. Use it to tailor answers; don't repeat it back unprompted.".to_string(),
+    ];
+    if other_facts.is_empty() && values.is_empty() && preferences.is_empty() && pattern_lines.is_empty() {
+        out.push(String::new());
+        out.push("(Nothing remembered yet.)".to_string());
+    }
+    push_section(&mut out, "Facts", other_facts);
+    push_section(&mut out, "Values", values);
+    push_section(&mut out, "Preferences", preferences);
+    push_section(&mut out, "Patterns", pattern_lines);
+
+    Ok(out.join("\n") + "\n")
+}
+
+// ============ Import ============
+
+/// Pasted text beyond this is cut before extraction
+const MAX_IMPORT_CHARS: usize = 20_000;
+const SEED_ID_PREFIX: &str = "seed:";
+
+/// What an imported seed produced, held in staging for review
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct SeedImport {
+    pub seed_id: String,
+    pub facts: Vec<StagedMemory>,
+    pub patterns: Vec<StagedMemory>,
+    pub truncated: bool,
+}
+
+fn check_seed_id(seed_id: &str) -> Result<(), String> {
+    if seed_id.starts_with(SEED_ID_PREFIX) {
+        Ok(())
+    } else {
+        Err(format!("Not a persona seed: {}", seed_id))
+    }
+}
+
+/// Extract facts and patterns from pasted text and hold them for review
+pub async fn import_persona_seed(text: &str) -> Result<SeedImport, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Paste some text to import".to_string());
+    }
+    let api_key = db::get_user_profile().ok().and_then(|p| p.anthropic_key)
+        .ok_or("An Anthropic API key is required to import a persona seed")?;
+
+    let truncated = text.chars().count() > MAX_IMPORT_CHARS;
+    let text: String = text.chars().take(MAX_IMPORT_CHARS).collect();
+    let seed_id = format!("{}{}", SEED_ID_PREFIX, uuid::Uuid::new_v4());
+
+    let existing_facts = db::get_all_user_facts().unwrap_or_default();
+    MemoryExtractor::for_import(&api_key, &seed_id)
+        .extract_from_exchange(&text, &[], &existing_facts, &seed_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let (facts, patterns): (Vec<StagedMemory>, Vec<StagedMemory>) = db::get_staged_memory_for_import(&seed_id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .partition(|item| item.kind == db::STAGED_FACT);
+    logging::log_memory(None, &format!(
+        "Imported persona seed {}: {} facts, {} patterns staged for review", seed_id, facts.len(), patterns.len()
+    ));
+
+    Ok(SeedImport { seed_id, facts, patterns, truncated })
+}
+
+/// Commit a reviewed seed: its remaining staged items go straight to long-term memory
+pub fn commit_persona_seed(seed_id: &str) -> Result<usize, String> {
+    check_seed_id(seed_id)?;
+    let items = db::get_staged_memory_for_import(seed_id).map_err(|e| e.to_string())?;
+    for item in &items {
+        memory::promote_staged_item(item)?;
+    }
+    logging::log_memory(None, &format!("Committed persona seed {}: {} items", seed_id, items.len()));
+    Ok(items.len())
+}
+
+pub fn discard_persona_seed(seed_id: &str) -> Result<usize, String> {
+    check_seed_id(seed_id)?;
+    let discarded = db::delete_staged_memory_for_import(seed_id).map_err(|e| e.to_string())?;
+    logging::log_memory(None, &format!("Discarded persona seed {}: {} items", seed_id, discarded));
+    Ok(discarded)
+}
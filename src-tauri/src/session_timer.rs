@@ -0,0 +1,135 @@
+//! Session timer and gentle wrap-up
+//!
+//! A conversation can carry a time budget -- the minutes the user meant to
+//! spend. The clock starts with the first message of the session and lives in
+//! memory only, like session weights. Once the budget is spent, every agent
+//! reply gets a wrap-up directive: shorter, pulling threads together instead of
+//! opening new ones, and closing with a steady "this is a good place to stop"
+//! rather than cutting the user off. Nothing is blocked; the user can keep going.
+
+use crate::db;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use ts_rs::TS;
+
+/// Setting: budget given to new sessions, in minutes (absent or "0" = no budget)
+pub const DEFAULT_BUDGET_KEY: &str = "session_time_budget_minutes";
+
+pub const MIN_BUDGET_MINUTES: i64 = 5;
+pub const MAX_BUDGET_MINUTES: i64 = 480;
+
+struct Timer {
+    started_at: DateTime<Utc>,
+    budget_minutes: Option<i64>,
+}
+
+static TIMERS: Lazy<Mutex<HashMap<String, Timer>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Where a session stands against its budget
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct SessionTimer {
+    pub conversation_id: String,
+    pub started_at: Option<String>,     // None until the first message of the session
+    pub budget_minutes: Option<i64>,
+    pub elapsed_minutes: i64,
+    pub over_budget: bool,
+}
+
+pub fn validate(minutes: i64) -> Result<i64, String> {
+    if (MIN_BUDGET_MINUTES..=MAX_BUDGET_MINUTES).contains(&minutes) {
+        Ok(minutes)
+    } else {
+        Err(format!("Session budget must be between {} and {} minutes", MIN_BUDGET_MINUTES, MAX_BUDGET_MINUTES))
+    }
+}
+
+pub fn default_budget() -> Option<i64> {
+    db::get_setting(DEFAULT_BUDGET_KEY).ok().flatten()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|m| *m > 0)
+}
+
+pub fn set_default_budget(minutes: Option<i64>) -> Result<(), String> {
+    let minutes = match minutes {
+        Some(0) | None => 0,
+        Some(minutes) => validate(minutes)?,
+    };
+    db::set_setting(DEFAULT_BUDGET_KEY, &minutes.to_string()).map_err(|e| e.to_string())
+}
+
+fn snapshot(conversation_id: &str, timer: Option<&Timer>) -> SessionTimer {
+    let Some(timer) = timer else {
+        return SessionTimer {
+            conversation_id: conversation_id.to_string(),
+            started_at: None,
+            budget_minutes: default_budget(),
+            elapsed_minutes: 0,
+            over_budget: false,
+        };
+    };
+    let elapsed_minutes = (Utc::now() - timer.started_at).num_minutes().max(0);
+    SessionTimer {
+        conversation_id: conversation_id.to_string(),
+        started_at: Some(timer.started_at.to_rfc3339()),
+        budget_minutes: timer.budget_minutes,
+        elapsed_minutes,
+        over_budget: timer.budget_minutes.is_some_and(|budget| elapsed_minutes >= budget),
+    }
+}
+
+/// Start the clock if this is the session's first message; returns the state after
+pub fn touch(conversation_id: &str) -> SessionTimer {
+    let mut timers = TIMERS.lock().unwrap();
+    let timer = timers.entry(conversation_id.to_string()).or_insert_with(|| Timer {
+        started_at: Utc::now(),
+        budget_minutes: default_budget(),
+    });
+    snapshot(conversation_id, Some(timer))
+}
+
+pub fn status(conversation_id: &str) -> SessionTimer {
+    let timers = TIMERS.lock().unwrap();
+    snapshot(conversation_id, timers.get(conversation_id))
+}
+
+/// Set (or with None/0, drop) this session's budget; the clock keeps running
+pub fn set_budget(conversation_id: &str, minutes: Option<i64>) -> Result<SessionTimer, String> {
+    let minutes = match minutes {
+        Some(0) | None => None,
+        Some(minutes) => Some(validate(minutes)?),
+    };
+    let mut timers = TIMERS.lock().unwrap();
+    let timer = timers.entry(conversation_id.to_string()).or_insert_with(|| Timer {
+        started_at: Utc::now(),
+        budget_minutes: None,
+    });
+    timer.budget_minutes = minutes;
+    Ok(snapshot(conversation_id, Some(timer)))
+}
+
+/// Start the clock over, keeping the budget
+pub fn restart(conversation_id: &str) -> SessionTimer {
+    let mut timers = TIMERS.lock().unwrap();
+    let budget_minutes = timers.get(conversation_id).map(|t| t.budget_minutes).unwrap_or_else(default_budget);
+    let timer = Timer { started_at: Utc::now(), budget_minutes };
+    let state = snapshot(conversation_id, Some(&timer));
+    timers.insert(conversation_id.to_string(), timer);
+    state
+}
+
+/// Forget the session's clock (when the conversation ends)
+pub fn clear(conversation_id: &str) {
+    TIMERS.lock().unwrap().remove(conversation_id);
+}
+
+/// Prompt directive once a session has run past its budget
+pub fn directive(elapsed_minutes: i64, budget_minutes: i64) -> String {
+    format!(
+        "\n\nWRAP-UP: This session has run {} minutes; the user set aside {}. Keep this reply shorter than usual. Don't open new threads or ask new questions -- pull what's been said into one or two things worth keeping. End on a steady note that this is a good place to stop, the way Volition would say it: not a verdict, just permission to rest. Never be abrupt or make the user feel dismissed.",
+        elapsed_minutes, budget_minutes
+    )
+}
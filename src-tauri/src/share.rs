@@ -0,0 +1,256 @@
+//! Explicit "share session" with a therapist or coach
+//!
+//! Nothing leaves the machine without a per-send confirmation. Sharing is a
+//! two-step flow like data destruction: `prepare` renders a redacted summary and
+//! transcript and returns it as a preview with a short-lived token, and
+//! `confirm` sends exactly that preview to the configured destination (SMTP
+//! email or webhook). Every confirmed send is logged with the full payload.
+
+use crate::agents;
+use crate::db::{self, ShareLogEntry};
+use crate::keychain;
+use crate::logging;
+use chrono::{DateTime, Duration, Utc};
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use ts_rs::TS;
+
+pub const DESTINATION_EMAIL: &str = "email";
+pub const DESTINATION_WEBHOOK: &str = "webhook";
+
+pub const SMTP_HOST_KEY: &str = "share_smtp_host";
+pub const SMTP_PORT_KEY: &str = "share_smtp_port";
+pub const SMTP_USERNAME_KEY: &str = "share_smtp_username";
+/// "true" once an SMTP password is in the keychain; the password itself never is in the database
+pub const SMTP_PASSWORD_SET_KEY: &str = "share_smtp_password_set";
+pub const EMAIL_FROM_KEY: &str = "share_email_from";
+pub const EMAIL_TO_KEY: &str = "share_email_to";
+pub const WEBHOOK_URL_KEY: &str = "share_webhook_url";
+
+const DEFAULT_SMTP_PORT: u16 = 587;
+const TOKEN_TTL_SECS: i64 = 300;
+
+#[derive(Debug, Clone)]
+struct PendingShare {
+    preview: SharePreview,
+    expires_at: DateTime<Utc>,
+}
+
+static PENDING: Lazy<Mutex<HashMap<String, PendingShare>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+static EMAIL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[\w.+-]+@[\w-]+(\.[\w-]+)+").unwrap());
+static URL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"https?://\S+").unwrap());
+static PHONE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\+?\d[\d\s().-]{7,}\d").unwrap());
+
+/// Destination settings as shown to the UI (the SMTP password is never returned)
+#[derive(Debug, Serialize, Deserialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct ShareSettings {
+    pub smtp_host: Option<String>,
+    pub smtp_port: Option<u16>,
+    pub smtp_username: Option<String>,
+    pub has_smtp_password: bool,
+    pub email_from: Option<String>,
+    pub email_to: Option<String>,
+    pub webhook_url: Option<String>,
+}
+
+/// Exactly what will be sent, pending confirmation
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct SharePreview {
+    pub token: String,
+    pub conversation_id: String,
+    pub destination: String,   // 'email' | 'webhook'
+    pub target: String,        // Recipient address or webhook URL
+    pub subject: String,
+    pub content: String,
+    pub expires_at: String,
+}
+
+fn setting(key: &str) -> Option<String> {
+    db::get_setting(key).ok().flatten().filter(|v| !v.trim().is_empty())
+}
+
+pub fn get_settings() -> ShareSettings {
+    ShareSettings {
+        smtp_host: setting(SMTP_HOST_KEY),
+        smtp_port: setting(SMTP_PORT_KEY).and_then(|p| p.parse().ok()),
+        smtp_username: setting(SMTP_USERNAME_KEY),
+        has_smtp_password: setting(SMTP_PASSWORD_SET_KEY).as_deref() == Some("true"),
+        email_from: setting(EMAIL_FROM_KEY),
+        email_to: setting(EMAIL_TO_KEY),
+        webhook_url: setting(WEBHOOK_URL_KEY),
+    }
+}
+
+/// Whether mail can go out unattended: a host, and the keychain password when
+/// the server takes a username
+pub fn smtp_ready() -> bool {
+    setting(SMTP_HOST_KEY).is_some()
+        && (setting(SMTP_USERNAME_KEY).is_none() || keychain::get(keychain::SMTP_PASSWORD).is_some())
+}
+
+/// Store the SMTP password in the keychain; "" removes it
+pub fn set_smtp_password(password: &str) -> Result<(), String> {
+    if password.is_empty() {
+        keychain::delete(keychain::SMTP_PASSWORD)?;
+    } else {
+        keychain::set(keychain::SMTP_PASSWORD, password)?;
+    }
+    db::set_setting(SMTP_PASSWORD_SET_KEY, if password.is_empty() { "false" } else { "true" }).map_err(|e| e.to_string())
+}
+
+/// Strip contact details and links before anything leaves the machine
+pub fn redact(text: &str) -> String {
+    let text = EMAIL_RE.replace_all(text, "[email]");
+    let text = URL_RE.replace_all(&text, "[link]");
+    PHONE_RE.replace_all(&text, "[number]").into_owned()
+}
+
+fn speaker(role: &str) -> String {
+    match role {
+        "user" => "Me".to_string(),
+        other => agents::display_name(other),
+    }
+}
+
+/// Redacted summary followed by the transcript
+fn render(conversation_id: &str) -> Result<(String, String), String> {
+    let conversation = db::get_conversation(conversation_id).map_err(|e| e.to_string())?
+        .ok_or("Conversation not found")?;
+    let messages = db::get_conversation_messages(conversation_id).map_err(|e| e.to_string())?;
+    let summary = db::get_conversation_summary(conversation_id).map_err(|e| e.to_string())?
+        .map(|s| s.summary)
+        .or(conversation.summary.clone());
+
+    let date = DateTime::parse_from_rfc3339(&conversation.created_at)
+        .map(|d| d.format("%B %-d, %Y").to_string())
+        .unwrap_or_else(|_| conversation.created_at.clone());
+    let subject = format!("Intersect session -- {}", date);
+
+    let mut content = String::new();
+    content.push_str(&format!("{}\n\nSUMMARY\n", subject));
+    content.push_str(&redact(summary.as_deref().unwrap_or("(No summary yet)")));
+    content.push_str("\n\nTRANSCRIPT\n");
+    for msg in messages.iter().filter(|m| m.role != "system" && m.status.as_deref() != Some(db::MESSAGE_STATUS_FAILED)) {
+        content.push_str(&format!("\n{}: {}\n", speaker(&msg.role), redact(&msg.content)));
+    }
+
+    Ok((subject, content))
+}
+
+/// Step one: render the redacted payload and hold it for confirmation
+pub fn prepare(conversation_id: &str, destination: &str) -> Result<SharePreview, String> {
+    let settings = get_settings();
+    let target = match destination {
+        DESTINATION_EMAIL => settings.email_to.ok_or("No recipient email configured")?,
+        DESTINATION_WEBHOOK => settings.webhook_url.ok_or("No webhook URL configured")?,
+        other => return Err(format!("Unknown share destination: {}", other)),
+    };
+
+    let (subject, content) = render(conversation_id)?;
+    let expires_at = Utc::now() + Duration::seconds(TOKEN_TTL_SECS);
+    let preview = SharePreview {
+        token: uuid::Uuid::new_v4().to_string(),
+        conversation_id: conversation_id.to_string(),
+        destination: destination.to_string(),
+        target,
+        subject,
+        content,
+        expires_at: expires_at.to_rfc3339(),
+    };
+
+    let mut pending = PENDING.lock().unwrap();
+    pending.retain(|_, p| Utc::now() < p.expires_at);
+    pending.insert(preview.token.clone(), PendingShare { preview: preview.clone(), expires_at });
+    Ok(preview)
+}
+
+/// Step two: send the previewed payload (single use) and log it
+pub async fn confirm(token: &str) -> Result<ShareLogEntry, String> {
+    let pending = PENDING.lock().unwrap().remove(token);
+    let preview = match pending {
+        Some(p) if Utc::now() < p.expires_at => p.preview,
+        _ => return Err("Share confirmation expired or invalid -- prepare it again".to_string()),
+    };
+
+    let result = match preview.destination.as_str() {
+        DESTINATION_EMAIL => send_email(&preview).await,
+        _ => send_webhook(&preview).await,
+    };
+
+    let entry = ShareLogEntry {
+        id: 0,
+        conversation_id: preview.conversation_id.clone(),
+        destination: preview.destination.clone(),
+        target: preview.target.clone(),
+        subject: preview.subject.clone(),
+        content: preview.content.clone(),
+        error: result.as_ref().err().cloned(),
+        shared_at: Utc::now().to_rfc3339(),
+    };
+    let id = db::save_share_log(&entry).map_err(|e| e.to_string())?;
+    logging::log_conversation(Some(&preview.conversation_id), &format!(
+        "[SHARE] {} to {} ({})",
+        preview.destination,
+        preview.target,
+        if result.is_ok() { "sent" } else { "failed" }
+    ));
+
+    result.map(|_| ShareLogEntry { id, ..entry })
+}
+
+async fn send_email(preview: &SharePreview) -> Result<(), String> {
+    send_plain_email(&preview.target, &preview.subject, &preview.content).await
+}
+
+/// Send a plain-text email through the configured SMTP server. Callers decide what
+/// may be sent; this only delivers it.
+pub async fn send_plain_email(to: &str, subject: &str, body: &str) -> Result<(), String> {
+    let host = setting(SMTP_HOST_KEY).ok_or("SMTP host not configured")?;
+    let port = setting(SMTP_PORT_KEY).and_then(|p| p.parse().ok()).unwrap_or(DEFAULT_SMTP_PORT);
+    let from = setting(EMAIL_FROM_KEY).or_else(|| setting(SMTP_USERNAME_KEY)).ok_or("Sender address not configured")?;
+
+    let email = lettre::Message::builder()
+        .from(from.parse().map_err(|e| format!("Invalid sender address: {}", e))?)
+        .to(to.parse().map_err(|e| format!("Invalid recipient address: {}", e))?)
+        .subject(subject.to_string())
+        .header(ContentType::TEXT_PLAIN)
+        .body(body.to_string())
+        .map_err(|e| e.to_string())?;
+
+    let mut transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)
+        .map_err(|e| e.to_string())?
+        .port(port);
+    if let (Some(user), Some(pass)) = (setting(SMTP_USERNAME_KEY), keychain::get(keychain::SMTP_PASSWORD)) {
+        transport = transport.credentials(Credentials::new(user, pass));
+    }
+
+    transport.build().send(email).await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+async fn send_webhook(preview: &SharePreview) -> Result<(), String> {
+    let response = reqwest::Client::new()
+        .post(&preview.target)
+        .json(&serde_json::json!({
+            "subject": preview.subject,
+            "content": preview.content,
+            "shared_at": Utc::now().to_rfc3339(),
+        }))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("Webhook returned {}", response.status()));
+    }
+    Ok(())
+}
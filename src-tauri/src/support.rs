@@ -0,0 +1,234 @@
+//! Support bundle for bug reports
+//!
+//! One zip to attach to a GitHub issue: recent logs, diagnostics (app and OS,
+//! schema version and integrity, per-table column and row counts), settings
+//! and performance metrics. No message content goes in. Only settings that
+//! describe how the app behaves are included (never house rules, addresses,
+//! paths or secrets), and every value is scrubbed of contact details, keys,
+//! quoted text and the names memory knows about. Log lines are cut down to
+//! their timestamp, category and tag, since the text after that can quote
+//! messages and fact values.
+
+use crate::anonymize::{self, Anonymizer};
+use crate::db;
+use crate::latency;
+use crate::logging;
+use crate::usage;
+use crate::{budgets, digest, i18n, intensity, jobs, memory, policy, power, session_timer, watchdog};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use ts_rs::TS;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// How many of the newest daily log files go in
+const LOG_FILES: usize = 7;
+
+/// The settings that go in: toggles, limits and modes. Anything else (house
+/// rules, email addresses, folder and server locations, secrets) stays out,
+/// including settings added later until they're listed here.
+const DIAGNOSTIC_SETTING_KEYS: [&str; 25] = [
+    anonymize::ANONYMIZED_KEY,
+    budgets::BUDGET_MODE_KEY,
+    budgets::LOCAL_MODEL_NAME_KEY,
+    budgets::MONTHLY_CAP_USD_KEY,
+    budgets::MONTHLY_CAP_TOKENS_KEY,
+    budgets::MONTHLY_CAP_MODE_KEY,
+    digest::DIGEST_ENABLED_KEY,
+    i18n::LANGUAGE_KEY,
+    intensity::CEILING_SETTING_KEY,
+    jobs::AUTO_CLOSE_DAYS_SETTING_KEY,
+    jobs::BACKGROUND_DEFERRED_UNTIL_KEY,
+    latency::LATENCY_SLO_MS_KEY,
+    memory::STAGING_DAYS_KEY,
+    memory::STAGING_CONFIRMATIONS_KEY,
+    memory::FACT_DECAY_DAYS_KEY,
+    memory::THEME_DECAY_WEEKS_KEY,
+    memory::BOOKMARKS_IN_CONTEXT_KEY,
+    memory::RELATED_IN_CONTEXT_KEY,
+    policy::QUIET_ENABLED_KEY,
+    policy::QUIET_START_KEY,
+    policy::QUIET_END_KEY,
+    policy::DND_UNTIL_KEY,
+    power::DEFER_ON_BATTERY_KEY,
+    session_timer::DEFAULT_BUDGET_KEY,
+    watchdog::AUTO_CORRECT_KEY,
+];
+
+static API_KEY_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(sk|pk|xox[bp])-[\w-]{8,}").unwrap());
+static QUOTED_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#""[^"]*""#).unwrap());
+/// `[timestamp] [CATEGORY] conversation=abcd1234 | [TAG] ...` up to the tag
+static LOG_HEADER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(\[[^\]]+\] \[[A-Z]+\])(?: conversation=\w+ \|)?(?: (\[[A-Z_]+\]))?").unwrap()
+});
+
+/// What was written, for the confirmation shown to the user
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct SupportBundle {
+    pub path: String,
+    pub files: Vec<String>,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct Diagnostics {
+    created_at: String,
+    app_version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    database_bytes: Option<u64>,
+    database_in_default_location: bool,
+    schema: Option<db::SchemaDiagnostics>,
+    errors: Vec<String>,            // What couldn't be collected
+}
+
+#[derive(Debug, Serialize)]
+struct Metrics {
+    latency: latency::LatencyStatus,
+    month_tokens: i64,
+    month_cost_usd: f64,
+}
+
+/// Strip anything identifying from a setting value
+fn scrub(text: &str, anonymizer: &Anonymizer) -> String {
+    let text = API_KEY_RE.replace_all(text, "[key]");
+    let text = QUOTED_RE.replace_all(&text, "\"[text]\"");
+    anonymizer.text(&text)
+}
+
+fn is_diagnostic(key: &str) -> bool {
+    DIAGNOSTIC_SETTING_KEYS.contains(&key)
+}
+
+fn diagnostics(app_handle: &tauri::AppHandle) -> Diagnostics {
+    let location = db::get_data_location(app_handle);
+    let mut errors = Vec::new();
+    let schema = db::get_schema_diagnostics()
+        .map_err(|e| errors.push(format!("schema: {}", e)))
+        .ok();
+    Diagnostics {
+        created_at: Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        database_bytes: fs::metadata(&location.path).map(|m| m.len()).ok(),
+        database_in_default_location: location.is_default,
+        schema,
+        errors,
+    }
+}
+
+fn settings(anonymizer: &Anonymizer) -> serde_json::Map<String, serde_json::Value> {
+    db::get_all_settings()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(key, _)| is_diagnostic(key))
+        .map(|(key, value)| (key, serde_json::Value::String(scrub(&value, anonymizer))))
+        .collect()
+}
+
+fn metrics() -> Metrics {
+    let (month_tokens, month_cost_usd) = db::get_usage_totals_since(&usage::month_start()).unwrap_or((0, 0.0));
+    Metrics { latency: latency::status(), month_tokens, month_cost_usd }
+}
+
+/// A log line without its message: timestamp, category and tag only
+fn log_header(line: &str) -> Option<String> {
+    let caps = LOG_HEADER_RE.captures(line)?;
+    Some(match caps.get(2) {
+        Some(tag) => format!("{} {}", &caps[1], tag.as_str()),
+        None => caps[1].to_string(),
+    })
+}
+
+/// The newest daily logs in `dir`, reduced to line headers
+fn logs_from(dir: &Path) -> Vec<(String, String)> {
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.extension().is_some_and(|ext| ext == "log")).collect())
+        .unwrap_or_default();
+    paths.sort();
+    paths.into_iter()
+        .rev()
+        .take(LOG_FILES)
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            let content = fs::read_to_string(&path).ok()?;
+            let headers: Vec<String> = content.lines().filter_map(log_header).collect();
+            Some((format!("logs/{}", name), headers.join("\n")))
+        })
+        .collect()
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+}
+
+/// Write the bundle to `path` (chosen with the save dialog)
+pub fn create_bundle(app_handle: &tauri::AppHandle, path: &str) -> Result<SupportBundle, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Choose where to save the bundle".to_string());
+    }
+    // Known names are swapped out whether or not anonymized rendering is on
+    let anonymizer = Anonymizer::from_facts(&db::get_all_user_facts().unwrap_or_default());
+
+    let mut entries: Vec<(String, String)> = vec![
+        ("diagnostics.json".to_string(), to_json(&diagnostics(app_handle))?),
+        ("settings.json".to_string(), to_json(&settings(&anonymizer))?),
+        ("metrics.json".to_string(), to_json(&metrics())?),
+    ];
+    entries.extend(logs_from(&logging::get_log_dir()));
+
+    let file = fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, content) in &entries {
+        zip.start_file(name.as_str(), options).map_err(|e| e.to_string())?;
+        zip.write_all(content.as_bytes()).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+
+    logging::log_conversation(None, &format!("Support bundle written ({} files)", entries.len()));
+    Ok(SupportBundle {
+        path: path.to_string(),
+        files: entries.into_iter().map(|(name, _)| name).collect(),
+        bytes: fs::metadata(Path::new(path)).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn bundled_logs_leave_out_message_text() {
+        let dir = std::env::temp_dir().join(format!("intersect-support-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("intersect-2026-01-01.log"),
+            "[2026-01-01 09:00:00] [MEMORY] conversation=abcd1234 | Starting extraction. User message: my sister Ana is sick\n\
+             [2026-01-01 09:00:01] [MEMORY] [CORRECTION] Updated fact (sister: Ana)\n\
+             [2026-01-01 09:00:02] [ERROR] Failed to parse extraction. Response: she lives in Lisbon\n",
+        ).unwrap();
+        
+        let logs = logs_from(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        
+        assert_eq!(logs.len(), 1);
+        let (name, content) = &logs[0];
+        assert_eq!(name, "logs/intersect-2026-01-01.log");
+        for text in ["sister", "Ana", "Lisbon", "extraction"] {
+            assert!(!content.contains(text), "{:?} leaked into {:?}", text, content);
+        }
+        assert_eq!(
+            content.lines().collect::<Vec<_>>(),
+            ["[2026-01-01 09:00:00] [MEMORY]", "[2026-01-01 09:00:01] [MEMORY] [CORRECTION]", "[2026-01-01 09:00:02] [ERROR]"],
+        );
+    }
+}
@@ -0,0 +1,294 @@
+//! API usage and cost tracking
+//!
+//! Both LLM clients record every successful call here: provider, model, what it
+//! was for (category / agent), token counts, latency, and an estimated cost from
+//! the static price table below. The usage log backs the CSV export and the
+//! monthly cost forecast.
+
+use crate::db::{self, UsageRecord};
+use crate::logging;
+use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use ts_rs::TS;
+
+/// USD per million tokens (input, output)
+fn price_per_mtok(model: &str) -> (f64, f64) {
+    let m = model.to_lowercase();
+    if m.contains("haiku") {
+        (0.80, 4.00)
+    } else if m.contains("sonnet") {
+        (3.00, 15.00)
+    } else if m.contains("opus") {
+        (15.00, 75.00)
+    } else if m.contains("gpt-4o-mini") {
+        (0.15, 0.60)
+    } else if m.contains("gpt-4o") {
+        (2.50, 10.00)
+    } else {
+        (0.0, 0.0)
+    }
+}
+
+pub fn estimate_cost_usd(model: &str, input_tokens: i64, output_tokens: i64) -> f64 {
+    let (input_price, output_price) = price_per_mtok(model);
+    (input_tokens as f64 * input_price + output_tokens as f64 * output_price) / 1_000_000.0
+}
+
+/// One API call as reported by a client
+pub struct CallUsage<'a> {
+    pub provider: &'a str,
+    pub model: &'a str,
+    pub category: &'a str,
+    pub agent: Option<&'a str>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub latency_ms: i64,
+}
+
+/// Model, tokens and estimated cost of one call, carried onto the message it produced
+#[derive(Debug, Serialize, Deserialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct MessageCost {
+    #[serde(default)]
+    pub model: String,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_usd: f64,
+}
+
+/// Record a call and return what it cost. Failures are logged, never surfaced --
+/// usage tracking must not break chat.
+pub fn record(call: CallUsage) -> MessageCost {
+    let record = UsageRecord {
+        id: 0,
+        provider: call.provider.to_string(),
+        model: call.model.to_string(),
+        category: call.category.to_string(),
+        agent: call.agent.map(|a| a.to_string()),
+        input_tokens: call.input_tokens,
+        output_tokens: call.output_tokens,
+        latency_ms: call.latency_ms,
+        cost_usd: estimate_cost_usd(call.model, call.input_tokens, call.output_tokens),
+        created_at: Utc::now().to_rfc3339(),
+    };
+    if let Err(e) = db::save_usage_record(&record) {
+        logging::log_error(None, &format!("Failed to record API usage: {}", e));
+    }
+    MessageCost {
+        model: record.model,
+        input_tokens: record.input_tokens,
+        output_tokens: record.output_tokens,
+        cost_usd: record.cost_usd,
+    }
+}
+
+// ============ CSV Export ============
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct UsageExportResult {
+    pub path: String,
+    pub call_count: usize,
+    pub total_cost_usd: f64,
+}
+
+/// One row of the summary section
+#[derive(Default)]
+struct UsageTotals {
+    model: String,
+    agent: String,
+    calls: usize,
+    input_tokens: i64,
+    output_tokens: i64,
+    cost_usd: f64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, record: &UsageRecord) {
+        self.calls += 1;
+        self.input_tokens += record.input_tokens;
+        self.output_tokens += record.output_tokens;
+        self.cost_usd += record.cost_usd;
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains(',') || value.contains('"') || value.contains('\n') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Export per-call rows plus a per-model/per-agent summary for [start, end).
+/// Both go in the one chosen file, the summary as a second section after a
+/// blank line, so nothing is written next to it.
+pub fn export_csv(start: Option<&str>, end: Option<&str>, path: &str) -> Result<UsageExportResult, String> {
+    let records = db::get_usage_records(start, end).map_err(|e| e.to_string())?;
+    let path = PathBuf::from(path);
+
+    let mut calls = String::from("timestamp,provider,model,category,agent,input_tokens,output_tokens,latency_ms,cost_usd\n");
+    for r in &records {
+        calls.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{:.6}\n",
+            csv_field(&r.created_at),
+            csv_field(&r.provider),
+            csv_field(&r.model),
+            csv_field(&r.category),
+            csv_field(r.agent.as_deref().unwrap_or("")),
+            r.input_tokens,
+            r.output_tokens,
+            r.latency_ms,
+            r.cost_usd,
+        ));
+    }
+
+    // Summary grouped by (model, agent), in first-seen order
+    let mut groups: Vec<UsageTotals> = Vec::new();
+    for r in &records {
+        let agent = r.agent.clone().unwrap_or_default();
+        let idx = match groups.iter().position(|g| g.model == r.model && g.agent == agent) {
+            Some(idx) => idx,
+            None => {
+                groups.push(UsageTotals { model: r.model.clone(), agent, ..Default::default() });
+                groups.len() - 1
+            }
+        };
+        groups[idx].add(r);
+    }
+    let mut total = UsageTotals { model: "TOTAL".to_string(), ..Default::default() };
+    for r in &records {
+        total.add(r);
+    }
+
+    let mut summary = String::from("model,agent,calls,input_tokens,output_tokens,cost_usd\n");
+    for g in groups.iter().chain(std::iter::once(&total)) {
+        summary.push_str(&format!(
+            "{},{},{},{},{},{:.6}\n",
+            csv_field(&g.model), csv_field(&g.agent), g.calls, g.input_tokens, g.output_tokens, g.cost_usd
+        ));
+    }
+
+    let contents = format!("{}\n{}", calls, summary);
+    fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    Ok(UsageExportResult {
+        path: path.display().to_string(),
+        call_count: records.len(),
+        total_cost_usd: total.cost_usd,
+    })
+}
+
+// ============ Forecast ============
+
+/// Trailing window the daily average is computed over
+const FORECAST_WINDOW_DAYS: i64 = 30;
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct CategoryForecast {
+    pub category: String,
+    pub trailing_cost_usd: f64,
+    pub daily_average_usd: f64,
+    pub projected_month_usd: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct UsageForecast {
+    pub window_days: i64,             // Days of history actually averaged (<= 30)
+    pub daily_average_usd: f64,
+    pub month_to_date_usd: f64,
+    pub projected_month_usd: f64,     // Month-to-date + daily average for the rest of the month
+    pub days_remaining: i64,
+    pub by_category: Vec<CategoryForecast>,
+    pub cap_usd: Option<f64>,
+    pub projected_exceed_date: Option<String>, // YYYY-MM-DD, when on pace to cross the cap this month
+}
+
+fn days_in_month(date: NaiveDate) -> i64 {
+    let (y, m) = if date.month() == 12 { (date.year() + 1, 1) } else { (date.year(), date.month() + 1) };
+    NaiveDate::from_ymd_opt(y, m, 1)
+        .map(|next| (next - Duration::days(1)).day() as i64)
+        .unwrap_or(30)
+}
+
+/// Local midnight on the first of this month, as an RFC 3339 UTC timestamp
+pub fn month_start() -> String {
+    Local::now().date_naive()
+        .with_day(1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .and_then(|d| Local.from_local_datetime(&d).earliest())
+        .map(|d| d.with_timezone(&Utc).to_rfc3339())
+        .unwrap_or_else(|| Utc::now().to_rfc3339())
+}
+
+/// Extrapolate this month's spend from the trailing 30-day average, per call category
+pub fn forecast(cap_usd: Option<f64>) -> Result<UsageForecast, String> {
+    let now = Utc::now();
+    let today = Local::now().date_naive();
+    let window_start = (now - Duration::days(FORECAST_WINDOW_DAYS)).to_rfc3339();
+    let records = db::get_usage_records(Some(&window_start), None).map_err(|e| e.to_string())?;
+
+    // With less than a month of history, average over the days we actually have
+    let window_days = records
+        .first()
+        .and_then(|r| chrono::DateTime::parse_from_rfc3339(&r.created_at).ok())
+        .map(|first| (now - first.with_timezone(&Utc)).num_days() + 1)
+        .unwrap_or(1)
+        .clamp(1, FORECAST_WINDOW_DAYS);
+
+    let month_to_date_usd: f64 = db::get_usage_records(Some(&month_start()), None)
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(|r| r.cost_usd)
+        .sum();
+
+    let days_remaining = days_in_month(today) - today.day() as i64;
+
+    let mut by_category: Vec<CategoryForecast> = Vec::new();
+    for r in &records {
+        match by_category.iter_mut().find(|c| c.category == r.category) {
+            Some(c) => c.trailing_cost_usd += r.cost_usd,
+            None => by_category.push(CategoryForecast {
+                category: r.category.clone(),
+                trailing_cost_usd: r.cost_usd,
+                daily_average_usd: 0.0,
+                projected_month_usd: 0.0,
+            }),
+        }
+    }
+    // Category projections spread the trailing average over the whole month
+    for c in &mut by_category {
+        c.daily_average_usd = c.trailing_cost_usd / window_days as f64;
+        c.projected_month_usd = c.daily_average_usd * days_in_month(today) as f64;
+    }
+    by_category.sort_by(|a, b| b.projected_month_usd.total_cmp(&a.projected_month_usd));
+
+    let daily_average_usd: f64 = records.iter().map(|r| r.cost_usd).sum::<f64>() / window_days as f64;
+    let projected_month_usd = month_to_date_usd + daily_average_usd * days_remaining as f64;
+
+    let projected_exceed_date = cap_usd.and_then(|cap| {
+        if month_to_date_usd >= cap {
+            return Some(today);
+        }
+        if daily_average_usd <= 0.0 {
+            return None;
+        }
+        let days_needed = ((cap - month_to_date_usd) / daily_average_usd).ceil() as i64;
+        (days_needed <= days_remaining).then(|| today + Duration::days(days_needed))
+    });
+
+    Ok(UsageForecast {
+        window_days,
+        daily_average_usd,
+        month_to_date_usd,
+        projected_month_usd,
+        days_remaining,
+        by_category,
+        cap_usd,
+        projected_exceed_date: projected_exceed_date.map(|d| d.format("%Y-%m-%d").to_string()),
+    })
+}
@@ -0,0 +1,230 @@
+//! Contradiction watchdog between agent replies and remembered facts
+//!
+//! After a round, each reply is scanned for structured claims about the user's
+//! people -- "your sister", "your sister Emma" -- and checked against
+//! high-confidence facts. Saying "sister" when memory only has a brother, or
+//! naming a different sister than the one remembered, is flagged as an insight.
+//! With auto-correct on, a one-line correction also goes into the conversation
+//! so the next replies (and the user) see it. Word matching only, no model call.
+
+use crate::agents;
+use crate::db::{self, Message, UserFact};
+use crate::logging;
+use chrono::Utc;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub const INSIGHT_KIND_CONTRADICTION: &str = "contradiction";
+pub const CORRECTION_RESPONSE_TYPE: &str = "contradiction_correction";
+/// Setting: post a correction into the conversation when a contradiction is found ("true"/"false", default off)
+pub const AUTO_CORRECT_KEY: &str = "contradiction_auto_correct";
+
+/// Only facts at least this certain are held against the agents
+const MIN_FACT_CONFIDENCE: f64 = 0.8;
+
+/// Relations that can't both describe the same person; either side may have synonyms
+const RELATION_PAIRS: [(&[&str], &[&str]); 9] = [
+    (&["sister"], &["brother"]),
+    (&["wife"], &["husband"]),
+    (&["girlfriend"], &["boyfriend"]),
+    (&["mom", "mother", "mum"], &["dad", "father"]),
+    (&["daughter"], &["son"]),
+    (&["aunt"], &["uncle"]),
+    (&["niece"], &["nephew"]),
+    (&["grandmother", "grandma"], &["grandfather", "grandpa"]),
+    (&["stepmom", "stepmother"], &["stepdad", "stepfather"]),
+];
+
+/// A reply that disagrees with what's remembered
+#[derive(Debug, Clone)]
+pub struct Contradiction {
+    pub agent: String,
+    pub claim: String,              // What the agent said, e.g. "your sister Emma"
+    pub fact: UserFact,
+    pub correction: String,         // What memory says instead, e.g. "brother"
+}
+
+pub fn auto_correct_enabled() -> bool {
+    db::get_setting(AUTO_CORRECT_KEY).ok().flatten().as_deref() == Some("true")
+}
+
+fn clean(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_string()
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_string())
+        .collect()
+}
+
+fn fact_text(fact: &UserFact) -> String {
+    format!("{} {}", fact.key.replace('_', " "), fact.value)
+}
+
+/// Capitalized words in a fact's value past the first (names, not sentence starts)
+fn names_in(fact: &UserFact) -> HashSet<String> {
+    fact.value
+        .split_whitespace()
+        .skip(1)
+        .map(clean)
+        .filter(|w| w.chars().next().map(|c| c.is_uppercase()).unwrap_or(false))
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// The name a fact gives for a relation: the capitalized word right after it
+/// ("sister Sarah"), or the fact's only name when the key is the relation
+fn remembered_name(fact: &UserFact, relation: &[&str]) -> Option<String> {
+    let tokens: Vec<String> = fact.value.split_whitespace().map(clean).collect();
+    let after = tokens.windows(2).find_map(|pair| {
+        let next_is_name = pair[1].chars().next().map(|c| c.is_uppercase()).unwrap_or(false);
+        (relation.contains(&pair[0].to_lowercase().as_str()) && next_is_name).then(|| pair[1].clone())
+    });
+    if after.is_some() {
+        return after;
+    }
+    let key_is_relation = words(&fact.key.replace('_', " ")).iter().any(|w| relation.contains(&w.as_str()));
+    let names: Vec<String> = fact.value.split_whitespace()
+        .map(clean)
+        .filter(|w| w.chars().next().map(|c| c.is_uppercase()).unwrap_or(false))
+        .collect();
+    match names.as_slice() {
+        [only] if key_is_relation => Some(only.clone()),
+        _ => None,
+    }
+}
+
+/// The relation a word names and the opposite side of its pair
+fn relation(word: &str) -> Option<(&'static [&'static str], &'static [&'static str])> {
+    RELATION_PAIRS.iter().find_map(|(a, b)| {
+        if a.contains(&word) {
+            Some((*a, *b))
+        } else if b.contains(&word) {
+            Some((*b, *a))
+        } else {
+            None
+        }
+    })
+}
+
+/// "your <relation>" claims in a reply, with the name after it if there is one
+fn relation_claims(content: &str) -> Vec<(String, Option<String>)> {
+    let tokens: Vec<String> = content.split_whitespace().map(clean).collect();
+    let mut claims = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if !token.eq_ignore_ascii_case("your") {
+            continue;
+        }
+        let Some(relation_word) = tokens.get(i + 1).map(|t| t.to_lowercase()) else {
+            continue;
+        };
+        if relation(&relation_word).is_none() {
+            continue;
+        }
+        let name = tokens.get(i + 2)
+            .filter(|t| t.chars().next().map(|c| c.is_uppercase()).unwrap_or(false))
+            .cloned();
+        claims.push((relation_word, name));
+    }
+    claims
+}
+
+/// Contradictions between one agent reply and the remembered facts
+pub fn check_reply(agent: &str, content: &str, facts: &[UserFact]) -> Vec<Contradiction> {
+    let trusted: Vec<&UserFact> = facts.iter().filter(|f| f.confidence >= MIN_FACT_CONFIDENCE).collect();
+    if trusted.is_empty() {
+        return Vec::new();
+    }
+    let mut found: Vec<Contradiction> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for (relation_word, name) in relation_claims(content) {
+        let Some((same, opposite)) = relation(&relation_word) else {
+            continue;
+        };
+        let mentions = |f: &UserFact, side: &[&str]| {
+            let fact_words = words(&fact_text(f));
+            side.iter().any(|w| fact_words.contains(*w))
+        };
+        let matching: Vec<&UserFact> = trusted.iter().copied().filter(|f| mentions(f, same)).collect();
+
+        // "your sister" when memory only knows a brother
+        if matching.is_empty() {
+            if let Some(fact) = trusted.iter().copied().find(|f| mentions(f, opposite)) {
+                let correction = opposite.iter()
+                    .find(|w| words(&fact_text(fact)).contains(**w))
+                    .unwrap_or(&opposite[0])
+                    .to_string();
+                let claim = format!("your {}", relation_word);
+                if seen.insert(claim.clone()) {
+                    found.push(Contradiction { agent: agent.to_string(), claim, fact: fact.clone(), correction });
+                }
+            }
+            continue;
+        }
+
+        // "your sister Emma" when the remembered sister has another name
+        if let Some(name) = name {
+            let known = matching.iter().any(|f| names_in(f).contains(&name.to_lowercase()));
+            let remembered = matching.iter().find_map(|f| remembered_name(f, same).map(|n| (*f, n)));
+            if let (false, Some((fact, remembered))) = (known, remembered.filter(|(_, n)| !n.eq_ignore_ascii_case(&name))) {
+                let claim = format!("your {} {}", relation_word, name);
+                if seen.insert(claim.clone()) {
+                    found.push(Contradiction {
+                        agent: agent.to_string(),
+                        claim,
+                        fact: fact.clone(),
+                        correction: format!("{} {}", relation_word, remembered),
+                    });
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Check a round's replies, flag what contradicts memory, and (when enabled)
+/// post a one-line correction for each. Returns the correction messages saved.
+pub fn check_round(conversation_id: &str, round_id: &str, replies: &[(String, String)], facts: &[UserFact]) -> Vec<Message> {
+    let mut corrections = Vec::new();
+    let auto_correct = auto_correct_enabled();
+
+    for (agent, content) in replies {
+        for contradiction in check_reply(agent, content, facts) {
+            let name = agents::display_name(&contradiction.agent);
+            let insight = format!(
+                "{} said \"{}\", but memory has {}: {}.",
+                name,
+                contradiction.claim,
+                contradiction.fact.key.replace('_', " "),
+                contradiction.fact.value
+            );
+            logging::log_memory(Some(conversation_id), &format!("[WATCHDOG] {}", insight));
+            if let Err(e) = db::save_insight(INSIGHT_KIND_CONTRADICTION, &insight) {
+                logging::log_error(Some(conversation_id), &format!("Failed to save contradiction insight: {}", e));
+            }
+
+            if !auto_correct {
+                continue;
+            }
+            let message = Message {
+                id: Uuid::new_v4().to_string(),
+                conversation_id: conversation_id.to_string(),
+                role: "system".to_string(),
+                content: format!("Correction: {} meant your {}, not \"{}\".", name, contradiction.correction, contradiction.claim),
+                response_type: Some(CORRECTION_RESPONSE_TYPE.to_string()),
+                timestamp: Utc::now().to_rfc3339(),
+                round_id: Some(round_id.to_string()),
+                ..Default::default()
+            };
+            match db::save_message(&message) {
+                Ok(()) => corrections.push(message),
+                Err(e) => logging::log_error(Some(conversation_id), &format!("Failed to save contradiction correction: {}", e)),
+            }
+        }
+    }
+    corrections
+}
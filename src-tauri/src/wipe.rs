@@ -0,0 +1,239 @@
+//! Full local data destruction
+//!
+//! For handing over or retiring a machine. Destruction is a two-step flow:
+//! `request_token` hands out a short-lived token, and `destroy_all_local_data`
+//! only proceeds with that token plus the typed confirmation phrase. Every file
+//! under the app data directory (database, WAL/SHM, anything stored alongside)
+//! and the log directory is overwritten before removal -- that covers every
+//! profile's database, plus the files of any moved to a folder of their own
+//! and the pre-restore snapshots kept beside them. Backups saved elsewhere are
+//! tracked in `backups.txt` in the data directory and deleted too. API keys and
+//! the SMTP password live in the OS keychain rather than the database, so each
+//! profile's are removed from there too.
+
+use crate::db;
+use crate::jobs;
+use crate::keychain;
+use crate::logging;
+use crate::notes;
+use crate::profiles;
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::Manager;
+use ts_rs::TS;
+
+pub const CONFIRMATION_PHRASE: &str = "DESTROY ALL LOCAL DATA";
+const TOKEN_TTL_SECS: i64 = 120;
+/// One path per line: every backup written with `backup_database`
+const BACKUP_LOG_FILE: &str = "backups.txt";
+
+/// (token, expires_at)
+type PendingToken = Option<(String, DateTime<Utc>)>;
+
+static PENDING_TOKEN: Lazy<Mutex<PendingToken>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[ts(export)]
+pub struct DestructionToken {
+    pub token: String,
+    pub confirmation_phrase: String,
+    pub expires_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, TS)]
+#[ts(export)]
+pub struct DestructionReport {
+    pub files_deleted: usize,
+    pub directories_removed: usize,
+    pub errors: Vec<String>,
+}
+
+/// Step one: issue a token that must be echoed back within two minutes
+pub fn request_token() -> DestructionToken {
+    let token = uuid::Uuid::new_v4().to_string();
+    let expires_at = Utc::now() + Duration::seconds(TOKEN_TTL_SECS);
+    *PENDING_TOKEN.lock().unwrap() = Some((token.clone(), expires_at));
+
+    DestructionToken {
+        token,
+        confirmation_phrase: CONFIRMATION_PHRASE.to_string(),
+        expires_at: expires_at.to_rfc3339(),
+    }
+}
+
+/// Consume the pending token; only succeeds once, before expiry
+fn take_valid_token(token: &str) -> bool {
+    let mut pending = PENDING_TOKEN.lock().unwrap();
+    match pending.take() {
+        Some((expected, expires_at)) => expected == token && Utc::now() < expires_at,
+        None => false,
+    }
+}
+
+/// Remember where a backup went, so a full wipe can find it later
+pub fn record_backup(app_handle: &tauri::AppHandle, path: &str) -> std::io::Result<()> {
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(profiles::data_dir(app_handle).join(BACKUP_LOG_FILE))?;
+    writeln!(log, "{}", path)
+}
+
+/// Backups recorded by `record_backup` that are still on disk
+fn recorded_backups(data_dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_to_string(data_dir.join(BACKUP_LOG_FILE))
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(PathBuf::from)
+        .filter(|path| path.exists())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Snapshots `restore_database` left beside a database ("intersect.pre-restore-<ts>.db")
+fn pre_restore_snapshots(db_path: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(stem)) = (db_path.parent(), db_path.file_stem().and_then(|s| s.to_str())) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.pre-restore-", stem);
+    fs::read_dir(dir).into_iter().flatten().flatten()
+        .filter(|entry| entry.file_name().to_str().is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".db")))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// Overwrite a file with zeros, flush to disk, then remove it. A symlink is
+/// just unlinked; whatever it points at is left alone.
+fn secure_delete_file(path: &Path) -> std::io::Result<()> {
+    let meta = fs::symlink_metadata(path)?;
+    if meta.file_type().is_symlink() {
+        return fs::remove_file(path);
+    }
+    let len = meta.len();
+    {
+        let mut file = OpenOptions::new().write(true).open(path)?;
+        let zeros = vec![0u8; 64 * 1024];
+        let mut remaining = len;
+        while remaining > 0 {
+            let chunk = remaining.min(zeros.len() as u64) as usize;
+            file.write_all(&zeros[..chunk])?;
+            remaining -= chunk as u64;
+        }
+        file.sync_all()?;
+    }
+    fs::remove_file(path)
+}
+
+/// Securely delete everything under `dir`, then remove the directory itself
+fn secure_delete_dir(dir: &Path, report: &mut DestructionReport) {
+    match fs::symlink_metadata(dir) {
+        Err(_) => return,
+        // A linked-in data or log directory: drop the link, leave its target alone
+        Ok(meta) if meta.file_type().is_symlink() => {
+            if let Err(e) = fs::remove_file(dir) {
+                report.errors.push(format!("{}: {}", dir.display(), e));
+            }
+            return;
+        }
+        Ok(_) => {}
+    }
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            report.errors.push(format!("{}: {}", dir.display(), e));
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // file_type doesn't follow links, so a linked directory isn't descended into
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            secure_delete_dir(&path, report);
+        } else {
+            match secure_delete_file(&path) {
+                Ok(()) => report.files_deleted += 1,
+                Err(e) => report.errors.push(format!("{}: {}", path.display(), e)),
+            }
+        }
+    }
+    match fs::remove_dir(dir) {
+        Ok(()) => report.directories_removed += 1,
+        Err(e) => report.errors.push(format!("{}: {}", dir.display(), e)),
+    }
+}
+
+/// Step two: destroy everything. Background work and file logging are stopped
+/// first; the caller quits the app once it has the report.
+pub fn destroy_all_local_data(
+    app_handle: &tauri::AppHandle,
+    token: &str,
+    confirmation_phrase: &str,
+) -> Result<DestructionReport, String> {
+    if confirmation_phrase.trim() != CONFIRMATION_PHRASE {
+        return Err("Confirmation phrase does not match".to_string());
+    }
+    if !take_valid_token(token) {
+        return Err("Destruction token is invalid or expired -- request a new one".to_string());
+    }
+
+    logging::log_conversation(None, "Destroying all local data");
+    // Nothing may write into the directories while (or after) they're removed
+    jobs::stop();
+    notes::stop_watching();
+    logging::disable_file_logging();
+
+    // Close the database so its files (including -wal/-shm) can be removed
+    let data_dir = profiles::data_dir(app_handle);
+    let profile_ids: Vec<String> = profiles::list(app_handle).profiles.into_iter().map(|p| p.id).collect();
+    // Databases moved to folders of their own; only their files go, not the folders
+    let moved_databases: Vec<PathBuf> = profile_ids.iter()
+        .map(|id| profiles::db_path(app_handle, id))
+        .filter(|path| !path.starts_with(&data_dir))
+        .collect();
+    let mut stray_files: Vec<PathBuf> = moved_databases.iter()
+        .flat_map(|path| std::iter::once(path.clone()).chain(pre_restore_snapshots(path)))
+        .flat_map(|path| db::database_files(&path))
+        .chain(recorded_backups(&data_dir))
+        .collect();
+    stray_files.sort();
+    stray_files.dedup();
+    db::close_database();
+
+    let mut report = DestructionReport::default();
+    for profile_id in &profile_ids {
+        for account in keychain::ALL_KEYS {
+            if let Err(e) = keychain::delete_for_profile(profile_id, account) {
+                report.errors.push(e);
+            }
+        }
+    }
+
+    for path in stray_files.into_iter().filter(|p| p.exists()) {
+        match secure_delete_file(&path) {
+            Ok(()) => report.files_deleted += 1,
+            Err(e) => report.errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    let mut dirs: Vec<PathBuf> = vec![data_dir];
+    for dir in [app_handle.path().app_log_dir().ok(), app_handle.path().app_config_dir().ok()].into_iter().flatten() {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs.push(logging::get_log_dir());
+
+    for dir in dirs {
+        secure_delete_dir(&dir, &mut report);
+    }
+
+    Ok(report)
+}
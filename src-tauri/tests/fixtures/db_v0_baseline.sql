@@ -0,0 +1,136 @@
+-- Intersect database as left by the first public release after its own
+-- migrations ran (disco conversations, persona points, summaries and themes).
+-- Produced by hand from that release's schema; user_version 0.
+
+CREATE TABLE user_profile (
+    id INTEGER PRIMARY KEY,
+    api_key TEXT,
+    anthropic_key TEXT,
+    instinct_weight REAL DEFAULT 0.33,
+    logic_weight REAL DEFAULT 0.33,
+    psyche_weight REAL DEFAULT 0.34,
+    total_messages INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE conversations (
+    id TEXT PRIMARY KEY,
+    title TEXT,
+    summary TEXT,
+    limbo_summary TEXT,
+    processed INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    is_disco INTEGER DEFAULT 0
+);
+
+CREATE TABLE messages (
+    id TEXT PRIMARY KEY,
+    conversation_id TEXT NOT NULL,
+    role TEXT NOT NULL,
+    content TEXT NOT NULL,
+    response_type TEXT,
+    references_message_id TEXT,
+    timestamp TEXT NOT NULL,
+    FOREIGN KEY (conversation_id) REFERENCES conversations(id)
+);
+
+CREATE TABLE user_context (
+    id INTEGER PRIMARY KEY,
+    key TEXT UNIQUE NOT NULL,
+    value TEXT NOT NULL,
+    confidence REAL DEFAULT 0.5,
+    source_agent TEXT,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE user_facts (
+    id INTEGER PRIMARY KEY,
+    category TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    confidence REAL DEFAULT 1.0,
+    source_type TEXT NOT NULL,
+    source_conversation_id TEXT,
+    first_mentioned TEXT NOT NULL,
+    last_confirmed TEXT NOT NULL,
+    mention_count INTEGER DEFAULT 1,
+    UNIQUE(category, key)
+);
+
+CREATE TABLE user_patterns (
+    id INTEGER PRIMARY KEY,
+    pattern_type TEXT NOT NULL,
+    description TEXT NOT NULL,
+    confidence REAL DEFAULT 0.5,
+    evidence TEXT NOT NULL,
+    first_observed TEXT NOT NULL,
+    last_updated TEXT NOT NULL,
+    observation_count INTEGER DEFAULT 1
+);
+
+CREATE TABLE conversation_summaries (
+    id INTEGER PRIMARY KEY,
+    conversation_id TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    key_topics TEXT NOT NULL,
+    emotional_tone TEXT,
+    user_state TEXT,
+    agents_involved TEXT NOT NULL,
+    message_count INTEGER,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (conversation_id) REFERENCES conversations(id)
+);
+
+CREATE TABLE recurring_themes (
+    id INTEGER PRIMARY KEY,
+    theme TEXT NOT NULL UNIQUE,
+    frequency INTEGER DEFAULT 1,
+    last_mentioned TEXT NOT NULL,
+    related_conversations TEXT
+);
+
+CREATE TABLE persona_profiles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    is_default INTEGER DEFAULT 0,
+    is_active INTEGER DEFAULT 0,
+    dominant_trait TEXT NOT NULL,
+    secondary_trait TEXT NOT NULL,
+    instinct_weight REAL DEFAULT 0.2,
+    logic_weight REAL DEFAULT 0.5,
+    psyche_weight REAL DEFAULT 0.3,
+    message_count INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    instinct_points INTEGER DEFAULT 4,
+    logic_points INTEGER DEFAULT 4,
+    psyche_points INTEGER DEFAULT 3
+);
+
+INSERT INTO user_profile VALUES (1, 'sk-openai', 'sk-ant', 0.20, 0.50, 0.30, 318, '2024-06-01T09:00:00+00:00', '2025-01-20T22:40:00+00:00');
+
+INSERT INTO conversations VALUES ('conv-base-1', 'Sleep and the new job', 'Talked through why sleep fell apart after starting the new role.', NULL, 1, '2025-01-18T23:00:00+00:00', '2025-01-18T23:40:00+00:00', 0);
+INSERT INTO conversations VALUES ('conv-base-2', 'Saying no to Sam', NULL, 'User wants to turn down a favour without guilt.', 0, '2025-01-20T22:00:00+00:00', '2025-01-20T22:40:00+00:00', 1);
+INSERT INTO conversations VALUES ('conv-base-3', NULL, NULL, NULL, 0, '2025-01-21T07:00:00+00:00', '2025-01-21T07:00:00+00:00', 0);
+
+INSERT INTO messages VALUES ('msg-b1', 'conv-base-1', 'user', 'I can''t sleep since I started the new job.', NULL, NULL, '2025-01-18T23:00:00+00:00');
+INSERT INTO messages VALUES ('msg-b2', 'conv-base-1', 'psyche', 'What are you bracing for when you lie down?', 'primary', NULL, '2025-01-18T23:00:06+00:00');
+INSERT INTO messages VALUES ('msg-b3', 'conv-base-1', 'logic', 'Track bedtime and screen time for a week -- then we''ll know.', 'addition', 'msg-b2', '2025-01-18T23:00:11+00:00');
+INSERT INTO messages VALUES ('msg-b4', 'conv-base-2', 'user', 'Sam asked me to help him move again. I don''t want to.', NULL, NULL, '2025-01-20T22:00:00+00:00');
+INSERT INTO messages VALUES ('msg-b5', 'conv-base-2', 'instinct', 'Then don''t. One sentence, no excuses.', 'primary', NULL, '2025-01-20T22:00:04+00:00');
+INSERT INTO messages VALUES ('msg-b6', 'conv-base-2', 'psyche', 'Notice the guilt arrives before he''s even asked twice.', 'rebuttal', 'msg-b5', '2025-01-20T22:00:09+00:00');
+
+INSERT INTO user_facts VALUES (1, 'work', 'job', 'product designer', 1.0, 'explicit', 'conv-base-1', '2025-01-18T23:00:00+00:00', '2025-01-20T22:00:00+00:00', 4);
+INSERT INTO user_facts VALUES (2, 'relationships', 'friend', 'Sam', 0.8, 'inferred', 'conv-base-2', '2025-01-20T22:00:00+00:00', '2025-01-20T22:00:00+00:00', 1);
+
+INSERT INTO user_patterns VALUES (1, 'emotional', 'Feels guilty before saying no', 0.6, '["conv-base-2"]', '2025-01-20T22:40:00+00:00', '2025-01-20T22:40:00+00:00', 2);
+
+INSERT INTO conversation_summaries VALUES (1, 'conv-base-1', 'Sleep trouble since the new job; agreed to track a week of evenings.', '["sleep","work"]', 'tired', 'anxious', '["psyche","logic"]', 3, '2025-01-18T23:45:00+00:00');
+
+INSERT INTO recurring_themes VALUES (1, 'sleep', 3, '2025-01-18T23:45:00+00:00', '["conv-base-1"]');
+
+INSERT INTO persona_profiles VALUES ('pp-logic', 'Logic', 1, 1, 'logic', 'logic', 0.30, 0.40, 0.30, 210, '2024-06-01T09:00:00+00:00', '2025-01-20T22:40:00+00:00', 3, 4, 4);
+INSERT INTO persona_profiles VALUES ('pp-instinct', 'Instinct', 0, 0, 'instinct', 'instinct', 0.40, 0.30, 0.30, 60, '2024-06-01T09:00:00+00:00', '2025-01-10T12:00:00+00:00', 4, 3, 4);
+INSERT INTO persona_profiles VALUES ('pp-psyche', 'Psyche', 0, 0, 'psyche', 'psyche', 0.30, 0.30, 0.40, 48, '2024-06-01T09:00:00+00:00', '2025-01-12T12:00:00+00:00', 4, 4, 3);
@@ -0,0 +1,90 @@
+-- Intersect database from before the anthropic_key, limbo_summary and persona
+-- message_count migrations (the oldest shape the migration chain handles).
+-- Produced by hand from that era's schema; user_version 0.
+
+CREATE TABLE user_profile (
+    id INTEGER PRIMARY KEY,
+    api_key TEXT,
+    instinct_weight REAL DEFAULT 0.33,
+    logic_weight REAL DEFAULT 0.33,
+    psyche_weight REAL DEFAULT 0.34,
+    total_messages INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE conversations (
+    id TEXT PRIMARY KEY,
+    title TEXT,
+    summary TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE messages (
+    id TEXT PRIMARY KEY,
+    conversation_id TEXT NOT NULL,
+    role TEXT NOT NULL,
+    content TEXT NOT NULL,
+    response_type TEXT,
+    references_message_id TEXT,
+    timestamp TEXT NOT NULL,
+    FOREIGN KEY (conversation_id) REFERENCES conversations(id)
+);
+
+CREATE TABLE user_context (
+    id INTEGER PRIMARY KEY,
+    key TEXT UNIQUE NOT NULL,
+    value TEXT NOT NULL,
+    confidence REAL DEFAULT 0.5,
+    source_agent TEXT,
+    updated_at TEXT NOT NULL
+);
+
+CREATE TABLE user_facts (
+    id INTEGER PRIMARY KEY,
+    category TEXT NOT NULL,
+    key TEXT NOT NULL,
+    value TEXT NOT NULL,
+    confidence REAL DEFAULT 1.0,
+    source_type TEXT NOT NULL,
+    source_conversation_id TEXT,
+    first_mentioned TEXT NOT NULL,
+    last_confirmed TEXT NOT NULL,
+    mention_count INTEGER DEFAULT 1,
+    UNIQUE(category, key)
+);
+
+CREATE TABLE persona_profiles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    is_default INTEGER DEFAULT 0,
+    is_active INTEGER DEFAULT 0,
+    dominant_trait TEXT NOT NULL,
+    secondary_trait TEXT NOT NULL,
+    instinct_weight REAL DEFAULT 0.2,
+    logic_weight REAL DEFAULT 0.5,
+    psyche_weight REAL DEFAULT 0.3,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+INSERT INTO user_profile VALUES (1, 'sk-legacy', 0.25, 0.45, 0.30, 42, '2024-01-02T09:00:00+00:00', '2024-03-01T21:15:00+00:00');
+
+INSERT INTO conversations VALUES ('conv-legacy-1', 'Moving to Lisbon', NULL, '2024-02-10T18:00:00+00:00', '2024-02-10T18:30:00+00:00');
+INSERT INTO conversations VALUES ('conv-legacy-2', NULL, NULL, '2024-02-11T08:00:00+00:00', '2024-02-11T08:05:00+00:00');
+
+INSERT INTO messages VALUES ('msg-l1', 'conv-legacy-1', 'user', 'I keep going back and forth about moving to Lisbon.', NULL, NULL, '2024-02-10T18:00:00+00:00');
+INSERT INTO messages VALUES ('msg-l2', 'conv-legacy-1', 'logic', 'List what actually changes if you go.', 'primary', NULL, '2024-02-10T18:00:05+00:00');
+INSERT INTO messages VALUES ('msg-l3', 'conv-legacy-1', 'psyche', 'What does staying protect you from?', 'addition', 'msg-l2', '2024-02-10T18:00:09+00:00');
+INSERT INTO messages VALUES ('msg-l4', 'conv-legacy-2', 'user', 'good morning', NULL, NULL, '2024-02-11T08:00:00+00:00');
+INSERT INTO messages VALUES ('msg-l5', 'conv-legacy-2', 'instinct', 'Morning. Coffee first.', 'primary', NULL, '2024-02-11T08:00:03+00:00');
+
+INSERT INTO user_context VALUES (1, 'home_city', 'Porto', 0.7, 'logic', '2024-02-10T18:10:00+00:00');
+
+INSERT INTO user_facts VALUES (1, 'personal', 'city', 'Porto', 1.0, 'explicit', 'conv-legacy-1', '2024-02-10T18:00:00+00:00', '2024-02-10T18:00:00+00:00', 3);
+INSERT INTO user_facts VALUES (2, 'work', 'job', 'architect', 0.9, 'explicit', NULL, '2024-01-05T10:00:00+00:00', '2024-02-01T10:00:00+00:00', 1);
+
+INSERT INTO persona_profiles VALUES ('pp-logic', 'Logic', 1, 1, 'logic', 'logic', 0.30, 0.40, 0.30, '2024-01-02T09:00:00+00:00', '2024-01-02T09:00:00+00:00');
+INSERT INTO persona_profiles VALUES ('pp-instinct', 'Instinct', 0, 0, 'instinct', 'instinct', 0.40, 0.30, 0.30, '2024-01-02T09:00:00+00:00', '2024-01-02T09:00:00+00:00');
+INSERT INTO persona_profiles VALUES ('pp-psyche', 'Psyche', 0, 0, 'psyche', 'psyche', 0.30, 0.30, 0.40, '2024-01-02T09:00:00+00:00', '2024-01-02T09:00:00+00:00');
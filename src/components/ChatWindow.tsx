@@ -486,7 +486,7 @@ export function ChatWindow({ onOpenSettings, recoveryNeeded, onRecoveryComplete
     setThinkingAgent('system'); // Governor is thinking
     
     try {
-      const result = await sendMessage(currentConversation.id, content, activeList, discoList);
+      const result = await sendMessage(currentConversation.id, content, activeList, discoList, userMessage.id);
       
       // Set debate mode if applicable
       if (result.debate_mode) {
@@ -665,7 +665,7 @@ export function ChatWindow({ onOpenSettings, recoveryNeeded, onRecoveryComplete
     setThinkingAgent('system');
     
     try {
-      const result = await sendMessage(currentConversation.id, content, activeList, discoList);
+      const result = await sendMessage(currentConversation.id, content, activeList, discoList, userMessage.id);
       
       if (result.debate_mode) {
         setDebateMode(result.debate_mode as DebateMode);
@@ -1,5 +1,58 @@
 import { invoke } from '@tauri-apps/api/core';
-import { UserProfile, Message, Conversation, UserContext, SendMessageResult, AgentType, PersonaProfile } from '../types';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import { UserProfile, Message, Conversation, UserContext, SendMessageResult, AgentResponse, AgentType, PersonaProfile } from '../types';
+import type { AgentBudget } from '../types/bindings/AgentBudget';
+import type { AgentPresentation } from '../types/bindings/AgentPresentation';
+import type { ArchieEvent } from '../types/bindings/ArchieEvent';
+import type { Attachment } from '../types/bindings/Attachment';
+import type { BackupResult } from '../types/bindings/BackupResult';
+import type { Bookmark } from '../types/bindings/Bookmark';
+import type { BudgetStatus } from '../types/bindings/BudgetStatus';
+import type { Chapter } from '../types/bindings/Chapter';
+import type { ConversationControls } from '../types/bindings/ConversationControls';
+import type { ConversationOpenerResult } from '../types/bindings/ConversationOpenerResult';
+import type { ConversationSettings } from '../types/bindings/ConversationSettings';
+import type { DailySpend } from '../types/bindings/DailySpend';
+import type { DataLocation } from '../types/bindings/DataLocation';
+import type { DataMigrationResult } from '../types/bindings/DataMigrationResult';
+import type { DigestSettings } from '../types/bindings/DigestSettings';
+import type { Disagreement } from '../types/bindings/Disagreement';
+import type { EmailLogEntry } from '../types/bindings/EmailLogEntry';
+import type { FactConflict } from '../types/bindings/FactConflict';
+import type { FactHistoryEntry } from '../types/bindings/FactHistoryEntry';
+import type { FactInfo } from '../types/bindings/FactInfo';
+import type { ImportedConversation } from '../types/bindings/ImportedConversation';
+import type { Insight } from '../types/bindings/Insight';
+import type { LateNightWindow } from '../types/bindings/LateNightWindow';
+import type { LatencyStatus } from '../types/bindings/LatencyStatus';
+import type { MaintenanceReport } from '../types/bindings/MaintenanceReport';
+import type { MemoryImport } from '../types/bindings/MemoryImport';
+import type { MentionCount } from '../types/bindings/MentionCount';
+import type { MessageRevision } from '../types/bindings/MessageRevision';
+import type { MessageSearchResult } from '../types/bindings/MessageSearchResult';
+import type { ModelSpend } from '../types/bindings/ModelSpend';
+import type { MigrationReport } from '../types/bindings/MigrationReport';
+import type { MonthlyHighlights } from '../types/bindings/MonthlyHighlights';
+import type { MoodPoint } from '../types/bindings/MoodPoint';
+import type { NotesStatus } from '../types/bindings/NotesStatus';
+import type { NotesSyncReport } from '../types/bindings/NotesSyncReport';
+import type { PendingBackgroundWork } from '../types/bindings/PendingBackgroundWork';
+import type { Profile } from '../types/bindings/Profile';
+import type { ProfileList } from '../types/bindings/ProfileList';
+import type { Project } from '../types/bindings/Project';
+import type { ProviderLatency } from '../types/bindings/ProviderLatency';
+import type { QueryResult } from '../types/bindings/QueryResult';
+import type { RecurringTheme } from '../types/bindings/RecurringTheme';
+import type { RestoreResult } from '../types/bindings/RestoreResult';
+import type { SeedImport } from '../types/bindings/SeedImport';
+import type { SessionTimer } from '../types/bindings/SessionTimer';
+import type { SupportBundle } from '../types/bindings/SupportBundle';
+import type { Tag } from '../types/bindings/Tag';
+import type { TrashedConversation } from '../types/bindings/TrashedConversation';
+import type { UserFact } from '../types/bindings/UserFact';
+import type { WeeklyReflection } from '../types/bindings/WeeklyReflection';
+import type { WeightPreview } from '../types/bindings/WeightPreview';
 
 // App initialization result
 export interface InitResult {
@@ -20,6 +73,69 @@ export async function initApp(): Promise<InitResult> {
   };
 }
 
+// Local profiles: each has its own database and API keys
+export type { Profile, ProfileList };
+
+export async function getProfiles(): Promise<ProfileList> {
+  return invoke<ProfileList>('get_profiles');
+}
+
+export async function createProfile(name: string): Promise<Profile> {
+  return invoke<Profile>('create_profile', { name });
+}
+
+export async function renameProfile(id: string, name: string): Promise<Profile> {
+  return invoke<Profile>('rename_profile', { id, name });
+}
+
+// The active profile can't be deleted
+export async function deleteProfile(id: string): Promise<void> {
+  await invoke('delete_profile', { id });
+}
+
+// Reopens the app on the other profile's data; reload state afterwards as after initApp
+export async function switchProfile(id: string): Promise<InitResult> {
+  const result = await invoke<{
+    status: string;
+    recovered_count: number;
+  }>('switch_profile', { id });
+
+  return {
+    status: result.status as 'ready' | 'recovery_needed',
+    recoveredCount: result.recovered_count,
+  };
+}
+
+// Backend events arrive on one channel as { version, kind, payload }; the types are generated from Rust
+export type { ArchieEvent } from '../types/bindings/ArchieEvent';
+export type { MigrationReport } from '../types/bindings/MigrationReport';
+export type { TableRowCount } from '../types/bindings/TableRowCount';
+
+type EventKind = ArchieEvent['kind'];
+type EventPayload<K extends EventKind> = Extract<ArchieEvent, { kind: K }>['payload'];
+
+// Every backend event
+export async function onAppEvent(handler: (event: ArchieEvent) => void): Promise<UnlistenFn> {
+  return listen<ArchieEvent>('archie-event', (event) => handler(event.payload));
+}
+
+// Backend events of one kind, with the payload typed for that kind
+export async function onAppEventKind<K extends EventKind>(
+  kind: K,
+  handler: (payload: EventPayload<K>) => void,
+): Promise<UnlistenFn> {
+  return onAppEvent((event) => {
+    if (event.kind === kind) {
+      handler(event.payload as EventPayload<K>);
+    }
+  });
+}
+
+// Database upgrade report, emitted once during init_app. Subscribe before calling initApp so it isn't missed
+export async function onMigrationReport(handler: (report: MigrationReport) => void): Promise<UnlistenFn> {
+  return onAppEventKind('migration-report', handler);
+}
+
 // Recover and finalize any orphaned conversations from crashes/force-quits
 export async function recoverConversations(): Promise<number> {
   return invoke<number>('recover_conversations');
@@ -79,6 +195,19 @@ export async function updateWeights(instinct: number, logic: number, psyche: num
   await invoke('update_weights', { instinct, logic, psyche });
 }
 
+// Try a balance before committing to it: routing plus cheap-model replies (mock uses sample lines), nothing saved
+export type { WeightPreview };
+
+export async function previewWeights(
+  instinct: number,
+  logic: number,
+  psyche: number,
+  sampleMessage: string,
+  mock = false,
+): Promise<WeightPreview> {
+  return invoke<WeightPreview>('preview_weights', { instinct, logic, psyche, sampleMessage, mock });
+}
+
 export async function updatePoints(instinct: number, logic: number, psyche: number): Promise<void> {
   await invoke('update_points', { instinct, logic, psyche });
 }
@@ -194,32 +323,46 @@ export async function getRecentConversations(limit: number): Promise<Conversatio
     title: string | null;
     summary: string | null;
     is_disco: boolean;
+    pinned: boolean;
+    locked: boolean;
+    project_id: number | null;
+    parent_conversation_id: string | null;
     created_at: string;
     updated_at: string;
   }[]>('get_recent_conversations', { limit });
   
+  // Pinned conversations come first
   return convs.map(c => ({
     id: c.id,
     title: c.title,
     summary: c.summary,
     isDisco: c.is_disco,
+    pinned: c.pinned,
+    locked: c.locked,
+    projectId: c.project_id,
+    parentConversationId: c.parent_conversation_id,
     createdAt: new Date(c.created_at),
     updatedAt: new Date(c.updated_at),
   }));
 }
 
-export async function getConversationMessages(conversationId: string): Promise<Message[]> {
-  const messages = await invoke<{
-    id: string;
-    conversation_id: string;
-    role: string;
-    content: string;
-    response_type: string | null;
-    references_message_id: string | null;
-    timestamp: string;
-  }[]>('get_conversation_messages', { conversationId });
-  
-  return messages.map(m => ({
+interface RawMessage {
+  id: string;
+  conversation_id: string;
+  role: string;
+  content: string;
+  response_type: string | null;
+  references_message_id: string | null;
+  timestamp: string;
+  latency_ms: number | null;
+  input_tokens: number | null;
+  output_tokens: number | null;
+  cost_usd: number | null;
+  model: string | null;
+}
+
+function toMessage(m: RawMessage): Message {
+  return {
     id: m.id,
     conversationId: m.conversation_id,
     role: m.role as Message['role'],
@@ -227,141 +370,1235 @@ export async function getConversationMessages(conversationId: string): Promise<M
     responseType: m.response_type as Message['responseType'],
     referencesMessageId: m.references_message_id || undefined,
     timestamp: new Date(m.timestamp),
-  }));
+    latencyMs: m.latency_ms ?? undefined,
+    cost: m.cost_usd != null
+      ? { model: m.model ?? '', input_tokens: m.input_tokens ?? 0, output_tokens: m.output_tokens ?? 0, cost_usd: m.cost_usd }
+      : undefined,
+  };
 }
 
-export async function clearConversation(conversationId: string): Promise<void> {
-  await invoke('clear_conversation', { conversationId });
+export async function getConversationMessages(conversationId: string): Promise<Message[]> {
+  const messages = await invoke<RawMessage[]>('get_conversation_messages', { conversationId });
+  return messages.map(toMessage);
 }
 
-export async function deleteConversation(conversationId: string): Promise<void> {
-  await invoke('delete_conversation', { conversationId });
+export interface MessagePage {
+  messages: Message[]; // Oldest first
+  hasMore: boolean;    // Older messages remain before messages[0]
 }
 
-export async function reopenConversation(conversationId: string): Promise<ConversationOpenerResult> {
-  return invoke<ConversationOpenerResult>('reopen_conversation', { conversationId });
+// Newest page when beforeId is omitted; pass the oldest loaded message id to lazy-load further back
+export async function getMessagesPage(conversationId: string, beforeId?: string, limit?: number): Promise<MessagePage> {
+  const page = await invoke<{ messages: RawMessage[]; has_more: boolean }>('get_messages_page', {
+    conversationId,
+    beforeId: beforeId || null,
+    limit: limit ?? null,
+  });
+  return { messages: page.messages.map(toMessage), hasMore: page.has_more };
 }
 
-export async function finalizeConversation(conversationId: string): Promise<void> {
-  await invoke('finalize_conversation', { conversationId });
+// ============ Search ============
+
+export type { MessageSearchResult };
+
+export async function searchMessages(query: string, limit?: number): Promise<MessageSearchResult[]> {
+  return invoke<MessageSearchResult[]>('search_messages', { query, limit: limit ?? null });
 }
 
-// Conversation opener result
-export interface ConversationOpenerResult {
-  agent: string;
+// ============ Bookmarks ============
+
+export type { Bookmark };
+
+export interface BookmarkFilter {
+  conversationId?: string;
+  role?: string;
+  query?: string;
+  limit?: number;
+}
+
+// Bookmarking an already-bookmarked message replaces its note
+export async function bookmarkMessage(messageId: string, note?: string): Promise<void> {
+  return invoke('bookmark_message', { messageId, note: note ?? null });
+}
+
+export async function removeBookmark(messageId: string): Promise<boolean> {
+  return invoke<boolean>('remove_bookmark', { messageId });
+}
+
+export async function getBookmarks(filter: BookmarkFilter = {}): Promise<Bookmark[]> {
+  return invoke<Bookmark[]>('get_bookmarks', {
+    conversationId: filter.conversationId ?? null,
+    role: filter.role ?? null,
+    query: filter.query ?? null,
+    limit: filter.limit ?? null,
+  });
+}
+
+export async function getBookmarksInContext(): Promise<boolean> {
+  return invoke<boolean>('get_bookmarks_in_context');
+}
+
+export async function setBookmarksInContext(enabled: boolean): Promise<void> {
+  return invoke('set_bookmarks_in_context', { enabled });
+}
+
+// ============ Conversation Export ============
+
+export type ExportFormat = 'markdown' | 'json';
+
+const EXPORT_COMMANDS: Record<ExportFormat, string> = {
+  markdown: 'export_conversation_markdown',
+  json: 'export_conversation_json',
+};
+
+// Rendered Markdown; also written to path when one is given
+export async function exportConversationMarkdown(conversationId: string, path?: string): Promise<string> {
+  return invoke<string>('export_conversation_markdown', { conversationId, path: path || null });
+}
+
+// Lossless JSON ({ format_version, exported_at, conversation, messages }); also written to path when given
+export async function exportConversationJson(conversationId: string, path?: string): Promise<string> {
+  return invoke<string>('export_conversation_json', { conversationId, path: path || null });
+}
+
+// Paginated transcript for archiving; the appendix lists memory facts the agents cited
+export async function exportConversationPdf(conversationId: string, path: string, includeCitations = false): Promise<string> {
+  return invoke<string>('export_conversation_pdf', { conversationId, path, includeCitations });
+}
+
+// Facts, patterns, summaries, themes and user context in one JSON document
+export async function exportMemory(path?: string): Promise<string> {
+  return invoke<string>('export_memory', { path: path || null });
+}
+
+export type { MemoryImport };
+
+export type MemoryImportStrategy = 'replace' | 'merge-keep-higher-confidence' | 'merge-newest-wins';
+
+// Restore or combine a memory export (the file's contents)
+export async function importMemory(json: string, strategy: MemoryImportStrategy): Promise<MemoryImport> {
+  return invoke<MemoryImport>('import_memory', { json, strategy });
+}
+
+export type { ImportedConversation };
+
+// Restore a conversation from the contents of a JSON export
+export async function importConversation(json: string): Promise<ImportedConversation> {
+  return invoke<ImportedConversation>('import_conversation', { json });
+}
+
+// Ask where to save, then write the file; null if the user cancelled
+export async function saveConversationExport(conversationId: string, suggestedName: string, format: ExportFormat = 'markdown'): Promise<string | null> {
+  const extension = format === 'json' ? 'json' : 'md';
+  const path = await save({
+    defaultPath: `${suggestedName.replace(/[\\/:*?"<>|]/g, '-')}.${extension}`,
+    filters: [{ name: format === 'json' ? 'JSON' : 'Markdown', extensions: [extension] }],
+  });
+  if (!path) return null;
+  await invoke<string>(EXPORT_COMMANDS[format], { conversationId, path });
+  return path;
+}
+
+// ============ Weekly Digest ============
+
+export type { DigestSettings, WeeklyReflection, MonthlyHighlights, EmailLogEntry };
+
+export async function getDigestSettings(): Promise<DigestSettings> {
+  return invoke<DigestSettings>('get_digest_settings');
+}
+
+// Omitted values are left unchanged; "" clears the address
+export async function setDigestSettings(settings: { enabled?: boolean; emailTo?: string }): Promise<DigestSettings> {
+  return invoke<DigestSettings>('set_digest_settings', {
+    enabled: settings.enabled ?? null,
+    emailTo: settings.emailTo ?? null,
+  });
+}
+
+export async function getWeeklyReflections(limit?: number): Promise<WeeklyReflection[]> {
+  return invoke<WeeklyReflection[]>('get_weekly_reflections', { limit: limit ?? null });
+}
+
+export async function getEmailLog(): Promise<EmailLogEntry[]> {
+  return invoke<EmailLogEntry[]>('get_email_log');
+}
+
+export async function getMonthlyHighlights(limit?: number): Promise<MonthlyHighlights[]> {
+  return invoke<MonthlyHighlights[]>('get_monthly_highlights', { limit: limit ?? null });
+}
+
+/** Write (or rewrite) a month's highlights now; null when nothing that month stood out */
+export async function generateMonthlyHighlights(month: string): Promise<MonthlyHighlights | null> {
+  return invoke<MonthlyHighlights | null>('generate_monthly_highlights', { month });
+}
+
+export async function exportHighlightsMarkdown(month: string, path?: string): Promise<string> {
+  return invoke<string>('export_highlights_markdown', { month, path: path ?? null });
+}
+
+/** Ask where to save a month's highlights and write them there. Returns the path, or null if cancelled. */
+export async function saveHighlightsExport(month: string): Promise<string | null> {
+  const path = await save({
+    defaultPath: `Highlights ${month}.md`,
+    filters: [{ name: 'Markdown', extensions: ['md'] }],
+  });
+  if (!path) return null;
+  await exportHighlightsMarkdown(month, path);
+  return path;
+}
+
+// ============ Notes Folder ============
+
+export type { NotesStatus, NotesSyncReport };
+
+export interface StoredDocument {
+  id: string;
+  source: string; // 'notes_folder' | 'distilled'
+  path: string;
+  title: string;
   content: string;
+  file_modified_at: string | null;
+  created_at: string;
+  updated_at: string;
 }
 
-// Conversation opener
-export async function getConversationOpener(): Promise<ConversationOpenerResult> {
-  return invoke<ConversationOpenerResult>('get_conversation_opener');
+export async function getNotesFolderStatus(): Promise<NotesStatus> {
+  return invoke<NotesStatus>('get_notes_folder_status');
 }
 
-// Send message
-export async function sendMessage(
-  conversationId: string,
-  userMessage: string,
-  activeAgents: AgentType[],
-  discoAgents: AgentType[] = []
-): Promise<SendMessageResult> {
-  return invoke<SendMessageResult>('send_message', {
-    conversationId,
-    userMessage,
-    activeAgents,
-    discoAgents,
+// Markdown/text files in the folder are ingested on the background job schedule; null stops watching
+export async function setNotesFolder(path: string | null): Promise<NotesStatus> {
+  return invoke<NotesStatus>('set_notes_folder', { path });
+}
+
+export async function syncNotesFolder(): Promise<NotesSyncReport> {
+  return invoke<NotesSyncReport>('sync_notes_folder');
+}
+
+export async function getDocuments(source?: string): Promise<StoredDocument[]> {
+  return invoke<StoredDocument[]>('get_documents', { source: source ?? null });
+}
+
+export type DistillFormat = 'decision_memo' | 'action_list' | 'essay_outline';
+
+/** Distill a conversation into a document; re-distilling to the same format replaces it */
+export async function distillConversation(conversationId: string, format: DistillFormat): Promise<StoredDocument> {
+  return invoke<StoredDocument>('distill_conversation', { conversationId, format });
+}
+
+export async function exportDocument(documentId: string, path?: string): Promise<string> {
+  return invoke<string>('export_document', { documentId, path: path ?? null });
+}
+
+/** Ask where to save a document as Markdown and write it there. Returns the path, or null if cancelled. */
+export async function saveDocumentExport(document: StoredDocument): Promise<string | null> {
+  const path = await save({
+    defaultPath: `${document.title.replace(/[\\/:*?"<>|]/g, '-')}.md`,
+    filters: [{ name: 'Markdown', extensions: ['md'] }],
   });
+  if (!path) return null;
+  await exportDocument(document.id, path);
+  return path;
 }
 
-// User context
-export async function getUserContext(): Promise<UserContext[]> {
-  const contexts = await invoke<{
-    id: number;
-    key: string;
-    value: string;
-    confidence: number;
-    source_agent: string | null;
+// ============ Conversation Tags ============
+
+export type { Tag };
+
+export async function getTags(): Promise<Tag[]> {
+  return invoke<Tag[]>('get_tags');
+}
+
+// Returns the existing tag's id if the name is already taken
+export async function addTag(name: string): Promise<number> {
+  return invoke<number>('add_tag', { name });
+}
+
+export async function deleteTag(id: number): Promise<void> {
+  await invoke('delete_tag', { id });
+}
+
+export async function tagConversation(conversationId: string, tagId: number): Promise<void> {
+  await invoke('tag_conversation', { conversationId, tagId });
+}
+
+export async function untagConversation(conversationId: string, tagId: number): Promise<void> {
+  await invoke('untag_conversation', { conversationId, tagId });
+}
+
+export async function getConversationTags(conversationId: string): Promise<Tag[]> {
+  return invoke<Tag[]>('get_conversation_tags', { conversationId });
+}
+
+export async function getConversationsByTag(tagId: number): Promise<Conversation[]> {
+  const convs = await invoke<{
+    id: string;
+    title: string | null;
+    summary: string | null;
+    is_disco: boolean;
+    pinned: boolean;
+    locked: boolean;
+    project_id: number | null;
+    parent_conversation_id: string | null;
+    created_at: string;
     updated_at: string;
-  }[]>('get_user_context');
-  
-  return contexts.map(c => ({
+  }[]>('get_conversations_by_tag', { tagId });
+
+  return convs.map(c => ({
     id: c.id,
-    key: c.key,
-    value: c.value,
-    confidence: c.confidence,
-    sourceAgent: c.source_agent,
+    title: c.title,
+    summary: c.summary,
+    isDisco: c.is_disco,
+    pinned: c.pinned,
+    locked: c.locked,
+    projectId: c.project_id,
+    parentConversationId: c.parent_conversation_id,
+    createdAt: new Date(c.created_at),
     updatedAt: new Date(c.updated_at),
   }));
 }
 
-export async function clearUserContext(): Promise<void> {
-  await invoke('clear_user_context');
-}
+// ============ Projects ============
 
-// ============ Memory System ============
+export type { Project };
 
-export interface FactInfo {
-  category: string;
-  key: string;
-  value: string;
-  confidence: number;
+export async function getProjects(): Promise<Project[]> {
+  return invoke<Project[]>('get_projects');
 }
 
-export interface PatternInfo {
-  patternType: string;
-  description: string;
-  confidence: number;
+export async function createProject(name: string, description?: string): Promise<Project> {
+  return invoke<Project>('create_project', { name, description: description ?? null });
 }
 
-export interface MemoryStats {
-  factCount: number;
-  patternCount: number;
-  themeCount: number;
-  topFacts: FactInfo[];
-  topPatterns: PatternInfo[];
-  topThemes: string[];
+// Replaces the description too; omit it to clear
+export async function updateProject(id: number, name: string, description?: string): Promise<Project> {
+  return invoke<Project>('update_project', { id, name, description: description ?? null });
 }
 
-export async function getMemoryStats(): Promise<MemoryStats> {
-  const stats = await invoke<{
-    fact_count: number;
-    pattern_count: number;
-    theme_count: number;
-    top_facts: { category: string; key: string; value: string; confidence: number }[];
-    top_patterns: { pattern_type: string; description: string; confidence: number }[];
-    top_themes: string[];
-  }>('get_memory_stats');
-  
-  return {
-    factCount: stats.fact_count,
-    patternCount: stats.pattern_count,
-    themeCount: stats.theme_count,
-    topFacts: stats.top_facts.map(f => ({
-      category: f.category,
-      key: f.key,
-      value: f.value,
-      confidence: f.confidence,
-    })),
-    topPatterns: stats.top_patterns.map(p => ({
-      patternType: p.pattern_type,
-      description: p.description,
-      confidence: p.confidence,
-    })),
-    topThemes: stats.top_themes,
-  };
+// The project's conversations are kept and become unfiled
+export async function deleteProject(id: number): Promise<void> {
+  await invoke('delete_project', { id });
 }
 
-export async function getUserProfileSummary(): Promise<string> {
-  return invoke<string>('get_user_profile_summary');
+// Pass null to take the conversation out of its project
+export async function setConversationProject(conversationId: string, projectId: number | null): Promise<void> {
+  await invoke('set_conversation_project', { conversationId, projectId });
 }
 
-// Governor Report (LLM-generated from knowledge base)
-export async function generateGovernorReport(profileId?: string): Promise<string> {
-  return invoke<string>('generate_governor_report', { profileId: profileId || null });
+export async function getConversationsByProject(projectId: number): Promise<Conversation[]> {
+  const convs = await invoke<{
+    id: string;
+    title: string | null;
+    summary: string | null;
+    is_disco: boolean;
+    pinned: boolean;
+    locked: boolean;
+    project_id: number | null;
+    parent_conversation_id: string | null;
+    created_at: string;
+    updated_at: string;
+  }[]>('get_conversations_by_project', { projectId });
+
+  return convs.map(c => ({
+    id: c.id,
+    title: c.title,
+    summary: c.summary,
+    isDisco: c.is_disco,
+    pinned: c.pinned,
+    locked: c.locked,
+    projectId: c.project_id,
+    parentConversationId: c.parent_conversation_id,
+    createdAt: new Date(c.created_at),
+    updatedAt: new Date(c.updated_at),
+  }));
 }
 
-// 3-Sentence User Summary
-export async function generateUserSummary(): Promise<string> {
-  return invoke<string>('generate_user_summary');
+// ============ Archive Import ============
+
+export interface ArchiveImport {
+  id: string;
+  source_path: string;
+  conversation_count: number;
+  message_count: number;
+  processed_count: number;
+  estimated_cost_usd: number; // Up-front ceiling for the whole analysis
+  cost_usd: number;
+  status: 'pending' | 'running' | 'paused' | 'review' | 'failed' | 'released' | 'discarded';
+  themes: string; // JSON map of theme -> conversation ids
+  error: string | null;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface HeldMemoryItem {
+  id: number;
+  kind: 'fact' | 'pattern';
+  category: string;
+  key: string;
+  value: string;
+  confidence: number;
+  source_type: string | null;
+  source_conversation_id: string | null;
+  first_seen: string;
+  last_seen: string;
+  confirmations: number;
+  held_by_import: string | null;
+}
+
+export type { MentionCount };
+
+export interface ImportReport {
+  import: ArchiveImport;
+  facts: HeldMemoryItem[];
+  patterns: HeldMemoryItem[];
+  themes: MentionCount[];
+  topics: MentionCount[];
+}
+
+// Archive format: [{ title, created_at, messages: [{ role, content, timestamp }] }]
+export async function importChatArchive(path: string): Promise<ArchiveImport> {
+  return invoke<ArchiveImport>('import_chat_archive', { path });
+}
+
+export async function getArchiveImports(): Promise<ArchiveImport[]> {
+  return invoke<ArchiveImport[]>('get_archive_imports');
+}
+
+// Progress arrives as "archive-import-progress" / "archive-import-complete" events
+export async function startArchiveAnalysis(importId: string): Promise<ArchiveImport> {
+  return invoke<ArchiveImport>('start_archive_analysis', { importId });
+}
+
+export async function pauseArchiveAnalysis(importId: string): Promise<void> {
+  return invoke('pause_archive_analysis', { importId });
+}
+
+export async function getArchiveImportReport(importId: string): Promise<ImportReport> {
+  return invoke<ImportReport>('get_archive_import_report', { importId });
+}
+
+// Hands the held items to normal staging; returns how many were released
+export async function releaseArchiveImport(importId: string): Promise<number> {
+  return invoke<number>('release_archive_import', { importId });
+}
+
+export async function discardArchiveImport(importId: string): Promise<number> {
+  return invoke<number>('discard_archive_import', { importId });
+}
+
+// ============ Model Routing ============
+
+export type ModelTier = 'economy' | 'premium';
+
+export interface AgentModelPolicy {
+  agent: string;
+  policy: 'adaptive' | ModelTier;
+}
+
+export interface ModelDecision {
+  id: number;
+  conversation_id: string | null;
+  agent: string;
+  complexity: 'simple' | 'moderate' | 'hard';
+  tier: ModelTier;
+  model: string;
+  reason: string;
+  created_at: string;
+}
+
+export async function getModelPolicies(): Promise<AgentModelPolicy[]> {
+  return invoke<AgentModelPolicy[]>('get_model_policies');
+}
+
+export async function setAgentModelPolicy(agent: string, policy: AgentModelPolicy['policy']): Promise<AgentModelPolicy[]> {
+  return invoke<AgentModelPolicy[]>('set_agent_model_policy', { agent, policy });
+}
+
+// null goes back to the per-agent policies
+export async function setConversationModelTier(conversationId: string, tier: ModelTier | null): Promise<void> {
+  await invoke('set_conversation_model_tier', { conversationId, tier });
+}
+
+export async function getConversationModelTier(conversationId: string): Promise<ModelTier | null> {
+  return invoke<ModelTier | null>('get_conversation_model_tier', { conversationId });
+}
+
+// Mode, mutes, verbosity and incognito set by "/mode", "/mute", "/verbosity" and "/incognito"
+export type { ConversationControls };
+
+export async function getConversationControls(conversationId: string): Promise<ConversationControls> {
+  return invoke<ConversationControls>('get_conversation_controls', { conversationId });
+}
+
+// Model tier, mode and agent weights for one conversation
+export type { ConversationSettings };
+
+export async function getConversationSettings(conversationId: string): Promise<ConversationSettings> {
+  return invoke<ConversationSettings>('get_conversation_settings', { conversationId });
+}
+
+/** Replaces every override; weights are all three or none and are scaled to sum to 1 */
+export async function setConversationSettings(
+  conversationId: string,
+  settings: { modelTier?: ModelTier | null; mode?: 'disco' | 'normal' | null; weights?: { instinct: number; logic: number; psyche: number } | null },
+): Promise<ConversationSettings> {
+  return invoke<ConversationSettings>('set_conversation_settings', {
+    conversationId,
+    modelTier: settings.modelTier ?? null,
+    mode: settings.mode ?? null,
+    instinctWeight: settings.weights?.instinct ?? null,
+    logicWeight: settings.weights?.logic ?? null,
+    psycheWeight: settings.weights?.psyche ?? null,
+  });
+}
+
+export async function getModelDecisions(conversationId?: string, limit?: number): Promise<ModelDecision[]> {
+  return invoke<ModelDecision[]>('get_model_decisions', {
+    conversationId: conversationId ?? null,
+    limit: limit ?? null,
+  });
+}
+
+// ============ Latency SLO ============
+
+export type { ProviderLatency, LatencyStatus };
+
+export async function getLatencyStatus(): Promise<LatencyStatus> {
+  return invoke<LatencyStatus>('get_latency_status');
+}
+
+// 0 turns automatic provider switching off
+export async function setLatencySlo(thresholdMs: number): Promise<LatencyStatus> {
+  return invoke<LatencyStatus>('set_latency_slo', { thresholdMs });
+}
+
+// ============ Insights ============
+
+export type { Insight };
+
+export async function getInsights(includeDismissed = false, limit?: number): Promise<Insight[]> {
+  return invoke<Insight[]>('get_insights', { includeDismissed, limit: limit ?? null });
+}
+
+export async function dismissInsight(id: number): Promise<void> {
+  await invoke('dismiss_insight', { id });
+}
+
+// ============ Action Items ============
+
+export interface ActionItem {
+  id: number;
+  conversation_id: string;
+  message_id: string | null; // Source message; null once it's deleted
+  text: string;
+  owner: string; // 'user' or the agent that proposed it
+  due: string | null; // As said in the conversation, e.g. 'Friday'
+  status: 'open' | 'done';
+  snoozed_until: string | null;
+  created_at: string;
+  completed_at: string | null;
+}
+
+export async function getActionItems(status?: 'open' | 'done', conversationId?: string): Promise<ActionItem[]> {
+  return invoke<ActionItem[]>('get_action_items', { status: status ?? null, conversationId: conversationId ?? null });
+}
+
+export async function completeActionItem(id: number, done = true): Promise<ActionItem> {
+  return invoke<ActionItem>('complete_action_item', { id, done });
+}
+
+/** Keep an item out of the agents' session context for a few days (0 wakes it now) */
+export async function snoozeActionItem(id: number, days: number): Promise<ActionItem> {
+  return invoke<ActionItem>('snooze_action_item', { id, days });
+}
+
+// ============ Chapters ============
+
+export type { Chapter };
+
+export async function getChapters(): Promise<Chapter[]> {
+  return invoke<Chapter[]>('get_chapters');
+}
+
+/** Dates are YYYY-MM-DD; leave endDate out while the chapter is still going */
+export async function createChapter(title: string, startDate: string, endDate?: string, description?: string): Promise<Chapter> {
+  return invoke<Chapter>('create_chapter', { title, startDate, endDate: endDate ?? null, description: description ?? null });
+}
+
+export async function updateChapter(id: number, title: string, startDate: string, endDate?: string, description?: string): Promise<Chapter> {
+  return invoke<Chapter>('update_chapter', { id, title, startDate, endDate: endDate ?? null, description: description ?? null });
+}
+
+/** Detected chapters stay dismissed rather than coming back */
+export async function deleteChapter(id: number): Promise<void> {
+  return invoke('delete_chapter', { id });
+}
+
+/** Place a conversation in a chapter (or take it out) regardless of dates */
+export async function setChapterConversation(chapterId: number, conversationId: string, linked = true): Promise<Chapter> {
+  return invoke<Chapter>('set_chapter_conversation', { chapterId, conversationId, linked });
+}
+
+/** Run chapter detection now; returns how many were added */
+export async function detectChapters(): Promise<number> {
+  return invoke<number>('detect_chapters');
+}
+
+// ============ Disagreements ============
+
+export type { Disagreement };
+
+/** Where the agents split, in one conversation or across all of them (newest first) */
+export async function getDisagreements(conversationId?: string, limit?: number): Promise<Disagreement[]> {
+  return invoke<Disagreement[]>('get_disagreements', { conversationId: conversationId ?? null, limit: limit ?? null });
+}
+
+// ============ Agent Budgets ============
+
+export type { AgentBudget };
+
+export interface BudgetSettings {
+  agents: AgentBudget[];
+  mode: 'silent' | 'local';
+  local_model_url: string | null;
+  local_model_name: string | null;
+}
+
+export async function getAgentBudgets(): Promise<BudgetSettings> {
+  return invoke<BudgetSettings>('get_agent_budgets');
+}
+
+// null or 0 removes the agent's limit
+export async function setAgentBudget(agent: string, dailyTokens: number | null): Promise<BudgetSettings> {
+  return invoke<BudgetSettings>('set_agent_budget', { agent, dailyTokens });
+}
+
+// "local" needs an OpenAI-compatible chat completions URL and a model name
+export async function setBudgetFallback(
+  mode: 'silent' | 'local',
+  localModelUrl?: string,
+  localModelName?: string,
+): Promise<BudgetSettings> {
+  return invoke<BudgetSettings>('set_budget_fallback', {
+    mode,
+    localModelUrl: localModelUrl ?? null,
+    localModelName: localModelName ?? null,
+  });
+}
+
+// ============ Monthly Budget ============
+
+export type { BudgetStatus };
+
+export async function getBudgetStatus(): Promise<BudgetStatus> {
+  return invoke<BudgetStatus>('get_budget_status');
+}
+
+// null or 0 removes that cap; "block" refuses hosted calls once a cap is reached, "warn" only flags it
+export async function setMonthlyBudget(
+  capUsd: number | null,
+  capTokens: number | null,
+  mode: 'block' | 'warn' = 'block',
+): Promise<BudgetStatus> {
+  return invoke<BudgetStatus>('set_monthly_budget', { capUsd, capTokens, mode });
+}
+
+// ============ Background Work ============
+
+// Paid background jobs wait until this launch's summary is approved
+export type { PendingBackgroundWork };
+
+export async function getPendingBackgroundWork(): Promise<PendingBackgroundWork> {
+  return invoke<PendingBackgroundWork>('get_pending_background_work');
+}
+
+export async function approveBackgroundWork(): Promise<PendingBackgroundWork> {
+  return invoke<PendingBackgroundWork>('approve_background_work');
+}
+
+// Holds every paid job for a day
+export async function deferBackgroundWork(): Promise<PendingBackgroundWork> {
+  return invoke<PendingBackgroundWork>('defer_background_work');
+}
+
+// ============ Plain Language ============
+
+// Reading level is a school grade (3-12); null turns plain language off for the profile
+export async function setProfileReadingLevel(profileId: string, level: number | null): Promise<void> {
+  return invoke('set_profile_reading_level', { profileId, level });
+}
+
+// Per-conversation override: a grade, 0 for off, or null to follow the profile
+export async function setConversationReadingLevel(conversationId: string, level: number | null): Promise<void> {
+  return invoke('set_conversation_reading_level', { conversationId, level });
+}
+
+export async function getReadingLevel(conversationId: string): Promise<number | null> {
+  return invoke<number | null>('get_reading_level', { conversationId });
+}
+
+// ============ Late-Night Mode ============
+
+export type { LateNightWindow };
+
+export async function getLateNightWindow(profileId: string): Promise<LateNightWindow | null> {
+  return invoke<LateNightWindow | null>('get_late_night_window', { profileId });
+}
+
+// Local "HH:MM" times (the window may wrap past midnight); pass nulls to turn it off
+export async function setLateNightWindow(profileId: string, start: string | null, end: string | null): Promise<void> {
+  return invoke('set_late_night_window', { profileId, start, end });
+}
+
+// ============ Session Timer ============
+
+export type { SessionTimer };
+
+export async function getSessionTimer(conversationId: string): Promise<SessionTimer> {
+  return invoke<SessionTimer>('get_session_timer', { conversationId });
+}
+
+// Minutes for this session, or null/0 for no budget
+export async function setSessionTimeBudget(conversationId: string, minutes: number | null): Promise<SessionTimer> {
+  return invoke<SessionTimer>('set_session_time_budget', { conversationId, minutes });
+}
+
+export async function restartSessionTimer(conversationId: string): Promise<SessionTimer> {
+  return invoke<SessionTimer>('restart_session_timer', { conversationId });
+}
+
+export async function getDefaultSessionTimeBudget(): Promise<number | null> {
+  return invoke<number | null>('get_default_session_time_budget');
+}
+
+export async function setDefaultSessionTimeBudget(minutes: number | null): Promise<void> {
+  return invoke('set_default_session_time_budget', { minutes });
+}
+
+// ============ Localization ============
+
+export interface LocaleBundle {
+  locale: string;
+  available: string[];
+  strings: Record<string, string>;
+}
+
+export async function getLocaleBundle(locale?: string): Promise<LocaleBundle> {
+  return invoke<LocaleBundle>('get_locale_bundle', { locale: locale || null });
+}
+
+// Also switches built-in prompts and agent names; returns the new bundle
+export async function setLanguage(locale: string): Promise<LocaleBundle> {
+  return invoke<LocaleBundle>('set_language', { locale });
+}
+
+// Pass null to restore the bundled value. Prompt overrides use "prompt.*" keys.
+export async function setLocaleString(locale: string, key: string, value: string | null): Promise<void> {
+  return invoke('set_locale_string', { locale, key, value });
+}
+
+// ============ Agent Manifest ============
+
+// Presentation metadata served from the agents table (icon is an asset key)
+export type { AgentPresentation };
+
+export async function getAgentManifest(): Promise<AgentPresentation[]> {
+  return invoke<AgentPresentation[]>('get_agent_manifest');
+}
+
+export async function updateAgentPresentation(
+  id: string,
+  changes: {
+    displayName?: string;
+    discoName?: string;
+    color?: string;
+    discoColor?: string;
+    icon?: string;
+    description?: string;
+    voiceSamples?: string[];
+  }
+): Promise<AgentPresentation> {
+  return invoke<AgentPresentation>('update_agent_presentation', {
+    id,
+    displayName: changes.displayName ?? null,
+    discoName: changes.discoName ?? null,
+    color: changes.color ?? null,
+    discoColor: changes.discoColor ?? null,
+    icon: changes.icon ?? null,
+    description: changes.description ?? null,
+    voiceSamples: changes.voiceSamples ?? null,
+  });
+}
+
+// Replays a conversation with its original pacing via replay-start/typing/message/complete events
+export async function replayConversation(conversationId: string, speed?: number): Promise<number> {
+  return invoke<number>('replay_conversation', { conversationId, speed: speed ?? null });
+}
+
+export async function clearConversation(conversationId: string): Promise<void> {
+  await invoke('clear_conversation', { conversationId });
+}
+
+// Returns how many messages were removed (more than one when replies go too)
+export async function deleteMessage(messageId: string, includeReplies = false): Promise<number> {
+  return invoke<number>('delete_message', { messageId, includeReplies });
+}
+
+export async function setConversationPinned(conversationId: string, pinned: boolean): Promise<void> {
+  await invoke('set_conversation_pinned', { conversationId, pinned });
+}
+
+export async function setConversationLocked(conversationId: string, locked: boolean): Promise<void> {
+  await invoke('set_conversation_locked', { conversationId, locked });
+}
+
+// Copies the conversation up to and including fromMessageId into a new one; the original is untouched
+export async function forkConversation(conversationId: string, fromMessageId: string): Promise<Conversation> {
+  const c = await invoke<{
+    id: string;
+    title: string | null;
+    summary: string | null;
+    is_disco: boolean;
+    pinned: boolean;
+    locked: boolean;
+    project_id: number | null;
+    parent_conversation_id: string | null;
+    created_at: string;
+    updated_at: string;
+  }>('fork_conversation', { conversationId, fromMessageId });
+
+  return {
+    id: c.id,
+    title: c.title,
+    summary: c.summary,
+    isDisco: c.is_disco,
+    pinned: c.pinned,
+    locked: c.locked,
+    projectId: c.project_id,
+    parentConversationId: c.parent_conversation_id,
+    createdAt: new Date(c.created_at),
+    updatedAt: new Date(c.updated_at),
+  };
+}
+
+export async function deleteConversation(conversationId: string): Promise<void> {
+  await invoke('delete_conversation', { conversationId });
+}
+
+export type { TrashedConversation };
+
+// Trashed conversations are hidden from lists and search, and purged after 30 days
+export async function trashConversation(conversationId: string): Promise<boolean> {
+  return invoke<boolean>('trash_conversation', { conversationId });
+}
+
+export async function restoreConversation(conversationId: string): Promise<boolean> {
+  return invoke<boolean>('restore_conversation', { conversationId });
+}
+
+export async function getTrashedConversations(): Promise<TrashedConversation[]> {
+  return invoke<TrashedConversation[]>('get_trashed_conversations');
+}
+
+export async function emptyTrash(): Promise<number> {
+  return invoke<number>('empty_trash');
+}
+
+export async function reopenConversation(conversationId: string): Promise<ConversationOpenerResult> {
+  return invoke<ConversationOpenerResult>('reopen_conversation', { conversationId });
+}
+
+export async function finalizeConversation(conversationId: string): Promise<void> {
+  await invoke('finalize_conversation', { conversationId });
+}
+
+// Conversation opener result
+export type { ConversationOpenerResult };
+
+// Conversation opener
+export async function getConversationOpener(): Promise<ConversationOpenerResult> {
+  return invoke<ConversationOpenerResult>('get_conversation_opener');
+}
+
+// Send message
+export async function sendMessage(
+  conversationId: string,
+  userMessage: string,
+  activeAgents: AgentType[],
+  discoAgents: AgentType[] = [],
+  idempotencyKey?: string,
+  attachmentPaths?: string[]
+): Promise<SendMessageResult> {
+  return invoke<SendMessageResult>('send_message', {
+    conversationId,
+    userMessage,
+    activeAgents,
+    discoAgents,
+    idempotencyKey,
+    attachmentPaths,
+  });
+}
+
+// Retry one agent whose slot in a round failed
+export async function retryAgent(roundId: string, agent: AgentType): Promise<AgentResponse> {
+  return invoke<AgentResponse>('retry_agent', { roundId, agent });
+}
+
+// Retry a user message whose send failed
+export async function retryMessage(
+  messageId: string,
+  activeAgents?: AgentType[],
+  discoAgents?: AgentType[]
+): Promise<SendMessageResult> {
+  return invoke<SendMessageResult>('retry_message', {
+    messageId,
+    activeAgents,
+    discoAgents,
+  });
+}
+
+export type { MessageRevision };
+
+// Edit one of your own messages; the old text is kept as a revision
+export async function updateMessageContent(messageId: string, newContent: string): Promise<void> {
+  await invoke('update_message_content', { messageId, newContent });
+}
+
+export async function getMessageRevisions(messageId: string): Promise<MessageRevision[]> {
+  return invoke<MessageRevision[]>('get_message_revisions', { messageId });
+}
+
+// Files attached to messages; text-like files are read into the agents' context
+export type { Attachment };
+
+export async function attachFile(messageId: string, path: string): Promise<Attachment> {
+  return invoke<Attachment>('attach_file', { messageId, path });
+}
+
+// Pick files for the next message; empty if the user cancelled
+export async function chooseAttachments(): Promise<string[]> {
+  const paths = await open({ multiple: true, directory: false });
+  if (Array.isArray(paths)) return paths;
+  return typeof paths === 'string' ? [paths] : [];
+}
+
+export async function getMessageAttachments(messageId: string): Promise<Attachment[]> {
+  return invoke<Attachment[]>('get_message_attachments', { messageId });
+}
+
+// The stored bytes; null when the file was too big to store and only its path was kept
+export async function getAttachmentData(id: string): Promise<Uint8Array | null> {
+  const data = await invoke<number[] | null>('get_attachment_data', { id });
+  return data ? new Uint8Array(data) : null;
+}
+
+export async function deleteAttachment(id: string): Promise<void> {
+  await invoke('delete_attachment', { id });
+}
+
+// Drop the replies to a user message (e.g. after editing it) and run the round again
+export async function regenerateReplies(
+  messageId: string,
+  activeAgents?: AgentType[],
+  discoAgents?: AgentType[]
+): Promise<SendMessageResult> {
+  return invoke<SendMessageResult>('regenerate_replies', {
+    messageId,
+    activeAgents,
+    discoAgents,
+  });
+}
+
+// User context
+export async function getUserContext(): Promise<UserContext[]> {
+  const contexts = await invoke<{
+    id: number;
+    key: string;
+    value: string;
+    confidence: number;
+    source_agent: string | null;
+    updated_at: string;
+  }[]>('get_user_context');
+  
+  return contexts.map(c => ({
+    id: c.id,
+    key: c.key,
+    value: c.value,
+    confidence: c.confidence,
+    sourceAgent: c.source_agent,
+    updatedAt: new Date(c.updated_at),
+  }));
+}
+
+export async function clearUserContext(): Promise<void> {
+  await invoke('clear_user_context');
+}
+
+// ============ Memory System ============
+
+export type { FactInfo, UserFact };
+
+export type PatternVerdict = 'confirmed' | 'denied' | 'unsure';
+
+export interface PatternInfo {
+  id: number;
+  patternType: string;
+  description: string;
+  confidence: number;
+  verdict: PatternVerdict | null;
+}
+
+export interface MemoryStats {
+  factCount: number;
+  patternCount: number;
+  themeCount: number;
+  topFacts: FactInfo[];
+  topPatterns: PatternInfo[];
+  topThemes: string[];
+}
+
+export async function getMemoryStats(): Promise<MemoryStats> {
+  const stats = await invoke<{
+    fact_count: number;
+    pattern_count: number;
+    theme_count: number;
+    top_facts: { id: number; category: string; key: string; value: string; confidence: number }[];
+    top_patterns: { id: number; pattern_type: string; description: string; confidence: number; verdict: PatternVerdict | null }[];
+    top_themes: string[];
+  }>('get_memory_stats');
+  
+  return {
+    factCount: stats.fact_count,
+    patternCount: stats.pattern_count,
+    themeCount: stats.theme_count,
+    topFacts: stats.top_facts.map(f => ({
+      id: f.id,
+      category: f.category,
+      key: f.key,
+      value: f.value,
+      confidence: f.confidence,
+    })),
+    topPatterns: stats.top_patterns.map(p => ({
+      id: p.id,
+      patternType: p.pattern_type,
+      description: p.description,
+      confidence: p.confidence,
+      verdict: p.verdict,
+    })),
+    topThemes: stats.top_themes,
+  };
+}
+
+// Hand edits are logged alongside in-chat corrections; confidence is 0-1
+export async function updateUserFact(factId: number, value: string, confidence: number): Promise<UserFact> {
+  return invoke<UserFact>('update_user_fact', { factId, value, confidence });
+}
+
+export async function deleteUserFact(factId: number): Promise<void> {
+  await invoke('delete_user_fact', { factId });
+}
+
+export type { FactConflict };
+
+export async function getConflictingFacts(): Promise<FactConflict[]> {
+  return invoke<FactConflict[]>('get_conflicting_facts');
+}
+
+export async function resolveFactConflict(conflictId: number, keepIncoming: boolean): Promise<UserFact> {
+  return invoke<UserFact>('resolve_fact_conflict', { conflictId, keepIncoming });
+}
+
+export type { FactHistoryEntry };
+
+// Earlier values of one fact (omit factId for every fact), newest first
+export async function getFactHistory(factId?: number, limit?: number): Promise<FactHistoryEntry[]> {
+  return invoke<FactHistoryEntry[]>('get_fact_history', { factId, limit });
+}
+
+export async function rollbackUserFact(historyId: number): Promise<UserFact> {
+  return invoke<UserFact>('rollback_user_fact', { historyId });
+}
+
+// Facts unconfirmed this many days are archived; frequently mentioned ones get a check-in first
+export async function getFactDecayDays(): Promise<number | null> {
+  return invoke<number | null>('get_fact_decay_days');
+}
+
+// 0 turns decay off
+export async function setFactDecayDays(days: number): Promise<void> {
+  await invoke('set_fact_decay_days', { days });
+}
+
+export type { RecurringTheme };
+
+export async function getRecurringThemes(limit?: number): Promise<RecurringTheme[]> {
+  return invoke<RecurringTheme[]>('get_recurring_themes', { limit });
+}
+
+export async function deleteTheme(id: number): Promise<void> {
+  await invoke('delete_theme', { id });
+}
+
+// Weeks without a mention before a theme's frequency starts halving; null = never
+export async function getThemeDecayWeeks(): Promise<number | null> {
+  return invoke<number | null>('get_theme_decay_weeks');
+}
+
+export async function setThemeDecayWeeks(weeks: number): Promise<void> {
+  await invoke('set_theme_decay_weeks', { weeks });
+}
+
+// Free-text rules appended to every agent's system prompt
+export async function getHouseRules(): Promise<string | null> {
+  return invoke<string | null>('get_house_rules');
+}
+
+// Empty text clears them
+export async function setHouseRules(text: string): Promise<string | null> {
+  return invoke<string | null>('set_house_rules', { text });
+}
+
+export interface ArchivedFact {
+  id: number;
+  category: string;
+  key: string;
+  value: string;
+  confidence: number;
+  mention_count: number;
+  last_confirmed: string;
+  archived_at: string;
+}
+
+export async function getArchivedFacts(): Promise<ArchivedFact[]> {
+  return invoke<ArchivedFact[]>('get_archived_facts');
+}
+
+// Plain-text memory summary to paste into other AI tools; same memory, same text
+export async function exportPersonaSeed(): Promise<string> {
+  return invoke<string>('export_persona_seed');
+}
+
+export interface StagedMemory {
+  id: number;
+  kind: 'fact' | 'pattern';
+  category: string;  // Fact category or pattern type
+  key: string;       // Fact key or pattern description
+  value: string;     // Fact value or pattern evidence
+  confidence: number;
+  source_type: string | null;
+  source_conversation_id: string | null;
+  first_seen: string;
+  last_seen: string;
+  confirmations: number;
+  held_by_import: string | null;
+}
+
+export type { SeedImport };
+
+// Extract from a pasted self-description or another assistant's memory; held for review
+export async function importPersonaSeed(text: string): Promise<SeedImport> {
+  return invoke<SeedImport>('import_persona_seed', { text });
+}
+
+// Move what's left of a reviewed seed into long-term memory; returns items committed
+export async function commitPersonaSeed(seedId: string): Promise<number> {
+  return invoke<number>('commit_persona_seed', { seedId });
+}
+
+export async function discardPersonaSeed(seedId: string): Promise<number> {
+  return invoke<number>('discard_persona_seed', { seedId });
+}
+
+// Denied patterns are kept out of prompts; confirmed ones keep a confidence floor
+export async function setPatternVerdict(id: number, verdict: PatternVerdict): Promise<void> {
+  return invoke('set_pattern_verdict', { id, verdict });
+}
+
+// Confidence is 0-1
+export async function updateUserPattern(id: number, description: string, confidence: number): Promise<void> {
+  return invoke('update_user_pattern', { id, description, confidence });
+}
+
+// Can be inferred again later; deny it instead to keep it out for good
+export async function deleteUserPattern(id: number): Promise<void> {
+  return invoke('delete_user_pattern', { id });
+}
+
+// Session context card: what the agents will know before the user types
+export interface SessionFact {
+  id: number;
+  category: string;
+  key: string;
+  value: string;
+  confidence: number;
+}
+
+export type { MoodPoint };
+
+export interface SessionContext {
+  top_facts: SessionFact[];
+  active_goals: SessionFact[];
+  mood_trend: MoodPoint[];
+  last_summary: string | null;
+  last_summary_at: string | null;
+}
+
+export async function getSessionContext(conversationId?: string): Promise<SessionContext> {
+  return invoke<SessionContext>('get_session_context', { conversationId: conversationId || null });
+}
+
+// Whether past conversations found for a new one's first message are also given to the agents
+export async function getRelatedInContext(): Promise<boolean> {
+  return invoke<boolean>('get_related_in_context');
+}
+
+export async function setRelatedInContext(enabled: boolean): Promise<void> {
+  await invoke('set_related_in_context', { enabled });
+}
+
+export async function getContradictionAutoCorrect(): Promise<boolean> {
+  return invoke<boolean>('get_contradiction_auto_correct');
+}
+
+export async function setContradictionAutoCorrect(enabled: boolean): Promise<void> {
+  await invoke('set_contradiction_auto_correct', { enabled });
+}
+
+export type { QueryResult };
+
+// One SELECT against a read-only connection; rows are capped and slow queries stopped
+export async function runReadonlyQuery(sql: string, maxRows?: number): Promise<QueryResult> {
+  return invoke<QueryResult>('run_readonly_query', { sql, maxRows: maxRows ?? null });
+}
+
+export async function getAnonymizedRendering(): Promise<boolean> {
+  return invoke<boolean>('get_anonymized_rendering');
+}
+
+// Swap names and contact details for stable pseudonyms in returned data; resolves to the new state
+export async function toggleAnonymizedRendering(): Promise<boolean> {
+  return invoke<boolean>('toggle_anonymized_rendering');
+}
+
+export type { SupportBundle };
+
+// Zip of scrubbed logs, diagnostics, settings and metrics for a bug report; `path` comes from the save dialog
+export async function createSupportBundle(path: string): Promise<SupportBundle> {
+  return invoke<SupportBundle>('create_support_bundle', { path });
+}
+
+export async function getUserProfileSummary(): Promise<string> {
+  return invoke<string>('get_user_profile_summary');
+}
+
+// Governor Report (LLM-generated from knowledge base)
+export async function generateGovernorReport(profileId?: string): Promise<string> {
+  return invoke<string>('generate_governor_report', { profileId: profileId || null });
+}
+
+// 3-Sentence User Summary
+export async function generateUserSummary(): Promise<string> {
+  return invoke<string>('generate_user_summary');
 }
 
 // Reset
@@ -369,6 +1606,97 @@ export async function resetAllData(): Promise<void> {
   await invoke('reset_all_data');
 }
 
+export type ResetScope = 'memory' | 'conversations' | 'weights' | 'agents';
+
+export interface ResetPreview {
+  scope: ResetScope;
+  affected: { table: string; rows: number; action: 'delete' | 'reset' }[];
+  total_rows: number;
+}
+
+// What a targeted reset would touch; show this before confirming
+export async function previewReset(scope: ResetScope): Promise<ResetPreview> {
+  return invoke<ResetPreview>('preview_reset', { scope });
+}
+
+const RESET_COMMANDS: Record<ResetScope, string> = {
+  memory: 'reset_memory_only',
+  conversations: 'reset_conversations_only',
+  weights: 'reset_weights_only',
+  agents: 'reset_agent_customizations',
+};
+
+export async function resetScope(scope: ResetScope): Promise<void> {
+  await invoke(RESET_COMMANDS[scope]);
+}
+
+// Backup & restore
+export type { BackupResult, RestoreResult };
+
+export async function backupDatabase(path: string): Promise<BackupResult> {
+  return invoke<BackupResult>('backup_database', { path });
+}
+
+// Ask where to save the backup; null if the user cancelled
+export async function saveDatabaseBackup(): Promise<BackupResult | null> {
+  const date = new Date().toISOString().slice(0, 10);
+  const path = await save({
+    defaultPath: `intersect-backup-${date}.db`,
+    filters: [{ name: 'Intersect backup', extensions: ['db'] }],
+  });
+  return path ? backupDatabase(path) : null;
+}
+
+export async function restoreDatabase(path: string): Promise<RestoreResult> {
+  return invoke<RestoreResult>('restore_database', { path });
+}
+
+// Pick a backup file and restore it; null if the user cancelled
+export async function chooseAndRestoreDatabase(): Promise<RestoreResult | null> {
+  const path = await open({
+    multiple: false,
+    directory: false,
+    filters: [{ name: 'Intersect backup', extensions: ['db'] }],
+  });
+  return typeof path === 'string' ? restoreDatabase(path) : null;
+}
+
+// Integrity check, ANALYZE and VACUUM; VACUUM is skipped if corruption is found
+export type { MaintenanceReport };
+
+export async function runDbMaintenance(): Promise<MaintenanceReport> {
+  return invoke<MaintenanceReport>('run_db_maintenance');
+}
+
+// Where the active profile's database lives
+export type { DataLocation, DataMigrationResult };
+
+export async function getDataLocation(): Promise<DataLocation> {
+  return invoke<DataLocation>('get_data_location');
+}
+
+// Copies and verifies the database in the new folder before switching to it
+export async function migrateDataDir(path: string): Promise<DataMigrationResult> {
+  return invoke<DataMigrationResult>('migrate_data_dir', { path });
+}
+
+// Pick a folder and move the database there; null if the user cancelled
+export async function chooseAndMigrateDataDir(): Promise<DataMigrationResult | null> {
+  const path = await open({ multiple: false, directory: true });
+  return typeof path === 'string' ? migrateDataDir(path) : null;
+}
+
+// Spend dashboard: API cost per day and per model in [start, end) (ISO timestamps, either optional)
+export type { DailySpend, ModelSpend };
+
+export async function getSpendByDay(start?: string, end?: string): Promise<DailySpend[]> {
+  return invoke<DailySpend[]>('get_spend_by_day', { start: start ?? null, end: end ?? null });
+}
+
+export async function getSpendByModel(start?: string, end?: string): Promise<ModelSpend[]> {
+  return invoke<ModelSpend[]>('get_spend_by_model', { start: start ?? null, end: end ?? null });
+}
+
 // Window controls
 export async function setAlwaysOnTop(alwaysOnTop: boolean): Promise<void> {
   await invoke('set_always_on_top', { alwaysOnTop });
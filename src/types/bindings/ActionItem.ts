@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A concrete next step pulled out of a conversation
+ */
+export type ActionItem = { id: number, conversation_id: string, message_id: string | null, text: string, owner: string, due: string | null, status: string, snoozed_until: string | null, created_at: string, completed_at: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AgentBudget = { agent: string, daily_tokens: number | null, used_today: number, exhausted: boolean, };
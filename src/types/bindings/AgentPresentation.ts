@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How an agent is presented in the UI (normal and Disco Mode variants)
+ */
+export type AgentPresentation = { id: string, display_name: string, disco_name: string | null, color: string, disco_color: string | null, icon: string, disco_icon: string | null, description: string, disco_description: string | null, voice_samples: string, sort_order: number, is_builtin: boolean, updated_at: string, };
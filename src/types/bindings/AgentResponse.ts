@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MessageCost } from "./MessageCost";
+
+export type AgentResponse = { agent: string, content: string, response_type: string, references_message_id: string | null, cost: MessageCost | null, };
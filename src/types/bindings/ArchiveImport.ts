@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An imported chat archive and the state of its background analysis
+ */
+export type ArchiveImport = { id: string, source_path: string, conversation_count: number, message_count: number, processed_count: number, estimated_cost_usd: number, cost_usd: number, status: string, themes: string, error: string | null, created_at: string, updated_at: string, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UserFact } from "./UserFact";
+
+/**
+ * A fact decay retired from long-term memory
+ */
+export type ArchivedFact = { archived_at: string, } & UserFact;
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A file attached to a message. The bytes are fetched separately (`get_attachment_data`).
+ */
+export type Attachment = { id: string, message_id: string, filename: string, mime_type: string, path: string | null, size_bytes: number, extracted_text: string | null, created_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A message the user flagged to find again, with an optional note on why
+ */
+export type Bookmark = { message_id: string, conversation_id: string, conversation_title: string | null, role: string, content: string, timestamp: string, note: string | null, bookmarked_at: string, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type BoundaryRule = { id: string, agent: string, description: string, keywords: string, start_time: string | null, end_time: string | null, action: string, enabled: boolean, created_at: string, };
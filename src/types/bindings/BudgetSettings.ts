@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AgentBudget } from "./AgentBudget";
+
+export type BudgetSettings = { agents: Array<AgentBudget>, mode: string, local_model_url: string | null, local_model_name: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type BudgetStatus = { cap_usd: number | null, cap_tokens: number | null, mode: string, spent_usd: number, used_tokens: number, fraction_used: number | null, reached: boolean, warning: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type CategoryForecast = { category: string, trailing_cost_usd: number, daily_average_usd: number, projected_month_usd: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A period of the user's life (see chapters.rs)
+ */
+export type Chapter = { id: number, title: string, start_date: string, end_date: string | null, description: string | null, source: string, conversation_ids: Array<string>, created_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Per-conversation controls set with slash commands (see controls.rs)
+ */
+export type ConversationControls = { mode: string | null, muted_agents: Array<string>, verbosity: string | null, incognito: boolean, };
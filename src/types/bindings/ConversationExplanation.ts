@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ConversationExplanation = { conversation_id: string, is_disco: boolean, intensity: number, intensity_label: string, intensity_ceiling: number, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ConversationInfo = { id: string, title: string | null, summary: string | null, is_disco: boolean, created_at: string, updated_at: string, closed_at: string | null, pinned: boolean, project_id: number | null, locked: boolean, parent_conversation_id: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Per-conversation model, mode and weight overrides (see conversation_settings.rs)
+ */
+export type ConversationSettings = { conversation_id: string, model_tier: string | null, mode: string | null, instinct_weight: number | null, logic_weight: number | null, psyche_weight: number | null, updated_at: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What changed, plus the in-channel confirmation
+ */
+export type CorrectionOutcome = { fact_id: number, action: string, key: string, old_value: string, new_value: string | null, confirmation: string, };
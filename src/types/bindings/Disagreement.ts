@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where two agents split in a round (see disagreement.rs)
+ */
+export type Disagreement = { id: number, conversation_id: string, round_id: string | null, axis: string, agent_a: string, stance_a: string, agent_b: string, stance_b: string, intensity: string, summary: string, created_at: string, };
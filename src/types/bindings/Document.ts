@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A text document ingested into the document store
+ */
+export type Document = { id: string, source: string, path: string, title: string, content: string, file_modified_at: string | null, created_at: string, updated_at: string, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type DreamMotif = { id: number, motif: string, motif_type: string, frequency: number, first_seen: string, last_seen: string, related_entries: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One attempt to email something out of the app
+ */
+export type EmailLogEntry = { id: number, content_type: string, content_id: string | null, target: string, subject: string, content: string, error: string | null, sent_at: string, };
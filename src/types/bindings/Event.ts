@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArchiveImport } from "./ArchiveImport";
+import type { ExerciseLineEvent } from "./ExerciseLineEvent";
+import type { ExerciseStepEvent } from "./ExerciseStepEvent";
+import type { ImportReport } from "./ImportReport";
+import type { MigrationReport } from "./MigrationReport";
+import type { ProactiveMessageEvent } from "./ProactiveMessageEvent";
+import type { ReplayMessageEvent } from "./ReplayMessageEvent";
+import type { ReplayProgressEvent } from "./ReplayProgressEvent";
+import type { ReplayTypingEvent } from "./ReplayTypingEvent";
+
+export type Event = { "kind": "migration-report", "payload": MigrationReport } | { "kind": "archive-import-progress", "payload": ArchiveImport } | { "kind": "archive-import-complete", "payload": ImportReport } | { "kind": "exercise-intro", "payload": ExerciseLineEvent } | { "kind": "exercise-step", "payload": ExerciseStepEvent } | { "kind": "exercise-complete", "payload": ExerciseLineEvent } | { "kind": "proactive-message", "payload": ProactiveMessageEvent } | { "kind": "replay-start", "payload": ReplayProgressEvent } | { "kind": "replay-typing", "payload": ReplayTypingEvent } | { "kind": "replay-message", "payload": ReplayMessageEvent } | { "kind": "replay-complete", "payload": ReplayProgressEvent };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Payload for the "exercise-intro" and "exercise-complete" events
+ */
+export type ExerciseLineEvent = { session_id: string, exercise_id: string, content: string, };
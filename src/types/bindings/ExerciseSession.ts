@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ExerciseSession = { id: string, exercise_id: string, conversation_id: string | null, intro: string | null, outro: string | null, started_at: string, completed_at: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Payload for the "exercise-step" event
+ */
+export type ExerciseStepEvent = { session_id: string, exercise_id: string, step_index: number, total_steps: number, phase: string, instruction: string, duration_secs: number, };
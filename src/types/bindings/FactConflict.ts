@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UserFact } from "./UserFact";
+
+/**
+ * An incoming value that disagreed with a remembered fact; both are kept
+ * until the user picks one
+ */
+export type FactConflict = { id: number, fact: UserFact, value: string, confidence: number, source_type: string, source_conversation_id: string | null, created_at: string, };
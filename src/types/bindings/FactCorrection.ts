@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type FactCorrection = { id: number, fact_id: number, category: string, key: string, old_value: string, new_value: string | null, action: string, conversation_id: string | null, user_message: string, created_at: string, };
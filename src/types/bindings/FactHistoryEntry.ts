@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A value a fact held before it was changed
+ */
+export type FactHistoryEntry = { id: number, fact_id: number, category: string, key: string, value: string, confidence: number, source_type: string, conversation_id: string | null, changed_at: string, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type GroundingExercise = { id: string, name: string, description: string, steps: string, is_builtin: boolean, created_at: string, };
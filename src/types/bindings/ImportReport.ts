@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArchiveImport } from "./ArchiveImport";
+import type { MentionCount } from "./MentionCount";
+import type { StagedMemory } from "./StagedMemory";
+
+/**
+ * First-pass memory report for an import, reviewed before anything is promoted
+ */
+export type ImportReport = { import: ArchiveImport, facts: Array<StagedMemory>, patterns: Array<StagedMemory>, themes: Array<MentionCount>, topics: Array<MentionCount>, };
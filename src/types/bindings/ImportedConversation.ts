@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Outcome of restoring an exported conversation
+ */
+export type ImportedConversation = { conversation_id: string, message_count: number, ids_regenerated: number, };
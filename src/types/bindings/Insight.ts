@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Something the app noticed and wants to tell the user (e.g. a provider switch)
+ */
+export type Insight = { id: number, kind: string, message: string, created_at: string, dismissed_at: string | null, };
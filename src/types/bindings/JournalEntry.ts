@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type JournalEntry = { id: string, entry_type: string, content: string, reflection: string | null, created_at: string, updated_at: string, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProviderLatency } from "./ProviderLatency";
+
+export type LatencyStatus = { threshold_ms: number | null, providers: Array<ProviderLatency>, fallback_until: string | null, };
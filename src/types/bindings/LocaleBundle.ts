@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Everything the frontend needs to render in one locale
+ */
+export type LocaleBundle = { locale: string, available: Array<string>, strings: { [key in string]?: string }, };
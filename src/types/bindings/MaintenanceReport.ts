@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a maintenance pass found and how much space it won back
+ */
+export type MaintenanceReport = { integrity_ok: boolean, integrity_issues: Array<string>, analyzed: boolean, vacuumed: boolean, bytes_before: number, bytes_after: number, duration_ms: number, };
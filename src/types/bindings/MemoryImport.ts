@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What importing a memory bundle changed
+ */
+export type MemoryImport = { strategy: string, facts_added: number, facts_updated: number, patterns_added: number, patterns_updated: number, summaries_added: number, summaries_updated: number, summaries_skipped: number, themes_added: number, themes_updated: number, context_added: number, context_updated: number, kept: number, };
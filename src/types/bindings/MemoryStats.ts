@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FactInfo } from "./FactInfo";
+import type { PatternInfo } from "./PatternInfo";
+
+export type MemoryStats = { fact_count: number, pattern_count: number, theme_count: number, top_facts: Array<FactInfo>, top_patterns: Array<PatternInfo>, top_themes: Array<string>, };
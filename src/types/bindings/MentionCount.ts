@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A theme or topic and the imported conversations it came up in
+ */
+export type MentionCount = { name: string, conversations: number, };
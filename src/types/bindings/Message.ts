@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Message = { id: string, conversation_id: string, role: string, content: string, response_type: string | null, references_message_id: string | null, timestamp: string, status: string | null, round_id: string | null, latency_ms: number | null, input_tokens: number | null, output_tokens: number | null, cost_usd: number | null, model: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Model, tokens and estimated cost of one call, carried onto the message it produced
+ */
+export type MessageCost = { model: string, input_tokens: number, output_tokens: number, cost_usd: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Message } from "./Message";
+
+/**
+ * One page of a conversation's history, oldest first
+ */
+export type MessagePage = { messages: Array<Message>, has_more: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A previous version of an edited message
+ */
+export type MessageRevision = { id: number, message_id: string, content: string, revised_at: string, };
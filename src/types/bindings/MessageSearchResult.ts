@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A full-text search hit with enough context to jump to it
+ */
+export type MessageSearchResult = { message_id: string, conversation_id: string, conversation_title: string | null, role: string, timestamp: string, snippet: string, };
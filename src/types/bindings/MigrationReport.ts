@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TableRowCount } from "./TableRowCount";
+
+/**
+ * What opening the database did to it, sent to the UI at startup
+ */
+export type MigrationReport = { from_version: number, to_version: number, tables_created: Array<string>, columns_added: Array<string>, row_counts: Array<TableRowCount>, tables_with_fewer_rows: Array<string>, duration_ms: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Audit record of which model answered an agent turn, and why
+ */
+export type ModelDecision = { id: number, conversation_id: string | null, agent: string, complexity: string, tier: string, model: string, reason: string, created_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Spend on one model
+ */
+export type ModelSpend = { provider: string, model: string, calls: number, input_tokens: number, output_tokens: number, cost_usd: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A month's highlight reel: the messages that mattered most, written up
+ */
+export type MonthlyHighlights = { id: number, month: string, content: string, message_ids: Array<string>, created_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One recent conversation's read on how the user was doing
+ */
+export type MoodPoint = { conversation_id: string, user_state: string | null, emotional_tone: string | null, at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A past conversation that covered the same ground as a new one's first message
+ */
+export type PastConversation = { conversation_id: string, title: string | null, summary: string, at: string, chapter: string | null, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PendingJob } from "./PendingJob";
+
+export type PendingBackgroundWork = { jobs: Array<PendingJob>, estimated_cost_usd: number, approved: boolean, deferred_until: string | null, power_deferring: boolean, };
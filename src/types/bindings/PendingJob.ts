@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PendingJob = { job: string, description: string, items: number, llm_calls: number, estimated_cost_usd: number, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PersonaProfile = { id: string, name: string, is_default: boolean, is_active: boolean, dominant_trait: string, secondary_trait: string, instinct_weight: number, logic_weight: number, psyche_weight: number, instinct_points: number, logic_points: number, psyche_points: number, message_count: number, created_at: string, updated_at: string, };
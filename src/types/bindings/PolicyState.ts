@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PolicyState = { quiet_hours_enabled: boolean, quiet_hours_start: string, quiet_hours_end: string, dnd_until: string | null, is_quiet_now: boolean, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PowerState = { on_battery: boolean, low_power_mode: boolean, defer_on_battery: boolean, deferring: boolean, };
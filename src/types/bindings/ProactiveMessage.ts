@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ProactiveMessage = { id: string, conversation_id: string | null, agent: string, content: string, topic_key: string, scheduled_for: string, status: string, delivered_at: string | null, seen_at: string | null, responded_at: string | null, suppressed_count: number, created_at: string, user_requested: boolean, };
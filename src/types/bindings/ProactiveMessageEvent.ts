@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Payload for the "proactive-message" event
+ */
+export type ProactiveMessageEvent = { id: string, conversation_id: string | null, agent: string, content: string, };
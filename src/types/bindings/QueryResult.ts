@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Rows from a read-only query, cells as JSON (blobs as a size note)
+ */
+export type QueryResult = { columns: Array<string>, rows: Array<Array<unknown>>, truncated: boolean, elapsed_ms: number, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type RecurringTheme = { id: number, theme: string, frequency: number, last_mentioned: string, related_conversations: string | null, };
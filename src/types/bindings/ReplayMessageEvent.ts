@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Message } from "./Message";
+
+/**
+ * Payload for the "replay-message" event
+ */
+export type ReplayMessageEvent = { conversation_id: string, index: number, total: number, message: Message, };
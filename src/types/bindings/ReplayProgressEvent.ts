@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Payload for the "replay-start" and "replay-complete" events
+ */
+export type ReplayProgressEvent = { conversation_id: string, total: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Payload for the "replay-typing" event
+ */
+export type ReplayTypingEvent = { conversation_id: string, role: string, duration_ms: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ResetImpact } from "./ResetImpact";
+
+/**
+ * What a targeted reset would touch, for confirmation before running it
+ */
+export type ResetPreview = { scope: string, affected: Array<ResetImpact>, total_rows: number, };
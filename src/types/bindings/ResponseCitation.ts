@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ResponseCitation = { message_id: string, agent: string, fact_id: number, category: string, key: string, value: string, first_mentioned: string, };
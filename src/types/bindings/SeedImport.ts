@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StagedMemory } from "./StagedMemory";
+
+/**
+ * What an imported seed produced, held in staging for review
+ */
+export type SeedImport = { seed_id: string, facts: Array<StagedMemory>, patterns: Array<StagedMemory>, truncated: boolean, };
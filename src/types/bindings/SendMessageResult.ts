@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AgentResponse } from "./AgentResponse";
+import type { CorrectionOutcome } from "./CorrectionOutcome";
+import type { Disagreement } from "./Disagreement";
+import type { Message } from "./Message";
+import type { MessageCost } from "./MessageCost";
+import type { PastConversation } from "./PastConversation";
+import type { WeightChangeNotification } from "./WeightChangeNotification";
+
+export type SendMessageResult = { responses: Array<AgentResponse>, debate_mode: string | null, weight_change: WeightChangeNotification | null, governor_response: string | null, governor_cost: MessageCost | null, round_id: string | null, failed_agents: Array<string>, memory_correction: CorrectionOutcome | null, related_conversations: Array<PastConversation>, control: Message | null, contradiction_corrections: Array<Message>, disagreement: Disagreement | null, };
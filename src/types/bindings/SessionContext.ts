@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MoodPoint } from "./MoodPoint";
+import type { UserFact } from "./UserFact";
+
+/**
+ * What the agents will know going into a session, shown before the user types
+ */
+export type SessionContext = { top_facts: Array<UserFact>, active_goals: Array<UserFact>, mood_trend: Array<MoodPoint>, last_summary: string | null, last_summary_at: string | null, };
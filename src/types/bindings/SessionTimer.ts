@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where a session stands against its budget
+ */
+export type SessionTimer = { conversation_id: string, started_at: string | null, budget_minutes: number | null, elapsed_minutes: number, over_budget: boolean, };
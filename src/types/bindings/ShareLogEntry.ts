@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ShareLogEntry = { id: number, conversation_id: string, destination: string, target: string, subject: string, content: string, error: string | null, shared_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Exactly what will be sent, pending confirmation
+ */
+export type SharePreview = { token: string, conversation_id: string, destination: string, target: string, subject: string, content: string, expires_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Destination settings as shown to the UI (the SMTP password is never returned)
+ */
+export type ShareSettings = { smtp_host: string | null, smtp_port: number | null, smtp_username: string | null, has_smtp_password: boolean, email_from: string | null, email_to: string | null, webhook_url: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A newly extracted fact or pattern waiting for promotion to long-term memory
+ */
+export type StagedMemory = { id: number, kind: string, category: string, key: string, value: string, confidence: number, source_type: string | null, source_conversation_id: string | null, first_seen: string, last_seen: string, confirmations: number, held_by_import: string | null, };
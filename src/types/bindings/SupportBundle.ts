@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What was written, for the confirmation shown to the user
+ */
+export type SupportBundle = { path: string, files: Array<string>, bytes: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A conversation in the trash
+ */
+export type TrashedConversation = { id: string, title: string | null, summary: string | null, created_at: string, updated_at: string, deleted_at: string, message_count: number, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CategoryForecast } from "./CategoryForecast";
+
+export type UsageForecast = { window_days: number, daily_average_usd: number, month_to_date_usd: number, projected_month_usd: number, days_remaining: number, by_category: Array<CategoryForecast>, cap_usd: number | null, projected_exceed_date: string | null, };
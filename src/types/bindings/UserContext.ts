@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type UserContext = { id: number, key: string, value: string, confidence: number, source_agent: string | null, updated_at: string, };
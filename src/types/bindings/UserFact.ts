@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type UserFact = { id: number, category: string, key: string, value: string, confidence: number, source_type: string, source_conversation_id: string | null, first_mentioned: string, last_confirmed: string, mention_count: number, valid_until: string | null, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type UserPattern = { id: number, pattern_type: string, description: string, confidence: number, evidence: string, first_observed: string, last_updated: string, observation_count: number, verdict: string | null, };
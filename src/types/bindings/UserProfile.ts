@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type UserProfile = { id: number, api_key: string | null, anthropic_key: string | null, instinct_weight: number, logic_weight: number, psyche_weight: number, total_messages: number, created_at: string, updated_at: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The Governor's reflection on one week
+ */
+export type WeeklyReflection = { id: number, week_start: string, content: string, created_at: string, };
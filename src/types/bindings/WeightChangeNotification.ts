@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type WeightChangeNotification = { message: string, old_dominant: string, new_dominant: string, change_type: string, };
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PreviewReply } from "./PreviewReply";
+
+export type WeightPreview = { instinct_weight: number, logic_weight: number, psyche_weight: number, primary_agent: string, secondary_agent: string | null, secondary_type: string | null, replies: Array<PreviewReply>, mock: boolean, };
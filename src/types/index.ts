@@ -1,3 +1,6 @@
+import type { Disagreement } from './bindings/Disagreement';
+import type { Message as StoredMessage } from './bindings/Message';
+
 // Agent types
 export type AgentType = 'instinct' | 'logic' | 'psyche';
 
@@ -29,6 +32,16 @@ export interface Message {
   isStreaming?: boolean;
   isDisco?: boolean;  // Whether this message was generated in Disco Mode
   agentName?: string; // For governor_thoughts: which agent said this
+  status?: 'pending' | 'sent' | 'failed'; // Delivery status of user messages
+  latencyMs?: number; // How long an agent reply took to generate (used by replay)
+  cost?: MessageCost; // Tokens and estimated cost of generating an agent/governor reply
+}
+
+export interface MessageCost {
+  model: string;                 // Model that produced the reply
+  input_tokens: number;
+  output_tokens: number;
+  cost_usd: number;
 }
 
 // Agent response from backend
@@ -37,6 +50,7 @@ export interface AgentResponse {
   content: string;
   response_type: string;
   references_message_id?: string;
+  cost: MessageCost | null;
 }
 
 // Weight change notification
@@ -54,6 +68,32 @@ export interface SendMessageResult {
   weight_change: WeightChangeNotification | null;
   governor_thoughts: string | null;
   governor_response: string | null;
+  governor_cost: MessageCost | null;
+  round_id: string | null;       // The user message this round answers
+  failed_agents: AgentType[];    // Agents whose slot failed and can be retried with retryAgent
+  memory_correction: MemoryCorrection | null; // A remembered fact the user just corrected in chat
+  related_conversations: PastConversation[];   // On a conversation's first message: past ones on the same topic
+  control: StoredMessage | null;  // What a slash command ("/recall", "/mute", ...) saved (as stored); no agents answer those
+  contradiction_corrections: StoredMessage[]; // One-line fixes posted when a reply contradicted a remembered fact (auto-correct on)
+  disagreement: Disagreement | null; // Where a rebuttal or debate split from the first voice ("The split is X vs Y")
+}
+
+// A past conversation that covered the same ground ("you've talked about this before")
+export interface PastConversation {
+  conversation_id: string;
+  title: string | null;
+  summary: string;               // First sentence of its summary
+  at: string;
+  chapter: string | null;        // The life chapter it falls in ("the layoff spring")
+}
+
+export interface MemoryCorrection {
+  fact_id: number;
+  action: 'update' | 'delete';
+  key: string;
+  old_value: string;
+  new_value: string | null;
+  confirmation: string;
 }
 
 // User profile (API keys and message count)
@@ -94,6 +134,10 @@ export interface Conversation {
   title: string | null;
   summary: string | null;
   isDisco: boolean;
+  pinned?: boolean;
+  locked?: boolean; // Read-only: no sends, edits, or deletes
+  projectId?: number | null; // Project the conversation is filed under
+  parentConversationId?: string | null; // The conversation this was forked from
   createdAt: Date;
   updatedAt: Date;
 }